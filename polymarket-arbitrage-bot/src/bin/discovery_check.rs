@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use arb_rust::services::market_discovery::find_15_min_market;
+use colored::*;
+
+// Real discovery for one coin (FYI: the saved Gamma fixtures are replayed by `cargo test`, not here)
+fn parse_coin() -> String {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    args.iter()
+        .position(|a| a == "--live")
+        .and_then(|i| args.get(i + 1).cloned())
+        .or_else(|| args.first().filter(|a| !a.starts_with("--")).cloned())
+        .unwrap_or_else(|| "BTC".to_string())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    colored::control::set_override(polymarket_term::init().color);
+    let coin = parse_coin();
+
+    outln!("{}", format!("🔍 Live discovery for {}\n", coin.to_uppercase()).cyan().bold());
+    match find_15_min_market(&coin).await? {
+        Some(market) => outln!("{}", format!("✓ {} (up {} / down {})", market.slug, market.up_token_id, market.down_token_id).green()),
+        None => {
+            outln!("{}", "✗ No active market (see the per-slug reasons above)".red());
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
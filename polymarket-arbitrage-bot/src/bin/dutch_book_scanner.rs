@@ -0,0 +1,228 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use arb_rust::config::{ConfigReport, Env, Feature};
+use arb_rust::services::create_clob_client::{create_clob_client, OrderType};
+use arb_rust::services::dutch_book::{scan_dutch_books, DutchBook};
+use arb_rust::services::execute_buy_order;
+use arb_rust::services::market_precision::set_order_minimums;
+use arb_rust::services::neg_risk::NegRiskConverter;
+use arb_rust::services::order_queue::SubmitPriority;
+use clap::Parser;
+use colored::*;
+
+// CLI options (FYI: clap rejects values that don't parse instead of scanning with a default)
+#[derive(Debug, Parser)]
+#[command(name = "dutch_book_scanner", about = "Polymarket Dutch book scanner - N-outcome baskets below payout")]
+struct Options {
+    #[arg(long, value_name = "BPS", help = "Taker fee in basis points applied to the basket cost [default: TAKER_FEE_BPS]")]
+    fee_bps: Option<f64>,
+
+    #[arg(long, value_name = "USDC", default_value_t = 0.005, help = "Extra edge required on top of fees (per basket)")]
+    min_edge: f64,
+
+    #[arg(long, value_name = "N", default_value_t = 1000, help = "How many active events to crawl")]
+    max_events: usize,
+
+    #[arg(long, value_name = "N", default_value_t = 20, help = "How many results to print")]
+    top: usize,
+
+    #[arg(long, help = "One-shot buy of the best basket (needs trading in FEATURES)")]
+    execute: bool,
+
+    #[arg(long, value_name = "TOKENS", help = "Baskets (tokens per leg) to buy when executing [default: TOKEN_AMOUNT]")]
+    size: Option<f64>,
+
+    // Read by polymarket_term::init (BTW: declared so clap accepts it)
+    #[arg(long, help = "ASCII glyphs, no colors")]
+    plain: bool,
+}
+
+fn print_book(rank: usize, book: &DutchBook) {
+    outln!(
+        "{}",
+        format!(
+            "#{} {} ({} legs{})",
+            rank,
+            book.event_title,
+            book.legs.len(),
+            match (&book.conversion, book.neg_risk) {
+                (Some(_), _) => ", neg-risk NO -> convert",
+                (None, true) => ", neg-risk",
+                (None, false) => "",
+            }
+        )
+        .green()
+        .bold()
+    );
+    outln!(
+        "{}",
+        format!(
+            "   Ask sum: {:.4} | Payout: {:.0} | Edge: {:.4} ({:.2}%) | Max size: {:.2} baskets",
+            book.ask_sum,
+            book.payout,
+            book.edge,
+            book.edge / book.ask_sum * 100.0,
+            book.max_baskets
+        )
+        .cyan()
+    );
+    for leg in &book.legs {
+        outln!(
+            "{}",
+            format!("     • {:<40} ask {:.4} x {:.2}", leg.label, leg.ask, leg.ask_size).bright_black()
+        );
+    }
+    outln!("{}", format!("   https://polymarket.com/event/{}\n", book.event_slug).bright_black());
+}
+
+// Buy every leg of one basket (IMO: FOK so a leg either fills fully or not at all)
+async fn execute_basket(env: &Env, book: &DutchBook, size: f64) -> anyhow::Result<()> {
+    let client = create_clob_client(env).await?;
+    outln!(
+        "{}",
+        format!("\n⚡ Executing best basket: {} x {:.2} tokens per leg\n", book.event_title, size)
+            .green()
+            .bold()
+    );
+
+    let mut spent = 0.0;
+    let mut filled_legs = 0;
+    for leg in &book.legs {
+        let result = execute_buy_order(&client, &leg.token_id, &leg.label, size * leg.ask, leg.ask, OrderType::FOK, SubmitPriority::Entry).await;
+        if result.success {
+            spent += result.amount;
+            filled_legs += 1;
+        } else {
+            // NGL: a failed leg leaves the basket unhedged, so stop here
+            outln!(
+                "{}",
+                format!(
+                    "✗ Leg '{}' failed: {} - stopping ({} of {} legs filled, check positions!)",
+                    leg.label,
+                    result.error.unwrap_or_default(),
+                    filled_legs,
+                    book.legs.len()
+                )
+                .red()
+                .bold()
+            );
+            return Ok(());
+        }
+    }
+
+    // NO set: convert right away instead of waiting for resolution (BTW: tokens stay held if this fails)
+    if book.conversion.is_some() && !env.features.allows(Feature::Merges) {
+        outln!(
+            "{}",
+            format!(
+                "\n✓ Basket bought: {} legs, ${:.2} USDC spent - NO tokens held to resolution (add merge to FEATURES to convert)\n",
+                filled_legs, spent
+            )
+            .green()
+        );
+        return Ok(());
+    }
+    if let Some(conversion) = &book.conversion {
+        outln!("{}", "Converting NO set through the NegRiskAdapter...".bright_black());
+        let converted = match NegRiskConverter::new(env).await {
+            // Converting locks in the basket's edge now instead of at resolution - that is what gas has to beat
+            Ok(converter) => converter
+                .convert(&conversion.market_id, &conversion.question_indices, size, size * book.edge)
+                .await,
+            Err(e) => Err(e),
+        };
+        match converted {
+            Ok(Some(tx)) => outln!(
+                "{}",
+                format!(
+                    "\n✓ Converted: {} legs, ${:.2} USDC spent, ${:.2} USDC received (tx {:?})\n",
+                    filled_legs,
+                    spent,
+                    size * book.payout,
+                    tx
+                )
+                .green()
+                .bold()
+            ),
+            Ok(None) => outln!(
+                "{}",
+                "✗ Conversion skipped: gas costs more than it gains - NO tokens are still held, convert later or hold to resolution"
+                    .yellow()
+                    .bold()
+            ),
+            Err(e) => outln!(
+                "{}",
+                format!(
+                    "✗ Conversion failed: {} - NO tokens are still held, convert later or hold to resolution",
+                    e
+                )
+                .red()
+                .bold()
+            ),
+        }
+        return Ok(());
+    }
+
+    outln!(
+        "{}",
+        format!(
+            "\n✓ Basket complete: {} legs, ${:.2} USDC spent, ${:.2} payout at resolution\n",
+            filled_legs, spent, size * book.payout
+        )
+        .green()
+        .bold()
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Options::parse();
+    colored::control::set_override(polymarket_term::init().color);
+    let env = Env::load();
+    // Env::load falls back to defaults on typos - never scan (or --execute) with a setting it couldn't read
+    let config_report = ConfigReport::collect(&env);
+    if config_report.has_errors() {
+        config_report.print(false);
+        std::process::exit(1);
+    }
+    set_order_minimums(&env);
+    let fee_bps = opts.fee_bps.unwrap_or(env.taker_fee_bps);
+
+    outln!("{}", "\n╔════════════════════════════════════════════════════════════════╗".cyan().bold());
+    outln!("{}", "║          Polymarket Dutch Book Scanner (N-outcome)            ║".cyan().bold());
+    outln!("{}", "╚════════════════════════════════════════════════════════════════╝\n".cyan().bold());
+    outln!(
+        "{}",
+        format!(
+            "Crawling up to {} active events (fee {:.0} bps, min edge {:.4})...\n",
+            opts.max_events, fee_bps, opts.min_edge
+        )
+        .bright_black()
+    );
+
+    let books = scan_dutch_books(&env.clob_http_url, fee_bps / 10_000.0, opts.min_edge, opts.max_events).await?;
+
+    if books.is_empty() {
+        outln!("{}", "No baskets priced below their payout after fees. Markets look efficient right now.\n".yellow());
+        return Ok(());
+    }
+
+    outln!("{}", format!("Found {} basket(s) below payout after fees:\n", books.len()).green().bold());
+    for (i, book) in books.iter().take(opts.top).enumerate() {
+        print_book(i + 1, book);
+    }
+
+    if opts.execute && !env.features.allows(Feature::Trading) {
+        outln!("{}", "--execute ignored: safe mode (add trading to FEATURES to place orders).\n".yellow());
+    } else if opts.execute {
+        let best = &books[0];
+        let size = opts.size.unwrap_or(env.token_amount).min(best.max_baskets);
+        execute_basket(&env, best, size).await?;
+    } else {
+        outln!("{}", "Dry run - pass --execute [--size N] to buy the best basket once.\n".bright_black());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use arb_rust::services::market_ledger::LegFill;
+use arb_rust::services::trade_journal::{read_journal, BookView, JournalEntry, JOURNAL_LEVELS};
+use arb_rust::utils::clock::market_time_str;
+use arb_rust::utils::logger::journal_log_path;
+use colored::*;
+use std::path::PathBuf;
+
+const CELL_WIDTH: usize = 22;
+
+// CLI options (FYI: same plain --flag parsing as dutch_book_scanner)
+struct Options {
+    file: PathBuf, // journal.jsonl to read
+    market: Option<String>, // Only markets whose slug contains this
+    coin: Option<String>,
+    outcome: Option<String>, // filled / partial / failed / aborted
+    last: usize, // Newest N entries (after filtering)
+}
+
+fn parse_options() -> Options {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value = |flag: &str| -> Option<String> {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+    Options {
+        file: value("--file").map(PathBuf::from).unwrap_or_else(journal_log_path),
+        market: value("--market"),
+        coin: value("--coin").map(|c| c.to_uppercase()),
+        outcome: value("--outcome").map(|o| o.to_lowercase()),
+        last: value("--last").and_then(|v| v.parse().ok()).unwrap_or(10),
+    }
+}
+
+// One level as "price x size" (blank past the end of the book)
+fn cell(levels: &[[f64; 2]], i: usize) -> String {
+    levels
+        .get(i)
+        .map(|l| format!("{:.4} x {:>9.2}", l[0], l[1]))
+        .unwrap_or_default()
+}
+
+// Asks (worst at the top) over bids, four books side by side: UP/DOWN at detection and at execution
+fn render_books(entry: &JournalEntry) {
+    let empty = BookView::default();
+    let exec = entry.executed.as_ref();
+    let books: [(&BookView, bool); 4] = [
+        (&entry.detected.up, true),
+        (exec.map(|e| &e.up).unwrap_or(&empty), exec.is_some()),
+        (&entry.detected.down, true),
+        (exec.map(|e| &e.down).unwrap_or(&empty), exec.is_some()),
+    ];
+    let row = |label: String, cells: Vec<String>| {
+        let body: String = cells.iter().map(|c| format!("{:<width$}", c, width = CELL_WIDTH)).collect();
+        outln!("   {:<6}{}", label, body);
+    };
+
+    row(
+        String::new(),
+        vec!["UP @ detection".into(), "UP @ execution".into(), "DOWN @ detection".into(), "DOWN @ execution".into()],
+    );
+    for i in (0..JOURNAL_LEVELS).rev() {
+        let cells = books.iter().map(|(b, seen)| if *seen { cell(&b.asks, i) } else { "-".into() }).collect();
+        row(format!("ask{}", i + 1), cells);
+    }
+    outln!("   {}", "-".repeat(6 + CELL_WIDTH * 4).bright_black());
+    for i in 0..JOURNAL_LEVELS {
+        let cells = books.iter().map(|(b, seen)| if *seen { cell(&b.bids, i) } else { "-".into() }).collect();
+        row(format!("bid{}", i + 1), cells);
+    }
+}
+
+fn leg_summary(name: &str, leg: &Option<LegFill>) -> Option<String> {
+    let leg = leg.as_ref()?;
+    Some(if leg.success {
+        format!(
+            "{} {:.2} @ {:.4} (${:.2}){}",
+            name,
+            leg.tokens,
+            leg.price,
+            leg.amount_usd,
+            if leg.partially_filled { ", partial" } else { "" }
+        )
+    } else {
+        format!("{} failed", name)
+    })
+}
+
+fn render_entry(entry: &JournalEntry) {
+    let outcome = match entry.outcome.as_str() {
+        "filled" => entry.outcome.to_uppercase().green(),
+        "partial" => entry.outcome.to_uppercase().yellow(),
+        _ => entry.outcome.to_uppercase().red(),
+    };
+    let latency = entry
+        .latency_ms()
+        .map(|ms| format!("detection → orders {}ms", ms))
+        .unwrap_or_else(|| "no execution books".to_string());
+    outln!(
+        "\n{} {}  {}  {}  {}  ({})",
+        "━━".bright_black(),
+        market_time_str(entry.detected.at, "%Y-%m-%d %H:%M:%S").bold(),
+        entry.coin.cyan().bold(),
+        entry.market,
+        entry.kind,
+        latency
+    );
+    outln!("   {} {}", outcome.bold(), entry.opportunity_key.bright_black());
+    render_books(entry);
+    let legs: Vec<String> = [leg_summary("UP", &entry.up), leg_summary("DOWN", &entry.down)]
+        .into_iter()
+        .flatten()
+        .collect();
+    if !legs.is_empty() {
+        outln!("   fills: {}", legs.join("  |  "));
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    colored::control::set_override(polymarket_term::init().color);
+    let options = parse_options();
+
+    let entries = read_journal(&options.file)
+        .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", options.file.display(), e))?;
+    let matching: Vec<&JournalEntry> = entries
+        .iter()
+        .filter(|e| options.market.as_ref().map_or(true, |m| e.market.contains(m.as_str())))
+        .filter(|e| options.coin.as_ref().map_or(true, |c| e.coin.eq_ignore_ascii_case(c)))
+        .filter(|e| options.outcome.as_ref().map_or(true, |o| e.outcome == *o))
+        .collect();
+
+    outln!(
+        "{}",
+        format!(
+            "📓 Trade journal {} - showing {} of {} matching ({} total)",
+            options.file.display(),
+            matching.len().min(options.last),
+            matching.len(),
+            entries.len()
+        )
+        .cyan()
+        .bold()
+    );
+    for entry in matching.iter().skip(matching.len().saturating_sub(options.last)) {
+        render_entry(entry);
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::config::validation::is_known_setting;
+use crate::config::AVAILABLE_COINS;
+use anyhow::{bail, Result};
+use clap::Parser;
+
+// Command-line flags for the main bot (FYI: each one just sets its env var before Env::load, so dotenv
+// won't override it and the config report validates it like any other setting)
+#[derive(Debug, Clone, Parser)]
+#[command(name = "arb-rust", version, about = "Polymarket arbitrage bot - 15-minute crypto markets")]
+pub struct Cli {
+    #[arg(long, value_name = "COIN", help = "Coin to trade, skips the selection menu (BTC, ETH, SOL, XRP)")]
+    pub coin: Option<String>,
+
+    #[arg(long, value_name = "SUM", help = "ARBITRAGE_THRESHOLD: max UP + DOWN ask sum that counts, e.g. 0.99")]
+    pub threshold: Option<String>,
+
+    #[arg(long, value_name = "TOKENS", help = "TOKEN_AMOUNT: tokens per side")]
+    pub token_amount: Option<String>,
+
+    #[arg(long, value_name = "USDC", help = "ARBITRAGE_AMOUNT_USDC: USDC per side")]
+    pub amount_usdc: Option<String>,
+
+    #[arg(long, value_name = "SPEC", help = "THRESHOLD_SCHEDULE: secs_left:threshold[:max_tokens],...")]
+    pub schedule: Option<String>,
+
+    #[arg(long, help = "ALERT_ONLY=true: watch ALERT_COINS and log spreads, no orders")]
+    pub alert_only: bool,
+
+    #[arg(long, value_name = "COINS", help = "ALERT_COINS: comma-separated coins watched in alert-only mode")]
+    pub alert_coins: Option<String>,
+
+    #[arg(long, value_name = "LEVEL", help = "LOG_LEVEL: error, warn, info or debug")]
+    pub log_level: Option<String>,
+
+    #[arg(long, value_name = "LIST", help = "FEATURES: e.g. trading,reverse_arb (empty = safe mode)")]
+    pub features: Option<String>,
+
+    #[arg(long = "set", value_name = "KEY=VALUE", help = "Any other setting, e.g. --set MAX_PER_MARKET_USD=20 (repeatable)")]
+    pub set: Vec<String>,
+
+    #[arg(long, help = "Print every setting with its source, then exit (1 on errors)")]
+    pub check_config: bool,
+
+    // Read by polymarket_term::init (BTW: declared so clap accepts it)
+    #[arg(long, help = "ASCII glyphs, no colors")]
+    pub plain: bool,
+}
+
+impl Cli {
+    // The coin from --coin, upper-cased and checked against AVAILABLE_COINS
+    pub fn coin(&self) -> Result<Option<String>> {
+        match self.coin.as_deref().map(|c| c.trim().to_uppercase()) {
+            Some(c) if !AVAILABLE_COINS.contains(&c.as_str()) => {
+                bail!("Unknown --coin {} (available: {})", c, AVAILABLE_COINS.join(", "))
+            }
+            coin => Ok(coin),
+        }
+    }
+
+    // KEY -> value for every setting given on the command line, named flags last so they win over --set
+    pub fn overrides(&self) -> Result<Vec<(String, String)>> {
+        let mut out: Vec<(String, String)> = Vec::new();
+        for pair in &self.set {
+            let Some((key, value)) = pair.split_once('=') else {
+                bail!("--set expects KEY=VALUE, got '{}'", pair);
+            };
+            let key = key.trim().to_uppercase();
+            if !is_known_setting(&key) {
+                bail!("--set {}: not a setting this bot reads (see `--check-config` for the list)", key);
+            }
+            out.push((key, value.trim().to_string()));
+        }
+        let named = [
+            ("ARBITRAGE_THRESHOLD", &self.threshold),
+            ("TOKEN_AMOUNT", &self.token_amount),
+            ("ARBITRAGE_AMOUNT_USDC", &self.amount_usdc),
+            ("THRESHOLD_SCHEDULE", &self.schedule),
+            ("ALERT_COINS", &self.alert_coins),
+            ("LOG_LEVEL", &self.log_level),
+            ("FEATURES", &self.features),
+        ];
+        for (key, value) in named {
+            if let Some(v) = value {
+                out.push((key.to_string(), v.trim().to_string()));
+            }
+        }
+        if self.alert_only {
+            out.push(("ALERT_ONLY".to_string(), "true".to_string()));
+        }
+        Ok(out)
+    }
+
+    // Put the overrides into the process env (call first thing in main, before Env::load); returns the keys set
+    pub fn apply_to_env(&self) -> Result<Vec<String>> {
+        let overrides = self.overrides()?;
+        let mut keys: Vec<String> = Vec::new();
+        for (key, value) in overrides {
+            std::env::set_var(&key, &value);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+}
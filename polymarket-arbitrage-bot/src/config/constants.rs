@@ -21,7 +21,6 @@ pub const GAMMA_API_HOST: &str = "https://gamma-api.polymarket.com";
 
 // Trading constants (IMO: these defaults work well for most cases)
 pub const TOKEN_AMOUNT: f64 = 5.0; // Fixed token qty per side (UP/DOWN)
-pub const MIN_ORDER_SIZE_USD: f64 = 1.0; // Min order size in USD (Polymarket requirement)
 pub const RENDER_THROTTLE_MS: u64 = 10; // UI update throttle (caps at ~100fps)
 
 pub fn get_token_amount(env: &Env) -> f64 {
@@ -1,11 +1,21 @@
+use crate::config::features::{Feature, FeatureFlags};
+use crate::config::threshold_schedule::ThresholdSchedule;
+use crate::config::validation::default_of;
+use crate::config::AVAILABLE_COINS;
+use crate::services::create_clob_client::OrderType;
+use crate::utils::logger::LogLevel;
 use dotenv::dotenv;
 use std::env;
+use std::str::FromStr;
 
 // Config struct for env vars (FYI: all optional fields can be None if not set)
 #[derive(Debug, Clone)]
 pub struct Env {
     pub clob_http_url: String, // CLOB HTTP API endpoint
     pub clob_ws_url: String, // WebSocket endpoint for orderbook updates
+    pub clob_ws_backup_urls: Vec<String>, // Fallback WS endpoints, tried in order when the primary is down
+    pub ws_primary_retry_secs: u64, // While on a backup, retry the primary this often (0 = stay)
+    pub ws_redundant: bool, // Two parallel WS connections, books deduplicated (one drop = no detection gap)
     pub private_key: Option<String>, // Wallet private key (required for trading)
     pub usdc_contract_address: Option<String>, // USDC contract addr on Polygon
     pub proxy_wallet: Option<String>, // Proxy wallet (Gnosis Safe or EOA)
@@ -13,6 +23,123 @@ pub struct Env {
     pub arbitrage_amount_usdc: f64, // USDC amount per token side
     pub token_amount: f64, // Fixed token qty to buy
     pub arbitrage_threshold: f64, // Threshold for arb detection (usually 1.0)
+    pub threshold_schedule: ThresholdSchedule, // Threshold + size cap by time-to-expiry (flat unless THRESHOLD_SCHEDULE is set)
+    pub taker_fee_bps: f64, // Taker fee on the pair cost, in basis points (counted against the edge)
+    pub up_order_type: OrderType, // Order type for the UP leg (FOK or FAK)
+    pub down_order_type: OrderType, // Order type for the DOWN leg (FOK or FAK)
+    pub session_max_spend_usd: Option<f64>, // Stop trading after this much USDC spent (whole session)
+    pub session_max_loss_usd: Option<f64>, // Stop trading after this much worst-case loss (whole session)
+    pub max_per_market_usd: Option<f64>, // Cap on USDC executed per market window (incl. trades in flight)
+    pub max_concurrent_trades: usize, // Trades allowed in flight at once (across markets)
+    pub execution_queue_size: usize, // Max trades queued + running in total
+    pub execution_queue_per_market: usize, // Max trades queued + running per market
+    pub execution_queue_max_wait_ms: u64, // Drop queued trades older than this (0 = never)
+    pub max_exec_latency_ms: u64, // Detection -> posting slower than this re-checks the books first (0 = off)
+    pub order_max_in_flight: usize, // Order requests on the wire at once (per CLOB client)
+    pub order_queue_size: usize, // New-entry orders allowed to wait for a slot (cancels / hedges always queue)
+    pub min_order_size_usd: f64, // Smallest order sent, in USDC (Polymarket currently rejects < $1)
+    pub min_order_size_tokens: f64, // Min tokens per order (FYI: a market reporting a higher min_order_size wins)
+    pub log_level: LogLevel, // Console verbosity: error, warn, info, debug
+    pub health_port: Option<u16>, // Serve GET /healthz on this port (None = off)
+    pub heartbeat_file: Option<String>, // Rewrite this file while healthy (None = off)
+    pub heartbeat_interval_secs: u64, // Heartbeat file write interval
+    pub health_max_book_age_secs: u64, // Unhealthy when no book update for this long
+    pub resource_check_secs: u64, // Sample RSS / tasks / backlogs this often and warn past the limits below (0 = off)
+    pub resource_max_rss_mb: f64, // Warn when resident memory goes above this
+    pub resource_max_tasks: usize, // Warn when more tokio tasks than this are alive
+    pub resource_max_backlog: usize, // Warn when the log queue or the trade queue holds more than this
+    pub resource_max_orderbooks: usize, // Warn when the WS book caches hold more entries than this (should stay ~2 per coin)
+    pub alert_only: bool, // Detect + alert only, never trade (no ClobClient needed)
+    pub alert_coins: Vec<String>, // Coins watched at once in ALERT_ONLY mode (default: all)
+    pub alert_webhook_url: Option<String>, // POST each ALERT_ONLY spread here as JSON (None = log only)
+    pub results_dir: String, // Per-market result JSON written here on market close
+    pub trade_journal: bool, // Append each trade's books at detection + execution to journal.jsonl
+    pub reverse_arb: bool, // Also sell held UP+DOWN pairs when the bids sum above 1 + REVERSE_ARB_FEE
+    pub reverse_arb_fee: f64, // Edge per pair required on top of $1 (fees + slippage)
+    pub settlement_verify_secs: u64, // Wait this long for fills to show in the CTF balances (0 = don't check)
+    pub settlement_tolerance: f64, // Token difference still counted as settled
+    pub hedge_hourly: bool, // Hedge leftover one-sided tokens (failed / partial leg) in the coin's 1h market
+    pub hedge_ratio: f64, // Hourly tokens bought per unhedged 15m token
+    pub hedge_max_price: f64, // Skip the hedge when the hourly ask is above this
+    pub hedge_max_usd: Option<f64>, // Cap on USDC per hedge
+    pub value_trades: bool, // Also buy one side alone when its ask sits VALUE_MIN_EDGE below the spot-model price
+    pub value_min_edge: f64, // Model price - ask required for a value buy
+    pub value_token_amount: f64, // Tokens per value buy (capped by the top ask size)
+    pub value_max_per_market_usd: f64, // Value spend cap per market window (separate from MAX_PER_MARKET_USD)
+    pub value_max_session_usd: f64, // Value spend cap for the whole session
+    pub value_spot_api: String, // Binance-compatible klines API for spot + volatility
+    pub value_spot_refresh_secs: u64, // Spot model refresh interval
+    pub influx_url: Option<String>, // Stream book / trade / latency metrics to this InfluxDB (None = off)
+    pub influx_token: Option<String>, // InfluxDB API token (sent as "Authorization: Token ...")
+    pub influx_org: String, // InfluxDB organization
+    pub influx_bucket: String, // InfluxDB bucket the points go to
+    pub influx_sample_ms: u64, // At most one book point per market this often
+    pub influx_flush_ms: u64, // Batch points this long before each write
+    pub gas_check: bool, // Skip neg-risk conversions whose benefit doesn't cover their gas (approval included)
+    pub gas_oracle_url: Option<String>, // Polygon gas station (None = the RPC's eth_gasPrice)
+    pub gas_price_api: String, // Binance-compatible API for the POL price (own setting, not VALUE_SPOT_API)
+    pub gas_min_benefit_ratio: f64, // Benefit must be at least this multiple of the gas cost in USD
+    pub gas_max_gwei: Option<f64>, // Skip on-chain actions while gas is above this (None = no cap)
+    pub features: FeatureFlags, // FEATURES allowlist (empty = safe mode, detection only)
+    pub blocked_features: Vec<&'static str>, // Strategies switched on above but not allowed by FEATURES (turned off)
+    pub api_creds_file: Option<String>, // Encrypted CLOB API credentials cache for warm starts (None = derive every start)
+    pub api_creds_max_age_hours: u64, // Re-derive cached credentials older than this (0 = never by age)
+    pub api_creds_check_secs: u64, // How often the credentials are re-checked with the CLOB (0 = only at startup)
+    pub prefetch_next_secs: u64, // Look up + subscribe the next window's market this long before the current one closes (0 = off)
+    pub min_fill_score: f64, // Skip arbitrage whose expected fill probability (from past outcomes) is below this (0 = off)
+    pub fill_score_min_samples: u32, // Past attempts needed before MIN_FILL_SCORE filters anything
+    pub max_trades_per_window: Option<u32>, // Executions per market window, every strategy (None = no cap)
+    pub max_spend_per_window_usd: Option<f64>, // USDC spent per market window, every strategy + hedges (None = no cap)
+    pub min_wallet_balance_usd: Option<f64>, // Pause new buys while PROXY_WALLET holds less USDC than this (None = off)
+    pub balance_check_secs: u64, // Wallet USDC balance polled this often for MIN_WALLET_BALANCE_USD
+}
+
+// Env value of `key`, else its SPECS default (AFAIK: an unparseable value falls back too, the config report flags it)
+fn setting<T: FromStr>(key: &str) -> T {
+    optional(key).unwrap_or_else(|| {
+        default_of(key)
+            .parse()
+            .unwrap_or_else(|_| panic!("SPECS default of {} does not parse", key))
+    })
+}
+
+// Env value of `key` if set and parseable (BTW: for keys whose SPECS default is "" = off)
+fn optional<T: FromStr>(key: &str) -> Option<T> {
+    env::var(key).ok().and_then(|v| v.trim().parse().ok())
+}
+
+// true / 1 when set, else the SPECS default
+fn flag(key: &str) -> bool {
+    env::var(key)
+        .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+        .unwrap_or_else(|_| default_of(key) == "true")
+}
+
+// Env value of `key` unless empty, else its SPECS default
+fn text(key: &str) -> String {
+    env::var(key)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| default_of(key).to_string())
+}
+
+// THRESHOLD_SCHEDULE if set and valid, else the static threshold/size (FYI: bad specs warn and fall back)
+fn threshold_schedule_var(threshold: f64, token_amount: f64) -> ThresholdSchedule {
+    match env::var("THRESHOLD_SCHEDULE") {
+        Ok(spec) if !spec.trim().is_empty() => match ThresholdSchedule::parse(&spec, token_amount) {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                eoutln!("Invalid THRESHOLD_SCHEDULE ({}), using ARBITRAGE_THRESHOLD", e);
+                ThresholdSchedule::flat(threshold, token_amount)
+            }
+        },
+        _ => ThresholdSchedule::flat(threshold, token_amount),
+    }
+}
+
+// Per-leg order type: UP_ORDER_TYPE / DOWN_ORDER_TYPE override ORDER_TYPE
+fn order_type_var(leg_key: &str) -> OrderType {
+    optional(leg_key).unwrap_or_else(|| setting("ORDER_TYPE"))
 }
 
 impl Env {
@@ -20,29 +147,146 @@ impl Env {
     pub fn load() -> Self {
         dotenv().ok(); // Load .env, ignore errors if file doesn't exist
 
-        Self {
-            clob_http_url: env::var("CLOB_HTTP_URL")
-                .unwrap_or_else(|_| "https://clob.polymarket.com".to_string()),
-            clob_ws_url: env::var("CLOB_WS_URL")
-                .unwrap_or_else(|_| "wss://ws-subscriptions-clob.polymarket.com/ws/market".to_string()),
+        let token_amount: f64 = setting("TOKEN_AMOUNT");
+        let arbitrage_threshold: f64 = setting("ARBITRAGE_THRESHOLD");
+
+        let mut loaded = Self {
+            clob_http_url: text("CLOB_HTTP_URL"),
+            clob_ws_url: text("CLOB_WS_URL"),
+            clob_ws_backup_urls: env::var("CLOB_WS_BACKUP_URLS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            ws_primary_retry_secs: setting("WS_PRIMARY_RETRY_SECS"),
+            ws_redundant: flag("WS_REDUNDANT"),
             private_key: env::var("PRIVATE_KEY").ok(),
             usdc_contract_address: env::var("USDC_CONTRACT_ADDRESS").ok(),
             proxy_wallet: env::var("PROXY_WALLET").ok(),
-            rpc_url: env::var("RPC_URL")
-                .unwrap_or_else(|_| "https://polygon-rpc.com".to_string()),
-            arbitrage_amount_usdc: env::var("ARBITRAGE_AMOUNT_USDC")
-                .unwrap_or_else(|_| "1.0".to_string())
-                .parse()
-                .unwrap_or(1.0),
-            token_amount: env::var("TOKEN_AMOUNT")
-                .unwrap_or_else(|_| "5.0".to_string())
-                .parse()
-                .unwrap_or(5.0),
-            arbitrage_threshold: env::var("ARBITRAGE_THRESHOLD")
-                .unwrap_or_else(|_| "1.0".to_string())
-                .parse()
-                .unwrap_or(1.0),
+            rpc_url: text("RPC_URL"),
+            arbitrage_amount_usdc: setting("ARBITRAGE_AMOUNT_USDC"),
+            token_amount,
+            arbitrage_threshold,
+            threshold_schedule: threshold_schedule_var(arbitrage_threshold, token_amount),
+            taker_fee_bps: setting("TAKER_FEE_BPS"),
+            up_order_type: order_type_var("UP_ORDER_TYPE"),
+            down_order_type: order_type_var("DOWN_ORDER_TYPE"),
+            session_max_spend_usd: optional("SESSION_MAX_SPEND_USD"),
+            session_max_loss_usd: optional("SESSION_MAX_LOSS_USD"),
+            max_per_market_usd: optional("MAX_PER_MARKET_USD"),
+            max_concurrent_trades: setting("MAX_CONCURRENT_TRADES"),
+            execution_queue_size: setting("EXECUTION_QUEUE_SIZE"),
+            execution_queue_per_market: setting("EXECUTION_QUEUE_PER_MARKET"),
+            execution_queue_max_wait_ms: setting("EXECUTION_QUEUE_MAX_WAIT_MS"),
+            max_exec_latency_ms: setting("MAX_EXEC_LATENCY_MS"),
+            order_max_in_flight: setting("ORDER_MAX_IN_FLIGHT"),
+            order_queue_size: setting("ORDER_QUEUE_SIZE"),
+            min_order_size_usd: setting("MIN_ORDER_SIZE_USD"),
+            min_order_size_tokens: setting("MIN_ORDER_SIZE_TOKENS"),
+            log_level: LogLevel::from_str(&text("LOG_LEVEL")).unwrap_or(LogLevel::Info),
+            health_port: optional("HEALTH_PORT"),
+            heartbeat_file: env::var("HEARTBEAT_FILE")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            heartbeat_interval_secs: setting("HEARTBEAT_INTERVAL_SECS"),
+            health_max_book_age_secs: setting("HEALTH_MAX_BOOK_AGE_SECS"),
+            resource_check_secs: setting("RESOURCE_CHECK_SECS"),
+            resource_max_rss_mb: setting("RESOURCE_MAX_RSS_MB"),
+            resource_max_tasks: setting("RESOURCE_MAX_TASKS"),
+            resource_max_backlog: setting("RESOURCE_MAX_BACKLOG"),
+            resource_max_orderbooks: setting("RESOURCE_MAX_ORDERBOOKS"),
+            alert_only: flag("ALERT_ONLY"),
+            alert_coins: env::var("ALERT_COINS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_uppercase())
+                        .filter(|s| AVAILABLE_COINS.contains(&s.as_str()))
+                        .collect::<Vec<_>>()
+                })
+                .ok()
+                .filter(|coins| !coins.is_empty())
+                .unwrap_or_else(|| AVAILABLE_COINS.iter().map(|c| c.to_string()).collect()),
+            alert_webhook_url: env::var("ALERT_WEBHOOK_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            results_dir: text("RESULTS_DIR"),
+            trade_journal: flag("TRADE_JOURNAL"),
+            reverse_arb: flag("REVERSE_ARB"),
+            reverse_arb_fee: setting("REVERSE_ARB_FEE"),
+            settlement_verify_secs: setting("SETTLEMENT_VERIFY_SECS"),
+            settlement_tolerance: setting("SETTLEMENT_TOLERANCE"),
+            hedge_hourly: flag("HEDGE_HOURLY"),
+            hedge_ratio: setting("HEDGE_RATIO"),
+            hedge_max_price: setting("HEDGE_MAX_PRICE"),
+            hedge_max_usd: optional("HEDGE_MAX_USD"),
+            value_trades: flag("VALUE_TRADES"),
+            value_min_edge: setting("VALUE_MIN_EDGE"),
+            value_token_amount: setting("VALUE_TOKEN_AMOUNT"),
+            value_max_per_market_usd: setting("VALUE_MAX_PER_MARKET_USD"),
+            value_max_session_usd: setting("VALUE_MAX_SESSION_USD"),
+            value_spot_api: text("VALUE_SPOT_API"),
+            value_spot_refresh_secs: setting("VALUE_SPOT_REFRESH_SECS"),
+            influx_url: env::var("INFLUX_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            influx_token: env::var("INFLUX_TOKEN")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            influx_org: env::var("INFLUX_ORG").unwrap_or_default(),
+            influx_bucket: text("INFLUX_BUCKET"),
+            influx_sample_ms: setting("INFLUX_SAMPLE_MS"),
+            influx_flush_ms: setting("INFLUX_FLUSH_MS"),
+            gas_check: flag("GAS_CHECK"),
+            // Set but empty = no oracle, eth_gasPrice only
+            gas_oracle_url: Some(env::var("GAS_ORACLE_URL").unwrap_or_else(|_| default_of("GAS_ORACLE_URL").to_string()))
+                .filter(|v| !v.trim().is_empty()),
+            gas_price_api: text("GAS_PRICE_API"),
+            gas_min_benefit_ratio: setting("GAS_MIN_BENEFIT_RATIO"),
+            gas_max_gwei: optional("GAS_MAX_GWEI"),
+            features: FeatureFlags::from_env(),
+            blocked_features: Vec::new(),
+            // Off unless set - the cache is a credential on disk
+            api_creds_file: env::var("API_CREDS_FILE")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("off")),
+            api_creds_max_age_hours: setting("API_CREDS_MAX_AGE_HOURS"),
+            api_creds_check_secs: setting("API_CREDS_CHECK_SECS"),
+            prefetch_next_secs: setting("PREFETCH_NEXT_SECS"),
+            min_fill_score: setting("MIN_FILL_SCORE"),
+            fill_score_min_samples: setting("FILL_SCORE_MIN_SAMPLES"),
+            max_trades_per_window: optional("MAX_TRADES_PER_WINDOW").filter(|n: &u32| *n > 0),
+            max_spend_per_window_usd: optional("MAX_SPEND_PER_WINDOW_USD").filter(|usd: &f64| *usd > 0.0),
+            min_wallet_balance_usd: optional("MIN_WALLET_BALANCE_USD").filter(|usd: &f64| *usd > 0.0),
+            balance_check_secs: setting::<u64>("BALANCE_CHECK_SECS").max(1),
+        };
+        loaded.apply_features();
+        loaded
+    }
+
+    // Strategy switches only stay on when FEATURES allows them (BTW: trading itself is checked where the ClobClient is made)
+    fn apply_features(&mut self) {
+        let features = self.features.clone();
+        let mut blocked = Vec::new();
+        for (on, feature) in [
+            (&mut self.reverse_arb, Feature::ReverseArb),
+            (&mut self.value_trades, Feature::ValueTrades),
+            (&mut self.hedge_hourly, Feature::Hedging),
+        ] {
+            if *on && !features.allows(feature) {
+                *on = false;
+                blocked.push(feature.as_str());
+            }
         }
+        self.blocked_features = blocked;
+    }
+
+    // Orders may be sent (FEATURES has trading and this isn't ALERT_ONLY)
+    pub fn trading_enabled(&self) -> bool {
+        !self.alert_only && self.features.allows(Feature::Trading)
     }
 }
 
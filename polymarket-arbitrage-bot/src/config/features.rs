@@ -0,0 +1,78 @@
+use std::env;
+
+// Everything that can move money (FYI: each one has to be listed in FEATURES before it runs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    Trading, // Auto-trading: ClobClient + arbitrage orders (every other feature needs this too)
+    ReverseArb, // REVERSE_ARB sales of held pairs
+    ValueTrades, // VALUE_TRADES single-sided buys
+    Hedging, // HEDGE_HOURLY buys in the 1h market
+    Merges, // Neg-risk NO-set conversions / merges (on-chain)
+}
+
+impl Feature {
+    pub const ALL: [Feature; 5] = [
+        Feature::Trading,
+        Feature::ReverseArb,
+        Feature::ValueTrades,
+        Feature::Hedging,
+        Feature::Merges,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Feature::Trading => "trading",
+            Feature::ReverseArb => "reverse_arb",
+            Feature::ValueTrades => "value_trades",
+            Feature::Hedging => "hedge",
+            Feature::Merges => "merge",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase().replace('-', "_");
+        Feature::ALL.into_iter().find(|f| f.as_str() == s)
+    }
+}
+
+// FEATURES=trading,hedge,... (empty / unset = safe mode: detection only, nothing is ever sent)
+// - a fresh .env has no FEATURES line, so nobody goes live before reading their settings
+// - strategy switches (REVERSE_ARB, VALUE_TRADES, HEDGE_HOURLY) still pick what runs;
+//   FEATURES is the allowlist on top of them
+#[derive(Debug, Clone, Default)]
+pub struct FeatureFlags {
+    enabled: Vec<Feature>,
+    pub unknown: Vec<String>, // Names FEATURES had that aren't features (warned about at startup)
+}
+
+impl FeatureFlags {
+    pub fn from_env() -> Self {
+        let mut flags = Self::default();
+        let raw = env::var("FEATURES").unwrap_or_default();
+        for name in raw.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            if name.eq_ignore_ascii_case("all") {
+                flags.enabled = Feature::ALL.to_vec();
+                continue;
+            }
+            match Feature::parse(name) {
+                Some(f) if !flags.enabled.contains(&f) => flags.enabled.push(f),
+                Some(_) => {}
+                None => flags.unknown.push(name.to_string()),
+            }
+        }
+        flags
+    }
+
+    // Listed, and trading is on (BTW: a strategy without trading would have nothing to place orders with)
+    pub fn allows(&self, feature: Feature) -> bool {
+        self.enabled.contains(&Feature::Trading) && self.enabled.contains(&feature)
+    }
+
+    pub fn safe_mode(&self) -> bool {
+        !self.allows(Feature::Trading)
+    }
+
+    pub fn enabled_names(&self) -> Vec<&'static str> {
+        Feature::ALL.iter().filter(|f| self.allows(**f)).map(|f| f.as_str()).collect()
+    }
+}
@@ -1,6 +1,14 @@
+pub mod cli;
 pub mod constants;
 pub mod env;
+pub mod features;
+pub mod threshold_schedule;
+pub mod validation;
 
+pub use cli::Cli;
 pub use constants::*;
 pub use env::Env;
+pub use features::*;
+pub use threshold_schedule::*;
+pub use validation::*;
 
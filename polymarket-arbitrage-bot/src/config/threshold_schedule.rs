@@ -0,0 +1,65 @@
+// One step of the time-to-expiry schedule (FYI: applies while at least min_secs_left remain)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdStep {
+    pub min_secs_left: i64, // Step applies while time-to-expiry >= this
+    pub threshold: f64, // Max ask sum that counts as an arb
+    pub max_tokens: f64, // Token cap per side
+}
+
+// Piecewise threshold/size schedule over time-to-expiry (BTW: sorted by min_secs_left desc)
+#[derive(Debug, Clone)]
+pub struct ThresholdSchedule {
+    steps: Vec<ThresholdStep>,
+}
+
+impl ThresholdSchedule {
+    // Single step = the old static ARBITRAGE_THRESHOLD / TOKEN_AMOUNT behaviour
+    pub fn flat(threshold: f64, max_tokens: f64) -> Self {
+        Self {
+            steps: vec![ThresholdStep { min_secs_left: 0, threshold, max_tokens }],
+        }
+    }
+
+    // Parse "secs_left:threshold[:max_tokens],..." e.g. "600:0.99:10,120:0.985:5,0:0.97:2"
+    // (IMO: max_tokens falls back to TOKEN_AMOUNT when a step leaves it out)
+    pub fn parse(spec: &str, default_tokens: f64) -> Result<Self, String> {
+        let mut steps = Vec::new();
+        for entry in spec.split(',').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let parts: Vec<&str> = entry.split(':').map(|p| p.trim()).collect();
+            if parts.len() < 2 || parts.len() > 3 {
+                return Err(format!("'{}' should be secs_left:threshold[:max_tokens]", entry));
+            }
+            let min_secs_left: i64 = parts[0]
+                .parse()
+                .map_err(|_| format!("bad secs_left in '{}'", entry))?;
+            let threshold: f64 = parts[1]
+                .parse()
+                .map_err(|_| format!("bad threshold in '{}'", entry))?;
+            let max_tokens: f64 = match parts.get(2) {
+                Some(p) => p.parse().map_err(|_| format!("bad max_tokens in '{}'", entry))?,
+                None => default_tokens,
+            };
+            if min_secs_left < 0 || threshold <= 0.0 || max_tokens <= 0.0 {
+                return Err(format!("'{}' must use non-negative secs and positive values", entry));
+            }
+            steps.push(ThresholdStep { min_secs_left, threshold, max_tokens });
+        }
+        if steps.is_empty() {
+            return Err("schedule is empty".to_string());
+        }
+        steps.sort_by(|a, b| b.min_secs_left.cmp(&a.min_secs_left));
+        Ok(Self { steps })
+    }
+
+    // Step for the given time-to-expiry (AFAIK: below the smallest bound we use the last step)
+    pub fn at(&self, secs_left: i64) -> &ThresholdStep {
+        self.steps
+            .iter()
+            .find(|s| secs_left >= s.min_secs_left)
+            .unwrap_or_else(|| self.steps.last().unwrap())
+    }
+
+    pub fn steps(&self) -> &[ThresholdStep] {
+        &self.steps
+    }
+}
@@ -0,0 +1,379 @@
+use crate::config::features::Feature;
+use crate::config::threshold_schedule::ThresholdSchedule;
+use crate::config::{Env, AVAILABLE_COINS};
+use colored::*;
+use std::env;
+
+// What a value has to look like (FYI: Env::load reads the same keys and falls back to the default on anything else)
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Bool,
+    Int { min: u64, max: u64 },
+    Num { min: f64, max: f64 },
+    Choice(&'static [&'static str]),
+    Url,
+    UrlList, // Comma-separated URLs
+    Address, // 0x + 40 hex
+    Key, // 0x + 64 hex (never printed)
+    Secret, // Free text, never printed
+    Text,
+    Schedule, // THRESHOLD_SCHEDULE syntax
+    Features, // FEATURES names
+    Coins, // ALERT_COINS tickers
+}
+
+// One setting Env reads (BTW: default "" = unset / off)
+struct Spec {
+    key: &'static str,
+    kind: Kind,
+    default: &'static str,
+    hint: &'static str,
+}
+
+const fn spec(key: &'static str, kind: Kind, default: &'static str, hint: &'static str) -> Spec {
+    Spec { key, kind, default, hint }
+}
+
+const ANY: u64 = u64::MAX;
+const MONEY: Kind = Kind::Num { min: 0.0, max: f64::MAX };
+const PRICE: Kind = Kind::Num { min: 0.0, max: 1.0 };
+const COUNT: Kind = Kind::Int { min: 1, max: ANY };
+const MILLIS: Kind = Kind::Int { min: 0, max: ANY };
+const SECS: Kind = Kind::Int { min: 0, max: ANY };
+
+// Every key in Env::load, in its order (FYI: Env::load falls back to these defaults, they live nowhere else)
+const SPECS: &[Spec] = &[
+    spec("CLOB_HTTP_URL", Kind::Url, "https://clob.polymarket.com", "the CLOB REST base URL, e.g. https://clob.polymarket.com"),
+    spec("CLOB_WS_URL", Kind::Url, "wss://ws-subscriptions-clob.polymarket.com/ws/market", "the market WebSocket URL (wss://...)"),
+    spec("CLOB_WS_BACKUP_URLS", Kind::UrlList, "", "comma-separated wss:// URLs, or leave empty"),
+    spec("WS_PRIMARY_RETRY_SECS", SECS, "300", "seconds as a whole number (0 = stay on the backup)"),
+    spec("WS_REDUNDANT", Kind::Bool, "false", "true or false"),
+    spec("PRIVATE_KEY", Kind::Key, "", "the wallet's 64-hex-digit private key (0x prefix optional)"),
+    spec("USDC_CONTRACT_ADDRESS", Kind::Address, "", "a 0x-prefixed 40-hex-digit contract address"),
+    spec("PROXY_WALLET", Kind::Address, "", "your Polymarket proxy wallet (0x + 40 hex digits, shown on your profile)"),
+    spec("RPC_URL", Kind::Url, "https://polygon-rpc.com", "a Polygon JSON-RPC URL (https://...)"),
+    spec("ARBITRAGE_AMOUNT_USDC", MONEY, "1.0", "USDC per side as a number, e.g. 1.0"),
+    spec("TOKEN_AMOUNT", Kind::Num { min: 0.01, max: f64::MAX }, "5.0", "tokens per side as a positive number, e.g. 5"),
+    spec("ARBITRAGE_THRESHOLD", Kind::Num { min: 0.01, max: 1.0 }, "1.0", "the max UP + DOWN ask sum that counts, between 0 and 1 (e.g. 0.99)"),
+    spec("THRESHOLD_SCHEDULE", Kind::Schedule, "", "secs_left:threshold[:max_tokens],... e.g. 600:0.99:10,120:0.985:5,0:0.97:2"),
+    spec("TAKER_FEE_BPS", Kind::Num { min: 0.0, max: 1000.0 }, "0", "basis points between 0 and 1000, e.g. 100 for 1%"),
+    spec("ORDER_TYPE", Kind::Choice(&["FOK", "FAK"]), "FAK", "FOK or FAK"),
+    spec("UP_ORDER_TYPE", Kind::Choice(&["FOK", "FAK"]), "", "FOK or FAK (unset = ORDER_TYPE)"),
+    spec("DOWN_ORDER_TYPE", Kind::Choice(&["FOK", "FAK"]), "", "FOK or FAK (unset = ORDER_TYPE)"),
+    spec("SESSION_MAX_SPEND_USD", MONEY, "", "USDC as a number, or leave unset for no cap"),
+    spec("SESSION_MAX_LOSS_USD", MONEY, "", "USDC as a number, or leave unset for no cap"),
+    spec("MAX_PER_MARKET_USD", MONEY, "", "USDC as a number, or leave unset for no cap"),
+    spec("MAX_CONCURRENT_TRADES", COUNT, "1", "a whole number of at least 1"),
+    spec("EXECUTION_QUEUE_SIZE", COUNT, "4", "a whole number of at least 1"),
+    spec("EXECUTION_QUEUE_PER_MARKET", COUNT, "2", "a whole number of at least 1"),
+    spec("EXECUTION_QUEUE_MAX_WAIT_MS", MILLIS, "2000", "milliseconds as a whole number (0 = never drop)"),
+    spec("MAX_EXEC_LATENCY_MS", MILLIS, "250", "milliseconds as a whole number (0 = off)"),
+    spec("ORDER_MAX_IN_FLIGHT", COUNT, "4", "a whole number of at least 1"),
+    spec("ORDER_QUEUE_SIZE", COUNT, "16", "a whole number of at least 1"),
+    spec("MIN_ORDER_SIZE_USD", MONEY, "1.0", "USDC as a number (Polymarket rejects orders under $1)"),
+    spec("MIN_ORDER_SIZE_TOKENS", MONEY, "5.0", "tokens as a number (Polymarket's minimum is 5)"),
+    spec("LOG_LEVEL", Kind::Choice(&["error", "warn", "warning", "info", "debug"]), "info", "error, warn, info or debug"),
+    spec("HEALTH_PORT", Kind::Int { min: 1, max: 65535 }, "", "a TCP port 1-65535, or leave unset"),
+    spec("HEARTBEAT_FILE", Kind::Text, "", "a file path, or leave unset"),
+    spec("HEARTBEAT_INTERVAL_SECS", COUNT, "5", "seconds as a whole number of at least 1"),
+    spec("HEALTH_MAX_BOOK_AGE_SECS", COUNT, "60", "seconds as a whole number of at least 1"),
+    spec("RESOURCE_CHECK_SECS", SECS, "60", "seconds as a whole number (0 = off)"),
+    spec("RESOURCE_MAX_RSS_MB", MONEY, "512", "megabytes as a number"),
+    spec("RESOURCE_MAX_TASKS", COUNT, "2000", "a whole number"),
+    spec("RESOURCE_MAX_BACKLOG", COUNT, "1000", "a whole number"),
+    spec("RESOURCE_MAX_ORDERBOOKS", COUNT, "200", "a whole number"),
+    spec("ALERT_ONLY", Kind::Bool, "false", "true or false"),
+    spec("ALERT_COINS", Kind::Coins, "", "comma-separated coins from BTC, ETH, SOL, XRP (unset = all)"),
+    spec("ALERT_WEBHOOK_URL", Kind::Url, "", "an https:// webhook URL, or leave unset"),
+    spec("RESULTS_DIR", Kind::Text, "arbitrage_results", "a directory path"),
+    spec("TRADE_JOURNAL", Kind::Bool, "true", "true or false"),
+    spec("REVERSE_ARB", Kind::Bool, "false", "true or false"),
+    spec("REVERSE_ARB_FEE", PRICE, "0.01", "edge per pair between 0 and 1, e.g. 0.01"),
+    spec("SETTLEMENT_VERIFY_SECS", SECS, "30", "seconds as a whole number (0 = don't verify)"),
+    spec("SETTLEMENT_TOLERANCE", MONEY, "0.01", "tokens as a number, e.g. 0.01"),
+    spec("HEDGE_HOURLY", Kind::Bool, "false", "true or false"),
+    spec("HEDGE_RATIO", MONEY, "1.0", "hourly tokens per unhedged token, e.g. 1.0"),
+    spec("HEDGE_MAX_PRICE", PRICE, "0.65", "a price between 0 and 1, e.g. 0.65"),
+    spec("HEDGE_MAX_USD", MONEY, "", "USDC as a number, or leave unset"),
+    spec("VALUE_TRADES", Kind::Bool, "false", "true or false"),
+    spec("VALUE_MIN_EDGE", PRICE, "0.08", "model price - ask between 0 and 1, e.g. 0.08"),
+    spec("VALUE_TOKEN_AMOUNT", MONEY, "10", "tokens as a number"),
+    spec("VALUE_MAX_PER_MARKET_USD", MONEY, "10", "USDC as a number"),
+    spec("VALUE_MAX_SESSION_USD", MONEY, "50", "USDC as a number"),
+    spec("VALUE_SPOT_API", Kind::Url, "https://api.binance.com", "a Binance-compatible API base URL"),
+    spec("VALUE_SPOT_REFRESH_SECS", COUNT, "5", "seconds as a whole number of at least 1"),
+    spec("INFLUX_URL", Kind::Url, "", "the InfluxDB URL (http(s)://host:8086), or leave unset"),
+    spec("INFLUX_TOKEN", Kind::Secret, "", "the InfluxDB API token"),
+    spec("INFLUX_ORG", Kind::Text, "", "the InfluxDB organization"),
+    spec("INFLUX_BUCKET", Kind::Text, "arbitrage", "the InfluxDB bucket"),
+    spec("INFLUX_SAMPLE_MS", MILLIS, "1000", "milliseconds as a whole number"),
+    spec("INFLUX_FLUSH_MS", COUNT, "1000", "milliseconds as a whole number of at least 1"),
+    spec("GAS_CHECK", Kind::Bool, "false", "true or false"),
+    spec("GAS_ORACLE_URL", Kind::Url, "https://gasstation.polygon.technology/v2", "a gas station URL, or set it empty to use eth_gasPrice"),
+    spec("GAS_PRICE_API", Kind::Url, "https://api.binance.com", "a Binance-compatible API base URL"),
+    spec("GAS_MIN_BENEFIT_RATIO", MONEY, "2.0", "a multiple as a number, e.g. 2.0"),
+    spec("GAS_MAX_GWEI", MONEY, "", "gwei as a number, or leave unset"),
+    spec("FEATURES", Kind::Features, "", "comma-separated: trading, reverse_arb, value_trades, hedge, merge (or all)"),
+    spec("API_CREDS_FILE", Kind::Text, "", "a file path, or leave unset / off"),
+    spec("API_CREDS_MAX_AGE_HOURS", SECS, "168", "hours as a whole number (0 = never by age)"),
+    spec("API_CREDS_CHECK_SECS", SECS, "3600", "seconds as a whole number (0 = only at startup)"),
+    spec("PREFETCH_NEXT_SECS", SECS, "120", "seconds as a whole number (0 = off)"),
+    spec("MIN_FILL_SCORE", PRICE, "0", "a probability between 0 and 1 (0 = off)"),
+    spec("FILL_SCORE_MIN_SAMPLES", Kind::Int { min: 0, max: u32::MAX as u64 }, "30", "a whole number"),
+    spec("MAX_TRADES_PER_WINDOW", Kind::Int { min: 0, max: u32::MAX as u64 }, "", "a whole number, or leave unset for no cap (0 = no cap)"),
+    spec("MAX_SPEND_PER_WINDOW_USD", MONEY, "", "USDC as a number, or leave unset for no cap (0 = no cap)"),
+    spec("MIN_WALLET_BALANCE_USD", MONEY, "", "USDC as a number, or leave unset to never pause (0 = off)"),
+    spec("BALANCE_CHECK_SECS", COUNT, "30", "seconds as a whole number (at least 1)"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Env, // Set in the environment or .env
+    Flag, // Command-line flag (overrides env, see config::cli)
+    Default,
+}
+
+// Whether Env reads this key (FYI: --set KEY=VALUE rejects anything else, typos included)
+pub fn is_known_setting(key: &str) -> bool {
+    SPECS.iter().any(|s| s.key == key)
+}
+
+// SPECS default of a key Env reads (BTW: a key missing from SPECS is a bug, so this panics)
+pub(crate) fn default_of(key: &str) -> &'static str {
+    SPECS
+        .iter()
+        .find(|s| s.key == key)
+        .map(|s| s.default)
+        .unwrap_or_else(|| panic!("{} has no entry in SPECS", key))
+}
+
+// One row of the report
+#[derive(Debug, Clone)]
+pub struct SettingCheck {
+    pub key: &'static str,
+    pub value: String, // As shown (secrets masked, "-" when unset)
+    pub source: Source,
+    pub error: Option<String>,
+    pub hint: &'static str,
+}
+
+// A problem that involves more than one setting
+#[derive(Debug, Clone)]
+pub struct ConfigProblem {
+    pub message: String,
+    pub hint: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigReport {
+    pub settings: Vec<SettingCheck>,
+    pub problems: Vec<ConfigProblem>,
+}
+
+fn is_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// None = valid (AFAIK: mirrors what Env::load accepts, minus the silent fallback)
+fn check(kind: Kind, raw: &str) -> Option<String> {
+    let v = raw.trim();
+    match kind {
+        Kind::Bool => {
+            let ok = ["true", "false", "1", "0"].iter().any(|b| v.eq_ignore_ascii_case(b));
+            (!ok).then(|| format!("'{}' is not true/false", v))
+        }
+        Kind::Int { min, max } => match v.parse::<u64>() {
+            Ok(n) if n < min || n > max => Some(format!("{} is out of range ({}..{})", n, min, if max == ANY { "".to_string() } else { max.to_string() })),
+            Ok(_) => None,
+            Err(_) => Some(format!("'{}' is not a whole number", v)),
+        },
+        Kind::Num { min, max } => match v.parse::<f64>() {
+            Ok(n) if !n.is_finite() => Some(format!("'{}' is not a finite number", v)),
+            Ok(n) if n < min || n > max => Some(format!("{} is out of range ({}..{})", n, min, if max == f64::MAX { "".to_string() } else { max.to_string() })),
+            Ok(_) => None,
+            Err(_) => Some(format!("'{}' is not a number", v)),
+        },
+        Kind::Choice(options) => {
+            (!options.iter().any(|o| v.eq_ignore_ascii_case(o))).then(|| format!("'{}' is not one of {}", v, options.join(", ")))
+        }
+        Kind::Url => {
+            let ok = ["http://", "https://", "ws://", "wss://"].iter().any(|s| v.starts_with(s) && v.len() > s.len());
+            (!ok).then(|| format!("'{}' is not an http(s):// or ws(s):// URL", v))
+        }
+        Kind::UrlList => v
+            .split(',')
+            .map(|u| u.trim())
+            .filter(|u| !u.is_empty())
+            .find_map(|u| check(Kind::Url, u)),
+        Kind::Address => {
+            let ok = v.strip_prefix("0x").map(|h| is_hex(h, 40)).unwrap_or(false);
+            (!ok).then(|| "not a 0x-prefixed 40-hex-digit address".to_string())
+        }
+        Kind::Key => {
+            (!is_hex(v.trim_start_matches("0x"), 64)).then(|| "not a 64-hex-digit private key".to_string())
+        }
+        Kind::Secret | Kind::Text => None,
+        Kind::Schedule => ThresholdSchedule::parse(v, 1.0).err(),
+        Kind::Features => {
+            let unknown: Vec<&str> = v
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("all") && Feature::parse(s).is_none())
+                .collect();
+            (!unknown.is_empty()).then(|| format!("unknown feature(s): {}", unknown.join(", ")))
+        }
+        Kind::Coins => {
+            let unknown: Vec<&str> = v
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty() && !AVAILABLE_COINS.contains(&s.to_uppercase().as_str()))
+                .collect();
+            (!unknown.is_empty()).then(|| format!("unknown coin(s): {}", unknown.join(", ")))
+        }
+    }
+}
+
+fn shown(kind: Kind, value: &str) -> String {
+    match kind {
+        _ if value.is_empty() => "-".to_string(),
+        Kind::Key | Kind::Secret => "********".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+impl ConfigReport {
+    // Check every setting as it is in the environment right now (call after Env::load so .env is in)
+    pub fn collect(loaded: &Env) -> Self {
+        let settings = SPECS
+            .iter()
+            .map(|s| {
+                let raw = env::var(s.key).ok().filter(|v| !v.trim().is_empty());
+                match raw {
+                    Some(v) => SettingCheck {
+                        key: s.key,
+                        value: shown(s.kind, v.trim()),
+                        source: Source::Env,
+                        error: check(s.kind, &v),
+                        hint: s.hint,
+                    },
+                    None => SettingCheck {
+                        key: s.key,
+                        value: shown(s.kind, s.default),
+                        source: Source::Default,
+                        error: None,
+                        hint: s.hint,
+                    },
+                }
+            })
+            .collect();
+
+        let mut problems = Vec::new();
+        if loaded.trading_enabled() {
+            for (missing, key) in [(loaded.private_key.is_none(), "PRIVATE_KEY"), (loaded.proxy_wallet.is_none(), "PROXY_WALLET")] {
+                if missing {
+                    problems.push(ConfigProblem {
+                        message: format!("FEATURES includes trading but {} is not set", key),
+                        hint: format!("set {} or remove trading from FEATURES (detection only)", key),
+                    });
+                }
+            }
+        }
+        if let Some(cap) = loaded.max_per_market_usd.filter(|c| *c < loaded.min_order_size_usd * 2.0) {
+            problems.push(ConfigProblem {
+                message: format!("MAX_PER_MARKET_USD={} can't fit one order per side of MIN_ORDER_SIZE_USD={}", cap, loaded.min_order_size_usd),
+                hint: "raise MAX_PER_MARKET_USD to at least twice MIN_ORDER_SIZE_USD".to_string(),
+            });
+        }
+        if loaded.execution_queue_per_market > loaded.execution_queue_size {
+            problems.push(ConfigProblem {
+                message: format!(
+                    "EXECUTION_QUEUE_PER_MARKET={} is above EXECUTION_QUEUE_SIZE={}",
+                    loaded.execution_queue_per_market, loaded.execution_queue_size
+                ),
+                hint: "lower EXECUTION_QUEUE_PER_MARKET or raise EXECUTION_QUEUE_SIZE".to_string(),
+            });
+        }
+        Self { settings, problems }
+    }
+
+    // Rows whose key was set by a command-line flag show "flag" as their source
+    pub fn with_flags(mut self, keys: &[String]) -> Self {
+        for s in self.settings.iter_mut().filter(|s| s.source == Source::Env && keys.iter().any(|k| k == s.key)) {
+            s.source = Source::Flag;
+        }
+        self
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.settings.iter().filter(|s| s.error.is_some()).count() + self.problems.len()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    // Full table with `full`, otherwise one summary line + the errors (BTW: startup uses the short form)
+    pub fn print(&self, full: bool) {
+        let from_env = self.settings.iter().filter(|s| s.source == Source::Env).count();
+        let from_flags = self.settings.iter().filter(|s| s.source == Source::Flag).count();
+        if full {
+            outln!("{}", format!("{:<28} {:<8} {}", "SETTING", "SOURCE", "VALUE").bold());
+            for s in &self.settings {
+                let source = match s.source {
+                    Source::Env => "env",
+                    Source::Flag => "flag",
+                    Source::Default => "default",
+                };
+                let line = format!("{:<28} {:<8} {}", s.key, source, s.value);
+                match s.error {
+                    Some(_) => outln!("{}", line.red()),
+                    None if s.source == Source::Default => outln!("{}", line.bright_black()),
+                    None => outln!("{}", line),
+                }
+            }
+            outln!();
+        }
+        outln!(
+            "{}",
+            format!(
+                "Config: {} settings ({} from env, {} from flags, {} defaults), {} error(s)",
+                self.settings.len(),
+                from_env,
+                from_flags,
+                self.settings.len() - from_env - from_flags,
+                self.error_count()
+            )
+                .bright_black()
+        );
+        for s in &self.settings {
+            if let Some(ref e) = s.error {
+                eoutln!("{}", format!("❌ {}: {}", s.key, e).red());
+                eoutln!("{}", format!("   -> expected {}", s.hint).yellow());
+            }
+        }
+        for p in &self.problems {
+            eoutln!("{}", format!("❌ {}", p.message).red());
+            eoutln!("{}", format!("   -> {}", p.hint).yellow());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Env::load panics on a default it can't parse, so every non-empty one must pass its own check
+    #[test]
+    fn defaults_pass_their_own_checks() {
+        for s in SPECS.iter().filter(|s| !s.default.is_empty()) {
+            assert_eq!(check(s.kind, s.default), None, "{} default '{}'", s.key, s.default);
+        }
+    }
+
+    #[test]
+    fn keys_are_unique() {
+        for (i, s) in SPECS.iter().enumerate() {
+            assert!(SPECS[i + 1..].iter().all(|o| o.key != s.key), "{} listed twice", s.key);
+        }
+    }
+}
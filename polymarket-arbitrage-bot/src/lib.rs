@@ -0,0 +1,7 @@
+// Library target (FYI: lets extra binaries in src/bin reuse config/services/utils)
+#[macro_use]
+extern crate polymarket_term; // outln!/out!/eoutln! - println! with the --plain ASCII fallback
+
+pub mod config;
+pub mod services;
+pub mod utils;
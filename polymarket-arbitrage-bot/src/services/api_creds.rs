@@ -0,0 +1,358 @@
+use crate::config::Env;
+use crate::utils::clock::now_ms;
+use crate::utils::logger::{log_console, log_error, LogLevel};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::URL_SAFE;
+use base64::Engine;
+use colored::*;
+use ethers::prelude::*;
+use ethers::types::transaction::eip712::TypedData;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+use tokio::time::{sleep, Duration};
+
+// Stored form: enc:v1:<nonce hex>:<ciphertext+tag hex> (same layout as the copy bot's encrypted configs)
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+// HKDF info (FYI: changing it makes every cached file unreadable, which only costs one re-derive)
+const KEY_CONTEXT: &[u8] = b"polymarket-arbitrage/api-creds/v1";
+const CLOB_AUTH_MESSAGE: &str = "This message attests that I control the given wallet";
+const POLYGON_CHAIN_ID: u64 = 137;
+// Backoff while the auth endpoint keeps failing - detection runs the whole time, only orders wait
+const DERIVE_RETRY_MIN: Duration = Duration::from_secs(5);
+const DERIVE_RETRY_MAX: Duration = Duration::from_secs(300);
+
+// CLOB L2 credentials (what every authenticated request is signed with)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCreds {
+    pub api_key: String,
+    pub secret: String, // base64url HMAC key
+    pub passphrase: String,
+    pub address: String, // Signer they belong to (lowercase)
+    pub clob_url: String, // CLOB they were derived on
+    pub derived_at: i64, // ms
+}
+
+impl ApiCreds {
+    // Still usable for this signer / CLOB without asking the server (the server check comes after)
+    fn matches(&self, address: &str, clob_url: &str, max_age_hours: u64) -> bool {
+        let max_age_ms = max_age_hours as i64 * 3_600_000;
+        self.address.eq_ignore_ascii_case(address)
+            && self.clob_url.trim_end_matches('/') == clob_url.trim_end_matches('/')
+            && (max_age_hours == 0 || now_ms() - self.derived_at < max_age_ms)
+    }
+
+    // POLY_* headers for one L2 request (HMAC-SHA256 over timestamp + method + path + body)
+    pub fn l2_headers(&self, method: &str, path: &str, body: &str) -> Result<Vec<(&'static str, String)>> {
+        let timestamp = (now_ms() / 1000).to_string();
+        let key = URL_SAFE.decode(&self.secret).map_err(|e| anyhow!("Bad API secret: {}", e))?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(&key).map_err(|e| anyhow!("Bad API secret: {}", e))?;
+        mac.update(format!("{}{}{}{}", timestamp, method, path, body).as_bytes());
+        Ok(vec![
+            ("POLY_ADDRESS", self.address.clone()),
+            ("POLY_SIGNATURE", URL_SAFE.encode(mac.finalize().into_bytes())),
+            ("POLY_TIMESTAMP", timestamp),
+            ("POLY_API_KEY", self.api_key.clone()),
+            ("POLY_PASSPHRASE", self.passphrase.clone()),
+        ])
+    }
+}
+
+// Key = HKDF-SHA256(PRIVATE_KEY) (FYI: anyone holding the key can derive the creds anyway, so it adds no new secret)
+fn cipher_for(wallet_key: &str) -> Result<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, wallet_key.trim().trim_start_matches("0x").as_bytes())
+        .expand(KEY_CONTEXT, &mut key)
+        .map_err(|e| anyhow!("Deriving the credentials cache key failed: {}", e))?;
+    Ok(Aes256Gcm::new(&key.into()))
+}
+
+fn encrypt(cipher: &Aes256Gcm, creds: &ApiCreds) -> Result<String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let plaintext = serde_json::to_vec(creds)?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| anyhow!("Encrypting API credentials failed"))?;
+    Ok(format!("{}{}:{}", ENCRYPTED_PREFIX, hex::encode(nonce), hex::encode(ciphertext)))
+}
+
+fn decrypt(cipher: &Aes256Gcm, stored: &str) -> Result<ApiCreds> {
+    let rest = stored
+        .trim()
+        .strip_prefix(ENCRYPTED_PREFIX)
+        .ok_or_else(|| anyhow!("not an encrypted credentials file"))?;
+    let (nonce_hex, ciphertext_hex) = rest.split_once(':').ok_or_else(|| anyhow!("malformed credentials file"))?;
+    let nonce = hex::decode(nonce_hex)?;
+    if nonce.len() != 12 {
+        return Err(anyhow!("malformed credentials file"));
+    }
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), hex::decode(ciphertext_hex)?.as_slice())
+        // Also what a changed PRIVATE_KEY looks like
+        .map_err(|_| anyhow!("cannot decrypt (PRIVATE_KEY changed?)"))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+// ClobAuth EIP-712 signature -> POLY_* L1 headers
+async fn l1_headers(wallet: &LocalWallet) -> Result<Vec<(&'static str, String)>> {
+    let timestamp = (now_ms() / 1000).to_string();
+    let typed: TypedData = serde_json::from_value(serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" }
+            ],
+            "ClobAuth": [
+                { "name": "address", "type": "address" },
+                { "name": "timestamp", "type": "string" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "message", "type": "string" }
+            ]
+        },
+        "primaryType": "ClobAuth",
+        "domain": { "name": "ClobAuthDomain", "version": "1", "chainId": POLYGON_CHAIN_ID },
+        "message": {
+            "address": format!("{:?}", wallet.address()),
+            "timestamp": timestamp,
+            "nonce": 0,
+            "message": CLOB_AUTH_MESSAGE
+        }
+    }))?;
+    let signature = wallet.sign_typed_data(&typed).await?;
+    Ok(vec![
+        ("POLY_ADDRESS", format!("{:?}", wallet.address())),
+        ("POLY_SIGNATURE", format!("0x{}", hex::encode(signature.to_vec()))),
+        ("POLY_TIMESTAMP", timestamp),
+        ("POLY_NONCE", "0".to_string()),
+    ])
+}
+
+// Existing key for this wallet (GET /auth/derive-api-key), else a new one (POST /auth/api-key)
+async fn derive_api_creds(http: &reqwest::Client, clob_url: &str, wallet: &LocalWallet) -> Result<ApiCreds> {
+    let base = clob_url.trim_end_matches('/');
+    let mut last_error = anyhow!("no response");
+    for (method, path) in [(reqwest::Method::GET, "/auth/derive-api-key"), (reqwest::Method::POST, "/auth/api-key")] {
+        let mut req = http.request(method, format!("{}{}", base, path)).timeout(Duration::from_secs(10));
+        for (name, value) in l1_headers(wallet).await? {
+            req = req.header(name, value);
+        }
+        let resp = match req.send().await {
+            Ok(r) => r,
+            Err(e) => {
+                last_error = anyhow!("{}: {}", path, e);
+                continue;
+            }
+        };
+        if !resp.status().is_success() {
+            last_error = anyhow!("{}: HTTP {}", path, resp.status());
+            continue;
+        }
+        let data: serde_json::Value = resp.json().await?;
+        let field = |key: &str| data.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+        if let (Some(api_key), Some(secret), Some(passphrase)) = (field("apiKey"), field("secret"), field("passphrase")) {
+            return Ok(ApiCreds {
+                api_key,
+                secret,
+                passphrase,
+                address: format!("{:?}", wallet.address()).to_lowercase(),
+                clob_url: base.to_string(),
+                derived_at: now_ms(),
+            });
+        }
+        last_error = anyhow!("{}: response has no credentials", path);
+    }
+    Err(last_error)
+}
+
+// Server still accepts them (Ok(false) = rejected, Err = couldn't tell)
+async fn validate_api_creds(http: &reqwest::Client, creds: &ApiCreds) -> Result<bool> {
+    let path = "/auth/api-keys";
+    let mut req = http.get(format!("{}{}", creds.clob_url, path)).timeout(Duration::from_secs(10));
+    for (name, value) in creds.l2_headers("GET", path, "")? {
+        req = req.header(name, value);
+    }
+    let status = req.send().await?.status();
+    if status.is_success() {
+        Ok(true)
+    } else if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        Ok(false)
+    } else {
+        Err(anyhow!("HTTP {}", status))
+    }
+}
+
+// One store (and one background task) per wallet + CLOB, shared by every ClobClient in the process
+static STORES: OnceLock<Mutex<HashMap<(String, String), Weak<ApiCredsStore>>>> = OnceLock::new();
+
+// API credentials for a wallet: warm-started from API_CREDS_FILE, derived / checked / refreshed in the background
+pub struct ApiCredsStore {
+    current: RwLock<Option<ApiCreds>>,
+    wallet: LocalWallet,
+    cipher: Aes256Gcm,
+    clob_url: String,
+    file: Option<String>,
+    max_age_hours: u64,
+    check_secs: u64,
+}
+
+impl ApiCredsStore {
+    // Never blocks on the network - a valid cache file means orders can go out right away.
+    // A second ClobClient for the same wallet + CLOB gets the running store instead of a new task
+    pub fn start(env: &Env, private_key: &str) -> Result<Arc<Self>> {
+        let wallet: LocalWallet = private_key.trim().parse()?;
+        let clob_url = env.clob_http_url.trim_end_matches('/').to_string();
+        let key = (format!("{:?}", wallet.address()).to_lowercase(), clob_url.clone());
+        let mut stores = STORES
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(store) = stores.get(&key).and_then(Weak::upgrade) {
+            return Ok(store);
+        }
+
+        let store = Arc::new(Self {
+            current: RwLock::new(None),
+            wallet: wallet.with_chain_id(POLYGON_CHAIN_ID),
+            cipher: cipher_for(private_key)?,
+            clob_url,
+            file: env.api_creds_file.clone(),
+            max_age_hours: env.api_creds_max_age_hours,
+            check_secs: env.api_creds_check_secs,
+        });
+        let cached = store.load_cached();
+        if cached.is_some() {
+            outln!("{}", "✓ API credentials loaded from cache (checking them in the background)".green());
+        } else {
+            outln!("{}", "Deriving API credentials in the background (orders wait until they're ready)".bright_black());
+        }
+        store.set(cached);
+        stores.retain(|_, s| s.strong_count() > 0);
+        stores.insert(key, Arc::downgrade(&store));
+        tokio::spawn(Self::maintain(Arc::downgrade(&store)));
+        Ok(store)
+    }
+
+    // A poisoned lock still holds a whole Option (every write replaces it in one go)
+    fn get(&self) -> Option<ApiCreds> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set(&self, creds: Option<ApiCreds>) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = creds;
+    }
+
+    // Creds for the next request (Err until the first derive finished)
+    pub fn current(&self) -> Result<ApiCreds> {
+        self.get().ok_or_else(|| anyhow!("API credentials not ready yet (still deriving)"))
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.get().is_some()
+    }
+
+    fn address(&self) -> String {
+        format!("{:?}", self.wallet.address()).to_lowercase()
+    }
+
+    fn load_cached(&self) -> Option<ApiCreds> {
+        let path = self.file.as_ref()?;
+        let stored = std::fs::read_to_string(path).ok()?;
+        match decrypt(&self.cipher, &stored) {
+            Ok(creds) if creds.matches(&self.address(), &self.clob_url, self.max_age_hours) => Some(creds),
+            Ok(_) => None, // Other wallet / CLOB, or too old - re-derived below
+            Err(e) => {
+                log_error(&format!("Ignoring {}: {}", path, e), Some("api_creds"));
+                None
+            }
+        }
+    }
+
+    fn save(&self, creds: &ApiCreds) {
+        let Some(ref path) = self.file else {
+            return;
+        };
+        let result = encrypt(&self.cipher, creds).and_then(|stored| {
+            std::fs::write(path, stored)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            log_error(&format!("Could not cache API credentials in {}: {}", path, e), Some("api_creds"));
+        }
+    }
+
+    fn next_check(&self) -> Option<Duration> {
+        (self.check_secs > 0).then(|| Duration::from_secs(self.check_secs))
+    }
+
+    // One pass: derive when there are no usable creds, else confirm them with the server.
+    // Returns how long to wait before the next pass (None = done, API_CREDS_CHECK_SECS=0)
+    async fn step(&self, http: &reqwest::Client, retry: &mut Duration) -> Option<Duration> {
+        let usable = self
+            .get()
+            .filter(|c| c.matches(&self.address(), &self.clob_url, self.max_age_hours));
+        let Some(creds) = usable else {
+            return match derive_api_creds(http, &self.clob_url, &self.wallet).await {
+                Ok(creds) => {
+                    self.save(&creds);
+                    self.set(Some(creds));
+                    *retry = DERIVE_RETRY_MIN;
+                    log_console(LogLevel::Info, move || {
+                        outln!("{}", "✓ API credentials ready".green());
+                    });
+                    self.next_check()
+                }
+                // Backs off so a down auth endpoint isn't hammered
+                Err(e) => {
+                    let wait = *retry;
+                    *retry = (wait * 2).min(DERIVE_RETRY_MAX);
+                    let msg = format!("Deriving API credentials failed, retrying in {}s: {}", wait.as_secs(), e);
+                    log_error(&msg, Some("api_creds"));
+                    log_console(LogLevel::Warn, move || {
+                        eoutln!("{}", format!("⚠️  {}", msg).yellow());
+                    });
+                    Some(wait)
+                }
+            };
+        };
+        match validate_api_creds(http, &creds).await {
+            Ok(true) => {}
+            Ok(false) => {
+                log_error("Cached API credentials were rejected, deriving new ones", Some("api_creds"));
+                self.set(None);
+                return Some(Duration::ZERO);
+            }
+            // A hiccup on the check is no reason to drop creds that worked
+            Err(e) => log_error(&format!("Could not check API credentials: {}", e), Some("api_creds")),
+        }
+        self.next_check()
+    }
+
+    // Background: derive if cold, else confirm the cached creds; then re-check every API_CREDS_CHECK_SECS.
+    // Holds only a Weak between passes, so it ends once the last ClobClient using the store is dropped
+    async fn maintain(store: Weak<Self>) {
+        let http = reqwest::Client::new();
+        let mut retry = DERIVE_RETRY_MIN;
+        loop {
+            let Some(this) = store.upgrade() else {
+                return;
+            };
+            let Some(wait) = this.step(&http, &mut retry).await else {
+                return;
+            };
+            drop(this);
+            sleep(wait).await;
+        }
+    }
+}
@@ -1,13 +1,14 @@
-use crate::config::{Env, MIN_ORDER_SIZE_USD, TOKEN_AMOUNT};
+use crate::config::Env;
 use crate::services::create_clob_client::{ClobClient, OrderResponse, OrderSide, OrderType};
-use crate::utils::logger::log_error;
+use crate::services::market_precision::{min_order_tokens, min_order_usd, precision_for, Precision};
+use crate::services::order_queue::SubmitPriority;
+use crate::utils::logger::{log_console, log_error, LogLevel};
 use anyhow::{anyhow, Result};
 use colored::*;
 
 // Trading constants (FYI: Polymarket has strict precision requirements)
-const MIN_TOKEN_AMOUNT: f64 = 5.0; // Min tokens per order
-const PRICE_DECIMALS: usize = 4; // Price precision (4 decimals)
-const TOKEN_DECIMALS: usize = 2; // Token qty precision (2 decimals)
+// BTW: order minimums are runtime config (MIN_ORDER_SIZE_USD / MIN_ORDER_SIZE_TOKENS, see market_precision)
+// BTW: price/size decimals come from the token's current tick size (see market_precision)
 const PRECISION_EPSILON: f64 = 0.000001; // Float comparison threshold
 
 #[derive(Debug, Clone)]
@@ -18,6 +19,7 @@ pub struct ArbitrageOrderResult {
     pub amount: f64,
     pub price: f64,
     pub tokens_bought: Option<f64>,
+    pub partially_filled: bool, // FAK only: some size was killed unfilled
     pub error: Option<String>,
 }
 
@@ -28,8 +30,8 @@ fn floor_to_decimals(value: f64, decimals: usize) -> f64 {
 }
 
 // Calculate precise amounts that meet API requirements (IMO: this is tricky due to rounding)
-fn calculate_precise_amounts(target_token_amount: f64, price: f64) -> (f64, f64) {
-    let mut token_amount = floor_to_decimals(target_token_amount, TOKEN_DECIMALS);
+fn calculate_precise_amounts(target_token_amount: f64, price: f64, precision: Precision) -> (f64, f64) {
+    let mut token_amount = floor_to_decimals(target_token_amount, precision.size);
     
     if token_amount < 0.01 {
         token_amount = 0.01;
@@ -37,9 +39,9 @@ fn calculate_precise_amounts(target_token_amount: f64, price: f64) -> (f64, f64)
 
     // Iterate to find amounts that satisfy precision (FYI: max 10 iterations)
     for _ in 0..10 {
-        let usdc_amount = floor_to_decimals(token_amount * price, PRICE_DECIMALS);
+        let usdc_amount = floor_to_decimals(token_amount * price, precision.amount);
         let calculated_token_amount = usdc_amount / price;
-        let floored_token_amount = floor_to_decimals(calculated_token_amount, TOKEN_DECIMALS);
+        let floored_token_amount = floor_to_decimals(calculated_token_amount, precision.size);
         let difference = (calculated_token_amount - floored_token_amount).abs();
 
         // Check if precision is good enough (BTW: must be exact within epsilon)
@@ -50,12 +52,12 @@ fn calculate_precise_amounts(target_token_amount: f64, price: f64) -> (f64, f64)
         token_amount = floored_token_amount;
         if token_amount < 0.01 {
             token_amount = 0.01; // Enforce minimum
-            let final_usdc = floor_to_decimals(token_amount * price, PRICE_DECIMALS);
+            let final_usdc = floor_to_decimals(token_amount * price, precision.amount);
             return (token_amount, final_usdc);
         }
     }
 
-    let final_usdc = floor_to_decimals(token_amount * price, PRICE_DECIMALS);
+    let final_usdc = floor_to_decimals(token_amount * price, precision.amount);
     (token_amount, final_usdc)
 }
 
@@ -67,10 +69,23 @@ fn create_error_result(token_id: &str, side: &str, error: String) -> ArbitrageOr
         amount: 0.0,
         price: 0.0,
         tokens_bought: None,
+        partially_filled: false,
         error: Some(error),
     }
 }
 
+// Tokens matched per the CLOB's own record of the order (BTW: for responses without fill amounts; None = still unknown)
+async fn reconcile_fill(clob_client: &ClobClient, resp: &OrderResponse) -> Option<f64> {
+    let order_id = resp.order_id.as_deref()?;
+    match clob_client.get_order(order_id).await {
+        Ok(order) => Some(order.size_matched),
+        Err(e) => {
+            log_error(&format!("Fill lookup for {} failed: {}", order_id, e), Some("reconcileFill"));
+            None
+        }
+    }
+}
+
 // Execute buy order for arbitrage (FYI: handles precision and validation)
 pub async fn execute_buy_order(
     clob_client: &ClobClient,
@@ -78,17 +93,20 @@ pub async fn execute_buy_order(
     side: &str,
     amount_usdc: f64,
     ask_price: f64,
+    order_type: OrderType,
+    priority: SubmitPriority, // Submission lane (Recovery for hedges, Entry otherwise)
 ) -> ArbitrageOrderResult {
     // Validate inputs (IMO: fail fast on bad data)
     if token_id.trim().is_empty() {
         return create_error_result(token_id, side, "Invalid tokenId".to_string());
     }
 
-    if amount_usdc < MIN_ORDER_SIZE_USD {
+    let min_usd = min_order_usd();
+    if amount_usdc < min_usd {
         return create_error_result(
             token_id,
             side,
-            format!("Order size (${:.2}) below minimum (${:.2})", amount_usdc, MIN_ORDER_SIZE_USD),
+            format!("Order size (${:.2}) below minimum (${:.2})", amount_usdc, min_usd),
         );
     }
 
@@ -96,48 +114,56 @@ pub async fn execute_buy_order(
         return create_error_result(token_id, side, format!("Invalid ask price: {}", ask_price));
     }
 
-    let floored_price = floor_to_decimals(ask_price, PRICE_DECIMALS);
+    // Rounding follows the tick size at send time (FYI: it can change between detection and execution)
+    let precision = precision_for(token_id);
+    let min_tokens = min_order_tokens(token_id);
+    let floored_price = floor_to_decimals(ask_price, precision.price);
     if floored_price <= 0.0 || !floored_price.is_finite() {
         return create_error_result(token_id, side, format!("Invalid floored price: {}", floored_price));
     }
 
     // Calculate token quantity (AFAIK: ensure we meet minimums)
     let initial_share_quantity = amount_usdc / floored_price;
-    let min_share_quantity = MIN_ORDER_SIZE_USD / floored_price;
-    let mut share_quantity = initial_share_quantity.max(min_share_quantity).max(MIN_TOKEN_AMOUNT);
+    let min_share_quantity = min_usd / floored_price;
+    let mut share_quantity = initial_share_quantity.max(min_share_quantity).max(min_tokens);
 
-    let (precise_token_amount, floored_amount_usdc) = calculate_precise_amounts(share_quantity, floored_price);
+    let (precise_token_amount, floored_amount_usdc) = calculate_precise_amounts(share_quantity, floored_price, precision);
     let mut share_quantity = precise_token_amount;
     let mut floored_amount_usdc = floored_amount_usdc;
 
     // Ensure minimums (BTW: round up if needed to meet min token requirement)
-    if share_quantity < MIN_TOKEN_AMOUNT {
-        share_quantity = (share_quantity * 100.0).ceil() / 100.0;
-        if share_quantity < MIN_TOKEN_AMOUNT {
-            share_quantity = MIN_TOKEN_AMOUNT; // Force to minimum
+    if share_quantity < min_tokens {
+        let scale = 10_f64.powi(precision.size as i32);
+        share_quantity = (share_quantity * scale).ceil() / scale;
+        if share_quantity < min_tokens {
+            share_quantity = min_tokens; // Force to minimum
         }
-        let (t, u) = calculate_precise_amounts(share_quantity, floored_price);
+        let (t, u) = calculate_precise_amounts(share_quantity, floored_price, precision);
         share_quantity = t;
         floored_amount_usdc = u;
     }
 
     // Final validation (FYI: precision adjustments might drop below minimum)
-    if floored_amount_usdc < MIN_ORDER_SIZE_USD {
+    if floored_amount_usdc < min_usd {
         return create_error_result(
             token_id,
             side,
-            format!("After precision adjustment, USDC amount (${:.2}) below minimum (${:.2})", floored_amount_usdc, MIN_ORDER_SIZE_USD),
+            format!("After precision adjustment, USDC amount (${:.2}) below minimum (${:.2})", floored_amount_usdc, min_usd),
         );
     }
 
-    println!(
-        "{}",
-        format!(
-            "[{}] Executing at ${:.4} (original: ${:.4})\n  Amount: ${:.4} USDC\n  Share quantity: {:.2} tokens\n  TokenID: {}...",
-            side, floored_price, ask_price, floored_amount_usdc, share_quantity, &token_id[..token_id.len().min(20)]
-        )
-        .cyan()
-    );
+    // Printed on the logging thread (BTW: the order goes out without waiting for the console)
+    let (log_side, log_token) = (side.to_string(), token_id[..token_id.len().min(20)].to_string());
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "[{}] Executing {} at ${:.4} (original: ${:.4})\n  Amount: ${:.4} USDC\n  Share quantity: {:.2} tokens\n  TokenID: {}...",
+                log_side, order_type.as_str(), floored_price, ask_price, floored_amount_usdc, share_quantity, log_token
+            )
+            .cyan()
+        );
+    });
 
     // Create and submit order (IMO: this is where we actually trade)
     match clob_client
@@ -150,42 +176,92 @@ pub async fn execute_buy_order(
         .await
     {
         Ok(signed_order) => {
-            match clob_client.post_order(&signed_order, OrderType::FAK).await {
+            match clob_client.submit_order(&signed_order, order_type, priority).await {
                 Ok(resp) => {
                     if resp.success {
-                        let tokens_bought = floored_amount_usdc / floored_price;
-                        println!(
+                        let requested_tokens = floored_amount_usdc / floored_price;
+                        // FOK is all-or-nothing; FAK may fill partially so only count what the API reports as filled
+                        let (tokens_bought, amount_spent) = match order_type {
+                            OrderType::FOK => (
+                                resp.filled_tokens().unwrap_or(requested_tokens),
+                                resp.filled_usdc().unwrap_or(floored_amount_usdc),
+                            ),
+                            OrderType::FAK => {
+                                // No amounts in the response = fill unknown: ask the CLOB, never assume a full fill
+                                let tokens = match resp.filled_tokens() {
+                                    Some(tokens) => Some(tokens),
+                                    None => reconcile_fill(clob_client, &resp).await,
+                                };
+                                let Some(tokens) = tokens else {
+                                    let error_msg = format!(
+                                        "{} order {} returned no fill info and the order lookup failed - booked as 0 tokens, check positions!",
+                                        order_type.as_str(),
+                                        resp.order_id.as_deref().unwrap_or("N/A")
+                                    );
+                                    outln!("{}", format!("⚠️  [{}] {}", side, error_msg).yellow().bold());
+                                    log_error(&error_msg, Some(&format!("executeBuyOrder-{}", side)));
+                                    return create_error_result(token_id, side, error_msg);
+                                };
+                                let usdc = resp
+                                    .filled_usdc()
+                                    .unwrap_or_else(|| floor_to_decimals(tokens * floored_price, precision.amount));
+                                (tokens, usdc)
+                            }
+                        };
+
+                        if tokens_bought <= PRECISION_EPSILON {
+                            let error_msg = format!("{} order killed with no fill", order_type.as_str());
+                            outln!("{}", format!("✗ [{}] {}", side, error_msg).red());
+                            log_error(&error_msg, Some(&format!("executeBuyOrder-{}", side)));
+                            return create_error_result(token_id, side, error_msg);
+                        }
+
+                        let partially_filled = order_type == OrderType::FAK
+                            && tokens_bought + PRECISION_EPSILON < requested_tokens;
+
+                        outln!(
                             "{}",
                             format!(
                                 "\n✓✓✓ [{}] ORDER COMPLETED ✓✓✓\n  Order ID: {}\n  Amount: ${:.4} USDC\n  Price: ${:.4}\n  Tokens Bought: {:.2} tokens\n",
                                 side,
                                 resp.order_id.as_ref().unwrap_or(&"N/A".to_string()),
-                                floored_amount_usdc,
+                                amount_spent,
                                 floored_price,
                                 tokens_bought
                             )
                             .green()
                         );
+                        if partially_filled {
+                            outln!(
+                                "{}",
+                                format!(
+                                    "⚠️  [{}] FAK partial fill: {:.2}/{:.2} tokens (rest killed)",
+                                    side, tokens_bought, requested_tokens
+                                )
+                                .yellow()
+                            );
+                        }
 
                         ArbitrageOrderResult {
                             success: true,
                             token_id: token_id.to_string(),
                             side: side.to_string(),
-                            amount: floored_amount_usdc,
+                            amount: amount_spent,
                             price: floored_price,
                             tokens_bought: Some(tokens_bought),
+                            partially_filled,
                             error: None,
                         }
                     } else {
                         let error_msg = resp.error.unwrap_or_else(|| "Unknown error".to_string());
-                        println!("{}", format!("✗ [{}] Order failed: {}", side, error_msg).red());
+                        outln!("{}", format!("✗ [{}] Order failed: {}", side, error_msg).red());
                         log_error(&format!("[{}] Order failed: {}", side, error_msg), Some(&format!("executeBuyOrder-{}", side)));
                         create_error_result(token_id, side, error_msg)
                     }
                 }
                 Err(e) => {
                     let error_msg = format!("Failed to post order: {}", e);
-                    println!("{}", format!("✗ [{}] {}", side, error_msg).red());
+                    outln!("{}", format!("✗ [{}] {}", side, error_msg).red());
                     log_error(&error_msg, Some(&format!("executeBuyOrder-{}", side)));
                     create_error_result(token_id, side, error_msg)
                 }
@@ -193,17 +269,18 @@ pub async fn execute_buy_order(
         }
         Err(e) => {
             let error_msg = format!("Failed to create order: {}", e);
-            println!("{}", format!("✗ [{}] {}", side, error_msg).red());
+            outln!("{}", format!("✗ [{}] {}", side, error_msg).red());
             log_error(&error_msg, Some(&format!("executeBuyOrder-{}", side)));
             create_error_result(token_id, side, error_msg)
         }
     }
 }
 
-fn calculate_trade_amounts(up_price: f64, down_price: f64) -> (f64, f64, f64) {
-    let token_amount = floor_to_decimals(TOKEN_AMOUNT, TOKEN_DECIMALS);
-    let up_amount_usdc = floor_to_decimals(token_amount * up_price, PRICE_DECIMALS);
-    let down_amount_usdc = floor_to_decimals(token_amount * down_price, PRICE_DECIMALS);
+// Both legs share one token amount (BTW: floored to the coarser size precision so neither leg is rounded again)
+fn calculate_trade_amounts(target_tokens: f64, up_price: f64, down_price: f64, up: Precision, down: Precision) -> (f64, f64, f64) {
+    let token_amount = floor_to_decimals(target_tokens, up.size.min(down.size));
+    let up_amount_usdc = floor_to_decimals(token_amount * up_price, up.amount);
+    let down_amount_usdc = floor_to_decimals(token_amount * down_price, down.amount);
     (token_amount, up_amount_usdc, down_amount_usdc)
 }
 
@@ -216,6 +293,7 @@ pub async fn execute_arbitrage_trade(
     down_price: f64,
     _up_bid_price: f64, // Unused (would be for liquidation)
     _down_bid_price: f64, // Unused
+    target_tokens: f64, // Tokens per side (capped by the threshold schedule)
     env: &Env,
 ) -> Result<(ArbitrageOrderResult, ArbitrageOrderResult, bool)> {
     // Validate inputs (AFAIK: fail fast on bad data)
@@ -227,10 +305,16 @@ pub async fn execute_arbitrage_trade(
         return Err(anyhow!("Invalid prices"));
     }
 
-    let (token_amount, up_amount_usdc, down_amount_usdc) = calculate_trade_amounts(up_price, down_price);
+    let (token_amount, up_amount_usdc, down_amount_usdc) = calculate_trade_amounts(
+        target_tokens,
+        up_price,
+        down_price,
+        precision_for(up_token_id),
+        precision_for(down_token_id),
+    );
 
     // Check minimums (BTW: both sides must meet minimum order size)
-    if up_amount_usdc < MIN_ORDER_SIZE_USD || down_amount_usdc < MIN_ORDER_SIZE_USD {
+    if up_amount_usdc < min_order_usd() || down_amount_usdc < min_order_usd() {
         return Err(anyhow!(
             "Order sizes below minimum: UP=${:.2}, DOWN=${:.2}",
             up_amount_usdc,
@@ -238,24 +322,41 @@ pub async fn execute_arbitrage_trade(
         ));
     }
 
-    println!(
-        "{}",
-        format!(
-            "\n⚡ Executing arbitrage trade: Buying {:.2} tokens each\n  UP: ${:.4} → ${:.2} USDC\n  DOWN: ${:.4} → ${:.2} USDC\n  Total: {:.2} tokens, ${:.2} USDC\n",
-            token_amount, up_price, up_amount_usdc, down_price, down_amount_usdc, token_amount * 2.0, up_amount_usdc + down_amount_usdc
-        )
-        .green()
-        .bold()
-    );
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "\n⚡ Executing arbitrage trade: Buying {:.2} tokens each\n  UP: ${:.4} → ${:.2} USDC\n  DOWN: ${:.4} → ${:.2} USDC\n  Total: {:.2} tokens, ${:.2} USDC\n",
+                token_amount, up_price, up_amount_usdc, down_price, down_amount_usdc, token_amount * 2.0, up_amount_usdc + down_amount_usdc
+            )
+            .green()
+            .bold()
+        );
+    });
 
-    // Execute both orders (IMO: sequential for now, could be parallel)
-    let up_result = execute_buy_order(clob_client, up_token_id, "UP", up_amount_usdc, up_price).await;
-    let down_result = execute_buy_order(clob_client, down_token_id, "DOWN", down_amount_usdc, down_price).await;
+    // Execute both orders (IMO: sequential for now, could be parallel - the queue only pipelines separate jobs)
+    let up_result = execute_buy_order(clob_client, up_token_id, "UP", up_amount_usdc, up_price, env.up_order_type, SubmitPriority::Entry).await;
+    let down_result = execute_buy_order(clob_client, down_token_id, "DOWN", down_amount_usdc, down_price, env.down_order_type, SubmitPriority::Entry).await;
 
     let both_success = up_result.success && down_result.success; // Check if both succeeded
 
+    // Partial FAK fills leave the legs uneven (FYI: the difference is unhedged inventory)
+    if both_success && (up_result.partially_filled || down_result.partially_filled) {
+        let up_tokens = up_result.tokens_bought.unwrap_or(0.0);
+        let down_tokens = down_result.tokens_bought.unwrap_or(0.0);
+        let imbalance = (up_tokens - down_tokens).abs();
+        if imbalance > PRECISION_EPSILON {
+            let msg = format!(
+                "Leg imbalance after partial fill: UP={:.2} DOWN={:.2} tokens ({:.2} unhedged)",
+                up_tokens, down_tokens, imbalance
+            );
+            outln!("{}", format!("⚠️  {}", msg).yellow().bold());
+            log_error(&msg, Some("executeArbitrageTrade"));
+        }
+    }
+
     if both_success {
-        println!(
+        outln!(
             "{}",
             format!(
                 "\n╔════════════════════════════════════════════════════════════════╗\n║         🎉 ARBITRAGE TRADE COMPLETED SUCCESSFULLY! 🎉          ║\n╚════════════════════════════════════════════════════════════════╝\n  ✅ UP Order:\n     • Tokens: {:.2}\n     • Price: ${:.4}\n     • Amount: ${:.2} USDC\n  ✅ DOWN Order:\n     • Tokens: {:.2}\n     • Price: ${:.4}\n     • Amount: ${:.2} USDC\n  📊 Summary:\n     • Total Spent: ${:.2} USDC\n     • Status: Both orders executed successfully\n╔════════════════════════════════════════════════════════════════╗\n\n",
@@ -282,3 +383,197 @@ pub async fn execute_arbitrage_trade(
     Ok((up_result, down_result, both_success))
 }
 
+
+// Sell held tokens into the bid (FYI: tokens_bought on the result = tokens sold, amount = USDC received)
+pub async fn execute_sell_order(
+    clob_client: &ClobClient,
+    token_id: &str,
+    side: &str,
+    tokens: f64,
+    bid_price: f64,
+    order_type: OrderType,
+    priority: SubmitPriority,
+) -> ArbitrageOrderResult {
+    if token_id.trim().is_empty() {
+        return create_error_result(token_id, side, "Invalid tokenId".to_string());
+    }
+    if bid_price <= 0.0 || !bid_price.is_finite() {
+        return create_error_result(token_id, side, format!("Invalid bid price: {}", bid_price));
+    }
+
+    // Floor both so we never offer more than we hold or ask above the bid (AFAIK: same precision as buys)
+    let precision = precision_for(token_id);
+    let floored_price = floor_to_decimals(bid_price, precision.price);
+    let share_quantity = floor_to_decimals(tokens, precision.size);
+    let expected_usdc = floor_to_decimals(share_quantity * floored_price, precision.amount);
+    if share_quantity < min_order_tokens(token_id) || expected_usdc < min_order_usd() {
+        return create_error_result(
+            token_id,
+            side,
+            format!("Sell size ({:.2} tokens, ${:.2}) below minimum", share_quantity, expected_usdc),
+        );
+    }
+
+    let (log_side, log_token) = (side.to_string(), token_id[..token_id.len().min(20)].to_string());
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "[{}] Selling {} at ${:.4} (original: ${:.4})\n  Share quantity: {:.2} tokens\n  Expected: ${:.4} USDC\n  TokenID: {}...",
+                log_side, order_type.as_str(), floored_price, bid_price, share_quantity, expected_usdc, log_token
+            )
+            .cyan()
+        );
+    });
+
+    // Market sells are sized in tokens (BTW: buys are sized in USDC)
+    let signed_order = match clob_client
+        .create_market_order(OrderSide::Sell, token_id, share_quantity, floored_price)
+        .await
+    {
+        Ok(order) => order,
+        Err(e) => {
+            let error_msg = format!("Failed to create sell order: {}", e);
+            outln!("{}", format!("✗ [{}] {}", side, error_msg).red());
+            log_error(&error_msg, Some(&format!("executeSellOrder-{}", side)));
+            return create_error_result(token_id, side, error_msg);
+        }
+    };
+
+    let resp = match clob_client.submit_order(&signed_order, order_type, priority).await {
+        Ok(resp) => resp,
+        Err(e) => {
+            let error_msg = format!("Failed to post sell order: {}", e);
+            outln!("{}", format!("✗ [{}] {}", side, error_msg).red());
+            log_error(&error_msg, Some(&format!("executeSellOrder-{}", side)));
+            return create_error_result(token_id, side, error_msg);
+        }
+    };
+    if !resp.success {
+        let error_msg = resp.error.unwrap_or_else(|| "Unknown error".to_string());
+        outln!("{}", format!("✗ [{}] Sell failed: {}", side, error_msg).red());
+        log_error(&format!("[{}] Sell failed: {}", side, error_msg), Some(&format!("executeSellOrder-{}", side)));
+        return create_error_result(token_id, side, error_msg);
+    }
+
+    // FOK is all-or-nothing; a FAK without fill amounts is looked up, never assumed sold in full
+    let tokens_sold = match (resp.sold_tokens(), order_type) {
+        (Some(tokens), _) => Some(tokens),
+        (None, OrderType::FOK) => Some(share_quantity),
+        (None, _) => reconcile_fill(clob_client, &resp).await,
+    };
+    let Some(tokens_sold) = tokens_sold.map(|t| t.min(share_quantity)) else {
+        let error_msg = format!(
+            "{} sell {} returned no fill info and the order lookup failed - booked as 0 tokens sold, check positions!",
+            order_type.as_str(),
+            resp.order_id.as_deref().unwrap_or("N/A")
+        );
+        outln!("{}", format!("⚠️  [{}] {}", side, error_msg).yellow().bold());
+        log_error(&error_msg, Some(&format!("executeSellOrder-{}", side)));
+        return create_error_result(token_id, side, error_msg);
+    };
+    let received = resp
+        .sold_usdc()
+        .unwrap_or_else(|| floor_to_decimals(tokens_sold * floored_price, precision.amount));
+    if tokens_sold <= PRECISION_EPSILON {
+        let error_msg = format!("{} sell killed with no fill", order_type.as_str());
+        outln!("{}", format!("✗ [{}] {}", side, error_msg).red());
+        log_error(&error_msg, Some(&format!("executeSellOrder-{}", side)));
+        return create_error_result(token_id, side, error_msg);
+    }
+    let partially_filled = tokens_sold + PRECISION_EPSILON < share_quantity;
+
+    outln!(
+        "{}",
+        format!(
+            "\n✓✓✓ [{}] SELL COMPLETED ✓✓✓\n  Order ID: {}\n  Tokens Sold: {:.2} tokens\n  Price: ${:.4}\n  Received: ${:.4} USDC\n",
+            side,
+            resp.order_id.as_ref().unwrap_or(&"N/A".to_string()),
+            tokens_sold,
+            floored_price,
+            received
+        )
+        .green()
+    );
+
+    ArbitrageOrderResult {
+        success: true,
+        token_id: token_id.to_string(),
+        side: side.to_string(),
+        amount: received,
+        price: floored_price,
+        tokens_bought: Some(tokens_sold),
+        partially_filled,
+        error: None,
+    }
+}
+
+// Reverse arbitrage: sell one UP + one DOWN we hold for more than the $1 they redeem for
+pub async fn execute_reverse_arbitrage_trade(
+    clob_client: &ClobClient,
+    up_token_id: &str,
+    down_token_id: &str,
+    up_bid: f64,
+    down_bid: f64,
+    tokens: f64, // Pairs to sell (already capped by inventory)
+    env: &Env,
+) -> Result<(ArbitrageOrderResult, ArbitrageOrderResult, bool)> {
+    if up_token_id.trim().is_empty() || down_token_id.trim().is_empty() {
+        return Err(anyhow!("Invalid token IDs"));
+    }
+    if up_bid <= 0.0 || !up_bid.is_finite() || down_bid <= 0.0 || !down_bid.is_finite() {
+        return Err(anyhow!("Invalid prices"));
+    }
+
+    let token_amount = floor_to_decimals(tokens, precision_for(up_token_id).size.min(precision_for(down_token_id).size));
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "\n⚡ Executing reverse arbitrage: Selling {:.2} tokens each\n  UP: ${:.4}\n  DOWN: ${:.4}\n  Bid sum: {:.4} (expected ${:.2} USDC)\n",
+                token_amount, up_bid, down_bid, up_bid + down_bid, token_amount * (up_bid + down_bid)
+            )
+            .green()
+            .bold()
+        );
+    });
+
+    let up_result = execute_sell_order(clob_client, up_token_id, "UP", token_amount, up_bid, env.up_order_type, SubmitPriority::Entry).await;
+    let down_result = execute_sell_order(clob_client, down_token_id, "DOWN", token_amount, down_bid, env.down_order_type, SubmitPriority::Entry).await;
+    let both_success = up_result.success && down_result.success;
+
+    // Selling unevenly leaves the remaining inventory unhedged (BTW: same warning as a lopsided buy)
+    let up_sold = if up_result.success { up_result.tokens_bought.unwrap_or(0.0) } else { 0.0 };
+    let down_sold = if down_result.success { down_result.tokens_bought.unwrap_or(0.0) } else { 0.0 };
+    if (up_sold - down_sold).abs() > PRECISION_EPSILON {
+        let msg = format!(
+            "Leg imbalance after reverse arbitrage: sold UP={:.2} DOWN={:.2} tokens ({:.2} now unhedged)",
+            up_sold, down_sold, (up_sold - down_sold).abs()
+        );
+        outln!("{}", format!("⚠️  {}", msg).yellow().bold());
+        log_error(&msg, Some("executeReverseArbitrageTrade"));
+    }
+
+    if both_success {
+        outln!(
+            "{}",
+            format!(
+                "\n✅ REVERSE ARBITRAGE COMPLETED\n  UP: {:.2} tokens @ ${:.4} -> ${:.2}\n  DOWN: {:.2} tokens @ ${:.4} -> ${:.2}\n  Received: ${:.2} USDC\n",
+                up_sold, up_result.price, up_result.amount,
+                down_sold, down_result.price, down_result.amount,
+                up_result.amount + down_result.amount
+            )
+            .green()
+            .bold()
+        );
+    } else {
+        let error_msg = format!(
+            "Reverse arbitrage failed - UP: {}, DOWN: {}",
+            up_result.error.as_deref().unwrap_or("OK"),
+            down_result.error.as_deref().unwrap_or("OK")
+        );
+        log_error(&error_msg, Some("executeReverseArbitrageTrade"));
+    }
+
+    Ok((up_result, down_result, both_success))
+}
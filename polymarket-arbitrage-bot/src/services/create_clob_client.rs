@@ -1,7 +1,36 @@
 use crate::config::Env;
+use crate::services::api_creds::ApiCredsStore;
+use crate::services::exposure::ARB_STRATEGY;
+use crate::services::order_queue::{OrderQueue, SubmitPriority};
 use anyhow::{anyhow, Result};
 use ethers::prelude::*;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+// CLOB order budget shared with other engines on the same wallet (FYI: set by the supervisor binary)
+pub use polymarket_gates::ClobGate;
+
+// 429 without a usable Retry-After
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(2);
+// Longest wait taken from the shared budget or a Retry-After header
+const MAX_CLOB_WAIT: Duration = Duration::from_secs(10);
+
+static CLOB_GATE: OnceLock<Arc<dyn ClobGate>> = OnceLock::new();
+
+// Install the shared CLOB budget (BTW: standalone runs never call this, so calls go out unpaced)
+pub fn set_clob_gate(gate: Arc<dyn ClobGate>) {
+    let _ = CLOB_GATE.set(gate);
+}
+
+// Wait for this engine's turn in the shared budget
+async fn clob_slot() {
+    if let Some(gate) = CLOB_GATE.get() {
+        let wait = gate.reserve_call(ARB_STRATEGY).min(MAX_CLOB_WAIT);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
 
 // Note: This is a placeholder implementation
 // In a real implementation, you would need to integrate with Polymarket's CLOB client SDK
@@ -10,6 +39,10 @@ use std::sync::Arc;
 pub struct ClobClient {
     // Placeholder fields
     // In a real implementation, this would contain the actual client
+    queue: Arc<OrderQueue>, // Every post / cancel goes through here (ORDER_MAX_IN_FLIGHT, lane priority)
+    creds: Arc<ApiCredsStore>, // L2 API credentials (shared per wallet, optionally cached in API_CREDS_FILE)
+    http: reqwest::Client,
+    clob_url: String,
 }
 
 impl ClobClient {
@@ -28,7 +61,7 @@ impl ClobClient {
         let code = provider.get_code(proxy_wallet.parse::<Address>()?, None).await?;
         let is_proxy_safe = !code.is_empty();
 
-        println!(
+        outln!(
             "{}",
             format!(
                 "Wallet type detected: {}",
@@ -41,11 +74,65 @@ impl ClobClient {
             .cyan()
         );
 
+        // Warm start from the cache file when it's still good (FYI: the first market discovery never waits on /auth)
+        let creds = ApiCredsStore::start(env, private_key)?;
+
         // TODO: Initialize actual CLOB client
         // This would require implementing the Polymarket CLOB API client in Rust
         // or using their REST API directly
 
-        Ok(ClobClient {})
+        Ok(ClobClient {
+            queue: OrderQueue::new(env),
+            creds,
+            http: reqwest::Client::new(),
+            clob_url: env.clob_http_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    pub fn queue(&self) -> &Arc<OrderQueue> {
+        &self.queue
+    }
+
+    // Authenticated CLOB REST call (FYI: L2 headers are signed over the exact body that is sent)
+    async fn l2_request(&self, method: reqwest::Method, path: &str, body: Option<serde_json::Value>) -> Result<serde_json::Value> {
+        let creds = self.creds.current()?;
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let mut req = self
+            .http
+            .request(method.clone(), format!("{}{}", self.clob_url, path))
+            .timeout(Duration::from_secs(10));
+        for (name, value) in creds.l2_headers(method.as_str(), path, &body)? {
+            req = req.header(name, value);
+        }
+        if !body.is_empty() {
+            req = req.header("Content-Type", "application/json").body(body);
+        }
+        clob_slot().await;
+        let resp = req.send().await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let wait = resp
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|s| s.is_finite() && *s > 0.0)
+                .map(|s| Duration::from_secs_f64(s).min(MAX_CLOB_WAIT))
+                .unwrap_or(DEFAULT_RETRY_AFTER);
+            if let Some(gate) = CLOB_GATE.get() {
+                gate.throttled(ARB_STRATEGY, wait);
+            }
+        }
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(anyhow!("{} {}: HTTP {} {}", method, path, status, text.trim()));
+        }
+        Ok(resp.json().await?)
+    }
+
+    // Orders can be signed right now (BTW: false only until the first background derive finishes)
+    pub fn creds_ready(&self) -> bool {
+        self.creds.is_ready()
     }
 
     pub async fn create_market_order(
@@ -60,14 +147,72 @@ impl ClobClient {
         Err(anyhow!("CLOB client not fully implemented - requires Polymarket SDK integration"))
     }
 
+    // POST /order body for one signed order (FYI: `owner` is the API key the order is posted under)
+    fn order_body(&self, signed_order: &str, order_type: OrderType) -> Result<serde_json::Value> {
+        let order: serde_json::Value = serde_json::from_str(signed_order)?;
+        Ok(serde_json::json!({
+            "order": order,
+            "owner": self.creds.current()?.api_key,
+            "orderType": order_type.as_str(),
+        }))
+    }
+
     pub async fn post_order(&self, signed_order: &str, order_type: OrderType) -> Result<OrderResponse> {
-        // TODO: Implement actual order posting
-        Err(anyhow!("CLOB client not fully implemented - requires Polymarket SDK integration"))
+        let body = self.order_body(signed_order, order_type)?;
+        let data = self.l2_request(reqwest::Method::POST, "/order", Some(body)).await?;
+        Ok(OrderResponse::from_json(&data))
+    }
+
+    // Post through the submission queue (FYI: waits for a slot in its lane, Err when an entry finds the queue full)
+    pub async fn submit_order(&self, signed_order: &str, order_type: OrderType, priority: SubmitPriority) -> Result<OrderResponse> {
+        let _slot = self.queue.acquire(priority).await?;
+        self.post_order(signed_order, order_type).await
+    }
+
+    // Cancel a resting order (BTW: always the front lane, ahead of anything new)
+    // Ok only when the CLOB lists the id as canceled - an order that already filled comes back in not_canceled
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let _slot = self.queue.acquire(SubmitPriority::Cancel).await?;
+        let data = self
+            .l2_request(reqwest::Method::DELETE, "/order", Some(serde_json::json!({ "orderID": order_id })))
+            .await?;
+        let canceled = data
+            .get("canceled")
+            .and_then(|v| v.as_array())
+            .map(|ids| ids.iter().any(|id| id.as_str() == Some(order_id)))
+            .unwrap_or(false);
+        if canceled {
+            return Ok(());
+        }
+        let reason = data
+            .get("not_canceled")
+            .and_then(|m| m.get(order_id))
+            .and_then(|v| v.as_str())
+            .unwrap_or("not in the canceled list");
+        Err(anyhow!("Cancel of {} refused: {}", order_id, reason))
+    }
+
+    // Current state of one of our orders (FYI: how a FAK without fill amounts in its response learns its fill)
+    pub async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        let data = self
+            .l2_request(reqwest::Method::GET, &format!("/data/order/{}", order_id), None)
+            .await?;
+        if data.is_null() {
+            return Err(anyhow!("Order {} not found", order_id));
+        }
+        Ok(OpenOrder::from_json(&data))
     }
 
     pub async fn post_orders(&self, orders: Vec<(String, OrderType)>) -> Result<Vec<OrderResponse>> {
-        // TODO: Implement batch order posting
-        Err(anyhow!("CLOB client not fully implemented - requires Polymarket SDK integration"))
+        let body = orders
+            .iter()
+            .map(|(signed_order, order_type)| self.order_body(signed_order, *order_type))
+            .collect::<Result<Vec<_>>>()?;
+        let data = self
+            .l2_request(reqwest::Method::POST, "/orders", Some(serde_json::Value::Array(body)))
+            .await?;
+        let responses = data.as_array().ok_or_else(|| anyhow!("POST /orders: expected an array, got {}", data))?;
+        Ok(responses.iter().map(OrderResponse::from_json).collect())
     }
 }
 
@@ -77,9 +222,32 @@ pub enum OrderSide {
     Sell,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderType {
-    FAK, // Fill and Kill
+    FOK, // Fill or Kill (all or nothing)
+    FAK, // Fill and Kill (partial fills allowed, rest cancelled)
+}
+
+// Parse from env string (FYI: case-insensitive, FOK / FAK only)
+impl std::str::FromStr for OrderType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_uppercase().as_str() {
+            "FOK" => Ok(OrderType::FOK),
+            "FAK" => Ok(OrderType::FAK),
+            other => Err(anyhow!("unknown order type {:?} (FOK or FAK)", other)),
+        }
+    }
+}
+
+impl OrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::FOK => "FOK",
+            OrderType::FAK => "FAK",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -87,9 +255,140 @@ pub struct OrderResponse {
     pub success: bool,
     pub order_id: Option<String>,
     pub error: Option<String>,
+    pub status: Option<String>, // matched / delayed / unmatched
+    pub making_amount: Option<f64>, // What we gave (USDC on a buy)
+    pub taking_amount: Option<f64>, // What we got (tokens on a buy)
+}
+
+impl OrderResponse {
+    // Parse CLOB /order response (AFAIK: amounts come back as strings, sometimes numbers)
+    pub fn from_json(data: &serde_json::Value) -> Self {
+        let parse_amount = |key: &str| -> Option<f64> {
+            let v = data.get(key)?;
+            v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        };
+        let error = data
+            .get("errorMsg")
+            .or_else(|| data.get("error"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        Self {
+            success: data.get("success").and_then(|v| v.as_bool()).unwrap_or(error.is_none()),
+            order_id: data
+                .get("orderID")
+                .or_else(|| data.get("orderId"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            error,
+            status: data.get("status").and_then(|v| v.as_str()).map(|s| s.to_string()),
+            making_amount: parse_amount("makingAmount"),
+            taking_amount: parse_amount("takingAmount"),
+        }
+    }
+
+    // Tokens actually received on a buy (BTW: None when the API gave no fill info)
+    pub fn filled_tokens(&self) -> Option<f64> {
+        self.taking_amount.filter(|v| v.is_finite() && *v >= 0.0)
+    }
+
+    // USDC actually spent on a buy
+    pub fn filled_usdc(&self) -> Option<f64> {
+        self.making_amount.filter(|v| v.is_finite() && *v >= 0.0)
+    }
+
+    // Tokens given up on a sell (FYI: making/taking swap meaning vs a buy)
+    pub fn sold_tokens(&self) -> Option<f64> {
+        self.filled_usdc()
+    }
+
+    // USDC received on a sell
+    pub fn sold_usdc(&self) -> Option<f64> {
+        self.filled_tokens()
+    }
+}
+
+// One resting order as the CLOB reports it
+#[derive(Debug, Clone)]
+pub struct OpenOrder {
+    pub status: String, // live / matched / cancelled
+    pub original_size: f64,
+    pub size_matched: f64,
+}
+
+impl OpenOrder {
+    pub fn from_json(data: &serde_json::Value) -> Self {
+        let parse_amount = |key: &str| -> f64 {
+            data.get(key)
+                .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .unwrap_or(0.0)
+        };
+        Self {
+            status: data.get("status").and_then(|v| v.as_str()).unwrap_or("").to_lowercase(),
+            original_size: parse_amount("original_size"),
+            size_matched: parse_amount("size_matched"),
+        }
+    }
+
+    // Still resting (BTW: anything else never fills further)
+    pub fn is_live(&self) -> bool {
+        self.status == "live"
+    }
+}
+
+impl Drop for ClobClient {
+    fn drop(&mut self) {
+        self.queue.close(); // Ends the queue's dispatcher task
+    }
 }
 
 pub async fn create_clob_client(env: &Env) -> Result<ClobClient> {
     ClobClient::new(env).await
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_partial_fill_amounts() {
+        let resp = OrderResponse::from_json(&json!({
+            "success": true,
+            "orderID": "0xabc",
+            "status": "matched",
+            "makingAmount": "2.5",
+            "takingAmount": 5.0,
+        }));
+        assert!(resp.success);
+        assert_eq!(resp.order_id.as_deref(), Some("0xabc"));
+        assert_eq!(resp.filled_usdc(), Some(2.5));
+        assert_eq!(resp.filled_tokens(), Some(5.0));
+    }
+
+    #[test]
+    fn missing_fill_amounts_stay_unknown() {
+        let resp = OrderResponse::from_json(&json!({ "success": true, "orderID": "0xabc", "status": "delayed" }));
+        assert!(resp.success);
+        assert_eq!(resp.filled_tokens(), None);
+        assert_eq!(resp.filled_usdc(), None);
+
+        // Blank / unparseable amounts are no fill info either
+        let resp = OrderResponse::from_json(&json!({ "success": true, "makingAmount": "", "takingAmount": "n/a" }));
+        assert_eq!(resp.filled_tokens(), None);
+        assert_eq!(resp.filled_usdc(), None);
+    }
+
+    #[test]
+    fn error_message_means_failure() {
+        let resp = OrderResponse::from_json(&json!({ "errorMsg": "not enough balance / allowance" }));
+        assert!(!resp.success);
+        assert_eq!(resp.error.as_deref(), Some("not enough balance / allowance"));
+
+        let resp = OrderResponse::from_json(&json!({ "success": true, "errorMsg": "" }));
+        assert!(resp.success);
+        assert_eq!(resp.error, None);
+    }
+}
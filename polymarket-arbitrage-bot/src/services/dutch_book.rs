@@ -0,0 +1,281 @@
+use crate::config::GAMMA_API_HOST;
+use crate::services::neg_risk::{conversion_payout, question_index};
+use anyhow::Result;
+use polymarket_book::Book;
+use serde_json::Value;
+use std::collections::HashMap;
+
+// One outcome of a basket (FYI: we buy the ask of every leg)
+#[derive(Debug, Clone)]
+pub struct BasketLeg {
+    pub token_id: String,
+    pub label: String,
+    pub ask: f64,
+    pub ask_size: f64,
+}
+
+// NO basket of a neg-risk event, redeemed early through the adapter instead of at resolution
+#[derive(Debug, Clone)]
+pub struct NegRiskConversion {
+    pub market_id: String, // negRiskMarketID (bytes32)
+    pub question_indices: Vec<u8>, // One per leg, for the index set
+}
+
+// Set of outcomes priced below what they pay out (IMO: a "Dutch book")
+#[derive(Debug, Clone)]
+pub struct DutchBook {
+    pub event_slug: String,
+    pub event_title: String,
+    pub neg_risk: bool, // Multi-market event (YES of each market, or NO of each market)
+    pub legs: Vec<BasketLeg>,
+    pub payout: f64, // USDC per basket: 1 for outcome/YES sets, N-1 for a converted NO set
+    pub conversion: Option<NegRiskConversion>, // Set = NO basket, convert after buying
+    pub ask_sum: f64,
+    pub edge: f64, // payout - ask_sum - fees (per basket)
+    pub max_baskets: f64, // Limited by the thinnest leg's top-of-book size
+}
+
+// Candidate basket before prices are known
+struct Candidate {
+    event_slug: String,
+    event_title: String,
+    neg_risk: bool,
+    legs: Vec<(String, String)>, // (token_id, label)
+    payout: f64,
+    conversion: Option<NegRiskConversion>,
+}
+
+// String-or-array JSON field (BTW: Gamma returns both forms)
+fn json_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => serde_json::from_str(s).unwrap_or_default(),
+        Some(Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn as_bool(value: Option<&Value>) -> bool {
+    value.and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+// Crawl active events from Gamma (AFAIK: paginated by offset, 100 per page)
+pub async fn fetch_active_events(max_events: usize) -> Result<Vec<Value>> {
+    let client = reqwest::Client::new();
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while events.len() < max_events {
+        let url = format!(
+            "{}/events?active=true&closed=false&limit=100&offset={}",
+            GAMMA_API_HOST, offset
+        );
+        let page: Value = client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(15))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let items = page.as_array().cloned().unwrap_or_default();
+        if items.is_empty() {
+            break;
+        }
+        offset += items.len();
+        events.extend(items);
+    }
+    events.truncate(max_events);
+    Ok(events)
+}
+
+// Turn an event into baskets of mutually exclusive outcomes
+// - negRisk event: YES token of every open market (exactly one resolves YES)
+//   + NO token of every market (converts to N-1 USDC via the NegRiskAdapter)
+// - otherwise: every outcome token of each market on its own
+fn event_candidates(event: &Value) -> Vec<Candidate> {
+    let slug = event.get("slug").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let title = event.get("title").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let markets: Vec<&Value> = event
+        .get("markets")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter(|m| !as_bool(m.get("closed"))).collect())
+        .unwrap_or_default();
+    let neg_risk = as_bool(event.get("negRisk")) || as_bool(event.get("enableNegRisk"));
+
+    if neg_risk && markets.len() > 1 {
+        // Skip if any open leg is not tradable (NGL: a missing leg breaks the hedge)
+        if markets.iter().any(|m| !as_bool(m.get("acceptingOrders"))) {
+            return Vec::new();
+        }
+        let label = |m: &Value| {
+            m.get("groupItemTitle")
+                .or_else(|| m.get("question"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("?")
+                .to_string()
+        };
+        let legs: Vec<(String, String)> = markets
+            .iter()
+            .filter_map(|m| {
+                let tokens = json_list(m.get("clobTokenIds"));
+                tokens.first().map(|t| (t.clone(), label(*m)))
+            })
+            .collect();
+        if legs.len() != markets.len() {
+            return Vec::new();
+        }
+        let mut out = vec![Candidate {
+            event_slug: slug.clone(),
+            event_title: title.clone(),
+            neg_risk: true,
+            legs,
+            payout: 1.0,
+            conversion: None,
+        }];
+
+        // NO side needs the adapter market id + every question's index (BTW: else we can't convert)
+        let market_id = event
+            .get("negRiskMarketID")
+            .or_else(|| markets[0].get("negRiskMarketID"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let no_legs: Vec<(String, String, u8)> = markets
+            .iter()
+            .filter_map(|m| {
+                let tokens = json_list(m.get("clobTokenIds"));
+                let index = m
+                    .get("questionID")
+                    .and_then(|v| v.as_str())
+                    .and_then(question_index)?;
+                tokens.get(1).map(|t| (t.clone(), format!("NO {}", label(*m)), index))
+            })
+            .collect();
+        if let (Some(market_id), true) = (market_id, no_legs.len() == markets.len()) {
+            out.push(Candidate {
+                event_slug: slug,
+                event_title: format!("{} (NO set)", title),
+                neg_risk: true,
+                payout: conversion_payout(no_legs.len()),
+                conversion: Some(NegRiskConversion {
+                    market_id,
+                    question_indices: no_legs.iter().map(|(_, _, i)| *i).collect(),
+                }),
+                legs: no_legs.into_iter().map(|(t, l, _)| (t, l)).collect(),
+            });
+        }
+        return out;
+    }
+
+    markets
+        .iter()
+        .filter(|m| as_bool(m.get("acceptingOrders")))
+        .filter_map(|m| {
+            let tokens = json_list(m.get("clobTokenIds"));
+            let outcomes = json_list(m.get("outcomes"));
+            if tokens.len() < 2 || tokens.len() != outcomes.len() {
+                return None;
+            }
+            Some(Candidate {
+                event_slug: m
+                    .get("slug")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&slug)
+                    .to_string(),
+                event_title: m
+                    .get("question")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(&title)
+                    .to_string(),
+                neg_risk: false,
+                legs: tokens.into_iter().zip(outcomes).collect(),
+                payout: 1.0,
+                conversion: None,
+            })
+        })
+        .collect()
+}
+
+// Best ask (price, size) per token via CLOB POST /books (FYI: batched 50 at a time)
+pub async fn fetch_best_asks(clob_http_url: &str, token_ids: &[String]) -> Result<HashMap<String, (f64, f64)>> {
+    let client = reqwest::Client::new();
+    let mut out = HashMap::new();
+    for chunk in token_ids.chunks(50) {
+        let body: Vec<Value> = chunk
+            .iter()
+            .map(|t| serde_json::json!({ "token_id": t }))
+            .collect();
+        let books: Value = client
+            .post(format!("{}/books", clob_http_url.trim_end_matches('/')))
+            .json(&body)
+            .timeout(std::time::Duration::from_secs(15))
+            .send()
+            .await?
+            .json()
+            .await?;
+        for book in books.as_array().cloned().unwrap_or_default() {
+            let Some(asset) = book.get("asset_id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            if let Some(level) = Book::from_json(&book).best_ask() {
+                out.insert(asset.to_string(), (level.price, level.size));
+            }
+        }
+    }
+    Ok(out)
+}
+
+// Full scan: events -> baskets -> books -> baskets under their payout by more than fees + min_edge
+pub async fn scan_dutch_books(
+    clob_http_url: &str,
+    fee_rate: f64,
+    min_edge: f64,
+    max_events: usize,
+) -> Result<Vec<DutchBook>> {
+    let events = fetch_active_events(max_events).await?;
+    let candidates: Vec<Candidate> = events.iter().flat_map(event_candidates).collect();
+
+    let token_ids: Vec<String> = candidates
+        .iter()
+        .flat_map(|c| c.legs.iter().map(|(t, _)| t.clone()))
+        .collect();
+    let asks = fetch_best_asks(clob_http_url, &token_ids).await?;
+
+    let mut books: Vec<DutchBook> = candidates
+        .into_iter()
+        .filter_map(|c| {
+            let legs: Vec<BasketLeg> = c
+                .legs
+                .iter()
+                .map(|(token_id, label)| {
+                    asks.get(token_id).map(|(ask, size)| BasketLeg {
+                        token_id: token_id.clone(),
+                        label: label.clone(),
+                        ask: *ask,
+                        ask_size: *size,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?; // Every leg needs an ask
+            let ask_sum: f64 = legs.iter().map(|l| l.ask).sum();
+            let edge = c.payout - ask_sum - ask_sum * fee_rate;
+            if edge <= min_edge {
+                return None;
+            }
+            let max_baskets = legs.iter().map(|l| l.ask_size).fold(f64::MAX, f64::min);
+            Some(DutchBook {
+                event_slug: c.event_slug,
+                event_title: c.event_title,
+                neg_risk: c.neg_risk,
+                legs,
+                payout: c.payout,
+                conversion: c.conversion,
+                ask_sum,
+                edge,
+                max_baskets,
+            })
+        })
+        .collect();
+
+    books.sort_by(|a, b| b.edge.partial_cmp(&a.edge).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(books)
+}
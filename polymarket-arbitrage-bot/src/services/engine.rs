@@ -0,0 +1,929 @@
+use crate::config::Env;
+use crate::services;
+use crate::services::create_clob_client::OrderType;
+use crate::services::execution_queue::ExecutionQueue;
+use crate::services::exposure::{release_exposure, ExposureHold};
+use crate::services::fill_score::{fill_score, load_fill_history};
+use crate::services::health::{start_health_reporting, HealthState};
+use crate::services::hedge::{hedge_residual, residual_exposure};
+use crate::services::market_discovery::{find_15_min_market, prefetch_next_market, CoinMarket, MarketWindow};
+use crate::services::market_ledger::MarketLedger;
+use crate::services::market_precision::{configured_min_tokens, set_order_minimums};
+use crate::services::market_result::spawn_market_result;
+use crate::services::opportunity::{evaluate_opportunity, OpportunityConfig, TradePlan};
+use crate::services::price_monitor::{display_coin_details, PriceMonitor};
+use crate::services::reverse_arb::{evaluate_reverse_opportunity, ReversePlan};
+use crate::services::session_budget::SessionBudget;
+use crate::services::settlement::SettlementVerifier;
+use crate::services::spread_alert::{publish_alert, SpreadAlert};
+use crate::services::timeseries::{record_prices, start_timeseries};
+use crate::services::trade_journal::{BookPair, JournalEntry};
+use crate::services::value_trades::{evaluate_value_opportunity, spawn_spot_feed, SpotModel, ValuePlan, ValueRisk};
+use crate::services::wallet_balance::{is_balance_paused, start_balance_watch};
+use crate::services::websocket_client::MarketWebSocket;
+use crate::utils::clock::{market_time_str, now_ms};
+use crate::utils::logger::{journal_log_path, log_console, LogLevel};
+use colored::*;
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+// Cleared by stop_arbitrage_engine (FYI: lets a supervisor shut the loop down with the rest of the process)
+static ENGINE_RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_arbitrage_engine() {
+    ENGINE_RUNNING.store(false, Ordering::SeqCst);
+}
+
+pub(crate) fn is_engine_running() -> bool {
+    ENGINE_RUNNING.load(Ordering::SeqCst)
+}
+
+// Trade one coin (FYI: auto-switches to next market when current closes)
+pub async fn run_arbitrage_engine(coin: &str, env: &Env) -> anyhow::Result<()> {
+    set_order_minimums(env); // MIN_ORDER_SIZE_USD / MIN_ORDER_SIZE_TOKENS (BTW: here so no entry point can skip it)
+    let health = Arc::new(HealthState::new(env.health_max_book_age_secs)); // Liveness for supervisors (/healthz + heartbeat)
+    start_health_reporting(&health, env);
+    start_timeseries(env); // INFLUX_URL metrics writer (no-op when unset)
+    start_balance_watch(env); // MIN_WALLET_BALANCE_USD pause / resume (no-op when unset)
+    if env.min_fill_score > 0.0 {
+        let samples = load_fill_history(&journal_log_path());
+        let (min_score, min_samples) = (env.min_fill_score, env.fill_score_min_samples);
+        log_console(LogLevel::Info, move || {
+            outln!(
+                "{}",
+                format!("📐 Fill scoring: {} past attempts, min score {:.2} (active from {} attempts)", samples, min_score, min_samples).cyan()
+            );
+        });
+    }
+    monitor_market_loop(coin, env, health).await
+}
+
+// ALERT_ONLY: watch every ALERT_COINS market at once and alert on spreads, never trade
+pub async fn run_alert_only(env: &Env) -> anyhow::Result<()> {
+    let health = Arc::new(HealthState::new(env.health_max_book_age_secs)); // One /healthz for all coins
+    start_health_reporting(&health, env);
+    start_timeseries(env); // INFLUX_URL metrics writer (no-op when unset)
+    let loops = env
+        .alert_coins
+        .iter()
+        .map(|coin| monitor_market_loop(coin, env, health.clone()));
+    for result in futures::future::join_all(loops).await {
+        result?;
+    }
+    Ok(())
+}
+
+// Retry a next-window lookup that came back empty this often (FYI: Gamma may list it only shortly before)
+const PREFETCH_RETRY_MS: i64 = 15_000;
+
+// Find the next window's market and subscribe its tokens now (BTW: the current market keeps trading; true = ready)
+async fn prefetch_next(current: Arc<CoinMarket>, ws: Arc<MarketWebSocket>) -> bool {
+    match prefetch_next_market(&current, MarketWindow::FifteenMin).await {
+        Ok(Some(next)) => {
+            ws.prefetch(vec![next.up_token_id.clone(), next.down_token_id.clone()]).await;
+            let (coin, slug) = (current.coin.clone(), next.slug);
+            log_console(LogLevel::Info, move || {
+                outln!("{}", format!("\n⏭  {} next market prefetched: {} (books streaming before roll-over)\n", coin, slug).bright_black());
+            });
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            let msg = e.to_string();
+            log_console(LogLevel::Debug, move || {
+                eoutln!("{}", format!("   Next market prefetch failed: {}", msg).bright_black());
+            });
+            false
+        }
+    }
+}
+
+// Main monitoring loop for one coin
+async fn monitor_market_loop(coin: &str, env: &Env, health: Arc<HealthState>) -> anyhow::Result<()> {
+    let mut ws: Option<Arc<MarketWebSocket>> = None; // WS connection (lazy init)
+    let clob_client = Arc::new(Mutex::new(None::<Arc<services::create_clob_client::ClobClient>>)); // Trading client (lazy init)
+    let monitor = Arc::new(Mutex::new(PriceMonitor::new())); // Price history tracker
+    let recent_opportunities = Arc::new(Mutex::new(RecentOpportunities::default())); // Dedup tracker (prevents duplicate trades)
+    let execution_queue = Arc::new(ExecutionQueue::new(env)); // Bounded trade queue (per-market serialization + global limit)
+    let session_budget = Arc::new(Mutex::new(SessionBudget::new(env))); // Spend/loss caps (survives market roll-overs)
+    let ledger = Arc::new(Mutex::new(MarketLedger::new(env))); // Inventory + notional per market window
+    let value_risk = Arc::new(Mutex::new(ValueRisk::default())); // VALUE_TRADES caps (separate from the arb caps)
+    let settlement = SettlementVerifier::new(env).unwrap_or_else(|e| {
+        eoutln!("{}", format!("Settlement verification disabled: {}", e).yellow());
+        None
+    }); // Fills checked against CTF balances (None = off)
+
+    while is_engine_running() {
+        match discover_and_monitor(coin, &mut ws, &clob_client, &monitor, &recent_opportunities, &execution_queue, &session_budget, &ledger, &value_risk, &settlement, &health, env).await {
+            Ok(Some(market)) => {
+                let mut prefetch: Option<tokio::task::JoinHandle<bool>> = None; // Next-window lookup in flight
+                let mut prefetched = false;
+                let mut next_prefetch_ms = 0i64;
+                // Monitor until market closes (BTW: auto-finds next market after)
+                while let Some(ref m) = market {
+                    if !is_engine_running() {
+                        break;
+                    }
+                    let end_date = chrono::DateTime::parse_from_rfc3339(&m.end_date)
+                        .unwrap_or_else(|_| chrono::Utc::now().into())
+                        .with_timezone(&chrono::Utc);
+                    let now = chrono::Utc::now();
+                    let time_until_end = (end_date - now).num_milliseconds();
+
+                    // PREFETCH_NEXT_SECS before the close: next market's tokens subscribed, so roll-over has no blind spot
+                    if prefetch.as_ref().is_some_and(|task| task.is_finished()) {
+                        prefetched = prefetch.take().unwrap().await.unwrap_or(false);
+                    }
+                    if env.prefetch_next_secs > 0
+                        && !prefetched
+                        && prefetch.is_none()
+                        && time_until_end <= env.prefetch_next_secs as i64 * 1000
+                        && now_ms() >= next_prefetch_ms
+                    {
+                        if let Some(ws) = ws.clone() {
+                            next_prefetch_ms = now_ms() + PREFETCH_RETRY_MS;
+                            prefetch = Some(tokio::spawn(prefetch_next(m.clone(), ws)));
+                        }
+                    }
+
+                    if time_until_end <= 0 {
+                        let pos = ledger.lock().await.position(&m.slug);
+                        release_exposure(pos.spent_usd); // Window resolved (BTW: frees the shared cap for the next one)
+                        if !env.alert_only {
+                            spawn_market_result(m.as_ref().clone(), pos.clone(), env.clone()); // RESULTS_DIR/<slug>.json, PnL filled in at resolution
+                        }
+                        outln!(
+                            "{}",
+                            format!(
+                                "\n\n╔════════════════════════════════════════════════════════════════╗\n║                    MARKET CLOSED                                 ║\n╚════════════════════════════════════════════════════════════════╝\n  Market: {}\n  Coin: {}\n  End Time: {}\n  Inventory: UP {:.2} / DOWN {:.2} tokens ({} trade(s), {} execution(s), ${:.2} spent)\n  Status: {}\n\n",
+                                m.slug, coin, end_date.format("%Y-%m-%d %H:%M:%S UTC"),
+                                pos.up_tokens, pos.down_tokens, pos.trades, pos.executions, pos.spent_usd,
+                                if prefetched { "Switching to the prefetched next market..." } else { "Searching for next market..." }
+                            )
+                            .yellow()
+                            .bold()
+                        );
+                        break;
+                    }
+
+                    sleep(Duration::from_secs(1)).await;
+                }
+            }
+            Ok(None) => {
+                outln!("{}", "Waiting 10 seconds before retrying...\n".yellow());
+                sleep(Duration::from_secs(10)).await;
+            }
+            Err(e) => {
+                eoutln!("{}", format!("Error: {}", e).red());
+                sleep(Duration::from_secs(10)).await;
+            }
+        }
+    }
+
+    session_budget.lock().await.print_summary();
+    Ok(())
+}
+
+// How many opportunity keys the dedup tracker remembers (FYI: oldest dropped first)
+const RECENT_OPPORTUNITIES: usize = 50;
+
+// Opportunity keys already acted on, in insertion order (AFAIK: the set answers lookups, the deque says what's oldest)
+#[derive(Default)]
+struct RecentOpportunities {
+    order: VecDeque<String>,
+    keys: HashSet<String>,
+}
+
+impl RecentOpportunities {
+    fn contains(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+}
+
+// Remember `key`, dropping the oldest once over RECENT_OPPORTUNITIES (false = already remembered)
+fn remember_opportunity(opps: &mut RecentOpportunities, key: String) -> bool {
+    if !opps.keys.insert(key.clone()) {
+        return false;
+    }
+    opps.order.push_back(key);
+    while opps.order.len() > RECENT_OPPORTUNITIES {
+        if let Some(oldest) = opps.order.pop_front() {
+            opps.keys.remove(&oldest);
+        }
+    }
+    true
+}
+
+// Both books as they are now (BTW: None = the spread closed while the trade waited)
+fn recheck_opportunity(ws: &MarketWebSocket, market: &CoinMarket, cfg: &OpportunityConfig) -> Option<TradePlan> {
+    let up = ws.get_orderbook(&market.up_token_id)?;
+    let down = ws.get_orderbook(&market.down_token_id)?;
+    evaluate_opportunity(&up, &down, cfg, chrono::Utc::now())
+}
+
+// Queue a sell-both against held pairs (FYI: same dedup / queue / latency rules as the buy path)
+async fn queue_reverse_arbitrage(
+    plan: ReversePlan,
+    detected: BookPair,
+    market: Arc<CoinMarket>,
+    cfg: Arc<OpportunityConfig>,
+    ws: Arc<MarketWebSocket>,
+    clob_client: &Arc<Mutex<Option<Arc<services::create_clob_client::ClobClient>>>>,
+    recent_opps: &Arc<Mutex<RecentOpportunities>>,
+    queue: &Arc<ExecutionQueue>,
+    budget: &Arc<Mutex<SessionBudget>>,
+    ledger: &Arc<Mutex<MarketLedger>>,
+    settlement: &Option<Arc<SettlementVerifier>>,
+    env: &Env,
+) {
+    let Some(client) = clob_client.lock().await.clone() else {
+        return;
+    };
+    if budget.lock().await.is_halted() {
+        return; // Halted sessions place nothing, sells included
+    }
+    let mut opps = recent_opps.lock().await;
+    if opps.contains(&plan.opportunity_key) {
+        return;
+    }
+    if ledger.lock().await.window_cap(&market.slug, 0.0).is_some() {
+        ledger.lock().await.record_missed(&market.slug, &plan.opportunity_key);
+        return; // Sales spend nothing, but they count against MAX_TRADES_PER_WINDOW
+    }
+
+    let (coin, up_bid, down_bid, bid_sum, edge) = (market.coin.clone(), plan.up_bid, plan.down_bid, plan.bid_sum, plan.edge);
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "\n⚡ [{}] REVERSE ARBITRAGE DETECTED - {}\n   UP_BID: {:.4} + DOWN_BID: {:.4} = {:.4}\n   Edge: {:.2}% per pair\n",
+                market_time_str(now_ms(), "%H:%M:%S"), coin, up_bid, down_bid, bid_sum, edge * 100.0
+            )
+            .green()
+            .bold()
+        );
+    });
+
+    // Inventory leaves the ledger now so a second detection can't sell the same pairs
+    let Some(pairs) = MarketLedger::reserve_pairs(ledger, &market.slug, plan.tokens).await else {
+        return;
+    };
+    let opportunity_key = plan.opportunity_key.clone();
+    let (job_env, job_ledger, job_budget, job_key) = (env.clone(), ledger.clone(), budget.clone(), opportunity_key.clone());
+    let job_market = market.clone();
+    let job_settlement = settlement.clone();
+    let journal = JournalEntry::new(&market, "reverse", &opportunity_key, detected);
+    let submitted = queue.try_submit(&market.slug, async move {
+        let mut plan = plan;
+        let latency_ms = now_ms() - plan.timestamp;
+        if job_env.max_exec_latency_ms > 0 && latency_ms > job_env.max_exec_latency_ms as i64 {
+            let fresh = ws.get_orderbook(&job_market.up_token_id).zip(ws.get_orderbook(&job_market.down_token_id)).and_then(|(up, down)| {
+                evaluate_reverse_opportunity(&up, &down, &cfg, job_env.reverse_arb_fee, pairs.tokens(), chrono::Utc::now())
+            });
+            match fresh {
+                Some(fresh) => plan = fresh,
+                None => {
+                    log_console(LogLevel::Warn, move || {
+                        outln!("{}", format!("   Reverse arbitrage delayed {}ms and the bids are gone - sale aborted", latency_ms).yellow());
+                    });
+                    job_ledger.lock().await.record_missed(&job_market.slug, &job_key);
+                    JournalEntry { executed: BookPair::from_ws(&ws, &job_market), ..journal }.record(&job_env);
+                    return;
+                }
+            }
+        }
+
+        if let Err(reason) = job_ledger.lock().await.begin_execution(&job_market.slug, 0.0) {
+            log_console(LogLevel::Info, move || {
+                outln!("{}", format!("   {} - sale dropped", reason).yellow());
+            });
+            job_ledger.lock().await.record_missed(&job_market.slug, &job_key);
+            return;
+        }
+
+        let journal = JournalEntry { executed: BookPair::from_ws(&ws, &job_market), ..journal };
+        let result = services::arbitrage_executor::execute_reverse_arbitrage_trade(
+            &client,
+            &job_market.up_token_id,
+            &job_market.down_token_id,
+            plan.up_bid,
+            plan.down_bid,
+            plan.tokens.min(pairs.tokens()),
+            &job_env,
+        )
+        .await;
+        match result {
+            Ok((ref up_result, ref down_result, _)) => journal.with_results(Some(up_result), Some(down_result)).record(&job_env),
+            Err(_) => journal.with_results(None, None).record(&job_env),
+        }
+        if let Ok((up_result, down_result, _)) = result {
+            job_budget.lock().await.record_sale(&up_result, &down_result);
+            pairs.settle(&up_result, &down_result).await;
+            if let Some(verifier) = job_settlement {
+                verifier.spawn_verify(job_ledger.clone(), job_market.clone());
+            }
+        }
+    })
+    .await;
+
+    // Remember it once queued (BTW: a full queue hands the pairs back and retries on the next tick)
+    if submitted {
+        remember_opportunity(&mut opps, opportunity_key);
+    } else {
+        ledger.lock().await.record_missed(&market.slug, &opportunity_key);
+    }
+}
+
+// Queue a single-sided value buy (FYI: own caps, otherwise the same dedup / queue / latency rules as arbitrage)
+async fn queue_value_trade(
+    plan: ValuePlan,
+    detected: BookPair,
+    market: Arc<CoinMarket>,
+    cfg: Arc<OpportunityConfig>,
+    ws: Arc<MarketWebSocket>,
+    spot: Arc<std::sync::Mutex<Option<SpotModel>>>,
+    clob_client: &Arc<Mutex<Option<Arc<services::create_clob_client::ClobClient>>>>,
+    recent_opps: &Arc<Mutex<RecentOpportunities>>,
+    queue: &Arc<ExecutionQueue>,
+    budget: &Arc<Mutex<SessionBudget>>,
+    ledger: &Arc<Mutex<MarketLedger>>,
+    value_risk: &Arc<Mutex<ValueRisk>>,
+    settlement: &Option<Arc<SettlementVerifier>>,
+    env: &Env,
+) {
+    let Some(client) = clob_client.lock().await.clone() else {
+        return;
+    };
+    let mut opps = recent_opps.lock().await;
+    if opps.contains(&plan.opportunity_key) {
+        return;
+    }
+
+    // Shrink to what VALUE_MAX_PER_MARKET_USD / VALUE_MAX_SESSION_USD still allow, then reserve it
+    let mut plan = plan;
+    let (planned_usd, mut budget_hold) = {
+        let mut risk = value_risk.lock().await;
+        plan.tokens = risk.size_for(&market.slug, plan.tokens, plan.ask, env);
+        let hold = if plan.tokens < configured_min_tokens()
+            || ledger.lock().await.window_cap(&market.slug, plan.planned_spend()).is_some()
+        {
+            None
+        } else {
+            SessionBudget::reserve(budget, plan.planned_spend()).await
+        };
+        let Some(hold) = hold else {
+            ledger.lock().await.record_missed(&market.slug, &plan.opportunity_key);
+            return;
+        };
+        risk.reserve(&market.slug, plan.planned_spend());
+        (plan.planned_spend(), hold)
+    };
+
+    let (coin, side, ask, model_price, edge) = (market.coin.clone(), plan.side, plan.ask, plan.model_price, plan.edge);
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "\n⚡ [{}] VALUE TRADE - {} {}\n   Ask: {:.4} vs model {:.4} (edge {:.2}%)\n",
+                market_time_str(now_ms(), "%H:%M:%S"), coin, side, ask, model_price, edge * 100.0
+            )
+            .cyan()
+            .bold()
+        );
+    });
+
+    let opportunity_key = plan.opportunity_key.clone();
+    let (job_env, job_ledger, job_risk, job_key) = (env.clone(), ledger.clone(), value_risk.clone(), opportunity_key.clone());
+    let job_market = market.clone();
+    let job_settlement = settlement.clone();
+    let journal = JournalEntry::new(&market, &format!("value-{}", plan.side.to_lowercase()), &opportunity_key, detected);
+    let submitted = queue.try_submit(&market.slug, async move {
+        let mut plan = plan;
+        let latency_ms = now_ms() - plan.timestamp;
+        if job_env.max_exec_latency_ms > 0 && latency_ms > job_env.max_exec_latency_ms as i64 {
+            let model = spot.lock().ok().and_then(|m| *m);
+            let fresh = ws.get_orderbook(&job_market.up_token_id).zip(ws.get_orderbook(&job_market.down_token_id)).zip(model).and_then(|((up, down), model)| {
+                evaluate_value_opportunity(&up, &down, &cfg, &model, &job_env, chrono::Utc::now())
+            });
+            match fresh.filter(|fresh| fresh.side == plan.side) {
+                Some(fresh) => {
+                    plan.ask = fresh.ask;
+                    plan.model_price = fresh.model_price;
+                    plan.tokens = plan.tokens.min(fresh.tokens);
+                }
+                None => {
+                    log_console(LogLevel::Warn, move || {
+                        outln!("{}", format!("   Value trade delayed {}ms and the edge is gone - order aborted", latency_ms).yellow());
+                    });
+                    job_risk.lock().await.settle(&job_market.slug, planned_usd, 0.0);
+                    job_ledger.lock().await.record_missed(&job_market.slug, &job_key);
+                    JournalEntry { executed: BookPair::from_ws(&ws, &job_market), ..journal }.record(&job_env);
+                    return;
+                }
+            }
+        }
+
+        // Session caps again at send time, then the window caps (FYI: other queued jobs may have filled meanwhile)
+        let send_check = match budget_hold.begin(plan.planned_spend()).await {
+            Ok(()) => job_ledger.lock().await.begin_execution(&job_market.slug, plan.planned_spend()),
+            Err(reason) => Err(reason),
+        };
+        if let Err(reason) = send_check {
+            log_console(LogLevel::Info, move || {
+                outln!("{}", format!("   {} - value order dropped", reason).yellow());
+            });
+            job_risk.lock().await.settle(&job_market.slug, planned_usd, 0.0);
+            job_ledger.lock().await.record_missed(&job_market.slug, &job_key);
+            return;
+        }
+
+        let journal = JournalEntry { executed: BookPair::from_ws(&ws, &job_market), ..journal };
+        let token_id = if plan.side == "UP" { &job_market.up_token_id } else { &job_market.down_token_id };
+        let result = services::arbitrage_executor::execute_buy_order(
+            &client,
+            token_id,
+            &format!("VALUE-{}", plan.side),
+            plan.planned_spend(),
+            plan.ask,
+            OrderType::FOK,
+            services::order_queue::SubmitPriority::Entry,
+        )
+        .await;
+        job_risk.lock().await.settle(&job_market.slug, planned_usd, if result.success { result.amount } else { 0.0 });
+        let legs = if plan.side == "UP" { (Some(&result), None) } else { (None, Some(&result)) };
+        journal.with_results(legs.0, legs.1).record(&job_env);
+        if result.success {
+            budget_hold.settle_value(result.amount).await;
+            job_ledger.lock().await.record_value(&job_market.slug, plan.side, plan.model_price, &result);
+            if let Some(verifier) = job_settlement {
+                verifier.spawn_verify(job_ledger.clone(), job_market.clone());
+            }
+        }
+    })
+    .await;
+
+    // Remember it once queued (BTW: a full queue gives the reservation back and retries on the next tick)
+    if submitted {
+        remember_opportunity(&mut opps, opportunity_key);
+    } else {
+        value_risk.lock().await.settle(&market.slug, planned_usd, 0.0);
+        ledger.lock().await.record_missed(&market.slug, &opportunity_key);
+    }
+}
+
+async fn discover_and_monitor(
+    coin: &str,
+    ws: &mut Option<Arc<MarketWebSocket>>,
+    clob_client: &Arc<Mutex<Option<Arc<services::create_clob_client::ClobClient>>>>,
+    monitor: &Arc<Mutex<PriceMonitor>>,
+    recent_opportunities: &Arc<Mutex<RecentOpportunities>>,
+    execution_queue: &Arc<ExecutionQueue>,
+    session_budget: &Arc<Mutex<SessionBudget>>,
+    ledger: &Arc<Mutex<MarketLedger>>,
+    value_risk: &Arc<Mutex<ValueRisk>>,
+    settlement: &Option<Arc<SettlementVerifier>>,
+    health: &Arc<HealthState>,
+    env: &Env,
+) -> anyhow::Result<Option<Arc<CoinMarket>>> {
+    outln!("{}", format!("\n🔍 Discovering market for {}...\n", coin).cyan());
+
+    // Initialize ClobClient if needed (FYI: lazy init, only creates once)
+    {
+        let mut client_guard = clob_client.lock().await;
+        if client_guard.is_none() && env.trading_enabled() {
+            outln!("{}", "Initializing ClobClient for trading...\n".bright_black());
+            match services::create_clob_client::create_clob_client(env).await {
+                Ok(client) => {
+                    health.set_order_queue(client.queue());
+                    *client_guard = Some(Arc::new(client));
+                    outln!("{}", "✓ ClobClient initialized\n".green());
+                }
+                Err(e) => {
+                    // NGL: trading disabled but detection still works
+                    outln!("{}", format!("⚠️  Warning: Failed to initialize ClobClient: {}\n", e).yellow());
+                    outln!("{}", "Arbitrage detection will work, but automatic trading is disabled.\n".yellow());
+                }
+            }
+        }
+    }
+
+    // Discover active 15-min market (AFAIK: checks current/next/prev windows)
+    let market = match find_15_min_market(coin).await? {
+        Some(m) => Arc::new(m),
+        None => {
+            outln!("{}", format!("⚠️  No active market found for {}. Will retry in 10 seconds...\n", coin).yellow());
+            return Ok(None);
+        }
+    };
+
+    outln!("{}", format!("✓ Market found: {}\n", market.slug).green());
+
+    // Balances before this window trades (BTW: awaited - the book callback that can trade isn't wired up yet,
+    // so no fill can land before the baseline and get counted in it)
+    if let Some(verifier) = settlement {
+        verifier.record_baselines(&market).await;
+    }
+
+    // Initialize WebSocket if needed (FYI: runs in background task with auto-reconnect)
+    if ws.is_none() {
+        outln!("{}", "Initializing WebSocket connection...\n".bright_black());
+        let ws_client = Arc::new(
+            MarketWebSocket::new(env.clob_ws_url.clone())
+                .with_backups(env.clob_ws_backup_urls.clone(), env.ws_primary_retry_secs)
+                .with_redundancy(env.ws_redundant)
+                .with_health(health.clone()),
+        );
+        
+        // Start WebSocket in background (BTW: auto-reconnects on disconnect)
+        let ws_clone = ws_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = ws_clone.run(true).await {
+                eoutln!("WebSocket error: {}", e);
+            }
+        });
+
+        sleep(Duration::from_secs(1)).await; // Give WS time to connect
+        *ws = Some(ws_client);
+    }
+
+    let ws_ref = ws.as_ref().unwrap();
+
+    // Set up orderbook callback (IMO: this is where the magic happens)
+    let monitor_clone = monitor.clone();
+    let clob_client_clone = clob_client.clone();
+    let recent_opps_clone = recent_opportunities.clone();
+    let queue_clone = execution_queue.clone();
+    let closed_notice_clone = Arc::new(AtomicBool::new(false)); // Per-market "closed" banner flag
+    let budget_clone = session_budget.clone();
+    let ledger_clone = ledger.clone();
+    let market_clone = market.clone();
+    let coin_str = coin.to_string();
+    let opportunity_cfg = Arc::new(OpportunityConfig::new(coin, &market.end_date, env)); // Detection inputs for this window
+    let env_clone = env.clone();
+    let ws_ref_clone = ws_ref.clone();
+    let health_clone = health.clone();
+    let settlement_clone = settlement.clone();
+    let value_risk_clone = value_risk.clone();
+    // Spot model for this window (BTW: only fetched when value trades can actually trade)
+    let spot_clone = if env.value_trades && !env.alert_only {
+        Some(spawn_spot_feed(coin.to_string(), opportunity_cfg.clone(), env.clone()))
+    } else {
+        None
+    };
+    ws_ref.on_book(Arc::new(move |snapshot| {
+        let market = market_clone.clone();
+        let coin = coin_str.clone();
+        let monitor = monitor_clone.clone();
+        let clob_client = clob_client_clone.clone();
+        let recent_opps = recent_opps_clone.clone();
+        let queue = queue_clone.clone();
+        let closed_notice = closed_notice_clone.clone();
+        let budget = budget_clone.clone();
+        let ledger = ledger_clone.clone();
+        let env = env_clone.clone();
+        let ws_ref = ws_ref_clone.clone();
+        let health = health_clone.clone();
+        let cfg = opportunity_cfg.clone();
+        let settlement = settlement_clone.clone();
+        let value_risk = value_risk_clone.clone();
+        let spot = spot_clone.clone();
+
+        tokio::spawn(async move {
+            // Check if market has closed (FYI: stops trading if closed)
+            let now = chrono::Utc::now();
+            let time_until_end = cfg.ms_left(now);
+
+            if time_until_end <= 0 {
+                // Market closed, stop trading (BTW: only show message once)
+                if !closed_notice.swap(true, Ordering::SeqCst) {
+                    let mins = (-time_until_end) / 60000;
+                    log_console(LogLevel::Warn, move || {
+                        outln!(
+                            "{}",
+                            format!(
+                                "\n⏰ MARKET CLOSED - {}\n   Market ended {} minute(s) ago\n   Trading has been stopped. Press Ctrl+C to exit.\n",
+                                coin, mins
+                            )
+                            .red()
+                            .bold()
+                        );
+                    });
+                }
+                return;
+            }
+
+            // Determine if this is UP or DOWN token (AFAIK: we need both for arb calc)
+            let is_up_token = snapshot.asset_id == market.up_token_id;
+            let is_down_token = snapshot.asset_id == market.down_token_id;
+
+            if !is_up_token && !is_down_token {
+                return; // Not our tokens, ignore
+            }
+
+            // Get both orderbooks (FYI: need both UP and DOWN for arbitrage calc)
+            let up_snapshot = ws_ref.get_orderbook(&market.up_token_id);
+            let down_snapshot = ws_ref.get_orderbook(&market.down_token_id);
+
+            if let (Some(up_snap), Some(down_snap)) = (up_snapshot, down_snapshot) {
+                // Prices vs the threshold for this point in the window (BTW: flat unless THRESHOLD_SCHEDULE is set)
+                let price_data = cfg.price_data(&up_snap, &down_snap, now);
+                record_prices(&market.slug, &price_data, time_until_end / 1000); // INFLUX_URL dashboards (sampled)
+
+                // Warn if market is closing soon
+                if !env.alert_only && time_until_end > 0 && time_until_end < 60000 {
+                    let secs = time_until_end / 1000;
+                    let coin = coin.clone();
+                    log_console(LogLevel::Warn, move || {
+                        outln!(
+                            "{}",
+                            format!(
+                                "\n⚠️  MARKET CLOSING SOON - {}\n   {} seconds remaining. Last chance to trade!\n",
+                                coin, secs
+                            )
+                            .yellow()
+                            .bold()
+                        );
+                    });
+                }
+
+                // ALERT_ONLY: report each opportunity once with the books behind it (BTW: nothing below trades)
+                if env.alert_only {
+                    if let Some(plan) = evaluate_opportunity(&up_snap, &down_snap, &cfg, now) {
+                        monitor.lock().await.record_arbitrage(&coin, &plan.prices);
+                        health.record_detection();
+                        let is_new = {
+                            let mut opps = recent_opps.lock().await;
+                            remember_opportunity(&mut opps, plan.opportunity_key.clone())
+                        };
+                        if is_new {
+                            let alert = SpreadAlert::new(&coin, &market.slug, &plan, &up_snap, &down_snap);
+                            publish_alert(&alert, &env).await;
+                        }
+                    }
+                }
+                // Arbitrage detection (IMO: the core logic lives in services::opportunity)
+                else if let Some(plan) = evaluate_opportunity(&up_snap, &down_snap, &cfg, now) {
+                    let mut monitor_guard = monitor.lock().await;
+                    monitor_guard.record_arbitrage(&coin, &plan.prices); // Log detection
+                    health.record_detection();
+
+                    drop(monitor_guard); // Don't hold the history lock while trading
+
+                    // Expected fill probability from past outcomes (BTW: None until FILL_SCORE_MIN_SAMPLES attempts are known)
+                    let detected = BookPair::capture(&up_snap, &down_snap);
+                    let score = if env.min_fill_score > 0.0 {
+                        fill_score(&detected, plan.ms_left, env.fill_score_min_samples)
+                    } else {
+                        None
+                    };
+                    let fillable = score.map_or(true, |s| s >= env.min_fill_score);
+
+                    let spread = plan.prices.spread * 100.0;
+                    let timestamp = now;
+                    let (coin_log, up_ask, down_ask, ask_sum) =
+                        (coin.clone(), plan.prices.up_ask, plan.prices.down_ask, plan.prices.ask_sum);
+                    let score_line = score.map(|s| format!("   Fill score: {:.2}\n", s)).unwrap_or_default();
+                    log_console(LogLevel::Info, move || {
+                        outln!(
+                            "{}",
+                            format!(
+                                "\n⚡ [{}] ARBITRAGE DETECTED - {}\n   UP_ASK: {:.4} + DOWN_ASK: {:.4} = {:.4}\n   Spread: {:.2}%\n{}",
+                                market_time_str(timestamp.timestamp_millis(), "%H:%M:%S"), coin_log, up_ask, down_ask, ask_sum, spread, score_line
+                            )
+                            .green()
+                            .bold()
+                        );
+                    });
+                    if !fillable {
+                        let min_score = env.min_fill_score;
+                        log_console(LogLevel::Debug, move || {
+                            outln!("{}", format!("   Fill score below MIN_FILL_SCORE {:.2} - not trading", min_score).bright_black());
+                        });
+                    }
+
+                    // Size = plan, shrunk to what's left under MAX_PER_MARKET_USD (FYI: counts queued trades too)
+                    let trade_tokens = ledger.lock().await.size_for(&market.slug, plan.tokens, plan.prices.ask_sum);
+                    let planned_spend = trade_tokens * plan.prices.ask_sum;
+                    let within_market_cap = trade_tokens >= configured_min_tokens();
+                    if !within_market_cap {
+                        let slug = market.slug.clone();
+                        log_console(LogLevel::Debug, move || {
+                            outln!("{}", format!("   MAX_PER_MARKET_USD reached for {} - not trading", slug).bright_black());
+                        });
+                    }
+                    let window_cap = ledger.lock().await.window_cap(&market.slug, planned_spend);
+                    if let Some(ref reason) = window_cap {
+                        let reason = reason.clone();
+                        log_console(LogLevel::Debug, move || {
+                            outln!("{}", format!("   {} - not trading", reason).bright_black());
+                        });
+                    }
+                    if is_balance_paused() {
+                        log_console(LogLevel::Debug, move || {
+                            outln!("{}", "   Wallet under MIN_WALLET_BALANCE_USD - not trading".bright_black());
+                        });
+                    }
+                    let within_budget = fillable && within_market_cap && window_cap.is_none() && budget.lock().await.can_trade(planned_spend); // Session caps (BTW: detection keeps running when halted)
+
+                    let client_guard = clob_client.lock().await;
+                    if let Some(ref client) = *client_guard {
+                        drop(client_guard); // Release lock before async ops (BTW: prevents deadlock)
+                        
+                        let mut opps = recent_opps.lock().await;
+                        
+                        // Check if we can trade (FYI: not duplicate, within budget - the plan already checked expiry)
+                        if !opps.contains(&plan.opportunity_key) && within_budget {
+
+                            // Execute trade via the queue (IMO: don't block price updates)
+                            let client_clone = client.clone();
+                            let market_clone = market.clone();
+                            let budget_clone = budget.clone();
+                            let job_prices = plan.prices.clone();
+                            let job_env = env.clone();
+                            let job_cfg = cfg.clone();
+                            let job_ws = ws_ref.clone();
+                            let job_ledger = ledger.clone();
+                            let job_key = plan.opportunity_key.clone();
+                            let job_settlement = settlement.clone();
+                            let journal = JournalEntry {
+                                ms_left: Some(plan.ms_left),
+                                ..JournalEntry::new(&market, "arbitrage", &plan.opportunity_key, detected)
+                            };
+                            
+                            // Session spend is held from here until the job settles (BTW: other queued jobs see it)
+                            let Some(mut budget_hold) = SessionBudget::reserve(&budget, planned_spend).await else {
+                                ledger.lock().await.record_missed(&market.slug, &plan.opportunity_key);
+                                return;
+                            };
+                            // Shared wallet cap when a supervisor runs other engines too (always passes standalone)
+                            let Some(mut exposure) = ExposureHold::reserve(planned_spend) else {
+                                log_console(LogLevel::Debug, || {
+                                    outln!("{}", "   Wallet exposure cap reached - not trading".bright_black());
+                                });
+                                ledger.lock().await.record_missed(&market.slug, &plan.opportunity_key);
+                                return;
+                            };
+                            let reservation = MarketLedger::reserve(&ledger, &market.slug, planned_spend).await;
+                            let submitted = queue.try_submit(&market.slug, async move {
+                                let mut job_prices = job_prices;
+                                let mut trade_tokens = trade_tokens;
+
+                                // Latency guard: don't fire at prices that are too old to trust
+                                let latency_ms = now_ms() - job_prices.timestamp;
+                                if job_env.max_exec_latency_ms > 0 && latency_ms > job_env.max_exec_latency_ms as i64 {
+                                    match recheck_opportunity(&job_ws, &market_clone, &job_cfg) {
+                                        Some(fresh) => {
+                                            let (old_sum, new_sum) = (job_prices.ask_sum, fresh.prices.ask_sum);
+                                            log_console(LogLevel::Info, move || {
+                                                outln!("{}", format!("   Execution delayed {}ms - spread still open ({:.4} -> {:.4}), trading at fresh asks", latency_ms, old_sum, new_sum).yellow());
+                                            });
+                                            trade_tokens = trade_tokens.min(fresh.tokens);
+                                            job_prices = fresh.prices;
+                                        }
+                                        None => {
+                                            log_console(LogLevel::Warn, move || {
+                                                outln!("{}", format!("   Execution delayed {}ms and the spread is gone - trade aborted", latency_ms).yellow());
+                                            });
+                                            job_ledger.lock().await.record_missed(&market_clone.slug, &job_key);
+                                            JournalEntry { executed: BookPair::from_ws(&job_ws, &market_clone), ..journal }.record(&job_env);
+                                            return;
+                                        }
+                                    }
+                                }
+
+                                // Session and window caps again at send time (FYI: a burst may have queued several before the first one filled)
+                                let send_check = match budget_hold.begin(trade_tokens * job_prices.ask_sum).await {
+                                    Ok(()) => job_ledger.lock().await.begin_execution(&market_clone.slug, trade_tokens * job_prices.ask_sum),
+                                    Err(reason) => Err(reason),
+                                };
+                                if let Err(reason) = send_check {
+                                    log_console(LogLevel::Info, move || {
+                                        outln!("{}", format!("   {} - trade dropped", reason).yellow());
+                                    });
+                                    job_ledger.lock().await.record_missed(&market_clone.slug, &job_key);
+                                    return;
+                                }
+
+                                let journal = JournalEntry { executed: BookPair::from_ws(&job_ws, &market_clone), ..journal };
+                                let result = services::arbitrage_executor::execute_arbitrage_trade(
+                                    &client_clone,
+                                    &market_clone.up_token_id,
+                                    &market_clone.down_token_id,
+                                    job_prices.up_ask,
+                                    job_prices.down_ask,
+                                    job_prices.up_bid,
+                                    job_prices.down_bid,
+                                    trade_tokens,
+                                    &job_env,
+                                ).await;
+
+                                match result {
+                                    Ok((ref up_result, ref down_result, _)) => journal.with_results(Some(up_result), Some(down_result)).record(&job_env),
+                                    Err(_) => journal.with_results(None, None).record(&job_env),
+                                }
+                                if let Ok((up_result, down_result, _)) = result {
+                                    exposure.spend(
+                                        [&up_result, &down_result]
+                                            .iter()
+                                            .filter(|r| r.success)
+                                            .map(|r| r.amount)
+                                            .sum(),
+                                    );
+                                    budget_hold.settle(&up_result, &down_result).await;
+                                    reservation.settle(&up_result, &down_result).await;
+                                    if let Some(verifier) = job_settlement {
+                                        verifier.spawn_verify(job_ledger.clone(), market_clone.clone());
+                                    }
+
+                                    // A failed / short leg leaves one-sided tokens (IMO: off unless HEDGE_HOURLY)
+                                    let residual = residual_exposure(&up_result, &down_result);
+                                    if job_env.hedge_hourly && residual.abs() >= configured_min_tokens() {
+                                        let worst_case = residual.abs() * job_env.hedge_ratio * job_env.hedge_max_price;
+                                        if budget_clone.lock().await.can_trade(worst_case) {
+                                            if let Some(fill) = hedge_residual(&client_clone, &market_clone, residual, &job_env).await {
+                                                budget_clone.lock().await.record_hedge(fill.amount_usd);
+                                                job_ledger.lock().await.record_hedge(&market_clone.slug, fill);
+                                            }
+                                        }
+                                    }
+                                }
+                            }).await;
+
+                            // Only remember the opportunity once it's actually queued (BTW: full queue = retry on next tick)
+                            if submitted {
+                                remember_opportunity(&mut opps, plan.opportunity_key.clone());
+                            } else {
+                                ledger.lock().await.record_missed(&market.slug, &plan.opportunity_key);
+                            }
+                        } else if !opps.contains(&plan.opportunity_key) {
+                            // Over MAX_PER_MARKET_USD / session caps, or under MIN_FILL_SCORE (BTW: counted in the market result file)
+                            ledger.lock().await.record_missed(&market.slug, &plan.opportunity_key);
+                        }
+                    }
+                }
+
+                // Reverse arbitrage: bids sum above 1 + fee and we hold pairs (IMO: off unless REVERSE_ARB)
+                if env.reverse_arb && !env.alert_only {
+                    let held_pairs = ledger.lock().await.sellable_pairs(&market.slug);
+                    if let Some(plan) = evaluate_reverse_opportunity(&up_snap, &down_snap, &cfg, env.reverse_arb_fee, held_pairs, now) {
+                        health.record_detection();
+                        queue_reverse_arbitrage(plan, BookPair::capture(&up_snap, &down_snap), market.clone(), cfg.clone(), ws_ref.clone(), &clob_client, &recent_opps, &queue, &budget, &ledger, &settlement, &env).await;
+                    }
+                }
+
+                // Value trades: one side priced well under the spot model (IMO: off unless VALUE_TRADES)
+                if let Some(spot) = spot {
+                    let model = spot.lock().ok().and_then(|m| *m);
+                    if let Some(plan) = model.and_then(|model| evaluate_value_opportunity(&up_snap, &down_snap, &cfg, &model, &env, now)) {
+                        health.record_detection();
+                        queue_value_trade(plan, BookPair::capture(&up_snap, &down_snap), market.clone(), cfg.clone(), ws_ref.clone(), spot, &clob_client, &recent_opps, &queue, &budget, &ledger, &value_risk, &settlement, &env).await;
+                    }
+                }
+
+                let mut monitor_guard = monitor.lock().await;
+                monitor_guard.add_to_history(&coin, price_data.clone(), &env);
+                let arb_history = monitor_guard.get_arbitrage_history(&coin);
+                drop(monitor_guard);
+                
+                // Display updated view (BTW: rendered on the logging thread, skipped below info and in ALERT_ONLY)
+                if !env.alert_only {
+                    log_console(LogLevel::Info, move || {
+                        display_coin_details(&coin, &price_data, &market, &arb_history);
+                    });
+                }
+            }
+        }));
+    }));
+
+    // Subscribe to both tokens
+    ws_ref.subscribe(vec![market.up_token_id.clone(), market.down_token_id.clone()]).await?;
+
+    sleep(Duration::from_secs(2)).await;
+
+    Ok(Some(market))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remembers_keys_and_evicts_the_oldest() {
+        let mut opps = RecentOpportunities::default();
+        for i in 0..RECENT_OPPORTUNITIES {
+            assert!(remember_opportunity(&mut opps, format!("opp-{}", i)));
+        }
+        assert!(!remember_opportunity(&mut opps, "opp-0".to_string()));
+
+        assert!(remember_opportunity(&mut opps, "newest".to_string()));
+        assert!(!opps.contains("opp-0"));
+        assert!(opps.contains("opp-1"));
+        assert!(opps.contains("newest"));
+        assert_eq!(opps.keys.len(), RECENT_OPPORTUNITIES);
+        assert_eq!(opps.order.len(), RECENT_OPPORTUNITIES);
+    }
+}
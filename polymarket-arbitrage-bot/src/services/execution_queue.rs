@@ -0,0 +1,110 @@
+use crate::config::Env;
+use crate::utils::clock::LatencyTimer;
+use crate::utils::resources::{gauge_add, gauge_sub, QUEUED_TRADES};
+use colored::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::{Mutex, Semaphore};
+
+// Bounded trade queue (FYI: replaces the old single is_executing_trade bool)
+// - global semaphore caps how many trades run at once (MAX_CONCURRENT_TRADES)
+// - per-market mutex serializes trades on the same market (no overlapping legs)
+// - pending counts bound how much can pile up, total and per market
+pub struct ExecutionQueue {
+    global: Arc<Semaphore>,
+    market_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    pending: Mutex<HashMap<String, usize>>, // Queued + running jobs per market
+    max_queue: usize,
+    max_per_market: usize,
+    max_wait_ms: u64,
+}
+
+impl ExecutionQueue {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            global: Arc::new(Semaphore::new(env.max_concurrent_trades.max(1))),
+            market_locks: Mutex::new(HashMap::new()),
+            pending: Mutex::new(HashMap::new()),
+            max_queue: env.execution_queue_size.max(1),
+            max_per_market: env.execution_queue_per_market.max(1),
+            max_wait_ms: env.execution_queue_max_wait_ms,
+        }
+    }
+
+    // Total jobs queued or running (BTW: handy for the UI / logs)
+    pub async fn len(&self) -> usize {
+        self.pending.lock().await.values().sum()
+    }
+
+    pub async fn is_busy(&self, market_key: &str) -> bool {
+        self.pending.lock().await.get(market_key).copied().unwrap_or(0) > 0
+    }
+
+    // Queue a trade for a market (returns false if the queue is full for it)
+    pub async fn try_submit<F>(self: &Arc<Self>, market_key: &str, job: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        {
+            let mut pending = self.pending.lock().await;
+            let total: usize = pending.values().sum();
+            let for_market = pending.get(market_key).copied().unwrap_or(0);
+            if total >= self.max_queue || for_market >= self.max_per_market {
+                return false;
+            }
+            *pending.entry(market_key.to_string()).or_insert(0) += 1;
+            gauge_add(&QUEUED_TRADES, 1);
+        }
+
+        let queue = self.clone();
+        let key = market_key.to_string();
+        let enqueued_at = LatencyTimer::start();
+
+        tokio::spawn(async move {
+            let market_lock = queue.market_lock(&key).await;
+            let _market_guard = market_lock.lock().await; // One trade per market at a time
+            let permit = queue.global.clone().acquire_owned().await;
+
+            // Drop stale jobs (AFAIK: prices they were built on are long gone)
+            let waited_ms = enqueued_at.elapsed_ms();
+            if queue.max_wait_ms > 0 && waited_ms > queue.max_wait_ms {
+                outln!(
+                    "{}",
+                    format!(
+                        "⏭️  Dropped queued trade for {} (waited {}ms > {}ms)",
+                        key, waited_ms, queue.max_wait_ms
+                    )
+                    .yellow()
+                );
+            } else if permit.is_ok() {
+                job.await;
+            }
+
+            queue.finish(&key).await;
+        });
+
+        true
+    }
+
+    async fn market_lock(&self, market_key: &str) -> Arc<Mutex<()>> {
+        self.market_locks
+            .lock()
+            .await
+            .entry(market_key.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+
+    async fn finish(&self, market_key: &str) {
+        let mut pending = self.pending.lock().await;
+        if let Some(count) = pending.get_mut(market_key) {
+            *count = count.saturating_sub(1);
+            gauge_sub(&QUEUED_TRADES, 1);
+            if *count == 0 {
+                pending.remove(market_key);
+                self.market_locks.lock().await.remove(market_key); // Clean up closed markets
+            }
+        }
+    }
+}
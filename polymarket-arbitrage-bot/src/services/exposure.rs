@@ -0,0 +1,59 @@
+use std::sync::{Arc, RwLock};
+
+// Capital cap shared with other engines on the same wallet (FYI: set by the supervisor binary)
+pub use polymarket_gates::ExposureGate;
+
+// Name this engine books its exposure and CLOB calls under
+pub const ARB_STRATEGY: &str = "arbitrage";
+
+lazy_static::lazy_static! {
+    static ref EXPOSURE_GATE: RwLock<Option<Arc<dyn ExposureGate>>> = RwLock::new(None);
+}
+
+// Install the shared gate (BTW: standalone runs never call this, so every check passes)
+pub fn set_exposure_gate(gate: Arc<dyn ExposureGate>) {
+    if let Ok(mut slot) = EXPOSURE_GATE.write() {
+        *slot = Some(gate);
+    }
+}
+
+fn gate() -> Option<Arc<dyn ExposureGate>> {
+    EXPOSURE_GATE.read().ok().and_then(|g| g.clone())
+}
+
+pub fn release_exposure(usd: f64) {
+    if let Some(gate) = gate() {
+        if usd > 0.0 {
+            gate.release(ARB_STRATEGY, usd);
+        }
+    }
+}
+
+// Exposure held for one queued trade (same shape as the ledger Reservation - unspent part released on drop)
+pub struct ExposureHold {
+    reserved: f64,
+    spent: f64,
+}
+
+impl ExposureHold {
+    // None = the shared cap is reached
+    pub fn reserve(usd: f64) -> Option<Self> {
+        if let Some(gate) = gate() {
+            if !gate.try_reserve(ARB_STRATEGY, usd) {
+                return None;
+            }
+        }
+        Some(Self { reserved: usd, spent: 0.0 })
+    }
+
+    // Book fills - they stay as exposure until the market window closes
+    pub fn spend(&mut self, usd: f64) {
+        self.spent += usd;
+    }
+}
+
+impl Drop for ExposureHold {
+    fn drop(&mut self) {
+        release_exposure(self.reserved - self.spent);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::services::trade_journal::{read_journal, BookPair, JournalEntry};
+use std::path::Path;
+use std::sync::Mutex;
+
+// Bucket edges per feature (FYI: 4 buckets each, the last one open-ended)
+const SIZE_EDGES: [f64; 3] = [10.0, 50.0, 200.0]; // Thinner side's top-ask size, tokens
+const SPREAD_EDGES: [f64; 3] = [0.01, 0.02, 0.04]; // 1 - ask sum
+const SECS_LEFT_EDGES: [f64; 3] = [60.0, 300.0, 900.0]; // Time left in the window at detection
+
+// Pseudo-trades pulling a thin bucket toward the overall fill rate (IMO: 3 lucky fills shouldn't mean 100%)
+const PRIOR_WEIGHT: f64 = 4.0;
+
+// What a detected spread looked like (BTW: same inputs live and when replaying journal.jsonl)
+#[derive(Debug, Clone, Copy)]
+pub struct FillFeatures {
+    pub top_size: f64,
+    pub spread: f64,
+    pub secs_left: Option<f64>, // None for journal lines written before msLeft was recorded
+}
+
+impl FillFeatures {
+    // None = a side had no asks (nothing to score)
+    pub fn from_books(books: &BookPair, ms_left: Option<i64>) -> Option<Self> {
+        let up = books.up.asks.first()?;
+        let down = books.down.asks.first()?;
+        Some(Self {
+            top_size: up[1].min(down[1]),
+            spread: 1.0 - up[0] - down[0],
+            secs_left: ms_left.map(|ms| ms as f64 / 1000.0),
+        })
+    }
+}
+
+fn bucket(value: f64, edges: &[f64; 3]) -> usize {
+    edges.iter().take_while(|edge| value >= **edge).count()
+}
+
+fn logit(p: f64) -> f64 {
+    (p / (1.0 - p)).ln()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    filled: u32,
+    total: u32,
+}
+
+impl Tally {
+    fn add(&mut self, filled: bool) {
+        self.total += 1;
+        if filled {
+            self.filled += 1;
+        }
+    }
+
+    // Fill rate shrunk toward `base` (AFAIK: empty bucket = exactly the base rate)
+    fn rate(&self, base: f64) -> f64 {
+        (self.filled as f64 + PRIOR_WEIGHT * base) / (self.total as f64 + PRIOR_WEIGHT)
+    }
+}
+
+// Fill rates per feature bucket, combined naive-Bayes style (FYI: each feature shifts the base odds)
+#[derive(Debug, Clone, Default)]
+pub struct FillModel {
+    overall: Tally,
+    size: [Tally; 4],
+    spread: [Tally; 4],
+    secs_left: [Tally; 4],
+}
+
+impl FillModel {
+    pub fn observe(&mut self, features: &FillFeatures, filled: bool) {
+        self.overall.add(filled);
+        self.size[bucket(features.top_size, &SIZE_EDGES)].add(filled);
+        self.spread[bucket(features.spread, &SPREAD_EDGES)].add(filled);
+        if let Some(secs) = features.secs_left {
+            self.secs_left[bucket(secs, &SECS_LEFT_EDGES)].add(filled);
+        }
+    }
+
+    // Arbitrage attempts seen so far
+    pub fn samples(&self) -> u32 {
+        self.overall.total
+    }
+
+    // Expected probability both legs fill, 0..1
+    pub fn score(&self, features: &FillFeatures) -> f64 {
+        let base = (self.overall.filled as f64 + 1.0) / (self.overall.total as f64 + 2.0);
+        let mut odds = logit(base);
+        let mut shift = |tally: &Tally| odds += logit(tally.rate(base)) - logit(base);
+        shift(&self.size[bucket(features.top_size, &SIZE_EDGES)]);
+        shift(&self.spread[bucket(features.spread, &SPREAD_EDGES)]);
+        if let Some(secs) = features.secs_left {
+            shift(&self.secs_left[bucket(secs, &SECS_LEFT_EDGES)]);
+        }
+        1.0 / (1.0 + (-odds).exp())
+    }
+}
+
+// Only two-leg arbitrage trains the model (BTW: "partial" and "aborted" count as misses - the spread wasn't really there)
+fn training_sample(entry: &JournalEntry) -> Option<(FillFeatures, bool)> {
+    if entry.kind != "arbitrage" {
+        return None;
+    }
+    let features = FillFeatures::from_books(&entry.detected, entry.ms_left)?;
+    Some((features, entry.outcome == "filled"))
+}
+
+struct FillHistory {
+    model: FillModel,
+    loaded: bool, // journal.jsonl replayed already (IMO: several coin loops share one model)
+}
+
+lazy_static::lazy_static! {
+    static ref FILL_HISTORY: Mutex<FillHistory> = Mutex::new(FillHistory { model: FillModel::default(), loaded: false });
+}
+
+// Seed the model from past journal lines, once per process (FYI: a missing file just starts empty)
+pub fn load_fill_history(path: &Path) -> u32 {
+    let Ok(mut history) = FILL_HISTORY.lock() else {
+        return 0;
+    };
+    if !history.loaded {
+        history.loaded = true;
+        for (features, filled) in read_journal(path).unwrap_or_default().iter().filter_map(training_sample) {
+            history.model.observe(&features, filled);
+        }
+    }
+    history.model.samples()
+}
+
+// Learn from a trade as it's journaled (BTW: runs with TRADE_JOURNAL off too, the model just forgets on restart)
+pub fn record_fill_outcome(entry: &JournalEntry) {
+    if let Some((features, filled)) = training_sample(entry) {
+        if let Ok(mut history) = FILL_HISTORY.lock() {
+            history.model.observe(&features, filled);
+        }
+    }
+}
+
+// Score for a live detection (None = fewer than `min_samples` attempts seen, too early to judge)
+pub fn fill_score(books: &BookPair, ms_left: i64, min_samples: u32) -> Option<f64> {
+    let features = FillFeatures::from_books(books, Some(ms_left))?;
+    let history = FILL_HISTORY.lock().ok()?;
+    if history.model.samples() < min_samples {
+        return None;
+    }
+    Some(history.model.score(&features))
+}
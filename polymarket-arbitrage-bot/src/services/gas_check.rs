@@ -0,0 +1,137 @@
+use crate::config::Env;
+use crate::utils::logger::{log_console, log_error, LogLevel};
+use anyhow::{anyhow, Result};
+use colored::*;
+use ethers::prelude::*;
+use serde_json::Value;
+
+// Gas token price on the spot API (FYI: MATIC was renamed POL in 2024, Binance lists POLUSDT)
+const GAS_TOKEN_SYMBOL: &str = "POLUSDT";
+
+// Polygon gas cost of one on-chain action, in USD
+#[derive(Debug, Clone, Copy)]
+pub struct GasQuote {
+    pub gas_units: u64,
+    pub gas_price_gwei: f64,
+    pub pol_usd: f64,
+    pub cost_usd: f64,
+}
+
+// Decides whether a neg-risk conversion (and the approval it may need) is worth its gas
+// - gas price from GAS_ORACLE_URL (Polygon gas station v2), or the RPC's eth_gasPrice when unset / down
+// - POL price from GAS_PRICE_API
+// - go only if benefit >= GAS_MIN_BENEFIT_RATIO x cost and gas is under GAS_MAX_GWEI
+pub struct GasGuard {
+    enabled: bool,
+    oracle_url: Option<String>,
+    spot_api: String,
+    min_benefit_ratio: f64,
+    max_gwei: Option<f64>,
+    provider: Provider<Http>,
+}
+
+impl GasGuard {
+    pub fn new(env: &Env) -> Result<Self> {
+        Ok(Self {
+            enabled: env.gas_check,
+            oracle_url: env.gas_oracle_url.clone(),
+            spot_api: env.gas_price_api.clone(),
+            min_benefit_ratio: env.gas_min_benefit_ratio,
+            max_gwei: env.gas_max_gwei,
+            provider: Provider::<Http>::try_from(&env.rpc_url)?,
+        })
+    }
+
+    // Gas station "standard" max fee (gwei)
+    async fn oracle_gwei(&self, url: &str) -> Result<f64> {
+        let body: Value = reqwest::Client::new()
+            .get(url)
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        body.get("standard")
+            .and_then(|s| s.get("maxFee"))
+            .and_then(|v| v.as_f64())
+            .ok_or_else(|| anyhow!("no standard.maxFee in gas oracle response"))
+    }
+
+    async fn gas_price_gwei(&self) -> Result<f64> {
+        if let Some(url) = self.oracle_url.as_deref() {
+            match self.oracle_gwei(url).await {
+                Ok(gwei) => return Ok(gwei),
+                Err(e) => log_error(&format!("Gas oracle failed, using eth_gasPrice: {}", e), Some("gas")),
+            }
+        }
+        let wei = self.provider.get_gas_price().await?;
+        Ok(wei.as_u128() as f64 / 1e9)
+    }
+
+    async fn pol_usd(&self) -> Result<f64> {
+        let url = format!("{}/api/v3/ticker/price?symbol={}", self.spot_api.trim_end_matches('/'), GAS_TOKEN_SYMBOL);
+        let body: Value = reqwest::Client::new()
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        body.get("price")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|p| *p > 0.0)
+            .ok_or_else(|| anyhow!("no {} price", GAS_TOKEN_SYMBOL))
+    }
+
+    pub async fn quote(&self, gas_units: u64) -> Result<GasQuote> {
+        let gas_price_gwei = self.gas_price_gwei().await?;
+        let pol_usd = self.pol_usd().await?;
+        Ok(GasQuote {
+            gas_units,
+            gas_price_gwei,
+            pol_usd,
+            cost_usd: gas_units as f64 * gas_price_gwei * 1e-9 * pol_usd,
+        })
+    }
+
+    // true = send it (BTW: an unpriceable action still goes out - the check never blocks on its own failure)
+    pub async fn worth_it(&self, action: &str, gas_units: u64, benefit_usd: f64) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let quote = match self.quote(gas_units).await {
+            Ok(q) => q,
+            Err(e) => {
+                log_error(&format!("Gas check for {} skipped, could not price gas: {}", action, e), Some("gas"));
+                return true;
+            }
+        };
+        let too_pricey = self.max_gwei.is_some_and(|max| quote.gas_price_gwei > max);
+        let go = !too_pricey && benefit_usd >= quote.cost_usd * self.min_benefit_ratio;
+        let msg = format!(
+            "Gas check {}: {} - benefit ${:.4} vs gas ${:.4} ({} gas @ {:.1} gwei, POL ${:.3}){}",
+            if go { "ok" } else { "SKIP" },
+            action,
+            benefit_usd,
+            quote.cost_usd,
+            quote.gas_units,
+            quote.gas_price_gwei,
+            quote.pol_usd,
+            if too_pricey { " - above GAS_MAX_GWEI" } else { "" }
+        );
+        if go {
+            log_console(LogLevel::Info, move || {
+                outln!("{}", format!("   ⛽ {}", msg).bright_black());
+            });
+        } else {
+            log_error(&msg, Some("gas"));
+            log_console(LogLevel::Warn, move || {
+                outln!("{}", format!("⛽ {}", msg).yellow());
+            });
+        }
+        go
+    }
+}
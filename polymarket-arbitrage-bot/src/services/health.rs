@@ -0,0 +1,207 @@
+use crate::config::Env;
+use crate::utils::clock::now_ms;
+use crate::services::order_queue::OrderQueue;
+use crate::services::wallet_balance::wallet_balance_json;
+use crate::services::websocket_client::ws_schema_json;
+use crate::utils::resources::ResourceSnapshot;
+use colored::*;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time::{sleep, Duration};
+
+// Liveness signals for external supervisors (FYI: systemd / Docker healthcheck)
+pub struct HealthState {
+    started_at_ms: i64,
+    ws_connected: AtomicBool,
+    last_book_ms: Mutex<HashMap<String, i64>>, // Per token (BTW: std Mutex, held for a single insert)
+    last_detection_ms: AtomicI64, // 0 = never
+    max_book_age_ms: i64,
+    order_queue: Mutex<Weak<OrderQueue>>, // The trading client's queue once there is one (BTW: weak, health never keeps a client alive)
+}
+
+impl HealthState {
+    pub fn new(max_book_age_secs: u64) -> Self {
+        Self {
+            started_at_ms: now_ms(),
+            ws_connected: AtomicBool::new(false),
+            last_book_ms: Mutex::new(HashMap::new()),
+            last_detection_ms: AtomicI64::new(0),
+            max_book_age_ms: max_book_age_secs as i64 * 1000,
+            order_queue: Mutex::new(Weak::new()),
+        }
+    }
+
+    pub fn set_order_queue(&self, queue: &Arc<OrderQueue>) {
+        if let Ok(mut current) = self.order_queue.lock() {
+            *current = Arc::downgrade(queue);
+        }
+    }
+
+    pub fn set_ws_connected(&self, connected: bool) {
+        self.ws_connected.store(connected, Ordering::Relaxed);
+    }
+
+    pub fn record_book(&self, asset_id: &str) {
+        if let Ok(mut books) = self.last_book_ms.lock() {
+            books.insert(asset_id.to_string(), now_ms());
+        }
+    }
+
+    pub fn record_detection(&self) {
+        self.last_detection_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    // Forget tokens from a closed market (AFAIK: they stop updating and would look stale)
+    pub fn retain_tokens(&self, token_ids: &[String]) {
+        if let Ok(mut books) = self.last_book_ms.lock() {
+            books.retain(|k, _| token_ids.contains(k));
+        }
+    }
+
+    // Healthy = WS connected and some book updated within max age
+    // (IMO: grace period after start so a fresh process isn't killed before its first book)
+    pub fn status(&self) -> (bool, serde_json::Value) {
+        let now = now_ms();
+        let books = self.last_book_ms.lock().map(|b| b.clone()).unwrap_or_default();
+        let ws_connected = self.ws_connected.load(Ordering::Relaxed);
+        let freshest_age = books.values().map(|t| now - t).min();
+        let within_grace = now - self.started_at_ms < self.max_book_age_ms;
+        let books_fresh = match freshest_age {
+            Some(age) => age <= self.max_book_age_ms,
+            None => within_grace,
+        };
+        let healthy = ws_connected && books_fresh || within_grace && books.is_empty();
+
+        let last_detection = self.last_detection_ms.load(Ordering::Relaxed);
+        let order_queue = self.order_queue.lock().ok().and_then(|q| q.upgrade());
+        let body = json!({
+            "healthy": healthy,
+            "ws_connected": ws_connected,
+            "uptime_secs": (now - self.started_at_ms) / 1000,
+            "book_age_ms": books
+                .iter()
+                .map(|(token, t)| (token.clone(), json!(now - t)))
+                .collect::<serde_json::Map<_, _>>(),
+            "max_book_age_ms": self.max_book_age_ms,
+            "last_detection_age_ms": if last_detection > 0 { json!(now - last_detection) } else { json!(null) },
+            "resources": ResourceSnapshot::take().to_json(),
+            "order_queue": order_queue.map(|q| q.stats_json()).unwrap_or(serde_json::Value::Null),
+            "ws_schema": ws_schema_json(),
+            "wallet_balance": wallet_balance_json(),
+        });
+        (healthy, body)
+    }
+}
+
+// Rewrite the heartbeat file while healthy (BTW: supervisors restart when its mtime goes stale)
+pub async fn run_heartbeat(state: Arc<HealthState>, path: String, interval_secs: u64) {
+    loop {
+        let (healthy, body) = state.status();
+        if healthy {
+            if let Err(e) = tokio::fs::write(&path, body.to_string()).await {
+                eoutln!("{}", format!("Heartbeat write failed ({}): {}", path, e).red());
+            }
+        }
+        sleep(Duration::from_secs(interval_secs.max(1))).await;
+    }
+}
+
+// Minimal GET /healthz server (FYI: 200 when healthy, 503 otherwise, JSON body either way)
+pub async fn serve_health(state: Arc<HealthState>, port: u16) {
+    let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(l) => l,
+        Err(e) => {
+            eoutln!("{}", format!("Health endpoint disabled: can't bind port {}: {}", port, e).red());
+            return;
+        }
+    };
+    outln!("{}", format!("Health endpoint: http://0.0.0.0:{}/healthz\n", port).bright_black());
+
+    loop {
+        let Ok((mut socket, _)) = listener.accept().await else {
+            continue;
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+            let (status, body) = if path == "/healthz" || path == "/health" {
+                let (healthy, body) = state.status();
+                (if healthy { "200 OK" } else { "503 Service Unavailable" }, body.to_string())
+            } else {
+                ("404 Not Found", json!({ "error": "not found" }).to_string())
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+// Gauges past their RESOURCE_MAX_* limit (BTW: empty = all fine)
+fn resource_breaches(snapshot: &ResourceSnapshot, env: &Env) -> Vec<(&'static str, String)> {
+    let mut out = Vec::new();
+    if let Some(mb) = snapshot.rss_mb.filter(|mb| *mb > env.resource_max_rss_mb) {
+        out.push(("rss", format!("RSS {:.0}MB > {:.0}MB", mb, env.resource_max_rss_mb)));
+    }
+    if let Some(tasks) = snapshot.tasks.filter(|t| *t > env.resource_max_tasks) {
+        out.push(("tasks", format!("{} tasks alive > {}", tasks, env.resource_max_tasks)));
+    }
+    if snapshot.log_backlog > env.resource_max_backlog {
+        out.push(("log_backlog", format!("log backlog {} > {}", snapshot.log_backlog, env.resource_max_backlog)));
+    }
+    if snapshot.queued_trades > env.resource_max_backlog {
+        out.push(("queued_trades", format!("trade queue {} > {}", snapshot.queued_trades, env.resource_max_backlog)));
+    }
+    let books = snapshot.orderbooks.max(snapshot.dedup_entries);
+    if books > env.resource_max_orderbooks {
+        out.push(("orderbooks", format!("{} cached books > {} (cache not pruned?)", books, env.resource_max_orderbooks)));
+    }
+    out
+}
+
+// Periodic self-check (IMO: catch leaks / backlogs while they're still warnings, not missed trades)
+// FYI: warns when a gauge crosses its limit and once more when it recovers, not on every sample
+pub async fn run_resource_monitor(env: Env) {
+    let mut breached: Vec<&'static str> = Vec::new();
+    loop {
+        sleep(Duration::from_secs(env.resource_check_secs.max(1))).await;
+        let snapshot = ResourceSnapshot::take();
+        let current = resource_breaches(&snapshot, &env);
+        for (name, msg) in &current {
+            if !breached.contains(name) {
+                eoutln!("{}", format!("⚠️  Resource limit: {}", msg).yellow().bold());
+            }
+        }
+        for name in &breached {
+            if !current.iter().any(|(n, _)| n == name) {
+                outln!("{}", format!("Resource back under its limit: {}", name).bright_black());
+            }
+        }
+        breached = current.into_iter().map(|(name, _)| name).collect();
+    }
+}
+
+// Start whatever the env enables (BTW: endpoint + heartbeat off by default, resource checks on)
+pub fn start_health_reporting(state: &Arc<HealthState>, env: &Env) {
+    if let Some(port) = env.health_port {
+        tokio::spawn(serve_health(state.clone(), port));
+    }
+    if let Some(ref path) = env.heartbeat_file {
+        tokio::spawn(run_heartbeat(state.clone(), path.clone(), env.heartbeat_interval_secs));
+    }
+    if env.resource_check_secs > 0 {
+        tokio::spawn(run_resource_monitor(env.clone()));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::config::Env;
+use crate::services::arbitrage_executor::{execute_buy_order, ArbitrageOrderResult};
+use crate::services::create_clob_client::{ClobClient, OrderType};
+use crate::services::dutch_book::fetch_best_asks;
+use crate::services::market_discovery::{find_market, CoinMarket, MarketWindow};
+use crate::services::market_precision::configured_min_tokens;
+use crate::services::order_queue::SubmitPriority;
+use crate::utils::clock::now_ms;
+use crate::utils::logger::{log_console, log_error, LogLevel};
+use colored::*;
+use serde::Serialize;
+
+// One hedge bought in the hourly market (FYI: kept on the 15m market's result file)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HedgeFill {
+    pub timestamp: i64,
+    pub hedge_market: String, // 1h market slug
+    pub side: String, // Hourly outcome bought (UP / DOWN)
+    pub tokens: f64,
+    pub amount_usd: f64,
+    pub price: f64,
+    pub residual_tokens: f64, // 15m imbalance it covers (+ = extra UP, - = extra DOWN)
+}
+
+// Tokens one trade left unpaired: positive = extra UP, negative = extra DOWN (BTW: failed legs count as 0)
+pub fn residual_exposure(up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) -> f64 {
+    let filled = |r: &ArbitrageOrderResult| if r.success { r.tokens_bought.unwrap_or(0.0) } else { 0.0 };
+    filled(up) - filled(down)
+}
+
+fn parse_end(end_date: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(end_date).ok().map(|d| d.with_timezone(&chrono::Utc))
+}
+
+fn skip(coin: &str, why: String) -> Option<HedgeFill> {
+    let coin = coin.to_string();
+    log_console(LogLevel::Warn, move || {
+        outln!("{}", format!("   {} 1h hedge skipped: {}", coin, why).yellow());
+    });
+    None
+}
+
+// Buy the opposite outcome of the coin's hourly market against leftover 15m exposure
+// IMO: an imperfect hedge - the hour can close the other way than its 15m slice - but it caps the damage of a lone leg
+pub async fn hedge_residual(client: &ClobClient, market: &CoinMarket, residual: f64, env: &Env) -> Option<HedgeFill> {
+    if residual.abs() < configured_min_tokens() {
+        return None;
+    }
+    let hourly = match find_market(&market.coin, MarketWindow::Hourly).await {
+        Ok(Some(m)) => m,
+        Ok(None) => return skip(&market.coin, "no active 1h market".to_string()),
+        Err(e) => return skip(&market.coin, format!("1h discovery failed: {}", e)),
+    };
+    // The hour has to cover the 15m window, else it resolves on a different move
+    if let (Some(hour_end), Some(window_end)) = (parse_end(&hourly.end_date), parse_end(&market.end_date)) {
+        if hour_end < window_end {
+            return skip(&market.coin, format!("{} ends before {}", hourly.slug, market.slug));
+        }
+    }
+
+    // Extra UP = long the move up, so buy the hour's DOWN (and the reverse)
+    let (side, token_id) = if residual > 0.0 {
+        ("DOWN", hourly.down_token_id.clone())
+    } else {
+        ("UP", hourly.up_token_id.clone())
+    };
+    let asks = match fetch_best_asks(&env.clob_http_url, std::slice::from_ref(&token_id)).await {
+        Ok(asks) => asks,
+        Err(e) => return skip(&market.coin, format!("1h book unavailable: {}", e)),
+    };
+    let Some(&(ask, depth)) = asks.get(&token_id) else {
+        return skip(&market.coin, format!("no asks on the 1h {}", side));
+    };
+    if ask > env.hedge_max_price {
+        return skip(&market.coin, format!("1h {} ask {:.4} above HEDGE_MAX_PRICE {:.2}", side, ask, env.hedge_max_price));
+    }
+
+    let mut tokens = (residual.abs() * env.hedge_ratio).min(depth);
+    if let Some(max_usd) = env.hedge_max_usd {
+        tokens = tokens.min(max_usd / ask);
+    }
+    if tokens < configured_min_tokens() {
+        return skip(&market.coin, format!("{:.2} tokens is below the order minimum", tokens));
+    }
+
+    let (coin, slug, residual_log) = (market.coin.clone(), hourly.slug.clone(), residual);
+    log_console(LogLevel::Info, move || {
+        outln!(
+            "{}",
+            format!(
+                "\n🛡  [{}] Hedging {:+.2} unpaired 15m tokens: buying {:.2} {} in {} at ${:.4}\n",
+                coin, residual_log, tokens, side, slug, ask
+            )
+            .cyan()
+            .bold()
+        );
+    });
+
+    let leg = format!("HEDGE-{}", side);
+    let result = execute_buy_order(client, &token_id, &leg, tokens * ask, ask, OrderType::FAK, SubmitPriority::Recovery).await;
+    if !result.success {
+        log_error(
+            &format!("1h hedge failed for {}: {}", market.slug, result.error.as_deref().unwrap_or("Unknown")),
+            Some("hedgeResidual"),
+        );
+        return None;
+    }
+    Some(HedgeFill {
+        timestamp: now_ms(),
+        hedge_market: hourly.slug,
+        side: side.to_string(),
+        tokens: result.tokens_bought.unwrap_or(0.0),
+        amount_usd: result.amount,
+        price: result.price,
+        residual_tokens: residual,
+    })
+}
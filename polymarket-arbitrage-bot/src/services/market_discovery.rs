@@ -1,7 +1,15 @@
 use crate::config::{coin_slug, GAMMA_API_HOST};
+use crate::services::market_precision::{set_min_size, set_tick_size};
+use crate::utils::clock::market_time;
+use crate::utils::logger::{log_console, LogLevel};
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Timelike, Utc};
+use colored::*;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 // Market data structure (FYI: contains everything we need for trading)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,79 +23,291 @@ pub struct CoinMarket {
     pub accepting_orders: bool, // Whether market is still open
 }
 
-// Raw Gamma API response (AFAIK: matches Polymarket's API format)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct GammaMarket {
-    slug: String,
-    question: String,
-    end_date: String,
-    accepting_orders: bool,
-    #[serde(rename = "clobTokenIds")]
-    clob_token_ids: serde_json::Value, // Can be string or array
-    outcomes: serde_json::Value, // Can be string or array
+// Slug templates tried per window, in order (FYI: {prefix} = coin_slug(), {coin} = ticker, {name} = full coin name)
+// BTW: Polymarket has renamed these before - the first one is today's, the rest are known/likely variants
+const SLUG_PATTERNS: &[&str] = &[
+    "{prefix}-{ts}",
+    "{coin}-up-or-down-15m-{ts}",
+    "{name}-updown-15m-{ts}",
+    "{name}-up-or-down-15m-{ts}",
+];
+
+// Hourly windows are named by their ET start (e.g. "bitcoin-up-or-down-october-15-3pm-et"), newer ones may follow the 15m style
+const HOURLY_SLUG_PATTERNS: &[&str] = &[
+    "{name}-up-or-down-{et}-et",
+    "{coin}-updown-1h-{ts}",
+];
+
+// Which up/down series to look for (FYI: the engine trades 15m, hedging looks at the hour around it)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketWindow {
+    FifteenMin,
+    Hourly,
 }
 
-// Fetch market data from Gamma API (BTW: 10s timeout to avoid hanging)
-async fn get_market_by_slug(slug: &str) -> Result<Option<GammaMarket>> {
-    let url = format!("{}/markets/slug/{}", GAMMA_API_HOST, slug);
-    let client = reqwest::Client::new();
-    
-    match client.get(&url).timeout(std::time::Duration::from_secs(10)).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                Ok(response.json().await.ok())
-            } else {
-                Ok(None)
+impl MarketWindow {
+    pub fn secs(&self) -> i64 {
+        match self {
+            MarketWindow::FifteenMin => 900,
+            MarketWindow::Hourly => 3600,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarketWindow::FifteenMin => "15m",
+            MarketWindow::Hourly => "1h",
+        }
+    }
+
+    // Start of the window containing `now` (BTW: 15m windows and ET hours both start on UTC quarter-hours)
+    pub fn start_ts(&self, now: DateTime<Utc>) -> i64 {
+        let ts = now.timestamp();
+        match self {
+            MarketWindow::FifteenMin => ts - ts.rem_euclid(900),
+            MarketWindow::Hourly => {
+                let et = market_time(now);
+                ts - (et.minute() as i64 * 60 + et.second() as i64)
             }
         }
-        Err(_) => Ok(None),
     }
-}
 
-// Parse JSON field that might be string or array (FYI: Polymarket API inconsistency)
-fn parse_json_field<T: for<'de> Deserialize<'de>>(value: &serde_json::Value) -> Result<Vec<T>> {
-    match value {
-        serde_json::Value::String(s) => {
-            // String format: parse as JSON string
-            let parsed: Vec<T> = serde_json::from_str(s)?;
-            Ok(parsed)
+    // Every slug to try for the window starting at ts
+    pub fn candidate_slugs(&self, coin: &str, prefix: &str, ts: i64) -> Vec<String> {
+        match self {
+            MarketWindow::FifteenMin => candidate_slugs(coin, prefix, ts),
+            MarketWindow::Hourly => hourly_slugs(coin, ts),
         }
-        serde_json::Value::Array(arr) => {
-            // Array format: parse directly
-            let parsed: Vec<T> = serde_json::from_value(serde_json::Value::Array(arr.clone()))?;
-            Ok(parsed)
+    }
+}
+
+// Field aliases, preferred name first (AFAIK: Gamma is camelCase today, older dumps/proxies use snake_case)
+const SLUG_KEYS: &[&str] = &["slug", "market_slug", "marketSlug"];
+const QUESTION_KEYS: &[&str] = &["question", "title"];
+const END_DATE_KEYS: &[&str] = &["endDate", "end_date", "endDateIso", "end_date_iso"];
+const ACCEPTING_KEYS: &[&str] = &["acceptingOrders", "accepting_orders"];
+const TOKEN_ID_KEYS: &[&str] = &["clobTokenIds", "clob_token_ids", "tokenIds", "token_ids"];
+const OUTCOME_KEYS: &[&str] = &["outcomes"];
+const OUTCOME_PRICE_KEYS: &[&str] = &["outcomePrices", "outcome_prices"];
+const TICK_SIZE_KEYS: &[&str] = &["orderPriceMinTickSize", "minimum_tick_size", "tick_size"];
+const MIN_SIZE_KEYS: &[&str] = &["orderMinSize", "minimum_order_size", "min_order_size"];
+
+// Which naming convention a Gamma response uses (IMO: a flip here is the early warning that discovery is about to break)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GammaSchema {
+    Camel = 1, // endDate / acceptingOrders / clobTokenIds
+    Snake = 2, // end_date / accepting_orders / clob_token_ids
+    Mixed = 3, // Both present (mid-migration)
+    Unknown = 4, // Neither - parser falls back to whatever it can find
+}
+
+impl GammaSchema {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GammaSchema::Camel => "camelCase",
+            GammaSchema::Snake => "snake_case",
+            GammaSchema::Mixed => "mixed",
+            GammaSchema::Unknown => "unknown",
         }
-        _ => Err(anyhow!("Invalid JSON field format")),
     }
 }
 
-// Map outcomes to token IDs (AFAIK: creates lookup map like {"up": "0x123...", "down": "0x456..."})
-fn parse_token_ids(market: &GammaMarket) -> Result<HashMap<String, String>> {
-    let clob_token_ids: Vec<String> = parse_json_field(&market.clob_token_ids)?;
-    let outcomes: Vec<String> = parse_json_field(&market.outcomes)?;
-    
-    let mut result = HashMap::new();
-    for (i, outcome) in outcomes.iter().enumerate() {
-        if i < clob_token_ids.len() {
-            result.insert(outcome.to_lowercase(), clob_token_ids[i].clone()); // Lowercase for case-insensitive lookup
+pub fn detect_schema(raw: &Value) -> GammaSchema {
+    let has = |keys: &[&str]| keys.iter().any(|k| raw.get(k).is_some());
+    let camel = has(&["endDate", "acceptingOrders", "clobTokenIds"]);
+    let snake = has(&["end_date", "accepting_orders", "clob_token_ids"]);
+    match (camel, snake) {
+        (true, false) => GammaSchema::Camel,
+        (false, true) => GammaSchema::Snake,
+        (true, true) => GammaSchema::Mixed,
+        (false, false) => GammaSchema::Unknown,
+    }
+}
+
+static LAST_SCHEMA: AtomicU8 = AtomicU8::new(0); // 0 = nothing seen yet
+
+// Warn once per schema change rather than on every lookup
+fn note_schema(schema: GammaSchema) {
+    let prev = LAST_SCHEMA.swap(schema as u8, Ordering::Relaxed);
+    if prev != 0 && prev != schema as u8 {
+        log_console(LogLevel::Warn, move || {
+            outln!("{}", format!("⚠️  Gamma market schema changed to {} - discovery is using field fallbacks", schema.as_str()).yellow());
+        });
+    }
+}
+
+// Gamma market after tolerant parsing (FYI: every field already resolved through its aliases)
+#[derive(Debug, Clone)]
+pub struct GammaMarket {
+    pub slug: String,
+    pub question: String,
+    pub end_date: String,
+    pub accepting_orders: bool,
+    pub closed: bool,
+    pub outcomes: Vec<String>,
+    pub token_ids: Vec<String>,
+    pub outcome_prices: Vec<String>, // "1"/"0" per outcome once resolved (empty while trading)
+    pub tick_size: Option<f64>, // Price increment at discovery time (BTW: can change mid-market, see websocket_client)
+    pub min_size: Option<f64>, // Min tokens per order
+    pub schema: GammaSchema,
+}
+
+fn first_field<'a>(raw: &'a Value, keys: &[&str]) -> Option<&'a Value> {
+    keys.iter().find_map(|k| raw.get(k).filter(|v| !v.is_null()))
+}
+
+fn str_field(raw: &Value, keys: &[&str]) -> Option<String> {
+    first_field(raw, keys).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn num_field(raw: &Value, keys: &[&str]) -> Option<f64> {
+    first_field(raw, keys).and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+}
+
+fn bool_field(raw: &Value, keys: &[&str]) -> Option<bool> {
+    first_field(raw, keys).and_then(|v| v.as_bool().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+}
+
+// List that might be a JSON-encoded string or an array (BTW: numbers are stringified, "0.5" and 0.5 both work)
+fn list_field(raw: &Value, keys: &[&str]) -> Vec<String> {
+    let Some(value) = first_field(raw, keys) else {
+        return Vec::new();
+    };
+    let items = match value {
+        Value::String(s) => serde_json::from_str::<Vec<Value>>(s).unwrap_or_default(),
+        Value::Array(arr) => arr.clone(),
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .filter_map(|v| match v {
+            Value::String(s) => Some(s),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Parse one Gamma market without trusting its shape (Err = why it can't be used)
+pub fn parse_gamma_market(raw: &Value) -> std::result::Result<GammaMarket, String> {
+    // /markets?slug= returns a list, /markets/slug/{slug} a single object
+    let raw = match raw {
+        Value::Array(arr) => arr.first().ok_or("empty market list")?,
+        other => other,
+    };
+    if !raw.is_object() {
+        return Err("response is not a market object".to_string());
+    }
+    let schema = detect_schema(raw);
+
+    let slug = str_field(raw, SLUG_KEYS).ok_or("no slug field")?;
+    let closed = bool_field(raw, &["closed"]).unwrap_or(false);
+    // No acceptingOrders field -> treat active && !closed as open (AFAIK: same thing for 15m markets)
+    let accepting_orders = bool_field(raw, ACCEPTING_KEYS)
+        .unwrap_or_else(|| bool_field(raw, &["active"]).unwrap_or(false) && !closed);
+
+    let (mut outcomes, mut token_ids) = (list_field(raw, OUTCOME_KEYS), list_field(raw, TOKEN_ID_KEYS));
+    // CLOB-style "tokens": [{token_id, outcome}] when the flat lists are missing
+    if token_ids.is_empty() {
+        if let Some(tokens) = raw.get("tokens").and_then(|t| t.as_array()) {
+            outcomes = tokens.iter().filter_map(|t| t.get("outcome")?.as_str().map(String::from)).collect();
+            token_ids = tokens.iter().filter_map(|t| t.get("token_id")?.as_str().map(String::from)).collect();
         }
     }
-    Ok(result)
+    if token_ids.is_empty() {
+        return Err(format!("no token ids (looked for {} and tokens[])", TOKEN_ID_KEYS.join("/")));
+    }
+    if outcomes.len() != token_ids.len() {
+        return Err(format!("{} outcomes vs {} token ids", outcomes.len(), token_ids.len()));
+    }
+
+    Ok(GammaMarket {
+        slug,
+        question: str_field(raw, QUESTION_KEYS).unwrap_or_default(),
+        end_date: str_field(raw, END_DATE_KEYS).unwrap_or_default(),
+        accepting_orders,
+        closed,
+        outcomes,
+        token_ids,
+        outcome_prices: list_field(raw, OUTCOME_PRICE_KEYS),
+        tick_size: num_field(raw, TICK_SIZE_KEYS),
+        min_size: num_field(raw, MIN_SIZE_KEYS),
+        schema,
+    })
+}
+
+// One slug lookup, for the no-market diagnostics
+enum Lookup {
+    Found(Value),
+    Missing(String), // HTTP status / network error
+}
+
+// Fetch market data from Gamma API (BTW: 10s timeout to avoid hanging)
+async fn get_market_by_slug(slug: &str) -> Lookup {
+    let url = format!("{}/markets/slug/{}", GAMMA_API_HOST, slug);
+    let client = reqwest::Client::new();
+
+    match client.get(&url).timeout(std::time::Duration::from_secs(10)).send().await {
+        Ok(response) if response.status().is_success() => match response.json::<Value>().await {
+            Ok(raw) => Lookup::Found(raw),
+            Err(e) => Lookup::Missing(format!("unreadable body: {}", e)),
+        },
+        Ok(response) => Lookup::Missing(format!("HTTP {}", response.status().as_u16())),
+        Err(e) => Lookup::Missing(format!("request failed: {}", e)),
+    }
+}
+
+// Winning outcome of a closed market, lowercased ("up"/"down"), None while unresolved
+pub async fn fetch_winning_outcome(slug: &str) -> Result<Option<String>> {
+    let market = match get_market_by_slug(slug).await {
+        Lookup::Found(raw) => parse_gamma_market(&raw).map_err(|e| anyhow!("{}: {}", slug, e))?,
+        Lookup::Missing(_) => return Ok(None),
+    };
+    if !market.closed {
+        return Ok(None);
+    }
+    let (outcomes, prices) = (&market.outcomes, &market.outcome_prices);
+    // Settled price is exactly 1 for the winner (AFAIK: 0.5/0.5 = not settled yet, keep polling)
+    Ok(prices
+        .iter()
+        .position(|p| p.parse::<f64>().map(|v| v >= 0.999).unwrap_or(false))
+        .and_then(|i| outcomes.get(i))
+        .map(|o| o.to_lowercase()))
+}
+
+// Map outcomes to token IDs (AFAIK: creates lookup map like {"up": "0x123...", "down": "0x456..."})
+fn parse_token_ids(market: &GammaMarket) -> HashMap<String, String> {
+    market
+        .outcomes
+        .iter()
+        .zip(&market.token_ids)
+        .map(|(outcome, id)| (outcome.to_lowercase(), id.clone())) // Lowercase for case-insensitive lookup
+        .collect()
 }
 
 fn parse_market_data(coin: &str, market: GammaMarket) -> Result<CoinMarket> {
-    let token_ids = parse_token_ids(&market)?;
+    let token_ids = parse_token_ids(&market);
     let up_token_id = token_ids
         .get("up")
         .or_else(|| token_ids.get("yes"))
-        .ok_or_else(|| anyhow!("UP token ID not found"))?
+        .ok_or_else(|| anyhow!("UP token ID not found (outcomes: {})", market.outcomes.join("/")))?
         .clone();
     let down_token_id = token_ids
         .get("down")
         .or_else(|| token_ids.get("no"))
-        .ok_or_else(|| anyhow!("DOWN token ID not found"))?
+        .ok_or_else(|| anyhow!("DOWN token ID not found (outcomes: {})", market.outcomes.join("/")))?
         .clone();
 
+    // Seed order rounding until the market channel reports a change (FYI: both tokens share the market's rules)
+    for token_id in [&up_token_id, &down_token_id] {
+        if let Some(tick_size) = market.tick_size {
+            set_tick_size(token_id, tick_size);
+        }
+        if let Some(min_size) = market.min_size {
+            set_min_size(token_id, min_size);
+        }
+    }
+
     Ok(CoinMarket {
         coin: coin.to_string(),
         up_token_id,
@@ -99,47 +319,291 @@ fn parse_market_data(coin: &str, market: GammaMarket) -> Result<CoinMarket> {
     })
 }
 
-// Find active 15-min market for coin (IMO: checks current/next/prev windows)
-pub async fn find_15_min_market(coin: &str) -> Result<Option<CoinMarket>> {
-    let coin_upper = coin.to_uppercase();
+// Raw Gamma response -> tradable market, same checks as live discovery minus acceptingOrders (FYI: the fixture tests replay fixtures/gamma through it)
+pub fn market_from_gamma(coin: &str, raw: &Value) -> Result<CoinMarket> {
+    let market = parse_gamma_market(raw).map_err(|e| anyhow!(e))?;
+    parse_market_data(&coin.to_uppercase(), market)
+}
+
+// Full coin name for slug patterns (BTW: tickers not listed just reuse the ticker)
+fn coin_name(coin: &str) -> String {
+    match coin {
+        "BTC" => "bitcoin".to_string(),
+        "ETH" => "ethereum".to_string(),
+        "SOL" => "solana".to_string(),
+        "XRP" => "xrp".to_string(),
+        other => other.to_lowercase(),
+    }
+}
+
+// Every slug to try for one window, deduplicated, in SLUG_PATTERNS order
+pub fn candidate_slugs(coin: &str, prefix: &str, ts: i64) -> Vec<String> {
+    let mut slugs: Vec<String> = Vec::new();
+    for pattern in SLUG_PATTERNS {
+        let slug = pattern
+            .replace("{prefix}", prefix)
+            .replace("{coin}", &coin.to_lowercase())
+            .replace("{name}", &coin_name(coin))
+            .replace("{ts}", &ts.to_string());
+        if !slugs.contains(&slug) {
+            slugs.push(slug);
+        }
+    }
+    slugs
+}
+
+// Hourly slugs for the ET hour starting at ts ("october-15-3pm" style date part)
+pub fn hourly_slugs(coin: &str, ts: i64) -> Vec<String> {
+    let start = DateTime::from_timestamp(ts, 0).unwrap_or_else(Utc::now);
+    let et = market_time(start).format("%B-%-d-%-I%P").to_string().to_lowercase();
+    let mut slugs: Vec<String> = Vec::new();
+    for pattern in HOURLY_SLUG_PATTERNS {
+        let slug = pattern
+            .replace("{coin}", &coin.to_lowercase())
+            .replace("{name}", &coin_name(coin))
+            .replace("{et}", &et)
+            .replace("{ts}", &ts.to_string());
+        if !slugs.contains(&slug) {
+            slugs.push(slug);
+        }
+    }
+    slugs
+}
+
+// Why each candidate didn't become the market (FYI: printed only when nothing is found)
+fn report_no_market(coin: &str, tried: Vec<(String, String)>) {
+    let coin = coin.to_string();
+    let found = tried.iter().filter(|(_, why)| !why.starts_with("HTTP 404")).count();
+    log_console(LogLevel::Warn, move || {
+        outln!(
+            "{}",
+            format!("   {} discovery: {} slugs searched, {} existed but were rejected", coin, tried.len(), found).yellow()
+        );
+        for (slug, why) in &tried {
+            outln!("{}", format!("     • {:<40} {}", slug, why).bright_black());
+        }
+    });
+}
+
+// Markets already resolved, by (coin, window, window start) (FYI: the prefetch fills it so a roll-over needs no Gamma round trip)
+static DISCOVERY_CACHE: OnceLock<Mutex<HashMap<(String, &'static str, i64), CoinMarket>>> = OnceLock::new();
+
+fn discovery_cache() -> &'static Mutex<HashMap<(String, &'static str, i64), CoinMarket>> {
+    DISCOVERY_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Still tradable at `now` (BTW: an unparseable end date counts as open, same as the engine's close check)
+fn ends_after(market: &CoinMarket, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&market.end_date)
+        .map(|end| end.with_timezone(&Utc) > now)
+        .unwrap_or(true)
+}
+
+fn cached_market(coin: &str, window: MarketWindow, ts: i64) -> Option<CoinMarket> {
+    let now = Utc::now();
+    let mut cache = discovery_cache().lock().ok()?;
+    cache.retain(|_, m| ends_after(m, now)); // Closed windows are never looked up again
+    cache.get(&(coin.to_string(), window.label(), ts)).cloned()
+}
+
+fn cache_market(coin: &str, window: MarketWindow, ts: i64, market: &CoinMarket) {
+    if let Ok(mut cache) = discovery_cache().lock() {
+        cache.insert((coin.to_string(), window.label(), ts), market.clone());
+    }
+}
+
+// Look up the window after `current` ahead of time and cache it (AFAIK: Gamma lists the next market before it opens)
+// FYI: not accepting orders yet is fine here, it only has to exist with both tokens - Ok(None) = not listed yet
+pub async fn prefetch_next_market(current: &CoinMarket, window: MarketWindow) -> Result<Option<CoinMarket>> {
+    let coin_upper = current.coin.to_uppercase();
     let prefix = coin_slug(&coin_upper)
         .ok_or_else(|| anyhow!("Unsupported coin: {}", coin_upper))?;
+    let end = DateTime::parse_from_rfc3339(&current.end_date)
+        .map(|end| end.with_timezone(&Utc))
+        .map_err(|e| anyhow!("{}: bad end date {:?}: {}", current.slug, current.end_date, e))?;
+    let ts = window.start_ts(end);
+    if let Some(cached) = cached_market(&coin_upper, window, ts) {
+        return Ok(Some(cached));
+    }
 
-    // Calculate current 15-minute window timestamp (FYI: rounds down to nearest 15min)
-    let now = chrono::Utc::now();
-    let minute = (now.minute() / 15) * 15; // Round down to 0, 15, 30, or 45
-    let current_window = now
-        .date_naive()
-        .and_hms_opt(now.hour(), minute, 0)
-        .unwrap();
-    let current_ts = current_window.and_utc().timestamp();
-
-    // Try current window (AFAIK: most likely to be active)
-    let mut slug = format!("{}-{}", prefix, current_ts);
-    if let Some(market) = get_market_by_slug(&slug).await? {
-        if market.accepting_orders {
-            return Ok(Some(parse_market_data(&coin_upper, market)?));
+    for slug in window.candidate_slugs(&coin_upper, prefix, ts) {
+        let Lookup::Found(raw) = get_market_by_slug(&slug).await else {
+            continue;
+        };
+        let Ok(market) = parse_gamma_market(&raw) else {
+            continue;
+        };
+        note_schema(market.schema);
+        if market.closed || market.slug == current.slug {
+            continue;
+        }
+        if let Ok(next) = parse_market_data(&coin_upper, market) {
+            cache_market(&coin_upper, window, ts, &next);
+            return Ok(Some(next));
         }
     }
+    Ok(None)
+}
 
-    // Try next window (BTW: in case current just ended)
-    let next_ts = current_ts + 900; // 15 minutes = 900 seconds
-    slug = format!("{}-{}", prefix, next_ts);
-    if let Some(market) = get_market_by_slug(&slug).await? {
-        if market.accepting_orders {
-            return Ok(Some(parse_market_data(&coin_upper, market)?));
-        }
+// Find active 15-min market for coin
+pub async fn find_15_min_market(coin: &str) -> Result<Option<CoinMarket>> {
+    find_market(coin, MarketWindow::FifteenMin).await
+}
+
+// Find the active market of one window length (IMO: checks current/next/prev windows, every slug pattern in each)
+pub async fn find_market(coin: &str, window: MarketWindow) -> Result<Option<CoinMarket>> {
+    let coin_upper = coin.to_uppercase();
+    let prefix = coin_slug(&coin_upper)
+        .ok_or_else(|| anyhow!("Unsupported coin: {}", coin_upper))?;
+
+    // Current window start (FYI: rounds down to the 15min / ET hour)
+    let current_ts = window.start_ts(chrono::Utc::now());
+    let step = window.secs();
+
+    // Prefetched at the end of the previous window (BTW: skips the slug search entirely)
+    if let Some(cached) = cached_market(&coin_upper, window, current_ts) {
+        return Ok(Some(cached));
     }
 
-    // Try previous window (FYI: might still be accepting orders)
-    let prev_ts = current_ts - 900;
-    slug = format!("{}-{}", prefix, prev_ts);
-    if let Some(market) = get_market_by_slug(&slug).await? {
-        if market.accepting_orders {
-            return Ok(Some(parse_market_data(&coin_upper, market)?));
+    // Current window is most likely active, then next (current just ended), then previous (might still be open)
+    let mut tried: Vec<(String, String)> = Vec::new();
+    for ts in [current_ts, current_ts + step, current_ts - step] {
+        for slug in window.candidate_slugs(&coin_upper, prefix, ts) {
+            let raw = match get_market_by_slug(&slug).await {
+                Lookup::Found(raw) => raw,
+                Lookup::Missing(why) => {
+                    tried.push((slug, why));
+                    continue;
+                }
+            };
+            let market = match parse_gamma_market(&raw) {
+                Ok(market) => market,
+                Err(why) => {
+                    let schema = detect_schema(&raw);
+                    note_schema(schema);
+                    tried.push((slug, format!("unparseable ({}, schema {})", why, schema.as_str())));
+                    continue;
+                }
+            };
+            note_schema(market.schema);
+            if !market.accepting_orders {
+                tried.push((slug, format!("not accepting orders{}", if market.closed { " (closed)" } else { "" })));
+                continue;
+            }
+            match parse_market_data(&coin_upper, market) {
+                Ok(found) => {
+                    cache_market(&coin_upper, window, ts, &found);
+                    return Ok(Some(found));
+                }
+                Err(e) => tried.push((slug, e.to_string())),
+            }
         }
     }
 
+    report_no_market(&format!("{} {}", coin_upper, window.label()), tried);
     Ok(None) // No active market found
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // Every fixture below - a new file in fixtures/gamma needs its own test
+    const FIXTURES: &[&str] = &[
+        "camel_case.json",
+        "clob_tokens.json",
+        "closed.json",
+        "missing_tokens.json",
+        "snake_case.json",
+        "unknown_outcomes.json",
+    ];
+
+    fn fixture_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/gamma")
+    }
+
+    // One fixture replayed through discovery: {description, coin, expect: {ok, slug?, up?, down?,
+    // acceptingOrders?}, response} -> (expect, schema detected on the response, parse result)
+    fn replay(name: &str) -> (Value, GammaSchema, Result<CoinMarket>) {
+        let text = std::fs::read_to_string(fixture_dir().join(name)).expect("fixture readable");
+        let fixture: Value = serde_json::from_str(&text).expect("fixture is JSON");
+        let coin = fixture.get("coin").and_then(|v| v.as_str()).unwrap_or("BTC");
+        let response = fixture.get("response").cloned().unwrap_or(Value::Null);
+        let schema = match &response {
+            Value::Array(arr) => arr.first().map(detect_schema).unwrap_or(GammaSchema::Unknown),
+            other => detect_schema(other),
+        };
+        let expect = fixture.get("expect").cloned().unwrap_or(Value::Null);
+        (expect, schema, market_from_gamma(coin, &response))
+    }
+
+    // Parsed market matches every field the fixture's "expect" lists
+    fn assert_expected(name: &str) -> CoinMarket {
+        let (expect, _, result) = replay(name);
+        assert_eq!(expect.get("ok").and_then(|v| v.as_bool()), Some(true), "{}: fixture expects a rejection", name);
+        let market = match result {
+            Ok(market) => market,
+            Err(e) => panic!("{}: expected a market, rejected: {}", name, e),
+        };
+        let got = [
+            ("slug", Value::from(market.slug.clone())),
+            ("up", Value::from(market.up_token_id.clone())),
+            ("down", Value::from(market.down_token_id.clone())),
+            ("acceptingOrders", Value::from(market.accepting_orders)),
+        ];
+        for (key, value) in got {
+            if let Some(want) = expect.get(key) {
+                assert_eq!(*want, value, "{}: {}", name, key);
+            }
+        }
+        market
+    }
+
+    #[test]
+    fn every_fixture_has_a_test() {
+        let mut files: Vec<String> = std::fs::read_dir(fixture_dir())
+            .expect("fixtures/gamma exists")
+            .filter_map(|e| e.ok().map(|e| e.file_name().to_string_lossy().to_string()))
+            .filter(|n| n.ends_with(".json"))
+            .collect();
+        files.sort();
+        assert_eq!(files, FIXTURES);
+    }
+
+    #[test]
+    fn parses_camel_case() {
+        assert_eq!(replay("camel_case.json").1, GammaSchema::Camel);
+        assert_expected("camel_case.json");
+    }
+
+    #[test]
+    fn parses_snake_case() {
+        assert_eq!(replay("snake_case.json").1, GammaSchema::Snake);
+        assert_expected("snake_case.json");
+    }
+
+    #[test]
+    fn parses_clob_style_tokens_in_an_array_response() {
+        assert_expected("clob_tokens.json");
+    }
+
+    #[test]
+    fn parses_a_market_that_stopped_accepting_orders() {
+        assert!(!assert_expected("closed.json").accepting_orders);
+    }
+
+    #[test]
+    fn rejects_missing_token_ids() {
+        let (expect, _, result) = replay("missing_tokens.json");
+        assert_eq!(expect["ok"], false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_outcomes() {
+        let (expect, _, result) = replay("unknown_outcomes.json");
+        assert_eq!(expect["ok"], false);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,377 @@
+use crate::config::Env;
+use crate::services::arbitrage_executor::ArbitrageOrderResult;
+use crate::services::hedge::HedgeFill;
+use crate::services::value_trades::ValueFill;
+use crate::utils::clock::now_ms;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+// One leg of an executed trade, as filled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegFill {
+    pub success: bool,
+    pub amount_usd: f64,
+    pub price: f64,
+    pub tokens: f64,
+    pub partially_filled: bool,
+}
+
+impl From<&ArbitrageOrderResult> for LegFill {
+    fn from(r: &ArbitrageOrderResult) -> Self {
+        Self {
+            success: r.success,
+            amount_usd: if r.success { r.amount } else { 0.0 },
+            price: r.price,
+            tokens: if r.success { r.tokens_bought.unwrap_or(0.0) } else { 0.0 },
+            partially_filled: r.partially_filled,
+        }
+    }
+}
+
+// Both legs of one trade (FYI: kept for the per-market result file)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeFill {
+    pub timestamp: i64,
+    pub up: LegFill,
+    pub down: LegFill,
+}
+
+// Inventory + notional executed in one market window (FYI: keyed by market slug)
+#[derive(Debug, Clone, Default)]
+pub struct MarketPosition {
+    pub spent_usd: f64, // USDC actually spent (both legs + value buys)
+    pub reserved_usd: f64, // Planned spend of trades still queued / running
+    pub up_tokens: f64,
+    pub down_tokens: f64,
+    pub trades: u32,
+    pub fills: Vec<TradeFill>,
+    pub received_usd: f64, // USDC back from reverse-arb sales (both legs)
+    pub selling_pairs: f64, // UP+DOWN pairs taken out of inventory by sales still in flight
+    pub sales: Vec<TradeFill>, // Reverse-arb sales (tokens = sold, amountUsd = received)
+    pub hedges: Vec<HedgeFill>, // 1h hedges bought against this window's unpaired tokens
+    pub hedge_spent_usd: f64, // USDC spent on them (BTW: not in spent_usd, they pay out in the hourly market)
+    pub value_fills: Vec<ValueFill>, // Single-sided value buys (VALUE_TRADES)
+    pub value_spent_usd: f64, // Their USDC (BTW: in spent_usd, but not counted against MAX_PER_MARKET_USD)
+    pub settlement_mismatches: u32, // Times the chain disagreed with the reported fills (ledger reconciled)
+    pub missed: HashSet<String>, // Opportunity keys detected but not traded (caps, budget, full queue)
+    pub executions: u32, // Orders sent in this window, every strategy (MAX_TRADES_PER_WINDOW)
+}
+
+impl MarketPosition {
+    // Tokens on one side not matched by the other (BTW: that's the unhedged part)
+    pub fn imbalance(&self) -> f64 {
+        (self.up_tokens - self.down_tokens).abs()
+    }
+
+    // Pairs we can still sell back (BTW: in-flight sales were already taken out of up/down)
+    pub fn sellable_pairs(&self) -> f64 {
+        self.up_tokens.min(self.down_tokens).max(0.0)
+    }
+
+    // Everything this window cost (BTW: hedges too - MAX_SPEND_PER_WINDOW_USD is about the burst, not the PnL)
+    pub fn window_spent(&self) -> f64 {
+        self.spent_usd + self.hedge_spent_usd
+    }
+}
+
+// Per-market ledger so repeated detections in one window don't stack past MAX_PER_MARKET_USD
+#[derive(Debug, Clone)]
+pub struct MarketLedger {
+    max_per_market_usd: Option<f64>,
+    max_trades_per_window: Option<u32>,
+    max_spend_per_window_usd: Option<f64>,
+    markets: HashMap<String, MarketPosition>,
+}
+
+impl MarketLedger {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            max_per_market_usd: env.max_per_market_usd,
+            max_trades_per_window: env.max_trades_per_window,
+            max_spend_per_window_usd: env.max_spend_per_window_usd,
+            markets: HashMap::new(),
+        }
+    }
+
+    pub fn position(&self, market: &str) -> MarketPosition {
+        self.markets.get(market).cloned().unwrap_or_default()
+    }
+
+    // USDC left under the cap, counting trades already in flight (None = no cap)
+    pub fn remaining_budget(&self, market: &str) -> Option<f64> {
+        let max = self.max_per_market_usd?;
+        let used = self
+            .markets
+            .get(market)
+            .map(|pos| pos.spent_usd - pos.value_spent_usd + pos.reserved_usd)
+            .unwrap_or(0.0);
+        Some((max - used).max(0.0))
+    }
+
+    // UP+DOWN pairs held in this market and not already being sold
+    pub fn sellable_pairs(&self, market: &str) -> f64 {
+        self.markets.get(market).map(|pos| pos.sellable_pairs()).unwrap_or(0.0)
+    }
+
+    // Tokens per side the chain should show for this window (BTW: pairs being sold are still in the wallet)
+    pub fn expected_holdings(&self, market: &str) -> (f64, f64) {
+        self.markets
+            .get(market)
+            .map(|pos| (pos.up_tokens + pos.selling_pairs, pos.down_tokens + pos.selling_pairs))
+            .unwrap_or((0.0, 0.0))
+    }
+
+    // Booked trades + sales so far (changes whenever something settles)
+    pub fn settled_trades(&self, market: &str) -> usize {
+        self.markets
+            .get(market)
+            .map(|pos| pos.fills.len() + pos.sales.len())
+            .unwrap_or(0)
+    }
+
+    // Replace booked inventory with what actually arrived on-chain
+    pub fn reconcile(&mut self, market: &str, up_onchain: f64, down_onchain: f64) {
+        let pos = self.markets.entry(market.to_string()).or_default();
+        pos.up_tokens = (up_onchain - pos.selling_pairs).max(0.0);
+        pos.down_tokens = (down_onchain - pos.selling_pairs).max(0.0);
+        pos.settlement_mismatches += 1;
+    }
+
+    // Why this window can't take another execution of `planned_usd` (None = within both window caps).
+    // FYI: keyed by slug like everything else here, so the counters start from zero at market roll
+    pub fn window_cap(&self, market: &str, planned_usd: f64) -> Option<String> {
+        let pos = self.markets.get(market);
+        let executions = pos.map(|p| p.executions).unwrap_or(0);
+        if let Some(max) = self.max_trades_per_window.filter(|max| executions >= *max) {
+            return Some(format!("MAX_TRADES_PER_WINDOW reached ({}/{})", executions, max));
+        }
+        let spent = pos.map(|p| p.window_spent()).unwrap_or(0.0);
+        if let Some(max) = self.max_spend_per_window_usd.filter(|max| spent + planned_usd > *max) {
+            return Some(format!("MAX_SPEND_PER_WINDOW_USD reached (${:.2} spent + ${:.2} > ${:.2})", spent, planned_usd, max));
+        }
+        None
+    }
+
+    // Claim an execution right before the orders go out (BTW: jobs for one market run one at a time, so no race here)
+    pub fn begin_execution(&mut self, market: &str, planned_usd: f64) -> Result<(), String> {
+        if let Some(reason) = self.window_cap(market, planned_usd) {
+            return Err(reason);
+        }
+        self.markets.entry(market.to_string()).or_default().executions += 1;
+        Ok(())
+    }
+
+    // Tokens per side we may buy at this ask sum (IMO: shrink to fit instead of skipping outright)
+    pub fn size_for(&self, market: &str, wanted_tokens: f64, ask_sum: f64) -> f64 {
+        match self.remaining_budget(market) {
+            Some(budget) if ask_sum > 0.0 => wanted_tokens.min(budget / ask_sum),
+            _ => wanted_tokens,
+        }
+    }
+
+    // Hold budget for a trade about to be queued (FYI: released when the Reservation is dropped unsettled)
+    pub async fn reserve(ledger: &Arc<Mutex<MarketLedger>>, market: &str, planned_usd: f64) -> Reservation {
+        ledger
+            .lock()
+            .await
+            .markets
+            .entry(market.to_string())
+            .or_default()
+            .reserved_usd += planned_usd;
+        Reservation {
+            ledger: ledger.clone(),
+            market: market.to_string(),
+            planned_usd,
+            settled: false,
+        }
+    }
+
+    // Take up to `wanted` UP+DOWN pairs out of inventory for a sale (None = nothing held)
+    pub async fn reserve_pairs(ledger: &Arc<Mutex<MarketLedger>>, market: &str, wanted: f64) -> Option<PairReservation> {
+        let mut guard = ledger.lock().await;
+        let pos = guard.markets.get_mut(market)?;
+        let tokens = wanted.min(pos.sellable_pairs());
+        if tokens <= 0.0 {
+            return None;
+        }
+        pos.up_tokens -= tokens;
+        pos.down_tokens -= tokens;
+        pos.selling_pairs += tokens;
+        Some(PairReservation {
+            ledger: ledger.clone(),
+            market: market.to_string(),
+            tokens,
+            settled: false,
+        })
+    }
+
+    // Book a 1h hedge against the 15m window it covers
+    pub fn record_hedge(&mut self, market: &str, fill: HedgeFill) {
+        let pos = self.markets.entry(market.to_string()).or_default();
+        pos.hedge_spent_usd += fill.amount_usd;
+        pos.hedges.push(fill);
+    }
+
+    // Book a single-sided value buy (FYI: its tokens join the window's inventory like any other fill)
+    pub fn record_value(&mut self, market: &str, side: &str, model_price: f64, result: &ArbitrageOrderResult) {
+        if !result.success {
+            return;
+        }
+        let tokens = result.tokens_bought.unwrap_or(0.0);
+        let pos = self.markets.entry(market.to_string()).or_default();
+        if side == "UP" {
+            pos.up_tokens += tokens;
+        } else {
+            pos.down_tokens += tokens;
+        }
+        pos.spent_usd += result.amount;
+        pos.value_spent_usd += result.amount;
+        pos.value_fills.push(ValueFill {
+            timestamp: now_ms(),
+            side: side.to_string(),
+            tokens,
+            amount_usd: result.amount,
+            price: result.price,
+            model_price,
+        });
+    }
+
+    // Detected but not traded (BTW: keyed by opportunity so one spread seen on many ticks counts once)
+    pub fn record_missed(&mut self, market: &str, opportunity_key: &str) {
+        self.markets
+            .entry(market.to_string())
+            .or_default()
+            .missed
+            .insert(opportunity_key.to_string());
+    }
+
+    // Give back a reservation (trade dropped or finished)
+    fn release(&mut self, market: &str, planned_usd: f64) {
+        if let Some(pos) = self.markets.get_mut(market) {
+            pos.reserved_usd = (pos.reserved_usd - planned_usd).max(0.0);
+        }
+    }
+
+    // Put back pairs a sale didn't sell (per leg - a lopsided sale leaves the difference held)
+    fn return_pairs(&mut self, market: &str, reserved: f64, up_unsold: f64, down_unsold: f64) {
+        if let Some(pos) = self.markets.get_mut(market) {
+            pos.selling_pairs = (pos.selling_pairs - reserved).max(0.0);
+            pos.up_tokens += up_unsold.max(0.0);
+            pos.down_tokens += down_unsold.max(0.0);
+        }
+    }
+
+    fn record_sale(&mut self, market: &str, reserved: f64, up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) {
+        let sold = |r: &ArbitrageOrderResult| if r.success { r.tokens_bought.unwrap_or(0.0).min(reserved) } else { 0.0 };
+        let (up_sold, down_sold) = (sold(up), sold(down));
+        self.return_pairs(market, reserved, reserved - up_sold, reserved - down_sold);
+        let pos = self.markets.entry(market.to_string()).or_default();
+        pos.received_usd += [up, down].iter().filter(|r| r.success).map(|r| r.amount).sum::<f64>();
+        pos.sales.push(TradeFill {
+            timestamp: now_ms(),
+            up: up.into(),
+            down: down.into(),
+        });
+    }
+
+    // Swap a reservation for what was actually filled
+    fn record_trade(
+        &mut self,
+        market: &str,
+        planned_usd: f64,
+        up: &ArbitrageOrderResult,
+        down: &ArbitrageOrderResult,
+    ) {
+        self.release(market, planned_usd);
+        let pos = self.markets.entry(market.to_string()).or_default();
+        if up.success {
+            pos.spent_usd += up.amount;
+            pos.up_tokens += up.tokens_bought.unwrap_or(0.0);
+        }
+        if down.success {
+            pos.spent_usd += down.amount;
+            pos.down_tokens += down.tokens_bought.unwrap_or(0.0);
+        }
+        pos.trades += 1;
+        pos.fills.push(TradeFill {
+            timestamp: now_ms(),
+            up: up.into(),
+            down: down.into(),
+        });
+    }
+}
+
+// Budget held for one queued trade (BTW: full queue / stale drop / failed trade all just drop it)
+pub struct Reservation {
+    ledger: Arc<Mutex<MarketLedger>>,
+    market: String,
+    planned_usd: f64,
+    settled: bool,
+}
+
+impl Reservation {
+    // Book the fills against the market and free the reservation
+    pub async fn settle(mut self, up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) {
+        self.ledger
+            .lock()
+            .await
+            .record_trade(&self.market, self.planned_usd, up, down);
+        self.settled = true;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+        // Drop can't await (IMO: a detached release is fine, it only ever lowers reserved_usd)
+        let ledger = self.ledger.clone();
+        let market = std::mem::take(&mut self.market);
+        let planned_usd = self.planned_usd;
+        tokio::spawn(async move {
+            ledger.lock().await.release(&market, planned_usd);
+        });
+    }
+}
+
+// Inventory held for one queued reverse-arb sale (FYI: unsold pairs go back on drop, like Reservation)
+pub struct PairReservation {
+    ledger: Arc<Mutex<MarketLedger>>,
+    market: String,
+    tokens: f64,
+    settled: bool,
+}
+
+impl PairReservation {
+    pub fn tokens(&self) -> f64 {
+        self.tokens
+    }
+
+    // Book the sold legs and hand back whatever didn't sell
+    pub async fn settle(mut self, up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) {
+        self.ledger
+            .lock()
+            .await
+            .record_sale(&self.market, self.tokens, up, down);
+        self.settled = true;
+    }
+}
+
+impl Drop for PairReservation {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+        let ledger = self.ledger.clone();
+        let market = std::mem::take(&mut self.market);
+        let tokens = self.tokens;
+        tokio::spawn(async move {
+            ledger.lock().await.return_pairs(&market, tokens, tokens, tokens);
+        });
+    }
+}
@@ -0,0 +1,132 @@
+use crate::config::Env;
+use crate::utils::logger::{log_console, LogLevel};
+use colored::*;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Rounding used for a token before its tick size is known (FYI: the old hard-coded 4/2 decimals)
+pub const DEFAULT_PRECISION: Precision = Precision { price: 4, size: 2, amount: 4 };
+
+// Decimals the CLOB accepts for one tick size (AFAIK: same table as the official clients' ROUNDING_CONFIG)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precision {
+    pub price: usize, // Limit price decimals
+    pub size: usize, // Token quantity decimals
+    pub amount: usize, // USDC amount decimals (price * size)
+}
+
+impl Precision {
+    // None = not a tick size Polymarket uses (BTW: keep whatever we had rather than guess)
+    pub fn for_tick_size(tick_size: f64) -> Option<Self> {
+        let price = [(0.1, 1), (0.01, 2), (0.001, 3), (0.0001, 4)]
+            .iter()
+            .find(|(tick, _)| (tick_size - tick).abs() < 1e-9)
+            .map(|(_, decimals)| *decimals)?;
+        Some(Self { price, size: 2, amount: price + 2 })
+    }
+}
+
+// What the market currently requires for one token (FYI: both change mid-market near 0/1)
+#[derive(Debug, Clone, Copy, Default)]
+struct TokenRules {
+    tick_size: Option<f64>,
+    min_size: Option<f64>, // Min tokens per order
+}
+
+lazy_static::lazy_static! {
+    static ref TOKEN_RULES: RwLock<HashMap<String, TokenRules>> = RwLock::new(HashMap::new());
+}
+
+// MIN_ORDER_SIZE_USD / MIN_ORDER_SIZE_TOKENS (FYI: set once at startup, before any order goes out)
+#[derive(Debug, Clone, Copy)]
+struct OrderMinimums {
+    usd: f64,
+    tokens: f64,
+}
+
+static ORDER_MINIMUMS: RwLock<OrderMinimums> = RwLock::new(OrderMinimums { usd: 1.0, tokens: 5.0 });
+
+pub fn set_order_minimums(env: &Env) {
+    let usd = if env.min_order_size_usd > 0.0 { env.min_order_size_usd } else { 1.0 };
+    let tokens = if env.min_order_size_tokens > 0.0 { env.min_order_size_tokens } else { 5.0 };
+    if usd < 1.0 {
+        log_console(LogLevel::Warn, move || {
+            outln!("{}", format!("MIN_ORDER_SIZE_USD={} is below Polymarket's $1 minimum - smaller orders will be rejected", usd).yellow());
+        });
+    }
+    if let Ok(mut minimums) = ORDER_MINIMUMS.write() {
+        *minimums = OrderMinimums { usd, tokens };
+    }
+}
+
+// Smallest order in USDC
+pub fn min_order_usd() -> f64 {
+    ORDER_MINIMUMS.read().map(|m| m.usd).unwrap_or(1.0)
+}
+
+// MIN_ORDER_SIZE_TOKENS (BTW: the floor used where no particular token is involved yet)
+pub fn configured_min_tokens() -> f64 {
+    ORDER_MINIMUMS.read().map(|m| m.tokens).unwrap_or(5.0)
+}
+
+// Record a tick size from discovery, a book or a tick_size_change event (BTW: logs only actual changes)
+pub fn set_tick_size(token_id: &str, tick_size: f64) {
+    if Precision::for_tick_size(tick_size).is_none() {
+        return;
+    }
+    let Ok(mut rules) = TOKEN_RULES.write() else {
+        return;
+    };
+    let entry = rules.entry(token_id.to_string()).or_default();
+    let previous = entry.tick_size.replace(tick_size);
+    if previous.is_some_and(|p| (p - tick_size).abs() > 1e-9) {
+        let token = token_id[..token_id.len().min(20)].to_string();
+        log_console(LogLevel::Info, move || {
+            outln!(
+                "{}",
+                format!("Tick size for {}... changed {} → {}", token, previous.unwrap_or_default(), tick_size).yellow()
+            );
+        });
+    }
+}
+
+// Record the market's min order size (AFAIK: from discovery or a book) - warns once when it beats MIN_ORDER_SIZE_TOKENS
+pub fn set_min_size(token_id: &str, min_size: f64) {
+    if min_size <= 0.0 || !min_size.is_finite() {
+        return;
+    }
+    let Ok(mut rules) = TOKEN_RULES.write() else {
+        return;
+    };
+    let previous = rules.entry(token_id.to_string()).or_default().min_size.replace(min_size);
+    let configured = configured_min_tokens();
+    if previous != Some(min_size) && min_size > configured {
+        let token = token_id[..token_id.len().min(20)].to_string();
+        log_console(LogLevel::Warn, move || {
+            outln!(
+                "{}",
+                format!("Market minimum for {}... is {} tokens (MIN_ORDER_SIZE_TOKENS={}) - using the market's", token, min_size, configured).yellow()
+            );
+        });
+    }
+}
+
+// Rounding to use for orders on this token right now
+pub fn precision_for(token_id: &str) -> Precision {
+    TOKEN_RULES
+        .read()
+        .ok()
+        .and_then(|rules| rules.get(token_id).and_then(|r| r.tick_size))
+        .and_then(Precision::for_tick_size)
+        .unwrap_or(DEFAULT_PRECISION)
+}
+
+// Market's min order size in tokens (None = not reported yet)
+pub fn min_size_for(token_id: &str) -> Option<f64> {
+    TOKEN_RULES.read().ok().and_then(|rules| rules.get(token_id).and_then(|r| r.min_size))
+}
+
+// Min tokens per order on this token right now: MIN_ORDER_SIZE_TOKENS, raised to the market's own minimum
+pub fn min_order_tokens(token_id: &str) -> f64 {
+    configured_min_tokens().max(min_size_for(token_id).unwrap_or(0.0))
+}
@@ -0,0 +1,136 @@
+use crate::config::Env;
+use crate::services::market_discovery::{fetch_winning_outcome, CoinMarket};
+use crate::services::market_ledger::MarketPosition;
+use crate::utils::clock::now_ms;
+use crate::utils::logger::{log_console, log_error, LogLevel};
+use colored::*;
+use serde_json::json;
+use std::path::PathBuf;
+use tokio::time::{sleep, Duration};
+
+// Gamma usually settles 15m markets within minutes (FYI: after this the file keeps winner = null)
+const RESOLUTION_POLL_INTERVAL: Duration = Duration::from_secs(60);
+const RESOLUTION_POLL_ATTEMPTS: u32 = 15;
+
+// USDC paid over the quoted price (BTW: price improvement shows up as 0, not negative)
+fn leg_fee(amount_usd: f64, tokens: f64, price: f64) -> f64 {
+    (amount_usd - tokens * price).max(0.0)
+}
+
+// Same shape as the copy bot's scan result files (scanDate/config/summary + rows) so aggregate tooling reads both
+fn build_result(market: &CoinMarket, pos: &MarketPosition, env: &Env, winner: Option<&str>) -> serde_json::Value {
+    let fills = pos
+        .fills
+        .iter()
+        .flat_map(|t| [&t.up, &t.down])
+        .filter(|l| l.success)
+        .count();
+    let fees_usd: f64 = pos
+        .fills
+        .iter()
+        .flat_map(|t| [&t.up, &t.down])
+        .filter(|l| l.success)
+        .map(|l| leg_fee(l.amount_usd, l.tokens, l.price))
+        .sum();
+    let hedged = pos.up_tokens.min(pos.down_tokens);
+    let payout_usd = winner.map(|w| match w {
+        "up" => pos.up_tokens,
+        "down" => pos.down_tokens,
+        _ => 0.0,
+    });
+
+    json!({
+        "scanDate": chrono::Utc::now().to_rfc3339(),
+        "marketSlug": market.slug,
+        "coin": market.coin,
+        "question": market.question,
+        "endDate": market.end_date,
+        "config": {
+            "tokenAmount": env.token_amount,
+            "arbitrageThreshold": env.arbitrage_threshold,
+            "maxPerMarketUsd": env.max_per_market_usd,
+            "maxTradesPerWindow": env.max_trades_per_window,
+            "maxSpendPerWindowUsd": env.max_spend_per_window_usd,
+            "upOrderType": env.up_order_type.as_str(),
+            "downOrderType": env.down_order_type.as_str(),
+        },
+        "summary": {
+            "trades": pos.trades,
+            "executions": pos.executions,
+            "fills": fills,
+            "spentUsd": pos.spent_usd,
+            "receivedUsd": pos.received_usd,
+            "sales": pos.sales.len(),
+            "feesUsd": fees_usd,
+            "upTokens": pos.up_tokens,
+            "downTokens": pos.down_tokens,
+            "hedgedTokens": hedged,
+            "unhedgedTokens": (pos.up_tokens - pos.down_tokens).abs(),
+            "pnlIfUp": pos.up_tokens + pos.received_usd - pos.spent_usd,
+            "pnlIfDown": pos.down_tokens + pos.received_usd - pos.spent_usd,
+            "winner": winner,
+            "payoutUsd": payout_usd,
+            "pnlUsd": payout_usd.map(|p| p + pos.received_usd - pos.spent_usd),
+            "missedOpportunities": pos.missed.len(),
+            "settlementMismatches": pos.settlement_mismatches,
+            "hedgeSpentUsd": pos.hedge_spent_usd, // Not in pnlUsd - hedges resolve with their 1h market
+            "valueBuys": pos.value_fills.len(),
+            "valueSpentUsd": pos.value_spent_usd, // Included in spentUsd / pnl
+        },
+        "trades": pos.fills,
+        "reverseSales": pos.sales,
+        "hedges": pos.hedges,
+        "valueTrades": pos.value_fills,
+    })
+}
+
+fn result_path(env: &Env, market: &CoinMarket) -> PathBuf {
+    PathBuf::from(&env.results_dir).join(format!("{}.json", market.slug))
+}
+
+fn write_result(env: &Env, market: &CoinMarket, pos: &MarketPosition, winner: Option<&str>) -> anyhow::Result<PathBuf> {
+    std::fs::create_dir_all(&env.results_dir)?;
+    let path = result_path(env, market);
+    let body = serde_json::to_string_pretty(&build_result(market, pos, env, winner))?;
+    std::fs::write(&path, body)?;
+    Ok(path)
+}
+
+// Write the market's result now, then rewrite it once Gamma reports the winner (IMO: PnL at resolution, not at close)
+pub fn spawn_market_result(market: CoinMarket, pos: MarketPosition, env: Env) {
+    tokio::spawn(async move {
+        match write_result(&env, &market, &pos, None) {
+            Ok(path) => {
+                let shown = path.display().to_string();
+                log_console(LogLevel::Info, move || {
+                    outln!("{}", format!("📄 Market result written to {}", shown).bright_black());
+                });
+            }
+            Err(e) => {
+                log_error(&format!("Failed to write result for {}: {}", market.slug, e), Some("results"));
+                return;
+            }
+        }
+
+        let closed_at = now_ms();
+        for _ in 0..RESOLUTION_POLL_ATTEMPTS {
+            sleep(RESOLUTION_POLL_INTERVAL).await;
+            let winner = match fetch_winning_outcome(&market.slug).await {
+                Ok(Some(winner)) => winner,
+                Ok(None) => continue,
+                Err(e) => {
+                    log_error(&format!("Resolution lookup failed for {}: {}", market.slug, e), Some("results"));
+                    continue;
+                }
+            };
+            if let Err(e) = write_result(&env, &market, &pos, Some(&winner)) {
+                log_error(&format!("Failed to update result for {}: {}", market.slug, e), Some("results"));
+            }
+            let (slug, secs) = (market.slug.clone(), (now_ms() - closed_at) / 1000);
+            log_console(LogLevel::Debug, move || {
+                outln!("{}", format!("   {} resolved {} after {}s", slug, winner.to_uppercase(), secs).bright_black());
+            });
+            return;
+        }
+    });
+}
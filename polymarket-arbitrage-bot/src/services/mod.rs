@@ -1,12 +1,57 @@
+pub mod api_creds;
 pub mod arbitrage_executor;
 pub mod create_clob_client;
+pub mod dutch_book;
+pub mod engine;
+pub mod execution_queue;
+pub mod exposure;
+pub mod fill_score;
+pub mod gas_check;
+pub mod health;
+pub mod hedge;
 pub mod market_discovery;
+pub mod market_ledger;
+pub mod market_precision;
+pub mod market_result;
+pub mod neg_risk;
+pub mod opportunity;
+pub mod order_queue;
 pub mod price_monitor;
+pub mod reverse_arb;
+pub mod session_budget;
+pub mod settlement;
+pub mod spread_alert;
+pub mod timeseries;
+pub mod trade_journal;
+pub mod value_trades;
+pub mod wallet_balance;
 pub mod websocket_client;
 
+pub use api_creds::*;
 pub use arbitrage_executor::*;
 pub use create_clob_client::*;
+pub use dutch_book::*;
+pub use engine::*;
+pub use execution_queue::*;
+pub use exposure::*;
+pub use fill_score::*;
+pub use gas_check::*;
+pub use health::*;
+pub use hedge::*;
 pub use market_discovery::*;
+pub use market_ledger::*;
+pub use market_precision::*;
+pub use market_result::*;
+pub use neg_risk::*;
+pub use opportunity::*;
+pub use order_queue::*;
 pub use price_monitor::*;
+pub use reverse_arb::*;
+pub use session_budget::*;
+pub use settlement::*;
+pub use spread_alert::*;
+pub use timeseries::*;
+pub use trade_journal::*;
+pub use value_trades::*;
 pub use websocket_client::*;
 
@@ -0,0 +1,159 @@
+use crate::config::Env;
+use crate::services::gas_check::GasGuard;
+use anyhow::{anyhow, bail, Result};
+use ethers::prelude::*;
+use std::sync::Arc;
+
+// Polymarket contracts on Polygon (FYI: NegRiskAdapter wraps the CTF for multi-market events)
+pub const NEG_RISK_ADAPTER: &str = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296";
+pub const CONDITIONAL_TOKENS: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+const POLYGON_CHAIN_ID: u64 = 137;
+const TOKEN_DECIMALS: i32 = 6; // Outcome tokens use USDC decimals
+// Gas used when the call can't be estimated yet (FYI: before the approval, every estimate reverts)
+const APPROVAL_GAS: u64 = 60_000;
+const CONVERSION_GAS_BASE: u64 = 150_000;
+const CONVERSION_GAS_PER_LEG: u64 = 60_000;
+
+abigen!(
+    NegRiskAdapter,
+    r#"[
+        function convertPositions(bytes32 marketId, uint256 indexSet, uint256 amount) external
+    ]"#
+);
+
+abigen!(
+    ConditionalTokens,
+    r#"[
+        function isApprovedForAll(address owner, address operator) external view returns (bool)
+        function setApprovalForAll(address operator, bool approved) external
+    ]"#
+);
+
+type SignerClient = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+// Position of a question inside its neg-risk market (AFAIK: last byte of the 32-byte questionID)
+pub fn question_index(question_id: &str) -> Option<u8> {
+    let hex = question_id.trim_start_matches("0x");
+    if hex.len() != 64 {
+        return None;
+    }
+    u8::from_str_radix(&hex[62..], 16).ok()
+}
+
+// Bitmask of the questions whose NO tokens get converted
+pub fn index_set(indices: &[u8]) -> U256 {
+    indices
+        .iter()
+        .fold(U256::zero(), |acc, i| acc | (U256::one() << (*i as usize)))
+}
+
+// USDC returned by converting NO on k of the event's markets (BTW: plus YES on the other N-k)
+pub fn conversion_payout(converted_legs: usize) -> f64 {
+    converted_legs.saturating_sub(1) as f64
+}
+
+fn to_units(amount: f64) -> U256 {
+    U256::from((amount * 10f64.powi(TOKEN_DECIMALS)).floor().max(0.0) as u64)
+}
+
+fn parse_bytes32(s: &str) -> Result<[u8; 32]> {
+    let hash: H256 = s.parse().map_err(|e| anyhow!("Bad bytes32 '{}': {}", s, e))?;
+    Ok(hash.0)
+}
+
+// Signs conversion txs from the PRIVATE_KEY wallet
+pub struct NegRiskConverter {
+    adapter: NegRiskAdapter<SignerClient>,
+    ctf: ConditionalTokens<SignerClient>,
+    owner: Address,
+    gas: GasGuard,
+}
+
+impl NegRiskConverter {
+    pub async fn new(env: &Env) -> Result<Self> {
+        let private_key = env
+            .private_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("PRIVATE_KEY is required"))?;
+        let wallet = private_key
+            .trim_start_matches("0x")
+            .parse::<LocalWallet>()?
+            .with_chain_id(POLYGON_CHAIN_ID);
+        let owner = wallet.address();
+
+        // Tokens have to sit in the signing wallet (NGL: Safe/proxy holders must convert via the Polymarket UI)
+        if let Some(proxy) = env.proxy_wallet.as_ref() {
+            let proxy: Address = proxy.parse()?;
+            if proxy != owner {
+                bail!(
+                    "Positions are held by PROXY_WALLET {:?}; neg-risk conversion only runs from the signing EOA {:?}",
+                    proxy,
+                    owner
+                );
+            }
+        }
+
+        let provider = Provider::<Http>::try_from(&env.rpc_url)?;
+        let client = Arc::new(SignerMiddleware::new(provider, wallet));
+        Ok(Self {
+            adapter: NegRiskAdapter::new(NEG_RISK_ADAPTER.parse::<Address>()?, client.clone()),
+            ctf: ConditionalTokens::new(CONDITIONAL_TOKENS.parse::<Address>()?, client),
+            owner,
+            gas: GasGuard::new(env)?,
+        })
+    }
+
+    async fn is_approved(&self) -> Result<bool> {
+        Ok(self.ctf.is_approved_for_all(self.owner, self.adapter.address()).call().await?)
+    }
+
+    // One-time ERC1155 approval so the adapter can pull our outcome tokens
+    pub async fn ensure_approval(&self) -> Result<()> {
+        let operator = self.adapter.address();
+        if self.is_approved().await? {
+            return Ok(());
+        }
+        let call = self.ctf.set_approval_for_all(operator, true);
+        let pending = call.send().await?;
+        let receipt = pending.await?.ok_or_else(|| anyhow!("Approval tx dropped"))?;
+        if receipt.status != Some(1u64.into()) {
+            bail!("Approval tx {:?} reverted", receipt.transaction_hash);
+        }
+        Ok(())
+    }
+
+    // Gas of `call` plus the approval it still needs (estimated when possible, else the fallback constants)
+    async fn gas_units<D: ethers::abi::Detokenize>(&self, call: &ContractCall<SignerClient, D>, fallback: u64) -> Result<u64> {
+        if !self.is_approved().await? {
+            return Ok(APPROVAL_GAS + fallback);
+        }
+        Ok(call.estimate_gas().await.map(|g| g.as_u64()).unwrap_or(fallback))
+    }
+
+    // NO x `amount` on each market in `indices` -> (k-1) x amount USDC + YES on the other markets
+    // None = skipped, gas would eat more than `benefit_usd` allows (tokens stay held)
+    pub async fn convert(
+        &self,
+        neg_risk_market_id: &str,
+        indices: &[u8],
+        amount: f64,
+        benefit_usd: f64,
+    ) -> Result<Option<H256>> {
+        let call = self.adapter.convert_positions(
+            parse_bytes32(neg_risk_market_id)?,
+            index_set(indices),
+            to_units(amount),
+        );
+        let fallback = CONVERSION_GAS_BASE + CONVERSION_GAS_PER_LEG * indices.len() as u64;
+        if !self.gas.worth_it("neg-risk conversion", self.gas_units(&call, fallback).await?, benefit_usd).await {
+            return Ok(None);
+        }
+        self.ensure_approval().await?;
+        let pending = call.send().await?;
+        let receipt = pending.await?.ok_or_else(|| anyhow!("Conversion tx dropped"))?;
+        if receipt.status != Some(1u64.into()) {
+            bail!("Conversion tx {:?} reverted", receipt.transaction_hash);
+        }
+        Ok(Some(receipt.transaction_hash))
+    }
+}
@@ -0,0 +1,201 @@
+use crate::config::threshold_schedule::ThresholdSchedule;
+use crate::config::Env;
+use crate::services::market_precision::configured_min_tokens;
+use crate::services::price_monitor::{create_price_data, PriceData};
+use crate::services::websocket_client::OrderbookSnapshot;
+use chrono::{DateTime, Utc};
+
+// Need at least this long left in the window to open a trade (FYI: both legs have to fill)
+pub const MIN_MS_TO_TRADE: i64 = 5000;
+
+// What the detection core needs to know about one market window (BTW: built once per market)
+#[derive(Debug, Clone)]
+pub struct OpportunityConfig {
+    pub coin: String,
+    pub market_end: DateTime<Utc>,
+    pub schedule: ThresholdSchedule, // Threshold + size cap by time-to-expiry
+    pub token_amount: f64, // TOKEN_AMOUNT (upper bound for every step)
+    pub fee_rate: f64, // TAKER_FEE_BPS as a fraction of the pair cost
+}
+
+impl OpportunityConfig {
+    // Unparseable end date = treat the market as ending now (AFAIK: same as before the extraction)
+    pub fn new(coin: &str, end_date: &str, env: &Env) -> Self {
+        let market_end = DateTime::parse_from_rfc3339(end_date)
+            .map(|d| d.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        Self {
+            coin: coin.to_string(),
+            market_end,
+            schedule: env.threshold_schedule.clone(),
+            token_amount: env.token_amount,
+            fee_rate: env.taker_fee_bps / 10_000.0,
+        }
+    }
+
+    pub fn ms_left(&self, now: DateTime<Utc>) -> i64 {
+        (self.market_end - now).num_milliseconds()
+    }
+
+    // Top-of-book prices judged against the threshold that applies right now
+    pub fn price_data(&self, up: &OrderbookSnapshot, down: &OrderbookSnapshot, now: DateTime<Utc>) -> PriceData {
+        let step = self.schedule.at(self.ms_left(now) / 1000);
+        create_price_data(&self.coin, Some(up), Some(down), step.threshold)
+    }
+}
+
+// A trade the callback should queue (IMO: sizing against budgets/ledger happens after this)
+#[derive(Debug, Clone)]
+pub struct TradePlan {
+    pub prices: PriceData,
+    pub tokens: f64, // Tokens per side from the schedule
+    pub opportunity_key: String, // Dedup key (same asks = same opportunity)
+    pub ms_left: i64,
+    pub edge: f64, // Threshold - ask sum - fees, per pair
+}
+
+impl TradePlan {
+    pub fn planned_spend(&self) -> f64 {
+        self.tokens * self.prices.ask_sum
+    }
+}
+
+// Pure detection: both books + window config + clock in, a trade plan (or nothing) out
+pub fn evaluate_opportunity(
+    up_book: &OrderbookSnapshot,
+    down_book: &OrderbookSnapshot,
+    cfg: &OpportunityConfig,
+    now: DateTime<Utc>,
+) -> Option<TradePlan> {
+    let ms_left = cfg.ms_left(now);
+    if ms_left <= MIN_MS_TO_TRADE {
+        return None; // Closed or too close to expiry
+    }
+
+    let prices = cfg.price_data(up_book, down_book, now);
+    // Empty ask side reads as price 0 (BTW: that's a missing book, not a free token)
+    if !prices.has_arbitrage || prices.up_ask <= 0.0 || prices.down_ask <= 0.0 {
+        return None;
+    }
+
+    let step = cfg.schedule.at(ms_left / 1000);
+    // Under the threshold on the asks alone isn't enough - the taker fee comes out of the same spread
+    let edge = step.threshold - prices.ask_sum * (1.0 + cfg.fee_rate);
+    if edge <= 0.0 {
+        return None;
+    }
+
+    let tokens = cfg.token_amount.min(step.max_tokens);
+    if tokens < configured_min_tokens() {
+        return None;
+    }
+
+    Some(TradePlan {
+        opportunity_key: format!("{:.4}_{:.4}", prices.up_ask, prices.down_ask),
+        prices,
+        tokens,
+        ms_left,
+        edge,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::websocket_client::OrderbookLevel;
+    use chrono::Duration;
+
+    fn book(ask: f64, size: f64) -> OrderbookSnapshot {
+        OrderbookSnapshot {
+            asset_id: "token".to_string(),
+            market: "market".to_string(),
+            timestamp: 0,
+            bids: vec![OrderbookLevel { price: (ask - 0.01).max(0.0), size }],
+            asks: if ask > 0.0 { vec![OrderbookLevel { price: ask, size }] } else { Vec::new() },
+            hash: None,
+        }
+    }
+
+    fn config(now: DateTime<Utc>, secs_left: i64, schedule: ThresholdSchedule, fee_rate: f64) -> OpportunityConfig {
+        OpportunityConfig {
+            coin: "BTC".to_string(),
+            market_end: now + Duration::seconds(secs_left),
+            schedule,
+            token_amount: 10.0,
+            fee_rate,
+        }
+    }
+
+    #[test]
+    fn plans_a_pair_under_the_threshold() {
+        let now = Utc::now();
+        let cfg = config(now, 600, ThresholdSchedule::flat(0.99, 10.0), 0.0);
+        let plan = evaluate_opportunity(&book(0.48, 50.0), &book(0.49, 50.0), &cfg, now).unwrap();
+        assert_eq!(plan.tokens, 10.0);
+        assert!((plan.edge - 0.02).abs() < 1e-9);
+        assert!((plan.planned_spend() - 9.7).abs() < 1e-9);
+        assert_eq!(plan.opportunity_key, "0.4800_0.4900");
+    }
+
+    #[test]
+    fn ignores_asks_at_or_above_the_threshold() {
+        let now = Utc::now();
+        let cfg = config(now, 600, ThresholdSchedule::flat(0.99, 10.0), 0.0);
+        assert!(evaluate_opportunity(&book(0.50, 50.0), &book(0.49, 50.0), &cfg, now).is_none());
+        assert!(evaluate_opportunity(&book(0.52, 50.0), &book(0.49, 50.0), &cfg, now).is_none());
+    }
+
+    #[test]
+    fn fees_can_eat_the_edge() {
+        let now = Utc::now();
+        // 0.97 in asks: 2 cents under the threshold before fees
+        let cheap = config(now, 600, ThresholdSchedule::flat(0.99, 10.0), 0.01);
+        let plan = evaluate_opportunity(&book(0.48, 50.0), &book(0.49, 50.0), &cheap, now).unwrap();
+        assert!((plan.edge - (0.99 - 0.97 * 1.01)).abs() < 1e-9);
+
+        let expensive = config(now, 600, ThresholdSchedule::flat(0.99, 10.0), 0.025);
+        assert!(evaluate_opportunity(&book(0.48, 50.0), &book(0.49, 50.0), &expensive, now).is_none());
+    }
+
+    #[test]
+    fn missing_ask_side_is_not_an_arb() {
+        let now = Utc::now();
+        let cfg = config(now, 600, ThresholdSchedule::flat(0.99, 10.0), 0.0);
+        assert!(evaluate_opportunity(&book(0.0, 50.0), &book(0.49, 50.0), &cfg, now).is_none());
+        assert!(evaluate_opportunity(&book(0.48, 50.0), &book(0.0, 50.0), &cfg, now).is_none());
+    }
+
+    #[test]
+    fn schedule_sets_threshold_and_size_by_time_left() {
+        let now = Utc::now();
+        let schedule = ThresholdSchedule::parse("600:0.99:10,120:0.985:6,0:0.97:2", 10.0).unwrap();
+
+        let early = config(now, 900, schedule.clone(), 0.0);
+        assert_eq!(evaluate_opportunity(&book(0.49, 50.0), &book(0.49, 50.0), &early, now).unwrap().tokens, 10.0);
+
+        // 0.98 passes the early step but not the 0.97 step
+        let late = config(now, 60, schedule.clone(), 0.0);
+        assert!(evaluate_opportunity(&book(0.49, 50.0), &book(0.49, 50.0), &late, now).is_none());
+
+        let middle = config(now, 300, schedule, 0.0);
+        assert_eq!(evaluate_opportunity(&book(0.49, 50.0), &book(0.49, 50.0), &middle, now).unwrap().tokens, 6.0);
+    }
+
+    #[test]
+    fn size_below_the_minimum_is_skipped() {
+        let now = Utc::now();
+        // Step caps tokens at 2, under the default 5-token minimum
+        let cfg = config(now, 600, ThresholdSchedule::flat(0.99, 2.0), 0.0);
+        assert!(evaluate_opportunity(&book(0.48, 50.0), &book(0.49, 50.0), &cfg, now).is_none());
+    }
+
+    #[test]
+    fn no_trades_near_expiry() {
+        let now = Utc::now();
+        let closing = config(now, MIN_MS_TO_TRADE / 1000, ThresholdSchedule::flat(0.99, 10.0), 0.0);
+        assert!(evaluate_opportunity(&book(0.48, 50.0), &book(0.49, 50.0), &closing, now).is_none());
+
+        let closed = config(now, -30, ThresholdSchedule::flat(0.99, 10.0), 0.0);
+        assert!(evaluate_opportunity(&book(0.48, 50.0), &book(0.49, 50.0), &closed, now).is_none());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::config::Env;
+use crate::services::timeseries::record_order_wait;
+use crate::utils::clock::LatencyTimer;
+use crate::utils::resources::{gauge_add, gauge_sub, IN_FLIGHT_ORDERS, QUEUED_ORDERS};
+use anyhow::{anyhow, Result};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{oneshot, Notify, OwnedSemaphorePermit, Semaphore};
+
+// Submission lanes, most urgent first (FYI: a free slot always goes to the highest non-empty lane)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitPriority {
+    Cancel, // Pull resting orders
+    Recovery, // Hedges / unwinds of a lopsided position
+    Entry, // New arbitrage / value legs
+}
+
+impl SubmitPriority {
+    pub const ALL: [SubmitPriority; 3] = [SubmitPriority::Cancel, SubmitPriority::Recovery, SubmitPriority::Entry];
+
+    fn index(&self) -> usize {
+        match self {
+            SubmitPriority::Cancel => 0,
+            SubmitPriority::Recovery => 1,
+            SubmitPriority::Entry => 2,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmitPriority::Cancel => "cancel",
+            SubmitPriority::Recovery => "recovery",
+            SubmitPriority::Entry => "entry",
+        }
+    }
+}
+
+// Queue wait per lane since startup
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaneStats {
+    pub submitted: u64,
+    pub rejected: u64, // Entries turned away with the queue full
+    pub total_wait_ms: u64,
+    pub max_wait_ms: u64,
+}
+
+impl LaneStats {
+    pub fn avg_wait_ms(&self) -> f64 {
+        if self.submitted == 0 {
+            0.0
+        } else {
+            self.total_wait_ms as f64 / self.submitted as f64
+        }
+    }
+}
+
+// Held while one request is on the wire (FYI: frees the in-flight slot on drop)
+pub struct SubmitSlot {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Drop for SubmitSlot {
+    fn drop(&mut self) {
+        gauge_sub(&IN_FLIGHT_ORDERS, 1);
+    }
+}
+
+// Bounded, prioritized submission queue in front of one CLOB connection
+// - ORDER_MAX_IN_FLIGHT requests on the wire at once; queued jobs pipeline instead of waiting on each other
+//   (AFAIK: CLOB orders carry a random salt, not a nonce, so nothing forces them to go out in order)
+// - cancels, then recovery, then entries get the next free slot
+// - only entries are bounded by ORDER_QUEUE_SIZE: a cancel or hedge is never turned away
+pub struct OrderQueue {
+    lanes: Mutex<[VecDeque<oneshot::Sender<OwnedSemaphorePermit>>; 3]>,
+    wake: Notify,
+    in_flight: Arc<Semaphore>,
+    max_queued: usize,
+    stats: Mutex<[LaneStats; 3]>, // This queue's lanes since it was created
+}
+
+impl OrderQueue {
+    // Spawns the dispatcher (BTW: runs until close(), which the owning client calls when it's dropped)
+    pub fn new(env: &Env) -> Arc<Self> {
+        let queue = Arc::new(Self {
+            lanes: Mutex::new([VecDeque::new(), VecDeque::new(), VecDeque::new()]),
+            wake: Notify::new(),
+            in_flight: Arc::new(Semaphore::new(env.order_max_in_flight.max(1))),
+            max_queued: env.order_queue_size.max(1),
+            stats: Mutex::new([LaneStats::default(); 3]),
+        });
+        tokio::spawn(queue.clone().dispatch());
+        queue
+    }
+
+    // Stop the dispatcher (FYI: callers still waiting get "order queue stopped", slots on the wire finish normally)
+    pub fn close(&self) {
+        self.in_flight.close();
+        self.wake.notify_one();
+    }
+
+    pub fn stats(&self) -> Vec<(SubmitPriority, LaneStats)> {
+        let stats = self.stats.lock().map(|s| *s).unwrap_or_default();
+        SubmitPriority::ALL.iter().map(|p| (*p, stats[p.index()])).collect()
+    }
+
+    pub fn stats_json(&self) -> serde_json::Value {
+        let lanes: serde_json::Map<String, serde_json::Value> = self
+            .stats()
+            .into_iter()
+            .map(|(p, s)| {
+                (
+                    p.as_str().to_string(),
+                    json!({
+                        "submitted": s.submitted,
+                        "rejected": s.rejected,
+                        "avg_wait_ms": (s.avg_wait_ms() * 10.0).round() / 10.0,
+                        "max_wait_ms": s.max_wait_ms,
+                    }),
+                )
+            })
+            .collect();
+        serde_json::Value::Object(lanes)
+    }
+
+    fn record(&self, priority: SubmitPriority, waited_ms: Option<u64>) {
+        if let Ok(mut stats) = self.stats.lock() {
+            let lane = &mut stats[priority.index()];
+            match waited_ms {
+                Some(ms) => {
+                    record_order_wait(priority.as_str(), ms);
+                    lane.submitted += 1;
+                    lane.total_wait_ms += ms;
+                    lane.max_wait_ms = lane.max_wait_ms.max(ms);
+                }
+                None => lane.rejected += 1,
+            }
+        }
+    }
+
+    // Wait for an in-flight slot in this lane (Err = entry rejected, queue full)
+    pub async fn acquire(&self, priority: SubmitPriority) -> Result<SubmitSlot> {
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut lanes = self.lanes.lock().map_err(|_| anyhow!("order queue poisoned"))?;
+            let queued: usize = lanes.iter().map(|q| q.len()).sum();
+            if priority == SubmitPriority::Entry && queued >= self.max_queued {
+                drop(lanes);
+                self.record(priority, None);
+                return Err(anyhow!("order queue full ({} waiting)", queued));
+            }
+            lanes[priority.index()].push_back(tx);
+        }
+        gauge_add(&QUEUED_ORDERS, 1);
+        self.wake.notify_one();
+
+        let waited = LatencyTimer::start();
+        let permit = rx.await.map_err(|_| anyhow!("order queue stopped"))?;
+        self.record(priority, Some(waited.elapsed_ms()));
+        gauge_add(&IN_FLIGHT_ORDERS, 1);
+        Ok(SubmitSlot { _permit: permit })
+    }
+
+    // Highest-priority waiter still listening (callers that gave up are dropped here)
+    fn pop(&self) -> Option<oneshot::Sender<OwnedSemaphorePermit>> {
+        let mut lanes = self.lanes.lock().ok()?;
+        for lane in lanes.iter_mut() {
+            while let Some(tx) = lane.pop_front() {
+                gauge_sub(&QUEUED_ORDERS, 1);
+                if !tx.is_closed() {
+                    return Some(tx);
+                }
+            }
+        }
+        None
+    }
+
+    // Slot first, then pick the waiter - so the lane order applies at the moment a slot frees up
+    async fn dispatch(self: Arc<Self>) {
+        loop {
+            let Ok(mut permit) = self.in_flight.clone().acquire_owned().await else {
+                return; // Closed
+            };
+            loop {
+                let waiter = loop {
+                    if self.in_flight.is_closed() {
+                        return;
+                    }
+                    if let Some(tx) = self.pop() {
+                        break tx;
+                    }
+                    self.wake.notified().await;
+                };
+                match waiter.send(permit) {
+                    Ok(()) => break,
+                    Err(returned) => permit = returned, // Caller went away in between, next one
+                }
+            }
+        }
+    }
+}
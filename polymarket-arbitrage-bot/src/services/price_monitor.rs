@@ -1,6 +1,7 @@
 use crate::config::Env;
 use crate::services::market_discovery::CoinMarket;
 use crate::services::websocket_client::OrderbookSnapshot;
+use crate::utils::clock::{market_time_str, now_ms};
 use crate::utils::logger::{log_monitor_data, MonitorData};
 use chrono::{DateTime, Utc};
 use colored::*;
@@ -34,7 +35,7 @@ pub fn create_price_data(
     coin: &str,
     up_snapshot: Option<&OrderbookSnapshot>,
     down_snapshot: Option<&OrderbookSnapshot>,
-    env: &Env,
+    threshold: f64, // From the time-to-expiry schedule
 ) -> PriceData {
     let up_bid = up_snapshot
         .and_then(|s| s.bids.first())
@@ -55,8 +56,8 @@ pub fn create_price_data(
 
     let bid_sum = up_bid + down_bid;
     let ask_sum = up_ask + down_ask;
-    let spread = env.arbitrage_threshold - ask_sum;
-    let has_arbitrage = ask_sum < env.arbitrage_threshold && spread > 0.0;
+    let spread = threshold - ask_sum;
+    let has_arbitrage = ask_sum < threshold && spread > 0.0;
 
     PriceData {
         coin: coin.to_string(),
@@ -68,7 +69,7 @@ pub fn create_price_data(
         ask_sum,
         spread,
         has_arbitrage,
-        timestamp: Utc::now().timestamp_millis(),
+        timestamp: now_ms(),
     }
 }
 
@@ -115,9 +116,9 @@ impl PriceMonitor {
             history.remove(0);
         }
 
-        // Log to monitor.log
+        // Log to monitor_<coin>.log (FYI: written by the logging thread)
         let time_str = format_timestamp(price_data.timestamp);
-        log_monitor_data(MonitorData {
+        log_monitor_data(coin, MonitorData {
             time: time_str,
             bid_up: price_data.up_bid,
             bid_down: price_data.down_bid,
@@ -134,24 +135,21 @@ impl PriceMonitor {
 }
 
 fn format_timestamp(timestamp: i64) -> String {
-    let dt = DateTime::from_timestamp_millis(timestamp).unwrap_or_else(|| Utc::now());
-    let est_offset = chrono::Duration::hours(-5);
-    let est_time = dt + est_offset;
-    est_time.format("%H:%M:%S EST").to_string()
+    market_time_str(timestamp, "%H:%M:%S")
 }
 
+// Full-screen market view (BTW: main runs this on the logging thread via log_console)
 pub fn display_coin_details(
     coin: &str,
     price_data: &PriceData,
     market: &CoinMarket,
-    monitor: &PriceMonitor,
-    env: &Env,
+    arb_history: &[ArbitrageDetection],
 ) {
-    print!("\x1B[2J\x1B[1;1H"); // Clear screen
+    out!("\x1B[2J\x1B[1;1H"); // Clear screen
 
     // Market information header
-    println!("{}", format!("{} - {}", coin, market.question).cyan().bold());
-    println!("{}", format!("Market: {}", market.slug).bright_black());
+    outln!("{}", format!("{} - {}", coin, market.question).cyan().bold());
+    outln!("{}", format!("Market: {}", market.slug).bright_black());
 
     // End date and countdown
     let end_date = DateTime::parse_from_rfc3339(&market.end_date)
@@ -162,43 +160,45 @@ pub fn display_coin_details(
     let mins = time_until_end / 60000;
     let secs = (time_until_end % 60000) / 1000;
 
-    let end_date_str = end_date.format("%m/%d/%Y %I:%M:%S %p").to_string();
+    let end_date_str = market_time_str(end_date.timestamp_millis(), "%m/%d/%Y %I:%M:%S %p");
 
     if time_until_end <= 0 {
-        println!("{}", format!("Ends: {} (MARKET CLOSED)", end_date_str).red().bold());
+        outln!("{}", format!("Ends: {} (MARKET CLOSED)", end_date_str).red().bold());
     } else if time_until_end < 60000 {
-        println!(
+        outln!(
             "{}",
             format!("Ends: {} ({}s remaining - CLOSING SOON!)", end_date_str, secs)
                 .yellow()
                 .bold()
         );
     } else if time_until_end < 300000 {
-        println!(
+        outln!(
             "{}",
             format!("Ends: {} ({}m {}s remaining)", end_date_str, mins, secs).yellow()
         );
     } else {
-        println!(
+        outln!(
             "{}",
             format!("Ends: {} ({}m {}s remaining)", end_date_str, mins, secs).bright_black()
         );
     }
-    println!();
-    println!("{}", "All price data is being logged to monitor.log".bright_black());
-    println!();
+    outln!();
+    outln!(
+        "{}",
+        format!("All price data is being logged to monitor_{}.log", coin.to_lowercase()).bright_black()
+    );
+    outln!();
 
     // Display recent arbitrage detections
-    let arb_history = monitor.get_arbitrage_history(coin);
     if !arb_history.is_empty() {
-        println!("{}", "─".repeat(100).green().bold());
-        println!("{}", "⚡ Recent Arbitrage Detections:".green().bold());
-        println!("{}", "─".repeat(100).bright_black());
+        outln!("{}", "─".repeat(100).green().bold());
+        outln!("{}", "⚡ Recent Arbitrage Detections:".green().bold());
+        outln!("{}", "─".repeat(100).bright_black());
 
         let recent_arb: Vec<_> = arb_history.iter().rev().take(5).collect();
         for arb in recent_arb {
             let time_str = format_timestamp(arb.timestamp);
-            println!(
+            outln!(
                 "{}",
                 format!(
                     "{:12} | UP_ASK={:.4} + DOWN_ASK={:.4} = {:.4} | Spread: {:.4} ({:.2}%)",
@@ -208,9 +208,9 @@ pub fn display_coin_details(
                 .bold()
             );
         }
-        println!();
+        outln!();
     }
 
-    println!("{}", "Press Ctrl+C to exit".yellow());
+    outln!("{}", "Press Ctrl+C to exit".yellow());
 }
 
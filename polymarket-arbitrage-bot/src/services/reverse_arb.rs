@@ -0,0 +1,76 @@
+use crate::services::market_precision::configured_min_tokens;
+use crate::services::opportunity::{OpportunityConfig, MIN_MS_TO_TRADE};
+use crate::services::websocket_client::OrderbookSnapshot;
+use crate::utils::clock::now_ms;
+use chrono::{DateTime, Utc};
+
+// A sell-both the callback should queue (FYI: the mirror of TradePlan - bids instead of asks)
+#[derive(Debug, Clone)]
+pub struct ReversePlan {
+    pub up_bid: f64,
+    pub down_bid: f64,
+    pub bid_sum: f64,
+    pub edge: f64, // bid_sum - (1 + fee) per pair
+    pub tokens: f64, // Pairs to sell (schedule, held inventory and top-of-book size, whichever is smallest)
+    pub opportunity_key: String, // Dedup key (same bids = same opportunity)
+    pub timestamp: i64,
+    pub ms_left: i64,
+}
+
+impl ReversePlan {
+    pub fn expected_proceeds(&self) -> f64 {
+        self.tokens * self.bid_sum
+    }
+}
+
+// Pure detection: UP bid + DOWN bid above 1 + fee, and we hold pairs to sell into it
+//
+// A pair redeems for exactly $1 at resolution, so selling it for more locks in the difference.
+// There's no shorting on Polymarket: inventory is the UP+DOWN pairs this window's buys left us.
+pub fn evaluate_reverse_opportunity(
+    up_book: &OrderbookSnapshot,
+    down_book: &OrderbookSnapshot,
+    cfg: &OpportunityConfig,
+    fee: f64,
+    held_pairs: f64,
+    now: DateTime<Utc>,
+) -> Option<ReversePlan> {
+    let ms_left = cfg.ms_left(now);
+    if ms_left <= MIN_MS_TO_TRADE || held_pairs < configured_min_tokens() {
+        return None;
+    }
+
+    let up = up_book.bids.first()?;
+    let down = down_book.bids.first()?;
+    if up.price <= 0.0 || down.price <= 0.0 {
+        return None;
+    }
+    let bid_sum = up.price + down.price;
+    let edge = bid_sum - (1.0 + fee);
+    if edge <= 0.0 {
+        return None;
+    }
+
+    // Only what the best bids absorb (BTW: deeper levels would eat the edge)
+    let step = cfg.schedule.at(ms_left / 1000);
+    let tokens = cfg
+        .token_amount
+        .min(step.max_tokens)
+        .min(held_pairs)
+        .min(up.size)
+        .min(down.size);
+    if tokens < configured_min_tokens() {
+        return None;
+    }
+
+    Some(ReversePlan {
+        up_bid: up.price,
+        down_bid: down.price,
+        bid_sum,
+        edge,
+        tokens,
+        opportunity_key: format!("rev_{:.4}_{:.4}", up.price, down.price),
+        timestamp: now_ms(),
+        ms_left,
+    })
+}
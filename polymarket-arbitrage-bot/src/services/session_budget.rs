@@ -0,0 +1,252 @@
+use crate::config::Env;
+use crate::services::arbitrage_executor::ArbitrageOrderResult;
+use crate::services::wallet_balance::is_balance_paused;
+use crate::utils::logger::log_error;
+use colored::*;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+// Session-wide spend/loss tracker (FYI: lives for the whole process, survives market roll-overs)
+#[derive(Debug, Clone)]
+pub struct SessionBudget {
+    max_spend_usd: Option<f64>,
+    max_loss_usd: Option<f64>,
+    started_at: chrono::DateTime<chrono::Utc>,
+    pub total_spent: f64, // USDC spent across both legs
+    pub total_received: f64, // USDC back from reverse-arb sales
+    pub hedge_spent: f64, // USDC spent on 1h hedges (also in total_spent, never counted as hedged payout)
+    pub value_spent: f64, // USDC spent on single-sided value buys (same treatment as hedges)
+    pub hedged_payout: f64, // Guaranteed payout (min of UP/DOWN tokens per trade)
+    pub trades: u32,
+    pub failed_legs: u32,
+    reserved_usd: f64, // Planned spend of queued / in-flight trades (BTW: counts against SESSION_MAX_SPEND_USD until settled)
+    halt_reason: Option<String>,
+}
+
+impl SessionBudget {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            max_spend_usd: env.session_max_spend_usd,
+            max_loss_usd: env.session_max_loss_usd,
+            started_at: chrono::Utc::now(),
+            total_spent: 0.0,
+            total_received: 0.0,
+            hedge_spent: 0.0,
+            value_spent: 0.0,
+            hedged_payout: 0.0,
+            trades: 0,
+            failed_legs: 0,
+            reserved_usd: 0.0,
+            halt_reason: None,
+        }
+    }
+
+    // Worst-case loss = spend not covered by a hedged payout (BTW: unhedged legs count fully)
+    pub fn worst_case_loss(&self) -> f64 {
+        (self.total_spent - self.total_received - self.hedged_payout).max(0.0)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halt_reason.is_some()
+    }
+
+    // Check before placing a trade (AFAIK: refuses if the planned spend would cross the cap)
+    // BTW: also refuses while the wallet is under MIN_WALLET_BALANCE_USD - that pause lifts by itself after a top-up
+    pub fn can_trade(&self, planned_spend: f64) -> bool {
+        self.blocked(planned_spend).is_none()
+    }
+
+    // Why a trade of `planned_spend` can't go out now (FYI: spend already held by queued trades counts too)
+    fn blocked(&self, planned_spend: f64) -> Option<String> {
+        if let Some(ref reason) = self.halt_reason {
+            return Some(format!("Trading halted ({})", reason));
+        }
+        if is_balance_paused() {
+            return Some("Wallet under MIN_WALLET_BALANCE_USD".to_string());
+        }
+        let committed = self.total_spent + self.reserved_usd;
+        match self.max_spend_usd {
+            Some(max) if committed + planned_spend > max => Some(format!(
+                "SESSION_MAX_SPEND_USD reached (${:.2} spent or queued + ${:.2} > ${:.2})",
+                committed, planned_spend, max
+            )),
+            _ => None,
+        }
+    }
+
+    // Hold the planned spend for a trade about to be queued (None = over the session caps)
+    // BTW: every queued job used to pass the same check, so a burst across markets could overshoot the cap
+    pub async fn reserve(budget: &Arc<Mutex<SessionBudget>>, planned_spend: f64) -> Option<BudgetHold> {
+        let mut guard = budget.lock().await;
+        if !guard.can_trade(planned_spend) {
+            return None;
+        }
+        guard.reserved_usd += planned_spend;
+        Some(BudgetHold {
+            budget: budget.clone(),
+            planned_usd: planned_spend,
+            settled: false,
+        })
+    }
+
+    fn release(&mut self, planned_spend: f64) {
+        self.reserved_usd = (self.reserved_usd - planned_spend).max(0.0);
+    }
+
+    // Record a finished arbitrage attempt and halt if a limit was hit
+    pub fn record_trade(&mut self, up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) {
+        let up_tokens = if up.success { up.tokens_bought.unwrap_or(0.0) } else { 0.0 };
+        let down_tokens = if down.success { down.tokens_bought.unwrap_or(0.0) } else { 0.0 };
+        if up.success {
+            self.total_spent += up.amount;
+        } else {
+            self.failed_legs += 1;
+        }
+        if down.success {
+            self.total_spent += down.amount;
+        } else {
+            self.failed_legs += 1;
+        }
+        self.hedged_payout += up_tokens.min(down_tokens);
+        self.trades += 1;
+        self.check_limits();
+    }
+
+    // Record a 1h hedge fill (BTW: its payout depends on the hour, so the worst case counts it as lost)
+    pub fn record_hedge(&mut self, amount_usd: f64) {
+        self.total_spent += amount_usd;
+        self.hedge_spent += amount_usd;
+        self.check_limits();
+    }
+
+    // Record a value buy (BTW: one side only, so the worst case counts the whole stake as lost)
+    pub fn record_value(&mut self, amount_usd: f64) {
+        self.total_spent += amount_usd;
+        self.value_spent += amount_usd;
+        self.check_limits();
+    }
+
+    // Halt once spend or worst-case loss crosses its cap
+    fn check_limits(&mut self) {
+        if self.is_halted() {
+            return;
+        }
+        if let Some(max) = self.max_spend_usd {
+            if self.total_spent >= max {
+                self.halt(format!(
+                    "SESSION_MAX_SPEND_USD reached (${:.2} / ${:.2})",
+                    self.total_spent, max
+                ));
+                return;
+            }
+        }
+        if let Some(max) = self.max_loss_usd {
+            let loss = self.worst_case_loss();
+            if loss >= max {
+                self.halt(format!(
+                    "SESSION_MAX_LOSS_USD reached (${:.2} / ${:.2})",
+                    loss, max
+                ));
+            }
+        }
+    }
+
+    // Record a reverse-arb sale (IMO: sold pairs trade their guaranteed $1 for the bids received)
+    pub fn record_sale(&mut self, up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) {
+        let sold = |r: &ArbitrageOrderResult| if r.success { r.tokens_bought.unwrap_or(0.0) } else { 0.0 };
+        self.total_received += [up, down].iter().filter(|r| r.success).map(|r| r.amount).sum::<f64>();
+        self.hedged_payout = (self.hedged_payout - sold(up).min(sold(down))).max(0.0);
+        self.failed_legs += [up, down].iter().filter(|r| !r.success).count() as u32;
+    }
+
+    fn halt(&mut self, reason: String) {
+        log_error(&format!("Trading halted: {}", reason), Some("sessionBudget"));
+        self.halt_reason = Some(reason);
+        self.print_summary();
+    }
+
+    pub fn print_summary(&self) {
+        let runtime_mins = (chrono::Utc::now() - self.started_at).num_minutes();
+        let status = match self.halt_reason {
+            Some(ref reason) => format!("HALTED - {}", reason),
+            None => "Active".to_string(),
+        };
+        outln!(
+            "{}",
+            format!(
+                "\n╔════════════════════════════════════════════════════════════════╗\n║                      SESSION SUMMARY                           ║\n╚════════════════════════════════════════════════════════════════╝\n  Status: {}\n  Runtime: {} minute(s)\n  Trades: {} ({} failed leg(s))\n  Total Spent: ${:.2} USDC\n  Sold Back: ${:.2} USDC\n  1h Hedges: ${:.2} USDC\n  Value Buys: ${:.2} USDC\n  Hedged Payout: ${:.2} USDC\n  Worst-case Loss: ${:.2} USDC\n",
+                status,
+                runtime_mins,
+                self.trades,
+                self.failed_legs,
+                self.total_spent,
+                self.total_received,
+                self.hedge_spent,
+                self.value_spent,
+                self.hedged_payout,
+                self.worst_case_loss()
+            )
+            .yellow()
+            .bold()
+        );
+        if self.is_halted() {
+            outln!(
+                "{}",
+                "  Detection continues, but no more trades will be placed.\n  Restart the bot to begin a new session.\n"
+                    .yellow()
+            );
+        }
+    }
+}
+
+// Session spend held for one queued trade (FYI: dropped unsettled = released, same as the ledger's Reservation)
+pub struct BudgetHold {
+    budget: Arc<Mutex<SessionBudget>>,
+    planned_usd: f64,
+    settled: bool,
+}
+
+impl BudgetHold {
+    // Re-check the caps at send time with the final size (AFAIK: the hold's own spend doesn't count against it)
+    pub async fn begin(&mut self, planned_usd: f64) -> Result<(), String> {
+        let mut budget = self.budget.lock().await;
+        budget.release(self.planned_usd);
+        self.planned_usd = 0.0;
+        if let Some(reason) = budget.blocked(planned_usd) {
+            return Err(reason);
+        }
+        budget.reserved_usd += planned_usd;
+        self.planned_usd = planned_usd;
+        Ok(())
+    }
+
+    // Swap the hold for the arbitrage fills
+    pub async fn settle(mut self, up: &ArbitrageOrderResult, down: &ArbitrageOrderResult) {
+        let mut budget = self.budget.lock().await;
+        budget.release(self.planned_usd);
+        budget.record_trade(up, down);
+        self.settled = true;
+    }
+
+    // Swap the hold for a filled value buy
+    pub async fn settle_value(mut self, amount_usd: f64) {
+        let mut budget = self.budget.lock().await;
+        budget.release(self.planned_usd);
+        budget.record_value(amount_usd);
+        self.settled = true;
+    }
+}
+
+impl Drop for BudgetHold {
+    fn drop(&mut self) {
+        if self.settled || self.planned_usd <= 0.0 {
+            return;
+        }
+        // Drop can't await (IMO: a detached release is fine, it only ever lowers reserved_usd)
+        let budget = self.budget.clone();
+        let planned_usd = self.planned_usd;
+        tokio::spawn(async move {
+            budget.lock().await.release(planned_usd);
+        });
+    }
+}
@@ -0,0 +1,130 @@
+use crate::config::Env;
+use crate::services::market_discovery::CoinMarket;
+use crate::services::market_ledger::MarketLedger;
+use crate::services::neg_risk::CONDITIONAL_TOKENS;
+use crate::utils::logger::{log_console, log_error, LogLevel};
+use anyhow::{anyhow, Result};
+use colored::*;
+use ethers::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration, Instant};
+
+const TOKEN_DECIMALS: i32 = 6; // Outcome tokens use USDC decimals
+const POLL_INTERVAL: Duration = Duration::from_secs(2); // ~1 Polygon block
+
+abigen!(
+    Erc1155Balance,
+    r#"[
+        function balanceOf(address account, uint256 id) external view returns (uint256)
+    ]"#
+);
+
+// Checks reported fills against CTF balances (FYI: the API can say "matched" for a trade that never settles)
+pub struct SettlementVerifier {
+    ctf: Erc1155Balance<Provider<Http>>,
+    holder: Address,
+    timeout: Duration,
+    tolerance: f64,
+    baselines: Mutex<HashMap<String, f64>>, // Balance per token before this window's first trade
+}
+
+impl SettlementVerifier {
+    // None = off (SETTLEMENT_VERIFY_SECS=0, ALERT_ONLY or no PROXY_WALLET)
+    pub fn new(env: &Env) -> Result<Option<Arc<Self>>> {
+        if env.settlement_verify_secs == 0 || env.alert_only {
+            return Ok(None);
+        }
+        let Some(holder) = env.proxy_wallet.as_ref() else {
+            return Ok(None);
+        };
+        let provider = Provider::<Http>::try_from(&env.rpc_url)?;
+        Ok(Some(Arc::new(Self {
+            ctf: Erc1155Balance::new(CONDITIONAL_TOKENS.parse::<Address>()?, Arc::new(provider)),
+            holder: holder.parse()?,
+            timeout: Duration::from_secs(env.settlement_verify_secs),
+            tolerance: env.settlement_tolerance,
+            baselines: Mutex::new(HashMap::new()),
+        })))
+    }
+
+    pub async fn balance(&self, token_id: &str) -> Result<f64> {
+        let id = U256::from_dec_str(token_id).map_err(|e| anyhow!("Bad token id {}: {}", token_id, e))?;
+        let raw = self.ctf.balance_of(self.holder, id).call().await?;
+        Ok(raw.as_u128() as f64 / 10f64.powi(TOKEN_DECIMALS))
+    }
+
+    // Read both tokens before anything trades (IMO: at discovery, so it never sits on the order path - the engine
+    // awaits it before the market's book callback exists)
+    pub async fn record_baselines(&self, market: &CoinMarket) {
+        for token in [&market.up_token_id, &market.down_token_id] {
+            if self.baselines.lock().await.contains_key(token) {
+                continue;
+            }
+            match self.balance(token).await {
+                Ok(balance) => {
+                    self.baselines.lock().await.insert(token.clone(), balance);
+                }
+                Err(e) => log_error(&format!("Settlement baseline for {} failed: {}", market.slug, e), Some("settlement")),
+            }
+        }
+    }
+
+    // After a trade is booked: wait for the chain to show what the ledger holds, reconcile if it never does
+    pub fn spawn_verify(self: &Arc<Self>, ledger: Arc<Mutex<MarketLedger>>, market: Arc<CoinMarket>) {
+        let verifier = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = verifier.verify(&ledger, &market).await {
+                log_error(&format!("Settlement check for {} failed: {}", market.slug, e), Some("settlement"));
+            }
+        });
+    }
+
+    async fn verify(&self, ledger: &Arc<Mutex<MarketLedger>>, market: &CoinMarket) -> Result<()> {
+        let (up_base, down_base) = {
+            let baselines = self.baselines.lock().await;
+            match (baselines.get(&market.up_token_id), baselines.get(&market.down_token_id)) {
+                (Some(up), Some(down)) => (*up, *down),
+                _ => return Ok(()), // No baseline = nothing to compare against
+            }
+        };
+        let started = Instant::now();
+        let version = ledger.lock().await.settled_trades(&market.slug);
+
+        loop {
+            let (up_expected, down_expected) = ledger.lock().await.expected_holdings(&market.slug);
+            let up_seen = self.balance(&market.up_token_id).await? - up_base;
+            let down_seen = self.balance(&market.down_token_id).await? - down_base;
+            if (up_seen - up_expected).abs() <= self.tolerance && (down_seen - down_expected).abs() <= self.tolerance {
+                let secs = started.elapsed().as_secs_f64();
+                log_console(LogLevel::Debug, move || {
+                    outln!("{}", format!("   ✓ Fills settled on-chain after {:.1}s", secs).bright_black());
+                });
+                return Ok(());
+            }
+            if started.elapsed() >= self.timeout {
+                // A newer trade settled meanwhile - its own check owns the comparison now
+                if ledger.lock().await.settled_trades(&market.slug) != version {
+                    return Ok(());
+                }
+                let msg = format!(
+                    "Settlement mismatch on {} after {}s: ledger UP {:.2} / DOWN {:.2}, on-chain UP {:.2} / DOWN {:.2} - ledger reconciled to chain",
+                    market.slug,
+                    self.timeout.as_secs(),
+                    up_expected,
+                    down_expected,
+                    up_seen,
+                    down_seen
+                );
+                log_error(&msg, Some("settlement"));
+                log_console(LogLevel::Warn, move || {
+                    outln!("{}", format!("⚠️  {}", msg).yellow().bold());
+                });
+                ledger.lock().await.reconcile(&market.slug, up_seen, down_seen);
+                return Ok(());
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+}
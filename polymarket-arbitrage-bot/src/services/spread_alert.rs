@@ -0,0 +1,109 @@
+use crate::config::Env;
+use crate::services::opportunity::TradePlan;
+use crate::services::websocket_client::{OrderbookLevel, OrderbookSnapshot};
+use crate::utils::logger::{log_alert, log_console, log_error, LogLevel};
+use colored::*;
+use serde_json::json;
+
+// Ask levels kept per side in the alert snapshot
+const SNAPSHOT_LEVELS: usize = 3;
+
+// One detected spread in ALERT_ONLY mode (FYI: what we'd have traded, with the books that showed it)
+#[derive(Debug, Clone)]
+pub struct SpreadAlert {
+    pub coin: String,
+    pub market_slug: String,
+    pub timestamp: i64,
+    pub secs_left: i64,
+    pub up_ask: f64,
+    pub down_ask: f64,
+    pub ask_sum: f64,
+    pub fillable_tokens: f64, // Plan size capped by the top-of-book size on both sides
+    pub estimated_profit_usd: f64, // fillable_tokens * (1 - ask_sum), before fees
+    pub up_asks: Vec<OrderbookLevel>,
+    pub down_asks: Vec<OrderbookLevel>,
+}
+
+impl SpreadAlert {
+    pub fn new(coin: &str, market_slug: &str, plan: &TradePlan, up: &OrderbookSnapshot, down: &OrderbookSnapshot) -> Self {
+        let top_size = |book: &OrderbookSnapshot| book.asks.first().map(|l| l.size).unwrap_or(0.0);
+        let fillable_tokens = plan.tokens.min(top_size(up)).min(top_size(down));
+        Self {
+            coin: coin.to_string(),
+            market_slug: market_slug.to_string(),
+            timestamp: plan.prices.timestamp,
+            secs_left: plan.ms_left / 1000,
+            up_ask: plan.prices.up_ask,
+            down_ask: plan.prices.down_ask,
+            ask_sum: plan.prices.ask_sum,
+            fillable_tokens,
+            estimated_profit_usd: fillable_tokens * (1.0 - plan.prices.ask_sum),
+            up_asks: up.asks.iter().take(SNAPSHOT_LEVELS).cloned().collect(),
+            down_asks: down.asks.iter().take(SNAPSHOT_LEVELS).cloned().collect(),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} spread: UP {:.4} + DOWN {:.4} = {:.4} | ~${:.2} on {:.2} tokens | {}s left | {}",
+            self.coin,
+            self.up_ask,
+            self.down_ask,
+            self.ask_sum,
+            self.estimated_profit_usd,
+            self.fillable_tokens,
+            self.secs_left,
+            self.market_slug
+        )
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let levels = |l: &[OrderbookLevel]| l.iter().map(|x| json!([x.price, x.size])).collect::<Vec<_>>();
+        json!({
+            "coin": self.coin,
+            "market": self.market_slug,
+            "timestamp": self.timestamp,
+            "secsLeft": self.secs_left,
+            "upAsk": self.up_ask,
+            "downAsk": self.down_ask,
+            "askSum": self.ask_sum,
+            "fillableTokens": self.fillable_tokens,
+            "estimatedProfitUsd": self.estimated_profit_usd,
+            "upAsks": levels(&self.up_asks),
+            "downAsks": levels(&self.down_asks),
+        })
+    }
+}
+
+// Console + alerts.log (JSONL) + ALERT_WEBHOOK_URL if set (BTW: never touches the order path)
+pub async fn publish_alert(alert: &SpreadAlert, env: &Env) {
+    let summary = alert.summary();
+    let console = summary.clone();
+    log_console(LogLevel::Warn, move || {
+        outln!("{}", format!("🔔 {}", console).yellow().bold());
+    });
+    log_alert(alert.to_json().to_string());
+
+    post_webhook(env, &summary, alert.to_json()).await;
+}
+
+// POST to ALERT_WEBHOOK_URL if set - "text" (Slack) + "content" (Discord) so common webhooks render it as-is
+pub async fn post_webhook(env: &Env, summary: &str, alert: serde_json::Value) {
+    let Some(ref url) = env.alert_webhook_url else {
+        return;
+    };
+    let body = json!({ "text": summary, "content": summary, "alert": alert });
+    let result = reqwest::Client::new()
+        .post(url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await;
+    match result {
+        Ok(resp) if !resp.status().is_success() => {
+            log_error(&format!("Alert webhook returned HTTP {}", resp.status()), Some("alert"))
+        }
+        Err(e) => log_error(&format!("Alert webhook failed: {}", e), Some("alert")),
+        _ => {}
+    }
+}
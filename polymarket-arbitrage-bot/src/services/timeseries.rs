@@ -0,0 +1,178 @@
+use crate::config::Env;
+use crate::services::price_monitor::PriceData;
+use crate::services::trade_journal::JournalEntry;
+use crate::utils::clock::now_ms;
+use crate::utils::logger::log_error;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::mpsc;
+use tokio::time::{interval, Duration};
+
+// Points waiting for the writer (FYI: past this they're dropped - dashboards never slow the engine down)
+const MAX_PENDING_POINTS: usize = 10_000;
+// Points per HTTP write at most
+const MAX_BATCH_POINTS: usize = 5_000;
+
+// Streams engine metrics to InfluxDB (v2 write API, line protocol) for live Grafana dashboards
+// - arb_book: ask_sum / spread / best asks + bids per market, sampled every INFLUX_SAMPLE_MS
+// - arb_trade: one point per trade attempt (sizes, USDC, detection -> orders latency)
+// - arb_order_wait: time each order waited for an in-flight slot, per submission lane
+// - arb_ws_unknown: first sighting of an unknown market channel type / field (annotation-style events)
+struct TimeseriesSink {
+    tx: mpsc::Sender<String>,
+    sample_ms: i64,
+    last_sample: Mutex<HashMap<String, i64>>, // Market -> last arb_book point
+}
+
+static SINK: OnceLock<TimeseriesSink> = OnceLock::new();
+
+// Start the writer if INFLUX_URL is set (BTW: safe to call from every engine, only the first one starts it)
+pub fn start_timeseries(env: &Env) {
+    let Some(url) = env.influx_url.clone() else {
+        return;
+    };
+    let (tx, rx) = mpsc::channel(MAX_PENDING_POINTS);
+    let sink = TimeseriesSink {
+        tx,
+        sample_ms: env.influx_sample_ms as i64,
+        last_sample: Mutex::new(HashMap::new()),
+    };
+    if SINK.set(sink).is_err() {
+        return;
+    }
+    let write_url = format!(
+        "{}/api/v2/write?org={}&bucket={}&precision=ms",
+        url.trim_end_matches('/'),
+        env.influx_org,
+        env.influx_bucket
+    );
+    tokio::spawn(run_writer(write_url, env.influx_token.clone(), env.influx_flush_ms.max(100), rx));
+}
+
+// Batches lines and POSTs them every INFLUX_FLUSH_MS (a failed batch is dropped and logged, not retried)
+async fn run_writer(url: String, token: Option<String>, flush_ms: u64, mut rx: mpsc::Receiver<String>) {
+    let client = reqwest::Client::new();
+    let mut ticker = interval(Duration::from_millis(flush_ms));
+    let mut batch: Vec<String> = Vec::new();
+    loop {
+        tokio::select! {
+            line = rx.recv() => match line {
+                Some(line) => {
+                    batch.push(line);
+                    if batch.len() < MAX_BATCH_POINTS {
+                        continue;
+                    }
+                }
+                None => return,
+            },
+            _ = ticker.tick() => {}
+        }
+        if batch.is_empty() {
+            continue;
+        }
+        let mut request = client
+            .post(&url)
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .timeout(Duration::from_secs(10))
+            .body(batch.join("\n"));
+        if let Some(ref token) = token {
+            request = request.header("Authorization", format!("Token {}", token));
+        }
+        let points = batch.len();
+        batch.clear();
+        match request.send().await {
+            Ok(resp) if !resp.status().is_success() => log_error(
+                &format!("Time-series write of {} points returned HTTP {}", points, resp.status()),
+                Some("timeseries"),
+            ),
+            Err(e) => log_error(&format!("Time-series write of {} points failed: {}", points, e), Some("timeseries")),
+            _ => {}
+        }
+    }
+}
+
+// Line protocol tag value (AFAIK: commas, spaces and '=' must be escaped)
+fn tag(value: &str) -> String {
+    value.replace(',', "\\,").replace(' ', "\\ ").replace('=', "\\=")
+}
+
+fn send(line: String) {
+    if let Some(sink) = SINK.get() {
+        let _ = sink.tx.try_send(line); // Full = dropped
+    }
+}
+
+// Best prices for one market (BTW: called on every book update, thinned out to INFLUX_SAMPLE_MS per market)
+pub fn record_prices(market: &str, prices: &PriceData, secs_left: i64) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    let now = now_ms();
+    {
+        let Ok(mut last) = sink.last_sample.lock() else {
+            return;
+        };
+        if last.get(market).is_some_and(|t| now - t < sink.sample_ms) {
+            return;
+        }
+        last.insert(market.to_string(), now);
+    }
+    send(format!(
+        "arb_book,coin={},market={} ask_sum={},bid_sum={},spread={},up_ask={},down_ask={},up_bid={},down_bid={},secs_left={}i {}",
+        tag(&prices.coin),
+        tag(market),
+        prices.ask_sum,
+        prices.bid_sum,
+        prices.spread,
+        prices.up_ask,
+        prices.down_ask,
+        prices.up_bid,
+        prices.down_bid,
+        secs_left,
+        now
+    ));
+}
+
+// One trade attempt, as written to the journal
+pub fn record_trade(entry: &JournalEntry) {
+    if SINK.get().is_none() {
+        return;
+    }
+    let top_ask = |asks: &[[f64; 2]]| asks.first().map(|l| l[0]).unwrap_or(0.0);
+    let detected_ask_sum = top_ask(&entry.detected.up.asks) + top_ask(&entry.detected.down.asks);
+    let (up_tokens, up_usd) = entry.up.as_ref().map(|l| (l.tokens, l.amount_usd)).unwrap_or_default();
+    let (down_tokens, down_usd) = entry.down.as_ref().map(|l| (l.tokens, l.amount_usd)).unwrap_or_default();
+    let mut fields = format!(
+        "detected_ask_sum={},up_tokens={},down_tokens={},up_usd={},down_usd={}",
+        detected_ask_sum, up_tokens, down_tokens, up_usd, down_usd
+    );
+    if let Some(latency) = entry.latency_ms() {
+        fields.push_str(&format!(",latency_ms={}i", latency));
+    }
+    send(format!(
+        "arb_trade,coin={},market={},kind={},outcome={} {} {}",
+        tag(&entry.coin),
+        tag(&entry.market),
+        tag(&entry.kind),
+        tag(&entry.outcome),
+        fields,
+        now_ms()
+    ));
+}
+
+// Queue wait of one order request (lane = cancel / recovery / entry)
+pub fn record_order_wait(lane: &str, wait_ms: u64) {
+    if SINK.get().is_none() {
+        return;
+    }
+    send(format!("arb_order_wait,lane={} wait_ms={}i {}", tag(lane), wait_ms, now_ms()));
+}
+
+// First sighting of an unknown WS message type (field empty) or field
+pub fn record_ws_unknown(kind: &str, field: &str) {
+    if SINK.get().is_none() {
+        return;
+    }
+    let field = if field.is_empty() { "-" } else { field };
+    send(format!("arb_ws_unknown,kind={},field={} count=1i {}", tag(kind), tag(field), now_ms()));
+}
@@ -0,0 +1,135 @@
+use crate::config::Env;
+use crate::services::fill_score::record_fill_outcome;
+use crate::services::arbitrage_executor::ArbitrageOrderResult;
+use crate::services::market_discovery::CoinMarket;
+use crate::services::market_ledger::LegFill;
+use crate::services::timeseries::record_trade;
+use crate::services::websocket_client::{MarketWebSocket, OrderbookSnapshot};
+use crate::utils::clock::now_ms;
+use crate::utils::logger::log_journal;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// Levels kept per side (FYI: enough to see what was behind the top when the spread vanished)
+pub const JOURNAL_LEVELS: usize = 5;
+
+// Top of one token's book as [price, size] pairs, best first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookView {
+    pub timestamp: i64, // Book timestamp from the feed
+    pub bids: Vec<[f64; 2]>,
+    pub asks: Vec<[f64; 2]>,
+}
+
+impl BookView {
+    pub fn capture(book: &OrderbookSnapshot) -> Self {
+        let top = |levels: &[crate::services::websocket_client::OrderbookLevel]| {
+            levels.iter().take(JOURNAL_LEVELS).map(|l| [l.price, l.size]).collect()
+        };
+        Self {
+            timestamp: book.timestamp,
+            bids: top(&book.bids),
+            asks: top(&book.asks),
+        }
+    }
+}
+
+// Both books at one moment (BTW: `at` is our clock, the books carry the feed's)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookPair {
+    pub at: i64,
+    pub up: BookView,
+    pub down: BookView,
+}
+
+impl BookPair {
+    pub fn capture(up: &OrderbookSnapshot, down: &OrderbookSnapshot) -> Self {
+        Self {
+            at: now_ms(),
+            up: BookView::capture(up),
+            down: BookView::capture(down),
+        }
+    }
+
+    // Whatever the WS cache holds right now (None = a book went missing)
+    pub fn from_ws(ws: &MarketWebSocket, market: &CoinMarket) -> Option<Self> {
+        let up = ws.get_orderbook(&market.up_token_id)?;
+        let down = ws.get_orderbook(&market.down_token_id)?;
+        Some(Self::capture(&up, &down))
+    }
+}
+
+// One traded (or aborted) opportunity in journal.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub coin: String,
+    pub market: String,
+    pub kind: String, // "arbitrage", "reverse" or "value-up" / "value-down"
+    pub opportunity_key: String,
+    pub outcome: String, // "filled", "partial", "failed" or "aborted" (edge gone by execution time)
+    pub detected: BookPair,
+    pub ms_left: Option<i64>, // Time left in the window at detection (FYI: feeds the fill-score model)
+    pub executed: Option<BookPair>, // Books right before the orders went out (or when the re-check gave up)
+    pub up: Option<LegFill>,
+    pub down: Option<LegFill>,
+}
+
+impl JournalEntry {
+    pub fn new(market: &CoinMarket, kind: &str, opportunity_key: &str, detected: BookPair) -> Self {
+        Self {
+            coin: market.coin.clone(),
+            market: market.slug.clone(),
+            kind: kind.to_string(),
+            opportunity_key: opportunity_key.to_string(),
+            outcome: "aborted".to_string(),
+            detected,
+            ms_left: None,
+            executed: None,
+            up: None,
+            down: None,
+        }
+    }
+
+    // Detection -> orders out (None when it never got that far)
+    pub fn latency_ms(&self) -> Option<i64> {
+        self.executed.as_ref().map(|e| e.at - self.detected.at)
+    }
+
+    // Fill the legs in (either may be absent for single-sided trades, both when the trade errored)
+    pub fn with_results(mut self, up: Option<&ArbitrageOrderResult>, down: Option<&ArbitrageOrderResult>) -> Self {
+        let legs: Vec<&ArbitrageOrderResult> = up.iter().chain(down.iter()).copied().collect();
+        self.outcome = if legs.is_empty() {
+            "failed"
+        } else if legs.iter().all(|r| r.success && !r.partially_filled) {
+            "filled"
+        } else if legs.iter().any(|r| r.success) {
+            "partial"
+        } else {
+            "failed"
+        }
+        .to_string();
+        self.up = up.map(LegFill::from);
+        self.down = down.map(LegFill::from);
+        self
+    }
+
+    pub fn record(&self, env: &Env) {
+        record_trade(self); // INFLUX_URL dashboards (no-op when off)
+        record_fill_outcome(self); // MIN_FILL_SCORE model
+        if !env.trade_journal {
+            return;
+        }
+        if let Ok(line) = serde_json::to_string(self) {
+            log_journal(line);
+        }
+    }
+}
+
+// Entries in file order (unparseable lines skipped, e.g. one cut off by a crash)
+pub fn read_journal(path: &Path) -> anyhow::Result<Vec<JournalEntry>> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(text.lines().filter_map(|l| serde_json::from_str(l).ok()).collect())
+}
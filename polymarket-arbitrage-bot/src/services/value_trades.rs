@@ -0,0 +1,233 @@
+use crate::config::Env;
+use crate::services::market_precision::configured_min_tokens;
+use crate::services::opportunity::{OpportunityConfig, MIN_MS_TO_TRADE};
+use crate::services::websocket_client::OrderbookSnapshot;
+use crate::utils::clock::now_ms;
+use crate::utils::logger::{log_console, LogLevel};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration};
+
+// Minutes of 1m candles behind the volatility estimate
+const VOL_LOOKBACK_MINS: usize = 60;
+
+// Spot inputs for the model (FYI: strike = spot at the window open, that's what "Up" is judged against)
+#[derive(Debug, Clone, Copy)]
+pub struct SpotModel {
+    pub spot: f64,
+    pub strike: f64,
+    pub sigma_per_sqrt_sec: f64, // Realized vol of log returns, scaled to one second
+    pub updated_ms: i64,
+}
+
+impl SpotModel {
+    // P(close >= strike) under a driftless lognormal (IMO: Black-Scholes digital, good enough for 15 minutes)
+    pub fn up_probability(&self, secs_left: f64) -> Option<f64> {
+        if self.spot <= 0.0 || self.strike <= 0.0 || self.sigma_per_sqrt_sec <= 0.0 || secs_left <= 0.0 {
+            return None;
+        }
+        let d = (self.spot / self.strike).ln() / (self.sigma_per_sqrt_sec * secs_left.sqrt());
+        Some(normal_cdf(d))
+    }
+}
+
+// Standard normal CDF (AFAIK: Abramowitz-Stegun 7.1.26, error < 1.5e-7)
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+// Binance-style 1m klines -> (open_time_ms, open, close)
+async fn fetch_klines(api: &str, symbol: &str, query: &str) -> Result<Vec<(i64, f64, f64)>> {
+    let url = format!("{}/api/v3/klines?symbol={}&interval=1m&{}", api.trim_end_matches('/'), symbol, query);
+    let rows: Vec<Vec<Value>> = reqwest::Client::new()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let num = |v: &Value| v.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| v.as_f64());
+    Ok(rows
+        .iter()
+        .filter_map(|r| Some((r.first()?.as_i64()?, num(r.get(1)?)?, num(r.get(4)?)?)))
+        .collect())
+}
+
+// Spot, window-open strike and realized vol for one coin (BTW: the last candle is still open, its close = last trade)
+pub async fn fetch_spot_model(api: &str, coin: &str, window_start_ms: i64) -> Result<SpotModel> {
+    let symbol = format!("{}USDT", coin.to_uppercase());
+    let recent = fetch_klines(api, &symbol, &format!("limit={}", VOL_LOOKBACK_MINS + 1)).await?;
+    let spot = recent.last().map(|k| k.2).ok_or_else(|| anyhow!("no candles for {}", symbol))?;
+    let strike = fetch_klines(api, &symbol, &format!("startTime={}&limit=1", window_start_ms))
+        .await?
+        .first()
+        .filter(|k| k.0 == window_start_ms)
+        .map(|k| k.1)
+        .ok_or_else(|| anyhow!("no {} candle at the window open", symbol))?;
+
+    let returns: Vec<f64> = recent
+        .windows(2)
+        .filter(|w| w[0].2 > 0.0 && w[1].2 > 0.0)
+        .map(|w| (w[1].2 / w[0].2).ln())
+        .collect();
+    if returns.len() < 10 {
+        return Err(anyhow!("not enough candles for a volatility estimate"));
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let var = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Ok(SpotModel {
+        spot,
+        strike,
+        sigma_per_sqrt_sec: var.sqrt() / 60f64.sqrt(),
+        updated_ms: now_ms(),
+    })
+}
+
+// Keep the model fresh until the window ends (FYI: one task per market window, only with VALUE_TRADES)
+pub fn spawn_spot_feed(coin: String, cfg: Arc<OpportunityConfig>, env: Env) -> Arc<Mutex<Option<SpotModel>>> {
+    let shared = Arc::new(Mutex::new(None));
+    let slot = shared.clone();
+    let window_start_ms = (cfg.market_end.timestamp() - 900) * 1000;
+    tokio::spawn(async move {
+        let mut failing = false;
+        while cfg.ms_left(Utc::now()) > 0 {
+            match fetch_spot_model(&env.value_spot_api, &coin, window_start_ms).await {
+                Ok(model) => {
+                    failing = false;
+                    if let Ok(mut slot) = slot.lock() {
+                        *slot = Some(model);
+                    }
+                }
+                // Log the first failure only (BTW: stale models are ignored by the evaluator anyway)
+                Err(e) if !failing => {
+                    failing = true;
+                    let coin = coin.clone();
+                    log_console(LogLevel::Warn, move || {
+                        outln!("{}", format!("   {} spot model unavailable (value trades paused): {}", coin, e).yellow());
+                    });
+                }
+                Err(_) => {}
+            }
+            sleep(Duration::from_secs(env.value_spot_refresh_secs.max(1))).await;
+        }
+    });
+    shared
+}
+
+// A single-sided buy the callback should queue (FYI: one outcome, priced off the model instead of the other book)
+#[derive(Debug, Clone)]
+pub struct ValuePlan {
+    pub side: &'static str, // "UP" or "DOWN"
+    pub ask: f64,
+    pub model_price: f64, // Model probability of this outcome
+    pub edge: f64, // model_price - ask
+    pub tokens: f64,
+    pub opportunity_key: String,
+    pub timestamp: i64,
+}
+
+impl ValuePlan {
+    pub fn planned_spend(&self) -> f64 {
+        self.tokens * self.ask
+    }
+}
+
+// Pure detection: the outcome whose ask sits furthest below the model price, if it clears VALUE_MIN_EDGE
+pub fn evaluate_value_opportunity(
+    up_book: &OrderbookSnapshot,
+    down_book: &OrderbookSnapshot,
+    cfg: &OpportunityConfig,
+    model: &SpotModel,
+    env: &Env,
+    now: DateTime<Utc>,
+) -> Option<ValuePlan> {
+    let ms_left = cfg.ms_left(now);
+    // A model older than a few refreshes is worse than none (BTW: spot moves faster than the books here)
+    let max_age_ms = env.value_spot_refresh_secs.max(1) as i64 * 3000;
+    if ms_left <= MIN_MS_TO_TRADE || now_ms() - model.updated_ms > max_age_ms {
+        return None;
+    }
+    let p_up = model.up_probability(ms_left as f64 / 1000.0)?;
+
+    let candidates = [("UP", up_book, p_up), ("DOWN", down_book, 1.0 - p_up)];
+    let (side, level, fair) = candidates
+        .iter()
+        .filter_map(|(side, book, fair)| book.asks.first().filter(|a| a.price > 0.0).map(|a| (*side, a, *fair)))
+        .max_by(|a, b| (a.2 - a.1.price).total_cmp(&(b.2 - b.1.price)))?;
+    let edge = fair - level.price;
+    if edge < env.value_min_edge {
+        return None;
+    }
+
+    // Top of book only (IMO: walking the book eats exactly the edge we're after)
+    let tokens = env.value_token_amount.min(level.size);
+    if tokens < configured_min_tokens() {
+        return None;
+    }
+
+    Some(ValuePlan {
+        side,
+        ask: level.price,
+        model_price: fair,
+        edge,
+        tokens,
+        opportunity_key: format!("val_{}_{:.4}", side, level.price),
+        timestamp: now_ms(),
+    })
+}
+
+// One filled value buy (FYI: kept on the market result file next to the arbitrage fills)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueFill {
+    pub timestamp: i64,
+    pub side: String,
+    pub tokens: f64,
+    pub amount_usd: f64,
+    pub price: f64,
+    pub model_price: f64,
+}
+
+// Value trades' own caps (BTW: separate from MAX_PER_MARKET_USD / the session budget, which only see them as spend)
+#[derive(Debug, Default)]
+pub struct ValueRisk {
+    session_used: f64, // Spent + reserved, whole session
+    market_used: HashMap<String, f64>, // Spent + reserved per market window
+}
+
+impl ValueRisk {
+    // Tokens we may buy at this ask under VALUE_MAX_PER_MARKET_USD / VALUE_MAX_SESSION_USD (shrinks instead of skipping)
+    pub fn size_for(&self, market: &str, wanted_tokens: f64, ask: f64, env: &Env) -> f64 {
+        let market_left = env.value_max_per_market_usd - self.market_used.get(market).copied().unwrap_or(0.0);
+        let session_left = env.value_max_session_usd - self.session_used;
+        wanted_tokens.min(market_left.min(session_left).max(0.0) / ask)
+    }
+
+    pub fn reserve(&mut self, market: &str, usd: f64) {
+        self.session_used += usd;
+        *self.market_used.entry(market.to_string()).or_insert(0.0) += usd;
+    }
+
+    // Swap the reservation for what was actually spent (0 when the order failed / never ran)
+    pub fn settle(&mut self, market: &str, reserved: f64, spent: f64) {
+        let unused = (reserved - spent).max(0.0);
+        self.session_used = (self.session_used - unused).max(0.0);
+        if let Some(used) = self.market_used.get_mut(market) {
+            *used = (*used - unused).max(0.0);
+        }
+    }
+}
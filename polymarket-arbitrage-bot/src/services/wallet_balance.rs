@@ -0,0 +1,134 @@
+use crate::config::Env;
+use crate::services::engine::is_engine_running;
+use crate::services::spread_alert::post_webhook;
+use crate::utils::clock::now_ms;
+use crate::utils::logger::{log_alert, log_console, log_error, LogLevel};
+use anyhow::Result;
+use colored::*;
+use ethers::prelude::*;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration};
+
+// Polymarket collateral (USDC.e on Polygon) when USDC_CONTRACT_ADDRESS is unset
+const DEFAULT_USDC: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+const USDC_DECIMALS: i32 = 6;
+
+abigen!(
+    Erc20Balance,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+// Set while the wallet is under MIN_WALLET_BALANCE_USD (FYI: checked by SessionBudget::can_trade, so every buy path stops)
+static LOW_BALANCE: AtomicBool = AtomicBool::new(false);
+static STARTED: AtomicBool = AtomicBool::new(false);
+// Last reading: (USDC, unix ms)
+static LAST_BALANCE: Mutex<Option<(f64, i64)>> = Mutex::new(None);
+
+pub fn is_balance_paused() -> bool {
+    LOW_BALANCE.load(Ordering::Relaxed)
+}
+
+// For /healthz + heartbeat (BTW: null when MIN_WALLET_BALANCE_USD is off)
+pub fn wallet_balance_json() -> serde_json::Value {
+    match LAST_BALANCE.lock().ok().and_then(|b| *b) {
+        Some((usdc, at)) => json!({ "usdc": usdc, "age_ms": now_ms() - at, "paused": is_balance_paused() }),
+        None => serde_json::Value::Null,
+    }
+}
+
+// Start the watcher if MIN_WALLET_BALANCE_USD is set (BTW: safe to call from every engine, only the first one starts it)
+pub fn start_balance_watch(env: &Env) {
+    let Some(min_usd) = env.min_wallet_balance_usd else {
+        return;
+    };
+    if !env.trading_enabled() || STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let Some(holder) = env.proxy_wallet.clone() else {
+        return; // Trading needs PROXY_WALLET anyway (the config check reports it)
+    };
+    let env = env.clone();
+    tokio::spawn(async move {
+        match UsdcBalance::new(&env, &holder) {
+            Ok(reader) => run_balance_watch(reader, min_usd, env).await,
+            Err(e) => {
+                let msg = format!("MIN_WALLET_BALANCE_USD disabled, can't read the USDC balance: {}", e);
+                log_error(&msg, Some("balance"));
+                log_console(LogLevel::Warn, move || {
+                    eoutln!("{}", msg.yellow());
+                });
+            }
+        }
+    });
+}
+
+struct UsdcBalance {
+    usdc: Erc20Balance<Provider<Http>>,
+    holder: Address,
+}
+
+impl UsdcBalance {
+    fn new(env: &Env, holder: &str) -> Result<Self> {
+        let provider = Provider::<Http>::try_from(&env.rpc_url)?;
+        let contract = env.usdc_contract_address.as_deref().unwrap_or(DEFAULT_USDC);
+        Ok(Self {
+            usdc: Erc20Balance::new(contract.parse::<Address>()?, Arc::new(provider)),
+            holder: holder.parse()?,
+        })
+    }
+
+    async fn read(&self) -> Result<f64> {
+        let raw = self.usdc.balance_of(self.holder).call().await?;
+        Ok(raw.as_u128() as f64 / 10f64.powi(USDC_DECIMALS))
+    }
+}
+
+// Poll every BALANCE_CHECK_SECS; pause under the floor, resume once a top-up brings it back (IMO: detection never stops)
+async fn run_balance_watch(reader: UsdcBalance, min_usd: f64, env: Env) {
+    while is_engine_running() {
+        match reader.read().await {
+            Ok(usdc) => {
+                if let Ok(mut last) = LAST_BALANCE.lock() {
+                    *last = Some((usdc, now_ms()));
+                }
+                let paused = is_balance_paused();
+                if !paused && usdc < min_usd {
+                    LOW_BALANCE.store(true, Ordering::Relaxed);
+                    let summary = format!(
+                        "Wallet USDC ${:.2} is below MIN_WALLET_BALANCE_USD ${:.2} - trading paused until a top-up (detection continues)",
+                        usdc, min_usd
+                    );
+                    publish(&env, "low_balance", usdc, min_usd, summary, true).await;
+                } else if paused && usdc >= min_usd {
+                    LOW_BALANCE.store(false, Ordering::Relaxed);
+                    let summary = format!("Top-up detected: wallet USDC ${:.2} is back above ${:.2} - trading resumed", usdc, min_usd);
+                    publish(&env, "balance_restored", usdc, min_usd, summary, false).await;
+                }
+            }
+            Err(e) => log_error(&format!("Wallet balance check failed: {}", e), Some("balance")), // Keeps the last state (BTW: an RPC blip neither pauses nor resumes)
+        }
+        sleep(Duration::from_secs(env.balance_check_secs)).await;
+    }
+}
+
+// Console + alerts.log + ALERT_WEBHOOK_URL, same channels as spread alerts
+async fn publish(env: &Env, event: &str, usdc: f64, min_usd: f64, summary: String, low: bool) {
+    let console = summary.clone();
+    log_console(LogLevel::Warn, move || {
+        if low {
+            eoutln!("{}", format!("💸 {}", console).red().bold());
+        } else {
+            outln!("{}", format!("💰 {}", console).green().bold());
+        }
+    });
+    if low {
+        log_error(&summary, Some("balance"));
+    }
+    let alert = json!({ "event": event, "timestamp": now_ms(), "usdc": usdc, "minWalletBalanceUsd": min_usd });
+    log_alert(alert.to_string());
+    post_webhook(env, &summary, alert).await;
+}
@@ -1,17 +1,20 @@
+use crate::services::health::HealthState;
+use crate::services::market_precision::{set_min_size, set_tick_size};
+use crate::services::timeseries::record_ws_unknown;
+use crate::utils::resources::{gauge_add, gauge_sub, CACHED_ORDERBOOKS, DEDUP_ENTRIES};
 use anyhow::{anyhow, Result};
 use futures_util::{SinkExt, StreamExt};
+use polymarket_book::schema::{MessageSchema, Novelty, SchemaTelemetry};
+use polymarket_book::Book;
 use serde_json::json;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{watch, Mutex};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
-// Single price level in orderbook (FYI: price + size)
-#[derive(Debug, Clone)]
-pub struct OrderbookLevel {
-    pub price: f64,
-    pub size: f64,
-}
+// Single price level in orderbook (FYI: shared with the copy bot via polymarket-book)
+pub type OrderbookLevel = polymarket_book::Level;
 
 // Full orderbook snapshot (AFAIK: contains all bids/asks for a token)
 #[derive(Debug, Clone)]
@@ -27,25 +30,122 @@ pub struct OrderbookSnapshot {
 // Callback type for orderbook updates (BTW: Arc allows sharing across threads)
 pub type BookCallback = Arc<dyn Fn(OrderbookSnapshot) + Send + Sync>;
 
+type WsStream = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+// Books remembered per asset for dedup (FYI: only needs to cover the lag between the two connections)
+const DEDUP_WINDOW: usize = 64;
+
+// Recently delivered books of one asset (AFAIK: hash when the server sends one, else the message itself)
+#[derive(Default)]
+struct DeliveredBooks {
+    newest_timestamp: i64,
+    keys: VecDeque<String>,
+}
+
+// Per-connection counters (BTW: "first" = this lane delivered the book before the other one)
+#[derive(Default)]
+struct LaneStats {
+    first: AtomicU64,
+    duplicates: AtomicU64,
+}
+
+// Market channel payloads this client was written against (FYI: bump the version whenever the table changes)
+const MARKET_SCHEMA_VERSION: u32 = 1;
+const MARKET_SCHEMA: &[MessageSchema] = &[
+    MessageSchema {
+        kind: "book",
+        fields: Some(&[
+            "event_type", "type", "asset_id", "market", "timestamp", "bids", "asks", "hash", "tick_size",
+            "min_order_size", "last_trade_price",
+        ]),
+    },
+    MessageSchema {
+        kind: "tick_size_change",
+        fields: Some(&["event_type", "type", "asset_id", "market", "old_tick_size", "new_tick_size", "side", "timestamp"]),
+    },
+    // Sent on the channel but not used (BTW: listed so they don't count as unknown)
+    MessageSchema { kind: "price_change", fields: None },
+    MessageSchema { kind: "last_trade_price", fields: None },
+    MessageSchema { kind: "best_bid_ask", fields: None },
+    MessageSchema { kind: "new_market", fields: None },
+    MessageSchema { kind: "market_resolved", fields: None },
+];
+
+static MARKET_TELEMETRY: OnceLock<SchemaTelemetry> = OnceLock::new();
+
+fn market_telemetry() -> &'static SchemaTelemetry {
+    MARKET_TELEMETRY.get_or_init(|| SchemaTelemetry::new("market", MARKET_SCHEMA_VERSION, MARKET_SCHEMA))
+}
+
+// Unknown types / fields seen on the market channel, for /healthz + heartbeat
+pub fn ws_schema_json() -> serde_json::Value {
+    market_telemetry().to_json()
+}
+
+// Count the message against MARKET_SCHEMA, log each new type / field once (IMO: an API change should be loud, not a missed arb)
+fn observe_schema(kind: &str, msg: &serde_json::Value) {
+    for novelty in market_telemetry().observe(kind, msg) {
+        match &novelty {
+            Novelty::Field { kind, field, .. } => record_ws_unknown(kind, field),
+            Novelty::Kind { kind, .. } | Novelty::Unparsed { kind, .. } => record_ws_unknown(kind, ""),
+        }
+        eoutln!("WS schema v{}: {}", MARKET_SCHEMA_VERSION, novelty);
+    }
+}
+
 pub struct MarketWebSocket {
-    url: String,
+    urls: Vec<String>, // Primary first, then backups (FYI: failover order)
+    primary_retry_secs: u64, // While on a backup, drop it and retry the primary this often (0 = never)
     subscribed_assets: Arc<Mutex<Vec<String>>>,
+    subscription_changes: watch::Sender<u64>, // Bumped on every subscribe / prefetch (FYI: live lanes resync on it)
     orderbooks: Arc<Mutex<HashMap<String, OrderbookSnapshot>>>,
     on_book_callback: Arc<Mutex<Option<BookCallback>>>,
     is_running: Arc<Mutex<bool>>,
+    health: Option<Arc<HealthState>>, // Liveness tracking for /healthz + heartbeat (optional)
+    lanes: usize, // Parallel connections (2 = redundant, streams merged + deduplicated)
+    live_lanes: Arc<AtomicUsize>, // Connections currently up
+    delivered: Arc<std::sync::Mutex<HashMap<String, DeliveredBooks>>>,
+    lane_stats: Arc<[LaneStats; 2]>,
 }
 
 impl MarketWebSocket {
     pub fn new(url: String) -> Self {
         Self {
-            url,
+            urls: vec![url],
+            primary_retry_secs: 0,
             subscribed_assets: Arc::new(Mutex::new(Vec::new())),
+            subscription_changes: watch::channel(0).0,
             orderbooks: Arc::new(Mutex::new(HashMap::new())),
             on_book_callback: Arc::new(Mutex::new(None)),
             is_running: Arc::new(Mutex::new(false)),
+            health: None,
+            lanes: 1,
+            live_lanes: Arc::new(AtomicUsize::new(0)),
+            delivered: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            lane_stats: Arc::new(Default::default()),
         }
     }
 
+    // Two connections to the same feed (IMO: a drop in the last minutes of a window shouldn't blind detection)
+    // FYI: with backups configured the second lane starts on the first backup, so one endpoint outage can't take both
+    pub fn with_redundancy(mut self, enabled: bool) -> Self {
+        self.lanes = if enabled { 2 } else { 1 };
+        self
+    }
+
+    // Backup endpoints for failover (IMO: a regional outage shouldn't stop detection)
+    pub fn with_backups(mut self, backup_urls: Vec<String>, primary_retry_secs: u64) -> Self {
+        self.urls.extend(backup_urls);
+        self.primary_retry_secs = primary_retry_secs;
+        self
+    }
+
+    // Report connection status + book updates to a health tracker (FYI: call before run)
+    pub fn with_health(mut self, health: Arc<HealthState>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
     // Register callback for orderbook updates (FYI: called whenever we get new data)
     pub fn on_book<F>(&self, callback: F)
     where
@@ -61,33 +161,8 @@ impl MarketWebSocket {
 
     // Parse orderbook from JSON (IMO: handles Polymarket's WS message format)
     fn parse_orderbook_snapshot(data: &serde_json::Value) -> Result<OrderbookSnapshot> {
-        let mut bids: Vec<OrderbookLevel> = data
-            .get("bids")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&[])
-            .iter()
-            .filter_map(|b| {
-                Some(OrderbookLevel {
-                    price: b.get("price")?.as_str()?.parse().ok()?,
-                    size: b.get("size")?.as_str()?.parse().ok()?,
-                })
-            })
-            .collect();
-        bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap()); // Sort bids desc (best bid first)
-
-        let mut asks: Vec<OrderbookLevel> = data
-            .get("asks")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&[])
-            .iter()
-            .filter_map(|a| {
-                Some(OrderbookLevel {
-                    price: a.get("price")?.as_str()?.parse().ok()?,
-                    size: a.get("size")?.as_str()?.parse().ok()?,
-                })
-            })
-            .collect();
-        asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap()); // Sort asks asc (best ask first)
+        // Bids desc / asks asc, best first (AFAIK: WS sends strings, REST sometimes numbers - both parse)
+        let Book { bids, asks } = Book::from_json(data);
 
         Ok(OrderbookSnapshot {
             asset_id: data
@@ -111,8 +186,41 @@ impl MarketWebSocket {
         })
     }
 
+    // True if the other lane already delivered this book (or a newer one) for the asset
+    fn is_duplicate(&self, lane: usize, msg: &serde_json::Value, snapshot: &OrderbookSnapshot) -> bool {
+        if self.lanes < 2 {
+            return false;
+        }
+        let key = snapshot.hash.clone().unwrap_or_else(|| msg.to_string());
+        let Ok(mut delivered) = self.delivered.lock() else {
+            return false;
+        };
+        if !delivered.contains_key(&snapshot.asset_id) {
+            gauge_add(&DEDUP_ENTRIES, 1);
+        }
+        let seen = delivered.entry(snapshot.asset_id.clone()).or_default();
+        // Stale = older than what the faster lane already handed to detection (BTW: never step the book back)
+        let stale = snapshot.timestamp > 0 && snapshot.timestamp < seen.newest_timestamp;
+        if stale || seen.keys.contains(&key) {
+            self.lane_stats[lane].duplicates.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        seen.newest_timestamp = seen.newest_timestamp.max(snapshot.timestamp);
+        seen.keys.push_back(key);
+        if seen.keys.len() > DEDUP_WINDOW {
+            seen.keys.pop_front();
+        }
+        self.lane_stats[lane].first.fetch_add(1, Ordering::Relaxed);
+        false
+    }
+
+    // Numeric field sent as a string or a number (FYI: the market channel uses strings for prices)
+    fn number_field(msg: &serde_json::Value, key: &str) -> Option<f64> {
+        msg.get(key).and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+    }
+
     // Handle incoming WS message (FYI: can be single msg or array of msgs)
-    async fn handle_message(&self, message: &str) -> Result<()> {
+    async fn handle_message(&self, lane: usize, message: &str) -> Result<()> {
         let data: serde_json::Value = serde_json::from_str(message)?;
 
         // Handle both single msg and array formats (AFAIK: Polymarket sends both)
@@ -127,15 +235,31 @@ impl MarketWebSocket {
                 .get("event_type")
                 .or_else(|| msg.get("type")) // Try both field names (BTW: API inconsistency)
                 .and_then(|v| v.as_str());
+            observe_schema(event_type.unwrap_or("(none)"), &msg);
 
             if event_type == Some("book") {
                 let snapshot = Self::parse_orderbook_snapshot(&msg)?;
+                if self.is_duplicate(lane, &msg, &snapshot) {
+                    continue;
+                }
+                // Books may carry the current rules too (BTW: absent on most snapshots)
+                if let Some(tick_size) = Self::number_field(&msg, "tick_size") {
+                    set_tick_size(&snapshot.asset_id, tick_size);
+                }
+                if let Some(min_size) = Self::number_field(&msg, "min_order_size") {
+                    set_min_size(&snapshot.asset_id, min_size);
+                }
                 let asset_id = snapshot.asset_id.clone();
                 
                 // Cache orderbook (IMO: allows quick lookups without WS roundtrip)
                 {
                     let mut orderbooks = self.orderbooks.blocking_lock();
-                    orderbooks.insert(asset_id.clone(), snapshot.clone());
+                    if orderbooks.insert(asset_id.clone(), snapshot.clone()).is_none() {
+                        gauge_add(&CACHED_ORDERBOOKS, 1);
+                    }
+                }
+                if let Some(ref health) = self.health {
+                    health.record_book(&asset_id);
                 }
 
                 // Call registered callback (FYI: triggers arbitrage detection)
@@ -143,6 +267,13 @@ impl MarketWebSocket {
                 if let Some(ref callback) = *callback_guard {
                     callback(snapshot.clone());
                 }
+            } else if event_type == Some("tick_size_change") {
+                // Polymarket narrows the tick near 0/1 (AFAIK: both lanes send it, set_tick_size is idempotent)
+                if let (Some(asset_id), Some(tick_size)) =
+                    (msg.get("asset_id").and_then(|v| v.as_str()), Self::number_field(&msg, "new_tick_size"))
+                {
+                    set_tick_size(asset_id, tick_size);
+                }
             }
         }
 
@@ -159,14 +290,96 @@ impl MarketWebSocket {
             let mut subscribed = self.subscribed_assets.blocking_lock();
             *subscribed = asset_ids.clone(); // Store for later subscription
         }
+        if let Some(ref health) = self.health {
+            health.retain_tokens(&asset_ids); // Drop book ages from the previous market
+        }
+        // Books + dedup state of the previous market (FYI: without this both grew by two tokens every window)
+        {
+            let mut orderbooks = self.orderbooks.blocking_lock();
+            let before = orderbooks.len();
+            orderbooks.retain(|asset, _| asset_ids.contains(asset));
+            gauge_sub(&CACHED_ORDERBOOKS, before - orderbooks.len());
+        }
+        if let Ok(mut delivered) = self.delivered.lock() {
+            let before = delivered.len();
+            delivered.retain(|asset, _| asset_ids.contains(asset));
+            gauge_sub(&DEDUP_ENTRIES, before - delivered.len());
+        }
 
-        // Subscription will be handled in the run loop (BTW: after WS connects)
+        // Connected lanes pick this up right away, others on (re)connect
+        self.subscription_changes.send_modify(|v| *v += 1);
         Ok(())
     }
 
+    // Add assets without dropping the current ones (FYI: next window's tokens, so its books are cached before roll-over)
+    pub async fn prefetch(&self, asset_ids: Vec<String>) {
+        {
+            let mut subscribed = self.subscribed_assets.blocking_lock();
+            for asset in asset_ids {
+                if !subscribed.contains(&asset) {
+                    subscribed.push(asset);
+                }
+            }
+        }
+        self.subscription_changes.send_modify(|v| *v += 1);
+    }
+
+    // Bring a live connection in line with subscribed_assets (AFAIK: the market channel takes subscribe/unsubscribe ops after the first message)
+    async fn sync_subscription(ws_stream: &mut WsStream, sent: &mut Vec<String>, wanted: &[String]) {
+        if sent.is_empty() {
+            if !wanted.is_empty() {
+                let subscribe_msg = json!({ "assets_ids": wanted, "type": "MARKET" });
+                let _ = ws_stream.send(Message::Text(subscribe_msg.to_string())).await;
+            }
+        } else {
+            let added: Vec<&String> = wanted.iter().filter(|a| !sent.contains(a)).collect();
+            let removed: Vec<&String> = sent.iter().filter(|a| !wanted.contains(a)).collect();
+            for (operation, assets) in [("subscribe", added), ("unsubscribe", removed)] {
+                if !assets.is_empty() {
+                    let msg = json!({ "assets_ids": assets, "operation": operation });
+                    let _ = ws_stream.send(Message::Text(msg.to_string())).await;
+                }
+            }
+        }
+        *sent = wanted.to_vec();
+    }
+
     // Main WS loop with auto-reconnect (IMO: keeps connection alive)
     pub async fn run(&self, auto_reconnect: bool) -> Result<()> {
         *self.is_running.blocking_lock() = true;
+        if self.lanes < 2 {
+            return self.run_lane(0, auto_reconnect).await;
+        }
+        outln!("Running {} redundant WebSocket connections (books deduplicated)", self.lanes);
+        let (first, second) = tokio::join!(self.run_lane(0, auto_reconnect), self.run_lane(1, auto_reconnect));
+        first.and(second)
+    }
+
+    fn set_lane_connected(&self, lane: usize, connected: bool) {
+        let live = if connected {
+            self.live_lanes.fetch_add(1, Ordering::SeqCst) + 1
+        } else {
+            self.live_lanes.fetch_sub(1, Ordering::SeqCst) - 1
+        };
+        if let Some(ref health) = self.health {
+            health.set_ws_connected(live > 0); // BTW: healthy while any lane is up
+        }
+        if self.lanes > 1 && !connected {
+            let stats = &self.lane_stats[lane];
+            eoutln!(
+                "WebSocket lane {} dropped ({} still connected) - it delivered {} books first, {} duplicates",
+                lane + 1,
+                live,
+                stats.first.load(Ordering::Relaxed),
+                stats.duplicates.load(Ordering::Relaxed)
+            );
+        }
+    }
+
+    // One connection with its own failover + reconnect (FYI: lane 0 starts on the primary)
+    async fn run_lane(&self, lane: usize, auto_reconnect: bool) -> Result<()> {
+        let home = lane % self.urls.len(); // Endpoint this lane returns to after a failover
+        let mut endpoint = home; // Index into urls (0 = primary)
 
         loop {
             if !*self.is_running.blocking_lock() {
@@ -174,26 +387,50 @@ impl MarketWebSocket {
             }
 
             // Connect to WS endpoint
-            match self.connect().await {
+            let mut retry_primary = false;
+            match self.connect(&self.urls[endpoint]).await {
                 Ok((mut ws_stream, _)) => {
-                    // Subscribe to assets (AFAIK: sends sub msg after connection)
-                    {
-                        let subscribed = self.subscribed_assets.blocking_lock();
-                        if !subscribed.is_empty() {
-                            let subscribe_msg = json!({
-                                "assets_ids": subscribed.clone(),
-                                "type": "MARKET"
-                            });
-                            let _ = ws_stream.send(Message::Text(subscribe_msg.to_string())).await;
-                        }
+                    if endpoint != home {
+                        outln!("WebSocket connected to backup endpoint {}", self.urls[endpoint]);
                     }
+                    self.set_lane_connected(lane, true);
+
+                    // Subscribe to assets (AFAIK: sends sub msg after connection)
+                    let mut changes = self.subscription_changes.subscribe(); // Before reading the list, so no change slips between
+                    let mut sent: Vec<String> = Vec::new();
+                    let wanted = self.subscribed_assets.blocking_lock().clone();
+                    Self::sync_subscription(&mut ws_stream, &mut sent, &wanted).await;
+
+                    // On a backup, go back to the primary after primary_retry_secs (BTW: it may have recovered)
+                    let primary_deadline = (endpoint != home && self.primary_retry_secs > 0)
+                        .then(|| tokio::time::Instant::now() + tokio::time::Duration::from_secs(self.primary_retry_secs));
 
                     // Handle incoming messages (FYI: processes orderbook updates)
                     while *self.is_running.blocking_lock() {
-                        match ws_stream.next().await {
+                        let next = tokio::select! {
+                            msg = ws_stream.next() => msg,
+                            _ = async {
+                                match primary_deadline {
+                                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                                    None => std::future::pending().await,
+                                }
+                            } => {
+                                outln!("Retrying primary WebSocket endpoint...");
+                                let _ = ws_stream.close(None).await;
+                                endpoint = home;
+                                retry_primary = true;
+                                break;
+                            }
+                            Ok(()) = changes.changed() => {
+                                let wanted = self.subscribed_assets.blocking_lock().clone();
+                                Self::sync_subscription(&mut ws_stream, &mut sent, &wanted).await;
+                                continue;
+                            }
+                        };
+                        match next {
                             Some(Ok(Message::Text(text))) => {
-                                if let Err(e) = self.handle_message(&text).await {
-                                    eprintln!("Error handling message: {}", e);
+                                if let Err(e) = self.handle_message(lane, &text).await {
+                                    eoutln!("Error handling message: {}", e);
                                 }
                             }
                             Some(Ok(Message::Ping(data))) => {
@@ -203,22 +440,32 @@ impl MarketWebSocket {
                                 break;
                             }
                             Some(Err(e)) => {
-                                eprintln!("WebSocket error: {}", e);
+                                eoutln!("WebSocket error: {}", e);
                                 break;
                             }
                             None => break,
                             _ => {}
                         }
                     }
+
+                    self.set_lane_connected(lane, false); // BTW: flips back on reconnect
                 }
                 Err(e) => {
-                    eprintln!("Connection error: {}", e);
+                    eoutln!("Connection error: {}", e);
+                    // Fail over to the next endpoint (AFAIK: wraps back to the primary)
+                    if self.urls.len() > 1 {
+                        endpoint = (endpoint + 1) % self.urls.len();
+                        eoutln!("Failing over to WebSocket endpoint {}", self.urls[endpoint]);
+                    }
                 }
             }
 
             if !auto_reconnect || !*self.is_running.blocking_lock() {
                 break;
             }
+            if retry_primary {
+                continue; // Planned switch, no backoff
+            }
 
             tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
         }
@@ -226,8 +473,8 @@ impl MarketWebSocket {
         Ok(())
     }
 
-    async fn connect(&self) -> Result<(tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>, tokio_tungstenite::tungstenite::handshake::client::Response)> {
-        let result = connect_async(&self.url).await?;
+    async fn connect(&self, url: &str) -> Result<(WsStream, tokio_tungstenite::tungstenite::handshake::client::Response)> {
+        let result = connect_async(url).await?;
         Ok(result)
     }
 
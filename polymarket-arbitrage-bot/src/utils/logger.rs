@@ -1,65 +1,132 @@
-use chrono::{DateTime, Utc};
+use crate::utils::clock::{market_time_str, now_ms};
+use crate::utils::resources::{gauge_add, gauge_sub, LOG_BACKLOG};
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{channel, Sender};
 use std::sync::Mutex;
 
+const MONITOR_HEADER: &str = "Time,Bid UP,Bid DOWN,Bid Sum,Ask UP,Ask DOWN,Ask Sum\n";
+
+// Work handed to the logging thread (FYI: formatting + I/O happen there, not on the hot path)
+type LogJob = Box<dyn FnOnce() + Send>;
+
 // Lazy static file paths and handles (FYI: created once, reused)
 lazy_static::lazy_static! {
-    static ref MONITOR_LOG_PATH: PathBuf = std::env::current_dir()
-        .unwrap_or_else(|_| PathBuf::from("."))
-        .join("monitor.log");
-    static ref ERROR_LOG_PATH: PathBuf = std::env::current_dir()
-        .unwrap_or_else(|_| PathBuf::from("."))
-        .join("error.log");
-    static ref MONITOR_FILE: Mutex<Option<File>> = Mutex::new(None); // Cached file handle
+    static ref LOG_DIR: PathBuf = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    static ref ERROR_LOG_PATH: PathBuf = LOG_DIR.join("error.log");
+    static ref ALERT_LOG_PATH: PathBuf = LOG_DIR.join("alerts.log"); // ALERT_ONLY spreads, JSONL (BTW: kept across runs)
+    static ref JOURNAL_LOG_PATH: PathBuf = LOG_DIR.join("journal.jsonl"); // Trade journal with book snapshots, JSONL (kept across runs)
+    static ref MONITOR_FILES: Mutex<HashMap<String, File>> = Mutex::new(HashMap::new()); // One handle per coin
     static ref ERROR_FILE: Mutex<Option<File>> = Mutex::new(None); // Cached file handle
+    static ref LOG_SENDER: Mutex<Option<Sender<LogJob>>> = Mutex::new(None); // Set by init_logging
 }
 
-// Ensure log files exist (AFAIK: creates empty files if missing)
-fn ensure_log_files() {
-    let _ = std::fs::File::create(&*MONITOR_LOG_PATH);
-    let _ = std::fs::File::create(&*ERROR_LOG_PATH);
+// Console verbosity (LOG_LEVEL env, ordered: error < warn < info < debug)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
 }
 
-// Format timestamp in EST (BTW: UTC-5, no DST handling)
-fn format_log_timestamp() -> String {
-    let now = Utc::now();
-    // Convert to EST (UTC-5)
-    let est_offset = chrono::Duration::hours(-5);
-    let est_time = now + est_offset;
-    est_time.format("%Y-%m-%d %H:%M:%S EST").to_string()
+impl LogLevel {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
 }
 
-// Log price data to monitor.log (FYI: CSV format for easy parsing)
-pub fn log_monitor_data(data: MonitorData) {
-    ensure_log_files();
-    
-    let mut file_guard = MONITOR_FILE.lock().unwrap();
-    if file_guard.is_none() {
-        if let Ok(file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&*MONITOR_LOG_PATH)
-        {
-            *file_guard = Some(file);
+static CONSOLE_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+// Start the logging thread (BTW: before this, jobs just run inline)
+pub fn init_logging(level: LogLevel) {
+    CONSOLE_LEVEL.store(level as u8, Ordering::Relaxed);
+    let (tx, rx) = channel::<LogJob>();
+    std::thread::spawn(move || {
+        for job in rx {
+            job();
         }
+    });
+    *LOG_SENDER.lock().unwrap() = Some(tx);
+}
+
+pub fn log_enabled(level: LogLevel) -> bool {
+    level as u8 <= CONSOLE_LEVEL.load(Ordering::Relaxed)
+}
+
+// Queue work for the logging thread (falls back to running it right here)
+fn dispatch(job: LogJob) {
+    // Backlog counts until the job has run (BTW: a growing number = the console can't keep up)
+    gauge_add(&LOG_BACKLOG, 1);
+    let job: LogJob = Box::new(move || {
+        job();
+        gauge_sub(&LOG_BACKLOG, 1);
+    });
+    let job = match LOG_SENDER.lock().unwrap().as_ref() {
+        Some(tx) => match tx.send(job) {
+            Ok(()) => return,
+            Err(e) => e.0,
+        },
+        None => job,
+    };
+    job();
+}
+
+// Console output at a level (IMO: pass a closure so the format! cost is off the caller too)
+pub fn log_console<F>(level: LogLevel, print: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    if log_enabled(level) {
+        dispatch(Box::new(print));
     }
-    
-    if let Some(ref mut file) = *file_guard {
-        let log_line = format!(
-            "{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4}\n",
-            data.time, data.bid_up, data.bid_down, data.bid_sum,
-            data.ask_up, data.ask_down, data.ask_sum
-        );
-        let _ = file.write_all(log_line.as_bytes());
-        let _ = file.flush();
-    }
+}
+
+// Log timestamps in market time (BTW: EST/EDT via utils::clock)
+fn format_log_timestamp() -> String {
+    market_time_str(now_ms(), "%Y-%m-%d %H:%M:%S")
+}
+
+// Per-coin monitor file, e.g. monitor_btc.log
+pub fn monitor_log_path(coin: &str) -> PathBuf {
+    LOG_DIR.join(format!("monitor_{}.log", coin.to_lowercase()))
+}
+
+// Log price data to monitor_<coin>.log (FYI: CSV format for easy parsing)
+pub fn log_monitor_data(coin: &str, data: MonitorData) {
+    let coin = coin.to_lowercase();
+    dispatch(Box::new(move || {
+        let mut files = MONITOR_FILES.lock().unwrap();
+        if !files.contains_key(&coin) {
+            // First write this session: start a fresh file with the CSV header
+            let path = monitor_log_path(&coin);
+            let _ = std::fs::write(&path, MONITOR_HEADER);
+            if let Ok(file) = OpenOptions::new().create(true).append(true).open(&path) {
+                files.insert(coin.clone(), file);
+            }
+        }
+
+        if let Some(file) = files.get_mut(&coin) {
+            let log_line = format!(
+                "{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4}\n",
+                data.time, data.bid_up, data.bid_down, data.bid_sum,
+                data.ask_up, data.ask_down, data.ask_sum
+            );
+            let _ = file.write_all(log_line.as_bytes());
+        }
+    }));
 }
 
 pub fn log_error(error: &str, context: Option<&str>) {
-    ensure_log_files();
-    
     let mut file_guard = ERROR_FILE.lock().unwrap();
     if file_guard.is_none() {
         if let Ok(file) = OpenOptions::new()
@@ -80,24 +147,35 @@ pub fn log_error(error: &str, context: Option<&str>) {
     }
 }
 
-pub fn clear_log_files() {
-    let header = "Time,Bid UP,Bid DOWN,Bid Sum,Ask UP,Ask DOWN,Ask Sum\n";
-    let _ = std::fs::write(&*MONITOR_LOG_PATH, header);
-    let _ = std::fs::write(&*ERROR_LOG_PATH, "");
-    
-    *MONITOR_FILE.lock().unwrap() = None;
-    *ERROR_FILE.lock().unwrap() = None;
+// Append one JSON line to alerts.log (FYI: on the logging thread, like the monitor files)
+pub fn log_alert(line: String) {
+    dispatch(Box::new(move || {
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&*ALERT_LOG_PATH) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }));
 }
 
-pub fn init_monitor_log() {
-    ensure_log_files();
-    
-    let header = "Time,Bid UP,Bid DOWN,Bid Sum,Ask UP,Ask DOWN,Ask Sum\n";
-    if let Ok(content) = std::fs::read_to_string(&*MONITOR_LOG_PATH) {
-        if content.trim().is_empty() {
-            let _ = std::fs::write(&*MONITOR_LOG_PATH, header);
+// Trade journal location (FYI: read back by the journal bin)
+pub fn journal_log_path() -> PathBuf {
+    JOURNAL_LOG_PATH.clone()
+}
+
+// Append one JSON line to journal.jsonl (BTW: same thread as alerts.log, trades never wait on the disk)
+pub fn log_journal(line: String) {
+    dispatch(Box::new(move || {
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&*JOURNAL_LOG_PATH) {
+            let _ = writeln!(file, "{}", line);
         }
-    }
+    }));
+}
+
+// Reset error.log (BTW: monitor_<coin>.log files are reset on their first write)
+pub fn clear_log_files() {
+    let _ = std::fs::write(&*ERROR_LOG_PATH, "");
+
+    MONITOR_FILES.lock().unwrap().clear();
+    *ERROR_FILE.lock().unwrap() = None;
 }
 
 #[derive(Debug, Clone)]
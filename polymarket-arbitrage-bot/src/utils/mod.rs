@@ -1,8 +1,12 @@
 pub mod coin_selector;
 pub mod keyboard;
 pub mod logger;
+pub mod resources;
 
+pub use polymarket_clock as clock; // Shared with the copy bot
+pub use clock::*;
 pub use coin_selector::*;
 pub use keyboard::*;
 pub use logger::*;
+pub use resources::*;
 
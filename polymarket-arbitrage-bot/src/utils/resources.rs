@@ -0,0 +1,72 @@
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Process-wide gauges for self-monitoring (FYI: components bump them, health/resource monitor read them)
+// BTW: plain counters so the hot path never takes a lock for bookkeeping
+pub static LOG_BACKLOG: AtomicUsize = AtomicUsize::new(0); // Jobs waiting for the logging thread
+pub static QUEUED_TRADES: AtomicUsize = AtomicUsize::new(0); // Trades queued or running, all coins
+pub static CACHED_ORDERBOOKS: AtomicUsize = AtomicUsize::new(0); // MarketWebSocket book cache entries, all coins
+pub static DEDUP_ENTRIES: AtomicUsize = AtomicUsize::new(0); // Assets tracked by the redundant-WS dedup
+pub static QUEUED_ORDERS: AtomicUsize = AtomicUsize::new(0); // Order requests waiting for a CLOB slot
+pub static IN_FLIGHT_ORDERS: AtomicUsize = AtomicUsize::new(0); // Order requests on the wire
+
+pub fn gauge_add(gauge: &AtomicUsize, n: usize) {
+    gauge.fetch_add(n, Ordering::Relaxed);
+}
+
+// Saturating so a missed increment can't wrap the gauge around
+pub fn gauge_sub(gauge: &AtomicUsize, n: usize) {
+    let _ = gauge.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| Some(v.saturating_sub(n)));
+}
+
+// Resident memory in MB (AFAIK: Linux only, None elsewhere)
+pub fn rss_mb() -> Option<f64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: f64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * 4096.0 / (1024.0 * 1024.0)) // 4 KiB pages
+}
+
+// Tasks alive on the current tokio runtime (None outside a runtime)
+pub fn alive_tasks() -> Option<usize> {
+    tokio::runtime::Handle::try_current().ok().map(|h| h.metrics().num_alive_tasks())
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSnapshot {
+    pub rss_mb: Option<f64>,
+    pub tasks: Option<usize>,
+    pub log_backlog: usize,
+    pub queued_trades: usize,
+    pub orderbooks: usize,
+    pub dedup_entries: usize,
+    pub queued_orders: usize,
+    pub in_flight_orders: usize,
+}
+
+impl ResourceSnapshot {
+    pub fn take() -> Self {
+        Self {
+            rss_mb: rss_mb(),
+            tasks: alive_tasks(),
+            log_backlog: LOG_BACKLOG.load(Ordering::Relaxed),
+            queued_trades: QUEUED_TRADES.load(Ordering::Relaxed),
+            orderbooks: CACHED_ORDERBOOKS.load(Ordering::Relaxed),
+            dedup_entries: DEDUP_ENTRIES.load(Ordering::Relaxed),
+            queued_orders: QUEUED_ORDERS.load(Ordering::Relaxed),
+            in_flight_orders: IN_FLIGHT_ORDERS.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "rss_mb": self.rss_mb.map(|mb| (mb * 10.0).round() / 10.0),
+            "tasks": self.tasks,
+            "log_backlog": self.log_backlog,
+            "queued_trades": self.queued_trades,
+            "orderbooks": self.orderbooks,
+            "dedup_entries": self.dedup_entries,
+            "queued_orders": self.queued_orders,
+            "in_flight_orders": self.in_flight_orders,
+        })
+    }
+}
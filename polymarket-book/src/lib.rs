@@ -0,0 +1,282 @@
+pub mod schema;
+
+use serde_json::Value;
+
+// One price level (price per token, size in tokens)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub price: f64,
+    pub size: f64,
+}
+
+impl Level {
+    // USD resting at this level
+    pub fn notional(&self) -> f64 {
+        self.price * self.size
+    }
+}
+
+// CLOB/Gamma numbers arrive as JSON numbers or numeric strings
+pub fn parse_f64(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+// `[{"price": "0.52", "size": "100"}, ...]` -> levels (unparseable entries dropped, order kept)
+pub fn parse_levels(value: &Value) -> Vec<Level> {
+    value
+        .as_array()
+        .map(|levels| {
+            levels
+                .iter()
+                .filter_map(|l| {
+                    Some(Level {
+                        price: parse_f64(l.get("price")?)?,
+                        size: parse_f64(l.get("size")?)?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Result of walking one side of the book
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Fill {
+    pub tokens: f64,      // Tokens available up to the target (less than asked when the book is thin)
+    pub cost: f64,        // USD for those tokens
+    pub vwap: f64,        // cost / tokens (0 when nothing fills)
+    pub worst_price: f64, // Price of the last level touched
+}
+
+impl Fill {
+    fn add(&mut self, level: &Level, tokens: f64) {
+        self.tokens += tokens;
+        self.cost += tokens * level.price;
+        self.worst_price = level.price;
+        self.vwap = if self.tokens > 0.0 { self.cost / self.tokens } else { 0.0 };
+    }
+}
+
+// Buy/sell `size` tokens against levels sorted best-first (stops at a zero-price level, like vwap_for_usd)
+pub fn vwap_to_size(levels: &[Level], size: f64) -> Fill {
+    let mut fill = Fill::default();
+    for level in levels {
+        let remaining = size - fill.tokens;
+        if remaining <= 0.0 || level.price <= 0.0 {
+            break;
+        }
+        fill.add(level, remaining.min(level.size));
+    }
+    fill
+}
+
+// Spend `usd` against levels sorted best-first
+pub fn vwap_for_usd(levels: &[Level], usd: f64) -> Fill {
+    let mut fill = Fill::default();
+    for level in levels {
+        let remaining = usd - fill.cost;
+        if remaining <= 0.0 || level.price <= 0.0 {
+            break;
+        }
+        fill.add(level, (remaining / level.price).min(level.size));
+    }
+    fill
+}
+
+// USD resting within `range` of the best price (levels sorted best-first, either side)
+pub fn depth_within(levels: &[Level], range: f64) -> f64 {
+    let Some(best) = levels.first().map(|l| l.price) else {
+        return 0.0;
+    };
+    levels
+        .iter()
+        .take_while(|l| (l.price - best).abs() <= range + f64::EPSILON)
+        .map(Level::notional)
+        .sum()
+}
+
+// Running totals per level: (price, cumulative tokens, cumulative USD)
+pub fn cumulative_depth(levels: &[Level]) -> Vec<(f64, f64, f64)> {
+    let (mut tokens, mut usd) = (0.0, 0.0);
+    levels
+        .iter()
+        .map(|l| {
+            tokens += l.size;
+            usd += l.notional();
+            (l.price, tokens, usd)
+        })
+        .collect()
+}
+
+// Relative change from the last (unix secs, price) point at or before `now - lookback_secs` to `current`
+// (-0.2 = fell 20%; None when the history doesn't reach back that far)
+pub fn momentum(history: &[(i64, f64)], now: i64, lookback_secs: i64, current: f64) -> Option<f64> {
+    let start = now - lookback_secs;
+    let (_, past) = history.iter().rev().find(|(t, _)| *t <= start)?;
+    (*past > 0.0).then(|| (current - past) / past)
+}
+
+// Both sides, best level first (bids descending, asks ascending)
+#[derive(Debug, Clone, Default)]
+pub struct Book {
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+impl Book {
+    pub fn new(mut bids: Vec<Level>, mut asks: Vec<Level>) -> Self {
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        Self { bids, asks }
+    }
+
+    // CLOB /book or WS "book" message (missing sides read as empty)
+    pub fn from_json(book: &Value) -> Self {
+        let side = |key: &str| book.get(key).map(parse_levels).unwrap_or_default();
+        Self::new(side("bids"), side("asks"))
+    }
+
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks.first().copied()
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    // Spread relative to mid, in basis points
+    pub fn spread_bps(&self) -> Option<f64> {
+        let mid = self.mid().filter(|m| *m > 0.0)?;
+        Some(self.spread()? / mid * 10_000.0)
+    }
+
+    // (bid size - ask size) / total over the top `levels` levels: +1 all bids, -1 all asks, 0 balanced/empty
+    pub fn imbalance(&self, levels: usize) -> f64 {
+        let bid: f64 = self.bids.iter().take(levels).map(|l| l.size).sum();
+        let ask: f64 = self.asks.iter().take(levels).map(|l| l.size).sum();
+        if bid + ask > 0.0 {
+            (bid - ask) / (bid + ask)
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn level(price: f64, size: f64) -> Level {
+        Level { price, size }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn fills_to_size_across_levels() {
+        let asks = [level(0.50, 10.0), level(0.52, 10.0)];
+        let fill = vwap_to_size(&asks, 15.0);
+        assert!(close(fill.tokens, 15.0));
+        assert!(close(fill.cost, 10.0 * 0.50 + 5.0 * 0.52));
+        assert!(close(fill.vwap, fill.cost / 15.0));
+        assert_eq!(fill.worst_price, 0.52);
+    }
+
+    #[test]
+    fn thin_book_fills_partially() {
+        let asks = [level(0.40, 3.0), level(0.45, 2.0)];
+        let fill = vwap_to_size(&asks, 10.0);
+        assert!(close(fill.tokens, 5.0));
+        assert!(close(fill.cost, 3.0 * 0.40 + 2.0 * 0.45));
+        assert_eq!(fill.worst_price, 0.45);
+        assert_eq!(vwap_to_size(&[], 10.0), Fill::default());
+    }
+
+    #[test]
+    fn usd_budget_runs_out_mid_level() {
+        let asks = [level(0.50, 10.0), level(0.60, 100.0)];
+        let fill = vwap_for_usd(&asks, 8.0);
+        // $5 takes the first level, the other $3 buys 5 tokens of the second
+        assert!(close(fill.tokens, 15.0));
+        assert!(close(fill.cost, 8.0));
+        assert_eq!(fill.worst_price, 0.60);
+    }
+
+    #[test]
+    fn usd_budget_larger_than_book() {
+        let asks = [level(0.50, 10.0)];
+        let fill = vwap_for_usd(&asks, 100.0);
+        assert!(close(fill.tokens, 10.0));
+        assert!(close(fill.cost, 5.0));
+    }
+
+    #[test]
+    fn zero_price_level_stops_the_walk() {
+        let levels = [level(0.30, 5.0), level(0.0, 50.0), level(0.35, 5.0)];
+        let by_usd = vwap_for_usd(&levels, 100.0);
+        assert!(close(by_usd.tokens, 5.0));
+        assert!(by_usd.vwap.is_finite());
+        let by_size = vwap_to_size(&levels, 20.0);
+        assert!(close(by_size.tokens, 5.0));
+        assert!(close(by_size.vwap, 0.30));
+        assert_eq!(vwap_for_usd(&[level(0.0, 10.0)], 5.0), Fill::default());
+    }
+
+    #[test]
+    fn new_sorts_bids_down_and_asks_up() {
+        let book = Book::new(
+            vec![level(0.40, 1.0), level(0.45, 1.0), level(0.42, 1.0)],
+            vec![level(0.55, 1.0), level(0.50, 1.0), level(0.60, 1.0)],
+        );
+        let prices = |levels: &[Level]| levels.iter().map(|l| l.price).collect::<Vec<_>>();
+        assert_eq!(prices(&book.bids), vec![0.45, 0.42, 0.40]);
+        assert_eq!(prices(&book.asks), vec![0.50, 0.55, 0.60]);
+        assert!(close(book.mid().unwrap(), 0.475));
+        assert!(close(book.spread().unwrap(), 0.05));
+    }
+
+    #[test]
+    fn from_json_reads_string_numbers_and_missing_sides() {
+        let book = Book::from_json(&json!({
+            "bids": [{"price": "0.48", "size": "20"}, {"price": "bad", "size": "1"}, {"price": 0.49, "size": 5}],
+        }));
+        assert_eq!(book.bids, vec![level(0.49, 5.0), level(0.48, 20.0)]);
+        assert!(book.asks.is_empty());
+        assert_eq!(book.mid(), None);
+    }
+
+    #[test]
+    fn depth_within_counts_levels_near_best() {
+        let asks = [level(0.50, 10.0), level(0.51, 10.0), level(0.53, 10.0)];
+        assert!(close(depth_within(&asks, 0.01), 0.50 * 10.0 + 0.51 * 10.0));
+        assert!(close(depth_within(&asks, 0.0), 5.0));
+        let bids = [level(0.49, 10.0), level(0.47, 10.0)];
+        assert!(close(depth_within(&bids, 0.02), 0.49 * 10.0 + 0.47 * 10.0));
+        assert_eq!(depth_within(&[], 1.0), 0.0);
+    }
+
+    #[test]
+    fn imbalance_over_top_levels() {
+        let book = Book::new(
+            vec![level(0.49, 30.0), level(0.48, 100.0)],
+            vec![level(0.51, 10.0), level(0.52, 100.0)],
+        );
+        assert!(close(book.imbalance(1), 0.5));
+        assert!(close(book.imbalance(2), (130.0 - 110.0) / 240.0));
+        assert_eq!(Book::new(vec![level(0.5, 1.0)], Vec::new()).imbalance(5), 1.0);
+        assert_eq!(Book::default().imbalance(5), 0.0);
+    }
+}
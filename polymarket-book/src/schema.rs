@@ -0,0 +1,155 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Raw payloads kept per unknown kind / field are cut to this many chars
+const SAMPLE_CHARS: usize = 400;
+
+// One message kind a client understands, with every top-level field it reads or knowingly skips
+// (fields: None = the kind is expected but its fields aren't tracked)
+pub struct MessageSchema {
+    pub kind: &'static str,
+    pub fields: Option<&'static [&'static str]>,
+}
+
+// Something the telemetry hasn't seen before - returned once so the caller can log it with its own logger
+#[derive(Debug, Clone)]
+pub enum Novelty {
+    Kind { kind: String, sample: String },
+    Field { kind: String, field: String, sample: String },
+    Unparsed { kind: String, error: String, sample: String },
+}
+
+impl std::fmt::Display for Novelty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Novelty::Kind { kind, sample } => write!(f, "unknown message type \"{}\": {}", kind, sample),
+            Novelty::Field { kind, field, sample } => {
+                write!(f, "unknown field \"{}\" on \"{}\": {}", field, kind, sample)
+            }
+            Novelty::Unparsed { kind, error, sample } => {
+                write!(f, "\"{}\" message could not be parsed ({}): {}", kind, error, sample)
+            }
+        }
+    }
+}
+
+// Count, first / last sighting (unix ms) and the first raw payload of one unknown kind, field or parse failure
+#[derive(Debug, Clone)]
+pub struct Sighting {
+    pub count: u64,
+    pub first_seen_ms: i64,
+    pub last_seen_ms: i64,
+    pub sample: String,
+}
+
+#[derive(Default)]
+struct Counters {
+    messages: u64,
+    kinds: HashMap<String, Sighting>,
+    fields: HashMap<(String, String), Sighting>,
+    unparsed: HashMap<String, Sighting>,
+}
+
+// Tracks WS payloads that don't match the schema a client was written against, so an API change
+// shows up in logs / metrics instead of as quietly missing books or trades
+pub struct SchemaTelemetry {
+    pub channel: &'static str,
+    pub version: u32, // Bumped whenever a client's schema table changes (reported next to the counts)
+    schemas: &'static [MessageSchema],
+    counters: Mutex<Counters>,
+}
+
+impl SchemaTelemetry {
+    pub fn new(channel: &'static str, version: u32, schemas: &'static [MessageSchema]) -> Self {
+        Self { channel, version, schemas, counters: Mutex::new(Counters::default()) }
+    }
+
+    // Check one message (or nested payload) of `kind` against the table; returns what is new this time
+    pub fn observe(&self, kind: &str, message: &Value) -> Vec<Novelty> {
+        let Ok(mut counters) = self.counters.lock() else {
+            return Vec::new();
+        };
+        counters.messages += 1;
+        let Some(schema) = self.schemas.iter().find(|s| s.kind == kind) else {
+            return bump(&mut counters.kinds, kind.to_string(), message)
+                .map(|sample| Novelty::Kind { kind: kind.to_string(), sample })
+                .into_iter()
+                .collect();
+        };
+        let (Some(known), Some(object)) = (schema.fields, message.as_object()) else {
+            return Vec::new();
+        };
+        let mut novel = Vec::new();
+        for field in object.keys().filter(|k| !known.contains(&k.as_str())) {
+            if let Some(sample) = bump(&mut counters.fields, (kind.to_string(), field.clone()), message) {
+                novel.push(Novelty::Field { kind: kind.to_string(), field: field.clone(), sample });
+            }
+        }
+        novel
+    }
+
+    // A known kind whose payload no longer deserializes (the usual way a renamed / retyped field hides)
+    pub fn unparsed(&self, kind: &str, error: &str, message: &Value) -> Option<Novelty> {
+        let mut counters = self.counters.lock().ok()?;
+        bump(&mut counters.unparsed, kind.to_string(), message).map(|sample| Novelty::Unparsed {
+            kind: kind.to_string(),
+            error: error.to_string(),
+            sample,
+        })
+    }
+
+    // Counts and samples for health endpoints / heartbeat files
+    pub fn to_json(&self) -> Value {
+        let Ok(counters) = self.counters.lock() else {
+            return Value::Null;
+        };
+        let sighting = |s: &Sighting| {
+            serde_json::json!({
+                "count": s.count,
+                "first_seen_ms": s.first_seen_ms,
+                "last_seen_ms": s.last_seen_ms,
+                "sample": s.sample,
+            })
+        };
+        let fields: serde_json::Map<String, Value> = counters
+            .fields
+            .iter()
+            .map(|((kind, field), s)| (format!("{}.{}", kind, field), sighting(s)))
+            .collect();
+        serde_json::json!({
+            "channel": self.channel,
+            "schema_version": self.version,
+            "messages": counters.messages,
+            "unknown_types": counters.kinds.iter().map(|(k, s)| (k.clone(), sighting(s))).collect::<serde_json::Map<_, _>>(),
+            "unknown_fields": fields,
+            "unparsed": counters.unparsed.iter().map(|(k, s)| (k.clone(), sighting(s))).collect::<serde_json::Map<_, _>>(),
+        })
+    }
+}
+
+// Count one sighting; the kept sample the first time this key shows up
+fn bump<K: std::hash::Hash + Eq>(map: &mut HashMap<K, Sighting>, key: K, message: &Value) -> Option<String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or(0);
+    match map.get_mut(&key) {
+        Some(s) => {
+            s.count += 1;
+            s.last_seen_ms = now;
+            None
+        }
+        None => {
+            let sample = sample(message);
+            map.insert(key, Sighting { count: 1, first_seen_ms: now, last_seen_ms: now, sample: sample.clone() });
+            Some(sample)
+        }
+    }
+}
+
+fn sample(message: &Value) -> String {
+    let raw = message.to_string();
+    match raw.char_indices().nth(SAMPLE_CHARS) {
+        Some((cut, _)) => format!("{}...", &raw[..cut]),
+        None => raw,
+    }
+}
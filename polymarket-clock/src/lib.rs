@@ -0,0 +1,85 @@
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, TimeZone, Utc};
+use std::time::{Duration, Instant};
+
+// Anything below this is seconds, not ms (1e12 ms = Sep 2001, no market is that old)
+const MS_THRESHOLD: i64 = 1_000_000_000_000;
+
+pub fn now_ms() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+// Unix timestamp in seconds or ms -> ms (data-api sends seconds, RTDS / CLOB send ms)
+pub fn to_ms(ts: i64) -> i64 {
+    if ts.abs() < MS_THRESHOLD {
+        ts * 1000
+    } else {
+        ts
+    }
+}
+
+// Unix timestamp in seconds or ms -> seconds
+pub fn to_secs(ts: i64) -> i64 {
+    to_ms(ts) / 1000
+}
+
+pub fn from_ms(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or_else(Utc::now)
+}
+
+// How long ago a seconds-or-ms timestamp was, in ms
+pub fn age_ms(ts: i64) -> i64 {
+    now_ms() - to_ms(ts)
+}
+
+// Nth Sunday of a month (n = 1 for the first)
+fn nth_sunday(year: i32, month: u32, n: u32) -> NaiveDate {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default();
+    let to_sunday = (7 - first.weekday().num_days_from_sunday()) % 7;
+    first + chrono::Duration::days((to_sunday + 7 * (n - 1)) as i64)
+}
+
+// US Eastern offset at this instant (DST: 2nd Sunday of March 07:00 UTC -> 1st Sunday of November 06:00 UTC)
+pub fn eastern_offset(utc: DateTime<Utc>) -> FixedOffset {
+    let year = utc.year();
+    let dst_start = nth_sunday(year, 3, 2).and_hms_opt(7, 0, 0).unwrap_or_default().and_utc();
+    let dst_end = nth_sunday(year, 11, 1).and_hms_opt(6, 0, 0).unwrap_or_default().and_utc();
+    let hours = if utc >= dst_start && utc < dst_end { -4 } else { -5 };
+    FixedOffset::east_opt(hours * 3600).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+}
+
+// Polymarket market time (markets, the 15m windows included, are named and resolved in ET)
+pub fn market_time(utc: DateTime<Utc>) -> DateTime<FixedOffset> {
+    utc.with_timezone(&eastern_offset(utc))
+}
+
+// e.g. market_time_str(ts, "%H:%M:%S") -> "14:05:09 EDT" (abbreviation follows DST); ts in seconds or ms
+pub fn market_time_str(ts: i64, fmt: &str) -> String {
+    let et = market_time(from_ms(to_ms(ts)));
+    let abbrev = if et.offset().local_minus_utc() == -4 * 3600 { "EDT" } else { "EST" };
+    format!("{} {}", et.format(fmt), abbrev)
+}
+
+// Machine-local time for operator-facing output, not log files; ts in seconds or ms
+pub fn local_time_str(ts: i64, fmt: &str) -> String {
+    Local.from_utc_datetime(&from_ms(to_ms(ts)).naive_utc()).format(fmt).to_string()
+}
+
+// Monotonic timer for latencies (unaffected by wall-clock jumps / NTP)
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyTimer {
+    started: Instant,
+}
+
+impl LatencyTimer {
+    pub fn start() -> Self {
+        Self { started: Instant::now() }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+}
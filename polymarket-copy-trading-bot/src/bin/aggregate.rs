@@ -1,85 +1,226 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use anyhow::Result;
 use polymarket_copy_rust::utils::theme::colors;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str =
+    "Usage: aggregate [dir|file|glob ...] [--lang en|ru] [--csv <dir>] [--md <file.md>] [--out <file.json>]";
+
+// Scanned when no paths are given
+const DEFAULT_DIRS: [&str; 4] = [
+    "trader_scan_results",
+    "trader_analysis_results",
+    "top_traders_results",
+    "strategy_factory_results",
+];
+const DEFAULT_OUTPUT: &str = "strategy_factory_results/aggregated_results.json";
+// Rows in the printed / exported tables (the JSON keeps 20 strategies)
+const TOP_STRATEGIES: usize = 15;
+const TOP_TRADERS: usize = 10;
+
+// Result file schema (one JSON object per file; snake_case or camelCase keys):
+// {
+//   "scanDate": "2024-05-01T12:00:00Z",                                 optional
+//   "config": { "historyDays": 30, "multiplier": 1.0,                    historyDays required, multiplier defaults to 1
+//               "minOrderSize": 1.0, "startingCapital": 1000.0 },       optional
+//   "traders": [                                                         or "results"
+//     { "address": "0x...", "roi": 12.5, "totalPnl": 125.0,             roi in %, traders without roi are ignored
+//       "winRate": 61.0, "copiedTrades": 40, "status": "ok" }
+//   ],
+//   "manifest": { "seed": 0, "configHash": "...", "dataHash": "..." }   optional (simulate --json writes one)
+// }
+// A strategy is historyDays x multiplier ("30d_1x"); files with the same pair are merged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResultFile {
+    #[serde(default, alias = "scanDate")]
+    scan_date: Option<String>,
+    #[serde(default)]
+    manifest: Option<Manifest>,
+    config: ResultConfig,
+    #[serde(default, alias = "results")]
+    traders: Vec<TraderResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ResultConfig {
+    #[serde(alias = "historyDays")]
+    history_days: Option<u32>,
+    #[serde(default = "default_multiplier")]
+    multiplier: f64,
+    #[serde(default, alias = "minOrderSize")]
+    min_order_size: Option<f64>,
+    #[serde(default, alias = "startingCapital")]
+    starting_capital: Option<f64>,
+}
+
+// Inputs of a reproducible run: files with the same configHash (and dataHash) come from the same options and data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default, alias = "configHash")]
+    config_hash: Option<String>,
+    #[serde(default, alias = "dataHash")]
+    data_hash: Option<String>,
+}
+
+fn default_multiplier() -> f64 {
+    1.0
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraderResult {
+    #[serde(default)]
     address: Option<String>,
+    #[serde(default)]
     roi: Option<f64>,
+    #[serde(default, alias = "totalPnl")]
     total_pnl: Option<f64>,
-    #[serde(rename = "totalPnl")]
-    total_pnl_camel: Option<f64>,
+    #[serde(default, alias = "winRate")]
     win_rate: Option<f64>,
-    #[serde(rename = "winRate")]
-    win_rate_camel: Option<f64>,
+    #[serde(default, alias = "copiedTrades")]
     copied_trades: Option<u32>,
-    #[serde(rename = "copiedTrades")]
-    copied_trades_camel: Option<u32>,
+    #[serde(default)]
     status: Option<String>,
 }
 
-impl TraderResult {
-    fn roi(&self) -> Option<f64> {
-        self.roi
-    }
-
-    fn total_pnl(&self) -> f64 {
-        self.total_pnl.or(self.total_pnl_camel).unwrap_or(0.0)
-    }
+// Output language - add a language by adding a Strings table and a match arm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    En,
+    Ru,
+}
 
-    fn win_rate(&self) -> f64 {
-        self.win_rate.or(self.win_rate_camel).unwrap_or(0.0)
+impl Lang {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" => Some(Lang::En),
+            "ru" | "russian" => Some(Lang::Ru),
+            _ => None,
+        }
     }
 
-    fn address(&self) -> Option<&str> {
-        self.address.as_deref()
+    fn strings(self) -> &'static Strings {
+        match self {
+            Lang::En => &EN,
+            Lang::Ru => &RU,
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ScanResult {
-    scan_date: Option<String>,
-    #[serde(rename = "scanDate")]
-    scan_date_camel: Option<String>,
-    config: Config,
-    summary: Option<Summary>,
-    traders: Option<Vec<TraderResult>>,
-    results: Option<Vec<TraderResult>>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Config {
-    history_days: Option<u32>,
-    #[serde(rename = "historyDays")]
-    history_days_camel: Option<u32>,
-    multiplier: Option<f64>,
-    min_order_size: Option<f64>,
-    #[serde(rename = "minOrderSize")]
-    min_order_size_camel: Option<f64>,
-    starting_capital: Option<f64>,
-    #[serde(rename = "startingCapital")]
-    starting_capital_camel: Option<f64>,
+// Every user-facing label; {} placeholders are filled in order by fill()
+struct Strings {
+    title: &'static str,
+    scanning: &'static str,
+    unreadable: &'static str,
+    processed: &'static str,
+    top_strategies: &'static str,
+    top_traders: &'static str,
+    overall: &'static str,
+    col_strategy: &'static str,
+    col_best_roi: &'static str,
+    col_best_win: &'static str,
+    col_best_pnl: &'static str,
+    col_avg_roi: &'static str,
+    col_profitable: &'static str,
+    col_files: &'static str,
+    col_address: &'static str,
+    col_best_strategy: &'static str,
+    col_times_found: &'static str,
+    total_files: &'static str,
+    total_strategies: &'static str,
+    total_traders: &'static str,
+    unique_traders: &'static str,
+    profitable_traders: &'static str,
+    best_strategy: &'static str,
+    saved: &'static str,
+    exported: &'static str,
+    no_results: &'static str,
+    mixed_inputs: &'static str,
 }
 
-impl Config {
-    fn history_days(&self) -> Option<u32> {
-        self.history_days.or(self.history_days_camel)
-    }
-
-    fn multiplier(&self) -> f64 {
-        self.multiplier.unwrap_or(1.0)
+const EN: Strings = Strings {
+    title: "📊 STRATEGY RESULTS AGGREGATOR",
+    scanning: "📁 Scanning {}: {} file(s)",
+    unreadable: "⚠ Skipped {} (not a result file: {})",
+    processed: "✓ Processed {} file(s), {} skipped",
+    top_strategies: "🏆 TOP STRATEGIES BY BEST ROI",
+    top_traders: "🎯 TOP TRADERS (found in several scans)",
+    overall: "📈 OVERALL",
+    col_strategy: "Strategy",
+    col_best_roi: "Best ROI",
+    col_best_win: "Best Win%",
+    col_best_pnl: "Best P&L",
+    col_avg_roi: "Avg ROI",
+    col_profitable: "Profitable",
+    col_files: "Files",
+    col_address: "Address",
+    col_best_strategy: "Best Strategy",
+    col_times_found: "Times found",
+    total_files: "Files:",
+    total_strategies: "Strategies:",
+    total_traders: "Traders:",
+    unique_traders: "Unique traders:",
+    profitable_traders: "Profitable traders:",
+    best_strategy: "🌟 BEST STRATEGY:",
+    saved: "✓ Aggregated results saved:",
+    exported: "✓ Exported:",
+    no_results: "No result files found",
+    mixed_inputs: "⚠ {} merges runs with {} different inputs (manifest configHash) - compare with care",
+};
+
+const RU: Strings = Strings {
+    title: "📊 АГРЕГАТОР РЕЗУЛЬТАТОВ ВСЕХ СТРАТЕГИЙ",
+    scanning: "📁 Сканирование {}: найдено {} файлов",
+    unreadable: "⚠ Пропущен {} (не файл результатов: {})",
+    processed: "✓ Обработано {} файлов, пропущено {}",
+    top_strategies: "🏆 ТОП СТРАТЕГИЙ ПО ЛУЧШЕМУ ROI",
+    top_traders: "🎯 ТОП ТРЕЙДЕРОВ (найдены в нескольких сканах)",
+    overall: "📈 ОБЩАЯ СТАТИСТИКА",
+    col_strategy: "Стратегия",
+    col_best_roi: "Лучший ROI",
+    col_best_win: "Лучший Win%",
+    col_best_pnl: "Лучший P&L",
+    col_avg_roi: "Средний ROI",
+    col_profitable: "Прибыльных",
+    col_files: "Файлов",
+    col_address: "Адрес",
+    col_best_strategy: "Лучшая стратегия",
+    col_times_found: "Найден раз",
+    total_files: "Всего файлов:",
+    total_strategies: "Всего стратегий:",
+    total_traders: "Всего трейдеров:",
+    unique_traders: "Уникальных трейдеров:",
+    profitable_traders: "Прибыльных трейдеров:",
+    best_strategy: "🌟 ЛУЧШАЯ СТРАТЕГИЯ:",
+    saved: "✓ Агрегированные результаты сохранены:",
+    exported: "✓ Экспортировано:",
+    no_results: "Файлы результатов не найдены",
+    mixed_inputs: "⚠ {} объединяет прогоны с {} разными входными данными (configHash в manifest) - сравнивать осторожно",
+};
+
+// Replace each {} in a label with the next value
+fn fill(template: &str, values: &[String]) -> String {
+    let mut out = String::new();
+    let mut values = values.iter();
+    let mut parts = template.split("{}").peekable();
+    while let Some(part) = parts.next() {
+        out.push_str(part);
+        if parts.peek().is_some() {
+            out.push_str(values.next().map(|v| v.as_str()).unwrap_or(""));
+        }
     }
+    out
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Summary {
-    total_analyzed: Option<u32>,
-    profitable: Option<u32>,
-    avg_roi: Option<f64>,
-    avg_win_rate: Option<f64>,
+// Pad to a display width - labels can be Cyrillic, so count chars, not bytes
+fn pad(s: &str, width: usize) -> String {
+    format!("{}{}", s, " ".repeat(width.saturating_sub(s.chars().count())))
 }
 
 #[derive(Debug, Clone)]
@@ -90,11 +231,31 @@ struct StrategyPerformance {
     best_roi: f64,
     best_win_rate: f64,
     best_pnl: f64,
-    avg_roi: f64,
-    avg_win_rate: f64,
+    roi_sum: f64,
+    win_rate_sum: f64,
     traders_analyzed: u32,
     profitable_traders: u32,
     files_count: u32,
+    config_hashes: BTreeSet<String>, // From the files' manifests (files without one aren't counted)
+}
+
+impl StrategyPerformance {
+    // Averages over every trader of every file of this strategy
+    fn avg_roi(&self) -> f64 {
+        if self.traders_analyzed == 0 {
+            0.0
+        } else {
+            self.roi_sum / self.traders_analyzed as f64
+        }
+    }
+
+    fn avg_win_rate(&self) -> f64 {
+        if self.traders_analyzed == 0 {
+            0.0
+        } else {
+            self.win_rate_sum / self.traders_analyzed as f64
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -104,189 +265,329 @@ struct TraderData {
     times_found: u32,
 }
 
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1).cloned())
+}
+
+// Positional arguments (anything that isn't a flag or a flag's value)
+fn positional(args: &[String]) -> Vec<String> {
+    const VALUE_FLAGS: [&str; 4] = ["--lang", "--csv", "--md", "--out"];
+    let mut out = Vec::new();
+    let mut skip_next = false;
+    for a in args {
+        if skip_next {
+            skip_next = false;
+        } else if VALUE_FLAGS.contains(&a.as_str()) {
+            skip_next = true;
+        } else if !a.starts_with("--") {
+            out.push(a.clone());
+        }
+    }
+    out
+}
+
+// Shell-style * and ? on one file name
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            wildcard_match(&pattern[1..], name) || (!name.is_empty() && wildcard_match(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => wildcard_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => wildcard_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+fn json_files_in(dir: &Path, pattern: Option<&str>) -> Result<Vec<PathBuf>> {
+    let pattern: Option<Vec<char>> = pattern.map(|p| p.chars().collect());
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let Some(name) = p.file_name().and_then(|n| n.to_str()) else {
+                return false;
+            };
+            match &pattern {
+                Some(pat) => wildcard_match(pat, &name.chars().collect::<Vec<_>>()),
+                None => name.ends_with(".json"),
+            }
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+// A directory (its *.json), a file, or a glob in the last path component (results/*_30d.json).
+// Missing default directories are skipped quietly; a missing path given on the command line is an error
+fn expand_source(source: &str, explicit: bool) -> Result<Option<(String, Vec<PathBuf>)>> {
+    let path = PathBuf::from(source);
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.contains('*') || name.contains('?') {
+        let dir = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        return Ok(Some((source.to_string(), json_files_in(&dir, Some(name))?)));
+    }
+    if path.is_dir() {
+        return Ok(Some((format!("{}/", source.trim_end_matches('/')), json_files_in(&path, None)?)));
+    }
+    if path.is_file() {
+        return Ok(Some((source.to_string(), vec![path])));
+    }
+    if explicit {
+        anyhow::bail!("{}: no such file or directory", source);
+    }
+    Ok(None)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_csv(path: &Path, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for row in rows {
+        csv.push_str(&row.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    fs::write(path, csv)?;
+    Ok(())
+}
+
+fn markdown_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut md = format!("| {} |\n", header.join(" | "));
+    md.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+    for row in rows {
+        md.push_str(&format!("| {} |\n", row.iter().map(|f| f.replace('|', "\\|")).collect::<Vec<_>>().join(" | ")));
+    }
+    md
+}
+
+fn signed(value: f64, decimals: usize) -> String {
+    format!("{}{:.*}", if value >= 0.0 { "+" } else { "" }, decimals, value)
+}
+
+// Usage:
+//   aggregate                                  the default result directories (see DEFAULT_DIRS)
+//   aggregate results/ other/*_30d.json        directories, files and globs (* and ? in the file name)
+//   aggregate --lang ru                        labels in Russian (default AGGREGATE_LANG, else English)
+//   aggregate --csv out/ --md report.md        also export the strategy / trader tables
+//   aggregate --out summary.json               where the aggregated JSON goes (default DEFAULT_OUTPUT)
 fn main() -> Result<()> {
-    println!();
-    println!(
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let lang_arg = arg_value(&args, "--lang").or_else(|| std::env::var("AGGREGATE_LANG").ok());
+    let lang = match lang_arg {
+        Some(l) => Lang::parse(&l).ok_or_else(|| anyhow::anyhow!("Unknown language '{}' (en, ru)\n{}", l, USAGE))?,
+        None => Lang::En,
+    };
+    let t = lang.strings();
+    let output_path = PathBuf::from(arg_value(&args, "--out").unwrap_or_else(|| DEFAULT_OUTPUT.to_string()));
+
+    outln!();
+    outln!(
         "{}{}{}",
         colors::ACCENT,
         "╔════════════════════════════════════════════════════════════════╗",
         colors::RESET
     );
-    println!(
-        "{}║          📊 АГРЕГАТОР РЕЗУЛЬТАТОВ ВСЕХ СТРАТЕГИЙ              ║{}",
-        colors::ACCENT,
-        colors::RESET
-    );
-    println!(
+    outln!("{}║  {}║{}", colors::ACCENT, pad(t.title, 62), colors::RESET);
+    outln!(
         "{}{}{}",
         colors::ACCENT,
         "╚════════════════════════════════════════════════════════════════╝",
         colors::RESET
     );
-    println!();
+    outln!();
 
-    let dirs = vec![
-        "trader_scan_results",
-        "trader_analysis_results",
-        "top_traders_results",
-        "strategy_factory_results",
-    ];
+    let sources = positional(&args);
+    let explicit = !sources.is_empty();
+    let sources: Vec<String> = if explicit {
+        sources
+    } else {
+        DEFAULT_DIRS.iter().map(|d| d.to_string()).collect()
+    };
 
     let mut all_strategies: HashMap<String, StrategyPerformance> = HashMap::new();
     let mut all_traders: HashMap<String, TraderData> = HashMap::new();
-    let mut total_files = 0;
+    let mut total_files: u32 = 0;
+    let mut skipped_files: u32 = 0;
 
-    for dir in &dirs {
-        let dir_path = PathBuf::from(dir);
-        if !dir_path.exists() {
+    for source in &sources {
+        let Some((label, files)) = expand_source(source, explicit)? else {
             continue;
-        }
-
-        let files: Vec<_> = fs::read_dir(&dir_path)?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|s| s == "json")
-                    .unwrap_or(false)
-            })
-            .collect();
-
-        println!(
-            "{}📁 Сканирование {}/: найдено {} файлов{}",
+        };
+        outln!(
+            "{}{}{}",
             colors::MUTED,
-            dir,
-            files.len(),
+            fill(t.scanning, &[label, files.len().to_string()]),
             colors::RESET
         );
 
-        for entry in files {
+        for file_path in files {
+            // Our own output lives next to the inputs by default
+            if file_path == output_path {
+                continue;
+            }
+            let parsed = fs::read_to_string(&file_path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<ResultFile>(&content)?));
+            let data = match parsed {
+                Ok(d) => d,
+                Err(e) => {
+                    skipped_files += 1;
+                    outln!(
+                        "{}{}{}",
+                        colors::WARN,
+                        fill(t.unreadable, &[file_path.display().to_string(), e.to_string()]),
+                        colors::RESET
+                    );
+                    continue;
+                }
+            };
+            let Some(history_days) = data.config.history_days else {
+                skipped_files += 1;
+                outln!(
+                    "{}{}{}",
+                    colors::WARN,
+                    fill(t.unreadable, &[file_path.display().to_string(), "config.historyDays missing".to_string()]),
+                    colors::RESET
+                );
+                continue;
+            };
             total_files += 1;
-            let file_path = entry.path();
-
-            if let Ok(content) = fs::read_to_string(&file_path) {
-                if let Ok(data) = serde_json::from_str::<ScanResult>(&content) {
-                    let config = data.config;
-                    let history_days = match config.history_days() {
-                        Some(d) => d,
-                        None => continue,
-                    };
-
-                    let strategy_id = format!("{}d_{}x", history_days, config.multiplier());
-
-                    all_strategies
-                        .entry(strategy_id.clone())
-                        .or_insert_with(|| StrategyPerformance {
-                            strategy_id: strategy_id.clone(),
-                            history_days,
-                            multiplier: config.multiplier(),
-                            best_roi: f64::NEG_INFINITY,
-                            best_win_rate: 0.0,
-                            best_pnl: f64::NEG_INFINITY,
-                            avg_roi: 0.0,
-                            avg_win_rate: 0.0,
-                            traders_analyzed: 0,
-                            profitable_traders: 0,
-                            files_count: 0,
-                        });
 
-                    let strategy = all_strategies.get_mut(&strategy_id).unwrap();
-                    strategy.files_count += 1;
-
-                    let traders = data.traders.or(data.results).unwrap_or_default();
-                    let mut total_roi = 0.0;
-                    let mut total_win_rate = 0.0;
-                    let mut traders_count = 0;
-
-                    for trader in traders {
-                        let roi = match trader.roi() {
-                            Some(r) => r,
-                            None => continue,
-                        };
-
-                        traders_count += 1;
-                        total_roi += roi;
-                        total_win_rate += trader.win_rate();
-
-                        if roi > strategy.best_roi {
-                            strategy.best_roi = roi;
-                        }
-                        if trader.win_rate() > strategy.best_win_rate {
-                            strategy.best_win_rate = trader.win_rate();
-                        }
-                        let pnl = trader.total_pnl();
-                        if pnl > strategy.best_pnl {
-                            strategy.best_pnl = pnl;
-                        }
-                        if roi > 0.0 {
-                            strategy.profitable_traders += 1;
-                        }
-
-                        if let Some(addr) = trader.address() {
-                            all_traders
-                                .entry(addr.to_string())
-                                .and_modify(|t| {
-                                    t.times_found += 1;
-                                    if roi > t.best_roi {
-                                        t.best_roi = roi;
-                                        t.best_strategy = strategy_id.clone();
-                                    }
-                                })
-                                .or_insert_with(|| TraderData {
-                                    best_roi: roi,
-                                    best_strategy: strategy_id.clone(),
-                                    times_found: 1,
-                                });
-                        }
-                    }
-
-                    strategy.traders_analyzed += traders_count;
-                    if traders_count > 0 {
-                        strategy.avg_roi = total_roi / traders_count as f64;
-                        strategy.avg_win_rate = total_win_rate / traders_count as f64;
-                    }
+            let multiplier = data.config.multiplier;
+            let strategy_id = format!("{}d_{}x", history_days, multiplier);
+            let strategy = all_strategies
+                .entry(strategy_id.clone())
+                .or_insert_with(|| StrategyPerformance {
+                    strategy_id: strategy_id.clone(),
+                    history_days,
+                    multiplier,
+                    best_roi: f64::NEG_INFINITY,
+                    best_win_rate: 0.0,
+                    best_pnl: f64::NEG_INFINITY,
+                    roi_sum: 0.0,
+                    win_rate_sum: 0.0,
+                    traders_analyzed: 0,
+                    profitable_traders: 0,
+                    files_count: 0,
+                    config_hashes: BTreeSet::new(),
+                });
+            strategy.files_count += 1;
+            if let Some(hash) = data.manifest.as_ref().and_then(|m| m.config_hash.clone()) {
+                strategy.config_hashes.insert(hash);
+            }
+
+            for trader in &data.traders {
+                let Some(roi) = trader.roi else {
+                    continue;
+                };
+                let win_rate = trader.win_rate.unwrap_or(0.0);
+                let pnl = trader.total_pnl.unwrap_or(0.0);
+
+                strategy.traders_analyzed += 1;
+                strategy.roi_sum += roi;
+                strategy.win_rate_sum += win_rate;
+                strategy.best_roi = strategy.best_roi.max(roi);
+                strategy.best_win_rate = strategy.best_win_rate.max(win_rate);
+                strategy.best_pnl = strategy.best_pnl.max(pnl);
+                if roi > 0.0 {
+                    strategy.profitable_traders += 1;
+                }
+
+                if let Some(addr) = trader.address.as_deref() {
+                    all_traders
+                        .entry(addr.to_lowercase())
+                        .and_modify(|d| {
+                            d.times_found += 1;
+                            if roi > d.best_roi {
+                                d.best_roi = roi;
+                                d.best_strategy = strategy_id.clone();
+                            }
+                        })
+                        .or_insert_with(|| TraderData {
+                            best_roi: roi,
+                            best_strategy: strategy_id.clone(),
+                            times_found: 1,
+                        });
                 }
             }
         }
     }
 
-    println!(
-        "{}✓ Обработано {} файлов{}\n",
+    outln!(
+        "{}{}{}\n",
         colors::SUCCESS,
-        total_files,
+        fill(t.processed, &[total_files.to_string(), skipped_files.to_string()]),
         colors::RESET
     );
+    if total_files == 0 {
+        outln!("{}{}{}\n", colors::WARN, t.no_results, colors::RESET);
+        return Ok(());
+    }
 
+    // Ties broken by id / address so the same inputs always print and export in the same order
     let mut strategies: Vec<_> = all_strategies.values().cloned().collect();
-    strategies.sort_by(|a, b| b.best_roi.partial_cmp(&a.best_roi).unwrap());
+    strategies.sort_by(|a, b| b.best_roi.total_cmp(&a.best_roi).then_with(|| a.strategy_id.cmp(&b.strategy_id)));
+    let mut top_traders: Vec<_> = all_traders.iter().collect();
+    top_traders.sort_by(|(a_addr, a), (b_addr, b)| b.best_roi.total_cmp(&a.best_roi).then_with(|| a_addr.cmp(b_addr)));
+    for s in strategies.iter().filter(|s| s.config_hashes.len() > 1) {
+        outln!(
+            "{}{}{}",
+            colors::WARN,
+            fill(t.mixed_inputs, &[s.strategy_id.clone(), s.config_hashes.len().to_string()]),
+            colors::RESET
+        );
+    }
 
-    println!("{}{}{}", colors::ACCENT, "═".repeat(100), colors::RESET);
-    println!("{}  🏆 ТОП СТРАТЕГИЙ ПО ЛУЧШЕМУ ROI{}", colors::ACCENT, colors::RESET);
-    println!("{}{}{}\n", colors::ACCENT, "═".repeat(100), colors::RESET);
+    outln!("{}{}{}", colors::ACCENT, "═".repeat(100), colors::RESET);
+    outln!("{}  {}{}", colors::ACCENT, t.top_strategies, colors::RESET);
+    outln!("{}{}{}\n", colors::ACCENT, "═".repeat(100), colors::RESET);
 
-    println!(
-        "{}  #  | Strategy      | Best ROI  | Best Win% | Best P&L   | Avg ROI   | Profitable | Files{}",
+    outln!(
+        "{}  #  | {} | {} | {} | {} | {} | {} | {}{}",
         colors::BOLD,
+        pad(t.col_strategy, 13),
+        pad(t.col_best_roi, 9),
+        pad(t.col_best_win, 9),
+        pad(t.col_best_pnl, 10),
+        pad(t.col_avg_roi, 9),
+        pad(t.col_profitable, 10),
+        t.col_files,
         colors::RESET
     );
-    println!("{}{}{}", colors::MUTED, "─".repeat(100), colors::RESET);
+    outln!("{}{}{}", colors::MUTED, "─".repeat(100), colors::RESET);
 
-    for (i, s) in strategies.iter().take(15).enumerate() {
+    for (i, s) in strategies.iter().take(TOP_STRATEGIES).enumerate() {
         let roi_color = if s.best_roi >= 0.0 {
             colors::SUCCESS
         } else {
             colors::ERROR
         };
-        let roi_sign = if s.best_roi >= 0.0 { "+" } else { "" };
-        let pnl_sign = if s.best_pnl >= 0.0 { "+" } else { "" };
-
-        let roi_str = format!("{}{:.1}%", roi_sign, s.best_roi);
-        let pnl_str = format!("{}{:.0}", pnl_sign, s.best_pnl);
+        let roi_str = format!("{}%", signed(s.best_roi, 1));
+        let pnl_str = signed(s.best_pnl, 0);
         let win_rate_str = format!("{:.1}%", s.best_win_rate);
-        let avg_roi_str = format!("{:.1}%", s.avg_roi);
+        let avg_roi_str = format!("{:.1}%", s.avg_roi());
         let profitable_str = format!("{}/{}", s.profitable_traders, s.traders_analyzed);
-        println!(
+        outln!(
             "  {}{:2}{} | {}{:13}{} | {}{:9}{} | {}{:9}{} | ${:9}{} | {:9} | {:10} | {}",
             colors::WARN,
             i + 1,
@@ -308,34 +609,29 @@ fn main() -> Result<()> {
         );
     }
 
-    println!("\n{}{}{}", colors::ACCENT, "═".repeat(100), colors::RESET);
-    println!(
-        "{}  🎯 ТОП ТРЕЙДЕРОВ (найдены в нескольких сканах){}",
-        colors::ACCENT,
-        colors::RESET
-    );
-    println!("{}{}{}\n", colors::ACCENT, "═".repeat(100), colors::RESET);
+    outln!("\n{}{}{}", colors::ACCENT, "═".repeat(100), colors::RESET);
+    outln!("{}  {}{}", colors::ACCENT, t.top_traders, colors::RESET);
+    outln!("{}{}{}\n", colors::ACCENT, "═".repeat(100), colors::RESET);
 
-    let mut top_traders: Vec<_> = all_traders.iter().collect();
-    top_traders.sort_by(|(_, a), (_, b)| b.best_roi.partial_cmp(&a.best_roi).unwrap());
-
-    println!(
-        "{}  #  | Address                                    | Best ROI  | Best Strategy | Найден раз{}",
+    outln!(
+        "{}  #  | {} | {} | {} | {}{}",
         colors::BOLD,
+        pad(t.col_address, 42),
+        pad(t.col_best_roi, 9),
+        pad(t.col_best_strategy, 13),
+        t.col_times_found,
         colors::RESET
     );
-    println!("{}{}{}", colors::MUTED, "─".repeat(100), colors::RESET);
+    outln!("{}{}{}", colors::MUTED, "─".repeat(100), colors::RESET);
 
-    for (i, (address, data)) in top_traders.iter().take(10).enumerate() {
+    for (i, (address, data)) in top_traders.iter().take(TOP_TRADERS).enumerate() {
         let roi_color = if data.best_roi >= 0.0 {
             colors::SUCCESS
         } else {
             colors::ERROR
         };
-        let roi_sign = if data.best_roi >= 0.0 { "+" } else { "" };
-
-        let roi_str = format!("{}{:.1}%", roi_sign, data.best_roi);
-        println!(
+        let roi_str = format!("{}%", signed(data.best_roi, 1));
+        outln!(
             "  {}{:2}{} | {}{:42}{} | {}{:9}{} | {}{:13}{} | {}",
             colors::WARN,
             i + 1,
@@ -353,9 +649,9 @@ fn main() -> Result<()> {
         );
     }
 
-    println!("\n{}{}{}", colors::ACCENT, "═".repeat(100), colors::RESET);
-    println!("{}  📈 ОБЩАЯ СТАТИСТИКА{}", colors::ACCENT, colors::RESET);
-    println!("{}{}{}\n", colors::ACCENT, "═".repeat(100), colors::RESET);
+    outln!("\n{}{}{}", colors::ACCENT, "═".repeat(100), colors::RESET);
+    outln!("{}  {}{}", colors::ACCENT, t.overall, colors::RESET);
+    outln!("{}{}{}\n", colors::ACCENT, "═".repeat(100), colors::RESET);
 
     let total_traders: u32 = strategies.iter().map(|s| s.traders_analyzed).sum();
     let total_profitable: u32 = strategies.iter().map(|s| s.profitable_traders).sum();
@@ -366,27 +662,13 @@ fn main() -> Result<()> {
         0.0
     };
 
-    println!("  Всего файлов:           {}{}{}", colors::ACCENT, total_files, colors::RESET);
-    println!(
-        "  Всего стратегий:        {}{}{}",
-        colors::ACCENT,
-        strategies.len(),
-        colors::RESET
-    );
-    println!(
-        "  Всего трейдеров:        {}{}{}",
-        colors::ACCENT,
-        total_traders,
-        colors::RESET
-    );
-    println!(
-        "  Уникальных трейдеров:   {}{}{}",
-        colors::ACCENT,
-        unique_traders,
-        colors::RESET
-    );
-    println!(
-        "  Прибыльных трейдеров:   {}{} ({:.1}%){}",
+    outln!("  {} {}{}{}", pad(t.total_files, 22), colors::ACCENT, total_files, colors::RESET);
+    outln!("  {} {}{}{}", pad(t.total_strategies, 22), colors::ACCENT, strategies.len(), colors::RESET);
+    outln!("  {} {}{}{}", pad(t.total_traders, 22), colors::ACCENT, total_traders, colors::RESET);
+    outln!("  {} {}{}{}", pad(t.unique_traders, 22), colors::ACCENT, unique_traders, colors::RESET);
+    outln!(
+        "  {} {}{} ({:.1}%){}",
+        pad(t.profitable_traders, 22),
         colors::SUCCESS,
         total_profitable,
         profitable_rate,
@@ -394,31 +676,83 @@ fn main() -> Result<()> {
     );
 
     if let Some(best) = strategies.first() {
-        println!("\n{}🌟 ЛУЧШАЯ СТРАТЕГИЯ:{}", colors::SUCCESS, colors::RESET);
-        println!("  ID: {}{}{}", colors::WARN, best.strategy_id, colors::RESET);
-        println!(
-            "  ROI: {}+{:.2}%{}",
-            colors::SUCCESS,
-            best.best_roi,
-            colors::RESET
-        );
-        println!(
-            "  Win Rate: {}{:.1}%{}",
-            colors::WARN,
-            best.best_win_rate,
-            colors::RESET
-        );
-        println!(
-            "  P&L: {}+${:.2}{}",
+        outln!("\n{}{}{}", colors::SUCCESS, t.best_strategy, colors::RESET);
+        outln!("  ID: {}{}{}", colors::WARN, best.strategy_id, colors::RESET);
+        outln!("  ROI: {}{}%{}", colors::SUCCESS, signed(best.best_roi, 2), colors::RESET);
+        outln!("  Win Rate: {}{:.1}%{}", colors::WARN, best.best_win_rate, colors::RESET);
+        outln!(
+            "  P&L: {}{}${:.2}{}",
             colors::SUCCESS,
-            best.best_pnl,
+            if best.best_pnl >= 0.0 { "+" } else { "-" },
+            best.best_pnl.abs(),
             colors::RESET
         );
     }
 
-    let output_dir = PathBuf::from("strategy_factory_results");
-    fs::create_dir_all(&output_dir)?;
-    let output_path = output_dir.join("aggregated_results.json");
+    // Table rows shared by the CSV and Markdown exports
+    let strategy_rows: Vec<Vec<String>> = strategies
+        .iter()
+        .take(TOP_STRATEGIES)
+        .enumerate()
+        .map(|(i, s)| {
+            vec![
+                (i + 1).to_string(),
+                s.strategy_id.clone(),
+                format!("{:.2}", s.best_roi),
+                format!("{:.2}", s.best_win_rate),
+                format!("{:.2}", s.best_pnl),
+                format!("{:.2}", s.avg_roi()),
+                format!("{}/{}", s.profitable_traders, s.traders_analyzed),
+                s.files_count.to_string(),
+            ]
+        })
+        .collect();
+    let trader_rows: Vec<Vec<String>> = top_traders
+        .iter()
+        .take(TOP_TRADERS)
+        .enumerate()
+        .map(|(i, (address, data))| {
+            vec![
+                (i + 1).to_string(),
+                (*address).clone(),
+                format!("{:.2}", data.best_roi),
+                data.best_strategy.clone(),
+                data.times_found.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut exported: Vec<String> = Vec::new();
+    // CSV headers stay English snake_case regardless of --lang (they're for spreadsheets / scripts)
+    if let Some(dir) = arg_value(&args, "--csv") {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir)?;
+        let strategies_csv = dir.join("strategies.csv");
+        let traders_csv = dir.join("traders.csv");
+        write_csv(
+            &strategies_csv,
+            &["rank", "strategy_id", "best_roi", "best_win_rate", "best_pnl", "avg_roi", "profitable", "files"],
+            &strategy_rows,
+        )?;
+        write_csv(&traders_csv, &["rank", "address", "best_roi", "best_strategy", "times_found"], &trader_rows)?;
+        exported.push(strategies_csv.display().to_string());
+        exported.push(traders_csv.display().to_string());
+    }
+    if let Some(md_path) = arg_value(&args, "--md") {
+        let md = format!(
+            "# {}\n\n## {}\n\n{}\n## {}\n\n{}",
+            t.title,
+            t.top_strategies,
+            markdown_table(
+                &["#", t.col_strategy, t.col_best_roi, t.col_best_win, t.col_best_pnl, t.col_avg_roi, t.col_profitable, t.col_files],
+                &strategy_rows
+            ),
+            t.top_traders,
+            markdown_table(&["#", t.col_address, t.col_best_roi, t.col_best_strategy, t.col_times_found], &trader_rows),
+        );
+        fs::write(&md_path, md)?;
+        exported.push(md_path);
+    }
 
     #[derive(Serialize)]
     struct Output {
@@ -431,6 +765,7 @@ fn main() -> Result<()> {
     #[derive(Serialize)]
     struct OutputSummary {
         total_files: u32,
+        skipped_files: u32,
         total_strategies: usize,
         total_traders: u32,
         unique_traders: usize,
@@ -451,6 +786,7 @@ fn main() -> Result<()> {
         traders_analyzed: u32,
         profitable_traders: u32,
         files_count: u32,
+        config_hashes: Vec<String>,
     }
 
     #[derive(Serialize)]
@@ -465,6 +801,7 @@ fn main() -> Result<()> {
         timestamp: chrono::Utc::now().to_rfc3339(),
         summary: OutputSummary {
             total_files,
+            skipped_files,
             total_strategies: strategies.len(),
             total_traders,
             unique_traders,
@@ -481,16 +818,17 @@ fn main() -> Result<()> {
                 best_roi: s.best_roi,
                 best_win_rate: s.best_win_rate,
                 best_pnl: s.best_pnl,
-                avg_roi: s.avg_roi,
-                avg_win_rate: s.avg_win_rate,
+                avg_roi: s.avg_roi(),
+                avg_win_rate: s.avg_win_rate(),
                 traders_analyzed: s.traders_analyzed,
                 profitable_traders: s.profitable_traders,
                 files_count: s.files_count,
+                config_hashes: s.config_hashes.iter().cloned().collect(),
             })
             .collect(),
         top_traders: top_traders
             .iter()
-            .take(10)
+            .take(TOP_TRADERS)
             .map(|(address, data)| TraderOutput {
                 address: (*address).clone(),
                 best_roi: data.best_roi,
@@ -500,14 +838,23 @@ fn main() -> Result<()> {
             .collect(),
     };
 
+    if let Some(parent) = output_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
     fs::write(&output_path, serde_json::to_string_pretty(&output)?)?;
-    println!(
-        "\n{}✓ Агрегированные результаты сохранены:{} {}{}\n",
+    outln!(
+        "\n{}{}{} {}{}{}",
         colors::SUCCESS,
+        t.saved,
         colors::RESET,
         colors::ACCENT,
-        output_path.display()
+        output_path.display(),
+        colors::RESET
     );
+    for path in exported {
+        outln!("{}{}{} {}{}{}", colors::SUCCESS, t.exported, colors::RESET, colors::ACCENT, path, colors::RESET);
+    }
+    outln!();
 
     Ok(())
 }
@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{backup_database, restore_database, BackupSummary, Progress};
+use polymarket_copy_rust::{Db, Logger};
+
+const USAGE: &str = "Usage: backup [create] [file.jsonl.gz] | restore <file.jsonl.gz> [--drop] [--json]";
+
+// Usage:
+//   backup [create] [file]        dump all bot collections (activities, positions, configs, annotations)
+//   backup restore <file> [--drop] load an archive back (--drop = replace collections instead of merging)
+//   --json                         print the per-collection counts as a JSON summary (for cron / scripts)
+// Only needs the storage settings (STORAGE_BACKEND, MONGO_URI / SQLITE_PATH), so it also works on a fresh server
+// before the rest of .env exists. Moving from Mongo to SQLite: back up with STORAGE_BACKEND=mongo, restore with sqlite
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().filter(|a| a != "--json").collect();
+    let json = Progress::json_requested();
+
+    match args.first().map(|s| s.as_str()) {
+        Some("help") | Some("--help") => outln!("{}", USAGE),
+        Some("restore") => {
+            let path = args
+                .get(1)
+                .filter(|a| !a.starts_with("--"))
+                .ok_or_else(|| anyhow::anyhow!(USAGE))?;
+            let drop_existing = args.iter().any(|a| a == "--drop");
+            let db = Db::open_from_env().await?;
+            if !json {
+                Logger::info(&format!(
+                    "Restoring {}{}...",
+                    path,
+                    if drop_existing { " (replacing existing collections)" } else { "" }
+                ));
+            }
+            let summary = restore_database(&db, path, drop_existing).await?;
+            print_summary("Restore", &summary);
+            if !json {
+                Logger::success(&format!("Restored {} document(s)", summary.values().sum::<u64>()));
+            }
+        }
+        first => {
+            let path_arg = if first == Some("create") { args.get(1) } else { args.first() };
+            let path = path_arg.cloned().unwrap_or_else(|| {
+                format!("backup_{}.jsonl.gz", chrono::Utc::now().format("%Y%m%d_%H%M%S"))
+            });
+            let db = Db::open_from_env().await?;
+            if !json {
+                Logger::info(&format!("Backing up to {}...", path));
+            }
+            let summary = backup_database(&db, &path).await?;
+            print_summary(&format!("Backup -> {}", path), &summary);
+            if !json {
+                Logger::success(&format!(
+                    "Backed up {} document(s) from {} collection(s) to {}",
+                    summary.values().sum::<u64>(),
+                    summary.len(),
+                    path
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_summary(operation: &str, summary: &BackupSummary) {
+    let mut progress = Progress::new(operation, Some(summary.len()));
+    for (name, count) in summary {
+        progress.ok(name, &format!("{} document(s)", count), None);
+    }
+    progress.finish();
+}
@@ -1,5 +1,9 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use anyhow::Result;
-use polymarket_copy_rust::{fetch_data, EnvConfig};
+use polymarket_copy_rust::{fetch_paginated, EnvConfig};
+use polymarket_copy_rust::utils::{clock, http_client, HttpPool};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Clone)]
@@ -28,16 +32,17 @@ struct Activity {
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
 
     let config = EnvConfig::from_env().await?;
     let wallet = &config.proxy_wallet;
 
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Api);
     let url = format!(
         "https://data-api.polymarket.com/activity?user={}&type=TRADE",
         wallet
     );
-    let activities: Vec<Activity> = fetch_data(
+    let activities: Vec<Activity> = fetch_paginated(
         &client,
         &url,
         config.request_timeout_ms,
@@ -51,7 +56,7 @@ async fn main() -> Result<()> {
     .collect();
 
     if activities.is_empty() {
-        println!("No trade data available");
+        outln!("No trade data available");
         return Ok(());
     }
 
@@ -59,14 +64,14 @@ async fn main() -> Result<()> {
         .unwrap()
         .timestamp();
 
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("📋 CLOSED POSITIONS (Redeemed October 31, 2025 at 18:00-18:14)");
-    println!("═══════════════════════════════════════════════════════════════\n");
-    println!("💰 TOTAL RECEIVED FROM REDEMPTION: $66.37 USDC\n");
+    outln!("═══════════════════════════════════════════════════════════════");
+    outln!("📋 CLOSED POSITIONS (Redeemed October 31, 2025 at 18:00-18:14)");
+    outln!("═══════════════════════════════════════════════════════════════\n");
+    outln!("💰 TOTAL RECEIVED FROM REDEMPTION: $66.37 USDC\n");
 
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("🛒 PURCHASES AFTER REDEMPTION (after 18:14 UTC October 31)");
-    println!("═══════════════════════════════════════════════════════════════\n");
+    outln!("═══════════════════════════════════════════════════════════════");
+    outln!("🛒 PURCHASES AFTER REDEMPTION (after 18:14 UTC October 31)");
+    outln!("═══════════════════════════════════════════════════════════════\n");
 
     let trades_after_redemption: Vec<&Activity> = activities
         .iter()
@@ -74,49 +79,47 @@ async fn main() -> Result<()> {
         .collect();
 
     if trades_after_redemption.is_empty() {
-        println!("✅ No purchases after redemption!\n");
-        println!("This means funds should be in the balance.");
+        outln!("✅ No purchases after redemption!\n");
+        outln!("This means funds should be in the balance.");
         return Ok(());
     }
 
     let mut total_spent = 0.0;
 
     for (i, trade) in trades_after_redemption.iter().enumerate() {
-        let date = chrono::DateTime::from_timestamp(trade.timestamp, 0)
-            .unwrap_or_default()
-            .format("%Y-%m-%d %H:%M:%S");
+        let date = clock::local_time_str(trade.timestamp, "%Y-%m-%d %H:%M:%S");
         let value = trade.usdc_size;
         total_spent += value;
 
-        println!("{}. 🟢 BOUGHT: {}", 
+        outln!("{}. 🟢 BOUGHT: {}", 
             i + 1,
             trade.title.as_deref()
                 .or_else(|| trade.market.as_deref())
                 .unwrap_or("Unknown")
         );
-        println!("   💸 Spent: ${:.2}", value);
-        println!("   📊 Size: {:.2} tokens @ ${:.4}", trade.size, trade.price);
-        println!("   📅 Date: {}", date);
+        outln!("   💸 Spent: ${:.2}", value);
+        outln!("   📊 Size: {:.2} tokens @ ${:.4}", trade.size, trade.price);
+        outln!("   📅 Date: {}", date);
         let tx_hash = &trade.transaction_hash;
-        println!(
+        outln!(
             "   🔗 TX: https://polygonscan.com/tx/{}...\n",
             &tx_hash[..tx_hash.len().min(20)]
         );
     }
 
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("📊 TOTAL PURCHASES AFTER REDEMPTION:");
-    println!("   Number of trades: {}", trades_after_redemption.len());
-    println!("   💸 SPENT: ${:.2} USDC", total_spent);
-    println!("═══════════════════════════════════════════════════════════════\n");
+    outln!("═══════════════════════════════════════════════════════════════");
+    outln!("📊 TOTAL PURCHASES AFTER REDEMPTION:");
+    outln!("   Number of trades: {}", trades_after_redemption.len());
+    outln!("   💸 SPENT: ${:.2} USDC", total_spent);
+    outln!("═══════════════════════════════════════════════════════════════\n");
 
-    println!("💡 EXPLANATION OF WHERE THE MONEY WENT:\n");
-    println!("   ✅ Received from redemption: +$66.37");
-    println!("   ❌ Spent on new purchases: -${:.2}", total_spent);
-    println!("   📊 Balance change: ${:.2}", 66.37 - total_spent);
-    println!("\n═══════════════════════════════════════════════════════════════\n");
+    outln!("💡 EXPLANATION OF WHERE THE MONEY WENT:\n");
+    outln!("   ✅ Received from redemption: +$66.37");
+    outln!("   ❌ Spent on new purchases: -${:.2}", total_spent);
+    outln!("   📊 Balance change: ${:.2}", 66.37 - total_spent);
+    outln!("\n═══════════════════════════════════════════════════════════════\n");
 
-    println!("💵 RECENT SALES:\n");
+    outln!("💵 RECENT SALES:\n");
     let recent_sells: Vec<&Activity> = activities
         .iter()
         .filter(|t| t.side == "SELL")
@@ -125,25 +128,23 @@ async fn main() -> Result<()> {
 
     let mut total_sold = 0.0;
     for (i, trade) in recent_sells.iter().enumerate() {
-        let date = chrono::DateTime::from_timestamp(trade.timestamp, 0)
-            .unwrap_or_default()
-            .format("%Y-%m-%d %H:%M:%S");
+        let date = clock::local_time_str(trade.timestamp, "%Y-%m-%d %H:%M:%S");
         let value = trade.usdc_size;
         total_sold += value;
 
-        println!("{}. 🔴 SOLD: {}",
+        outln!("{}. 🔴 SOLD: {}",
             i + 1,
             trade.title.as_deref()
                 .or_else(|| trade.market.as_deref())
                 .unwrap_or("Unknown")
         );
-        println!("   💰 Received: ${:.2}", value);
-        println!("   📅 Date: {}\n", date);
+        outln!("   💰 Received: ${:.2}", value);
+        outln!("   📅 Date: {}\n", date);
     }
 
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("💵 Sold in recent trades: ${:.2}", total_sold);
-    println!("═══════════════════════════════════════════════════════════════");
+    outln!("═══════════════════════════════════════════════════════════════");
+    outln!("💵 Sold in recent trades: ${:.2}", total_sold);
+    outln!("═══════════════════════════════════════════════════════════════");
 
     Ok(())
 }
@@ -1,3 +1,6 @@
+#[macro_use]
+extern crate polymarket_term;
+
 const POLYMARKET_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
 const POLYMARKET_COLLATERAL: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
 const NATIVE_USDC_ADDRESS: &str = "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359";
@@ -11,15 +14,16 @@ use polymarket_copy_rust::{
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
     let config = EnvConfig::from_env().await?;
 
-    println!();
-    println!(
+    outln!();
+    outln!(
         "{} Checking USDC balance and allowance...{}",
         colors::ACCENT,
         colors::RESET
     );
-    println!();
+    outln!();
 
     let configured_usdc = config.usdc_contract_address.trim().to_lowercase();
     let polymarket_collateral_lower = POLYMARKET_COLLATERAL.trim().to_lowercase();
@@ -40,39 +44,39 @@ async fn main() -> Result<()> {
     )
     .await?;
 
-    println!("  USDC Decimals: {}", local_decimals);
-    println!();
-    println!(
+    outln!("  USDC Decimals: {}", local_decimals);
+    outln!();
+    outln!(
         "  Your USDC Balance ({}): {} {:.6} USDC {}",
         config.usdc_contract_address,
         colors::SUCCESS,
         local_balance,
         colors::RESET
     );
-    println!(
+    outln!(
         "  Current Allowance ({}): {} {:.6} USDC {}",
         config.usdc_contract_address,
         colors::SUCCESS,
         local_allowance,
         colors::RESET
     );
-    println!("  Polymarket Exchange: {}", POLYMARKET_EXCHANGE);
-    println!();
+    outln!("  Polymarket Exchange: {}", POLYMARKET_EXCHANGE);
+    outln!();
 
     if configured_usdc != native_usdc_lower {
         match get_erc20_balance(&config.rpc_url, NATIVE_USDC_ADDRESS, &config.proxy_wallet).await {
             Ok((native_balance, _)) if native_balance > 0.0 => {
-                println!(
+                outln!(
                     "{} Detected native USDC (Polygon PoS) balance:{}",
                     colors::ACCENT,
                     colors::RESET
                 );
-                println!(
+                outln!(
                     "    {:.6} tokens at {}",
                     native_balance, NATIVE_USDC_ADDRESS
                 );
-                println!("    Polymarket does not recognize this token. Swap to USDC.e (0x2791...) to trade.");
-                println!();
+                outln!("    Polymarket does not recognize this token. Swap to USDC.e (0x2791...) to trade.");
+                outln!();
             }
             _ => {}
         }
@@ -96,46 +100,46 @@ async fn main() -> Result<()> {
         };
 
     if !uses_polymarket_collateral {
-        println!(
+        outln!(
             "{} Polymarket collateral token is USDC.e (bridged) at address{}",
             colors::WARN,
             colors::RESET
         );
-        println!("    {}", POLYMARKET_COLLATERAL);
-        println!(
+        outln!("    {}", POLYMARKET_COLLATERAL);
+        outln!(
             "{} Polymarket-tracked USDC balance: {:.6} USDC{}",
             colors::WARN,
             polymarket_balance,
             colors::RESET
         );
-        println!(
+        outln!(
             "{} Polymarket-tracked allowance: {:.6} USDC{}",
             colors::WARN,
             polymarket_allowance,
             colors::RESET
         );
-        println!();
-        println!("{} Swap native USDC to USDC.e or update your .env to point at the collateral token before trading.{}", colors::WARN, colors::RESET);
-        println!();
+        outln!();
+        outln!("{} Swap native USDC to USDC.e or update your .env to point at the collateral token before trading.{}", colors::WARN, colors::RESET);
+        outln!();
     }
 
     if polymarket_allowance.is_infinite()
         || (polymarket_allowance >= polymarket_balance && polymarket_allowance > 0.0)
     {
-        println!(
+        outln!(
             "{} Allowance is already sufficient! No action needed.{}",
             colors::SUCCESS,
             colors::RESET
         );
-        println!();
+        outln!();
     } else {
-        println!(
+        outln!(
             "{} Allowance is insufficient or zero!{}",
             colors::WARN,
             colors::RESET
         );
-        println!("  Use the Makefile command to set allowance: make set-token-allowance");
-        println!();
+        outln!("  Use the Makefile command to set allowance: make set-token-allowance");
+        outln!();
     }
 
     Ok(())
@@ -1,5 +1,11 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use anyhow::Result;
-use polymarket_copy_rust::{fetch_data, EnvConfig, Logger};
+use polymarket_copy_rust::utils::{
+    fetch_wallet_positions, http_client, in_order_window, is_open_position, ActivityKind, HttpPool, PositionTotals,
+};
+use polymarket_copy_rust::{fetch_paginated, BotOrder, Db, EnvConfig, Logger, UserPosition};
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -25,169 +31,170 @@ struct Activity {
     outcome: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct Position {
-    asset: String,
-    #[serde(rename = "conditionId")]
-    condition_id: String,
-    size: f64,
-    #[serde(rename = "avgPrice")]
-    avg_price: f64,
-    #[serde(rename = "initialValue")]
-    initial_value: f64,
-    #[serde(rename = "currentValue")]
-    current_value: f64,
-    #[serde(rename = "cashPnl")]
-    cash_pnl: f64,
-    #[serde(rename = "percentPnl")]
-    percent_pnl: f64,
-    #[serde(rename = "totalBought")]
-    total_bought: f64,
-    #[serde(rename = "realizedPnl")]
-    realized_pnl: f64,
-    #[serde(rename = "percentRealizedPnl")]
-    percent_realized_pnl: f64,
-    #[serde(rename = "curPrice")]
-    cur_price: f64,
-    redeemable: Option<bool>,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
 #[derive(Default)]
 struct TradeGroup {
     buys: Vec<Activity>,
     sells: Vec<Activity>,
 }
 
+// Buys/sells of one asset by one origin (bot or manual)
+#[derive(Default, Clone, Copy)]
+struct OriginBook {
+    bought_tokens: f64,
+    bought_usdc: f64,
+    sold_tokens: f64,
+    sold_usdc: f64,
+}
+
+#[derive(Default)]
+struct OriginTotals {
+    trades: usize,
+    volume: f64,
+    realized: f64,
+    unrealized: f64,
+}
+
+impl OriginBook {
+    fn add(&mut self, trade: &Activity) {
+        match ActivityKind::parse(&trade.activity_type, &trade.side) {
+            ActivityKind::Buy => {
+                self.bought_tokens += trade.size;
+                self.bought_usdc += trade.usdc_size;
+            }
+            ActivityKind::Sell => {
+                self.sold_tokens += trade.size;
+                self.sold_usdc += trade.usdc_size;
+            }
+            _ => {}
+        }
+    }
+
+    fn avg_cost(&self) -> f64 {
+        if self.bought_tokens > 0.0 {
+            self.bought_usdc / self.bought_tokens
+        } else {
+            0.0
+        }
+    }
+
+    // Average-cost realized PnL, and mark-to-market of what's still held (cur_price None = position gone)
+    fn pnl(&self, cur_price: Option<f64>) -> (f64, f64) {
+        let realized = self.sold_usdc - self.sold_tokens.min(self.bought_tokens) * self.avg_cost();
+        let held = (self.bought_tokens - self.sold_tokens).max(0.0);
+        let unrealized = cur_price.map(|p| held * (p - self.avg_cost())).unwrap_or(0.0);
+        (realized, unrealized)
+    }
+}
+
+// Any run in the same market covers the fill (a run can trade both outcomes) - classification, not claiming
+fn is_bot_fill(trade: &Activity, orders: &[BotOrder]) -> bool {
+    orders
+        .iter()
+        .any(|o| o.condition_id == trade.condition_id && in_order_window(o, trade.timestamp))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
 
     let config = EnvConfig::from_env().await?;
     let proxy_wallet = &config.proxy_wallet;
 
-    println!();
-    println!("🔍 Detailed P&L discrepancy check\n");
-    println!("Wallet: {}\n", proxy_wallet);
+    outln!();
+    outln!("🔍 Detailed P&L discrepancy check\n");
+    outln!("Wallet: {}\n", proxy_wallet);
     Logger::separator();
-    println!();
+    outln!();
 
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Api);
 
-    println!("📊 Fetching data from Polymarket API...\n");
+    outln!("📊 Fetching data from Polymarket API...\n");
 
-    let positions_url = format!(
-        "https://data-api.polymarket.com/positions?user={}",
-        proxy_wallet
-    );
-    let positions: Vec<Position> = fetch_data(
-        &client,
-        &positions_url,
-        config.request_timeout_ms,
-        config.network_retry_limit,
-    )
-    .await?
-    .as_array()
-    .ok_or_else(|| anyhow::anyhow!("Expected array of positions"))?
-    .iter()
-    .filter_map(|v| serde_json::from_value(v.clone()).ok())
-    .collect();
+    let positions = fetch_wallet_positions(&config, &client, proxy_wallet).await?;
+    let totals = PositionTotals::from_positions(&positions);
 
-    println!("Fetched positions: {}\n", positions.len());
+    outln!("Fetched positions: {}\n", positions.len());
 
-    let open_positions: Vec<Position> = positions.iter().filter(|p| p.size > 0.0).cloned().collect();
-    let closed_positions: Vec<Position> = positions.iter().filter(|p| p.size == 0.0).cloned().collect();
+    let (open_positions, closed_positions): (Vec<UserPosition>, Vec<UserPosition>) =
+        positions.into_iter().partition(is_open_position);
 
-    println!("• Open: {}", open_positions.len());
-    println!("• Closed: {}\n", closed_positions.len());
+    outln!("• Open: {}", open_positions.len());
+    outln!("• Closed: {}\n", closed_positions.len());
 
     Logger::separator();
-    println!();
-
-    println!("📈 OPEN POSITIONS:\n");
-    let mut total_open_value = 0.0;
-    let mut total_open_initial = 0.0;
-    let mut total_unrealized_pnl = 0.0;
-    let mut total_open_realized = 0.0;
+    outln!();
 
+    outln!("📈 OPEN POSITIONS:\n");
     for (idx, pos) in open_positions.iter().enumerate() {
-        total_open_value += pos.current_value;
-        total_open_initial += pos.initial_value;
-        total_unrealized_pnl += pos.cash_pnl;
-        total_open_realized += pos.realized_pnl;
-
-        println!("{}. {} - {}", 
+        outln!("{}. {} - {}", 
             idx + 1,
             pos.title.as_deref().unwrap_or("Unknown"),
             pos.outcome.as_deref().unwrap_or("N/A")
         );
-        println!("   Size: {:.2} @ ${:.3}", pos.size, pos.avg_price);
-        println!("   Current Value: ${:.2}", pos.current_value);
-        println!("   Initial Value: ${:.2}", pos.initial_value);
-        println!("   Unrealized P&L: ${:.2} ({:.2}%)", pos.cash_pnl, pos.percent_pnl);
-        println!("   Realized P&L: ${:.2}", pos.realized_pnl);
-        println!();
+        outln!("   Size: {:.2} @ ${:.3}", pos.size.unwrap_or(0.0), pos.avg_price.unwrap_or(0.0));
+        outln!("   Current Value: ${:.2}", pos.current_value.unwrap_or(0.0));
+        outln!("   Initial Value: ${:.2}", pos.initial_value.unwrap_or(0.0));
+        outln!(
+            "   Unrealized P&L: ${:.2} ({:.2}%)",
+            pos.cash_pnl.unwrap_or(0.0),
+            pos.percent_pnl.unwrap_or(0.0)
+        );
+        outln!("   Realized P&L: ${:.2}", pos.realized_pnl.unwrap_or(0.0));
+        outln!();
     }
 
-    println!("   TOTAL for open:");
-    println!("   • Current value: ${:.2}", total_open_value);
-    println!("   • Initial value: ${:.2}", total_open_initial);
-    println!("   • Unrealized P&L: ${:.2}", total_unrealized_pnl);
-    println!("   • Realized P&L: ${:.2}\n", total_open_realized);
+    outln!("   TOTAL for open:");
+    outln!("   • Current value: ${:.2}", totals.open_value);
+    outln!("   • Initial value: ${:.2}", totals.open_initial);
+    outln!("   • Unrealized P&L: ${:.2}", totals.unrealized_pnl);
+    outln!("   • Realized P&L: ${:.2}\n", totals.open_realized_pnl);
 
     Logger::separator();
-    println!();
-
-    println!("✅ CLOSED POSITIONS:\n");
-    let mut total_closed_realized = 0.0;
-    let mut total_closed_initial = 0.0;
+    outln!();
 
+    outln!("✅ CLOSED POSITIONS:\n");
     if !closed_positions.is_empty() {
         for (idx, pos) in closed_positions.iter().enumerate() {
-            total_closed_realized += pos.realized_pnl;
-            total_closed_initial += pos.initial_value;
-
-            println!("{}. {} - {}",
+            outln!("{}. {} - {}",
                 idx + 1,
                 pos.title.as_deref().unwrap_or("Unknown"),
                 pos.outcome.as_deref().unwrap_or("N/A")
             );
-            println!("   Initial Value: ${:.2}", pos.initial_value);
-            println!("   Realized P&L: ${:.2}", pos.realized_pnl);
-            println!("   % P&L: {:.2}%", pos.percent_realized_pnl);
-            println!();
+            outln!("   Initial Value: ${:.2}", pos.initial_value.unwrap_or(0.0));
+            outln!("   Realized P&L: ${:.2}", pos.realized_pnl.unwrap_or(0.0));
+            outln!("   % P&L: {:.2}%", pos.percent_realized_pnl.unwrap_or(0.0));
+            outln!();
         }
 
-        println!("   TOTAL for closed:");
-        println!("   • Initial investments: ${:.2}", total_closed_initial);
-        println!("   • Realized P&L: ${:.2}\n", total_closed_realized);
+        outln!("   TOTAL for closed:");
+        outln!("   • Initial investments: ${:.2}", totals.closed_initial);
+        outln!("   • Realized P&L: ${:.2}\n", totals.closed_realized_pnl);
     } else {
-        println!("   ❌ No closed positions found in API\n");
+        outln!("   ❌ No closed positions found in API\n");
     }
 
     Logger::separator();
-    println!();
+    outln!();
 
-    println!("📊 OVERALL STATISTICS:\n");
-    let total_realized = total_open_realized + total_closed_realized;
+    outln!("📊 OVERALL STATISTICS:\n");
+    let total_realized = totals.realized_pnl();
 
-    println!("   • Open positions - Realized P&L: ${:.2}", total_open_realized);
-    println!("   • Closed positions - Realized P&L: ${:.2}", total_closed_realized);
-    println!("   • Unrealized P&L: ${:.2}", total_unrealized_pnl);
-    println!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("   💰 TOTAL REALIZED PROFIT: ${:.2}\n", total_realized);
+    outln!("   • Open positions - Realized P&L: ${:.2}", totals.open_realized_pnl);
+    outln!("   • Closed positions - Realized P&L: ${:.2}", totals.closed_realized_pnl);
+    outln!("   • Unrealized P&L: ${:.2}", totals.unrealized_pnl);
+    outln!("   ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    outln!("   💰 TOTAL REALIZED PROFIT: ${:.2}\n", total_realized);
 
     Logger::separator();
-    println!();
+    outln!();
 
-    println!("🔎 CHECK THROUGH TRADE HISTORY:\n");
+    outln!("🔎 CHECK THROUGH TRADE HISTORY:\n");
     let activity_url = format!(
         "https://data-api.polymarket.com/activity?user={}&type=TRADE",
         proxy_wallet
     );
-    let activities: Vec<Activity> = fetch_data(
+    let activities: Vec<Activity> = fetch_paginated(
         &client,
         &activity_url,
         config.request_timeout_ms,
@@ -205,14 +212,14 @@ async fn main() -> Result<()> {
     for trade in &activities {
         let key = format!("{}:{}", trade.condition_id, trade.asset);
         let group = market_trades.entry(key).or_insert_with(TradeGroup::default);
-        if trade.side == "BUY" {
-            group.buys.push((*trade).clone());
-        } else {
-            group.sells.push((*trade).clone());
+        match ActivityKind::parse(&trade.activity_type, &trade.side) {
+            ActivityKind::Buy => group.buys.push((*trade).clone()),
+            ActivityKind::Sell => group.sells.push((*trade).clone()),
+            _ => {}
         }
     }
 
-    println!("   Found markets with activity: {}\n", market_trades.len());
+    outln!("   Found markets with activity: {}\n", market_trades.len());
 
     let mut calculated_realized_pnl = 0.0;
     let mut markets_with_profit = 0;
@@ -224,11 +231,11 @@ async fn main() -> Result<()> {
 
         if pnl.abs() > 0.01 {
             let market = trades.buys.first().or_else(|| trades.sells.first()).unwrap();
-            println!("   {}", market.title.as_deref().unwrap_or("Unknown"));
-            println!("   • Bought: ${:.2}", total_bought);
-            println!("   • Sold: ${:.2}", total_sold);
-            println!("   • P&L: ${:.2}", pnl);
-            println!();
+            outln!("   {}", market.title.as_deref().unwrap_or("Unknown"));
+            outln!("   • Bought: ${:.2}", total_bought);
+            outln!("   • Sold: ${:.2}", total_sold);
+            outln!("   • P&L: ${:.2}", pnl);
+            outln!();
 
             if total_sold > 0.0 {
                 calculated_realized_pnl += pnl;
@@ -237,40 +244,83 @@ async fn main() -> Result<()> {
         }
     }
 
-    println!("   💰 Calculated realized profit: ${:.2}", calculated_realized_pnl);
-    println!("   📊 Markets with closed profit: {}\n", markets_with_profit);
+    outln!("   💰 Calculated realized profit: ${:.2}", calculated_realized_pnl);
+    outln!("   📊 Markets with closed profit: {}\n", markets_with_profit);
+
+    Logger::separator();
+    outln!();
+
+    outln!("🤖 BOT vs MANUAL ATTRIBUTION:\n");
+    let bot_orders = match Db::open(&config).await {
+        Ok(db) => db.get_bot_orders(None).await.unwrap_or_default(),
+        Err(e) => {
+            outln!("   ⚠️  Could not load the bot order ledger ({}) - every fill counts as manual\n", e);
+            Vec::new()
+        }
+    };
+    let cur_prices: HashMap<&str, f64> = open_positions
+        .iter()
+        .filter_map(|p| Some((p.asset.as_deref()?, p.cur_price?)))
+        .collect();
+
+    let mut books: HashMap<(bool, String), OriginBook> = HashMap::new();
+    let mut totals = [OriginTotals::default(), OriginTotals::default()]; // [bot, manual]
+    for trade in &activities {
+        let bot = is_bot_fill(trade, &bot_orders);
+        books.entry((bot, trade.asset.clone())).or_default().add(trade);
+        let t = &mut totals[if bot { 0 } else { 1 }];
+        t.trades += 1;
+        t.volume += trade.usdc_size;
+    }
+    for ((bot, asset), book) in &books {
+        let (realized, unrealized) = book.pnl(cur_prices.get(asset.as_str()).copied());
+        let t = &mut totals[if *bot { 0 } else { 1 }];
+        t.realized += realized;
+        t.unrealized += unrealized;
+    }
+
+    outln!("   Bot order runs on record: {}\n", bot_orders.len());
+    for (label, t) in [("Copied (bot)", &totals[0]), ("Manual", &totals[1])] {
+        outln!("   {}", label);
+        outln!("   • Trades: {} (${:.2} volume)", t.trades, t.volume);
+        outln!("   • Realized P&L: ${:.2}", t.realized);
+        outln!("   • Unrealized P&L: ${:.2}", t.unrealized);
+        outln!("   • Total: ${:.2}", t.realized + t.unrealized);
+        outln!();
+    }
+    outln!("   (Average-cost basis per origin; resolved positions redeemed outside trades are not counted)\n");
 
     Logger::separator();
-    println!();
+    outln!();
 
-    println!("💡 CONCLUSIONS:\n");
-    println!("   1. API returns realized profit: ${:.2}", total_realized);
-    println!("   2. Calculated from trade history: ${:.2}", calculated_realized_pnl);
-    println!("   3. Polymarket UI shows: ~$12.02\n");
+    outln!("💡 CONCLUSIONS:\n");
+    outln!("   1. API returns realized profit: ${:.2}", total_realized);
+    outln!("   2. Calculated from trade history: ${:.2}", calculated_realized_pnl);
+    outln!("   3. Polymarket UI shows: ~$12.02\n");
 
     if (total_realized - calculated_realized_pnl).abs() > 1.0 {
-        println!("   ⚠️  DISCREPANCY DETECTED!\n");
-        println!("   Possible reasons:");
-        println!("   • API only counts partially closed positions");
-        println!("   • UI includes unrealized partial sales");
-        println!("   • Data synchronization delay between UI and API");
-        println!("   • Different P&L calculation methodology\n");
+        outln!("   ⚠️  DISCREPANCY DETECTED!\n");
+        outln!("   Possible reasons:");
+        outln!("   • API only counts partially closed positions");
+        outln!("   • UI includes unrealized partial sales");
+        outln!("   • Data synchronization delay between UI and API");
+        outln!("   • Different P&L calculation methodology\n");
     }
 
-    println!("   📈 Why chart shows $0.00:");
-    println!("   • Amount too small ($2-12) for visualization");
-    println!("   • Timeline doesn't start from $0");
-    println!("   • Chart requires at least several data points");
-    println!("   • UI update delay (can be 1-24 hours)\n");
+    outln!("   📈 Why chart shows $0.00:");
+    outln!("   • Amount too small ($2-12) for visualization");
+    outln!("   • Timeline doesn't start from $0");
+    outln!("   • Chart requires at least several data points");
+    outln!("   • UI update delay (can be 1-24 hours)\n");
 
-    println!("   🔧 Recommendations:");
-    println!("   1. Wait 24 hours for full update");
-    println!("   2. Close more positions to increase realized profit");
-    println!("   3. Try clearing browser cache");
-    println!("   4. Check in incognito mode\n");
+    outln!("   🔧 Recommendations:");
+    outln!("   1. Wait 24 hours for full update");
+    outln!("   2. Close more positions to increase realized profit");
+    outln!("   3. Try clearing browser cache");
+    outln!("   4. Check in incognito mode\n");
 
     Logger::separator();
-    println!();
+    outln!();
 
     Ok(())
 }
@@ -1,5 +1,11 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use anyhow::Result;
-use polymarket_copy_rust::{fetch_data, get_usdc_balance, EnvConfig, Logger};
+use polymarket_copy_rust::{fetch_paginated, get_usdc_balance, EnvConfig, Logger, UserPosition};
+use polymarket_copy_rust::utils::{
+    clock, fetch_wallet_positions, http_client, is_open_position, ActivityKind, HttpPool, PositionTotals,
+};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -24,139 +30,96 @@ struct Activity {
     outcome: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct Position {
-    asset: String,
-    #[serde(rename = "conditionId")]
-    condition_id: String,
-    size: f64,
-    #[serde(rename = "avgPrice")]
-    avg_price: f64,
-    #[serde(rename = "initialValue")]
-    initial_value: f64,
-    #[serde(rename = "currentValue")]
-    current_value: f64,
-    #[serde(rename = "cashPnl")]
-    cash_pnl: f64,
-    #[serde(rename = "percentPnl")]
-    percent_pnl: f64,
-    #[serde(rename = "totalBought")]
-    total_bought: f64,
-    #[serde(rename = "realizedPnl")]
-    realized_pnl: f64,
-    #[serde(rename = "percentRealizedPnl")]
-    percent_realized_pnl: f64,
-    #[serde(rename = "curPrice")]
-    cur_price: f64,
-    title: Option<String>,
-    slug: Option<String>,
-    outcome: Option<String>,
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
 
     let config = EnvConfig::from_env().await?;
     let proxy_wallet = &config.proxy_wallet;
 
-    println!();
-    println!("🔍 Checking your wallet statistics on Polymarket\n");
-    println!("Wallet: {}\n", proxy_wallet);
+    outln!();
+    outln!("🔍 Checking your wallet statistics on Polymarket\n");
+    outln!("Wallet: {}\n", proxy_wallet);
     Logger::separator();
-    println!();
+    outln!();
 
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Api);
 
-    println!("💰 USDC BALANCE");
+    outln!("💰 USDC BALANCE");
     let balance = get_usdc_balance(
         &config.rpc_url,
         &config.usdc_contract_address,
         proxy_wallet,
     )
     .await?;
-    println!("   Available: ${:.2}\n", balance);
+    outln!("   Available: ${:.2}\n", balance);
 
-    println!("📊 OPEN POSITIONS");
-    let positions_url = format!(
-        "https://data-api.polymarket.com/positions?user={}",
-        proxy_wallet
-    );
-    let positions: Vec<Position> = fetch_data(
-        &client,
-        &positions_url,
-        config.request_timeout_ms,
-        config.network_retry_limit,
-    )
-    .await?
-    .as_array()
-    .ok_or_else(|| anyhow::anyhow!("Expected array of positions"))?
-    .iter()
-    .filter_map(|v| serde_json::from_value(v.clone()).ok())
-    .collect();
+    outln!("📊 OPEN POSITIONS");
+    let all_positions = fetch_wallet_positions(&config, &client, proxy_wallet).await?;
+    let totals = PositionTotals::from_positions(&all_positions);
+    let positions: Vec<UserPosition> = all_positions.into_iter().filter(is_open_position).collect();
 
     if !positions.is_empty() {
-        println!("   Total positions: {}\n", positions.len());
+        outln!("   Total positions: {}\n", positions.len());
 
-        let total_value: f64 = positions.iter().map(|p| p.current_value).sum();
-        let total_initial_value: f64 = positions.iter().map(|p| p.initial_value).sum();
-        let total_unrealized_pnl: f64 = positions.iter().map(|p| p.cash_pnl).sum();
-        let total_realized_pnl: f64 = positions.iter().map(|p| p.realized_pnl).sum();
+        let total_value = totals.open_value;
+        let total_initial_value = totals.open_initial;
+        let total_unrealized_pnl = totals.unrealized_pnl;
+        let total_realized_pnl = totals.realized_pnl();
 
-        println!("   💵 Current value: ${:.2}", total_value);
-        println!("   💵 Initial value: ${:.2}", total_initial_value);
+        outln!("   💵 Current value: ${:.2}", total_value);
+        outln!("   💵 Initial value: ${:.2}", total_initial_value);
         if total_initial_value > 0.0 {
-            println!(
+            outln!(
                 "   📈 Unrealized P&L: ${:.2} ({:.2}%)",
                 total_unrealized_pnl,
                 (total_unrealized_pnl / total_initial_value) * 100.0
             );
         } else {
-            println!("   📈 Unrealized P&L: ${:.2}", total_unrealized_pnl);
+            outln!("   📈 Unrealized P&L: ${:.2}", total_unrealized_pnl);
         }
-        println!("   ✅ Realized P&L: ${:.2}\n", total_realized_pnl);
+        outln!("   ✅ Realized P&L (open + closed positions): ${:.2}\n", total_realized_pnl);
 
-        println!("   🏆 Top-5 positions by profit:\n");
+        outln!("   🏆 Top-5 positions by profit:\n");
         let mut top_positions = positions.clone();
-        top_positions.sort_by(|a, b| {
-            b.percent_pnl
-                .partial_cmp(&a.percent_pnl)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        top_positions.sort_by(|a, b| b.percent_pnl.unwrap_or(0.0).total_cmp(&a.percent_pnl.unwrap_or(0.0)));
         top_positions.truncate(5);
 
         for (idx, pos) in top_positions.iter().enumerate() {
-            let pnl_sign = if pos.percent_pnl >= 0.0 { "📈" } else { "📉" };
-            println!("   {}. {} {}", idx + 1, pnl_sign, pos.title.as_deref().unwrap_or("Unknown"));
+            let pnl_sign = if pos.percent_pnl.unwrap_or(0.0) >= 0.0 { "📈" } else { "📉" };
+            outln!("   {}. {} {}", idx + 1, pnl_sign, pos.title.as_deref().unwrap_or("Unknown"));
             if let Some(ref outcome) = pos.outcome {
-                println!("      {}", outcome);
+                outln!("      {}", outcome);
             }
-            println!(
+            outln!(
                 "      Size: {:.2} tokens @ ${:.3}",
-                pos.size, pos.avg_price
+                pos.size.unwrap_or(0.0),
+                pos.avg_price.unwrap_or(0.0)
             );
-            println!(
+            outln!(
                 "      P&L: ${:.2} ({:.2}%)",
-                pos.cash_pnl, pos.percent_pnl
+                pos.cash_pnl.unwrap_or(0.0),
+                pos.percent_pnl.unwrap_or(0.0)
             );
-            println!("      Current price: ${:.3}", pos.cur_price);
+            outln!("      Current price: ${:.3}", pos.cur_price.unwrap_or(0.0));
             if let Some(ref slug) = pos.slug {
-                println!("      📍 https://polymarket.com/event/{}", slug);
+                outln!("      📍 https://polymarket.com/event/{}", slug);
             }
-            println!();
+            outln!();
         }
     } else {
-        println!("   ❌ No open positions found\n");
+        outln!("   ❌ No open positions found\n");
     }
 
     Logger::separator();
-    println!();
-    println!("📜 TRADE HISTORY (last 20)\n");
+    outln!();
+    outln!("📜 TRADE HISTORY (last 20)\n");
     let activity_url = format!(
         "https://data-api.polymarket.com/activity?user={}&type=TRADE",
         proxy_wallet
     );
-    let activities: Vec<Activity> = fetch_data(
+    let activities: Vec<Activity> = fetch_paginated(
         &client,
         &activity_url,
         config.request_timeout_ms,
@@ -170,96 +133,95 @@ async fn main() -> Result<()> {
     .collect();
 
     if !activities.is_empty() {
-        println!("   Total trades in API: {}\n", activities.len());
+        outln!("   Total trades in API: {}\n", activities.len());
 
-        let buy_trades: Vec<&Activity> = activities.iter().filter(|a| a.side == "BUY").collect();
-        let sell_trades: Vec<&Activity> = activities.iter().filter(|a| a.side == "SELL").collect();
+        let kind = |a: &Activity| ActivityKind::parse(&a.activity_type, &a.side);
+        let buy_trades: Vec<&Activity> = activities.iter().filter(|a| kind(a) == ActivityKind::Buy).collect();
+        let sell_trades: Vec<&Activity> = activities.iter().filter(|a| kind(a) == ActivityKind::Sell).collect();
         let total_buy_volume: f64 = buy_trades.iter().map(|t| t.usdc_size).sum();
         let total_sell_volume: f64 = sell_trades.iter().map(|t| t.usdc_size).sum();
 
-        println!("   📊 Trade statistics:");
-        println!(
+        outln!("   📊 Trade statistics:");
+        outln!(
             "      • Buys: {} (volume: ${:.2})",
             buy_trades.len(),
             total_buy_volume
         );
-        println!(
+        outln!(
             "      • Sells: {} (volume: ${:.2})",
             sell_trades.len(),
             total_sell_volume
         );
-        println!(
+        outln!(
             "      • Total volume: ${:.2}\n",
             total_buy_volume + total_sell_volume
         );
 
         let recent_trades: Vec<&Activity> = activities.iter().take(20).collect();
-        println!("   📝 Last 20 trades:\n");
+        outln!("   📝 Last 20 trades:\n");
 
         for (idx, trade) in recent_trades.iter().enumerate() {
-            let date = chrono::DateTime::from_timestamp(trade.timestamp, 0)
-                .unwrap_or_default()
-                .format("%Y-%m-%d %H:%M:%S");
+            let date = clock::local_time_str(trade.timestamp, "%Y-%m-%d %H:%M:%S");
             let side_icon = if trade.side == "BUY" { "🟢" } else { "🔴" };
-            println!(
+            outln!(
                 "   {}. {} {} - {}",
                 idx + 1,
                 side_icon,
                 trade.side,
                 date
             );
-            println!("      {}", trade.title.as_deref().unwrap_or("Unknown Market"));
+            outln!("      {}", trade.title.as_deref().unwrap_or("Unknown Market"));
             if let Some(ref outcome) = trade.outcome {
-                println!("      {}", outcome);
+                outln!("      {}", outcome);
             }
-            println!(
+            outln!(
                 "      Volume: ${:.2} @ ${:.3}",
                 trade.usdc_size, trade.price
             );
             let tx_hash = &trade.transaction_hash;
-            println!(
+            outln!(
                 "      TX: {}...{}",
                 &tx_hash[..tx_hash.len().min(10)],
                 &tx_hash[tx_hash.len().saturating_sub(8)..]
             );
-            println!("      🔗 https://polygonscan.com/tx/{}", tx_hash);
-            println!();
+            outln!("      🔗 https://polygonscan.com/tx/{}", tx_hash);
+            outln!();
         }
     } else {
-        println!("   ❌ Trade history not found\n");
+        outln!("   ❌ Trade history not found\n");
     }
 
     Logger::separator();
-    println!();
-    println!("❓ WHY NO P&L CHARTS ON POLYMARKET?\n");
-    println!("   Profit/Loss charts on Polymarket only show REALIZED");
-    println!("   profit (closed positions). This is why it shows $0.00:\n");
+    outln!();
+    outln!("❓ WHY NO P&L CHARTS ON POLYMARKET?\n");
+    outln!("   Profit/Loss charts on Polymarket only show REALIZED");
+    outln!("   profit (closed positions). This is why it shows $0.00:\n");
 
-    if !positions.is_empty() {
-        let total_realized_pnl: f64 = positions.iter().map(|p| p.realized_pnl).sum();
-        let total_unrealized_pnl: f64 = positions.iter().map(|p| p.cash_pnl).sum();
+    if totals.open + totals.closed > 0 {
+        let total_realized_pnl = totals.realized_pnl();
+        let total_unrealized_pnl = totals.unrealized_pnl;
 
-        println!("   ✅ Realized P&L (closed positions):");
-        println!("      → ${:.2} ← THIS is displayed on the chart\n", total_realized_pnl);
+        outln!("   ✅ Realized P&L (closed positions):");
+        outln!("      → ${:.2} ← THIS is displayed on the chart\n", total_realized_pnl);
 
-        println!("   📊 Unrealized P&L (open positions):");
-        println!(
+        outln!("   📊 Unrealized P&L (open positions):");
+        outln!(
             "      → ${:.2} ← THIS is NOT displayed on the chart\n",
             total_unrealized_pnl
         );
 
         if total_realized_pnl == 0.0 {
-            println!("   💡 Solution: To see charts, you need to:");
-            println!("      1. Close several positions with profit");
-            println!("      2. Wait 5-10 minutes for Polymarket API to update");
-            println!("      3. P&L chart will start displaying data\n");
+            outln!("   💡 Solution: To see charts, you need to:");
+            outln!("      1. Close several positions with profit");
+            outln!("      2. Wait 5-10 minutes for Polymarket API to update");
+            outln!("      3. P&L chart will start displaying data\n");
         }
     }
 
     Logger::separator();
-    println!();
-    println!("✅ Check completed!\n");
-    println!("📱 Your profile: https://polymarket.com/profile/{}\n", proxy_wallet);
+    outln!();
+    outln!("✅ Check completed!\n");
+    outln!("📱 Your profile: https://polymarket.com/profile/{}\n", proxy_wallet);
 
     Ok(())
 }
@@ -0,0 +1,150 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{clock, http_client, scan_wallet_inflows, HttpPool};
+use polymarket_copy_rust::{fetch_paginated, get_usdc_balance, Db, EnvConfig, Logger};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+const USAGE: &str = "Usage: daily_summary [--days N] [--csv file.csv] [--no-scan]";
+
+// Cash movements of one market day (ET)
+#[derive(Default, Clone, Copy)]
+struct Day {
+    bought: f64,
+    sold: f64,
+    redeemed: f64,
+    rewards: f64,
+    deposits: f64,
+}
+
+impl Day {
+    // Performance cash flow - deposits are capital, not profit
+    fn net(&self) -> f64 {
+        self.sold + self.redeemed + self.rewards - self.bought
+    }
+}
+
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1).cloned())
+}
+
+// Usage:
+//   daily_summary [--days 30]     per-day buys / sells / redemptions / rewards and the cumulative P&L curve
+//   daily_summary --csv eq.csv    also write the rows as CSV
+//   daily_summary --no-scan       use stored inflows only (skip the RPC log scan)
+// Rewards are USDC transfers into PROXY_WALLET found in the chain logs (see wallet_inflows), so the
+// curve matches the wallet's actual growth, not just trading
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let days: i64 = arg_value(&args, "--days").and_then(|v| v.parse().ok()).unwrap_or(30);
+    let csv_path = arg_value(&args, "--csv");
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let client = http_client(HttpPool::Api);
+    let since = clock::now_ms() / 1000 - days * 86_400;
+
+    if !args.iter().any(|a| a == "--no-scan") {
+        Logger::info("Scanning USDC transfers into the wallet...");
+        match scan_wallet_inflows(&config, &db, days.max(0) as u64).await {
+            Ok(scan) => Logger::info(&format!(
+                "Blocks {}-{}: {} inflow(s), {} reward(s) (${:.2})",
+                scan.from_block, scan.to_block, scan.inflows, scan.rewards, scan.reward_usd
+            )),
+            Err(e) => Logger::warning(&format!("Inflow scan failed ({}) - using stored inflows", e)),
+        }
+    }
+
+    let mut book: BTreeMap<String, Day> = BTreeMap::new();
+    let day_key = |ts: i64| clock::market_time(clock::from_ms(clock::to_ms(ts))).format("%Y-%m-%d").to_string();
+
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&start={}",
+        config.proxy_wallet, since
+    );
+    let activity = fetch_paginated(&client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+    for a in activity.as_array().cloned().unwrap_or_default() {
+        let ts = a.get("timestamp").and_then(|v| v.as_i64()).unwrap_or(0);
+        let usdc = a.get("usdcSize").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let day = book.entry(day_key(ts)).or_default();
+        match (
+            a.get("type").and_then(|v| v.as_str()).unwrap_or(""),
+            a.get("side").and_then(|v| v.as_str()).unwrap_or(""),
+        ) {
+            ("TRADE", "BUY") => day.bought += usdc,
+            ("TRADE", _) => day.sold += usdc,
+            ("REDEEM", _) => day.redeemed += usdc,
+            _ => {} // REWARD activity is counted from the chain inflows below (same money)
+        }
+    }
+    for inflow in db.get_wallet_inflows(Some(since)).await? {
+        let day = book.entry(day_key(inflow.timestamp)).or_default();
+        match inflow.kind.as_str() {
+            "reward" => day.rewards += inflow.amount,
+            "deposit" => day.deposits += inflow.amount,
+            _ => {} // Trades / redemptions are already in the activity totals
+        }
+    }
+
+    outln!("\n📅 Daily summary - last {} day(s), {}\n", days, config.proxy_wallet);
+    outln!(
+        "  {:<10} {:>10} {:>10} {:>10} {:>9} {:>10} {:>11} {:>10}",
+        "DATE", "BOUGHT", "SOLD", "REDEEMED", "REWARDS", "NET", "CUM P&L", "DEPOSITS"
+    );
+    let mut rows = Vec::new();
+    let mut cumulative = 0.0;
+    let mut totals = Day::default();
+    for (date, d) in &book {
+        cumulative += d.net();
+        totals.bought += d.bought;
+        totals.sold += d.sold;
+        totals.redeemed += d.redeemed;
+        totals.rewards += d.rewards;
+        totals.deposits += d.deposits;
+        outln!(
+            "  {:<10} {:>10.2} {:>10.2} {:>10.2} {:>9.2} {:>+10.2} {:>+11.2} {:>10.2}",
+            date, d.bought, d.sold, d.redeemed, d.rewards, d.net(), cumulative, d.deposits
+        );
+        rows.push((date.clone(), *d, cumulative));
+    }
+    outln!();
+    Logger::separator();
+
+    let trading = totals.net() - totals.rewards;
+    outln!("  Trading cash flow:  ${:+.2}", trading);
+    outln!("  Rewards / airdrops: ${:+.2}", totals.rewards);
+    outln!("  Total:              ${:+.2}", totals.net());
+    if totals.deposits > 0.0 {
+        outln!("  Deposits (excluded): ${:.2}", totals.deposits);
+    }
+    if let Ok(balance) = get_usdc_balance(&config.rpc_url, &config.usdc_contract_address, &config.proxy_wallet).await {
+        outln!("  USDC balance now:   ${:.2}", balance);
+    }
+    outln!("\n  (Cash basis: open positions count at cost until sold or redeemed)\n");
+
+    if let Some(path) = csv_path {
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "date,bought,sold,redeemed,rewards,net,cumulative_pnl,deposits")?;
+        for (date, d, cum) in &rows {
+            writeln!(
+                file,
+                "{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6}",
+                date, d.bought, d.sold, d.redeemed, d.rewards, d.net(), cum, d.deposits
+            )?;
+        }
+        Logger::success(&format!("Wrote {} row(s) to {}", rows.len(), path));
+    }
+    Ok(())
+}
@@ -0,0 +1,333 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{
+    attribute_fills, clock, http_client, theme::colors, HttpPool, ATTRIBUTION_SLACK_BEFORE_MS,
+};
+use polymarket_copy_rust::{fetch_data, fetch_paginated, BotOrder, Db, EnvConfig, Logger};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const USAGE: &str = "Usage: execution_quality [--days N] [--min-orders N] [--json]";
+
+#[derive(Debug, Deserialize, Clone)]
+struct Activity {
+    timestamp: i64,
+    #[serde(rename = "conditionId")]
+    condition_id: String,
+    size: f64,
+    #[serde(rename = "usdcSize")]
+    usdc_size: f64,
+    asset: String,
+    side: String,
+    title: Option<String>,
+}
+
+// One copied trade: my VWAP vs the trader's price and vs the mid when the copy started
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CopyFill {
+    trader: String,
+    title: String,
+    category: String,
+    side: String,
+    hour_et: u32,
+    trader_price: f64,
+    mid_price: Option<f64>,
+    vwap: f64,
+    usd: f64,
+    slippage_bps: f64,             // vs trader's price, + = worse than the trader
+    mid_slippage_bps: Option<f64>, // vs mid at detection, + = worse than mid
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupQuality {
+    name: String,
+    orders: usize,
+    usd: f64,
+    avg_slippage_bps: f64,
+    avg_mid_slippage_bps: Option<f64>,
+    edge_lost_usd: f64, // Sum of slippage x notional - where the bot gives up the most
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QualityReport {
+    wallet: String,
+    days: i64,
+    bot_orders: usize,
+    benchmarked: usize,
+    avg_slippage_bps: f64,
+    edge_lost_usd: f64,
+    by_trader: Vec<GroupQuality>,
+    by_category: Vec<GroupQuality>,
+    by_hour_et: Vec<GroupQuality>,
+    fills: Vec<CopyFill>,
+}
+
+// Buys pay up when the price is higher, sells when it is lower
+fn slippage_bps(side: &str, price: f64, reference: f64) -> f64 {
+    let diff = if side == "BUY" { price - reference } else { reference - price };
+    diff / reference * 10_000.0
+}
+
+async fn fetch_categories(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    condition_ids: &[String],
+) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for chunk in condition_ids.chunks(20) {
+        let query = chunk
+            .iter()
+            .map(|c| format!("condition_ids={}", c))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("https://gamma-api.polymarket.com/markets?{}", query);
+        let Ok(markets) =
+            fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await
+        else {
+            continue;
+        };
+        for m in markets.as_array().into_iter().flatten() {
+            let cid = m.get("conditionId").and_then(|v| v.as_str());
+            let category = m
+                .get("category")
+                .and_then(|v| v.as_str())
+                .filter(|c| !c.is_empty());
+            if let (Some(cid), Some(category)) = (cid, category) {
+                out.insert(cid.to_string(), category.to_string());
+            }
+        }
+    }
+    out
+}
+
+// My fills inside each order's window -> one CopyFill per order that has a trader price (each fill counted once)
+fn benchmark(orders: &[BotOrder], trades: &[Activity], categories: &HashMap<String, String>) -> Vec<CopyFill> {
+    let side_of = |order: &BotOrder| if order.side == "BUY" { "BUY" } else { "SELL" }; // MERGE sells the position
+    let graded = |order: &BotOrder| order.trader_price.is_some_and(|p| p > 0.0);
+    let claimed = attribute_fills(orders, trades, |t| t.timestamp, |o, t| {
+        graded(o) && t.asset == o.asset && t.side == side_of(o)
+    });
+    let mut out = Vec::new();
+    for (order, fills) in orders.iter().zip(claimed) {
+        let Some(trader_price) = order.trader_price.filter(|p| *p > 0.0) else {
+            continue;
+        };
+        let side = side_of(order);
+        let (mut tokens, mut usd, mut title) = (0.0, 0.0, None);
+        for t in fills.iter().map(|&i| &trades[i]) {
+            tokens += t.size;
+            usd += t.usdc_size;
+            title = title.or_else(|| t.title.clone());
+        }
+        if tokens <= 0.0 {
+            continue; // Nothing filled (skipped / rejected) - no execution to grade
+        }
+        let vwap = usd / tokens;
+        out.push(CopyFill {
+            trader: order.trader.clone(),
+            title: title.unwrap_or_else(|| "Unknown".to_string()),
+            category: categories
+                .get(&order.condition_id)
+                .cloned()
+                .unwrap_or_else(|| "Uncategorized".to_string()),
+            side: side.to_string(),
+            hour_et: clock::market_time_str(order.started_at, "%H").parse().unwrap_or(0),
+            trader_price,
+            mid_price: order.mid_price,
+            vwap,
+            usd,
+            slippage_bps: slippage_bps(side, vwap, trader_price),
+            mid_slippage_bps: order.mid_price.filter(|m| *m > 0.0).map(|m| slippage_bps(side, vwap, m)),
+        });
+    }
+    out
+}
+
+fn summarize(name: String, fills: &[&CopyFill]) -> GroupQuality {
+    let n = fills.len().max(1) as f64;
+    let mids: Vec<f64> = fills.iter().filter_map(|f| f.mid_slippage_bps).collect();
+    GroupQuality {
+        name,
+        orders: fills.len(),
+        usd: fills.iter().map(|f| f.usd).sum(),
+        avg_slippage_bps: fills.iter().map(|f| f.slippage_bps).sum::<f64>() / n,
+        avg_mid_slippage_bps: (!mids.is_empty()).then(|| mids.iter().sum::<f64>() / mids.len() as f64),
+        edge_lost_usd: fills.iter().map(|f| f.slippage_bps / 10_000.0 * f.usd).sum(),
+    }
+}
+
+// Worst groups first
+fn group_by(fills: &[CopyFill], min_orders: usize, key: impl Fn(&CopyFill) -> String) -> Vec<GroupQuality> {
+    let mut groups: HashMap<String, Vec<&CopyFill>> = HashMap::new();
+    for f in fills {
+        groups.entry(key(f)).or_default().push(f);
+    }
+    let mut out: Vec<GroupQuality> = groups
+        .into_iter()
+        .filter(|(_, g)| g.len() >= min_orders)
+        .map(|(name, g)| summarize(name, &g))
+        .collect();
+    out.sort_by(|a, b| b.edge_lost_usd.total_cmp(&a.edge_lost_usd));
+    out
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        format!("{}…", s.chars().take(max - 1).collect::<String>())
+    }
+}
+
+fn bps_color(bps: f64) -> &'static str {
+    if bps > 0.0 {
+        colors::ERROR
+    } else {
+        colors::SUCCESS
+    }
+}
+
+fn print_groups(title: &str, groups: &[GroupQuality]) {
+    Logger::header(title);
+    if groups.is_empty() {
+        outln!("  (no data)");
+        outln!();
+        return;
+    }
+    outln!(
+        "  {:<28} {:>6} {:>11} {:>12} {:>10} {:>11}",
+        "Name", "Orders", "Volume", "vs trader", "vs mid", "Edge lost"
+    );
+    for g in groups {
+        let mid = g.avg_mid_slippage_bps.map(|b| format!("{:+.0} bps", b)).unwrap_or_else(|| "-".to_string());
+        outln!(
+            "  {:<28} {:>6} {:>11} {}{:>12}{} {:>10} {:>11}",
+            truncate(&g.name, 28),
+            g.orders,
+            format!("${:.2}", g.usd),
+            bps_color(g.avg_slippage_bps),
+            format!("{:+.0} bps", g.avg_slippage_bps),
+            colors::RESET,
+            mid,
+            format!("${:.2}", g.edge_lost_usd)
+        );
+    }
+    outln!();
+}
+
+fn print_report(r: &QualityReport) {
+    outln!();
+    outln!(
+        "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+        colors::ACCENT
+    );
+    outln!("     POLYMARKET BOT — EXECUTION QUALITY");
+    outln!(
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
+        colors::RESET
+    );
+    outln!("  Window: last {} day(s)", r.days);
+    outln!("  Bot orders:   {} ({} with fills and a trader price)", r.bot_orders, r.benchmarked);
+    if r.benchmarked == 0 {
+        outln!();
+        outln!("  (nothing to benchmark - orders recorded before trader prices were stored are ignored)");
+        outln!();
+        return;
+    }
+    outln!(
+        "  Avg slippage: {}{:+.1} bps{} vs the trader's price",
+        bps_color(r.avg_slippage_bps),
+        r.avg_slippage_bps,
+        colors::RESET
+    );
+    outln!("  Edge lost:    ${:.2}", r.edge_lost_usd);
+    outln!();
+
+    print_groups("By trader", &r.by_trader);
+    print_groups("By category", &r.by_category);
+    print_groups("By hour (ET)", &r.by_hour_et);
+}
+
+// Usage:
+//   execution_quality [--days 30]      my VWAP vs trader price / mid for every copied trade, grouped by trader, category, hour
+//   execution_quality --min-orders 5   hide groups with fewer orders
+//   execution_quality --json           the full report (incl. per-order rows) as JSON
+// Slippage is in bps of the benchmark price, positive = the bot did worse; only orders recorded with a trader price count
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let value = |flag: &str| -> Option<String> {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+    let days: i64 = value("--days").and_then(|v| v.parse().ok()).unwrap_or(30);
+    let min_orders: usize = value("--min-orders").and_then(|v| v.parse().ok()).unwrap_or(1);
+    let json = args.iter().any(|a| a == "--json");
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let http_client = http_client(HttpPool::Api);
+
+    let since_ms = clock::now_ms() - days * 86_400_000;
+    let orders = db.get_bot_orders(Some(since_ms)).await?;
+
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&type=TRADE&start={}",
+        config.proxy_wallet,
+        since_ms / 1000 - ATTRIBUTION_SLACK_BEFORE_MS / 1000
+    );
+    let trades: Vec<Activity> = fetch_paginated(
+        &http_client,
+        &url,
+        config.request_timeout_ms,
+        config.network_retry_limit,
+    )
+    .await?
+    .as_array()
+    .map(|arr| arr.iter().filter_map(|v| serde_json::from_value(v.clone()).ok()).collect())
+    .unwrap_or_default();
+
+    let mut condition_ids: Vec<String> = orders
+        .iter()
+        .filter(|o| o.trader_price.is_some())
+        .map(|o| o.condition_id.clone())
+        .collect();
+    condition_ids.sort();
+    condition_ids.dedup();
+    let categories = fetch_categories(&config, &http_client, &condition_ids).await;
+
+    let fills = benchmark(&orders, &trades, &categories);
+    let overall = summarize(String::new(), &fills.iter().collect::<Vec<_>>());
+    let report = QualityReport {
+        wallet: config.proxy_wallet.clone(),
+        days,
+        bot_orders: orders.len(),
+        benchmarked: fills.len(),
+        avg_slippage_bps: overall.avg_slippage_bps,
+        edge_lost_usd: overall.edge_lost_usd,
+        by_trader: group_by(&fills, min_orders, |f| Logger::format_address(&f.trader)),
+        by_category: group_by(&fills, min_orders, |f| f.category.clone()),
+        by_hour_et: group_by(&fills, min_orders, |f| format!("{:02}:00", f.hour_et)),
+        fills,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
@@ -0,0 +1,263 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{clock, http_client, HttpPool, Progress};
+use polymarket_copy_rust::{fetch_data, BackfillCursor, Db, EnvConfig, Logger};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::time::Duration;
+
+const USAGE: &str =
+    "Usage: fetch_history [--days N] [--traders 0xa,0xb] [--delay-ms N] [--max-chunks N] [--status] [--json]";
+const PAGE_LIMIT: usize = 500;
+
+struct Options {
+    days: i64,
+    traders: Option<Vec<String>>,
+    delay_ms: u64,
+    max_chunks: Option<u64>,
+    status_only: bool,
+}
+
+fn parse_options() -> Options {
+    let args: Vec<String> = polymarket_term::args().collect();
+    let value = |flag: &str| -> Option<String> {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+    Options {
+        days: value("--days").and_then(|v| v.parse().ok()).unwrap_or(90),
+        traders: value("--traders").map(|v| {
+            v.split(',')
+                .map(|a| a.trim().to_lowercase())
+                .filter(|a| !a.is_empty())
+                .collect()
+        }),
+        delay_ms: value("--delay-ms").and_then(|v| v.parse().ok()).unwrap_or(250),
+        max_chunks: value("--max-chunks").and_then(|v| v.parse().ok()),
+        status_only: args.iter().any(|a| a == "--status"),
+    }
+}
+
+// Usage:
+//   fetch_history [--days 90]         backfill every USER_ADDRESSES trader, newest first, resuming where the last run stopped
+//   fetch_history --traders 0xa,0xb   only these traders
+//   fetch_history --max-chunks 20     stop after 20 pages (the next run continues from the saved cursors)
+//   fetch_history --status            print progress without fetching
+//   fetch_history --json              per-page results and the summary as JSON (no tables)
+// Trades land in history_<trader>, one cursor per trader in backfill_cursors (saved after every page, so Ctrl-C is safe)
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    if std::env::args().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let options = parse_options();
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let client = http_client(HttpPool::Api);
+
+    let traders = options
+        .traders
+        .clone()
+        .unwrap_or_else(|| config.user_addresses.iter().map(|a| a.to_lowercase()).collect());
+    let now = clock::now_ms() / 1000;
+    let target_start_ts = now - options.days * 86_400;
+
+    let mut cursors = Vec::new();
+    for trader in &traders {
+        let cursor = match db.get_backfill_cursor(trader).await? {
+            // A longer --days reopens a finished backfill from where it stopped
+            Some(mut c) => {
+                if target_start_ts < c.target_start_ts {
+                    c.target_start_ts = target_start_ts;
+                    c.done = false;
+                }
+                c
+            }
+            None => BackfillCursor {
+                trader: trader.clone(),
+                target_start_ts,
+                cursor_ts: now,
+                cursor_offset: 0,
+                newest_ts: now,
+                trades: 0,
+                chunks: 0,
+                done: false,
+                updated_at: 0,
+            },
+        };
+        cursors.push(cursor);
+    }
+
+    if options.status_only {
+        print_progress(&cursors, now);
+        return Ok(());
+    }
+    let mut progress = Progress::new("Backfill", None);
+
+    // Trades since the previous run first (resumed cursors only), so the covered range has no gap at the top
+    for cursor in cursors.iter_mut().filter(|c| c.updated_at > 0 && c.newest_ts < now) {
+        let (mut end, mut offset) = (now, 0);
+        loop {
+            let page = fetch_page(&client, &config, &cursor.trader, cursor.newest_ts, end, offset).await?;
+            cursor.trades += db.upsert_history_trades(&cursor.trader, &page).await?;
+            cursor.chunks += 1;
+            match next_page(&page, end, offset) {
+                Some(next) => (end, offset) = next,
+                None => break,
+            }
+            tokio::time::sleep(Duration::from_millis(options.delay_ms)).await;
+        }
+        cursor.newest_ts = now;
+        cursor.updated_at = clock::now_ms();
+        db.save_backfill_cursor(cursor).await?;
+    }
+
+    // Then one page per trader per round, so a trader with years of history doesn't starve the rest
+    // (a trader whose page fails is left for the next run; its cursor still points at the failed page)
+    let mut chunks_run = 0u64;
+    let mut failed: HashSet<String> = HashSet::new();
+    'rounds: while cursors.iter().any(|c| !c.done && !failed.contains(&c.trader)) {
+        for cursor in cursors.iter_mut().filter(|c| !c.done && !failed.contains(&c.trader)) {
+            if options.max_chunks.map(|m| chunks_run >= m).unwrap_or(false) {
+                if !progress.is_json() {
+                    Logger::info("Chunk limit reached - run again to continue from the saved cursors");
+                }
+                break 'rounds;
+            }
+            progress.tick(&short(&cursor.trader));
+            let page = match fetch_page(
+                &client,
+                &config,
+                &cursor.trader,
+                cursor.target_start_ts,
+                cursor.cursor_ts,
+                cursor.cursor_offset,
+            )
+            .await
+            {
+                Ok(page) => page,
+                Err(e) => {
+                    progress.failed(&short(&cursor.trader), &e.to_string());
+                    failed.insert(cursor.trader.clone());
+                    continue;
+                }
+            };
+            let inserted = db.upsert_history_trades(&cursor.trader, &page).await?;
+            cursor.trades += inserted;
+            cursor.chunks += 1;
+            chunks_run += 1;
+
+            match next_page(&page, cursor.cursor_ts, cursor.cursor_offset) {
+                Some((end, offset)) => {
+                    cursor.cursor_ts = end;
+                    cursor.cursor_offset = offset;
+                }
+                None => {
+                    cursor.cursor_ts = cursor.target_start_ts;
+                    cursor.cursor_offset = 0;
+                    cursor.done = true;
+                }
+            }
+            cursor.updated_at = clock::now_ms();
+            db.save_backfill_cursor(cursor).await?;
+
+            progress.ok(
+                &short(&cursor.trader),
+                &format!(
+                    "+{:<4} trades  back to {}{}",
+                    inserted,
+                    clock::local_time_str(cursor.cursor_ts, "%Y-%m-%d %H:%M"),
+                    if cursor.done { "  (done)" } else { "" }
+                ),
+                None,
+            );
+            tokio::time::sleep(Duration::from_millis(options.delay_ms)).await;
+        }
+    }
+
+    if !progress.is_json() {
+        print_progress(&cursors, now);
+    }
+    if progress.finish().failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+// Newest-first page of TRADE activity in [start, end], skipping the first `offset` rows
+// (fetch_data already waits on the data-api rate limit)
+async fn fetch_page(
+    client: &reqwest::Client,
+    config: &EnvConfig,
+    trader: &str,
+    start: i64,
+    end: i64,
+    offset: u64,
+) -> Result<Vec<Value>> {
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&type=TRADE&start={}&end={}&limit={}&offset={}&sortBy=TIMESTAMP&sortDirection=DESC",
+        trader, start, end, PAGE_LIMIT, offset
+    );
+    let data = fetch_data(client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+    Ok(data.as_array().cloned().unwrap_or_default())
+}
+
+fn trade_ts(trade: &Value) -> Option<i64> {
+    trade.get("timestamp").and_then(|v| v.as_i64())
+}
+
+// (end, offset) of the page after `page`, fetched at (end, offset); None = nothing older left in the window.
+// The next page ends at the oldest second on this one and skips the trades of that second already stored,
+// so a full page from one second (a bursty trader) moves on by offset instead of repeating forever
+fn next_page(page: &[Value], end: i64, offset: u64) -> Option<(i64, u64)> {
+    if page.len() < PAGE_LIMIT {
+        return None;
+    }
+    let oldest = page.iter().filter_map(trade_ts).min()?;
+    if oldest >= end {
+        return Some((end, offset + page.len() as u64));
+    }
+    let seen = page.iter().filter(|t| trade_ts(t) == Some(oldest)).count() as u64;
+    Some((oldest, seen))
+}
+
+fn short(addr: &str) -> String {
+    if addr.len() > 10 {
+        format!("{}...{}", &addr[..6], &addr[addr.len() - 4..])
+    } else {
+        addr.to_string()
+    }
+}
+
+fn print_progress(cursors: &[BackfillCursor], now: i64) {
+    outln!("\n📥 Backfill progress\n");
+    outln!("  {:<14} {:>8} {:>9} {:>7}  {:<17} {}", "TRADER", "COVERED", "TRADES", "CHUNKS", "BACK TO", "STATUS");
+    for c in cursors {
+        let span = (now - c.target_start_ts).max(1) as f64;
+        let covered = ((now - c.cursor_ts).max(0) as f64 / span * 100.0).min(100.0);
+        let status = if c.done {
+            "done"
+        } else if c.updated_at == 0 {
+            "not started"
+        } else {
+            "partial"
+        };
+        outln!(
+            "  {:<14} {:>7.1}% {:>9} {:>7}  {:<17} {}",
+            short(&c.trader),
+            if c.done { 100.0 } else { covered },
+            c.trades,
+            c.chunks,
+            clock::local_time_str(c.cursor_ts, "%Y-%m-%d %H:%M"),
+            status
+        );
+    }
+    outln!();
+}
@@ -1,26 +1,35 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use anyhow::Result;
 use polymarket_copy_rust::{
-    get_usdc_balance, perform_health_check, utils::theme::colors, Db, EnvConfig, Logger,
+    get_usdc_balance, perform_health_check, utils::theme::colors, utils::verify_chain, Db, EnvConfig,
+    Logger,
 };
+use polymarket_copy_rust::utils::{http_client, HttpPool};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
 
-    println!();
-    println!(
+    outln!();
+    outln!(
         "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
         colors::ACCENT
     );
-    println!("     POLYMARKET BOT — HEALTH CHECK");
-    println!(
+    outln!("     POLYMARKET BOT — HEALTH CHECK");
+    outln!(
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
         colors::RESET
     );
-    println!();
+    outln!();
 
     let config = EnvConfig::from_env().await?;
-    let db_ok = Db::connect(&config.mongo_uri).await.is_ok();
+    let db_test = match Db::open(&config).await {
+        Ok(db) => db.self_test(&config.user_addresses, config.resource_max_mongo_ms).await,
+        Err(e) => Err(e),
+    };
     let balance = get_usdc_balance(
         &config.rpc_url,
         &config.usdc_contract_address,
@@ -28,7 +37,7 @@ async fn main() -> Result<()> {
     )
     .await;
     let polymarket_ok = polymarket_copy_rust::fetch_data(
-        &reqwest::Client::new(),
+        &http_client(HttpPool::Api),
         "https://data-api.polymarket.com/positions?user=0x0000000000000000000000000000000000000000",
         config.request_timeout_ms,
         config.network_retry_limit,
@@ -36,20 +45,27 @@ async fn main() -> Result<()> {
     .await
     .is_ok();
 
-    let health = perform_health_check(db_ok, &config.rpc_url, balance, polymarket_ok).await;
+    let health = perform_health_check(db_test, &config.rpc_url, balance, polymarket_ok).await;
+    let chain = verify_chain(&config).await;
 
     Logger::separator();
     Logger::header("SYSTEM CHECK");
-    let overall = if health.healthy {
+    let healthy = health.healthy && chain.is_ok();
+    let overall = if healthy {
         "All systems go"
     } else {
         "Degraded — fix issues below"
     };
     Logger::health_line(
         "Overall",
-        if health.healthy { "ok" } else { "error" },
+        if healthy { "ok" } else { "error" },
         overall,
     );
+    match &chain {
+        Ok(c) if c.warnings.is_empty() => Logger::health_line("Chain", "ok", &c.summary()),
+        Ok(c) => Logger::health_line("Chain", "warning", &c.summary()),
+        Err(e) => Logger::health_line("Chain", "error", &e.to_string()),
+    }
     Logger::health_line(
         "Database",
         &health.checks.database.status,
@@ -66,24 +82,27 @@ async fn main() -> Result<()> {
         &health.checks.polymarket_api.status,
         &health.checks.polymarket_api.message,
     );
+    for gauge in &health.rate_limits {
+        Logger::health_line(&format!("Quota {}", gauge.host), gauge.status(), &gauge.summary());
+    }
     Logger::separator();
 
-    if health.healthy {
-        println!();
-        println!(
+    if healthy {
+        outln!();
+        outln!(
             "{} Ready to run: make run{}",
             colors::SUCCESS,
             colors::RESET
         );
-        println!();
+        outln!();
     } else {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{} Fix the issues above, then run make health-check again.{}",
             colors::WARN,
             colors::RESET
         );
-        println!();
+        outln!();
         std::process::exit(1);
     }
 
@@ -1,69 +1,84 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use polymarket_copy_rust::utils::theme::colors;
 
 fn main() {
+    polymarket_term::init();
     let cyan = colors::ACCENT;
     let green = colors::SUCCESS;
     let yellow = "\x1b[33;1m";
     let blue = "\x1b[34;1m";
     let reset = colors::RESET;
 
-    println!("{cyan}");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("     POLYMARKET COPY TRADING BOT — COMMANDS (Rust)");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("{reset}\n");
+    outln!("{cyan}");
+    outln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    outln!("     POLYMARKET COPY TRADING BOT — COMMANDS (Rust)");
+    outln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    outln!("{reset}\n");
 
-    println!("{yellow}GETTING STARTED{reset}\n");
-    println!("  {green}make setup{reset}             Ensure .env exists from .env.example");
-    println!("  {green}make health-check{reset}      Verify DB, RPC, balance, API");
-    println!("  {green}make build{reset}             Build release binary");
-    println!("  {green}make run{reset}               Run the trading bot (default)");
-    println!("  {green}make dev{reset}               Run bot in dev (debug build)");
-    println!();
+    outln!("{yellow}GETTING STARTED{reset}\n");
+    outln!("  {green}make setup{reset}             Ensure .env exists from .env.example");
+    outln!("  {green}make health-check{reset}      Verify DB, RPC, balance, API");
+    outln!("  {green}make build{reset}             Build release binary");
+    outln!("  {green}make run{reset}               Run the trading bot (default)");
+    outln!("  {green}make dev{reset}               Run bot in dev (debug build)");
+    outln!();
 
-    println!("{yellow}WALLET & ALLOWANCE{reset}\n");
-    println!("  {green}make check-allowance{reset}    Show USDC balance and Polymarket allowance");
-    println!("  {green}make verify-allowance{reset}  Verify allowance (stub)");
-    println!("  {green}make set-token-allowance{reset} Set USDC approval (stub)");
-    println!("  {green}make check-proxy{reset}        Check proxy wallet (stub)");
-    println!("  {green}make check-both{reset}         Check both wallets (stub)");
-    println!();
+    outln!("{yellow}WALLET & ALLOWANCE{reset}\n");
+    outln!("  {green}make check-allowance{reset}    Show USDC balance and Polymarket allowance");
+    outln!("  {green}make verify-allowance{reset}  Verify allowance (stub)");
+    outln!("  {green}make set-token-allowance{reset} Set USDC approval (stub)");
+    outln!("  {green}make check-proxy{reset}        Check proxy wallet (stub)");
+    outln!("  {green}make check-both{reset}         Check both wallets (stub)");
+    outln!();
 
-    println!("{yellow}MONITORING & STATS{reset}\n");
-    println!("  {green}make check-stats{reset}       Trading stats (stub)");
-    println!("  {green}make check-activity{reset}    Recent activity (stub)");
-    println!("  {green}make check-pnl{reset}         PnL discrepancy (stub)");
-    println!();
+    outln!("{yellow}MONITORING & STATS{reset}\n");
+    outln!("  {green}make check-stats{reset}       Trading stats (stub)");
+    outln!("  {green}make check-activity{reset}    Recent activity (stub)");
+    outln!("  {green}make check-pnl{reset}         PnL discrepancy (stub)");
+    outln!("  {green}make skips{reset}             Skipped trades by reason (why trades weren't copied)");
+    outln!("  {green}make tags ARGS=\"...\"{reset}   Tag/note traders & markets, PnL per tag, CSV export");
+    outln!("  {green}make overrides ARGS=\"...\"{reset} Runtime config overrides (copy size, caps, pause)");
+    outln!("  {green}make report{reset}            Daily / weekly digest (ARGS=\"weekly --json\", --send delivers it)");
+    outln!("  {green}make tune{reset}              Suggested config changes from copies + skips (ARGS=\"--days N\")");
+    outln!("  {green}make logs{reset}              Replay what the bot logged (ARGS=\"100 error,trade,order\")");
+    outln!("  {green}make risk{reset}              Portfolio concentration, expected loss, scenarios (ARGS=\"--json f\")");
+    outln!("  {green}make execution-quality{reset} Copy slippage vs trader price / mid by trader, category, hour (ARGS=\"--days N\")");
+    outln!("  {green}make paper{reset}             DRY_RUN paper trades: PnL / ROI per trader (ARGS=\"--trader 0x.. --json\")");
+    outln!("  {green}make backup{reset}            Dump all bot collections to a .jsonl.gz archive (ARGS=\"file\")");
+    outln!("  {green}make restore ARGS=\"f\"{reset}   Restore an archive (add --drop to replace instead of merge)");
+    outln!();
 
-    println!("{yellow}POSITION MANAGEMENT{reset}\n");
-    println!("  {green}make manual-sell{reset}       Manually sell position (stub)");
-    println!("  {green}make sell-large{reset}         Sell large positions (stub)");
-    println!("  {green}make close-stale{reset}        Close stale positions (stub)");
-    println!("  {green}make close-resolved{reset}     Close resolved (stub)");
-    println!("  {green}make redeem-resolved{reset}   Redeem resolved (stub)");
-    println!("  {green}make transfer-to-gnosis{reset} Transfer to Gnosis Safe (stub)");
-    println!();
+    outln!("{yellow}POSITION MANAGEMENT{reset}\n");
+    outln!("  {green}make manual-sell{reset}       Manually sell position (stub)");
+    outln!("  {green}make sell-large{reset}         Sell large positions (stub)");
+    outln!("  {green}make close-stale{reset}        Close stale positions (stub)");
+    outln!("  {green}make close-resolved{reset}     Close resolved (stub)");
+    outln!("  {green}make redeem-resolved{reset}   Redeem resolved (stub)");
+    outln!("  {green}make transfer-to-gnosis{reset} Transfer to Gnosis Safe (stub)");
+    outln!();
 
-    println!("{yellow}TRADER RESEARCH{reset}\n");
-    println!("  {green}make find-traders{reset}      Find best traders (stub)");
-    println!("  {green}make find-low-risk{reset}      Low-risk traders (stub)");
-    println!("  {green}make scan-traders{reset}       Scan traders (stub)");
-    println!("  {green}make scan-markets{reset}       Scan from markets (stub)");
-    println!();
+    outln!("{yellow}TRADER RESEARCH{reset}\n");
+    outln!("  {green}make find-traders{reset}      Find best traders (stub)");
+    outln!("  {green}make find-low-risk{reset}      Low-risk traders (stub)");
+    outln!("  {green}make scan-traders{reset}       Scan traders (stub)");
+    outln!("  {green}make scan-markets{reset}       Scan from markets (stub)");
+    outln!();
 
-    println!("{yellow}SIMULATION{reset}\n");
-    println!("  {green}make simulate{reset}           Simulate profitability (stub)");
-    println!("  {green}make simulate-old{reset}       Old logic sim (stub)");
-    println!("  {green}make sim{reset}                 Run simulations (stub)");
-    println!("  {green}make compare{reset}            Compare results (stub)");
-    println!("  {green}make fetch-history{reset}      Fetch historical trades (stub)");
-    println!("  {green}make aggregate{reset}          Aggregate results (stub)");
-    println!("  {green}make audit{reset} / make audit-old{reset}  Algorithm audit (stub)");
-    println!();
+    outln!("{yellow}SIMULATION{reset}\n");
+    outln!("  {green}make simulate{reset}           Copy-latency PnL sensitivity (needs fetch-history)");
+    outln!("  {green}make simulate-old{reset}       Old logic sim (stub)");
+    outln!("  {green}make sim{reset}                 Run simulations (stub)");
+    outln!("  {green}make compare{reset}            Compare results (stub)");
+    outln!("  {green}make fetch-history{reset}      Backfill trader history (resumable)");
+    outln!("  {green}make aggregate{reset}          Aggregate results (stub)");
+    outln!("  {green}make audit{reset} / make audit-old{reset}  Algorithm audit (stub)");
+    outln!();
 
-    println!("{blue}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{reset}\n");
-    println!("  New user? Run: make setup && make health-check");
-    println!("  Then: make run");
-    println!("  (Stub) = not yet implemented in Rust; use TS project for full features.)");
-    println!();
+    outln!("{blue}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{reset}\n");
+    outln!("  New user? Run: make setup && make health-check");
+    outln!("  Then: make run");
+    outln!("  (Stub) = not yet implemented in Rust; use TS project for full features.)");
+    outln!();
 }
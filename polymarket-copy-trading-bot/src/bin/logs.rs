@@ -0,0 +1,39 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{format_log_event, recent_log_lines, LOGS_USAGE};
+use polymarket_copy_rust::{Db, EnvConfig};
+
+// Usage:
+//   logs [N] [level,...]   last N lines the bot logged (default 50), e.g. `logs 100 error,warning`
+//   logs ... --json        the same lines as JSON
+// Reads the "log_events" collection, so it works from any machine that can reach MONGO_URI
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("Usage: logs [--json]\n{}", LOGS_USAGE);
+        return Ok(());
+    }
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<String> = args.into_iter().filter(|a| a != "--json").collect();
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+
+    let events = recent_log_lines(&db, &args).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+    } else if events.is_empty() {
+        outln!("No log events stored (the bot keeps them while LOG_EVENTS_MAX > 0)");
+    } else {
+        for event in &events {
+            outln!("{}", format_log_event(event));
+        }
+    }
+    Ok(())
+}
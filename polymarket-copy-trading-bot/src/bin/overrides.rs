@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{run_override_command, OVERRIDE_USAGE};
+use polymarket_copy_rust::{Db, EnvConfig, Logger};
+
+// Usage: overrides [list] | set <KEY> <value> | clear <KEY>
+//        overrides pause-market|resume-market|close-market <conditionId> | resize-market <conditionId> <usd|off>
+// Runtime overrides live in the Mongo configs collection and win over .env
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.first().map(|a| a == "help" || a == "--help").unwrap_or(false) {
+        outln!("{}", OVERRIDE_USAGE);
+        return Ok(());
+    }
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+
+    match run_override_command(&db, &args).await {
+        Ok(reply) => outln!("{}", reply),
+        Err(e) => Logger::error(&e.to_string()),
+    }
+    Ok(())
+}
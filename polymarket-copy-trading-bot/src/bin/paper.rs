@@ -0,0 +1,199 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{
+    fetch_quote, http_client, paper_books, paper_cash, theme::colors, HttpPool, PaperBook,
+};
+use polymarket_copy_rust::{Db, EnvConfig, Logger};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const USAGE: &str = "Usage: paper [--trader 0x...] [--json]";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraderPaper {
+    trader: String,
+    buys: usize,
+    sells: usize,
+    bought_usd: f64,
+    sold_usd: f64,
+    realized_pnl: f64,
+    open_positions: usize,
+    open_cost_usd: f64,
+    open_value_usd: f64,
+    unrealized_pnl: f64,
+    total_pnl: f64,
+    roi: f64, // Total PnL / USD bought
+    unpriced: usize, // Open positions without a book (valued at cost)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PaperReport {
+    dry_run: bool,
+    trades: usize,
+    start_balance_usd: f64,
+    cash_usd: f64,
+    open_value_usd: f64,
+    equity_usd: f64,
+    total_pnl: f64,
+    traders: Vec<TraderPaper>,
+}
+
+fn pnl_color(v: f64) -> &'static str {
+    if v >= 0.0 {
+        colors::SUCCESS
+    } else {
+        colors::ERROR
+    }
+}
+
+// Open holdings at the book mid; a dead book keeps the position at cost and is counted as unpriced
+fn evaluate(book: &PaperBook, mids: &HashMap<String, f64>) -> TraderPaper {
+    let (mut open_cost, mut open_value, mut unpriced) = (0.0, 0.0, 0);
+    for h in book.holdings.values() {
+        open_cost += h.cost_usd;
+        match mids.get(&h.asset) {
+            Some(mid) => open_value += h.size * mid,
+            None => {
+                open_value += h.cost_usd;
+                unpriced += 1;
+            }
+        }
+    }
+    let unrealized = open_value - open_cost;
+    let total = book.realized_pnl + unrealized;
+    TraderPaper {
+        trader: book.trader.clone(),
+        buys: book.buys,
+        sells: book.sells,
+        bought_usd: book.bought_usd,
+        sold_usd: book.sold_usd,
+        realized_pnl: book.realized_pnl,
+        open_positions: book.holdings.len(),
+        open_cost_usd: open_cost,
+        open_value_usd: open_value,
+        unrealized_pnl: unrealized,
+        total_pnl: total,
+        roi: if book.bought_usd > 0.0 { total / book.bought_usd } else { 0.0 },
+        unpriced,
+    }
+}
+
+fn print_report(r: &PaperReport) {
+    outln!();
+    outln!(
+        "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+        colors::ACCENT
+    );
+    outln!("     POLYMARKET BOT — PAPER TRADING");
+    outln!(
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
+        colors::RESET
+    );
+    outln!("  DRY_RUN:       {}", if r.dry_run { "on" } else { "off (showing earlier paper trades)" });
+    outln!("  Paper trades:  {}", r.trades);
+    if r.trades == 0 {
+        outln!();
+        outln!("  (nothing yet - run the bot with DRY_RUN=true to record paper trades)");
+        outln!();
+        return;
+    }
+    outln!("  Start:         ${:.2} (PAPER_BALANCE_USD)", r.start_balance_usd);
+    outln!("  Cash:          ${:.2}", r.cash_usd);
+    outln!("  Open value:    ${:.2}", r.open_value_usd);
+    outln!(
+        "  Equity:        ${:.2} ({}{:+.2}{})",
+        r.equity_usd,
+        pnl_color(r.total_pnl),
+        r.total_pnl,
+        colors::RESET
+    );
+    outln!();
+
+    Logger::header("By trader (best first)");
+    outln!(
+        "  {:<14} {:>5} {:>5} {:>11} {:>11} {:>10} {:>10} {:>10} {:>8}",
+        "Trader", "Buys", "Sells", "Bought", "Sold", "Realized", "Open", "Total", "ROI"
+    );
+    for t in &r.traders {
+        outln!(
+            "  {:<14} {:>5} {:>5} {:>11} {:>11} {:>10} {:>10} {}{:>10}{} {:>8}{}",
+            Logger::format_address(&t.trader),
+            t.buys,
+            t.sells,
+            format!("${:.2}", t.bought_usd),
+            format!("${:.2}", t.sold_usd),
+            format!("{:+.2}", t.realized_pnl),
+            format!("${:.2}", t.open_value_usd),
+            pnl_color(t.total_pnl),
+            format!("{:+.2}", t.total_pnl),
+            colors::RESET,
+            format!("{:+.1}%", t.roi * 100.0),
+            if t.unpriced > 0 { format!("  ({} at cost - no book)", t.unpriced) } else { String::new() }
+        );
+    }
+    outln!();
+}
+
+// Usage:
+//   paper                   paper_trades (DRY_RUN fills) replayed per trader: realized PnL, open positions at the mid, ROI
+//   paper --trader 0x...    one trader only
+//   paper --json            the report as JSON
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let trader = args
+        .iter()
+        .position(|a| a == "--trader")
+        .and_then(|i| args.get(i + 1).cloned());
+    let json = args.iter().any(|a| a == "--json");
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let http_client = http_client(HttpPool::Api);
+
+    let trades = db.get_paper_trades(trader.as_deref()).await?;
+    let books = paper_books(&trades);
+
+    let mut mids: HashMap<String, f64> = HashMap::new();
+    for asset in books.values().flat_map(|b| b.holdings.keys()) {
+        if mids.contains_key(asset) {
+            continue;
+        }
+        if let Some((bid, ask)) = fetch_quote(&config, &http_client, asset).await.filter(|(bid, _)| *bid > 0.0) {
+            mids.insert(asset.clone(), (bid + ask) / 2.0);
+        }
+    }
+
+    let mut traders: Vec<TraderPaper> = books.values().map(|b| evaluate(b, &mids)).collect();
+    traders.sort_by(|a, b| b.total_pnl.total_cmp(&a.total_pnl));
+    let cash = paper_cash(config.paper_balance_usd, &trades);
+    let open_value: f64 = traders.iter().map(|t| t.open_value_usd).sum();
+    let report = PaperReport {
+        dry_run: config.dry_run,
+        trades: trades.len(),
+        start_balance_usd: config.paper_balance_usd,
+        cash_usd: cash,
+        open_value_usd: open_value,
+        equity_usd: cash + open_value,
+        total_pnl: traders.iter().map(|t| t.total_pnl).sum(),
+        traders,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
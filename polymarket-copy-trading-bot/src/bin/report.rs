@@ -0,0 +1,45 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{build_digest, deliver_digest, http_client, HttpPool, ReportPeriod};
+use polymarket_copy_rust::{Db, EnvConfig, Logger};
+
+const USAGE: &str = "Usage: report [daily|weekly] [--json] [--send]";
+
+// Usage:
+//   report                 print today's digest (PnL, trades, skips, winners / losers, exposure)
+//   report weekly --json   the 7-day digest as the JSON the webhook receives
+//   report daily --send    deliver it now via REPORT_WEBHOOK_URL / SMTP (does not touch the schedule)
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let period = match args.iter().find(|a| !a.starts_with("--")) {
+        Some(p) => ReportPeriod::parse(p).ok_or_else(|| anyhow::anyhow!("{}", USAGE))?,
+        None => ReportPeriod::Daily,
+    };
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let client = http_client(HttpPool::Api);
+
+    let digest = build_digest(&config, &db, &client, period).await?;
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", serde_json::to_string_pretty(&digest)?);
+    } else {
+        outln!("\n{}\n", digest.to_text());
+    }
+
+    if args.iter().any(|a| a == "--send") {
+        deliver_digest(&config, &client, &digest).await?;
+        Logger::success(&format!("Sent the {} digest", period.as_str()));
+    }
+    Ok(())
+}
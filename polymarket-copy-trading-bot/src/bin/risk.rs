@@ -0,0 +1,352 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::theme::colors;
+use polymarket_copy_rust::utils::{http_client, HttpPool};
+use polymarket_copy_rust::{fetch_data, fetch_paginated, EnvConfig, Logger, UserPosition};
+use serde::Serialize;
+use std::collections::HashMap;
+
+// Uniform price drops applied to every open position in the scenario table
+const PRICE_SHOCKS: [f64; 3] = [0.10, 0.25, 0.50];
+
+#[derive(Serialize, Clone)]
+struct PositionRisk {
+    title: String,
+    outcome: String,
+    event: String,
+    category: String,
+    size: f64,
+    cur_price: f64,
+    exposure_usd: f64,      // Current value = what's lost if it resolves against me
+    cost_basis_usd: f64,    // Initial value = loss vs what I paid
+    expected_loss_usd: f64, // Exposure x market probability of resolving against me
+}
+
+#[derive(Serialize)]
+struct GroupRisk {
+    name: String,
+    positions: usize,
+    exposure_usd: f64,
+    share: f64,
+    expected_loss_usd: f64,
+}
+
+#[derive(Serialize)]
+struct Scenario {
+    name: String,
+    loss_usd: f64,
+    share_of_exposure: f64,
+}
+
+#[derive(Serialize)]
+struct RiskReport {
+    wallet: String,
+    generated_at: String,
+    open_positions: usize,
+    total_exposure_usd: f64,
+    total_cost_basis_usd: f64,
+    expected_loss_usd: f64,
+    top5_position_share: f64,
+    top5_event_share: f64,
+    by_event: Vec<GroupRisk>,
+    by_category: Vec<GroupRisk>,
+    scenarios: Vec<Scenario>,
+    positions: Vec<PositionRisk>,
+}
+
+// Usage:
+//   risk                    concentration + loss table for my open positions
+//   risk --json <file>      also write the report as JSON
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    let json_path = match args.iter().position(|a| a == "--json") {
+        Some(i) => Some(
+            args.get(i + 1)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Usage: risk [--json <file>]"))?,
+        ),
+        None => None,
+    };
+
+    let config = EnvConfig::from_env().await?;
+    let http_client = http_client(HttpPool::Api);
+
+    let url = format!(
+        "https://data-api.polymarket.com/positions?user={}",
+        config.proxy_wallet
+    );
+    let positions: Vec<UserPosition> = fetch_paginated(
+        &http_client,
+        &url,
+        config.request_timeout_ms,
+        config.network_retry_limit,
+    )
+    .await?
+    .as_array()
+    .map(|arr| {
+        arr.iter()
+            .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
+            .filter(|p| p.size.unwrap_or(0.0) > 0.0 && !p.redeemable.unwrap_or(false))
+            .collect()
+    })
+    .unwrap_or_default();
+
+    let condition_ids: Vec<String> = positions
+        .iter()
+        .filter_map(|p| p.condition_id.clone())
+        .collect();
+    let categories = fetch_categories(&config, &http_client, &condition_ids).await;
+
+    let mut rows: Vec<PositionRisk> = positions
+        .iter()
+        .map(|p| {
+            let cur_price = p.cur_price.unwrap_or(0.0);
+            let exposure = p
+                .current_value
+                .unwrap_or(p.size.unwrap_or(0.0) * cur_price);
+            PositionRisk {
+                title: p.title.clone().unwrap_or_else(|| "Unknown".to_string()),
+                outcome: p.outcome.clone().unwrap_or_else(|| "?".to_string()),
+                event: p
+                    .event_slug
+                    .clone()
+                    .or_else(|| p.slug.clone())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                category: p
+                    .condition_id
+                    .as_ref()
+                    .and_then(|c| categories.get(c).cloned())
+                    .unwrap_or_else(|| "uncategorized".to_string()),
+                size: p.size.unwrap_or(0.0),
+                cur_price,
+                exposure_usd: exposure,
+                cost_basis_usd: p.initial_value.unwrap_or(0.0),
+                expected_loss_usd: exposure * (1.0 - cur_price).clamp(0.0, 1.0),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| b.exposure_usd.total_cmp(&a.exposure_usd));
+
+    let report = build_report(&config.proxy_wallet, rows);
+    print_report(&report);
+
+    if let Some(path) = json_path {
+        tokio::fs::write(&path, serde_json::to_string_pretty(&report)?).await?;
+        Logger::success(&format!("Risk report written to {}", path));
+    }
+    Ok(())
+}
+
+// conditionId -> Gamma category (markets without one are left out)
+async fn fetch_categories(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    condition_ids: &[String],
+) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for chunk in condition_ids.chunks(20) {
+        let query = chunk
+            .iter()
+            .map(|c| format!("condition_ids={}", c))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("https://gamma-api.polymarket.com/markets?{}", query);
+        let Ok(markets) =
+            fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await
+        else {
+            continue;
+        };
+        for m in markets.as_array().into_iter().flatten() {
+            let cid = m.get("conditionId").and_then(|v| v.as_str());
+            let category = m
+                .get("category")
+                .and_then(|v| v.as_str())
+                .filter(|c| !c.is_empty());
+            if let (Some(cid), Some(category)) = (cid, category) {
+                out.insert(cid.to_string(), category.to_string());
+            }
+        }
+    }
+    out
+}
+
+fn share(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total
+    } else {
+        0.0
+    }
+}
+
+fn group_by(rows: &[PositionRisk], total: f64, key: impl Fn(&PositionRisk) -> &str) -> Vec<GroupRisk> {
+    let mut groups: HashMap<&str, GroupRisk> = HashMap::new();
+    for row in rows {
+        let name = key(row);
+        let g = groups.entry(name).or_insert_with(|| GroupRisk {
+            name: name.to_string(),
+            positions: 0,
+            exposure_usd: 0.0,
+            share: 0.0,
+            expected_loss_usd: 0.0,
+        });
+        g.positions += 1;
+        g.exposure_usd += row.exposure_usd;
+        g.expected_loss_usd += row.expected_loss_usd;
+    }
+    let mut out: Vec<GroupRisk> = groups.into_values().collect();
+    for g in &mut out {
+        g.share = share(g.exposure_usd, total);
+    }
+    out.sort_by(|a, b| b.exposure_usd.total_cmp(&a.exposure_usd));
+    out
+}
+
+fn build_report(wallet: &str, rows: Vec<PositionRisk>) -> RiskReport {
+    let total: f64 = rows.iter().map(|r| r.exposure_usd).sum();
+    let by_event = group_by(&rows, total, |r| &r.event);
+    let by_category = group_by(&rows, total, |r| &r.category);
+
+    let top5_position: f64 = rows.iter().take(5).map(|r| r.exposure_usd).sum();
+    let top5_event: f64 = by_event.iter().take(5).map(|g| g.exposure_usd).sum();
+
+    let mut scenarios = vec![Scenario {
+        name: "Everything resolves against me".to_string(),
+        loss_usd: total,
+        share_of_exposure: share(total, total),
+    }];
+    if let Some(e) = by_event.first() {
+        scenarios.push(Scenario {
+            name: format!("Largest event lost ({})", e.name),
+            loss_usd: e.exposure_usd,
+            share_of_exposure: e.share,
+        });
+    }
+    if let Some(c) = by_category.first() {
+        scenarios.push(Scenario {
+            name: format!("Largest category lost ({})", c.name),
+            loss_usd: c.exposure_usd,
+            share_of_exposure: c.share,
+        });
+    }
+    for shock in PRICE_SHOCKS {
+        scenarios.push(Scenario {
+            name: format!("All prices -{:.0}%", shock * 100.0),
+            loss_usd: total * shock,
+            share_of_exposure: shock,
+        });
+    }
+
+    RiskReport {
+        wallet: wallet.to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        open_positions: rows.len(),
+        total_exposure_usd: total,
+        total_cost_basis_usd: rows.iter().map(|r| r.cost_basis_usd).sum(),
+        expected_loss_usd: rows.iter().map(|r| r.expected_loss_usd).sum(),
+        top5_position_share: share(top5_position, total),
+        top5_event_share: share(top5_event, total),
+        by_event,
+        by_category,
+        scenarios,
+        positions: rows,
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        format!("{}…", s.chars().take(max - 1).collect::<String>())
+    } else {
+        s.to_string()
+    }
+}
+
+fn print_groups(title: &str, groups: &[GroupRisk]) {
+    Logger::header(title);
+    outln!("  {:<40} {:>4} {:>12} {:>7} {:>12}", "Name", "Pos", "Exposure", "Share", "Exp. loss");
+    for g in groups.iter().take(10) {
+        outln!(
+            "  {:<40} {:>4} {:>12} {:>6.1}% {:>12}",
+            truncate(&g.name, 40),
+            g.positions,
+            format!("${:.2}", g.exposure_usd),
+            g.share * 100.0,
+            format!("${:.2}", g.expected_loss_usd)
+        );
+    }
+    if groups.len() > 10 {
+        outln!("  {}... {} more{}", colors::MUTED, groups.len() - 10, colors::RESET);
+    }
+    outln!();
+}
+
+fn print_report(r: &RiskReport) {
+    outln!();
+    outln!(
+        "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+        colors::ACCENT
+    );
+    outln!("     POLYMARKET BOT — PORTFOLIO RISK");
+    outln!(
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
+        colors::RESET
+    );
+    outln!();
+
+    if r.open_positions == 0 {
+        outln!("  (no open positions)");
+        outln!();
+        return;
+    }
+
+    outln!("  Wallet:           {}", Logger::format_address(&r.wallet));
+    outln!("  Open positions:   {}", r.open_positions);
+    outln!("  Exposure:         {}", Logger::money(r.total_exposure_usd));
+    outln!("  Cost basis:       {}", Logger::money(r.total_cost_basis_usd));
+    outln!("  Expected loss:    {} (at market odds)", Logger::money(r.expected_loss_usd));
+    outln!("  Top-5 positions:  {:.1}% of exposure", r.top5_position_share * 100.0);
+    outln!("  Top-5 events:     {:.1}% of exposure", r.top5_event_share * 100.0);
+    outln!();
+
+    Logger::header("Largest positions");
+    outln!(
+        "  {:<36} {:<8} {:>7} {:>12} {:>12}",
+        "Market", "Outcome", "Price", "Exposure", "Exp. loss"
+    );
+    for p in r.positions.iter().take(10) {
+        outln!(
+            "  {:<36} {:<8} {:>7.3} {:>12} {:>12}",
+            truncate(&p.title, 36),
+            truncate(&p.outcome, 8),
+            p.cur_price,
+            format!("${:.2}", p.exposure_usd),
+            format!("${:.2}", p.expected_loss_usd)
+        );
+    }
+    outln!();
+
+    print_groups("By event", &r.by_event);
+    print_groups("By category", &r.by_category);
+
+    Logger::header("Scenarios");
+    for s in &r.scenarios {
+        outln!(
+            "  {:<52} {:>12} {:>6.1}%",
+            truncate(&s.name, 52),
+            format!("-${:.2}", s.loss_usd),
+            s.share_of_exposure * 100.0
+        );
+    }
+    outln!();
+    outln!(
+        "  {}Exposure = current value (lost in full if a position resolves against you){}",
+        colors::MUTED,
+        colors::RESET
+    );
+    outln!();
+}
@@ -1,3 +1,6 @@
+#[macro_use]
+extern crate polymarket_term;
+
 const CTF_CONTRACT: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
 const POLYMARKET_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
 const POLYGON_CHAIN_ID: u64 = 137;
@@ -6,7 +9,9 @@ use alloy::network::TransactionBuilder;
 use alloy::providers::Provider;
 use alloy::signers::Signer;
 use anyhow::Result;
+use polymarket_copy_rust::config::SignerBackend;
 use polymarket_copy_rust::{utils::theme::colors, EnvConfig, Logger};
+use polymarket_copy_rust::utils::{encode_call, http_client, HttpPool};
 use std::str::FromStr;
 
 async fn ctf_is_approved_for_all(
@@ -27,7 +32,7 @@ async fn ctf_is_approved_for_all(
         "params": [{"to": ctf_contract, "data": data}, "latest"],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Rpc);
     let resp = client
         .post(rpc_url)
         .json(&body)
@@ -50,26 +55,33 @@ async fn ctf_is_approved_for_all(
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
     let config = EnvConfig::from_env().await?;
+    if config.signer_backend != SignerBackend::Local {
+        anyhow::bail!(
+            "set_token_allowance signs with PRIVATE_KEY - not available with SIGNER_BACKEND={}; approve from your signer's wallet instead",
+            config.signer_backend.label()
+        );
+    }
 
     let signer =
         alloy::signers::local::PrivateKeySigner::from_str(&format!("0x{}", config.private_key))?
             .with_chain_id(Some(POLYGON_CHAIN_ID));
     let eoa = format!("0x{:x}", signer.address());
 
-    println!();
-    println!(
+    outln!();
+    outln!(
         "{} Setting CTF token allowance for Polymarket{}",
         colors::ACCENT,
         colors::RESET
     );
-    println!();
-    println!("  Wallet (EOA): {}", Logger::format_address(&eoa));
-    println!("  CTF:          {}", CTF_CONTRACT);
-    println!("  Exchange:     {}", POLYMARKET_EXCHANGE);
-    println!();
+    outln!();
+    outln!("  Wallet (EOA): {}", Logger::format_address(&eoa));
+    outln!("  CTF:          {}", CTF_CONTRACT);
+    outln!("  Exchange:     {}", POLYMARKET_EXCHANGE);
+    outln!();
 
-    println!(
+    outln!(
         "{} Checking current approval status...{}",
         colors::ACCENT,
         colors::RESET
@@ -78,29 +90,23 @@ async fn main() -> Result<()> {
         ctf_is_approved_for_all(&config.rpc_url, CTF_CONTRACT, &eoa, POLYMARKET_EXCHANGE).await?;
 
     if is_approved {
-        println!(
+        outln!(
             "{} Tokens already approved. You can sell positions.{}",
             colors::SUCCESS,
             colors::RESET
         );
-        println!();
+        outln!();
         return Ok(());
     }
 
-    println!(
+    outln!(
         "{} Tokens not approved. Sending setApprovalForAll tx...{}",
         colors::WARN,
         colors::RESET
     );
-    println!();
+    outln!();
 
-    let exchange_trim = POLYMARKET_EXCHANGE.trim_start_matches("0x").to_lowercase();
-    let calldata_hex = format!("0xa22cb465{:0>64}{:0>64}", exchange_trim, "1");
-    let calldata_str = calldata_hex.trim_start_matches("0x");
-    let data_bytes: Vec<u8> = (0..calldata_str.len())
-        .step_by(2)
-        .map(|i| u8::from_str_radix(&calldata_str[i..i + 2], 16).unwrap_or(0))
-        .collect();
+    let data_bytes = encode_call("a22cb465", &[POLYMARKET_EXCHANGE, "1"])?;
 
     let to_addr = CTF_CONTRACT.parse::<alloy::primitives::Address>()?;
     let tx = alloy::rpc::types::TransactionRequest::default()
@@ -116,44 +122,44 @@ async fn main() -> Result<()> {
 
     let pending = provider.send_transaction(tx).await?;
     let tx_hash = *pending.tx_hash();
-    println!("  Tx sent: 0x{:x}", tx_hash);
-    println!("  Waiting for confirmation...");
+    outln!("  Tx sent: 0x{:x}", tx_hash);
+    outln!("  Waiting for confirmation...");
     let receipt = pending.get_receipt().await?;
     if receipt.status() {
-        println!(
+        outln!(
             "{} Success. Tokens approved.{}",
             colors::SUCCESS,
             colors::RESET
         );
-        println!("  https://polygonscan.com/tx/0x{:x}", tx_hash);
-        println!();
+        outln!("  https://polygonscan.com/tx/0x{:x}", tx_hash);
+        outln!();
 
-        println!("{} Verifying approval...{}", colors::ACCENT, colors::RESET);
+        outln!("{} Verifying approval...{}", colors::ACCENT, colors::RESET);
         let verified =
             ctf_is_approved_for_all(&config.rpc_url, CTF_CONTRACT, &eoa, POLYMARKET_EXCHANGE)
                 .await?;
         if verified {
-            println!(
+            outln!(
                 "{} Verification: Approval confirmed on-chain{}",
                 colors::SUCCESS,
                 colors::RESET
             );
-            println!(
+            outln!(
                 "{} You can now sell your positions.{}",
                 colors::SUCCESS,
                 colors::RESET
             );
         } else {
-            println!(
+            outln!(
                 "{} Warning: Approval verification failed.{}",
                 colors::WARN,
                 colors::RESET
             );
         }
     } else {
-        eprintln!("{} Transaction reverted.{}", colors::ERROR, colors::RESET);
+        eoutln!("{} Transaction reverted.{}", colors::ERROR, colors::RESET);
         std::process::exit(1);
     }
-    println!();
+    outln!();
     Ok(())
 }
@@ -1,38 +1,42 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use polymarket_copy_rust::utils::theme::colors;
 use std::fs;
 use std::path::Path;
 
 fn main() {
+    polymarket_term::init();
     let example = Path::new(".env.example");
     let env = Path::new(".env");
 
-    println!();
-    println!(
+    outln!();
+    outln!(
         "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
         colors::ACCENT
     );
-    println!("     POLYMARKET COPY TRADING BOT — SETUP");
-    println!(
+    outln!("     POLYMARKET COPY TRADING BOT — SETUP");
+    outln!(
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
         colors::RESET
     );
-    println!();
+    outln!();
 
     if env.exists() {
-        println!(
+        outln!(
             "{} .env already exists. Edit it to change configuration.{}",
             colors::SUCCESS,
             colors::RESET
         );
-        println!();
-        println!("  Validate: make validate-setup");
-        println!("  Health:   make health-check");
-        println!();
+        outln!();
+        outln!("  Validate: make validate-setup");
+        outln!("  Health:   make health-check");
+        outln!();
         return;
     }
 
     if !example.exists() {
-        eprintln!(
+        eoutln!(
             "{} .env.example not found. Cannot create .env.{}",
             colors::ERROR,
             colors::RESET
@@ -42,22 +46,22 @@ fn main() {
 
     match fs::copy(example, env) {
         Ok(_) => {
-            println!(
+            outln!(
                 "{} Created .env from .env.example.{}",
                 colors::SUCCESS,
                 colors::RESET
             );
-            println!();
-            println!("  Next steps:");
-            println!("  1. Open .env and set: USER_ADDRESSES, PROXY_WALLET, PRIVATE_KEY,");
-            println!("     MONGO_URI, RPC_URL, CLOB_HTTP_URL, CLOB_WS_URL, USDC_CONTRACT_ADDRESS");
-            println!("  2. Run: make validate-setup");
-            println!("  3. Run: make health-check");
-            println!("  4. Run: make run");
-            println!();
+            outln!();
+            outln!("  Next steps:");
+            outln!("  1. Open .env and set: USER_ADDRESSES, PROXY_WALLET, PRIVATE_KEY,");
+            outln!("     MONGO_URI, RPC_URL, CLOB_HTTP_URL, CLOB_WS_URL, USDC_CONTRACT_ADDRESS");
+            outln!("  2. Run: make validate-setup");
+            outln!("  3. Run: make health-check");
+            outln!("  4. Run: make run");
+            outln!();
         }
         Err(e) => {
-            eprintln!(
+            eoutln!(
                 "{} Failed to create .env: {}{}",
                 colors::ERROR,
                 e,
@@ -0,0 +1,477 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{clock, http_client, HttpPool};
+use polymarket_copy_rust::{fetch_data, Db, EnvConfig, Logger};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+const USAGE: &str = "Usage: simulate [--days N] [--until UNIX_SECS] [--traders 0xa,0xb] [--delays 0,1,5,30,60] [--usd N] \
+[--fidelity MIN] [--seed N] [--slippage-bps N] [--json [file.json]]";
+// Interpolating across a longer gap than this would invent prices - the trade is skipped instead
+const MAX_PRICE_GAP_SECS: i64 = 3_600;
+// --json without a file name writes here (where aggregate looks by default)
+const RESULTS_DIR: &str = "strategy_factory_results";
+
+// Every input that shapes the result - hashed into the manifest's configHash, so two result files with the same hash
+// (and dataHash) came from the same run
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Options {
+    days: i64,
+    until: i64, // Unix secs; trades after it are ignored (default now - pass it back to rerun the same window)
+    traders: Option<Vec<String>>,
+    delays: Vec<i64>,
+    usd_per_buy: f64,
+    fidelity_mins: u32,
+    seed: u64,
+    slippage_bps: f64, // Random extra slippage per fill, uniform 0..N bps against us (0 = fills at the series price)
+    #[serde(skip)]
+    json: Option<Option<String>>, // --json (default path) / --json FILE
+}
+
+fn parse_options() -> Options {
+    let args: Vec<String> = polymarket_term::args().collect();
+    let value = |flag: &str| -> Option<String> {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+    let list = |v: String| -> Vec<String> {
+        v.split(',')
+            .map(|a| a.trim().to_lowercase())
+            .filter(|a| !a.is_empty())
+            .collect()
+    };
+    let mut delays: Vec<i64> = value("--delays")
+        .map(|v| list(v).iter().filter_map(|d| d.parse().ok()).collect())
+        .unwrap_or_else(|| vec![0, 1, 5, 30, 60]);
+    if !delays.contains(&0) {
+        delays.push(0); // Baseline for the deltas
+    }
+    delays.sort_unstable();
+    delays.dedup();
+    let mut traders = value("--traders").map(list);
+    if let Some(t) = traders.as_mut() {
+        t.sort();
+        t.dedup();
+    }
+    Options {
+        days: value("--days").and_then(|v| v.parse().ok()).unwrap_or(30),
+        until: value("--until").and_then(|v| v.parse().ok()).unwrap_or_else(|| clock::now_ms() / 1000),
+        traders,
+        delays,
+        usd_per_buy: value("--usd").and_then(|v| v.parse().ok()).unwrap_or(10.0),
+        fidelity_mins: value("--fidelity").and_then(|v| v.parse().ok()).unwrap_or(1),
+        seed: value("--seed")
+            .or_else(|| std::env::var("SIM_SEED").ok())
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0),
+        slippage_bps: value("--slippage-bps").and_then(|v| v.parse().ok()).unwrap_or(0.0_f64).max(0.0),
+        json: args
+            .iter()
+            .position(|a| a == "--json")
+            .map(|i| args.get(i + 1).filter(|f| !f.starts_with("--")).cloned()),
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+// SplitMix64 - tiny, seedable and identical on every platform / build (no rand dependency for one noise source)
+struct SimRng(u64);
+
+impl SimRng {
+    // One independent stream per (seed, label), so a trader's result doesn't depend on who was simulated before them
+    fn stream(seed: u64, label: &str) -> Self {
+        let digest = Sha256::digest(format!("{}:{}", seed, label).as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        SimRng(u64::from_le_bytes(bytes))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[derive(Clone)]
+struct Trade {
+    ts: i64,
+    tx: String, // Tie-break for trades in the same second (Mongo returns those in no fixed order)
+    asset: String,
+    side: String,
+    price: f64,
+    size: f64,
+}
+
+impl Trade {
+    fn from_json(v: &Value) -> Option<Self> {
+        let num = |k: &str| v.get(k).and_then(|x| x.as_f64().or_else(|| x.as_str()?.parse().ok()));
+        Some(Trade {
+            ts: v.get("timestamp")?.as_i64()?,
+            tx: v.get("transactionHash").and_then(|x| x.as_str()).unwrap_or("").to_string(),
+            asset: v.get("asset")?.as_str()?.to_string(),
+            side: v.get("side")?.as_str()?.to_uppercase(),
+            price: num("price")?,
+            size: num("size")?,
+        })
+    }
+}
+
+// CLOB /prices-history points for one token, ascending by time
+struct Series(Vec<(i64, f64)>);
+
+impl Series {
+    // Linear interpolation between the surrounding points (None = outside the data or across a long gap)
+    fn price_at(&self, ts: i64) -> Option<f64> {
+        let points = &self.0;
+        let i = points.partition_point(|(t, _)| *t <= ts);
+        match (i.checked_sub(1).map(|j| points[j]), points.get(i).copied()) {
+            (Some((t0, p0)), Some((t1, p1))) if t1 - t0 <= MAX_PRICE_GAP_SECS => {
+                Some(p0 + (p1 - p0) * (ts - t0) as f64 / (t1 - t0).max(1) as f64)
+            }
+            (Some((t0, p0)), None) if ts - t0 <= MAX_PRICE_GAP_SECS => Some(p0),
+            _ => None,
+        }
+    }
+
+    fn last(&self) -> Option<f64> {
+        self.0.last().map(|(_, p)| *p)
+    }
+}
+
+async fn fetch_series(client: &reqwest::Client, config: &EnvConfig, asset: &str, start: i64, end: i64, fidelity: u32) -> Option<Series> {
+    let url = format!(
+        "{}/prices-history?market={}&startTs={}&endTs={}&fidelity={}",
+        config.clob_http_url.trim_end_matches('/'),
+        asset,
+        start,
+        end,
+        fidelity
+    );
+    let data = fetch_data(client, &url, config.request_timeout_ms, config.network_retry_limit).await.ok()?;
+    let mut points: Vec<(i64, f64)> = data
+        .get("history")?
+        .as_array()?
+        .iter()
+        .filter_map(|p| Some((p.get("t")?.as_i64()?, p.get("p")?.as_f64()?)))
+        .collect();
+    points.sort_by_key(|(t, _)| *t);
+    (!points.is_empty()).then_some(Series(points))
+}
+
+// One copy run of a trader's trades at a fixed execution delay
+// (BTreeMaps so sums over held tokens add up in the same order every run)
+#[derive(Default, Clone)]
+struct Run {
+    fills: u32,
+    skipped: u32,      // No price data at T+delay, or nothing held to sell
+    slippage_usd: f64, // Paid vs the trader's price (positive = worse)
+    notional: f64,
+    spent: f64, // USD put into buys (the ROI base)
+    cash: f64,
+    held: BTreeMap<String, f64>,
+    our_bought: BTreeMap<String, f64>,
+    trader_bought: BTreeMap<String, f64>,
+}
+
+impl Run {
+    fn apply(&mut self, trade: &Trade, fill_price: Option<f64>, usd_per_buy: f64) {
+        let Some(p) = fill_price.filter(|p| *p > 0.001 && *p < 0.999) else {
+            self.skipped += 1;
+            return;
+        };
+        if trade.side == "BUY" {
+            let tokens = usd_per_buy / p;
+            self.cash -= usd_per_buy;
+            *self.held.entry(trade.asset.clone()).or_default() += tokens;
+            *self.our_bought.entry(trade.asset.clone()).or_default() += tokens;
+            *self.trader_bought.entry(trade.asset.clone()).or_default() += trade.size;
+            self.slippage_usd += tokens * (p - trade.price);
+            self.notional += usd_per_buy;
+            self.spent += usd_per_buy;
+        } else {
+            // Sell the same share of what we copied as the trader sells of what they bought
+            let ratio = match (self.our_bought.get(&trade.asset), self.trader_bought.get(&trade.asset)) {
+                (Some(ours), Some(theirs)) if *theirs > 0.0 => ours / theirs,
+                _ => 0.0,
+            };
+            let held = self.held.entry(trade.asset.clone()).or_default();
+            let tokens = (trade.size * ratio).min(*held);
+            if tokens <= 0.0 {
+                self.skipped += 1;
+                return;
+            }
+            *held -= tokens;
+            self.cash += tokens * p;
+            self.slippage_usd += tokens * (trade.price - p);
+            self.notional += tokens * p;
+        }
+        self.fills += 1;
+    }
+
+    // Cash plus what's still held at the last known price
+    fn pnl(&self, series: &BTreeMap<String, Series>) -> f64 {
+        self.cash
+            + self
+                .held
+                .iter()
+                .map(|(asset, tokens)| tokens * series.get(asset).and_then(Series::last).unwrap_or(0.0))
+                .sum::<f64>()
+    }
+
+    fn slippage_bps(&self) -> f64 {
+        if self.notional > 0.0 {
+            self.slippage_usd / self.notional * 10_000.0
+        } else {
+            0.0
+        }
+    }
+
+    fn roi(&self, pnl: f64) -> f64 {
+        if self.spent > 0.0 {
+            pnl / self.spent * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+// Usage:
+//   simulate [--days 30]              replay each trader's backfilled trades (fetch_history) at every delay
+//   simulate --delays 0,2,10,45       execution delays in seconds (0 = the trader's own fill price)
+//   simulate --usd 25                 USD per copied buy (sells mirror the trader's share sold)
+//   simulate --fidelity 5             price-history resolution in minutes (1 = finest the CLOB serves)
+//   simulate --slippage-bps 20        random extra slippage per fill, drawn from --seed (default 0, or SIM_SEED)
+//   simulate --until 1717200000       end of the window (unix secs, default now)
+//   simulate --json [file.json]       also write the result with its manifest (default strategy_factory_results/)
+// The same options, --until and stored history give the same numbers: trades are replayed in (time, tx) order,
+// every (trader, delay) run draws from its own seeded stream, and the JSON manifest carries a hash of the options
+// (configHash) and of the trades + prices used (dataHash) so two result files can be checked for comparability.
+// Fill price at T+d comes from the CLOB price history, interpolated between points - with 1-minute data
+// sub-minute delays are an estimate of the drift, not the book the order would have hit
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    if std::env::args().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let mut options = parse_options();
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let client = http_client(HttpPool::Api);
+    let since = options.until - options.days * 86_400;
+    let mut traders = options
+        .traders
+        .clone()
+        .unwrap_or_else(|| config.user_addresses.iter().map(|a| a.to_lowercase()).collect());
+    traders.sort();
+    traders.dedup();
+    options.traders = Some(traders.clone()); // The manifest records who was simulated, not just the flag
+    let max_delay = options.delays.iter().copied().max().unwrap_or(0);
+
+    let mut trades_by_trader: Vec<(String, Vec<Trade>)> = Vec::new();
+    for trader in &traders {
+        let mut trades: Vec<Trade> = db
+            .get_history_trades(trader, since)
+            .await?
+            .iter()
+            .filter_map(Trade::from_json)
+            .filter(|t| t.ts <= options.until)
+            .collect();
+        trades.sort_by(|a, b| a.ts.cmp(&b.ts).then_with(|| a.tx.cmp(&b.tx)).then_with(|| a.asset.cmp(&b.asset)));
+        if trades.is_empty() {
+            Logger::warning(&format!(
+                "No stored history for {} - run fetch_history --days {} first",
+                Logger::format_address(trader),
+                options.days
+            ));
+            continue;
+        }
+        trades_by_trader.push((trader.clone(), trades));
+    }
+    if trades_by_trader.is_empty() {
+        return Ok(());
+    }
+
+    // One price series per token over the span it was traded (plus the longest delay)
+    let mut spans: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+    for trade in trades_by_trader.iter().flat_map(|(_, t)| t) {
+        let span = spans.entry(trade.asset.clone()).or_insert((trade.ts, trade.ts));
+        span.0 = span.0.min(trade.ts);
+        span.1 = span.1.max(trade.ts);
+    }
+    Logger::info(&format!("Fetching price history for {} token(s)...", spans.len()));
+    let now = clock::now_ms() / 1000;
+    let mut series: BTreeMap<String, Series> = BTreeMap::new();
+    for (asset, (start, end)) in &spans {
+        let end = (end + max_delay + 3_600).min(now).max(*end);
+        if let Some(s) = fetch_series(&client, &config, asset, start - 3_600, end, options.fidelity_mins).await {
+            series.insert(asset.clone(), s);
+        }
+    }
+
+    // Manifest: what went in, so a result file can be reproduced and compared with others
+    let config_hash = sha256_hex(serde_json::to_string(&options)?.as_bytes());
+    let mut data = String::new();
+    for (trader, trades) in &trades_by_trader {
+        for t in trades {
+            data.push_str(&format!("{}|{}|{}|{}|{}|{}|{}\n", trader, t.ts, t.tx, t.asset, t.side, t.price, t.size));
+        }
+    }
+    for (asset, s) in &series {
+        for (t, p) in &s.0 {
+            data.push_str(&format!("{}|{}|{}\n", asset, t, p));
+        }
+    }
+    let data_hash = sha256_hex(data.as_bytes());
+    let all_ts = trades_by_trader.iter().flat_map(|(_, t)| t.iter().map(|t| t.ts));
+    let manifest = json!({
+        "tool": "simulate",
+        "version": env!("CARGO_PKG_VERSION"),
+        "seed": options.seed,
+        "configHash": config_hash,
+        "dataHash": data_hash,
+        "options": &options,
+        "dataRange": {
+            "from": since,
+            "until": options.until,
+            "firstTrade": all_ts.clone().min(),
+            "lastTrade": all_ts.max(),
+            "trades": trades_by_trader.iter().map(|(_, t)| t.len()).sum::<usize>(),
+            "tokensWithPrices": series.len(),
+            "tokens": spans.len(),
+        },
+    });
+
+    outln!(
+        "\n⏱  Copy latency vs PnL - last {} day(s), ${:.0} per buy, {} token(s) with price data\n",
+        options.days,
+        options.usd_per_buy,
+        series.len()
+    );
+    outln!(
+        "  seed {}, slippage model {} bps, config {} / data {}\n",
+        options.seed,
+        options.slippage_bps,
+        &config_hash[..12],
+        &data_hash[..12]
+    );
+    let mut result_traders: Vec<Value> = Vec::new();
+    let mut totals: Vec<(f64, u32, u32)> = vec![(0.0, 0, 0); options.delays.len()];
+    for (trader, trades) in &trades_by_trader {
+        outln!("  {} ({} trades)", Logger::format_address(trader), trades.len());
+        outln!("  {:>7} {:>7} {:>8} {:>11} {:>11} {:>10}", "DELAY", "FILLS", "SKIPPED", "SLIP (bps)", "PNL", "VS 0s");
+        let mut baseline = 0.0;
+        let mut rows: Vec<Value> = Vec::new();
+        for (i, delay) in options.delays.iter().enumerate() {
+            let mut run = Run::default();
+            let mut rng = SimRng::stream(options.seed, &format!("{}:{}", trader, delay));
+            for trade in trades {
+                // Tokens without price history are skipped at every delay so the rows stay comparable
+                let fill = series.get(&trade.asset).and_then(|s| {
+                    if *delay == 0 {
+                        Some(trade.price)
+                    } else {
+                        s.price_at(trade.ts + delay)
+                    }
+                });
+                // Drawn for every trade (filled or not) so one skip doesn't shift the noise of every later fill
+                let noise = rng.next_f64() * options.slippage_bps / 10_000.0;
+                let fill = fill.map(|p| if trade.side == "BUY" { p * (1.0 + noise) } else { p * (1.0 - noise) });
+                run.apply(trade, fill, options.usd_per_buy);
+            }
+            let pnl = run.pnl(&series);
+            if *delay == 0 {
+                baseline = pnl;
+            }
+            totals[i].0 += pnl;
+            totals[i].1 += run.fills;
+            totals[i].2 += run.skipped;
+            rows.push(json!({
+                "delaySecs": delay,
+                "fills": run.fills,
+                "skipped": run.skipped,
+                "slippageBps": run.slippage_bps(),
+                "pnl": pnl,
+                "roi": run.roi(pnl),
+                "vsInstant": pnl - baseline,
+            }));
+            outln!(
+                "  {:>6}s {:>7} {:>8} {:>+11.1} {:>+11.2} {:>+10.2}",
+                delay,
+                run.fills,
+                run.skipped,
+                run.slippage_bps(),
+                pnl,
+                pnl - baseline
+            );
+        }
+        outln!();
+        // Instant-copy row as the trader's roi / totalPnl (what aggregate ranks); every delay under "delays"
+        let (roi, pnl, fills) = (rows[0]["roi"].clone(), rows[0]["pnl"].clone(), rows[0]["fills"].clone());
+        result_traders.push(json!({
+            "address": trader,
+            "roi": roi,
+            "totalPnl": pnl,
+            "copiedTrades": fills,
+            "status": "ok",
+            "delays": rows,
+        }));
+    }
+
+    Logger::separator();
+    outln!("  ALL TRADERS");
+    let baseline = totals.first().map(|t| t.0).unwrap_or(0.0);
+    for (delay, (pnl, fills, skipped)) in options.delays.iter().zip(&totals) {
+        outln!(
+            "  {:>6}s {:>7} fills {:>6} skipped  PnL {:>+10.2}  ({:+.2} vs instant)",
+            delay,
+            fills,
+            skipped,
+            pnl,
+            pnl - baseline
+        );
+    }
+    outln!("\n  (Open positions marked at the last price in the series; fees not modelled)\n");
+
+    if let Some(path) = &options.json {
+        let path = path.clone().unwrap_or_else(|| {
+            format!("{}/simulate_{}d_seed{}_{}.json", RESULTS_DIR, options.days, options.seed, options.until)
+        });
+        // Shaped like the other strategy result files (see aggregate.rs) plus the manifest
+        let result = json!({
+            "scanDate": chrono::DateTime::from_timestamp(options.until, 0).map(|d| d.to_rfc3339()),
+            "manifest": manifest,
+            "config": {
+                "historyDays": options.days,
+                "multiplier": 1.0,
+                "minOrderSize": options.usd_per_buy,
+            },
+            "traders": result_traders,
+        });
+        if let Some(dir) = std::path::Path::new(&path).parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(&result)?)?;
+        Logger::success(&format!("Result written to {} (rerun with --until {} --seed {})", path, options.until, options.seed));
+    }
+    Ok(())
+}
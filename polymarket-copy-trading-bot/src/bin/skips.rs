@@ -0,0 +1,83 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::{utils::theme::colors, Db, EnvConfig, Logger, SkipReason};
+use std::collections::HashMap;
+
+// Usage: skips [days]  (default: all time)
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let days: Option<i64> = polymarket_term::args().next().and_then(|v| v.parse().ok());
+    let since_ts = days.map(|d| chrono::Utc::now().timestamp() - d * 86_400);
+
+    outln!();
+    outln!(
+        "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+        colors::ACCENT
+    );
+    outln!("     POLYMARKET BOT — SKIPPED TRADES");
+    outln!(
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
+        colors::RESET
+    );
+    match days {
+        Some(d) => outln!("  Window: last {} day(s)", d),
+        None => outln!("  Window: all time"),
+    }
+    outln!();
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+
+    let mut totals: HashMap<String, u64> = HashMap::new();
+    for address in &config.user_addresses {
+        let counts = db.count_skips(address, since_ts).await?;
+        let trader_total: u64 = counts.iter().map(|(_, c)| c).sum();
+
+        Logger::header(&format!(
+            "Trader {} — {} skipped",
+            Logger::format_address(address),
+            trader_total
+        ));
+        if counts.is_empty() {
+            outln!("  (no skipped trades)");
+        }
+        for (reason, count) in &counts {
+            outln!("  {:<28} {:>6}", reason, count);
+            *totals.entry(reason.clone()).or_insert(0) += count;
+        }
+        outln!();
+    }
+
+    let mut totals: Vec<(String, u64)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1));
+    let grand_total: u64 = totals.iter().map(|(_, c)| c).sum();
+
+    Logger::separator();
+    Logger::header(&format!("ALL TRADERS — {} skipped", grand_total));
+    for (reason, count) in &totals {
+        let pct = if grand_total > 0 {
+            *count as f64 / grand_total as f64 * 100.0
+        } else {
+            0.0
+        };
+        let hint = SkipReason::parse(reason).map(|r| r.hint()).unwrap_or("");
+        outln!(
+            "  {:<28} {:>6} ({:>5.1}%)  {}{}{}",
+            reason,
+            count,
+            pct,
+            colors::MUTED,
+            hint,
+            colors::RESET
+        );
+    }
+    Logger::separator();
+    outln!();
+
+    Ok(())
+}
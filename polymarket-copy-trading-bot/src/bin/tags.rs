@@ -0,0 +1,224 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::theme::colors;
+use polymarket_copy_rust::utils::{
+    fetch_wallet_positions, http_client, is_open_position, run_tag_command, HttpPool, TAG_USAGE,
+};
+use polymarket_copy_rust::{Db, EnvConfig, Logger, UserActivity, UserPosition};
+use std::collections::{BTreeSet, HashMap};
+
+// Usage:
+//   tags add|rm|note|show|list ...   manage trader / market tags and notes
+//   tags report [tag]                copied trades + PnL per tag (each market counted under one tag)
+//   tags export <file.csv> [tag]     copied trades as CSV, optionally filtered by tag
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+
+    match args.first().map(|s| s.as_str()) {
+        Some("report") => report(&config, &db, args.get(1).map(|s| s.as_str())).await,
+        Some("export") => {
+            let Some(path) = args.get(1) else {
+                anyhow::bail!("Usage: tags export <file.csv> [tag]");
+            };
+            export(&config, &db, path, args.get(2).map(|s| s.as_str())).await
+        }
+        Some(_) => {
+            match run_tag_command(&db, &args).await {
+                Ok(reply) => outln!("{}", reply),
+                Err(e) => Logger::error(&e.to_string()),
+            }
+            Ok(())
+        }
+        None => {
+            outln!("{}\nreport [tag]\nexport <file.csv> [tag]", TAG_USAGE);
+            Ok(())
+        }
+    }
+}
+
+// (trader, activity) for every copied trade, optionally only one tag
+async fn copied_trades(
+    config: &EnvConfig,
+    db: &Db,
+    tag: Option<&str>,
+) -> Result<Vec<(String, UserActivity)>> {
+    let mut out = Vec::new();
+    for address in &config.user_addresses {
+        for activity in db.find_copied_activities(address, tag).await? {
+            out.push((address.clone(), activity));
+        }
+    }
+    Ok(out)
+}
+
+// A market whose copied trades carry several tags belongs to one of them, so per-tag PnL adds up to the
+// total: the first tag of the earliest tagged trade in it
+fn owner_tags(trades: &[(String, UserActivity)]) -> HashMap<String, String> {
+    let mut earliest: HashMap<String, (i64, String)> = HashMap::new();
+    for (_, a) in trades {
+        let (Some(asset), Some(tag)) = (a.asset.as_ref(), a.tags.as_ref().and_then(|t| t.first())) else {
+            continue;
+        };
+        let ts = a.timestamp.unwrap_or(i64::MAX);
+        if earliest.get(asset).is_none_or(|(seen, _)| ts < *seen) {
+            earliest.insert(asset.clone(), (ts, tag.clone()));
+        }
+    }
+    earliest.into_iter().map(|(asset, (_, tag))| (asset, tag)).collect()
+}
+
+async fn report(config: &EnvConfig, db: &Db, tag: Option<&str>) -> Result<()> {
+    outln!();
+    outln!(
+        "{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
+        colors::ACCENT
+    );
+    outln!("     POLYMARKET BOT — PnL BY TAG");
+    outln!(
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}",
+        colors::RESET
+    );
+    outln!();
+
+    let trades = copied_trades(config, db, tag).await?;
+    let tags: BTreeSet<String> = match tag {
+        Some(t) => [t.trim().to_lowercase()].into_iter().collect(),
+        None => trades
+            .iter()
+            .flat_map(|(_, a)| a.tags.clone().unwrap_or_default())
+            .collect(),
+    };
+    if tags.is_empty() {
+        outln!("  (no tagged trades yet - tags are stamped on trades copied after tagging)");
+        outln!();
+        return Ok(());
+    }
+
+    // Our positions (open and closed), keyed by asset (PnL = unrealized + realized)
+    let http_client = http_client(HttpPool::Api);
+    let positions: HashMap<String, UserPosition> = fetch_wallet_positions(config, &http_client, &config.proxy_wallet)
+        .await?
+        .into_iter()
+        .filter_map(|p| p.asset.clone().map(|a| (a, p)))
+        .collect();
+
+    // One tag asked for: every market it touches. All tags: each market under its owner tag only
+    let (owners, by_owner) = (owner_tags(&trades), tags.len() > 1);
+    let mut shared = 0;
+    for tag in &tags {
+        let tagged: Vec<&UserActivity> = trades
+            .iter()
+            .map(|(_, a)| a)
+            .filter(|a| {
+                let has_tags = a.tags.as_ref().is_some_and(|t| !t.is_empty());
+                if by_owner {
+                    has_tags && a.asset.as_ref().and_then(|asset| owners.get(asset)) == Some(tag)
+                } else {
+                    a.tags.as_ref().is_some_and(|t| t.contains(tag))
+                }
+            })
+            .collect();
+        let buys = tagged.iter().filter(|a| a.side_buy()).count();
+        let sells = tagged.len() - buys;
+        let assets: BTreeSet<&str> = tagged.iter().filter_map(|a| a.asset.as_deref()).collect();
+        if !by_owner {
+            shared = tagged
+                .iter()
+                .filter(|a| a.tags.as_ref().is_some_and(|t| t.iter().any(|t| t != tag)))
+                .filter_map(|a| a.asset.as_deref())
+                .collect::<BTreeSet<&str>>()
+                .len();
+        }
+
+        let mut invested = 0.0;
+        let mut pnl = 0.0;
+        let mut open = 0;
+        for asset in &assets {
+            if let Some(p) = positions.get(*asset) {
+                invested += p.initial_value.unwrap_or(0.0);
+                pnl += p.cash_pnl.unwrap_or(0.0) + p.realized_pnl.unwrap_or(0.0);
+                if is_open_position(p) {
+                    open += 1;
+                }
+            }
+        }
+
+        Logger::header(&format!("Tag {}", tag));
+        outln!("  Copied trades:  {} ({} buys / {} sells)", tagged.len(), buys, sells);
+        outln!("  Markets:        {} ({} open)", assets.len(), open);
+        outln!("  Invested:       {}", Logger::money(invested));
+        outln!("  PnL:            {}", Logger::money(pnl));
+        outln!();
+    }
+
+    if tags.len() > 1 {
+        outln!(
+            "  {}A market with trades under several tags is counted once, under the first tag of its earliest copied trade{}",
+            colors::MUTED,
+            colors::RESET
+        );
+    } else if shared > 0 {
+        outln!(
+            "  {}{} of these markets also carry other tags - their PnL shows up in those tags' reports too{}",
+            colors::MUTED,
+            shared,
+            colors::RESET
+        );
+    }
+    outln!(
+        "  {}PnL comes from your open and closed positions (data-api /positions and /closed-positions){}",
+        colors::MUTED,
+        colors::RESET
+    );
+    outln!();
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+async fn export(config: &EnvConfig, db: &Db, path: &str, tag: Option<&str>) -> Result<()> {
+    let trades = copied_trades(config, db, tag).await?;
+
+    let mut csv = String::from(
+        "timestamp,trader,side,title,outcome,price,trader_usdc,my_bought_size,tags,transaction_hash\n",
+    );
+    for (trader, a) in &trades {
+        let row = [
+            a.timestamp.unwrap_or(0).to_string(),
+            trader.clone(),
+            a.side.clone().unwrap_or_default(),
+            a.title.clone().unwrap_or_default(),
+            a.outcome.clone().unwrap_or_default(),
+            format!("{:.4}", a.price.unwrap_or(0.0)),
+            format!("{:.2}", a.usdc_size.unwrap_or(0.0)),
+            format!("{:.2}", a.my_bought_size.unwrap_or(0.0)),
+            a.tags.clone().unwrap_or_default().join(";"),
+            a.transaction_hash.clone().unwrap_or_default(),
+        ];
+        csv.push_str(&row.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+
+    tokio::fs::write(path, csv).await?;
+    Logger::success(&format!(
+        "Exported {} trade(s){} to {}",
+        trades.len(),
+        tag.map(|t| format!(" tagged '{}'", t)).unwrap_or_default(),
+        path
+    ));
+    Ok(())
+}
@@ -1,3 +1,6 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -6,11 +9,23 @@ use teloxide::prelude::*;
 use teloxide::types::{BotCommand, InlineKeyboardButton, InlineKeyboardMarkup, MessageId};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::{Command as TokioCommand, Child as TokioChild};
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
+use tokio::task::JoinHandle;
 use std::process::Stdio;
+use polymarket_copy_rust::{CopyEngine, EngineEvent, EnvConfig};
 
 type ProcessMap = Arc<Mutex<HashMap<String, (TokioChild, MessageId)>>>;
 
+// The copy engine runs inside this process (one per process, see README "Embedding"), owned by the chat that started it
+struct RunningEngine {
+    chat_id: ChatId,
+    msg_id: MessageId, // Status message: log tail + Stop / Status buttons
+    engine: CopyEngine,
+    forwarder: JoinHandle<()>,
+}
+
+type EngineSlot = Arc<Mutex<Option<RunningEngine>>>;
+
 fn get_user_config_path(user_id: i64) -> PathBuf {
     PathBuf::from("users").join(format!("{}", user_id))
 }
@@ -139,24 +154,27 @@ fn format_env_file(vars: &HashMap<String, String>) -> String {
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
     
     let bot_token = std::env::var("TELEGRAM_BOT_TOKEN")
         .expect("TELEGRAM_BOT_TOKEN environment variable is required. Get it from @BotFather on Telegram.");
     
     let bot = Bot::new(bot_token);
     let processes: ProcessMap = Arc::new(Mutex::new(HashMap::new()));
+    let engine: EngineSlot = Arc::new(Mutex::new(None));
     
-    println!("🤖 Telegram bot starting...");
+    outln!("🤖 Telegram bot starting...");
     
     let commands = vec![
         BotCommand::new("start", "get started with the bot"),
         BotCommand::new("menu", "view main menu"),
         BotCommand::new("help", "tips and frequently asked questions"),
+        BotCommand::new("status", "state of the running copy bot"),
     ];
     if let Err(e) = bot.set_my_commands(commands).await {
-        eprintln!("⚠️ Failed to set bot commands: {}", e);
+        eoutln!("⚠️ Failed to set bot commands: {}", e);
     } else {
-        println!("✅ Bot commands menu set successfully");
+        outln!("✅ Bot commands menu set successfully");
     }
     
     let handler = dptree::entry()
@@ -164,14 +182,20 @@ async fn main() {
         .branch(Update::filter_callback_query().endpoint(handle_callback_query));
     
     Dispatcher::builder(bot, handler)
-        .dependencies(dptree::deps![processes])
+        .dependencies(dptree::deps![processes, engine.clone()])
         .enable_ctrlc_handler()
         .build()
         .dispatch()
         .await;
+
+    // Ctrl+C: let the copy engine flush and close its DB like `cargo run` does
+    if let Some(running) = engine.lock().await.take() {
+        running.engine.stop().await;
+        let _ = running.forwarder.await;
+    }
 }
 
-async fn handle_message_command(bot: Bot, msg: Message) -> ResponseResult<()> {
+async fn handle_message_command(bot: Bot, msg: Message, engine: EngineSlot) -> ResponseResult<()> {
     if let Some(text) = msg.text() {
         match text {
             "/start" | "/menu" => {
@@ -179,18 +203,18 @@ async fn handle_message_command(bot: Bot, msg: Message) -> ResponseResult<()> {
                 let username = msg.chat.username().map(|s| s.to_string()).unwrap_or_else(|| "N/A".to_string());
                 let first_name = msg.chat.first_name().map(|s| s.to_string()).unwrap_or_else(|| "N/A".to_string());
                 
-                println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-                println!("👤 User Started Bot");
-                println!("   User ID: {}", user_id);
-                println!("   Username: @{}", username);
-                println!("   Name: {}", first_name);
-                println!("   Config: users/{}", user_id);
-                println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                outln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                outln!("👤 User Started Bot");
+                outln!("   User ID: {}", user_id);
+                outln!("   Username: @{}", username);
+                outln!("   Name: {}", first_name);
+                outln!("   Config: users/{}", user_id);
+                outln!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                 
                 if let Err(e) = initialize_user_config(user_id) {
-                    eprintln!("⚠️ Failed to initialize user config for {}: {}", user_id, e);
+                    eoutln!("⚠️ Failed to initialize user config for {}: {}", user_id, e);
                 } else {
-                    println!("✅ User config file initialized: users/{}", user_id);
+                    outln!("✅ User config file initialized: users/{}", user_id);
                 }
                 
                 send_main_menu(&bot, msg.chat.id).await?;
@@ -198,9 +222,21 @@ async fn handle_message_command(bot: Bot, msg: Message) -> ResponseResult<()> {
             "/help" => {
                 handle_help_command(&bot, msg.chat.id).await?;
             }
+            "/status" => {
+                handle_engine_status(&bot, msg.chat.id, engine).await?;
+            }
             cmd if cmd.starts_with("/set ") => {
                 handle_set_command(&bot, msg.chat.id, cmd, Some(msg.id)).await?;
             }
+            cmd if cmd == "/tag" || cmd.starts_with("/tag ") => {
+                handle_tag_command(&bot, msg.chat.id, cmd).await?;
+            }
+            cmd if cmd == "/override" || cmd.starts_with("/override ") => {
+                handle_override_command(&bot, msg.chat.id, cmd).await?;
+            }
+            cmd if cmd == "/logs" || cmd.starts_with("/logs ") => {
+                handle_logs_command(&bot, msg.chat.id, cmd).await?;
+            }
             _ => {
                 bot.send_message(msg.chat.id, "Use /start or /menu to see the main menu.")
                     .await?;
@@ -214,6 +250,7 @@ async fn handle_callback_query(
     bot: Bot,
     q: CallbackQuery,
     processes: ProcessMap,
+    engine: EngineSlot,
 ) -> ResponseResult<()> {
     if let Some(data) = q.data {
         if let Some(msg) = q.message {
@@ -311,7 +348,13 @@ async fn handle_callback_query(
                     handle_run_binary(&bot, chat_id, "audit_old", processes.clone(), msg.id).await?;
                 }
                 "run_main_bot" => {
-                    handle_run_binary(&bot, chat_id, "polymarket-copy-rust", processes.clone(), msg.id).await?;
+                    handle_run_engine(&bot, chat_id, engine.clone(), msg.id).await?;
+                }
+                "engine_status" => {
+                    handle_engine_status(&bot, chat_id, engine.clone()).await?;
+                }
+                "stop_engine" => {
+                    handle_stop_engine(&bot, chat_id, engine.clone()).await?;
                 }
                 "more_commands" => {
                     bot.answer_callback_query(q.id.clone())
@@ -399,6 +442,11 @@ async fn handle_help_command(bot: &Bot, chat_id: ChatId) -> ResponseResult<()> {
 • `/start` or `/menu` \- Open main menu
 • `/help` \- Show this help message
 • `/set VAR_NAME value` \- Set an environment variable
+• `/override set COPY_SIZE 5` \- Change a running bot \(also `list`, `clear KEY`; `TRADING_PAUSED true` pauses copying\)
+• `/override pause\-market 0x\.\.\.` \- Stop copying one market \(also `resume\-market`, `close\-market` sells it, `resize\-market 0x\.\.\. 25` caps it\)
+• `/tag add trader 0x\.\.\. experiment\-a` \- Tag a trader or market \(also `rm`, `note`, `show`, `list`\)
+• `/logs 50 error,trade` \- Last lines the running bot logged, optionally only some levels
+• `/status` \- Traders, pause flags, balance and resources of the bot started from this chat
 
 *Environment Variables:*
 • `USER_ADDRESSES` \- Comma\-separated trader addresses to copy
@@ -739,6 +787,74 @@ async fn handle_set_command(bot: &Bot, chat_id: ChatId, cmd: &str, msg_id: Optio
     Ok(())
 }
 
+// /tag add|rm|note|show|list ... - same commands as the `tags` CLI, against the user's MONGO_URI
+async fn handle_tag_command(bot: &Bot, chat_id: ChatId, cmd: &str) -> ResponseResult<()> {
+    let args: Vec<String> = cmd.split_whitespace().skip(1).map(|s| s.to_string()).collect();
+    if args.is_empty() {
+        bot.send_message(chat_id, format!("Usage:\n{}", polymarket_copy_rust::utils::TAG_USAGE))
+            .await?;
+        return Ok(());
+    }
+
+    let reply = match polymarket_copy_rust::Db::connect(&user_mongo_uri(chat_id.0)).await {
+        Ok(db) => match polymarket_copy_rust::utils::run_tag_command(&db, &args).await {
+            Ok(text) => format!("🏷️ {}", text),
+            Err(e) => format!("❌ {}", e),
+        },
+        Err(e) => format!("❌ Failed to connect to MongoDB: {}", e),
+    };
+    bot.send_message(chat_id, reply).await?;
+    Ok(())
+}
+
+// /override list|set KEY value|clear KEY|pause-market|close-market|resize-market ... - changes a running bot via the configs collection
+async fn handle_override_command(bot: &Bot, chat_id: ChatId, cmd: &str) -> ResponseResult<()> {
+    let args: Vec<String> = cmd.split_whitespace().skip(1).map(|s| s.to_string()).collect();
+    let reply = match polymarket_copy_rust::Db::connect(&user_mongo_uri(chat_id.0)).await {
+        Ok(db) => match polymarket_copy_rust::utils::run_override_command(&db, &args).await {
+            Ok(text) => format!("⚙️ {}", text),
+            Err(e) => format!("❌ {}", e),
+        },
+        Err(e) => format!("❌ Failed to connect to MongoDB: {}", e),
+    };
+    bot.send_message(chat_id, reply).await?;
+    Ok(())
+}
+
+// /logs [N] [level,...] - replay the last lines a running bot logged ("log_events" in the user's MONGO_URI)
+async fn handle_logs_command(bot: &Bot, chat_id: ChatId, cmd: &str) -> ResponseResult<()> {
+    // Telegram caps a message at 4096 chars - keep the newest lines that fit
+    const MAX_REPLY_CHARS: usize = 4000;
+    let args: Vec<String> = cmd.split_whitespace().skip(1).map(|s| s.to_string()).collect();
+    let reply = match polymarket_copy_rust::Db::connect(&user_mongo_uri(chat_id.0)).await {
+        Ok(db) => match polymarket_copy_rust::utils::run_logs_command(&db, &args).await {
+            Ok(text) if text.len() > MAX_REPLY_CHARS => {
+                let mut start = text.len() - MAX_REPLY_CHARS;
+                while !text.is_char_boundary(start) {
+                    start += 1;
+                }
+                let tail = &text[start..];
+                format!("…{}", tail.split_once('\n').map(|(_, rest)| rest).unwrap_or(tail))
+            }
+            Ok(text) => text,
+            Err(e) => format!("❌ {}\nUsage:\n{}", e, polymarket_copy_rust::utils::LOGS_USAGE),
+        },
+        Err(e) => format!("❌ Failed to connect to MongoDB: {}", e),
+    };
+    bot.send_message(chat_id, reply).await?;
+    Ok(())
+}
+
+// MONGO_URI from the user's config file, then env, then the local default
+fn user_mongo_uri(user_id: i64) -> String {
+    parse_env_file(&read_user_config(user_id))
+        .get("MONGO_URI")
+        .cloned()
+        .filter(|v| !v.is_empty())
+        .or_else(|| std::env::var("MONGO_URI").ok())
+        .unwrap_or_else(|| "mongodb://localhost:27017/polymarket_copytrading".to_string())
+}
+
 async fn handle_validate_setup(
     bot: &Bot,
     chat_id: ChatId,
@@ -1153,6 +1269,232 @@ async fn handle_stop_process(
     Ok(())
 }
 
+// EnvConfig reads the process environment: apply the user's file for the load, then put the previous values back
+async fn load_user_engine_config(user_id: i64) -> anyhow::Result<EnvConfig> {
+    let vars = parse_env_file(&read_user_config(user_id));
+    let previous: Vec<(String, Option<String>)> =
+        vars.keys().map(|k| (k.clone(), std::env::var(k).ok())).collect();
+    for (key, value) in &vars {
+        std::env::set_var(key, value);
+    }
+    let config = EnvConfig::from_env().await;
+    for (key, value) in previous {
+        match value {
+            Some(value) => std::env::set_var(&key, value),
+            None => std::env::remove_var(&key),
+        }
+    }
+    config
+}
+
+fn engine_keyboard() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("📊 Status", "engine_status"),
+        InlineKeyboardButton::callback("🛑 Stop", "stop_engine"),
+    ]])
+}
+
+async fn handle_run_engine(
+    bot: &Bot,
+    chat_id: ChatId,
+    engine: EngineSlot,
+    callback_msg_id: MessageId,
+) -> ResponseResult<()> {
+    // Held through startup so a second Run can't race this one
+    let mut slot = engine.lock().await;
+    if let Some(running) = slot.as_ref() {
+        let text = if running.chat_id == chat_id {
+            "⚠️ The copy bot is already running! Use /status or the Stop button."
+        } else {
+            "⚠️ Another user's copy bot is running in this process - only one can run at a time."
+        };
+        bot.edit_message_text(chat_id, callback_msg_id, text).await?;
+        return Ok(());
+    }
+
+    if let Err(e) = initialize_user_config(chat_id.0) {
+        bot.edit_message_text(chat_id, callback_msg_id, format!("❌ Failed to initialize your config file: {}", e))
+            .await?;
+        return Ok(());
+    }
+    let config = match load_user_engine_config(chat_id.0).await {
+        Ok(config) => config,
+        Err(e) => {
+            bot.edit_message_text(chat_id, callback_msg_id, format!("❌ Config error: {}", e)).await?;
+            return Ok(());
+        }
+    };
+    bot.edit_message_text(chat_id, callback_msg_id, "🔄 Starting the copy bot (chain check, DB self-test, system check)...")
+        .await?;
+
+    // Subscribed before start() so the startup lines reach the chat too
+    let events = polymarket_copy_rust::subscribe();
+    let forward_alerts = config.alert_chat_id.as_deref() != Some(chat_id.0.to_string().as_str());
+    match CopyEngine::start(config).await {
+        Ok(started) => {
+            let forwarder = tokio::spawn(forward_engine_events(bot.clone(), chat_id, callback_msg_id, forward_alerts, events));
+            *slot = Some(RunningEngine { chat_id, msg_id: callback_msg_id, engine: started, forwarder });
+            bot.edit_message_text(chat_id, callback_msg_id, "🔄 *Copy bot* is running\\.\\.\\.")
+                .reply_markup(engine_keyboard())
+                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                .await?;
+        }
+        Err(e) => {
+            let keyboard = InlineKeyboardMarkup::new(vec![vec![
+                InlineKeyboardButton::callback("◀️ Back to Menu", "back_to_menu")
+            ]]);
+            bot.edit_message_text(chat_id, callback_msg_id, format!("❌ The copy bot failed to start: {}", e))
+                .reply_markup(keyboard)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+fn engine_log_text(tail: &std::collections::VecDeque<String>) -> String {
+    let log: Vec<&str> = tail.iter().map(|s| s.as_str()).collect();
+    let formatted = format_output_with_emoji(&log.join("\n"), None);
+    format!("🔄 *Copy bot* is running\\.\\.\\.\n\n```\n{}\n```", escape_markdown(&formatted))
+}
+
+// Engine events -> chat: the log tail in the status message, orders and alerts as their own messages
+async fn forward_engine_events(
+    bot: Bot,
+    chat_id: ChatId,
+    msg_id: MessageId,
+    forward_alerts: bool, // false when ALERT_CHAT_ID already routes alerts to this chat
+    mut events: broadcast::Receiver<EngineEvent>,
+) {
+    const TAIL_LINES: usize = 30;
+    let update_interval = std::time::Duration::from_secs(2);
+    let mut tail: std::collections::VecDeque<String> = std::collections::VecDeque::new();
+    let mut last_update = std::time::Instant::now();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        match event {
+            EngineEvent::Log(line) => {
+                tail.push_back(format!("{}: {}", line.level, line.message));
+                if tail.len() > TAIL_LINES {
+                    tail.pop_front();
+                }
+                if last_update.elapsed() >= update_interval {
+                    let mut text = engine_log_text(&tail);
+                    // Telegram caps a message at 4096 chars - drop the oldest lines until it fits
+                    while text.len() > 4096 && tail.len() > 1 {
+                        tail.pop_front();
+                        text = engine_log_text(&tail);
+                    }
+                    let _ = bot
+                        .edit_message_text(chat_id, msg_id, text)
+                        .reply_markup(engine_keyboard())
+                        .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                        .await;
+                    last_update = std::time::Instant::now();
+                }
+            }
+            EngineEvent::Alert(alert) if forward_alerts => {
+                let _ = bot
+                    .send_message(chat_id, format!("{} {}: {}", alert.severity.icon(), alert.kind.as_str(), alert.message))
+                    .await;
+            }
+            EngineEvent::Order(order) => {
+                let _ = bot
+                    .send_message(
+                        chat_id,
+                        format!(
+                            "📝 Copied {} from {} in {} ({} ms)",
+                            order.side,
+                            polymarket_copy_rust::Logger::format_address(&order.trader),
+                            polymarket_copy_rust::Logger::format_address(&order.condition_id),
+                            order.finished_at - order.started_at
+                        ),
+                    )
+                    .await;
+            }
+            EngineEvent::Stopped => break,
+            _ => {}
+        }
+    }
+}
+
+async fn handle_engine_status(bot: &Bot, chat_id: ChatId, engine: EngineSlot) -> ResponseResult<()> {
+    let slot = engine.lock().await;
+    let running = match slot.as_ref() {
+        Some(running) if running.chat_id == chat_id => running,
+        _ => {
+            bot.send_message(chat_id, "The copy bot is not running from this chat. Start it from /menu.").await?;
+            return Ok(());
+        }
+    };
+    let state = running.engine.state().await;
+    drop(slot);
+
+    let mut lines = vec![
+        format!("Running since: {}", chrono::DateTime::from_timestamp_millis(state.started_at).map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string()).unwrap_or_default()),
+        format!("Proxy wallet: {}", state.proxy_wallet),
+        format!("Traders: {}", state.traders.len()),
+        format!("Trading paused: {}", if state.trading_paused { "yes" } else { "no" }),
+    ];
+    if !state.paused_traders.is_empty() {
+        lines.push(format!("Paused traders: {}", state.paused_traders.join(", ")));
+    }
+    if !state.overrides.is_empty() {
+        let overrides: Vec<String> = state.overrides.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        lines.push(format!("Overrides: {}", overrides.join(", ")));
+    }
+    lines.push(match state.usdc_balance {
+        Some(balance) => format!("USDC balance: ${:.2}", balance),
+        None => "USDC balance: unavailable".to_string(),
+    });
+    if state.volatility_haircut < 1.0 {
+        lines.push(format!("Volatility haircut: x{:.2}", state.volatility_haircut));
+    }
+    if state.drawdown_scale < 1.0 {
+        lines.push(format!("Drawdown scale: x{:.2}", state.drawdown_scale));
+    }
+    if let Some(r) = state.resources {
+        lines.push(format!("Trade backlog: {}", r.trade_backlog));
+        if let Some(rss) = r.rss_mb {
+            lines.push(format!("Memory: {:.0} MB", rss));
+        }
+    }
+    bot.send_message(chat_id, lines.join("\n")).await?;
+    Ok(())
+}
+
+async fn handle_stop_engine(bot: &Bot, chat_id: ChatId, engine: EngineSlot) -> ResponseResult<()> {
+    let running = {
+        let mut slot = engine.lock().await;
+        match slot.as_ref() {
+            Some(running) if running.chat_id == chat_id => slot.take(),
+            _ => None,
+        }
+    };
+    let Some(running) = running else {
+        return Ok(());
+    };
+
+    let _ = bot
+        .edit_message_text(chat_id, running.msg_id, "⏳ Stopping the copy bot (flushing buffered trades)...")
+        .await;
+    running.engine.stop().await;
+    let _ = running.forwarder.await;
+
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("◀️ Back to Menu", "back_to_menu")
+    ]]);
+    let _ = bot.edit_message_text(chat_id, running.msg_id, "🛑 *Copy bot* stopped by user\\.")
+        .reply_markup(keyboard)
+        .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+        .await;
+    Ok(())
+}
+
 fn escape_markdown(text: &str) -> String {
     text.replace('*', "\\*")
         .replace('_', "\\_")
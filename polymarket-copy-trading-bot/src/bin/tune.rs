@@ -0,0 +1,48 @@
+#[macro_use]
+extern crate polymarket_term;
+
+use anyhow::Result;
+use polymarket_copy_rust::utils::{build_tuning_report, http_client, HttpPool};
+use polymarket_copy_rust::config::effective_config;
+use polymarket_copy_rust::services::refresh_overrides;
+use polymarket_copy_rust::{Db, EnvConfig, Logger};
+
+const USAGE: &str = "Usage: tune [--days N] [--json]";
+
+// Usage:
+//   tune [--days 7]   suggested config changes from stored bot orders, wallet fills and skip reasons
+//   tune --json       the suggestions as JSON (same rows the weekly digest carries)
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init();
+
+    let args: Vec<String> = polymarket_term::args().collect();
+    if args.iter().any(|a| a == "--help" || a == "help") {
+        outln!("{}", USAGE);
+        return Ok(());
+    }
+    let days: i64 = args
+        .iter()
+        .position(|a| a == "--days")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7);
+
+    let config = EnvConfig::from_env().await?;
+    let db = Db::open(&config).await?;
+    let client = http_client(HttpPool::Api);
+    // Suggestions compare against what the running bot uses, DB overrides included
+    if let Err(e) = refresh_overrides(&db).await {
+        Logger::warning(&format!("Could not load config overrides: {}", e));
+    }
+    let config = effective_config(&config);
+
+    let report = build_tuning_report(&config, &db, &client, days).await?;
+    if args.iter().any(|a| a == "--json") {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        outln!("\n{}\n", report.to_text());
+    }
+    Ok(())
+}
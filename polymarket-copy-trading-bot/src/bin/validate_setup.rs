@@ -1,13 +1,17 @@
+#[macro_use]
+extern crate polymarket_term;
+
 use anyhow::Result;
 use polymarket_copy_rust::{utils::theme::colors, EnvConfig, Logger};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    polymarket_term::init();
 
     match EnvConfig::from_env().await {
         Ok(config) => {
-            println!(
+            outln!(
                 "{} Configuration OK: {} trader(s), vault {}",
                 colors::SUCCESS,
                 config.user_addresses.len(),
@@ -16,7 +20,7 @@ async fn main() -> Result<()> {
             Ok(())
         }
         Err(e) => {
-            eprintln!("{} Configuration error: {}", colors::ERROR, e);
+            eoutln!("{} Configuration error: {}", colors::ERROR, e);
             Err(e)
         }
     }
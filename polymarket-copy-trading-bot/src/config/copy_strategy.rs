@@ -31,6 +31,28 @@ pub struct CopyStrategyConfig {
     pub adaptive_threshold: Option<f64>,
     pub tiered_multipliers: Option<Vec<MultiplierTier>>,
     pub trade_multiplier: Option<f64>,
+    pub max_depth_percent: Option<f64>,
+    pub min_volume_24h_usd: Option<f64>,
+    pub depth_price_range: f64,
+    pub trailing_stop_percent: Option<f64>, // Sell when the bid falls this % below the post-entry high (None = off)
+    pub volatility_haircut: f64, // Size multiplier while the volatility regime is turbulent (1.0 = none; set per trade, not from env)
+    pub drawdown_scale: f64, // Size multiplier from my equity drawdown (1.0 = none; set per trade, not from env)
+}
+
+// Market liquidity snapshot used for scaling (depth = USD within depth_price_range of best)
+#[derive(Debug, Clone, Copy)]
+pub struct MarketLiquidity {
+    pub depth_usd: f64,
+    pub volume_24h_usd: Option<f64>,
+}
+
+// Expected cost of crossing the spread + fee vs expected edge for one copy (all USD)
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionCost {
+    pub spread_cost_usd: f64,    // Paid above mid by buying at the ask
+    pub fee_usd: f64,            // Taker fee on the notional
+    pub expected_edge_usd: f64,  // Trader's historical return on capital x order size
+    pub expected_value_usd: f64, // Edge minus costs
 }
 
 // Result of order size calculation (with reasoning)
@@ -43,7 +65,9 @@ pub struct OrderSizeCalculation {
     pub capped_by_max: bool,
     pub reduced_by_balance: bool,
     pub below_minimum: bool,
+    pub reduced_by_liquidity: bool,
     pub reasoning: String,
+    pub cost: Option<ExecutionCost>,
 }
 
 // Linear interpolation helper
@@ -89,12 +113,52 @@ pub fn get_trade_multiplier(config: &CopyStrategyConfig, trader_order_size: f64)
     config.trade_multiplier.unwrap_or(1.0)
 }
 
-// Calculate order size based on strategy, limits, & balance
+// Scale down in illiquid markets: cap at % of visible depth, shrink below 24h volume floor
+// Returns (new amount, reasoning suffix) - None when nothing changed
+fn scale_by_liquidity(
+    config: &CopyStrategyConfig,
+    amount: f64,
+    liquidity: &MarketLiquidity,
+) -> Option<(f64, String)> {
+    let mut scaled = amount;
+    let mut notes = Vec::new();
+
+    if let (Some(min_vol), Some(vol)) = (config.min_volume_24h_usd, liquidity.volume_24h_usd) {
+        if min_vol > 0.0 && vol < min_vol {
+            let factor = (vol / min_vol).clamp(0.0, 1.0);
+            scaled *= factor;
+            notes.push(format!(
+                "24h volume ${:.0} < ${:.0}: x{:.2}",
+                vol, min_vol, factor
+            ));
+        }
+    }
+
+    if let Some(pct) = config.max_depth_percent {
+        let cap = liquidity.depth_usd * pct / 100.0;
+        if scaled > cap {
+            scaled = cap;
+            notes.push(format!(
+                "{}% of ${:.2} visible depth = ${:.2}",
+                pct, liquidity.depth_usd, cap
+            ));
+        }
+    }
+
+    if notes.is_empty() {
+        None
+    } else {
+        Some((scaled, format!(" → Liquidity: {}", notes.join(", "))))
+    }
+}
+
+// Calculate order size based on strategy, limits, liquidity & balance
 pub fn calculate_order_size(
     config: &CopyStrategyConfig,
     trader_order_size: f64,
     available_balance: f64,
     current_position_size: f64,
+    liquidity: Option<&MarketLiquidity>,
 ) -> OrderSizeCalculation {
     // Calc base amount based on strategy
     let (base_amount, strategy, mut reasoning) = match config.strategy {
@@ -135,6 +199,7 @@ pub fn calculate_order_size(
     let mut capped_by_max = false;
     let mut reduced_by_balance = false;
     let mut below_minimum = false;
+    let mut reduced_by_liquidity = false;
 
     if final_amount > config.max_order_size_usd {
         final_amount = config.max_order_size_usd;
@@ -142,6 +207,34 @@ pub fn calculate_order_size(
         reasoning.push_str(&format!(" → Capped at max ${}", config.max_order_size_usd));
     }
 
+    if let Some((scaled, note)) = liquidity.and_then(|l| scale_by_liquidity(config, final_amount, l)) {
+        final_amount = scaled;
+        reduced_by_liquidity = true;
+        reasoning.push_str(&note);
+    }
+
+    // Turbulent markets: shrink every copy until things calm down (VOL_REGIME_SOURCE) - after the max / liquidity
+    // clamps, so a capped order is still cut
+    if config.volatility_haircut < 1.0 {
+        let before = final_amount;
+        final_amount *= config.volatility_haircut.max(0.0);
+        reasoning.push_str(&format!(
+            " → {}x volatility haircut: ${:.2} → ${:.2}",
+            config.volatility_haircut, before, final_amount
+        ));
+    }
+
+    // Equity below its recent high: copy smaller until it recovers (DRAWDOWN_LEVELS) - also after the clamps
+    let drawdown_scaled = config.drawdown_scale < 1.0;
+    if drawdown_scaled {
+        let before = final_amount;
+        final_amount *= config.drawdown_scale.max(0.0);
+        reasoning.push_str(&format!(
+            " → {}x drawdown scale: ${:.2} → ${:.2}",
+            config.drawdown_scale, before, final_amount
+        ));
+    }
+
     if let Some(max_pos) = config.max_position_size_usd {
         let new_total = current_position_size + final_amount;
         if new_total > max_pos {
@@ -168,8 +261,17 @@ pub fn calculate_order_size(
 
     if final_amount < config.min_order_size_usd {
         below_minimum = true;
-        reasoning.push_str(&format!(" → Below minimum ${}", config.min_order_size_usd));
-        final_amount = config.min_order_size_usd;
+        if drawdown_scaled {
+            // Bumping back up to the minimum would undo the drawdown scale - skip the trade instead
+            reasoning.push_str(&format!(
+                " → Below minimum ${} after drawdown scale - skipped",
+                config.min_order_size_usd
+            ));
+            final_amount = 0.0;
+        } else {
+            reasoning.push_str(&format!(" → Below minimum ${}", config.min_order_size_usd));
+            final_amount = config.min_order_size_usd;
+        }
     }
 
     OrderSizeCalculation {
@@ -180,8 +282,52 @@ pub fn calculate_order_size(
         capped_by_max,
         reduced_by_balance,
         below_minimum,
+        reduced_by_liquidity,
         reasoning,
+        cost: None,
+    }
+}
+
+// Attach the cost model to a sized order: spread (mid -> ask) + fee vs trader alpha
+// (no trader history = zero edge, so EV is just minus the costs)
+pub fn estimate_execution_cost(
+    calc: &mut OrderSizeCalculation,
+    best_bid: f64,
+    best_ask: f64,
+    fee_rate: f64,
+    trader_alpha: Option<f64>,
+) {
+    if best_ask <= 0.0 || calc.final_amount <= 0.0 {
+        return;
     }
+    let mid = if best_bid > 0.0 && best_bid < best_ask {
+        (best_bid + best_ask) / 2.0
+    } else {
+        best_ask
+    };
+    let amount = calc.final_amount;
+    let spread_cost_usd = amount * (1.0 - mid / best_ask);
+    let fee_usd = amount * fee_rate;
+    let expected_edge_usd = amount * trader_alpha.unwrap_or(0.0);
+    let expected_value_usd = expected_edge_usd - spread_cost_usd - fee_usd;
+
+    calc.reasoning.push_str(&format!(
+        " → Cost: spread ${:.3} + fee ${:.3} vs edge ${:.3}{} = EV ${:.3}",
+        spread_cost_usd,
+        fee_usd,
+        expected_edge_usd,
+        match trader_alpha {
+            Some(a) => format!(" ({:+.1}% alpha)", a * 100.0),
+            None => " (no history)".to_string(),
+        },
+        expected_value_usd
+    ));
+    calc.cost = Some(ExecutionCost {
+        spread_cost_usd,
+        fee_usd,
+        expected_edge_usd,
+        expected_value_usd,
+    });
 }
 
 pub fn parse_tiered_multipliers(tiers_str: &str) -> Result<Vec<MultiplierTier>> {
@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+// How a copy order hits the book: cross the spread (taker) or rest one tick inside it (maker)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionPolicy {
+    Taker,
+    Maker,
+}
+
+impl ExecutionPolicy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_uppercase().as_str() {
+            "TAKER" => Some(ExecutionPolicy::Taker),
+            "MAKER" => Some(ExecutionPolicy::Maker),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionPolicy::Taker => "TAKER",
+            ExecutionPolicy::Maker => "MAKER",
+        }
+    }
+}
+
+// Market liquidity class (from USD depth on the side we would take)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiquidityClass {
+    Thin,
+    Normal,
+    Deep,
+}
+
+impl LiquidityClass {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_uppercase().as_str() {
+            "THIN" => Some(LiquidityClass::Thin),
+            "NORMAL" => Some(LiquidityClass::Normal),
+            "DEEP" => Some(LiquidityClass::Deep),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LiquidityClass::Thin => "thin",
+            LiquidityClass::Normal => "normal",
+            LiquidityClass::Deep => "deep",
+        }
+    }
+}
+
+// Execution policy config (default + per-trader + per-liquidity-class overrides)
+#[derive(Debug, Clone)]
+pub struct ExecutionPolicyConfig {
+    pub default_policy: ExecutionPolicy,
+    pub by_trader: HashMap<String, ExecutionPolicy>,
+    pub by_liquidity: HashMap<LiquidityClass, ExecutionPolicy>,
+    pub maker_min_spread: f64,
+    pub maker_fallback_secs: u64,
+    pub thin_depth_usd: f64,
+    pub deep_depth_usd: f64,
+}
+
+impl Default for ExecutionPolicyConfig {
+    fn default() -> Self {
+        Self {
+            default_policy: ExecutionPolicy::Taker,
+            by_trader: HashMap::new(),
+            by_liquidity: HashMap::new(),
+            maker_min_spread: 0.03,
+            maker_fallback_secs: 10,
+            thin_depth_usd: 500.0,
+            deep_depth_usd: 5000.0,
+        }
+    }
+}
+
+impl ExecutionPolicyConfig {
+    pub fn classify_liquidity(&self, depth_usd: f64) -> LiquidityClass {
+        if depth_usd < self.thin_depth_usd {
+            LiquidityClass::Thin
+        } else if depth_usd >= self.deep_depth_usd {
+            LiquidityClass::Deep
+        } else {
+            LiquidityClass::Normal
+        }
+    }
+
+    // Trader override wins, then liquidity class, then default
+    pub fn resolve(&self, trader: &str, liquidity: LiquidityClass) -> ExecutionPolicy {
+        if let Some(p) = self.by_trader.get(&trader.to_lowercase()) {
+            return *p;
+        }
+        if let Some(p) = self.by_liquidity.get(&liquidity) {
+            return *p;
+        }
+        self.default_policy
+    }
+}
+
+// Parse "0xabc...:maker,0xdef...:taker"
+pub fn parse_trader_policies(input: &str) -> Result<HashMap<String, ExecutionPolicy>> {
+    let mut map = HashMap::new();
+    for part in input.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (addr, policy) = part
+            .rsplit_once(':')
+            .context("Invalid trader policy: expected address:policy")?;
+        let policy = ExecutionPolicy::parse(policy)
+            .with_context(|| format!("Invalid execution policy in: {}", part))?;
+        if !super::is_valid_ethereum_address(addr) {
+            anyhow::bail!("Invalid address in EXECUTION_POLICY_BY_TRADER: {}", addr);
+        }
+        map.insert(addr.trim().to_lowercase(), policy);
+    }
+    Ok(map)
+}
+
+// Parse "thin:maker,normal:maker,deep:taker"
+pub fn parse_liquidity_policies(input: &str) -> Result<HashMap<LiquidityClass, ExecutionPolicy>> {
+    let mut map = HashMap::new();
+    for part in input.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (class, policy) = part
+            .split_once(':')
+            .context("Invalid liquidity policy: expected class:policy")?;
+        let class = LiquidityClass::parse(class)
+            .with_context(|| format!("Invalid liquidity class in: {} (thin/normal/deep)", part))?;
+        let policy = ExecutionPolicy::parse(policy)
+            .with_context(|| format!("Invalid execution policy in: {}", part))?;
+        map.insert(class, policy);
+    }
+    Ok(map)
+}
@@ -1,13 +1,29 @@
 mod copy_strategy;
+mod execution_policy;
+mod overrides;
 
 pub use copy_strategy::{
-    calculate_order_size, get_trade_multiplier, parse_tiered_multipliers, CopyStrategy,
-    CopyStrategyConfig,
+    calculate_order_size, estimate_execution_cost, get_trade_multiplier, parse_tiered_multipliers,
+    CopyStrategy, CopyStrategyConfig, ExecutionCost, MarketLiquidity,
+};
+pub use execution_policy::{
+    parse_liquidity_policies, parse_trader_policies, ExecutionPolicy, ExecutionPolicyConfig,
+    LiquidityClass,
+};
+pub use overrides::{
+    active_overrides, apply_overrides, effective_config, format_market_caps, is_overridable,
+    parse_market_caps, parse_markets, set_active_overrides, validate_override, OVERRIDABLE_KEYS,
 };
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::env;
 
+use crate::types::{MarketCategory, UserActivity};
+
+// RTDS WebSocket URL (Polymarket real-time data stream) - first entry of RTDS_URLS by default
+pub const DEFAULT_RTDS_URL: &str = "wss://ws-live-data.polymarket.com";
+
 pub fn is_valid_ethereum_address(addr: &str) -> bool {
     let s = addr.trim().trim_start_matches("0x");
     s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
@@ -43,6 +59,65 @@ pub fn parse_user_addresses(input: &str) -> Result<Vec<String>> {
     Ok(addresses)
 }
 
+// Linked wallets copied as one trader: trades net together and share sizing, scoring and limits
+#[derive(Debug, Clone)]
+pub struct TraderGroup {
+    pub name: String,
+    pub addresses: Vec<String>, // Lowercase
+}
+
+// TRADER_GROUPS="whale:0xaaa+0xbbb,alice:0xccc+0xddd+0xeee"
+pub fn parse_trader_groups(input: &str) -> Result<Vec<TraderGroup>> {
+    let mut groups: Vec<TraderGroup> = Vec::new();
+    for entry in input.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let (name, members) = entry
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid TRADER_GROUPS entry (expected name:0xa+0xb): {}", entry))?;
+        let name = name.trim().to_string();
+        let addresses: Vec<String> = members
+            .split('+')
+            .map(|a| a.trim().to_lowercase())
+            .filter(|a| !a.is_empty())
+            .collect();
+        if name.is_empty() || addresses.len() < 2 {
+            anyhow::bail!("TRADER_GROUPS entry needs a name and at least two addresses: {}", entry);
+        }
+        for addr in &addresses {
+            if !is_valid_ethereum_address(addr) {
+                anyhow::bail!("Invalid Ethereum address in TRADER_GROUPS: {}", addr);
+            }
+            if groups.iter().any(|g| g.addresses.contains(addr)) {
+                anyhow::bail!("Address {} is in more than one TRADER_GROUPS group", addr);
+            }
+        }
+        groups.push(TraderGroup { name, addresses });
+    }
+    Ok(groups)
+}
+
+// Liquidity-aware scaling (both unset = off)
+fn parse_liquidity_scaling(config: &mut CopyStrategyConfig) {
+    config.max_depth_percent = env::var("MAX_DEPTH_PERCENT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|p: &f64| *p > 0.0);
+    config.min_volume_24h_usd = env::var("MIN_MARKET_VOLUME_24H_USD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &f64| *v > 0.0);
+    if let Some(v) = env::var("LIQUIDITY_DEPTH_RANGE").ok().and_then(|v| v.parse().ok()) {
+        config.depth_price_range = v;
+    }
+}
+
+// TRAILING_STOP_PERCENT: 0 / unset = no trailing stop, values >= 100 would never trigger
+fn trailing_stop_from_env() -> Option<f64> {
+    env::var("TRAILING_STOP_PERCENT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|p: &f64| *p > 0.0 && *p < 100.0)
+}
+
 fn parse_copy_strategy_from_env() -> Result<CopyStrategyConfig> {
     let has_legacy = env::var("COPY_PERCENTAGE").is_ok() && env::var("COPY_STRATEGY").is_err();
     if has_legacy {
@@ -81,7 +156,14 @@ fn parse_copy_strategy_from_env() -> Result<CopyStrategyConfig> {
             } else {
                 None
             },
+            max_depth_percent: None,
+            min_volume_24h_usd: None,
+            depth_price_range: 0.05,
+            trailing_stop_percent: trailing_stop_from_env(),
+            volatility_haircut: 1.0,
+            drawdown_scale: 1.0,
         };
+        parse_liquidity_scaling(&mut config);
         if let Ok(tiers_str) = env::var("TIERED_MULTIPLIERS") {
             config.tiered_multipliers = Some(parse_tiered_multipliers(&tiers_str)?);
         }
@@ -131,7 +213,14 @@ fn parse_copy_strategy_from_env() -> Result<CopyStrategyConfig> {
                     None
                 }
             }),
+        max_depth_percent: None,
+        min_volume_24h_usd: None,
+        depth_price_range: 0.05,
+        trailing_stop_percent: trailing_stop_from_env(),
+        volatility_haircut: 1.0,
+        drawdown_scale: 1.0,
     };
+    parse_liquidity_scaling(&mut config);
 
     if let Ok(tiers_str) = env::var("TIERED_MULTIPLIERS") {
         config.tiered_multipliers = Some(parse_tiered_multipliers(&tiers_str)?);
@@ -159,6 +248,204 @@ fn parse_copy_strategy_from_env() -> Result<CopyStrategyConfig> {
     Ok(config)
 }
 
+fn parse_execution_policy_from_env() -> Result<ExecutionPolicyConfig> {
+    let mut config = ExecutionPolicyConfig::default();
+    if let Ok(v) = env::var("EXECUTION_POLICY") {
+        config.default_policy = ExecutionPolicy::parse(&v)
+            .with_context(|| format!("Invalid EXECUTION_POLICY: {} (TAKER or MAKER)", v))?;
+    }
+    if let Ok(v) = env::var("EXECUTION_POLICY_BY_TRADER") {
+        config.by_trader = parse_trader_policies(&v)?;
+    }
+    if let Ok(v) = env::var("EXECUTION_POLICY_BY_LIQUIDITY") {
+        config.by_liquidity = parse_liquidity_policies(&v)?;
+    }
+    if let Some(v) = env::var("MAKER_MIN_SPREAD").ok().and_then(|v| v.parse().ok()) {
+        config.maker_min_spread = v;
+    }
+    if let Some(v) = env::var("MAKER_FALLBACK_SECS").ok().and_then(|v| v.parse().ok()) {
+        config.maker_fallback_secs = v;
+    }
+    if let Some(v) = env::var("LIQUIDITY_THIN_USD").ok().and_then(|v| v.parse().ok()) {
+        config.thin_depth_usd = v;
+    }
+    if let Some(v) = env::var("LIQUIDITY_DEEP_USD").ok().and_then(|v| v.parse().ok()) {
+        config.deep_depth_usd = v;
+    }
+    Ok(config)
+}
+
+// What to do when the trader buys one outcome and I already hold the other one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OppositeSidePolicy {
+    Allow, // Buy anyway (both sides held)
+    Skip,  // Don't copy the buy
+    Net,   // Sell my opposite tokens first, buy only what's left
+}
+
+impl OppositeSidePolicy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "allow" => Some(OppositeSidePolicy::Allow),
+            "skip" => Some(OppositeSidePolicy::Skip),
+            "net" => Some(OppositeSidePolicy::Net),
+            _ => None,
+        }
+    }
+}
+
+// What to do with a trader's copies past MAX_COPIES_PER_MARKET_PER_HOUR in one market
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketThrottleMode {
+    Skip,      // Mark extra trades skipped
+    Aggregate, // Buffer extra trades, net buys against sells, copy only the net
+}
+
+impl MarketThrottleMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "skip" => Some(MarketThrottleMode::Skip),
+            "aggregate" | "net" => Some(MarketThrottleMode::Aggregate),
+            _ => None,
+        }
+    }
+}
+
+// Where the volatility regime detector reads realized volatility from (VOL_REGIME_SOURCE)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolatilitySource {
+    Off,
+    Markets, // Our open positions' prices: median hourly stdev of 1-minute moves, in price points (0.05 = 5c)
+    Crypto,  // VOL_CRYPTO_SYMBOL 1-minute klines: hourly stdev of returns, in percent
+}
+
+impl VolatilitySource {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "" => Some(VolatilitySource::Off),
+            "markets" | "market" => Some(VolatilitySource::Markets),
+            "crypto" => Some(VolatilitySource::Crypto),
+            _ => None,
+        }
+    }
+
+    // VOL_HIGH when unset, in the source's own unit
+    fn default_high(&self) -> f64 {
+        match self {
+            VolatilitySource::Crypto => 1.0,
+            _ => 0.05,
+        }
+    }
+}
+
+// One DRAWDOWN_LEVELS step: from `percent` below the equity high, copy sizes are multiplied by `scale`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawdownLevel {
+    pub percent: f64,
+    pub scale: f64,
+}
+
+// "10:0.75,20:0.5,30:0.25" - drawdown % : size multiplier, deeper levels must scale down further
+pub fn parse_drawdown_levels(s: &str) -> Result<Vec<DrawdownLevel>> {
+    let mut levels = Vec::new();
+    for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (pct, scale) = part
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid DRAWDOWN_LEVELS entry '{}' (expected percent:scale)", part))?;
+        let level = DrawdownLevel {
+            percent: pct.trim().trim_end_matches('%').parse().map_err(|_| anyhow::anyhow!("Invalid DRAWDOWN_LEVELS percent in '{}'", part))?,
+            scale: scale.trim().parse().map_err(|_| anyhow::anyhow!("Invalid DRAWDOWN_LEVELS scale in '{}'", part))?,
+        };
+        if !(level.percent > 0.0 && level.percent < 100.0) || !(0.0..=1.0).contains(&level.scale) {
+            anyhow::bail!("Invalid DRAWDOWN_LEVELS entry '{}' (percent 0-100, scale 0-1)", part);
+        }
+        levels.push(level);
+    }
+    levels.sort_by(|a, b| a.percent.total_cmp(&b.percent));
+    if levels.windows(2).any(|w| w[1].scale > w[0].scale) {
+        anyhow::bail!("DRAWDOWN_LEVELS: a deeper drawdown can't copy bigger than a shallower one");
+    }
+    Ok(levels)
+}
+
+// "politics:200,sports:50,entertainment:0" - USD of open positions allowed per market category (0 = never copy it)
+pub fn parse_category_caps(s: &str) -> Result<HashMap<MarketCategory, f64>> {
+    let mut caps = HashMap::new();
+    for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, usd) = part
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid CATEGORY_MAX_USD entry '{}' (expected category:usd)", part))?;
+        let category = MarketCategory::parse(name).ok_or_else(|| {
+            anyhow::anyhow!("Unknown category '{}' in CATEGORY_MAX_USD (crypto, politics, sports, entertainment, other)", name.trim())
+        })?;
+        let usd: f64 = usd
+            .trim()
+            .trim_start_matches('$')
+            .parse()
+            .ok()
+            .filter(|v: &f64| *v >= 0.0)
+            .ok_or_else(|| anyhow::anyhow!("Invalid CATEGORY_MAX_USD amount in '{}'", part))?;
+        caps.insert(category, usd);
+    }
+    Ok(caps)
+}
+
+// What signs orders (SIGNER_BACKEND) - only "local" needs PRIVATE_KEY on this machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignerBackend {
+    Local,  // PRIVATE_KEY in the env / .env
+    Remote, // REMOTE_SIGNER_URL signing service (JSON-RPC) - the key lives on another host
+}
+
+impl SignerBackend {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "local" | "" => Some(SignerBackend::Local),
+            "remote" => Some(SignerBackend::Remote),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignerBackend::Local => "local key",
+            SignerBackend::Remote => "remote signer",
+        }
+    }
+}
+
+// Where trades, positions and settings are stored (STORAGE_BACKEND)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBackend {
+    Mongo,  // MongoDB server at MONGO_URI
+    Sqlite, // Embedded SQLite file at SQLITE_PATH - no database server to run
+}
+
+impl StorageBackend {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "mongo" | "mongodb" | "" => Some(StorageBackend::Mongo),
+            "sqlite" => Some(StorageBackend::Sqlite),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageBackend::Mongo => "MongoDB",
+            StorageBackend::Sqlite => "SQLite",
+        }
+    }
+
+    pub fn sqlite_path_from_env() -> String {
+        env::var("SQLITE_PATH")
+            .ok()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "polymarket_copytrading.db".to_string())
+    }
+}
+
 #[derive(Clone)]
 pub struct EnvConfig {
     pub user_addresses: Vec<String>,
@@ -166,32 +453,160 @@ pub struct EnvConfig {
     pub private_key: String,
     pub clob_http_url: String,
     pub clob_ws_url: String,
+    pub rtds_urls: Vec<String>,
+    pub ws_primary_retry_secs: u64,
+    pub rtds_catchup_max_minutes: u64,
     pub fetch_interval_secs: u64,
     pub too_old_timestamp_hours: i64,
     pub retry_limit: u32,
     pub copy_strategy_config: CopyStrategyConfig,
+    pub execution_policy_config: ExecutionPolicyConfig,
     pub request_timeout_ms: u64,
     pub network_retry_limit: u32,
     pub trade_aggregation_enabled: bool,
     pub trade_aggregation_window_seconds: u64,
+    pub trade_aggregation_net_sides: bool,
     pub mongo_uri: String,
     pub rpc_url: String,
     pub usdc_contract_address: String,
+    pub allowance_check_interval_secs: u64,
+    pub allowance_buffer_orders: f64,
+    pub allowance_auto_topup: bool,
+    pub gas_check: bool, // Skip the allowance top-up when the shortfall doesn't cover its gas
+    pub gas_oracle_url: Option<String>, // Polygon gas station (None = the RPC's eth_gasPrice)
+    pub gas_price_api: String, // Binance-style spot API for the POL price
+    pub gas_min_benefit_ratio: f64,
+    pub gas_max_gwei: Option<f64>,
+    pub signature_type: Option<u8>,
+    pub resolve_trader_proxies: bool,
+    pub min_sell_price_ratio: f64,
+    pub sell_floor_reeval_secs: u64,
+    pub sell_floor_max_reevals: u32,
+    pub exit_escalation_steps: u32,
+    pub exit_escalation_interval_secs: u64,
+    pub exit_escalation_step_percent: f64,
+    pub alert_telegram_token: Option<String>,
+    pub alert_chat_id: Option<String>,
+    pub opposite_side_policy: OppositeSidePolicy,
+    pub max_copies_per_market_per_hour: u32,
+    pub market_throttle_mode: MarketThrottleMode,
+    pub taker_fee_bps: f64,
+    pub require_positive_ev: bool,
+    pub book_max_age_ms: u64,
+    pub copy_delay_seconds: u64,
+    pub copy_delay_max_drift_percent: f64,
+    pub trading_paused: bool,
+    pub paused_traders: Vec<String>,
+    pub config_refresh_secs: u64,
+    pub rewards_senders: Vec<String>,
+    pub rewards_scan_block_chunk: u64,
+    pub heartbeat_url: Option<String>,
+    pub heartbeat_interval_secs: u64,
+    pub heartbeat_file: String,
+    pub wallet_watch_interval_secs: u64,
+    pub trailing_stop_interval_secs: u64,
+    pub alert_routes: String,
+    pub alert_file: String,
+    pub alert_discord_webhook_url: Option<String>,
+    pub alert_webhook_url: Option<String>,
+    pub alert_dedup_secs: u64,
+    pub alert_rate_limit_per_min: u32,
+    pub dormant_trader_days: u64,
+    pub dormant_check_interval_secs: u64,
+    pub dormant_trailing_stop_percent: Option<f64>,
+    pub resource_check_secs: u64,
+    pub resource_max_rss_mb: f64,
+    pub resource_max_tasks: usize,
+    pub resource_max_backlog: u64,
+    pub resource_max_mongo_ms: u64,
+    pub entry_imbalance_filter: bool,
+    pub entry_min_imbalance: f64,
+    pub entry_imbalance_levels: usize,
+    pub entry_momentum_filter: bool,
+    pub entry_momentum_lookback_secs: u64,
+    pub entry_max_adverse_move_percent: f64,
+    // Markets never copied: conditionIds, or words matched against the slug / event slug / title (lowercased)
+    pub market_blacklist: Vec<String>,
+    // Buys the trader filled outside this price band are not copied (sells always are)
+    pub copy_min_price: Option<f64>,
+    pub copy_max_price: Option<f64>,
+    // Per-market interventions - DB overrides only (pause-market / close-market / resize-market)
+    pub paused_markets: Vec<String>,
+    pub closed_markets: Vec<String>,
+    pub market_max_usd: Vec<(String, f64)>,
+    pub report_schedule: Vec<String>,
+    pub report_hour: u32,
+    pub report_weekday: String,
+    pub report_webhook_url: Option<String>,
+    pub report_smtp_host: Option<String>,
+    pub report_smtp_port: u16,
+    pub report_smtp_user: Option<String>,
+    pub report_smtp_password: Option<String>,
+    pub report_email_from: Option<String>,
+    pub report_email_to: Vec<String>,
+    pub trader_groups: Vec<TraderGroup>,
+    pub min_order_size_tokens: f64,
+    pub log_events_max: u64, // Logger lines kept in "log_events" for replay (0 = file only)
+    pub usdc_code_hash: Option<String>, // keccak256 of the USDC contract code, checked at startup when set
+    pub capital_sample_secs: u64, // How often cash vs deployed is recorded for the capital report (0 = off)
+    pub idle_cash_apr_percent: f64, // Yield idle USDC could earn elsewhere - prices the opportunity cost
+    pub vol_regime_source: VolatilitySource, // Volatility regime detector input (Off = no haircut)
+    pub vol_high: f64, // Turbulent at or above this realized volatility (unit depends on the source)
+    pub vol_calm: f64, // Calm again at or below this (below VOL_HIGH, so the regime doesn't flap)
+    pub vol_haircut: f64, // Copy size multiplier while turbulent
+    pub vol_lookback_minutes: u64, // Window the realized volatility is measured over
+    pub vol_check_secs: u64, // How often the regime is re-measured
+    pub vol_crypto_symbol: String, // Binance symbol for the crypto source
+    pub vol_crypto_api: String, // Binance-compatible klines endpoint
+    pub http_pool_max_idle: usize, // Idle keep-alive connections per host in each shared HTTP client
+    pub http_pool_idle_secs: u64, // Idle connections are closed after this
+    pub http_tcp_keepalive_secs: u64, // TCP keepalive probes on pooled connections (0 = off)
+    pub http_connect_timeout_ms: u64,
+    pub https_proxy: Option<String>, // HTTPS_PROXY for every outbound HTTPS call (NO_PROXY hosts bypass it)
+    pub dispute_check_secs: u64, // How often held markets are checked for a UMA resolution dispute (0 = off)
+    pub trade_notes: bool, // Raise the trader's recent public comment on a market they trade as a trade_note alert
+    pub trade_notes_max_age_hours: u64, // Older comments aren't shown
+    pub signer_backend: SignerBackend,
+    pub remote_signer_url: Option<String>, // JSON-RPC endpoint of the signing service (SIGNER_BACKEND=remote)
+    pub remote_signer_token: Option<String>, // Sent as "Authorization: Bearer ..."
+    pub remote_signer_address: Option<String>, // Account to sign with (None = the service's first account)
+    pub drawdown_levels: Vec<DrawdownLevel>, // Copy size steps as equity falls below its high (empty = off)
+    pub drawdown_recovery_pct: f64, // A level is left once the drawdown is this many points back under it
+    pub drawdown_lookback_days: u64, // Equity high = the highest capital sample this far back
+    pub category_max_usd: HashMap<MarketCategory, f64>, // Open-position cap per market category (empty = no caps)
+    pub storage_backend: StorageBackend,
+    pub sqlite_path: String, // Database file when STORAGE_BACKEND=sqlite
+    pub shutdown_flush_seconds: u64, // On stop, buffered aggregation groups get this long to execute (0 = defer them all)
+    pub dry_run: bool, // Paper trading: full copy flow, orders recorded in paper_trades instead of posted
+    pub paper_balance_usd: f64, // Starting USDC of the DRY_RUN wallet (paper buys / sells move it)
 }
 
 impl EnvConfig {
     pub async fn from_env() -> Result<Self> {
         dotenvy::dotenv().ok();
 
-        let required = [
+        let raw_backend = env::var("SIGNER_BACKEND").unwrap_or_default();
+        // The CLOB client signs orders and auth as raw EIP-712 hashes, which the Ledger Ethereum app refuses -
+        // stop here instead of failing on every order
+        if raw_backend.trim().eq_ignore_ascii_case("ledger") {
+            anyhow::bail!(
+                "SIGNER_BACKEND=ledger is not supported: the Ledger Ethereum app won't sign the raw order hashes the CLOB client uses. Use SIGNER_BACKEND=remote to keep the key off this host"
+            );
+        }
+        let signer_backend = SignerBackend::parse(&raw_backend)
+            .ok_or_else(|| anyhow::anyhow!("Invalid SIGNER_BACKEND (local or remote)"))?;
+        let mut required = vec![
             "USER_ADDRESSES",
             "PROXY_WALLET",
-            "PRIVATE_KEY",
             "CLOB_HTTP_URL",
             "CLOB_WS_URL",
             "RPC_URL",
             "USDC_CONTRACT_ADDRESS",
         ];
+        match signer_backend {
+            SignerBackend::Local => required.push("PRIVATE_KEY"),
+            SignerBackend::Remote => required.push("REMOTE_SIGNER_URL"),
+        }
         for key in &required {
             if env::var(key).unwrap_or_default().trim().is_empty() {
                 anyhow::bail!(
@@ -212,10 +627,25 @@ impl EnvConfig {
             }
         }
 
-        let user_addresses = parse_user_addresses(&env::var("USER_ADDRESSES")?)?;
+        let mut user_addresses = parse_user_addresses(&env::var("USER_ADDRESSES")?)?;
+        // Every wallet of a group is monitored, even when only one of them is in USER_ADDRESSES
+        let trader_groups = parse_trader_groups(&env::var("TRADER_GROUPS").unwrap_or_default())?;
+        for addr in trader_groups.iter().flat_map(|g| g.addresses.iter()) {
+            if !user_addresses.contains(addr) {
+                user_addresses.push(addr.clone());
+            }
+        }
         if user_addresses.is_empty() {
             anyhow::bail!("USER_ADDRESSES must contain at least one address");
         }
+        // Copying our own wallet would re-copy every execution in a loop
+        let own_wallet = env::var("PROXY_WALLET")?.trim().to_lowercase();
+        if user_addresses.iter().any(|a| a.to_lowercase() == own_wallet) {
+            anyhow::bail!(
+                "USER_ADDRESSES contains your own PROXY_WALLET ({}) - remove it, the bot would copy its own trades",
+                own_wallet
+            );
+        }
 
         let fetch_interval_secs: u64 = env::var("FETCH_INTERVAL")
             .ok()
@@ -244,7 +674,65 @@ impl EnvConfig {
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(300);
-        let private_key = env::var("PRIVATE_KEY")?
+        let trade_aggregation_net_sides = env::var("TRADE_AGGREGATION_NET_SIDES")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false);
+        let allowance_check_interval_secs: u64 = env::var("ALLOWANCE_CHECK_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(300);
+        let allowance_buffer_orders: f64 = env::var("ALLOWANCE_BUFFER_ORDERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5.0);
+        let allowance_auto_topup = env::var("ALLOWANCE_AUTO_TOPUP")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false);
+        let gas_check = env::var("GAS_CHECK")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false);
+        // Set but empty = no oracle, eth_gasPrice only
+        let gas_oracle_url = Some(
+            env::var("GAS_ORACLE_URL").unwrap_or_else(|_| "https://gasstation.polygon.technology/v2".to_string()),
+        )
+        .filter(|v| !v.trim().is_empty());
+        let gas_price_api = env::var("GAS_PRICE_API")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "https://api.binance.com".to_string());
+        let gas_min_benefit_ratio: f64 = env::var("GAS_MIN_BENEFIT_RATIO")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v: &f64| *v >= 0.0)
+            .unwrap_or(2.0);
+        let gas_max_gwei: Option<f64> = env::var("GAS_MAX_GWEI")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v: &f64| *v > 0.0);
+        let opposite_side_policy = match env::var("OPPOSITE_SIDE_POLICY") {
+            Ok(v) if !v.trim().is_empty() => OppositeSidePolicy::parse(&v).ok_or_else(|| {
+                anyhow::anyhow!("Invalid OPPOSITE_SIDE_POLICY: {} (allow, skip or net)", v)
+            })?,
+            _ => OppositeSidePolicy::Allow,
+        };
+        let market_throttle_mode = match env::var("MARKET_THROTTLE_MODE") {
+            Ok(v) if !v.trim().is_empty() => MarketThrottleMode::parse(&v).ok_or_else(|| {
+                anyhow::anyhow!("Invalid MARKET_THROTTLE_MODE: {} (skip or aggregate)", v)
+            })?,
+            _ => MarketThrottleMode::Skip,
+        };
+        let vol_regime_source = VolatilitySource::parse(&env::var("VOL_REGIME_SOURCE").unwrap_or_default())
+            .ok_or_else(|| anyhow::anyhow!("Invalid VOL_REGIME_SOURCE (off, markets or crypto)"))?;
+        let vol_high: f64 = env::var("VOL_HIGH")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|v: &f64| *v > 0.0)
+            .unwrap_or(vol_regime_source.default_high());
+        // Empty unless SIGNER_BACKEND=local - a key left in .env is ignored by the other backends
+        let private_key = env::var("PRIVATE_KEY")
+            .ok()
+            .filter(|_| signer_backend == SignerBackend::Local)
+            .unwrap_or_default()
             .trim()
             .trim_start_matches("0x")
             .to_string();
@@ -252,7 +740,7 @@ impl EnvConfig {
         let mongo_uri = env::var("MONGO_URI")
             .unwrap_or_else(|_| "mongodb://localhost:27017/polymarket_copytrading".into());
 
-        Ok(Self {
+        let config = Self {
             user_addresses,
             proxy_wallet: env::var("PROXY_WALLET")?.trim().to_string(),
             private_key,
@@ -261,17 +749,506 @@ impl EnvConfig {
                 .trim_end_matches('/')
                 .to_string(),
             clob_ws_url: env::var("CLOB_WS_URL")?.trim().to_string(),
+            rtds_urls: env::var("RTDS_URLS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                })
+                .ok()
+                .filter(|urls| !urls.is_empty())
+                .unwrap_or_else(|| vec![DEFAULT_RTDS_URL.to_string()]),
+            ws_primary_retry_secs: env::var("WS_PRIMARY_RETRY_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            rtds_catchup_max_minutes: env::var("RTDS_CATCHUP_MAX_MINUTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
             fetch_interval_secs,
             too_old_timestamp_hours,
             retry_limit,
             copy_strategy_config: parse_copy_strategy_from_env()?,
+            execution_policy_config: parse_execution_policy_from_env()?,
             request_timeout_ms,
             network_retry_limit,
             trade_aggregation_enabled,
             trade_aggregation_window_seconds,
+            trade_aggregation_net_sides,
             mongo_uri,
             rpc_url: env::var("RPC_URL")?.trim().to_string(),
             usdc_contract_address: env::var("USDC_CONTRACT_ADDRESS")?.trim().to_string(),
-        })
+            allowance_check_interval_secs,
+            allowance_buffer_orders,
+            allowance_auto_topup,
+            gas_check,
+            gas_oracle_url,
+            gas_price_api,
+            gas_min_benefit_ratio,
+            gas_max_gwei,
+            signature_type: env::var("SIGNATURE_TYPE")
+                .ok()
+                .and_then(|v| v.trim().parse().ok()),
+            resolve_trader_proxies: env::var("RESOLVE_TRADER_PROXIES")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(true),
+            min_sell_price_ratio: env::var("MIN_SELL_PRICE_RATIO")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.0),
+            sell_floor_reeval_secs: env::var("SELL_FLOOR_REEVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            sell_floor_max_reevals: env::var("SELL_FLOOR_MAX_REEVALS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            exit_escalation_steps: env::var("EXIT_ESCALATION_STEPS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            exit_escalation_interval_secs: env::var("EXIT_ESCALATION_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            exit_escalation_step_percent: env::var("EXIT_ESCALATION_STEP_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            alert_telegram_token: env::var("TELEGRAM_BOT_TOKEN")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            alert_chat_id: env::var("ALERT_CHAT_ID")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            opposite_side_policy,
+            max_copies_per_market_per_hour: env::var("MAX_COPIES_PER_MARKET_PER_HOUR")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            market_throttle_mode,
+            taker_fee_bps: env::var("TAKER_FEE_BPS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.0),
+            require_positive_ev: env::var("REQUIRE_POSITIVE_EV")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            book_max_age_ms: env::var("BOOK_MAX_AGE_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            copy_delay_seconds: env::var("COPY_DELAY_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            copy_delay_max_drift_percent: env::var("COPY_DELAY_MAX_DRIFT_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            trading_paused: env::var("TRADING_PAUSED")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            paused_traders: env::var("PAUSED_TRADERS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_lowercase())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            config_refresh_secs: env::var("CONFIG_REFRESH_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            rewards_senders: env::var("REWARDS_SENDERS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_lowercase())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            rewards_scan_block_chunk: env::var("REWARDS_SCAN_BLOCK_CHUNK")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &u64| *v > 0)
+                .unwrap_or(2000),
+            heartbeat_url: env::var("HEARTBEAT_URL")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            heartbeat_interval_secs: env::var("HEARTBEAT_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            heartbeat_file: env::var("HEARTBEAT_FILE")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "logs/heartbeat.json".to_string()),
+            wallet_watch_interval_secs: env::var("WALLET_WATCH_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            trailing_stop_interval_secs: env::var("TRAILING_STOP_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            alert_routes: env::var("ALERT_ROUTES")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or_else(|| "console:info,file:info,telegram:warn,discord:warn,webhook:warn".to_string()),
+            alert_file: env::var("ALERT_FILE")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "logs/alerts.jsonl".to_string()),
+            alert_discord_webhook_url: env::var("DISCORD_WEBHOOK_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            alert_webhook_url: env::var("ALERT_WEBHOOK_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            alert_dedup_secs: env::var("ALERT_DEDUP_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            alert_rate_limit_per_min: env::var("ALERT_RATE_LIMIT_PER_MIN")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            dormant_trader_days: env::var("DORMANT_TRADER_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(7),
+            dormant_check_interval_secs: env::var("DORMANT_CHECK_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            dormant_trailing_stop_percent: env::var("DORMANT_TRAILING_STOP_PERCENT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|p| *p > 0.0 && *p < 100.0),
+            resource_check_secs: env::var("RESOURCE_CHECK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            resource_max_rss_mb: env::var("RESOURCE_MAX_RSS_MB")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(512.0),
+            resource_max_tasks: env::var("RESOURCE_MAX_TASKS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
+            resource_max_backlog: env::var("RESOURCE_MAX_BACKLOG")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(200),
+            resource_max_mongo_ms: env::var("RESOURCE_MAX_MONGO_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(500),
+            entry_imbalance_filter: env::var("ENTRY_IMBALANCE_FILTER")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            entry_min_imbalance: env::var("ENTRY_MIN_IMBALANCE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|v| v.clamp(-1.0, 1.0))
+                .unwrap_or(-0.6),
+            entry_imbalance_levels: env::var("ENTRY_IMBALANCE_LEVELS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(5),
+            entry_momentum_filter: env::var("ENTRY_MOMENTUM_FILTER")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            entry_momentum_lookback_secs: env::var("ENTRY_MOMENTUM_LOOKBACK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(300),
+            entry_max_adverse_move_percent: env::var("ENTRY_MAX_ADVERSE_MOVE_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(15.0),
+            market_blacklist: env::var("MARKET_BLACKLIST")
+                .map(|v| overrides::parse_markets(&v))
+                .unwrap_or_default(),
+            copy_min_price: env::var("COPY_MIN_PRICE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|p| *p > 0.0 && *p < 1.0),
+            copy_max_price: env::var("COPY_MAX_PRICE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|p| *p > 0.0 && *p < 1.0),
+            paused_markets: Vec::new(),
+            closed_markets: Vec::new(),
+            market_max_usd: Vec::new(),
+            report_schedule: env::var("REPORT_SCHEDULE")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_lowercase())
+                        .filter(|s| s == "daily" || s == "weekly")
+                        .collect()
+                })
+                .unwrap_or_default(),
+            report_hour: env::var("REPORT_HOUR")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|h| *h < 24)
+                .unwrap_or(8),
+            report_weekday: env::var("REPORT_WEEKDAY")
+                .ok()
+                .map(|v| v.trim().to_lowercase())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "mon".to_string()),
+            report_webhook_url: env::var("REPORT_WEBHOOK_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            report_smtp_host: env::var("REPORT_SMTP_HOST")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            report_smtp_port: env::var("REPORT_SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(587),
+            report_smtp_user: env::var("REPORT_SMTP_USER")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            report_smtp_password: env::var("REPORT_SMTP_PASSWORD")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            report_email_from: env::var("REPORT_EMAIL_FROM")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            report_email_to: env::var("REPORT_EMAIL_TO")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            trader_groups,
+            min_order_size_tokens: env::var("MIN_ORDER_SIZE_TOKENS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| *v > 0.0)
+                .unwrap_or(1.0),
+            log_events_max: env::var("LOG_EVENTS_MAX")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5000),
+            usdc_code_hash: env::var("USDC_CODE_HASH")
+                .ok()
+                .map(|v| v.trim().to_lowercase())
+                .filter(|v| !v.is_empty()),
+            capital_sample_secs: env::var("CAPITAL_SAMPLE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(900),
+            idle_cash_apr_percent: env::var("IDLE_CASH_APR_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| *v >= 0.0)
+                .unwrap_or(5.0),
+            vol_regime_source,
+            vol_high,
+            vol_calm: env::var("VOL_CALM")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| *v > 0.0 && *v < vol_high)
+                .unwrap_or(vol_high * 0.7),
+            vol_haircut: env::var("VOL_HAIRCUT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| *v >= 0.0 && *v <= 1.0)
+                .unwrap_or(0.5),
+            vol_lookback_minutes: env::var("VOL_LOOKBACK_MINUTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &u64| *v >= 5)
+                .unwrap_or(60),
+            vol_check_secs: env::var("VOL_CHECK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &u64| *v > 0)
+                .unwrap_or(300),
+            vol_crypto_symbol: env::var("VOL_CRYPTO_SYMBOL")
+                .ok()
+                .map(|v| v.trim().to_uppercase())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "BTCUSDT".to_string()),
+            vol_crypto_api: env::var("VOL_CRYPTO_API")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "https://api.binance.com/api/v3/klines".to_string()),
+            http_pool_max_idle: env::var("HTTP_POOL_MAX_IDLE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(16),
+            http_pool_idle_secs: env::var("HTTP_POOL_IDLE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(90),
+            http_tcp_keepalive_secs: env::var("HTTP_TCP_KEEPALIVE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            http_connect_timeout_ms: env::var("HTTP_CONNECT_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &u64| *v > 0)
+                .unwrap_or(5000),
+            https_proxy: env::var("HTTPS_PROXY")
+                .or_else(|_| env::var("https_proxy"))
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            dispute_check_secs: env::var("DISPUTE_CHECK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            trade_notes: env::var("TRADE_NOTES")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            trade_notes_max_age_hours: env::var("TRADE_NOTES_MAX_AGE_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(72),
+            signer_backend,
+            remote_signer_url: env::var("REMOTE_SIGNER_URL")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            remote_signer_token: env::var("REMOTE_SIGNER_TOKEN")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty()),
+            remote_signer_address: env::var("REMOTE_SIGNER_ADDRESS")
+                .ok()
+                .map(|v| v.trim().to_lowercase())
+                .filter(|v| !v.is_empty()),
+            drawdown_levels: parse_drawdown_levels(&env::var("DRAWDOWN_LEVELS").unwrap_or_default())?,
+            drawdown_recovery_pct: env::var("DRAWDOWN_RECOVERY_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| *v >= 0.0)
+                .unwrap_or(2.0),
+            drawdown_lookback_days: env::var("DRAWDOWN_LOOKBACK_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &u64| *v > 0)
+                .unwrap_or(30),
+            category_max_usd: parse_category_caps(&env::var("CATEGORY_MAX_USD").unwrap_or_default())?,
+            storage_backend: StorageBackend::parse(&env::var("STORAGE_BACKEND").unwrap_or_default())
+                .ok_or_else(|| anyhow::anyhow!("Invalid STORAGE_BACKEND (mongo or sqlite)"))?,
+            sqlite_path: StorageBackend::sqlite_path_from_env(),
+            shutdown_flush_seconds: env::var("SHUTDOWN_FLUSH_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(20),
+            dry_run: env::var("DRY_RUN")
+                .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+                .unwrap_or(false),
+            paper_balance_usd: env::var("PAPER_BALANCE_USD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &f64| *v >= 0.0)
+                .unwrap_or(1000.0),
+        };
+        // The top-up can only sign for an EOA PROXY_WALLET with the local key - anything else would raise a
+        // Critical alert on every drift check instead
+        if config.allowance_auto_topup {
+            crate::utils::approval_signer(&config).map_err(|e| {
+                anyhow::anyhow!("ALLOWANCE_AUTO_TOPUP=true can't work with this wallet: {} (set it to false and approve by hand)", e)
+            })?;
+        }
+        // Shared HTTP clients are built on first use with these settings
+        crate::utils::configure_http(crate::utils::HttpSettings::from_config(&config));
+        Ok(config)
+    }
+
+    // True for our own PROXY_WALLET - its trades must never be copied
+    pub fn is_own_wallet(&self, address: &str) -> bool {
+        address.trim().eq_ignore_ascii_case(self.proxy_wallet.trim())
+    }
+
+    // TRADER_GROUPS group this wallet belongs to
+    pub fn trader_group(&self, address: &str) -> Option<&TraderGroup> {
+        let addr = address.trim().to_lowercase();
+        self.trader_groups.iter().find(|g| g.addresses.contains(&addr))
+    }
+
+    // All wallets of the logical trader (just this one when ungrouped)
+    pub fn group_members(&self, address: &str) -> Vec<String> {
+        match self.trader_group(address) {
+            Some(g) => g.addresses.clone(),
+            None => vec![address.trim().to_lowercase()],
+        }
+    }
+
+    // Key shared by every wallet of one logical trader (aggregation, throttling, score cache)
+    pub fn trader_key(&self, address: &str) -> String {
+        match self.trader_group(address) {
+            Some(g) => format!("group:{}", g.name),
+            None => address.trim().to_lowercase(),
+        }
+    }
+
+    // Paused if any wallet of the group is in PAUSED_TRADERS
+    pub fn is_trader_paused(&self, address: &str) -> bool {
+        self.group_members(address).iter().any(|a| self.paused_traders.contains(a))
+    }
+
+    // New buys in this market are skipped (paused or being closed out)
+    pub fn is_market_blocked(&self, condition_id: &str) -> bool {
+        let cid = condition_id.trim().to_lowercase();
+        self.paused_markets.contains(&cid) || self.closed_markets.contains(&cid)
+    }
+
+    // MARKET_BLACKLIST entry this trade's market matches (a conditionId exactly, anything else inside the
+    // slug / event slug / title)
+    pub fn blacklist_match(&self, trade: &UserActivity) -> Option<&str> {
+        let cid = trade.condition_id.as_deref().unwrap_or("").trim().to_lowercase();
+        let text: Vec<String> = [&trade.slug, &trade.event_slug, &trade.title]
+            .into_iter()
+            .flatten()
+            .map(|s| s.to_lowercase())
+            .collect();
+        self.market_blacklist
+            .iter()
+            .find(|entry| {
+                if entry.starts_with("0x") {
+                    **entry == cid
+                } else {
+                    text.iter().any(|t| t.contains(entry.as_str()))
+                }
+            })
+            .map(|entry| entry.as_str())
+    }
+
+    // Trader's price is outside COPY_MIN_PRICE..COPY_MAX_PRICE
+    pub fn outside_price_band(&self, price: f64) -> bool {
+        self.copy_min_price.is_some_and(|min| price < min) || self.copy_max_price.is_some_and(|max| price > max)
+    }
+
+    // MARKET_MAX_USD cap for this market, if one was set with resize-market
+    pub fn market_cap_usd(&self, condition_id: &str) -> Option<f64> {
+        self.market_max_usd
+            .iter()
+            .find(|(cid, _)| cid.eq_ignore_ascii_case(condition_id.trim()))
+            .map(|(_, usd)| *usd)
     }
 }
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use std::sync::RwLock;
+
+use super::EnvConfig;
+
+// Keys that can be changed at runtime through the Mongo "configs" collection
+// (latest document per key wins over env; an empty value clears the override)
+pub const OVERRIDABLE_KEYS: [&str; 18] = [
+    "COPY_SIZE",
+    "MAX_ORDER_SIZE_USD",
+    "MIN_ORDER_SIZE_USD",
+    "MIN_ORDER_SIZE_TOKENS",
+    "MAX_POSITION_SIZE_USD",
+    "TRADE_MULTIPLIER",
+    "MAX_DEPTH_PERCENT",
+    "TRADING_PAUSED",
+    "PAUSED_TRADERS",
+    "TRAILING_STOP_PERCENT",
+    "ENTRY_IMBALANCE_FILTER",
+    "ENTRY_MOMENTUM_FILTER",
+    "PAUSED_MARKETS",
+    "CLOSED_MARKETS",
+    "MARKET_MAX_USD",
+    "MARKET_BLACKLIST",
+    "COPY_MIN_PRICE",
+    "COPY_MAX_PRICE",
+];
+
+// Last overrides loaded from the DB (refreshed by the config refresher service)
+static ACTIVE_OVERRIDES: RwLock<Vec<(String, String)>> = RwLock::new(Vec::new());
+
+pub fn is_overridable(key: &str) -> bool {
+    OVERRIDABLE_KEYS.contains(&key)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_traders(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn is_condition_id(value: &str) -> bool {
+    value.len() == 66 && value.starts_with("0x") && value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Comma-separated condition ids (lowercased), as stored for PAUSED_MARKETS / CLOSED_MARKETS
+pub fn parse_markets(value: &str) -> Vec<String> {
+    parse_traders(value)
+}
+
+// "conditionId:usd,conditionId:usd" as stored for MARKET_MAX_USD (bad entries dropped)
+pub fn parse_market_caps(value: &str) -> Vec<(String, f64)> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (cid, usd) = entry.trim().split_once(':')?;
+            let usd: f64 = usd.trim().parse().ok()?;
+            Some((cid.trim().to_lowercase(), usd))
+        })
+        .filter(|(cid, usd)| is_condition_id(cid) && *usd >= 0.0)
+        .collect()
+}
+
+pub fn format_market_caps(caps: &[(String, f64)]) -> String {
+    caps.iter()
+        .map(|(cid, usd)| format!("{}:{}", cid, usd))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Check a value before it is stored (so a typo can't silently do nothing)
+pub fn validate_override(key: &str, value: &str) -> Result<()> {
+    if !is_overridable(key) {
+        anyhow::bail!(
+            "{} can't be overridden at runtime (allowed: {})",
+            key,
+            OVERRIDABLE_KEYS.join(", ")
+        );
+    }
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(()); // Clears the override
+    }
+    match key {
+        "TRADING_PAUSED" | "ENTRY_IMBALANCE_FILTER" | "ENTRY_MOMENTUM_FILTER" => {
+            parse_bool(value).ok_or_else(|| anyhow::anyhow!("{} must be true or false", key))?;
+        }
+        "PAUSED_TRADERS" => {
+            for addr in parse_traders(value) {
+                if !super::is_valid_ethereum_address(&addr) {
+                    anyhow::bail!("Invalid address in PAUSED_TRADERS: {}", addr);
+                }
+            }
+        }
+        "PAUSED_MARKETS" | "CLOSED_MARKETS" => {
+            for cid in parse_markets(value) {
+                if !is_condition_id(&cid) {
+                    anyhow::bail!("Invalid conditionId in {}: {}", key, cid);
+                }
+            }
+        }
+        "MARKET_BLACKLIST" => {} // Any words; conditionIds are matched exactly
+        "COPY_MIN_PRICE" | "COPY_MAX_PRICE" => {
+            let n: f64 = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("{} must be a number", key))?;
+            if !(0.0..=1.0).contains(&n) {
+                anyhow::bail!("{} must be between 0 and 1", key);
+            }
+        }
+        "MARKET_MAX_USD" => {
+            let entries = value.split(',').filter(|e| !e.trim().is_empty()).count();
+            if parse_market_caps(value).len() != entries {
+                anyhow::bail!("MARKET_MAX_USD must be conditionId:usd pairs, comma-separated");
+            }
+        }
+        _ => {
+            let n: f64 = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("{} must be a number", key))?;
+            if n < 0.0 {
+                anyhow::bail!("{} must not be negative", key);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn set_active_overrides(overrides: Vec<(String, String)>) {
+    if let Ok(mut guard) = ACTIVE_OVERRIDES.write() {
+        *guard = overrides;
+    }
+}
+
+pub fn active_overrides() -> Vec<(String, String)> {
+    ACTIVE_OVERRIDES
+        .read()
+        .map(|g| g.clone())
+        .unwrap_or_default()
+}
+
+// Env config with DB overrides applied on top (precedence: DB > env > defaults)
+pub fn apply_overrides(base: &EnvConfig, overrides: &[(String, String)]) -> EnvConfig {
+    let mut config = base.clone();
+    for (key, value) in overrides {
+        if validate_override(key, value).is_err() || value.trim().is_empty() {
+            continue;
+        }
+        let value = value.trim();
+        let num = value.parse::<f64>().ok();
+        let strategy = &mut config.copy_strategy_config;
+        match key.as_str() {
+            "COPY_SIZE" => strategy.copy_size = num.unwrap_or(strategy.copy_size),
+            "MAX_ORDER_SIZE_USD" => strategy.max_order_size_usd = num.unwrap_or(strategy.max_order_size_usd),
+            "MIN_ORDER_SIZE_USD" => strategy.min_order_size_usd = num.unwrap_or(strategy.min_order_size_usd),
+            "MIN_ORDER_SIZE_TOKENS" => {
+                config.min_order_size_tokens = num.filter(|n| *n > 0.0).unwrap_or(config.min_order_size_tokens)
+            }
+            "MAX_POSITION_SIZE_USD" => strategy.max_position_size_usd = num,
+            "TRADE_MULTIPLIER" => strategy.trade_multiplier = num,
+            "MAX_DEPTH_PERCENT" => strategy.max_depth_percent = num.filter(|p| *p > 0.0),
+            "TRAILING_STOP_PERCENT" => strategy.trailing_stop_percent = num.filter(|p| *p > 0.0 && *p < 100.0),
+            "TRADING_PAUSED" => config.trading_paused = parse_bool(value).unwrap_or(false),
+            "PAUSED_TRADERS" => config.paused_traders = parse_traders(value),
+            "ENTRY_IMBALANCE_FILTER" => config.entry_imbalance_filter = parse_bool(value).unwrap_or(false),
+            "ENTRY_MOMENTUM_FILTER" => config.entry_momentum_filter = parse_bool(value).unwrap_or(false),
+            "PAUSED_MARKETS" => config.paused_markets = parse_markets(value),
+            "CLOSED_MARKETS" => config.closed_markets = parse_markets(value),
+            "MARKET_MAX_USD" => config.market_max_usd = parse_market_caps(value),
+            "MARKET_BLACKLIST" => config.market_blacklist = parse_markets(value),
+            "COPY_MIN_PRICE" => config.copy_min_price = num.filter(|p| *p > 0.0 && *p < 1.0),
+            "COPY_MAX_PRICE" => config.copy_max_price = num.filter(|p| *p > 0.0 && *p < 1.0),
+            _ => {}
+        }
+    }
+    config
+}
+
+// Current effective config for a running service
+pub fn effective_config(base: &EnvConfig) -> EnvConfig {
+    apply_overrides(base, &active_overrides())
+}
@@ -0,0 +1,293 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mongodb::bson::{doc, oid::ObjectId, Document};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::config::{EnvConfig, StorageBackend};
+use crate::engine::{emit, EngineEvent};
+use crate::types::{
+    Annotation, AnnotationKind, BackfillCursor, BotOrder, CapitalSample, DbSelfTest, LogEvent, PaperTrade, SkipReason, UserActivity,
+    UserPosition, WalletInflow,
+};
+use crate::utils::{config_cipher, is_encrypted_config_value};
+
+mod mongo;
+mod sqlite;
+
+pub use mongo::MongoStore;
+pub use sqlite::SqliteStore;
+
+// Everything the bot and its tools persist. Both backends keep the same documents under the same collection
+// names (user_activities_<trader>, configs, annotations, bot_orders...), so a backup taken from one restores
+// into the other. Records stay BSON-shaped (ObjectId ids, bson dates) whichever backend holds them
+#[async_trait]
+pub trait TradeStore: Send + Sync {
+    // Count total activities for a trader
+    async fn count_activities(&self, user_address: &str) -> Result<u64>;
+    // Insert new trade activity
+    async fn insert_activity(&self, user_address: &str, activity: &UserActivity) -> Result<()>;
+    // Find activity by tx hash (duplicate check)
+    async fn find_activity_by_tx(&self, user_address: &str, transaction_hash: &str) -> Result<Option<UserActivity>>;
+    // Find unprocessed trades (not executed by bot yet)
+    async fn find_unprocessed_trades(&self, user_address: &str) -> Result<Vec<UserActivity>>;
+    // Trades seen but not yet handled by the executor (the copy pipeline's backlog)
+    async fn count_unprocessed_trades(&self, user_address: &str) -> Result<u64>;
+    // Round trip to the store (self-monitoring times this)
+    async fn ping(&self) -> Result<()>;
+    // Write / read / delete round trip, latency, then ensure_indexes
+    async fn self_test(&self, user_addresses: &[String], max_latency_ms: u64) -> Result<DbSelfTest>;
+    // Indexes behind the hot queries (idempotent); returns how many were created
+    async fn ensure_indexes(&self, user_addresses: &[String]) -> Result<usize>;
+    // $set the fields of `update` on one activity
+    async fn update_activity(&self, user_address: &str, id: &ObjectId, update: &Document) -> Result<()>;
+    // Count skipped activities per reason, most first (optionally since a unix timestamp in seconds)
+    async fn count_skips(&self, user_address: &str, since_ts: Option<i64>) -> Result<Vec<(String, u64)>>;
+    async fn mark_historical_processed(&self, user_address: &str) -> Result<u64>;
+    async fn upsert_position(&self, user_address: &str, position: &UserPosition) -> Result<()>;
+    async fn get_positions(&self, user_address: &str) -> Result<Vec<UserPosition>>;
+    // Sensitive keys (PRIVATE_KEY, *SECRET*, ...) are stored AES-GCM encrypted; empty = cleared, stored as is
+    async fn set_config(&self, key: &str, value: &str) -> Result<()>;
+    // Latest value per key among `keys` (runtime overrides; empty value = cleared)
+    async fn get_config_overrides(&self, keys: &[&str]) -> Result<Vec<(String, String)>>;
+    // Startup migration: encrypt sensitive config values still stored in plaintext and fingerprint stored
+    // PRIVATE_KEY_n values that have none yet (returns how many documents changed)
+    async fn encrypt_plaintext_configs(&self) -> Result<usize>;
+    async fn find_all_buy_activities_for_asset(
+        &self,
+        user_address: &str,
+        asset: &str,
+        condition_id: &Option<String>,
+    ) -> Result<Vec<UserActivity>>;
+    async fn update_many_activities(
+        &self,
+        user_address: &str,
+        asset: &str,
+        condition_id: &Option<String>,
+        update: &Document,
+    ) -> Result<u64>;
+    async fn get_annotation(&self, kind: AnnotationKind, key: &str) -> Result<Option<Annotation>>;
+    async fn add_tags(&self, kind: AnnotationKind, key: &str, tags: &[String]) -> Result<()>;
+    async fn remove_tags(&self, kind: AnnotationKind, key: &str, tags: &[String]) -> Result<()>;
+    // Empty note clears it
+    async fn set_note(&self, kind: AnnotationKind, key: &str, note: &str) -> Result<()>;
+    // All annotations, optionally only those carrying a tag
+    async fn list_annotations(&self, tag: Option<&str>) -> Result<Vec<Annotation>>;
+    // Union of trader tags and market tags for one activity (copied onto the execution record)
+    async fn tags_for_trade(&self, user_address: &str, trade: &UserActivity) -> Result<Vec<String>>;
+    // Activities the bot executed (historical imports excluded), optionally only those carrying a tag
+    async fn find_copied_activities(&self, user_address: &str, tag: Option<&str>) -> Result<Vec<UserActivity>>;
+    async fn insert_bot_order(&self, order: &BotOrder) -> Result<()>;
+    // Bot order windows, oldest first (since = ms, None = all)
+    async fn get_bot_orders(&self, since: Option<i64>) -> Result<Vec<BotOrder>>;
+    // Backfilled trades since `since` (seconds), oldest first, as the data-api JSON they came from
+    async fn get_history_trades(&self, user_address: &str, since: i64) -> Result<Vec<serde_json::Value>>;
+    async fn get_backfill_cursors(&self) -> Result<Vec<BackfillCursor>>;
+    async fn get_backfill_cursor(&self, trader: &str) -> Result<Option<BackfillCursor>>;
+    async fn save_backfill_cursor(&self, cursor: &BackfillCursor) -> Result<()>;
+    // Upsert trades keyed by tx hash + asset + side (pages overlap at their boundaries); returns how many were new
+    async fn upsert_history_trades(&self, user_address: &str, trades: &[serde_json::Value]) -> Result<u64>;
+    async fn save_wallet_inflows(&self, inflows: &[WalletInflow]) -> Result<()>;
+    // Oldest first; since in seconds
+    async fn get_wallet_inflows(&self, since: Option<i64>) -> Result<Vec<WalletInflow>>;
+    // Last block an incremental chain scan finished ("scan_cursors", one doc per scanner)
+    async fn get_scan_block(&self, scanner: &str) -> Result<Option<u64>>;
+    async fn set_scan_block(&self, scanner: &str, block: u64) -> Result<()>;
+    // Trailing-stop high watermarks by asset ("trailing_stops"), so a restart doesn't reset them
+    async fn get_trailing_highs(&self) -> Result<HashMap<String, f64>>;
+    async fn set_trailing_high(&self, asset: &str, high: f64) -> Result<()>;
+    async fn clear_trailing_high(&self, asset: &str) -> Result<()>;
+    // Newest activity we have for a trader (raw timestamp, seconds or ms as the API sent it)
+    async fn latest_activity_timestamp(&self, user_address: &str) -> Result<Option<i64>>;
+    // Traders flagged dormant ("dormant_traders") -> the trailing-stop tag we added for them, if any
+    async fn get_dormant_traders(&self) -> Result<HashMap<String, Option<String>>>;
+    async fn set_dormant_trader(&self, trader: &str, auto_tag: Option<&str>) -> Result<()>;
+    async fn clear_dormant_trader(&self, trader: &str) -> Result<()>;
+    // When each scheduled digest last went out ("report_runs", one doc per period and channel, e.g. "daily:email")
+    async fn get_report_sent(&self, period: &str) -> Result<Option<String>>;
+    async fn set_report_sent(&self, period: &str, slot: &str) -> Result<()>;
+    // Keep at most max_events lines in "log_events", oldest dropped first
+    async fn ensure_log_events(&self, max_events: u64) -> Result<()>;
+    async fn insert_log_events(&self, events: &[LogEvent]) -> Result<()>;
+    // Newest `limit` lines, oldest first; levels = prefixes to keep (e.g. ERROR, ORDER), empty = all
+    async fn recent_log_events(&self, limit: i64, levels: &[String]) -> Result<Vec<LogEvent>>;
+    async fn insert_capital_sample(&self, sample: &CapitalSample) -> Result<()>;
+    // Samples in [from_ms, to_ms], oldest first
+    async fn get_capital_samples(&self, from_ms: i64, to_ms: i64) -> Result<Vec<CapitalSample>>;
+    // DRY_RUN fills ("paper_trades")
+    async fn insert_paper_trade(&self, trade: &PaperTrade) -> Result<()>;
+    // Oldest first, optionally one trader's only
+    async fn get_paper_trades(&self, trader: Option<&str>) -> Result<Vec<PaperTrade>>;
+    // Every collection in the bot DB, sorted (backup / restore)
+    async fn collection_names(&self) -> Result<Vec<String>>;
+    async fn dump_collection(&self, name: &str) -> Result<Vec<Document>>;
+    async fn drop_collection(&self, name: &str) -> Result<()>;
+    // Upsert by _id (insert when it has none)
+    async fn restore_document(&self, name: &str, document: &Document) -> Result<()>;
+    async fn close(&self) -> Result<()>;
+
+    // Mark activity as handled without copying it, recording why
+    async fn mark_skipped(&self, user_address: &str, id: &ObjectId, reason: SkipReason) -> Result<()> {
+        self.update_activity(
+            user_address,
+            id,
+            &doc! {
+                "bot": true,
+                "skipReason": reason.as_str(),
+                "skippedAt": mongodb::bson::DateTime::now(),
+            },
+        )
+        .await?;
+        emit(EngineEvent::Skipped { trader: user_address.to_string(), reason: reason.as_str().to_string() });
+        Ok(())
+    }
+
+    async fn record_bot_order(&self, order: &BotOrder) -> Result<()> {
+        self.insert_bot_order(order).await?;
+        emit(EngineEvent::Order(order.clone()));
+        Ok(())
+    }
+
+    // Move a trader's history to another address (a configured signer resolved to its proxyWallet): the
+    // per-trader collections are merged into the new ones and dropped, bot orders / paper trades are re-keyed,
+    // the backfill cursor and trader tags / note are carried over. Returns the documents moved
+    async fn migrate_trader(&self, from: &str, to: &str) -> Result<u64> {
+        let (from, to) = (from.to_lowercase(), to.to_lowercase());
+        if from == to {
+            return Ok(0);
+        }
+        let names = self.collection_names().await?;
+        let mut moved = 0;
+
+        for prefix in ["user_activities_", "user_positions_", "history_"] {
+            let (source, target) = (format!("{}{}", prefix, from), format!("{}{}", prefix, to));
+            if !names.contains(&source) {
+                continue;
+            }
+            // Same trade / position already stored under the new address wins (history rows upsert by _id)
+            let key = |d: &Document| match prefix {
+                "user_positions_" => d.get_str("asset").unwrap_or("").to_string(),
+                _ => ["transactionHash", "asset", "side"].map(|f| d.get_str(f).unwrap_or("")).join(":"),
+            };
+            let existing: std::collections::HashSet<String> = if names.contains(&target) {
+                self.dump_collection(&target).await?.iter().map(key).collect()
+            } else {
+                Default::default()
+            };
+            for document in self.dump_collection(&source).await? {
+                if prefix != "history_" && existing.contains(&key(&document)) {
+                    continue;
+                }
+                self.restore_document(&target, &document).await?;
+                moved += 1;
+            }
+            self.drop_collection(&source).await?;
+        }
+
+        for collection in ["bot_orders", "paper_trades"] {
+            if !names.iter().any(|n| n == collection) {
+                continue;
+            }
+            for mut document in self.dump_collection(collection).await? {
+                if !document.get_str("trader").is_ok_and(|t| t.eq_ignore_ascii_case(&from)) {
+                    continue;
+                }
+                document.insert("trader", to.as_str());
+                self.restore_document(collection, &document).await?;
+                moved += 1;
+            }
+        }
+
+        if let Some(mut cursor) = self.get_backfill_cursor(&from).await? {
+            if self.get_backfill_cursor(&to).await?.is_none() {
+                cursor.trader = to.clone();
+                self.save_backfill_cursor(&cursor).await?;
+            }
+        }
+        if let Some(annotation) = self.get_annotation(AnnotationKind::Trader, &from).await? {
+            if !annotation.tags.is_empty() {
+                self.add_tags(AnnotationKind::Trader, &to, &annotation.tags).await?;
+            }
+            let target_note = self.get_annotation(AnnotationKind::Trader, &to).await?.and_then(|a| a.note);
+            if let (Some(note), None) = (annotation.note, target_note) {
+                self.set_note(AnnotationKind::Trader, &to, &note).await?;
+            }
+        }
+        Ok(moved)
+    }
+}
+
+// Handle to whichever store STORAGE_BACKEND picked - cheap to clone, derefs to the TradeStore methods
+#[derive(Clone)]
+pub struct Db {
+    store: Arc<dyn TradeStore>,
+    backend: StorageBackend,
+}
+
+impl Deref for Db {
+    type Target = dyn TradeStore;
+
+    fn deref(&self) -> &Self::Target {
+        self.store.as_ref()
+    }
+}
+
+impl Db {
+    // MongoDB at `uri` (tools that are handed a URI, e.g. the Telegram bot's per-user config)
+    pub async fn connect(uri: &str) -> Result<Self> {
+        Ok(Self { store: Arc::new(MongoStore::connect(uri).await?), backend: StorageBackend::Mongo })
+    }
+
+    // Embedded SQLite file at `path` (created on first use)
+    pub async fn open_sqlite(path: &str) -> Result<Self> {
+        Ok(Self { store: Arc::new(SqliteStore::open(path).await?), backend: StorageBackend::Sqlite })
+    }
+
+    // The store the config points at (STORAGE_BACKEND, MONGO_URI / SQLITE_PATH)
+    pub async fn open(config: &EnvConfig) -> Result<Self> {
+        match config.storage_backend {
+            StorageBackend::Mongo => Self::connect(&config.mongo_uri).await,
+            StorageBackend::Sqlite => Self::open_sqlite(&config.sqlite_path).await,
+        }
+    }
+
+    // Same choice from the storage variables alone, for tools that run before the rest of .env exists
+    pub async fn open_from_env() -> Result<Self> {
+        let backend = StorageBackend::parse(&std::env::var("STORAGE_BACKEND").unwrap_or_default())
+            .ok_or_else(|| anyhow::anyhow!("Invalid STORAGE_BACKEND (mongo or sqlite)"))?;
+        match backend {
+            StorageBackend::Mongo => {
+                let uri = std::env::var("MONGO_URI")
+                    .unwrap_or_else(|_| "mongodb://localhost:27017/polymarket_copytrading".into());
+                Self::connect(&uri).await
+            }
+            StorageBackend::Sqlite => Self::open_sqlite(&StorageBackend::sqlite_path_from_env()).await,
+        }
+    }
+
+    pub fn backend(&self) -> StorageBackend {
+        self.backend
+    }
+}
+
+// Trader keys are lowercase addresses, market keys are kept as given (conditionId / slug)
+pub(crate) fn annotation_key(kind: AnnotationKind, key: &str) -> String {
+    match kind {
+        AnnotationKind::Trader => key.trim().to_lowercase(),
+        AnnotationKind::Market => key.trim().to_string(),
+    }
+}
+
+// PRIVATE_KEY_1, PRIVATE_KEY_2... - where set_config("PRIVATE_KEY") stores each distinct key
+pub(crate) fn is_private_key_slot(key: &str) -> bool {
+    key.strip_prefix("PRIVATE_KEY_").is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+pub(crate) fn decrypt_config_value(stored: &str) -> Result<String> {
+    if is_encrypted_config_value(stored) {
+        config_cipher()?.decrypt(stored)
+    } else {
+        Ok(stored.to_string())
+    }
+}
@@ -0,0 +1,968 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mongodb::{
+    bson::{doc, to_document, Document},
+    options::{FindOneAndUpdateOptions, ReplaceOptions},
+    Client, Collection, Database,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{annotation_key, decrypt_config_value, is_private_key_slot, TradeStore};
+use crate::types::{normalize_tag, Annotation, AnnotationKind, BackfillCursor, BotOrder, CapitalSample, DbSelfTest, LogEvent, PaperTrade, UserActivity, UserPosition, WalletInflow};
+use crate::utils::{config_cipher, is_encrypted_config_value, is_sensitive_config_key};
+
+// MongoDB backend (STORAGE_BACKEND=mongo, the default) - one collection per trader for activities / positions
+#[derive(Clone)]
+pub struct MongoStore {
+    _client: Arc<Client>,
+    db: Database,
+}
+
+impl MongoStore {
+    // Connect to MongoDB (default DB: polymarket_copytrading)
+    pub async fn connect(uri: &str) -> Result<Self> {
+        let client = Client::with_uri_str(uri).await?;
+        let db = client.database("polymarket_copytrading");
+        Ok(Self {
+            _client: Arc::new(client),
+            db: db.clone(),
+        })
+    }
+
+    // Get collection for trader's activities (one per trader)
+    pub fn activity_collection(&self, user_address: &str) -> Collection<UserActivity> {
+        let name = format!("user_activities_{}", user_address.to_lowercase());
+        self.db.collection(&name)
+    }
+
+    // Get collection for trader's positions
+    pub fn position_collection(&self, user_address: &str) -> Collection<UserPosition> {
+        let name = format!("user_positions_{}", user_address.to_lowercase());
+        self.db.collection(&name)
+    }
+
+    pub fn config_collection(&self) -> Collection<mongodb::bson::Document> {
+        self.db.collection("configs")
+    }
+
+    async fn get_next_sequence_number(&self, key_prefix: &str) -> Result<u32> {
+        let coll = self.config_collection();
+        let filter = doc! {
+            "key": { "$regex": format!("^{}_\\d+$", key_prefix) }
+        };
+        let mut cursor = coll.find(filter, None).await?;
+        let mut numbers = Vec::new();
+        while cursor.advance().await? {
+            let doc = cursor.deserialize_current()?;
+            if let Ok(key) = doc.get_str("key") {
+                if let Some(underscore_pos) = key.rfind('_') {
+                    if let Ok(num) = key[underscore_pos + 1..].parse::<u32>() {
+                        numbers.push(num);
+                    }
+                }
+            }
+        }
+        Ok(if numbers.is_empty() {
+            1
+        } else {
+            numbers.iter().max().unwrap() + 1
+        })
+    }
+
+    pub fn annotation_collection(&self) -> Collection<Annotation> {
+        self.db.collection("annotations")
+    }
+
+    async fn upsert_annotation(
+        &self,
+        kind: AnnotationKind,
+        key: &str,
+        update: mongodb::bson::Document,
+    ) -> Result<()> {
+        let filter = doc! { "kind": kind.as_str(), "key": annotation_key(kind, key) };
+        let opts = FindOneAndUpdateOptions::builder().upsert(true).build();
+        self.annotation_collection()
+            .find_one_and_update(filter, update, opts)
+            .await?;
+        Ok(())
+    }
+
+    pub fn bot_order_collection(&self) -> Collection<BotOrder> {
+        self.db.collection("bot_orders")
+    }
+
+    // Raw data-API trades for backfills - kept apart from user_activities_* so the executor never copies them
+    pub fn history_collection(&self, user_address: &str) -> Collection<mongodb::bson::Document> {
+        let name = format!("history_{}", user_address.to_lowercase());
+        self.db.collection(&name)
+    }
+
+    pub fn backfill_cursor_collection(&self) -> Collection<BackfillCursor> {
+        self.db.collection("backfill_cursors")
+    }
+
+    pub fn wallet_inflow_collection(&self) -> Collection<WalletInflow> {
+        self.db.collection("wallet_inflows")
+    }
+
+    pub fn log_event_collection(&self) -> Collection<LogEvent> {
+        self.db.collection("log_events")
+    }
+
+    pub fn capital_sample_collection(&self) -> Collection<CapitalSample> {
+        self.db.collection("capital_samples")
+    }
+
+    pub fn paper_trade_collection(&self) -> Collection<PaperTrade> {
+        self.db.collection("paper_trades")
+    }
+
+    // Untyped handle for backup / restore
+    pub fn raw_collection(&self, name: &str) -> Collection<mongodb::bson::Document> {
+        self.db.collection(name)
+    }
+}
+
+#[async_trait]
+impl TradeStore for MongoStore {
+    // Count total activities for a trader
+    async fn count_activities(&self, user_address: &str) -> Result<u64> {
+        let coll = self.activity_collection(user_address);
+        Ok(coll.estimated_document_count(None).await?)
+    }
+
+    // Insert new trade activity
+    async fn insert_activity(&self, user_address: &str, activity: &UserActivity) -> Result<()> {
+        let coll = self.activity_collection(user_address);
+        coll.insert_one(activity, None).await?;
+        Ok(())
+    }
+
+    // Find activity by tx hash (duplicate check)
+    async fn find_activity_by_tx(
+        &self,
+        user_address: &str,
+        transaction_hash: &str,
+    ) -> Result<Option<UserActivity>> {
+        let coll = self.activity_collection(user_address);
+        let filter = doc! { "transactionHash": transaction_hash };
+        Ok(coll.find_one(filter, None).await?)
+    }
+
+    // Find unprocessed trades (not executed by bot yet)
+    async fn find_unprocessed_trades(&self, user_address: &str) -> Result<Vec<UserActivity>> {
+        let coll = self.activity_collection(user_address);
+        let filter = doc! {
+            "type": "TRADE",
+            "bot": false,
+            "botExcutedTime": 0_i64
+        };
+        let mut cursor = coll.find(filter, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    // Trades seen but not yet handled by the executor (the copy pipeline's backlog)
+    async fn count_unprocessed_trades(&self, user_address: &str) -> Result<u64> {
+        let filter = doc! { "type": "TRADE", "bot": false, "botExcutedTime": 0_i64 };
+        Ok(self.activity_collection(user_address).count_documents(filter, None).await?)
+    }
+
+    // Server round trip (self-monitoring times this)
+    async fn ping(&self) -> Result<()> {
+        self.db.run_command(doc! { "ping": 1 }, None).await?;
+        Ok(())
+    }
+
+    // Write / read / delete round trip in "_selftest", server role + latency, then ensure_indexes
+    // (BTW: Client::with_uri_str connects lazily, so connect() succeeding proves nothing about the server)
+    async fn self_test(&self, user_addresses: &[String], max_latency_ms: u64) -> Result<DbSelfTest> {
+        let mut warnings = Vec::new();
+
+        let started = crate::utils::clock::now_ms();
+        self.ping().await?;
+        let ping_ms = (crate::utils::clock::now_ms() - started).max(0) as u64;
+        if ping_ms > max_latency_ms {
+            warnings.push(format!("ping {} ms (limit {} ms)", ping_ms, max_latency_ms));
+        }
+
+        let hello = self.db.run_command(doc! { "hello": 1 }, None).await?;
+        let writable = hello
+            .get_bool("isWritablePrimary")
+            .or_else(|_| hello.get_bool("ismaster"))
+            .unwrap_or(false);
+        if !writable {
+            anyhow::bail!("connected to a non-primary member - MONGO_URI must reach the primary");
+        }
+        let replica_set = hello.get_str("setName").ok().map(String::from);
+        if replica_set.is_none() {
+            warnings.push("standalone server (no replica set, no failover)".to_string());
+        }
+
+        let started = crate::utils::clock::now_ms();
+        let scratch = self.raw_collection("_selftest");
+        let id = mongodb::bson::oid::ObjectId::new();
+        scratch.insert_one(doc! { "_id": id, "at": started }, None).await?;
+        let read = scratch.find_one(doc! { "_id": id }, None).await?;
+        if read.and_then(|d| d.get_i64("at").ok()) != Some(started) {
+            anyhow::bail!("self-test document did not read back");
+        }
+        if scratch.delete_one(doc! { "_id": id }, None).await?.deleted_count != 1 {
+            anyhow::bail!("self-test document could not be deleted");
+        }
+        let round_trip_ms = (crate::utils::clock::now_ms() - started).max(0) as u64;
+        if round_trip_ms > max_latency_ms * 3 {
+            warnings.push(format!("write/read/delete took {} ms", round_trip_ms));
+        }
+
+        let indexes_created = self.ensure_indexes(user_addresses).await?;
+        Ok(DbSelfTest {
+            ping_ms,
+            round_trip_ms,
+            replica_set,
+            indexes_created,
+            warnings,
+        })
+    }
+
+    // Indexes behind the hot queries (idempotent - existing ones are skipped); returns how many were created
+    async fn ensure_indexes(&self, user_addresses: &[String]) -> Result<usize> {
+        let mut wanted: Vec<(String, mongodb::bson::Document)> = vec![
+            ("configs".to_string(), doc! { "key": 1, "timestamp": -1 }),
+            ("annotations".to_string(), doc! { "kind": 1, "key": 1 }),
+            ("bot_orders".to_string(), doc! { "startedAt": 1 }),
+            ("bot_orders".to_string(), doc! { "finishedAt": 1 }),
+        ];
+        for address in user_addresses {
+            let activities = format!("user_activities_{}", address.to_lowercase());
+            wanted.push((activities.clone(), doc! { "transactionHash": 1 }));
+            wanted.push((activities.clone(), doc! { "type": 1, "bot": 1, "botExcutedTime": 1 }));
+            wanted.push((activities, doc! { "timestamp": -1 }));
+            wanted.push((format!("user_positions_{}", address.to_lowercase()), doc! { "asset": 1, "conditionId": 1 }));
+        }
+
+        let mut created = 0;
+        for (collection, keys) in wanted {
+            let coll = self.raw_collection(&collection);
+            // Server default name (field_dir_...), so an index made by hand with the same keys counts as present
+            let name = keys
+                .iter()
+                .map(|(k, v)| format!("{}_{}", k, v))
+                .collect::<Vec<_>>()
+                .join("_");
+            let existing = coll.list_index_names().await.unwrap_or_default(); // Missing collection = no indexes yet
+            if existing.contains(&name) {
+                continue;
+            }
+            coll.create_index(mongodb::IndexModel::builder().keys(keys).build(), None)
+                .await?;
+            created += 1;
+        }
+        Ok(created)
+    }
+
+    async fn update_activity(
+        &self,
+        user_address: &str,
+        id: &mongodb::bson::oid::ObjectId,
+        update: &mongodb::bson::Document,
+    ) -> Result<()> {
+        let coll = self.activity_collection(user_address);
+        let filter = doc! { "_id": id };
+        coll.update_one(filter, doc! { "$set": update }, None)
+            .await?;
+        Ok(())
+    }
+
+    // Count skipped activities per reason (optionally since a unix timestamp in seconds)
+    async fn count_skips(
+        &self,
+        user_address: &str,
+        since_ts: Option<i64>,
+    ) -> Result<Vec<(String, u64)>> {
+        let coll = self.activity_collection(user_address);
+        let mut match_doc = doc! { "skipReason": { "$exists": true, "$ne": null } };
+        if let Some(ts) = since_ts {
+            match_doc.insert(
+                "skippedAt",
+                doc! { "$gte": mongodb::bson::DateTime::from_millis(ts * 1000) },
+            );
+        }
+        let pipeline = vec![
+            doc! { "$match": match_doc },
+            doc! { "$group": { "_id": "$skipReason", "count": { "$sum": 1 } } },
+            doc! { "$sort": { "count": -1 } },
+        ];
+        let mut cursor = coll.aggregate(pipeline, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            let d = cursor.deserialize_current()?;
+            let reason = d.get_str("_id").unwrap_or("unknown").to_string();
+            let count = d
+                .get_i32("count")
+                .map(|c| c as u64)
+                .or_else(|_| d.get_i64("count").map(|c| c as u64))
+                .unwrap_or(0);
+            out.push((reason, count));
+        }
+        Ok(out)
+    }
+
+    async fn mark_historical_processed(&self, user_address: &str) -> Result<u64> {
+        let coll = self.activity_collection(user_address);
+        let filter = doc! { "bot": false };
+        let update = doc! { "$set": { "bot": true, "botExcutedTime": 999_i64 } };
+        let result = coll.update_many(filter, update, None).await?;
+        Ok(result.modified_count)
+    }
+
+    async fn upsert_position(&self, user_address: &str, position: &UserPosition) -> Result<()> {
+        let coll = self.position_collection(user_address);
+        let filter = doc! {
+            "asset": position.asset.as_deref().unwrap_or(""),
+            "conditionId": position.condition_id.as_deref().unwrap_or("")
+        };
+        let mut set_doc = to_document(position)?;
+        set_doc.remove("_id");
+        let update = doc! { "$set": set_doc };
+        let opts = FindOneAndUpdateOptions::builder().upsert(true).build();
+        coll.find_one_and_update(filter, update, opts).await?;
+        Ok(())
+    }
+
+    async fn get_positions(&self, user_address: &str) -> Result<Vec<UserPosition>> {
+        let coll = self.position_collection(user_address);
+        let mut cursor = coll.find(doc! {}, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    // Sensitive keys (PRIVATE_KEY, *SECRET*, ...) are stored AES-GCM encrypted; empty = cleared, stored as is
+    async fn set_config(&self, key: &str, value: &str) -> Result<()> {
+        let coll = self.config_collection();
+        let mut final_key = key.to_string();
+
+        let mut fingerprint = None;
+
+        if key == "PRIVATE_KEY" {
+            // Ciphertexts are randomized, so match on the stored fingerprint (documents written before
+            // fingerprints existed are decrypted and compared)
+            let fp = config_cipher()?.fingerprint(value);
+            let filter = doc! {
+                "key": { "$regex": "^PRIVATE_KEY_\\d+$" },
+                "$or": [{ "fingerprint": &fp }, { "fingerprint": { "$exists": false } }],
+            };
+            let mut cursor = coll.find(filter, None).await?;
+            while cursor.advance().await? {
+                let stored = cursor.deserialize_current()?;
+                if stored.get_str("fingerprint").is_ok()
+                    || decrypt_config_value(stored.get_str("value").unwrap_or_default())? == value
+                {
+                    return Ok(());
+                }
+            }
+
+            let sequence_number = self.get_next_sequence_number("PRIVATE_KEY").await?;
+            final_key = format!("PRIVATE_KEY_{}", sequence_number);
+            fingerprint = Some(fp);
+        }
+
+        let stored = if is_sensitive_config_key(key) && !value.is_empty() {
+            config_cipher()?.encrypt(value)?
+        } else {
+            value.to_string()
+        };
+        let mut doc = doc! {
+            "key": &final_key,
+            "value": stored,
+            "timestamp": mongodb::bson::DateTime::now(),
+        };
+        if let Some(fp) = fingerprint {
+            doc.insert("fingerprint", fp);
+        }
+        coll.insert_one(doc, None).await?;
+        Ok(())
+    }
+
+    // Latest value per key among `keys` (runtime overrides; empty value = cleared)
+    async fn get_config_overrides(&self, keys: &[&str]) -> Result<Vec<(String, String)>> {
+        let pipeline = vec![
+            doc! { "$match": { "key": { "$in": keys } } },
+            doc! { "$sort": { "timestamp": -1 } },
+            doc! { "$group": { "_id": "$key", "value": { "$first": "$value" } } },
+            doc! { "$sort": { "_id": 1 } },
+        ];
+        let mut cursor = self.config_collection().aggregate(pipeline, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            let d = cursor.deserialize_current()?;
+            let key = d.get_str("_id").unwrap_or_default().to_string();
+            let value = decrypt_config_value(d.get_str("value").unwrap_or_default())?.trim().to_string();
+            if !key.is_empty() && !value.is_empty() {
+                out.push((key, value));
+            }
+        }
+        Ok(out)
+    }
+
+    async fn encrypt_plaintext_configs(&self) -> Result<usize> {
+        let coll = self.config_collection();
+        let mut cursor = coll.find(doc! {}, None).await?;
+        let mut pending = Vec::new();
+        while cursor.advance().await? {
+            let d = cursor.deserialize_current()?;
+            let key = d.get_str("key").unwrap_or_default();
+            let value = d.get_str("value").unwrap_or_default();
+            if !is_sensitive_config_key(key) || value.is_empty() {
+                continue;
+            }
+            let plaintext = !is_encrypted_config_value(value);
+            let unfingerprinted = is_private_key_slot(key) && d.get_str("fingerprint").is_err();
+            if plaintext || unfingerprinted {
+                if let Ok(id) = d.get_object_id("_id") {
+                    pending.push((id, value.to_string(), plaintext, unfingerprinted));
+                }
+            }
+        }
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let cipher = config_cipher()?;
+        for (id, value, plaintext, unfingerprinted) in &pending {
+            let mut set = doc! {};
+            if *plaintext {
+                set.insert("value", cipher.encrypt(value)?);
+            }
+            if *unfingerprinted {
+                set.insert("fingerprint", cipher.fingerprint(&cipher.decrypt(value)?));
+            }
+            coll.update_one(doc! { "_id": id }, doc! { "$set": set }, None).await?;
+        }
+        Ok(pending.len())
+    }
+
+    async fn find_all_buy_activities_for_asset(
+        &self,
+        user_address: &str,
+        asset: &str,
+        condition_id: &Option<String>,
+    ) -> Result<Vec<UserActivity>> {
+        let coll = self.activity_collection(user_address);
+        let mut filter = doc! {
+            "asset": asset,
+            "side": "BUY",
+            "bot": true,
+            "myBoughtSize": { "$exists": true, "$gt": 0.0 }
+        };
+        if let Some(ref cid) = condition_id {
+            filter.insert("conditionId", cid);
+        }
+        let mut cursor = coll.find(filter, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    async fn update_many_activities(
+        &self,
+        user_address: &str,
+        asset: &str,
+        condition_id: &Option<String>,
+        update: &mongodb::bson::Document,
+    ) -> Result<u64> {
+        let coll = self.activity_collection(user_address);
+        let mut filter = doc! {
+            "asset": asset,
+            "side": "BUY",
+            "bot": true,
+            "myBoughtSize": { "$exists": true, "$gt": 0.0 }
+        };
+        if let Some(ref cid) = condition_id {
+            filter.insert("conditionId", cid);
+        }
+        let result = coll.update_many(filter, doc! { "$set": update }, None).await?;
+        Ok(result.modified_count)
+    }
+
+    async fn get_annotation(&self, kind: AnnotationKind, key: &str) -> Result<Option<Annotation>> {
+        let filter = doc! { "kind": kind.as_str(), "key": annotation_key(kind, key) };
+        Ok(self.annotation_collection().find_one(filter, None).await?)
+    }
+
+    async fn add_tags(&self, kind: AnnotationKind, key: &str, tags: &[String]) -> Result<()> {
+        let tags: Vec<String> = tags.iter().map(|t| normalize_tag(t)).filter(|t| !t.is_empty()).collect();
+        self.upsert_annotation(
+            kind,
+            key,
+            doc! {
+                "$addToSet": { "tags": { "$each": tags } },
+                "$set": { "updatedAt": mongodb::bson::DateTime::now() },
+            },
+        )
+        .await
+    }
+
+    async fn remove_tags(&self, kind: AnnotationKind, key: &str, tags: &[String]) -> Result<()> {
+        let tags: Vec<String> = tags.iter().map(|t| normalize_tag(t)).collect();
+        self.upsert_annotation(
+            kind,
+            key,
+            doc! {
+                "$pullAll": { "tags": tags },
+                "$set": { "updatedAt": mongodb::bson::DateTime::now() },
+            },
+        )
+        .await
+    }
+
+    // Empty note clears it
+    async fn set_note(&self, kind: AnnotationKind, key: &str, note: &str) -> Result<()> {
+        let note = note.trim();
+        let note = if note.is_empty() {
+            mongodb::bson::Bson::Null
+        } else {
+            mongodb::bson::Bson::String(note.to_string())
+        };
+        self.upsert_annotation(
+            kind,
+            key,
+            doc! { "$set": { "note": note, "updatedAt": mongodb::bson::DateTime::now() } },
+        )
+        .await
+    }
+
+    // All annotations, optionally only those carrying a tag
+    async fn list_annotations(&self, tag: Option<&str>) -> Result<Vec<Annotation>> {
+        let filter = match tag {
+            Some(t) => doc! { "tags": normalize_tag(t) },
+            None => doc! {},
+        };
+        let mut cursor = self.annotation_collection().find(filter, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    // Union of trader tags and market tags for one activity (copied onto the execution record)
+    async fn tags_for_trade(&self, user_address: &str, trade: &UserActivity) -> Result<Vec<String>> {
+        let market_keys: Vec<&str> = [&trade.condition_id, &trade.slug, &trade.event_slug]
+            .iter()
+            .filter_map(|k| k.as_deref())
+            .filter(|k| !k.is_empty())
+            .collect();
+        let filter = doc! {
+            "$or": [
+                { "kind": AnnotationKind::Trader.as_str(), "key": user_address.to_lowercase() },
+                { "kind": AnnotationKind::Market.as_str(), "key": { "$in": market_keys } },
+            ]
+        };
+        let mut cursor = self.annotation_collection().find(filter, None).await?;
+        let mut tags: Vec<String> = Vec::new();
+        while cursor.advance().await? {
+            let annotation = cursor.deserialize_current()?;
+            for tag in annotation.tags {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        tags.sort();
+        Ok(tags)
+    }
+
+    // Activities the bot executed (historical imports excluded), optionally only those carrying a tag
+    async fn find_copied_activities(
+        &self,
+        user_address: &str,
+        tag: Option<&str>,
+    ) -> Result<Vec<UserActivity>> {
+        let coll = self.activity_collection(user_address);
+        let mut filter = doc! {
+            "type": "TRADE",
+            "botExcutedTime": { "$gt": 0_i64, "$ne": 999_i64 },
+            "skipReason": null,
+        };
+        if let Some(t) = tag {
+            filter.insert("tags", normalize_tag(t));
+        }
+        let mut cursor = coll.find(filter, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    async fn insert_bot_order(&self, order: &BotOrder) -> Result<()> {
+        self.bot_order_collection().insert_one(order, None).await?;
+        Ok(())
+    }
+
+    // Bot order windows, oldest first (since = ms, None = all)
+    async fn get_bot_orders(&self, since: Option<i64>) -> Result<Vec<BotOrder>> {
+        let filter = match since {
+            Some(ms) => doc! { "finishedAt": { "$gte": ms } },
+            None => doc! {},
+        };
+        let opts = mongodb::options::FindOptions::builder()
+            .sort(doc! { "startedAt": 1 })
+            .build();
+        let mut cursor = self.bot_order_collection().find(filter, opts).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    // Backfilled trades since `since` (seconds), oldest first, as the data-api JSON they came from
+    async fn get_history_trades(&self, user_address: &str, since: i64) -> Result<Vec<serde_json::Value>> {
+        let opts = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": 1 })
+            .build();
+        let mut cursor = self
+            .history_collection(user_address)
+            .find(doc! { "timestamp": { "$gte": since } }, opts)
+            .await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            let d: mongodb::bson::Document = cursor.deserialize_current()?;
+            out.push(mongodb::bson::Bson::Document(d).into_relaxed_extjson());
+        }
+        Ok(out)
+    }
+
+    async fn get_backfill_cursors(&self) -> Result<Vec<BackfillCursor>> {
+        let mut cursor = self.backfill_cursor_collection().find(doc! {}, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    async fn get_backfill_cursor(&self, trader: &str) -> Result<Option<BackfillCursor>> {
+        Ok(self
+            .backfill_cursor_collection()
+            .find_one(doc! { "_id": trader.to_lowercase() }, None)
+            .await?)
+    }
+
+    async fn save_backfill_cursor(&self, cursor: &BackfillCursor) -> Result<()> {
+        let opts = mongodb::options::ReplaceOptions::builder().upsert(true).build();
+        self.backfill_cursor_collection()
+            .replace_one(doc! { "_id": &cursor.trader }, cursor, opts)
+            .await?;
+        Ok(())
+    }
+
+    // Upsert trades keyed by tx hash + asset + side (pages overlap at their boundaries); returns how many were new
+    async fn upsert_history_trades(&self, user_address: &str, trades: &[serde_json::Value]) -> Result<u64> {
+        let coll = self.history_collection(user_address);
+        let opts = mongodb::options::UpdateOptions::builder().upsert(true).build();
+        let mut inserted = 0;
+        for trade in trades {
+            let field = |k: &str| trade.get(k).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let id = format!("{}:{}:{}", field("transactionHash"), field("asset"), field("side"));
+            let mut d = to_document(trade)?;
+            d.remove("_id");
+            let res = coll
+                .update_one(doc! { "_id": &id }, doc! { "$setOnInsert": d }, opts.clone())
+                .await?;
+            if res.upserted_id.is_some() {
+                inserted += 1;
+            }
+        }
+        Ok(inserted)
+    }
+
+    async fn save_wallet_inflows(&self, inflows: &[WalletInflow]) -> Result<()> {
+        let opts = mongodb::options::ReplaceOptions::builder().upsert(true).build();
+        for inflow in inflows {
+            self.wallet_inflow_collection()
+                .replace_one(doc! { "_id": &inflow.id }, inflow, opts.clone())
+                .await?;
+        }
+        Ok(())
+    }
+
+    // Oldest first; since in seconds
+    async fn get_wallet_inflows(&self, since: Option<i64>) -> Result<Vec<WalletInflow>> {
+        let filter = match since {
+            Some(ts) => doc! { "timestamp": { "$gte": ts } },
+            None => doc! {},
+        };
+        let opts = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": 1 })
+            .build();
+        let mut cursor = self.wallet_inflow_collection().find(filter, opts).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    // Last block an incremental chain scan finished ("scan_cursors", one doc per scanner)
+    async fn get_scan_block(&self, scanner: &str) -> Result<Option<u64>> {
+        let doc = self
+            .raw_collection("scan_cursors")
+            .find_one(doc! { "_id": scanner }, None)
+            .await?;
+        Ok(doc.and_then(|d| d.get_i64("block").ok()).map(|b| b as u64))
+    }
+
+    async fn set_scan_block(&self, scanner: &str, block: u64) -> Result<()> {
+        let opts = mongodb::options::UpdateOptions::builder().upsert(true).build();
+        self.raw_collection("scan_cursors")
+            .update_one(
+                doc! { "_id": scanner },
+                doc! { "$set": { "block": block as i64, "updatedAt": crate::utils::clock::now_ms() } },
+                opts,
+            )
+            .await?;
+        Ok(())
+    }
+
+    // Trailing-stop high watermarks by asset ("trailing_stops"), so a restart doesn't reset them
+    async fn get_trailing_highs(&self) -> Result<HashMap<String, f64>> {
+        let mut cursor = self.raw_collection("trailing_stops").find(doc! {}, None).await?;
+        let mut out = HashMap::new();
+        while cursor.advance().await? {
+            let d = cursor.deserialize_current()?;
+            if let (Ok(asset), Ok(high)) = (d.get_str("_id"), d.get_f64("high")) {
+                out.insert(asset.to_string(), high);
+            }
+        }
+        Ok(out)
+    }
+
+    async fn set_trailing_high(&self, asset: &str, high: f64) -> Result<()> {
+        let opts = mongodb::options::UpdateOptions::builder().upsert(true).build();
+        self.raw_collection("trailing_stops")
+            .update_one(
+                doc! { "_id": asset },
+                doc! { "$set": { "high": high, "updatedAt": crate::utils::clock::now_ms() } },
+                opts,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn clear_trailing_high(&self, asset: &str) -> Result<()> {
+        self.raw_collection("trailing_stops")
+            .delete_one(doc! { "_id": asset }, None)
+            .await?;
+        Ok(())
+    }
+
+    // Newest activity we have for a trader (raw timestamp, seconds or ms as the API sent it)
+    async fn latest_activity_timestamp(&self, user_address: &str) -> Result<Option<i64>> {
+        let opts = mongodb::options::FindOneOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .build();
+        let latest = self
+            .activity_collection(user_address)
+            .find_one(doc! { "timestamp": { "$exists": true } }, opts)
+            .await?;
+        Ok(latest.and_then(|a| a.timestamp))
+    }
+
+    // Traders flagged dormant ("dormant_traders") -> the trailing-stop tag we added for them, if any
+    async fn get_dormant_traders(&self) -> Result<HashMap<String, Option<String>>> {
+        let mut cursor = self.raw_collection("dormant_traders").find(doc! {}, None).await?;
+        let mut out = HashMap::new();
+        while cursor.advance().await? {
+            let d = cursor.deserialize_current()?;
+            if let Ok(trader) = d.get_str("_id") {
+                out.insert(trader.to_string(), d.get_str("autoTag").ok().map(String::from));
+            }
+        }
+        Ok(out)
+    }
+
+    async fn set_dormant_trader(&self, trader: &str, auto_tag: Option<&str>) -> Result<()> {
+        let opts = mongodb::options::UpdateOptions::builder().upsert(true).build();
+        self.raw_collection("dormant_traders")
+            .update_one(
+                doc! { "_id": trader.to_lowercase() },
+                doc! { "$set": { "autoTag": auto_tag, "since": crate::utils::clock::now_ms() } },
+                opts,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn clear_dormant_trader(&self, trader: &str) -> Result<()> {
+        self.raw_collection("dormant_traders")
+            .delete_one(doc! { "_id": trader.to_lowercase() }, None)
+            .await?;
+        Ok(())
+    }
+
+    // When each scheduled digest last went out ("report_runs", one doc per period key, e.g. "daily")
+    async fn get_report_sent(&self, period: &str) -> Result<Option<String>> {
+        let doc = self
+            .raw_collection("report_runs")
+            .find_one(doc! { "_id": period }, None)
+            .await?;
+        Ok(doc.and_then(|d| d.get_str("slot").ok().map(String::from)))
+    }
+
+    async fn set_report_sent(&self, period: &str, slot: &str) -> Result<()> {
+        let opts = mongodb::options::UpdateOptions::builder().upsert(true).build();
+        self.raw_collection("report_runs")
+            .update_one(
+                doc! { "_id": period },
+                doc! { "$set": { "slot": slot, "sentAt": crate::utils::clock::now_ms() } },
+                opts,
+            )
+            .await?;
+        Ok(())
+    }
+
+    // Create "log_events" as a capped collection - Mongo drops the oldest lines past max_events
+    // (an existing collection is left as it is, drop it to change the cap)
+    async fn ensure_log_events(&self, max_events: u64) -> Result<()> {
+        let existing = self
+            .db
+            .list_collection_names(doc! { "name": "log_events" })
+            .await?;
+        if existing.is_empty() {
+            let opts = mongodb::options::CreateCollectionOptions::builder()
+                .capped(true)
+                .max(max_events)
+                .size(max_events.saturating_mul(1024).max(4096)) // ~1 KB per line
+                .build();
+            self.db.create_collection("log_events", opts).await?;
+        }
+        Ok(())
+    }
+
+    async fn insert_log_events(&self, events: &[LogEvent]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.log_event_collection().insert_many(events, None).await?;
+        Ok(())
+    }
+
+    // Newest `limit` lines, oldest first; levels = prefixes to keep (e.g. ERROR, ORDER), empty = all
+    async fn recent_log_events(&self, limit: i64, levels: &[String]) -> Result<Vec<LogEvent>> {
+        let filter = if levels.is_empty() {
+            doc! {}
+        } else {
+            let prefixes: Vec<String> = levels
+                .iter()
+                .map(|l| l.chars().filter(|c| c.is_ascii_alphabetic()).collect::<String>().to_uppercase())
+                .filter(|l| !l.is_empty())
+                .collect();
+            doc! { "level": { "$regex": format!("^({})", prefixes.join("|")) } }
+        };
+        let opts = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .limit(limit)
+            .build();
+        let mut cursor = self.log_event_collection().find(filter, opts).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        out.reverse();
+        Ok(out)
+    }
+
+    async fn insert_capital_sample(&self, sample: &CapitalSample) -> Result<()> {
+        self.capital_sample_collection().insert_one(sample, None).await?;
+        Ok(())
+    }
+
+    // Samples in [from_ms, to_ms], oldest first
+    async fn get_capital_samples(&self, from_ms: i64, to_ms: i64) -> Result<Vec<CapitalSample>> {
+        let opts = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": 1 })
+            .build();
+        let mut cursor = self
+            .capital_sample_collection()
+            .find(doc! { "timestamp": { "$gte": from_ms, "$lte": to_ms } }, opts)
+            .await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    async fn insert_paper_trade(&self, trade: &PaperTrade) -> Result<()> {
+        self.paper_trade_collection().insert_one(trade, None).await?;
+        Ok(())
+    }
+
+    async fn get_paper_trades(&self, trader: Option<&str>) -> Result<Vec<PaperTrade>> {
+        let filter = match trader {
+            Some(t) => doc! { "trader": t.to_lowercase() },
+            None => doc! {},
+        };
+        let opts = mongodb::options::FindOptions::builder()
+            .sort(doc! { "timestamp": 1 })
+            .build();
+        let mut cursor = self.paper_trade_collection().find(filter, opts).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    // Every collection in the bot DB (activities/positions per trader, configs, annotations...)
+    async fn collection_names(&self) -> Result<Vec<String>> {
+        let mut names = self.db.list_collection_names(None).await?;
+        names.retain(|n| !n.starts_with("system."));
+        names.sort();
+        Ok(names)
+    }
+
+    async fn dump_collection(&self, name: &str) -> Result<Vec<Document>> {
+        let mut cursor = self.raw_collection(name).find(doc! {}, None).await?;
+        let mut out = Vec::new();
+        while cursor.advance().await? {
+            out.push(cursor.deserialize_current()?);
+        }
+        Ok(out)
+    }
+
+    async fn drop_collection(&self, name: &str) -> Result<()> {
+        self.raw_collection(name).drop(None).await?;
+        Ok(())
+    }
+
+    async fn restore_document(&self, name: &str, document: &Document) -> Result<()> {
+        let coll = self.raw_collection(name);
+        match document.get("_id").cloned() {
+            Some(id) => {
+                let upsert = ReplaceOptions::builder().upsert(true).build();
+                coll.replace_one(doc! { "_id": id }, document, upsert).await?;
+            }
+            None => {
+                coll.insert_one(document, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
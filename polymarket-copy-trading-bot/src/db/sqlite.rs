@@ -0,0 +1,976 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mongodb::bson::{doc, from_bson, oid::ObjectId, to_document, Bson, Document};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::{annotation_key, decrypt_config_value, is_private_key_slot, TradeStore};
+use crate::types::{normalize_tag, Annotation, AnnotationKind, BackfillCursor, BotOrder, CapitalSample, DbSelfTest, LogEvent, PaperTrade, UserActivity, UserPosition, WalletInflow};
+use crate::utils::{config_cipher, is_encrypted_config_value, is_sensitive_config_key};
+
+// One table for every collection: each row is a Mongo-shaped document as relaxed extended JSON, keyed by
+// (collection, _id). Queries read fields with json_extract; the hot ones are backed by the expression indexes below
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS documents (
+    collection TEXT NOT NULL,
+    id TEXT NOT NULL,
+    doc TEXT NOT NULL,
+    PRIMARY KEY (collection, id)
+)";
+
+// (name, target) - a query only uses an index when it spells the expression exactly the same way
+const INDEXES: &[(&str, &str)] = &[
+    ("documents_tx", "documents (collection, json_extract(doc, '$.transactionHash'))"),
+    (
+        "documents_pending",
+        "documents (collection, json_extract(doc, '$.type'), json_extract(doc, '$.bot'), json_extract(doc, '$.botExcutedTime'))",
+    ),
+    ("documents_timestamp", "documents (collection, json_extract(doc, '$.timestamp'))"),
+    ("documents_key", "documents (collection, json_extract(doc, '$.key'))"),
+];
+
+// Trades the executor hasn't handled yet (JSON booleans read back as 0 / 1)
+const PENDING: &str = "json_extract(doc, '$.type') = 'TRADE' AND json_extract(doc, '$.bot') = 0 \
+     AND json_extract(doc, '$.botExcutedTime') = 0";
+// Copied buys of one asset that still hold tokens
+const HELD_BUYS: &str = "json_extract(doc, '$.asset') = ? AND json_extract(doc, '$.side') = 'BUY' \
+     AND json_extract(doc, '$.bot') = 1 AND json_extract(doc, '$.myBoughtSize') > 0";
+
+// Embedded SQLite backend (STORAGE_BACKEND=sqlite) - the whole DB is one file, no server to run.
+// rusqlite is blocking, so every call runs on tokio's blocking pool behind one connection
+#[derive(Clone)]
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+    log_events_max: Arc<AtomicU64>, // 0 = uncapped (ensure_log_events not called)
+}
+
+impl SqliteStore {
+    // Open (or create) the database file and its schema
+    pub async fn open(path: &str) -> Result<Self> {
+        let path = path.to_string();
+        let conn = tokio::task::spawn_blocking(move || -> Result<Connection> {
+            if let Some(dir) = std::path::Path::new(&path).parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            let conn = Connection::open(&path)?;
+            conn.busy_timeout(Duration::from_secs(5))?; // A CLI tool reading while the bot writes
+            conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+            conn.execute(SCHEMA, [])?;
+            Ok(conn)
+        })
+        .await??;
+        let store = Self { conn: Arc::new(Mutex::new(conn)), log_events_max: Arc::new(AtomicU64::new(0)) };
+        store.ensure_indexes(&[]).await?;
+        Ok(store)
+    }
+
+    async fn call<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|_| anyhow::anyhow!("SQLite connection lock poisoned"))?;
+            f(&mut conn)
+        })
+        .await?
+    }
+
+    // Create the annotation if it doesn't exist yet (like Mongo's upsert), then change it
+    async fn upsert_annotation<F>(&self, kind: AnnotationKind, key: &str, change: F) -> Result<()>
+    where
+        F: FnOnce(&mut Value) + Send + 'static,
+    {
+        let key = annotation_key(kind, key);
+        self.call(move |c| {
+            upsert_one(
+                c,
+                "annotations",
+                "json_extract(doc, '$.kind') = ? AND json_extract(doc, '$.key') = ?",
+                vec![text(kind.as_str()), text(&key)],
+                json!({ "kind": kind.as_str(), "key": key }),
+                change,
+            )
+        })
+        .await
+    }
+
+    // Merge fields into the document with this string _id, creating it if missing
+    async fn upsert_by_id(&self, collection: &'static str, id: &str, fields: Value) -> Result<()> {
+        let id = id.to_string();
+        self.call(move |c| {
+            upsert_one(c, collection, "id = ?", vec![text(&id)], json!({ "_id": id }), |d| merge(d, &fields))
+        })
+        .await
+    }
+
+    async fn get_by_id(&self, collection: &'static str, id: &str) -> Result<Option<Value>> {
+        let id = id.to_string();
+        self.call(move |c| Ok(find(c, collection, "id = ?", vec![text(&id)], "")?.into_iter().next().map(|(_, d)| d)))
+            .await
+    }
+
+    async fn delete_by_id(&self, collection: &'static str, id: &str) -> Result<()> {
+        let id = id.to_string();
+        self.call(move |c| {
+            c.execute("DELETE FROM documents WHERE collection = ?1 AND id = ?2", params![collection, id])?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn all(&self, collection: &str) -> Result<Vec<(String, Value)>> {
+        let collection = collection.to_string();
+        self.call(move |c| find(c, &collection, "1", vec![], "ORDER BY rowid")).await
+    }
+}
+
+fn activities(user_address: &str) -> String {
+    format!("user_activities_{}", user_address.to_lowercase())
+}
+
+fn positions(user_address: &str) -> String {
+    format!("user_positions_{}", user_address.to_lowercase())
+}
+
+fn history(user_address: &str) -> String {
+    format!("history_{}", user_address.to_lowercase())
+}
+
+fn text(s: &str) -> SqlValue {
+    SqlValue::Text(s.to_string())
+}
+
+// Through BSON, so ObjectIds, dates and int64s are written the way a Mongo export would write them
+fn encode<T: Serialize>(value: &T) -> Result<Value> {
+    Ok(Bson::Document(to_document(value)?).into_relaxed_extjson())
+}
+
+fn decode<T: DeserializeOwned>(doc: Value) -> Result<T> {
+    Ok(from_bson(Bson::try_from(doc)?)?)
+}
+
+fn now_date() -> Value {
+    Bson::DateTime(mongodb::bson::DateTime::now()).into_relaxed_extjson()
+}
+
+// The row key for a document's _id: ObjectId hex, the string itself, or the JSON of anything else
+fn id_text(doc: &Value) -> Option<String> {
+    match doc.get("_id")? {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Object(o) if o.contains_key("$oid") => o.get("$oid").and_then(|v| v.as_str()).map(String::from),
+        other => Some(other.to_string()),
+    }
+}
+
+// A new document keeps its _id, or gets a fresh ObjectId like Mongo would assign
+fn with_id(mut doc: Value) -> (String, Value) {
+    if id_text(&doc).is_none() {
+        if let Value::Object(fields) = &mut doc {
+            fields.insert("_id".to_string(), json!({ "$oid": ObjectId::new().to_hex() }));
+        }
+    }
+    (id_text(&doc).unwrap_or_default(), doc)
+}
+
+// $set: copy every field of `fields` onto the document (dotted names reach into nested documents)
+fn merge(doc: &mut Value, fields: &Value) {
+    let Some(fields) = fields.as_object() else {
+        return;
+    };
+    for (name, value) in fields {
+        if name == "_id" {
+            continue;
+        }
+        let mut target = &mut *doc;
+        let mut path: Vec<&str> = name.split('.').collect();
+        let last = path.pop().unwrap_or_default();
+        for part in path {
+            if !target.get(part).is_some_and(|v| v.is_object()) {
+                target[part] = json!({});
+            }
+            target = &mut target[part];
+        }
+        target[last] = value.clone();
+    }
+}
+
+// Documents of one collection matching `filter` (SQL over the JSON, ? placeholders filled from args, then
+// any placeholders in `tail`)
+fn find(conn: &Connection, collection: &str, filter: &str, args: Vec<SqlValue>, tail: &str) -> Result<Vec<(String, Value)>> {
+    let sql = format!("SELECT id, doc FROM documents WHERE collection = ? AND ({}) {}", filter, tail);
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let mut bound = vec![text(collection)];
+    bound.extend(args);
+    let rows = stmt.query_map(params_from_iter(bound), |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut out = Vec::new();
+    for row in rows {
+        let (id, doc) = row?;
+        out.push((id, serde_json::from_str(&doc)?));
+    }
+    Ok(out)
+}
+
+fn find_typed<T: DeserializeOwned>(
+    conn: &Connection,
+    collection: &str,
+    filter: &str,
+    args: Vec<SqlValue>,
+    tail: &str,
+) -> Result<Vec<T>> {
+    find(conn, collection, filter, args, tail)?.into_iter().map(|(_, d)| decode(d)).collect()
+}
+
+fn count(conn: &Connection, collection: &str, filter: &str, args: Vec<SqlValue>) -> Result<u64> {
+    let sql = format!("SELECT COUNT(*) FROM documents WHERE collection = ? AND ({})", filter);
+    let mut bound = vec![text(collection)];
+    bound.extend(args);
+    let n: i64 = conn.query_row(&sql, params_from_iter(bound), |row| row.get(0))?;
+    Ok(n.max(0) as u64)
+}
+
+fn put(conn: &Connection, collection: &str, id: &str, doc: &Value) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO documents (collection, id, doc) VALUES (?1, ?2, ?3)",
+        params![collection, id, doc.to_string()],
+    )?;
+    Ok(())
+}
+
+// Insert a new document (an _id that already exists is an error, as in Mongo)
+fn insert(conn: &Connection, collection: &str, doc: Value) -> Result<()> {
+    let (id, doc) = with_id(doc);
+    conn.execute(
+        "INSERT INTO documents (collection, id, doc) VALUES (?1, ?2, ?3)",
+        params![collection, id, doc.to_string()],
+    )?;
+    Ok(())
+}
+
+// Change the first document matching the filter, or `seed` (plus a new _id) when nothing matches
+fn upsert_one<F: FnOnce(&mut Value)>(
+    conn: &Connection,
+    collection: &str,
+    filter: &str,
+    args: Vec<SqlValue>,
+    seed: Value,
+    change: F,
+) -> Result<()> {
+    let (id, mut doc) = match find(conn, collection, filter, args, "LIMIT 1")?.into_iter().next() {
+        Some(found) => found,
+        None => with_id(seed),
+    };
+    change(&mut doc);
+    put(conn, collection, &id, &doc)
+}
+
+#[async_trait]
+impl TradeStore for SqliteStore {
+    async fn count_activities(&self, user_address: &str) -> Result<u64> {
+        let coll = activities(user_address);
+        self.call(move |c| count(c, &coll, "1", vec![])).await
+    }
+
+    async fn insert_activity(&self, user_address: &str, activity: &UserActivity) -> Result<()> {
+        let (coll, doc) = (activities(user_address), encode(activity)?);
+        self.call(move |c| insert(c, &coll, doc)).await
+    }
+
+    async fn find_activity_by_tx(&self, user_address: &str, transaction_hash: &str) -> Result<Option<UserActivity>> {
+        let (coll, tx) = (activities(user_address), text(transaction_hash));
+        let found = self
+            .call(move |c| find(c, &coll, "json_extract(doc, '$.transactionHash') = ?", vec![tx], "LIMIT 1"))
+            .await?;
+        found.into_iter().next().map(|(_, d)| decode(d)).transpose()
+    }
+
+    async fn find_unprocessed_trades(&self, user_address: &str) -> Result<Vec<UserActivity>> {
+        let coll = activities(user_address);
+        self.call(move |c| find_typed(c, &coll, PENDING, vec![], "ORDER BY rowid")).await
+    }
+
+    async fn count_unprocessed_trades(&self, user_address: &str) -> Result<u64> {
+        let coll = activities(user_address);
+        self.call(move |c| count(c, &coll, PENDING, vec![])).await
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.call(|c| {
+            c.query_row("SELECT 1", [], |_| Ok(()))?;
+            Ok(())
+        })
+        .await
+    }
+
+    // Same checks as the Mongo backend minus the server role (there is no server)
+    async fn self_test(&self, user_addresses: &[String], max_latency_ms: u64) -> Result<DbSelfTest> {
+        let mut warnings = Vec::new();
+
+        let started = crate::utils::clock::now_ms();
+        self.ping().await?;
+        let ping_ms = (crate::utils::clock::now_ms() - started).max(0) as u64;
+        if ping_ms > max_latency_ms {
+            warnings.push(format!("ping {} ms (limit {} ms)", ping_ms, max_latency_ms));
+        }
+
+        let started = crate::utils::clock::now_ms();
+        let id = ObjectId::new().to_hex();
+        self.call(move |c| {
+            insert(c, "_selftest", json!({ "_id": { "$oid": id }, "at": started }))?;
+            let read = find(c, "_selftest", "id = ?", vec![text(&id)], "")?;
+            if read.first().and_then(|(_, d)| d.get("at")).and_then(|v| v.as_i64()) != Some(started) {
+                anyhow::bail!("self-test document did not read back");
+            }
+            let deleted = c.execute("DELETE FROM documents WHERE collection = '_selftest' AND id = ?1", params![id])?;
+            if deleted != 1 {
+                anyhow::bail!("self-test document could not be deleted");
+            }
+            Ok(())
+        })
+        .await?;
+        let round_trip_ms = (crate::utils::clock::now_ms() - started).max(0) as u64;
+        if round_trip_ms > max_latency_ms * 3 {
+            warnings.push(format!("write/read/delete took {} ms", round_trip_ms));
+        }
+
+        let indexes_created = self.ensure_indexes(user_addresses).await?;
+        Ok(DbSelfTest {
+            ping_ms,
+            round_trip_ms,
+            replica_set: None,
+            indexes_created,
+            warnings,
+        })
+    }
+
+    // One table, so the indexes cover every trader at once
+    async fn ensure_indexes(&self, _user_addresses: &[String]) -> Result<usize> {
+        self.call(|c| {
+            let mut created = 0;
+            for (name, target) in INDEXES {
+                let exists: i64 = c.query_row(
+                    "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = ?1",
+                    params![name],
+                    |row| row.get(0),
+                )?;
+                if exists == 0 {
+                    c.execute(&format!("CREATE INDEX IF NOT EXISTS {} ON {}", name, target), [])?;
+                    created += 1;
+                }
+            }
+            Ok(created)
+        })
+        .await
+    }
+
+    async fn update_activity(&self, user_address: &str, id: &ObjectId, update: &Document) -> Result<()> {
+        let (coll, id, fields) = (activities(user_address), id.to_hex(), encode(update)?);
+        self.call(move |c| {
+            if let Some((id, mut doc)) = find(c, &coll, "id = ?", vec![text(&id)], "")?.into_iter().next() {
+                merge(&mut doc, &fields);
+                put(c, &coll, &id, &doc)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn count_skips(&self, user_address: &str, since_ts: Option<i64>) -> Result<Vec<(String, u64)>> {
+        let coll = activities(user_address);
+        let skipped = self
+            .call(move |c| find(c, &coll, "json_extract(doc, '$.skipReason') IS NOT NULL", vec![], ""))
+            .await?;
+        let mut counts: HashMap<String, u64> = HashMap::new();
+        for (_, d) in skipped {
+            if let Some(ts) = since_ts {
+                let at = d.get("skippedAt").cloned().map(decode::<mongodb::bson::DateTime>).transpose().ok().flatten();
+                if !at.is_some_and(|at| at.timestamp_millis() >= ts * 1000) {
+                    continue;
+                }
+            }
+            let reason = d.get("skipReason").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
+            *counts.entry(reason).or_insert(0) += 1;
+        }
+        let mut out: Vec<(String, u64)> = counts.into_iter().collect();
+        out.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(out)
+    }
+
+    async fn mark_historical_processed(&self, user_address: &str) -> Result<u64> {
+        let coll = activities(user_address);
+        self.call(move |c| {
+            let changed = c.execute(
+                "UPDATE documents SET doc = json_set(doc, '$.bot', json('true'), '$.botExcutedTime', 999) \
+                 WHERE collection = ?1 AND json_extract(doc, '$.bot') = 0",
+                params![coll],
+            )?;
+            Ok(changed as u64)
+        })
+        .await
+    }
+
+    async fn upsert_position(&self, user_address: &str, position: &UserPosition) -> Result<()> {
+        let coll = positions(user_address);
+        let asset = position.asset.clone().unwrap_or_default();
+        let condition_id = position.condition_id.clone().unwrap_or_default();
+        let fields = encode(position)?;
+        self.call(move |c| {
+            upsert_one(
+                c,
+                &coll,
+                "json_extract(doc, '$.asset') = ? AND json_extract(doc, '$.conditionId') = ?",
+                vec![text(&asset), text(&condition_id)],
+                json!({ "asset": asset, "conditionId": condition_id }),
+                |d| merge(d, &fields),
+            )
+        })
+        .await
+    }
+
+    async fn get_positions(&self, user_address: &str) -> Result<Vec<UserPosition>> {
+        let coll = positions(user_address);
+        self.call(move |c| find_typed(c, &coll, "1", vec![], "ORDER BY rowid")).await
+    }
+
+    async fn set_config(&self, key: &str, value: &str) -> Result<()> {
+        let (key, value) = (key.to_string(), value.to_string());
+        self.call(move |c| {
+            let mut final_key = key.clone();
+            let mut fingerprint = None;
+            if key == "PRIVATE_KEY" {
+                // Ciphertexts are randomized, so match on the stored fingerprint (documents written before
+                // fingerprints existed are decrypted and compared)
+                let fp = config_cipher()?.fingerprint(&value);
+                let mut numbers = Vec::new();
+                for (_, d) in find(c, "configs", "json_extract(doc, '$.key') GLOB 'PRIVATE_KEY_[0-9]*'", vec![], "")? {
+                    let stored_key = d.get("key").and_then(|v| v.as_str()).unwrap_or_default();
+                    let Ok(n) = stored_key.trim_start_matches("PRIVATE_KEY_").parse::<u32>() else {
+                        continue;
+                    };
+                    let same = match d.get("fingerprint").and_then(|v| v.as_str()) {
+                        Some(stored_fp) => stored_fp == fp,
+                        None => decrypt_config_value(d.get("value").and_then(|v| v.as_str()).unwrap_or_default())? == value,
+                    };
+                    if same {
+                        return Ok(());
+                    }
+                    numbers.push(n);
+                }
+                final_key = format!("PRIVATE_KEY_{}", numbers.iter().max().map(|n| n + 1).unwrap_or(1));
+                fingerprint = Some(fp);
+            }
+
+            let stored = if is_sensitive_config_key(&key) && !value.is_empty() {
+                config_cipher()?.encrypt(&value)?
+            } else {
+                value
+            };
+            let mut doc = doc! {
+                "key": &final_key,
+                "value": stored,
+                "timestamp": mongodb::bson::DateTime::now(),
+            };
+            if let Some(fp) = fingerprint {
+                doc.insert("fingerprint", fp);
+            }
+            insert(c, "configs", encode(&doc)?)
+        })
+        .await
+    }
+
+    async fn get_config_overrides(&self, keys: &[&str]) -> Result<Vec<(String, String)>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let filter = format!("json_extract(doc, '$.key') IN ({})", vec!["?"; keys.len()].join(", "));
+        let args: Vec<SqlValue> = keys.iter().map(|k| text(k)).collect();
+        let rows = self.call(move |c| find(c, "configs", &filter, args, "ORDER BY rowid")).await?;
+        // Written in order, so the last row per key is the newest
+        let mut latest: BTreeMap<String, String> = BTreeMap::new();
+        for (_, d) in rows {
+            if let (Some(key), Some(value)) = (d.get("key").and_then(|v| v.as_str()), d.get("value").and_then(|v| v.as_str())) {
+                latest.insert(key.to_string(), value.to_string());
+            }
+        }
+        let mut out = Vec::new();
+        for (key, stored) in latest {
+            let value = decrypt_config_value(&stored)?.trim().to_string();
+            if !key.is_empty() && !value.is_empty() {
+                out.push((key, value));
+            }
+        }
+        Ok(out)
+    }
+
+    async fn encrypt_plaintext_configs(&self) -> Result<usize> {
+        self.call(|c| {
+            let pending: Vec<(String, Value)> = find(c, "configs", "1", vec![], "")?
+                .into_iter()
+                .filter(|(_, d)| {
+                    let key = d.get("key").and_then(|v| v.as_str()).unwrap_or_default();
+                    let value = d.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+                    is_sensitive_config_key(key)
+                        && !value.is_empty()
+                        && (!is_encrypted_config_value(value) || (is_private_key_slot(key) && d.get("fingerprint").is_none()))
+                })
+                .collect();
+            if pending.is_empty() {
+                return Ok(0);
+            }
+            let cipher = config_cipher()?;
+            for (id, mut d) in pending.clone() {
+                let key = d.get("key").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                let value = d.get("value").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                if !is_encrypted_config_value(&value) {
+                    d["value"] = Value::String(cipher.encrypt(&value)?);
+                }
+                if is_private_key_slot(&key) && d.get("fingerprint").is_none() {
+                    d["fingerprint"] = Value::String(cipher.fingerprint(&cipher.decrypt(&value)?));
+                }
+                put(c, "configs", &id, &d)?;
+            }
+            Ok(pending.len())
+        })
+        .await
+    }
+
+    async fn find_all_buy_activities_for_asset(
+        &self,
+        user_address: &str,
+        asset: &str,
+        condition_id: &Option<String>,
+    ) -> Result<Vec<UserActivity>> {
+        let coll = activities(user_address);
+        let mut filter = HELD_BUYS.to_string();
+        let mut args = vec![text(asset)];
+        if let Some(cid) = condition_id {
+            filter.push_str(" AND json_extract(doc, '$.conditionId') = ?");
+            args.push(text(cid));
+        }
+        self.call(move |c| find_typed(c, &coll, &filter, args, "ORDER BY rowid")).await
+    }
+
+    async fn update_many_activities(
+        &self,
+        user_address: &str,
+        asset: &str,
+        condition_id: &Option<String>,
+        update: &Document,
+    ) -> Result<u64> {
+        let (coll, fields) = (activities(user_address), encode(update)?);
+        let mut filter = HELD_BUYS.to_string();
+        let mut args = vec![text(asset)];
+        if let Some(cid) = condition_id {
+            filter.push_str(" AND json_extract(doc, '$.conditionId') = ?");
+            args.push(text(cid));
+        }
+        self.call(move |c| {
+            let tx = c.transaction()?;
+            let mut modified = 0;
+            for (id, mut doc) in find(&tx, &coll, &filter, args, "")? {
+                let before = doc.clone();
+                merge(&mut doc, &fields);
+                if doc != before {
+                    put(&tx, &coll, &id, &doc)?;
+                    modified += 1;
+                }
+            }
+            tx.commit()?;
+            Ok(modified)
+        })
+        .await
+    }
+
+    async fn get_annotation(&self, kind: AnnotationKind, key: &str) -> Result<Option<Annotation>> {
+        let args = vec![text(kind.as_str()), text(&annotation_key(kind, key))];
+        let found = self
+            .call(move |c| {
+                find(c, "annotations", "json_extract(doc, '$.kind') = ? AND json_extract(doc, '$.key') = ?", args, "LIMIT 1")
+            })
+            .await?;
+        found.into_iter().next().map(|(_, d)| decode(d)).transpose()
+    }
+
+    async fn add_tags(&self, kind: AnnotationKind, key: &str, tags: &[String]) -> Result<()> {
+        let tags: Vec<String> = tags.iter().map(|t| normalize_tag(t)).filter(|t| !t.is_empty()).collect();
+        let updated_at = now_date();
+        self.upsert_annotation(kind, key, move |d| {
+            if !d.get("tags").is_some_and(|t| t.is_array()) {
+                d["tags"] = json!([]);
+            }
+            if let Some(existing) = d["tags"].as_array_mut() {
+                for tag in tags {
+                    if !existing.iter().any(|t| t.as_str() == Some(tag.as_str())) {
+                        existing.push(Value::String(tag));
+                    }
+                }
+            }
+            d["updatedAt"] = updated_at;
+        })
+        .await
+    }
+
+    async fn remove_tags(&self, kind: AnnotationKind, key: &str, tags: &[String]) -> Result<()> {
+        let tags: Vec<String> = tags.iter().map(|t| normalize_tag(t)).collect();
+        let updated_at = now_date();
+        self.upsert_annotation(kind, key, move |d| {
+            if let Some(existing) = d.get_mut("tags").and_then(|t| t.as_array_mut()) {
+                existing.retain(|t| !t.as_str().is_some_and(|t| tags.iter().any(|r| r == t)));
+            }
+            d["updatedAt"] = updated_at;
+        })
+        .await
+    }
+
+    async fn set_note(&self, kind: AnnotationKind, key: &str, note: &str) -> Result<()> {
+        let note = note.trim();
+        let note = if note.is_empty() { Value::Null } else { Value::String(note.to_string()) };
+        let updated_at = now_date();
+        self.upsert_annotation(kind, key, move |d| {
+            d["note"] = note;
+            d["updatedAt"] = updated_at;
+        })
+        .await
+    }
+
+    async fn list_annotations(&self, tag: Option<&str>) -> Result<Vec<Annotation>> {
+        let (filter, args) = match tag {
+            Some(t) => (
+                "EXISTS (SELECT 1 FROM json_each(doc, '$.tags') WHERE json_each.value = ?)",
+                vec![text(&normalize_tag(t))],
+            ),
+            None => ("1", vec![]),
+        };
+        self.call(move |c| find_typed(c, "annotations", filter, args, "ORDER BY rowid")).await
+    }
+
+    async fn tags_for_trade(&self, user_address: &str, trade: &UserActivity) -> Result<Vec<String>> {
+        let market_keys: Vec<String> = [&trade.condition_id, &trade.slug, &trade.event_slug]
+            .iter()
+            .filter_map(|k| k.as_deref())
+            .filter(|k| !k.is_empty())
+            .map(String::from)
+            .collect();
+        let mut filter = "(json_extract(doc, '$.kind') = ? AND json_extract(doc, '$.key') = ?)".to_string();
+        let mut args = vec![text(AnnotationKind::Trader.as_str()), text(&user_address.to_lowercase())];
+        if !market_keys.is_empty() {
+            filter.push_str(&format!(
+                " OR (json_extract(doc, '$.kind') = ? AND json_extract(doc, '$.key') IN ({}))",
+                vec!["?"; market_keys.len()].join(", ")
+            ));
+            args.push(text(AnnotationKind::Market.as_str()));
+            args.extend(market_keys.iter().map(|k| text(k)));
+        }
+        let annotations: Vec<Annotation> =
+            self.call(move |c| find_typed(c, "annotations", &filter, args, "")).await?;
+        let mut tags: Vec<String> = Vec::new();
+        for tag in annotations.into_iter().flat_map(|a| a.tags) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags.sort();
+        Ok(tags)
+    }
+
+    async fn find_copied_activities(&self, user_address: &str, tag: Option<&str>) -> Result<Vec<UserActivity>> {
+        let coll = activities(user_address);
+        let mut filter = "json_extract(doc, '$.type') = 'TRADE' AND json_extract(doc, '$.botExcutedTime') > 0 \
+             AND json_extract(doc, '$.botExcutedTime') != 999 AND json_extract(doc, '$.skipReason') IS NULL"
+            .to_string();
+        let mut args = Vec::new();
+        if let Some(t) = tag {
+            filter.push_str(" AND EXISTS (SELECT 1 FROM json_each(doc, '$.tags') WHERE json_each.value = ?)");
+            args.push(text(&normalize_tag(t)));
+        }
+        self.call(move |c| find_typed(c, &coll, &filter, args, "ORDER BY rowid")).await
+    }
+
+    async fn insert_bot_order(&self, order: &BotOrder) -> Result<()> {
+        let doc = encode(order)?;
+        self.call(move |c| insert(c, "bot_orders", doc)).await
+    }
+
+    async fn get_bot_orders(&self, since: Option<i64>) -> Result<Vec<BotOrder>> {
+        let (filter, args) = match since {
+            Some(ms) => ("json_extract(doc, '$.finishedAt') >= ?", vec![SqlValue::Integer(ms)]),
+            None => ("1", vec![]),
+        };
+        self.call(move |c| find_typed(c, "bot_orders", filter, args, "ORDER BY json_extract(doc, '$.startedAt')"))
+            .await
+    }
+
+    async fn get_history_trades(&self, user_address: &str, since: i64) -> Result<Vec<serde_json::Value>> {
+        let coll = history(user_address);
+        let rows = self
+            .call(move |c| {
+                find(
+                    c,
+                    &coll,
+                    "json_extract(doc, '$.timestamp') >= ?",
+                    vec![SqlValue::Integer(since)],
+                    "ORDER BY json_extract(doc, '$.timestamp')",
+                )
+            })
+            .await?;
+        Ok(rows.into_iter().map(|(_, d)| d).collect())
+    }
+
+    async fn get_backfill_cursors(&self) -> Result<Vec<BackfillCursor>> {
+        self.all("backfill_cursors").await?.into_iter().map(|(_, d)| decode(d)).collect()
+    }
+
+    async fn get_backfill_cursor(&self, trader: &str) -> Result<Option<BackfillCursor>> {
+        self.get_by_id("backfill_cursors", &trader.to_lowercase()).await?.map(decode).transpose()
+    }
+
+    async fn save_backfill_cursor(&self, cursor: &BackfillCursor) -> Result<()> {
+        let (id, doc) = with_id(encode(cursor)?);
+        self.call(move |c| put(c, "backfill_cursors", &id, &doc)).await
+    }
+
+    async fn upsert_history_trades(&self, user_address: &str, trades: &[serde_json::Value]) -> Result<u64> {
+        let coll = history(user_address);
+        let mut rows = Vec::new();
+        for trade in trades {
+            let field = |k: &str| trade.get(k).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let id = format!("{}:{}:{}", field("transactionHash"), field("asset"), field("side"));
+            let mut d = to_document(trade)?;
+            d.insert("_id", id.clone());
+            rows.push((id, Bson::Document(d).into_relaxed_extjson()));
+        }
+        self.call(move |c| {
+            let tx = c.transaction()?;
+            let mut inserted = 0;
+            for (id, doc) in rows {
+                inserted += tx.execute(
+                    "INSERT OR IGNORE INTO documents (collection, id, doc) VALUES (?1, ?2, ?3)",
+                    params![coll, id, doc.to_string()],
+                )? as u64;
+            }
+            tx.commit()?;
+            Ok(inserted)
+        })
+        .await
+    }
+
+    async fn save_wallet_inflows(&self, inflows: &[WalletInflow]) -> Result<()> {
+        let docs: Vec<(String, Value)> = inflows.iter().map(|i| Ok(with_id(encode(i)?))).collect::<Result<_>>()?;
+        self.call(move |c| {
+            let tx = c.transaction()?;
+            for (id, doc) in &docs {
+                put(&tx, "wallet_inflows", id, doc)?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_wallet_inflows(&self, since: Option<i64>) -> Result<Vec<WalletInflow>> {
+        let (filter, args) = match since {
+            Some(ts) => ("json_extract(doc, '$.timestamp') >= ?", vec![SqlValue::Integer(ts)]),
+            None => ("1", vec![]),
+        };
+        self.call(move |c| find_typed(c, "wallet_inflows", filter, args, "ORDER BY json_extract(doc, '$.timestamp')"))
+            .await
+    }
+
+    async fn get_scan_block(&self, scanner: &str) -> Result<Option<u64>> {
+        let doc = self.get_by_id("scan_cursors", scanner).await?;
+        Ok(doc.and_then(|d| d.get("block").and_then(|v| v.as_i64())).map(|b| b as u64))
+    }
+
+    async fn set_scan_block(&self, scanner: &str, block: u64) -> Result<()> {
+        let fields = json!({ "block": block as i64, "updatedAt": crate::utils::clock::now_ms() });
+        self.upsert_by_id("scan_cursors", scanner, fields).await
+    }
+
+    async fn get_trailing_highs(&self) -> Result<HashMap<String, f64>> {
+        let mut out = HashMap::new();
+        for (asset, d) in self.all("trailing_stops").await? {
+            if let Some(high) = d.get("high").and_then(|v| v.as_f64()) {
+                out.insert(asset, high);
+            }
+        }
+        Ok(out)
+    }
+
+    async fn set_trailing_high(&self, asset: &str, high: f64) -> Result<()> {
+        let fields = json!({ "high": high, "updatedAt": crate::utils::clock::now_ms() });
+        self.upsert_by_id("trailing_stops", asset, fields).await
+    }
+
+    async fn clear_trailing_high(&self, asset: &str) -> Result<()> {
+        self.delete_by_id("trailing_stops", asset).await
+    }
+
+    async fn latest_activity_timestamp(&self, user_address: &str) -> Result<Option<i64>> {
+        let coll = activities(user_address);
+        let latest = self
+            .call(move |c| {
+                find(
+                    c,
+                    &coll,
+                    "json_extract(doc, '$.timestamp') IS NOT NULL",
+                    vec![],
+                    "ORDER BY json_extract(doc, '$.timestamp') DESC LIMIT 1",
+                )
+            })
+            .await?;
+        Ok(latest.first().and_then(|(_, d)| d.get("timestamp")).and_then(|v| v.as_i64()))
+    }
+
+    async fn get_dormant_traders(&self) -> Result<HashMap<String, Option<String>>> {
+        Ok(self
+            .all("dormant_traders")
+            .await?
+            .into_iter()
+            .map(|(trader, d)| (trader, d.get("autoTag").and_then(|v| v.as_str()).map(String::from)))
+            .collect())
+    }
+
+    async fn set_dormant_trader(&self, trader: &str, auto_tag: Option<&str>) -> Result<()> {
+        let fields = json!({ "autoTag": auto_tag, "since": crate::utils::clock::now_ms() });
+        self.upsert_by_id("dormant_traders", &trader.to_lowercase(), fields).await
+    }
+
+    async fn clear_dormant_trader(&self, trader: &str) -> Result<()> {
+        self.delete_by_id("dormant_traders", &trader.to_lowercase()).await
+    }
+
+    async fn get_report_sent(&self, period: &str) -> Result<Option<String>> {
+        let doc = self.get_by_id("report_runs", period).await?;
+        Ok(doc.and_then(|d| d.get("slot").and_then(|v| v.as_str()).map(String::from)))
+    }
+
+    async fn set_report_sent(&self, period: &str, slot: &str) -> Result<()> {
+        let fields = json!({ "slot": slot, "sentAt": crate::utils::clock::now_ms() });
+        self.upsert_by_id("report_runs", period, fields).await
+    }
+
+    // No capped collections in SQLite - insert_log_events trims to the newest max_events instead
+    async fn ensure_log_events(&self, max_events: u64) -> Result<()> {
+        self.log_events_max.store(max_events, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn insert_log_events(&self, events: &[LogEvent]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let docs: Vec<Value> = events.iter().map(encode).collect::<Result<_>>()?;
+        let max_events = self.log_events_max.load(Ordering::Relaxed);
+        self.call(move |c| {
+            let tx = c.transaction()?;
+            for doc in docs {
+                insert(&tx, "log_events", doc)?;
+            }
+            if max_events > 0 {
+                tx.execute(
+                    "DELETE FROM documents WHERE collection = 'log_events' AND rowid NOT IN \
+                     (SELECT rowid FROM documents WHERE collection = 'log_events' ORDER BY rowid DESC LIMIT ?1)",
+                    params![max_events as i64],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn recent_log_events(&self, limit: i64, levels: &[String]) -> Result<Vec<LogEvent>> {
+        let prefixes: Vec<String> = levels
+            .iter()
+            .map(|l| l.chars().filter(|c| c.is_ascii_alphabetic()).collect::<String>().to_uppercase())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let filter = if prefixes.is_empty() {
+            "1".to_string()
+        } else {
+            vec!["json_extract(doc, '$.level') LIKE ?"; prefixes.len()].join(" OR ")
+        };
+        let mut args: Vec<SqlValue> = prefixes.iter().map(|p| text(&format!("{}%", p))).collect();
+        args.push(SqlValue::Integer(limit));
+        let mut out: Vec<LogEvent> =
+            self.call(move |c| find_typed(c, "log_events", &filter, args, "ORDER BY rowid DESC LIMIT ?")).await?;
+        out.reverse();
+        Ok(out)
+    }
+
+    async fn insert_capital_sample(&self, sample: &CapitalSample) -> Result<()> {
+        let doc = encode(sample)?;
+        self.call(move |c| insert(c, "capital_samples", doc)).await
+    }
+
+    async fn get_capital_samples(&self, from_ms: i64, to_ms: i64) -> Result<Vec<CapitalSample>> {
+        self.call(move |c| {
+            find_typed(
+                c,
+                "capital_samples",
+                "json_extract(doc, '$.timestamp') BETWEEN ? AND ?",
+                vec![SqlValue::Integer(from_ms), SqlValue::Integer(to_ms)],
+                "ORDER BY json_extract(doc, '$.timestamp')",
+            )
+        })
+        .await
+    }
+
+    async fn insert_paper_trade(&self, trade: &PaperTrade) -> Result<()> {
+        let doc = encode(trade)?;
+        self.call(move |c| insert(c, "paper_trades", doc)).await
+    }
+
+    async fn get_paper_trades(&self, trader: Option<&str>) -> Result<Vec<PaperTrade>> {
+        let (filter, args) = match trader {
+            Some(t) => ("json_extract(doc, '$.trader') = ?", vec![text(&t.to_lowercase())]),
+            None => ("1", vec![]),
+        };
+        self.call(move |c| find_typed(c, "paper_trades", filter, args, "ORDER BY json_extract(doc, '$.timestamp')"))
+            .await
+    }
+
+    async fn collection_names(&self) -> Result<Vec<String>> {
+        self.call(|c| {
+            let mut stmt = c.prepare("SELECT DISTINCT collection FROM documents ORDER BY collection")?;
+            let names = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(names)
+        })
+        .await
+    }
+
+    async fn dump_collection(&self, name: &str) -> Result<Vec<Document>> {
+        let mut out = Vec::new();
+        for (_, d) in self.all(name).await? {
+            if let Bson::Document(d) = Bson::try_from(d)? {
+                out.push(d);
+            }
+        }
+        Ok(out)
+    }
+
+    async fn drop_collection(&self, name: &str) -> Result<()> {
+        let name = name.to_string();
+        self.call(move |c| {
+            c.execute("DELETE FROM documents WHERE collection = ?1", params![name])?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn restore_document(&self, name: &str, document: &Document) -> Result<()> {
+        let (name, (id, doc)) = (name.to_string(), with_id(encode(document)?));
+        self.call(move |c| put(c, &name, &id, &doc)).await
+    }
+
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+use crate::config::{active_overrides, effective_config, EnvConfig};
+use crate::db::Db;
+use crate::services::{
+    cached_usdc_balance, drawdown_scale, finish_heartbeat, latest_resources, refresh_overrides, run_allowance_monitor,
+    run_capital_sampler, run_config_refresher, run_dormant_traders, run_heartbeat, run_market_disputes,
+    run_market_overrides, run_report_scheduler, run_resource_monitor, run_trade_executor, run_trade_monitor,
+    run_trailing_stop, run_volatility_regime, run_wallet_watcher, stop_allowance_monitor, stop_capital_sampler,
+    stop_config_refresher, stop_dormant_traders, stop_heartbeat, stop_market_disputes, stop_market_overrides,
+    stop_report_scheduler, stop_resource_monitor, stop_trade_executor, stop_trade_monitor,
+    stop_trailing_stop, stop_volatility_regime, stop_wallet_watcher, volatility_haircut, ResourceSnapshot,
+    TradeMonitorHandle,
+};
+use crate::types::{BotOrder, LogEvent};
+use crate::utils::{
+    self, clock, create_signer, get_usdc_balance, http_client, perform_health_check, resolve_trader_addresses, resolve_wallet_kind, Alert,
+    HttpPool, Logger,
+};
+
+// Embedding API: what main.rs, and any other program linking polymarket_copy_rust, runs the copy engine with.
+// Semver: CopyEngine, EngineEvent, EngineState and the crate-root re-exports only change in breaking ways
+// on a minor bump while the crate is 0.x (see README "Embedding")
+
+// Events buffered per subscriber; a slower one misses the oldest (RecvError::Lagged) instead of stalling the bot
+pub const EVENT_BUFFER: usize = 1024;
+
+static EVENTS: OnceLock<broadcast::Sender<EngineEvent>> = OnceLock::new();
+// The services keep process-wide state (stop flags, caches), so one engine per process
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// What the engine reports while it runs (serialized with a "type" tag, e.g. {"type":"skipped",...})
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum EngineEvent {
+    Started { traders: Vec<String>, proxy_wallet: String },
+    Log(LogEvent), // Every Logger line that also goes to the log file
+    Alert(Alert), // Everything raise_alert sees, before routing / dedup
+    Order(BotOrder), // A copy order run finished (see "bot_orders")
+    Skipped { trader: String, reason: String }, // A trader's trade was not copied (SkipReason)
+    Stopped,
+}
+
+fn events() -> &'static broadcast::Sender<EngineEvent> {
+    EVENTS.get_or_init(|| broadcast::channel(EVENT_BUFFER).0)
+}
+
+// Publish to subscribers (no-op without any, so the CLIs pay nothing)
+pub(crate) fn emit(event: EngineEvent) {
+    let tx = events();
+    if tx.receiver_count() > 0 {
+        let _ = tx.send(event);
+    }
+}
+
+// Events from now on; works before start() too, so nothing from the startup is missed
+pub fn subscribe() -> broadcast::Receiver<EngineEvent> {
+    events().subscribe()
+}
+
+// Snapshot for status pages / bots
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineState {
+    pub started_at: i64, // ms
+    pub traders: Vec<String>, // Resolved USER_ADDRESSES being copied
+    pub proxy_wallet: String,
+    pub trading_paused: bool, // TRADING_PAUSED, runtime overrides included
+    pub paused_traders: Vec<String>,
+    pub overrides: Vec<(String, String)>, // Runtime overrides from the configs collection now in effect
+    pub usdc_balance: Option<f64>,
+    pub volatility_haircut: f64, // 1.0 unless the volatility regime is turbulent
+    pub drawdown_scale: f64, // 1.0 unless my equity is past a DRAWDOWN_LEVELS step
+    pub resources: Option<ResourceSnapshot>, // Last resource monitor sample
+}
+
+// A running copy engine (monitor, executor and every background service)
+pub struct CopyEngine {
+    config: EnvConfig,
+    db: Db,
+    started_at: i64,
+    tasks: Vec<JoinHandle<()>>,
+    executor: JoinHandle<()>, // Awaited on stop so buffered trades can flush (SHUTDOWN_FLUSH_SECONDS)
+    executor_exit: watch::Receiver<Option<String>>, // Set when the executor returns ("stopped" / "failed: ...")
+    _monitor: TradeMonitorHandle,
+    stopped: bool, // Set by stop(); otherwise Drop tears the services down without the flush
+}
+
+impl CopyEngine {
+    // Startup checks (chain, DB self-test, system check), then every service; Err if another engine runs here
+    pub async fn start(config: EnvConfig) -> Result<CopyEngine> {
+        if ACTIVE.swap(true, Ordering::SeqCst) {
+            anyhow::bail!("A copy engine is already running in this process");
+        }
+        match Self::launch(config).await {
+            Ok(engine) => {
+                emit(EngineEvent::Started {
+                    traders: engine.config.user_addresses.clone(),
+                    proxy_wallet: engine.config.proxy_wallet.clone(),
+                });
+                Ok(engine)
+            }
+            Err(e) => {
+                Logger::stop_persisting();
+                ACTIVE.store(false, Ordering::SeqCst);
+                Err(e)
+            }
+        }
+    }
+
+    async fn launch(mut config: EnvConfig) -> Result<CopyEngine> {
+        // Connect to DB (config is loaded by the caller, e.g. EnvConfig::from_env)
+        // Wrong network (Ethereum mainnet, a testnet, a bad USDC address) would sign orders nobody can fill
+        match utils::verify_chain(&config).await {
+            Ok(check) => Logger::info(&format!("Chain check: {}", check.summary())),
+            Err(e) => anyhow::bail!("Chain check failed: {}", e),
+        }
+        let db = Db::open(&config).await?;
+        // Real round trip before anything relies on the DB (reported in the system check below)
+        let db_test = db.self_test(&config.user_addresses, config.resource_max_mongo_ms).await;
+        let db_ok = db_test.is_ok();
+
+        // Keep Logger lines in the capped "log_events" collection for `logs` / Telegram /logs
+        if config.log_events_max > 0 {
+            match db.ensure_log_events(config.log_events_max).await {
+                Ok(()) => Logger::persist_to(&db),
+                Err(e) => Logger::warning(&format!("Log replay disabled, could not set up log_events: {}", e)),
+            }
+        }
+
+        // Older versions stored secrets in plaintext - encrypt them in place before writing anything new. A failure
+        // (no key configured, wrong key for the stored ciphertexts) stops the startup instead of leaving them readable
+        let migrated = db
+            .encrypt_plaintext_configs()
+            .await
+            .map_err(|e| anyhow::anyhow!("Could not migrate the secrets in the configs collection: {}", e))?;
+        if migrated > 0 {
+            Logger::info(&format!("Encrypted / fingerprinted {} stored secret(s) in the configs collection", migrated));
+        }
+
+        // Store priv key in DB (AES-GCM encrypted, key from CONFIG_ENCRYPTION_SECRET / CONFIG_SECRET_FILE) - local signer only
+        if !config.private_key.is_empty() {
+            if let Err(e) = db.set_config("PRIVATE_KEY", &config.private_key).await {
+                Logger::error(&format!("Failed to store PRIVATE_KEY in database: {}", e));
+            }
+        }
+
+        Logger::startup(&config.user_addresses, &config.proxy_wallet);
+
+        // Run health checks - DB, RPC, balance, Polymarket API
+        Logger::info("Running system check…");
+        let balance = get_usdc_balance(
+            &config.rpc_url,
+            &config.usdc_contract_address,
+            &config.proxy_wallet,
+        )
+        .await;
+        let polymarket_ok = utils::fetch_data(
+            &http_client(HttpPool::Api),
+            "https://data-api.polymarket.com/positions?user=0x0000000000000000000000000000000000000000",
+            config.request_timeout_ms,
+            config.network_retry_limit,
+        )
+        .await
+        .is_ok();
+        let health = perform_health_check(db_test, &config.rpc_url, balance, polymarket_ok).await;
+
+        Logger::separator();
+        Logger::header("SYSTEM CHECK");
+        let overall = if health.healthy {
+            "All systems go"
+        } else {
+            "Degraded — check items below"
+        };
+        Logger::health_line(
+            "Overall",
+            if health.healthy { "ok" } else { "error" },
+            overall,
+        );
+        Logger::health_line(
+            "Database",
+            &health.checks.database.status,
+            &health.checks.database.message,
+        );
+        Logger::health_line("RPC", &health.checks.rpc.status, &health.checks.rpc.message);
+        Logger::health_line(
+            "Balance",
+            &health.checks.balance.status,
+            &health.checks.balance.message,
+        );
+        Logger::health_line(
+            "Polymarket API",
+            &health.checks.polymarket_api.status,
+            &health.checks.polymarket_api.message,
+        );
+        for gauge in &health.rate_limits {
+            Logger::health_line(&format!("Quota {}", gauge.host), gauge.status(), &gauge.summary());
+        }
+        Logger::separator();
+
+        // Without a working DB nothing can be tracked or deduplicated - stop here rather than copy blind
+        if !db_ok {
+            anyhow::bail!("Database self-test failed: {}", health.checks.database.message);
+        }
+
+        // Continue even if health check fails (degraded mode)
+        if !health.healthy {
+            Logger::warning("System check reported issues; continuing anyway.");
+        }
+
+        // Init CLOB client (handles wallet type detection)
+        Logger::info("Initializing CLOB client...");
+        let signer = create_signer(&config).await?;
+        // Resolved once: every CLOB client made later (executor, trailing stop, overrides) reuses it
+        let kind = resolve_wallet_kind(&config, &signer).await?;
+        Logger::info(&format!("Wallet type detected: {}", kind.label()));
+        if kind == utils::WalletKind::PolyProxy {
+            Logger::info("Trading from your Polymarket deposit address - balances are read from PROXY_WALLET");
+        }
+        config.signature_type = Some(kind.signature_type());
+        Logger::success("CLOB client ready");
+
+        Logger::separator();
+        // Shared keep-alive client (REQUEST_TIMEOUT_MS, HTTP_POOL_* settings) for every service
+        let http_client = http_client(HttpPool::Api);
+
+        // Map trader addresses to the proxy wallets that actually show up in RTDS
+        if config.resolve_trader_proxies {
+            Logger::info("Resolving trader wallets...");
+            config.user_addresses = resolve_trader_addresses(&mut config, &db, &http_client).await;
+            // Resolved proxies have their own activity / position collections
+            if let Err(e) = db.ensure_indexes(&config.user_addresses).await {
+                Logger::warning(&format!("Could not create indexes for resolved traders: {}", e));
+            }
+            // A signer / EOA entry can resolve to our own proxy wallet
+            if let Some(own) = config.user_addresses.iter().find(|a| config.is_own_wallet(a)) {
+                anyhow::bail!(
+                    "USER_ADDRESSES resolves to your own PROXY_WALLET ({}) - remove that entry, the bot would copy its own trades",
+                    own
+                );
+            }
+        }
+
+        let mut tasks: Vec<JoinHandle<()>> = Vec::new();
+
+        // Runtime overrides from the configs collection (take precedence over env)
+        if let Err(e) = refresh_overrides(&db).await {
+            Logger::warning(&format!("Could not load config overrides: {}", e));
+        }
+        tasks.push(tokio::spawn(run_config_refresher(config.clone(), db.clone())));
+
+        // Start monitor (watches for new trades via RTDS)
+        Logger::info("Starting trade monitor...");
+        let monitor = match run_trade_monitor(&config, &db, &http_client).await {
+            Ok(monitor) => monitor,
+            Err(e) => {
+                // Nothing else is running yet but the config refresher
+                stop_config_refresher();
+                for task in &tasks {
+                    task.abort();
+                }
+                return Err(e);
+            }
+        };
+
+        // Wallet watcher: deposits / withdrawals / approvals update the cached balance as they land
+        tasks.push(tokio::spawn(run_wallet_watcher(config.clone(), http_client.clone())));
+
+        // Volatility regime: copy sizes x VOL_HAIRCUT while realized volatility is above VOL_HIGH (VOL_REGIME_SOURCE)
+        tasks.push(tokio::spawn(run_volatility_regime(config.clone(), http_client.clone())));
+
+        // Start executor (processes trades & executes orders)
+        Logger::info("Starting trade executor...");
+        let config_clone = config.clone();
+        let db_clone = db.clone();
+        let http_clone = http_client.clone();
+        let (exit_tx, executor_exit) = watch::channel(None);
+        let executor = tokio::spawn(async move {
+            let reason = match run_trade_executor(&config_clone, &db_clone, &http_clone).await {
+                Ok(()) => "stopped".to_string(),
+                Err(e) => {
+                    Logger::error(&format!("Trade executor error: {}", e));
+                    format!("failed: {}", e)
+                }
+            };
+            let _ = exit_tx.send(Some(reason));
+        });
+
+        // Trailing stop on copied positions (TRAILING_STOP_PERCENT or trailing-stop-<pct> tags)
+        tasks.push(tokio::spawn(run_trailing_stop(config.clone(), db.clone(), http_client.clone())));
+
+        // close-market / resize-market: sell or trim positions in markets flagged through overrides
+        tasks.push(tokio::spawn(run_market_overrides(config.clone(), db.clone(), http_client.clone())));
+
+        // Pause copying / selling in markets whose resolution is disputed (DISPUTE_CHECK_SECS)
+        tasks.push(tokio::spawn(run_market_disputes(config.clone(), http_client.clone())));
+
+        // Flag copied traders that went quiet while we still hold their positions (DORMANT_TRADER_DAYS)
+        tasks.push(tokio::spawn(run_dormant_traders(config.clone(), db.clone(), http_client.clone())));
+
+        // Periodic allowance drift check (warns before buys start getting rejected)
+        tasks.push(tokio::spawn(run_allowance_monitor(config.clone(), http_client.clone())));
+
+        // Self-monitoring: memory, tasks, copy backlog, Mongo latency (warns past RESOURCE_MAX_*)
+        tasks.push(tokio::spawn(run_resource_monitor(config.clone(), db.clone())));
+
+        // Cash vs deployed samples for the weekly capital-efficiency section (CAPITAL_SAMPLE_SECS)
+        tasks.push(tokio::spawn(run_capital_sampler(config.clone(), db.clone(), http_client.clone())));
+
+        // Daily / weekly digests by webhook or email (REPORT_SCHEDULE)
+        tasks.push(tokio::spawn(run_report_scheduler(config.clone(), db.clone(), http_client.clone())));
+
+        // Dead-man's switch: ping HEARTBEAT_URL and stamp the last-heartbeat file while the bot is alive
+        tasks.push(tokio::spawn(run_heartbeat(config.clone(), http_client.clone())));
+
+        Ok(CopyEngine {
+            config,
+            db,
+            started_at: clock::now_ms(),
+            tasks,
+            executor,
+            executor_exit,
+            _monitor: monitor,
+            stopped: false,
+        })
+    }
+
+    fn signal_stop() {
+        stop_trade_monitor();
+        stop_trade_executor();
+        stop_allowance_monitor();
+        stop_config_refresher();
+        stop_heartbeat();
+        stop_wallet_watcher();
+        stop_trailing_stop();
+        stop_market_overrides();
+        stop_market_disputes();
+        stop_dormant_traders();
+        stop_resource_monitor();
+        stop_report_scheduler();
+        stop_capital_sampler();
+        stop_volatility_regime();
+    }
+
+    // Stop every service, let the executor flush its buffers (SHUTDOWN_FLUSH_SECONDS), close the DB
+    pub async fn stop(mut self) {
+        Self::signal_stop();
+        // Grace period + headroom for the copy in flight when the stop came (the flush bounds itself)
+        let flush_wait = tokio::time::Duration::from_secs(effective_config(&self.config).shutdown_flush_seconds + 10);
+        if tokio::time::timeout(flush_wait, &mut self.executor).await.is_err() {
+            Logger::warning("Trade executor did not stop in time - aborting it");
+            self.executor.abort();
+        }
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        for task in &self.tasks {
+            task.abort();
+        }
+        finish_heartbeat(&self.config);
+        Logger::stop_persisting();
+        let _ = self.db.close().await;
+        self.stopped = true;
+        emit(EngineEvent::Stopped);
+        ACTIVE.store(false, Ordering::SeqCst);
+    }
+
+    // Resolves once the trade executor has exited ("stopped", "failed: ..." or "panicked") - for hosts that
+    // should go down with it. After stop() it resolves with "stopped"
+    pub async fn executor_exited(&self) -> String {
+        let mut exit = self.executor_exit.clone();
+        loop {
+            if let Some(reason) = exit.borrow_and_update().clone() {
+                return reason;
+            }
+            // Sender gone without a reason = the task panicked (or was aborted)
+            if exit.changed().await.is_err() {
+                return exit.borrow().clone().unwrap_or_else(|| "panicked".to_string());
+            }
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<EngineEvent> {
+        subscribe()
+    }
+
+    pub async fn state(&self) -> EngineState {
+        let effective = effective_config(&self.config);
+        EngineState {
+            started_at: self.started_at,
+            traders: self.config.user_addresses.clone(),
+            proxy_wallet: self.config.proxy_wallet.clone(),
+            trading_paused: effective.trading_paused,
+            paused_traders: effective.paused_traders.clone(),
+            overrides: active_overrides(),
+            usdc_balance: cached_usdc_balance(&self.config).await.ok(),
+            volatility_haircut: volatility_haircut(),
+            drawdown_scale: drawdown_scale(),
+            resources: latest_resources(),
+        }
+    }
+
+    // Config the engine runs with right now: traders resolved to their proxies, runtime overrides applied
+    // (the same values the executor sees on its next pass)
+    pub fn config(&self) -> EnvConfig {
+        effective_config(&self.config)
+    }
+
+    // Same DB handle the services use - positions, activities, bot_orders, log_events
+    pub fn db(&self) -> &Db {
+        &self.db
+    }
+}
+
+// Dropped without stop() (embedder panicked or forgot): abort everything now, buffered trades are lost
+impl Drop for CopyEngine {
+    fn drop(&mut self) {
+        if self.stopped {
+            return;
+        }
+        Self::signal_stop();
+        self.executor.abort();
+        for task in &self.tasks {
+            task.abort();
+        }
+        Logger::stop_persisting();
+        emit(EngineEvent::Stopped);
+        ACTIVE.store(false, Ordering::SeqCst);
+    }
+}
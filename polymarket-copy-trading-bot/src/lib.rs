@@ -1,12 +1,17 @@
+#[macro_use]
+extern crate polymarket_term; // outln!/out!/eoutln! - println! with the --plain ASCII fallback
+
 pub mod config;
 pub mod db;
+pub mod engine;
 pub mod services;
 pub mod types;
 pub mod utils;
 
 pub use config::{CopyStrategy, CopyStrategyConfig, EnvConfig};
-pub use db::Db;
-pub use types::{RtdsActivity, UserActivity, UserPosition};
+pub use db::{Db, MongoStore, SqliteStore, TradeStore};
+pub use engine::{subscribe, CopyEngine, EngineEvent, EngineState};
+pub use types::{Annotation, AnnotationKind, BackfillCursor, BotOrder, CapitalSample, DbSelfTest, LogEvent, MarketCategory, PaperTrade, RtdsActivity, SkipReason, UserActivity, UserPosition, WalletInflow};
 pub use utils::{
-    fetch_data, get_usdc_allowance, get_usdc_balance, perform_health_check, theme, Logger,
+    fetch_data, fetch_paginated, get_usdc_allowance, get_usdc_balance, perform_health_check, theme, Logger,
 };
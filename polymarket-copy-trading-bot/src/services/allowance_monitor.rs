@@ -0,0 +1,138 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use crate::config::{effective_config, EnvConfig};
+use crate::utils::{
+    self, approve_usdc_max, gas_worth_it, get_usdc_allowance, raise_alert, Alert, AlertKind, Logger, Severity,
+    APPROVE_GAS, POLYMARKET_EXCHANGE,
+};
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_allowance_monitor() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+// Open exposure = current value of my open positions
+async fn fetch_open_exposure(config: &EnvConfig, http_client: &reqwest::Client) -> Result<f64> {
+    let url = format!(
+        "https://data-api.polymarket.com/positions?user={}",
+        config.proxy_wallet
+    );
+    let data = utils::fetch_paginated(
+        http_client,
+        &url,
+        config.request_timeout_ms,
+        config.network_retry_limit,
+    )
+    .await?;
+    Ok(data
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| p.get("currentValue").and_then(|v| v.as_f64()))
+                .sum()
+        })
+        .unwrap_or(0.0))
+}
+
+// One drift check: compare allowance to open exposure + planned spend, top up if enabled
+async fn check_allowance(config: &EnvConfig, http_client: &reqwest::Client) -> Result<()> {
+    let allowance = get_usdc_allowance(
+        &config.rpc_url,
+        &config.usdc_contract_address,
+        &config.proxy_wallet,
+        POLYMARKET_EXCHANGE,
+    )
+    .await?;
+    if allowance.is_infinite() {
+        return Ok(());
+    }
+
+    // No exposure figure = no honest drift check (BTW: 0 would understate what's needed), retried next time
+    let open_exposure = match fetch_open_exposure(config, http_client).await {
+        Ok(exposure) => exposure,
+        Err(e) => {
+            Logger::warning(&format!("Allowance check skipped: positions fetch failed: {}", e));
+            return Ok(());
+        }
+    };
+    let planned_spend =
+        config.copy_strategy_config.max_order_size_usd * config.allowance_buffer_orders;
+    let required = open_exposure + planned_spend;
+    if allowance >= required {
+        return Ok(());
+    }
+
+    let drift = format!(
+        "USDC allowance drift: ${:.2} approved, ${:.2} needed (exposure ${:.2} + planned ${:.2})",
+        allowance, required, open_exposure, planned_spend
+    );
+
+    if !config.allowance_auto_topup {
+        raise_alert(
+            config,
+            http_client,
+            Alert::new(AlertKind::AllowanceLow, format!("{} - buys will start failing soon. Run: make check-allowance", drift))
+                .key("drift"),
+        )
+        .await;
+        return Ok(());
+    }
+    Logger::warning(&drift);
+    // Paper trading never spends the allowance - no real approval for it
+    if config.dry_run {
+        Logger::info("DRY_RUN - not sending the USDC approval");
+        return Ok(());
+    }
+
+    // Benefit = the shortfall the approval unblocks; skipped now, retried next check
+    if !gas_worth_it(config, http_client, "USDC approval", APPROVE_GAS, required - allowance).await {
+        return Ok(());
+    }
+
+    Logger::info("ALLOWANCE_AUTO_TOPUP enabled - sending USDC approval...");
+    match approve_usdc_max(config).await {
+        Ok(tx_hash) => Logger::success(&format!(
+            "USDC allowance restored: https://polygonscan.com/tx/{}",
+            tx_hash
+        )),
+        Err(e) => {
+            raise_alert(
+                config,
+                http_client,
+                Alert::new(AlertKind::AllowanceLow, format!("Allowance top-up failed: {}", e))
+                    .severity(Severity::Critical)
+                    .key("topup"),
+            )
+            .await
+        }
+    }
+    Ok(())
+}
+
+// Background loop - runs until stop_allowance_monitor() (disabled when interval is 0)
+pub async fn run_allowance_monitor(config: EnvConfig, http_client: reqwest::Client) {
+    if config.allowance_check_interval_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    Logger::info(&format!(
+        "Allowance drift check every {}s (auto top-up: {})",
+        config.allowance_check_interval_secs,
+        match (config.allowance_auto_topup, config.dry_run) {
+            (true, false) => "on",
+            (true, true) => "off under DRY_RUN",
+            _ => "off",
+        }
+    ));
+
+    while RUNNING.load(Ordering::SeqCst) {
+        if let Err(e) = check_allowance(&effective_config(&config), &http_client).await {
+            Logger::error(&format!("Allowance check failed: {}", e));
+        }
+        sleep(Duration::from_secs(config.allowance_check_interval_secs)).await;
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::services::drawdown_scaler::{record_equity, seed_drawdown};
+use crate::services::wallet_watcher::cached_usdc_balance;
+use crate::types::{CapitalSample, UserPosition};
+use crate::utils::{clock, fetch_paginated, Logger};
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_capital_sampler() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+async fn sample(config: &EnvConfig, http_client: &reqwest::Client) -> Result<CapitalSample> {
+    let cash_usd = cached_usdc_balance(config).await?;
+    let url = format!("https://data-api.polymarket.com/positions?user={}", config.proxy_wallet);
+    let positions = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+    let deployed_usd = positions
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
+                .filter(|p| p.size.unwrap_or(0.0) > 0.0)
+                .filter_map(|p| p.current_value)
+                .sum()
+        })
+        .unwrap_or(0.0);
+    Ok(CapitalSample {
+        timestamp: clock::now_ms(),
+        cash_usd,
+        deployed_usd,
+    })
+}
+
+// Background loop - records cash vs deployed every CAPITAL_SAMPLE_SECS until stop_capital_sampler()
+// Feeds the capital-efficiency section of the weekly digest (see utils::capital) and the drawdown scaler
+pub async fn run_capital_sampler(config: EnvConfig, db: Db, http_client: reqwest::Client) {
+    if config.capital_sample_secs == 0 {
+        if !config.drawdown_levels.is_empty() {
+            Logger::warning("DRAWDOWN_LEVELS needs CAPITAL_SAMPLE_SECS > 0 - copy sizes won't follow the drawdown");
+        }
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    seed_drawdown(&config, &db).await;
+
+    while RUNNING.load(Ordering::SeqCst) {
+        // A missed sample only widens the gap the report skips over
+        match sample(&config, &http_client).await {
+            Ok(s) => {
+                record_equity(&config, &s);
+                if let Err(e) = db.insert_capital_sample(&s).await {
+                    Logger::warning(&format!("Could not store capital sample: {}", e));
+                }
+            }
+            Err(e) => Logger::warning(&format!("Capital sample failed: {}", e)),
+        }
+        sleep(Duration::from_secs(config.capital_sample_secs)).await;
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use crate::config::{active_overrides, set_active_overrides, EnvConfig, OVERRIDABLE_KEYS};
+use crate::db::Db;
+use crate::utils::Logger;
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_config_refresher() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+// Load overrides from the configs collection, log what changed since last time
+pub async fn refresh_overrides(db: &Db) -> Result<()> {
+    let latest = db.get_config_overrides(&OVERRIDABLE_KEYS).await?;
+    let previous = active_overrides();
+    if latest == previous {
+        return Ok(());
+    }
+
+    for (key, value) in &latest {
+        if !previous.iter().any(|(k, v)| k == key && v == value) {
+            Logger::info(&format!("Config override: {}={} (DB)", key, value));
+        }
+    }
+    for (key, _) in &previous {
+        if !latest.iter().any(|(k, _)| k == key) {
+            Logger::info(&format!("Config override cleared: {} (back to env)", key));
+        }
+    }
+    set_active_overrides(latest);
+    Ok(())
+}
+
+// Background loop - runs until stop_config_refresher() (disabled when interval is 0)
+pub async fn run_config_refresher(config: EnvConfig, db: Db) {
+    if config.config_refresh_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+
+    while RUNNING.load(Ordering::SeqCst) {
+        sleep(Duration::from_secs(config.config_refresh_secs)).await;
+        if let Err(e) = refresh_overrides(&db).await {
+            Logger::warning(&format!("Config override refresh failed: {}", e));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use super::trailing_stop::open_positions;
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::types::{AnnotationKind, UserPosition};
+use crate::utils::{clock, raise_alert, Alert, AlertKind, Logger};
+
+// Marks traders whose trailing stop we switched on, so it can be switched off when they trade again
+const DORMANT_TAG: &str = "dormant";
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_dormant_traders() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+// Our open positions that were built by copying this trader
+async fn mirrored_positions<'a>(db: &Db, trader: &str, positions: &'a [UserPosition]) -> Vec<&'a UserPosition> {
+    let mut out = Vec::new();
+    for position in positions {
+        let Some(asset) = position.asset.as_deref() else {
+            continue;
+        };
+        let copied = db
+            .find_all_buy_activities_for_asset(trader, asset, &position.condition_id)
+            .await
+            .map(|buys| !buys.is_empty())
+            .unwrap_or(false);
+        if copied {
+            out.push(position);
+        }
+    }
+    out
+}
+
+struct DormantTraders {
+    flagged: HashMap<String, Option<String>>, // Dormant trader -> trailing-stop tag we added (None = alert only)
+}
+
+impl DormantTraders {
+    // Trader is producing signals again: unflag and undo the automatic trailing stop
+    async fn resume(&mut self, db: &Db, trader: &str) {
+        let Some(auto_tag) = self.flagged.remove(trader) else {
+            return;
+        };
+        if let Some(tag) = auto_tag {
+            if let Err(e) = db
+                .remove_tags(AnnotationKind::Trader, trader, &[tag, DORMANT_TAG.to_string()])
+                .await
+            {
+                Logger::warning(&format!("Could not remove dormant tags from {}: {}", Logger::format_address(trader), e));
+            }
+        }
+        let _ = db.clear_dormant_trader(trader).await;
+        Logger::info(&format!("Trader {} is active again - no longer flagged dormant", Logger::format_address(trader)));
+    }
+
+    // Flag a newly dormant trader; returns the summary line for the alert
+    async fn flag(&mut self, config: &EnvConfig, db: &Db, trader: &str, idle_days: i64, mirrored: &[&UserPosition]) -> String {
+        let value: f64 = mirrored.iter().map(|p| p.current_value.unwrap_or(0.0)).sum();
+        let mut line = format!(
+            "{} - no signals for {} days, {} position(s) still held (${:.2})",
+            Logger::format_address(trader),
+            idle_days,
+            mirrored.len(),
+            value
+        );
+
+        // Hand their positions to the trailing stop, unless the trader already has a trailing-stop tag of their own
+        let has_own_rule = db
+            .get_annotation(AnnotationKind::Trader, trader)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|a| a.tags.iter().any(|t| t.starts_with("trailing-stop-")));
+        let auto_tag = config
+            .dormant_trailing_stop_percent
+            .filter(|_| !has_own_rule)
+            .map(|p| format!("trailing-stop-{}", p));
+        if let Some(ref tag) = auto_tag {
+            match db
+                .add_tags(AnnotationKind::Trader, trader, &[tag.clone(), DORMANT_TAG.to_string()])
+                .await
+            {
+                Ok(()) => line.push_str(&format!(" - trailing stop enabled ({})", tag)),
+                Err(e) => Logger::warning(&format!("Could not tag {} for the trailing stop: {}", Logger::format_address(trader), e)),
+            }
+        }
+        if let Err(e) = db.set_dormant_trader(trader, auto_tag.as_deref()).await {
+            Logger::warning(&format!("Failed to save dormant trader: {}", e));
+        }
+        self.flagged.insert(trader.to_string(), auto_tag);
+        line
+    }
+
+    async fn check(&mut self, config: &EnvConfig, db: &Db, http_client: &reqwest::Client) -> Result<()> {
+        let now = clock::now_ms();
+        let threshold_ms = config.dormant_trader_days as i64 * 86_400_000;
+        let positions = open_positions(config, http_client).await?;
+        let mut newly_dormant = Vec::new();
+
+        for trader in &config.user_addresses {
+            let trader = trader.to_lowercase();
+            // No activity recorded at all = nothing copied yet, so nothing to hold either
+            let Some(last) = db.latest_activity_timestamp(&trader).await?.map(clock::to_ms) else {
+                continue;
+            };
+            if now - last < threshold_ms {
+                self.resume(db, &trader).await;
+                continue;
+            }
+            if self.flagged.contains_key(&trader) {
+                continue;
+            }
+            // Quiet but nothing of theirs left open -> nothing to review (re-checked next time)
+            let mirrored = mirrored_positions(db, &trader, &positions).await;
+            if mirrored.is_empty() {
+                continue;
+            }
+            newly_dormant.push(self.flag(config, db, &trader, (now - last) / 86_400_000, &mirrored).await);
+        }
+
+        if !newly_dormant.is_empty() {
+            raise_alert(
+                config,
+                http_client,
+                Alert::new(
+                    AlertKind::DormantTrader,
+                    format!(
+                        "Dormant trader(s) - consider reviewing or closing their positions:\n{}",
+                        newly_dormant.join("\n")
+                    ),
+                ),
+            )
+            .await;
+        }
+        Ok(())
+    }
+}
+
+// Background loop - runs until stop_dormant_traders() (disabled when DORMANT_TRADER_DAYS or the interval is 0)
+pub async fn run_dormant_traders(config: EnvConfig, db: Db, http_client: reqwest::Client) {
+    if config.dormant_trader_days == 0 || config.dormant_check_interval_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let mut dormant = DormantTraders {
+        flagged: db.get_dormant_traders().await.unwrap_or_default(),
+    };
+
+    while RUNNING.load(Ordering::SeqCst) {
+        if let Err(e) = dormant.check(&config, &db, &http_client).await {
+            Logger::warning(&format!("Dormant trader check failed: {}", e));
+        }
+        sleep(Duration::from_secs(config.dormant_check_interval_secs)).await;
+    }
+}
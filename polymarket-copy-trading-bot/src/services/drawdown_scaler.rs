@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::types::CapitalSample;
+use crate::utils::{clock, Logger};
+
+// Equity curve the high is taken from: (timestamp ms, cash + deployed), oldest first
+static EQUITY: Mutex<VecDeque<(i64, f64)>> = Mutex::new(VecDeque::new());
+
+// Current state (None until the first sample, or with DRAWDOWN_LEVELS unset)
+static DRAWDOWN: Mutex<Option<DrawdownState>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy)]
+pub struct DrawdownState {
+    pub equity_usd: f64,
+    pub high_usd: f64, // Highest equity within DRAWDOWN_LOOKBACK_DAYS
+    pub drawdown_percent: f64,
+    pub level: usize, // DRAWDOWN_LEVELS steps passed (0 = full size)
+    pub scale: f64, // Multiplier applied to copy sizes right now
+    pub measured_at: i64,
+}
+
+pub fn drawdown_state() -> Option<DrawdownState> {
+    DRAWDOWN.lock().ok().and_then(|d| *d)
+}
+
+// Size multiplier for the next copy (1.0 at full size, off or not measured yet)
+pub fn drawdown_scale() -> f64 {
+    drawdown_state().map(|d| d.scale).unwrap_or(1.0)
+}
+
+// Start the curve from the stored capital samples, so a restart mid-drawdown doesn't take the dip as the new high
+pub async fn seed_drawdown(config: &EnvConfig, db: &Db) {
+    if config.drawdown_levels.is_empty() {
+        return;
+    }
+    let now = clock::now_ms();
+    let from = now - config.drawdown_lookback_days as i64 * 86_400_000;
+    match db.get_capital_samples(from, now).await {
+        Ok(samples) => {
+            if let Ok(mut curve) = EQUITY.lock() {
+                curve.clear();
+                curve.extend(samples.iter().map(|s| (s.timestamp, s.cash_usd + s.deployed_usd)));
+            }
+        }
+        Err(e) => Logger::warning(&format!("Could not load capital samples for drawdown scaling: {}", e)),
+    }
+}
+
+// Steps passed at this drawdown: deeper as soon as a level is reached, back up only DRAWDOWN_RECOVERY_PCT under it
+fn next_level(config: &EnvConfig, level: usize, drawdown: f64) -> usize {
+    let levels = &config.drawdown_levels;
+    let mut level = level.min(levels.len());
+    while level < levels.len() && drawdown >= levels[level].percent {
+        level += 1;
+    }
+    while level > 0 && drawdown < levels[level - 1].percent - config.drawdown_recovery_pct {
+        level -= 1;
+    }
+    level
+}
+
+// Called by the capital sampler with each new sample - moves the scale and logs every step
+pub fn record_equity(config: &EnvConfig, sample: &CapitalSample) {
+    if config.drawdown_levels.is_empty() {
+        return;
+    }
+    let equity = sample.cash_usd + sample.deployed_usd;
+    let high = {
+        let Ok(mut curve) = EQUITY.lock() else {
+            return;
+        };
+        curve.push_back((sample.timestamp, equity));
+        let from = sample.timestamp - config.drawdown_lookback_days as i64 * 86_400_000;
+        while curve.front().is_some_and(|(ts, _)| *ts < from) {
+            curve.pop_front();
+        }
+        curve.iter().map(|(_, e)| *e).fold(equity, f64::max)
+    };
+    let drawdown = if high > 0.0 { ((high - equity) / high * 100.0).max(0.0) } else { 0.0 };
+
+    let previous = drawdown_state().map(|d| d.level).unwrap_or(0);
+    let level = next_level(config, previous, drawdown);
+    let scale = if level == 0 { 1.0 } else { config.drawdown_levels[level - 1].scale };
+    if level > previous {
+        Logger::warning(&format!(
+            "Equity ${:.2} is {:.1}% below its ${:.2} high - copy sizes scaled to {}x",
+            equity, drawdown, high, scale
+        ));
+    } else if level < previous {
+        if level == 0 {
+            Logger::success(&format!("Drawdown recovered to {:.1}% - copy sizes back to full", drawdown));
+        } else {
+            Logger::info(&format!("Drawdown eased to {:.1}% - copy sizes scaled to {}x", drawdown, scale));
+        }
+    }
+
+    if let Ok(mut state) = DRAWDOWN.lock() {
+        *state = Some(DrawdownState {
+            equity_usd: equity,
+            high_usd: high,
+            drawdown_percent: drawdown,
+            level,
+            scale,
+            measured_at: sample.timestamp,
+        });
+    }
+}
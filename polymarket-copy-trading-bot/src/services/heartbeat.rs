@@ -0,0 +1,142 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use super::resource_monitor::{latest_resources, ResourceSnapshot};
+use super::trade_monitor::rtds_schema_json;
+use crate::config::EnvConfig;
+use crate::utils::{clock, raise_alert, Alert, AlertKind, Logger};
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_heartbeat() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+// Last-heartbeat file - rewritten every beat, so after a crash it shows when the process was last alive
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HeartbeatRecord {
+    pid: u32,
+    started_at: i64,
+    last_beat: i64,
+    beats: u64,
+    last_ping_ok: Option<bool>,
+    last_ping_error: Option<String>,
+    clean_shutdown: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resources: Option<ResourceSnapshot>, // Latest self-monitoring sample (RESOURCE_CHECK_SECS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ws_schema: Option<serde_json::Value>, // Unknown RTDS types / fields / unparseable trades seen so far
+}
+
+fn read_record(path: &str) -> Option<HeartbeatRecord> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_record(path: &str, record: &HeartbeatRecord) -> Result<()> {
+    if let Some(dir) = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Write then rename, so a crash mid-write never leaves a truncated file behind
+    let tmp = format!("{}.tmp", path);
+    std::fs::write(&tmp, serde_json::to_string_pretty(record)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+// healthchecks.io-style ping: any 2xx counts
+async fn ping(config: &EnvConfig, http_client: &reqwest::Client, url: &str) -> Result<()> {
+    let resp = http_client
+        .get(url)
+        .timeout(Duration::from_millis(config.request_timeout_ms))
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        anyhow::bail!("HTTP {}", resp.status());
+    }
+    Ok(())
+}
+
+// Previous run died without stopping cleanly: log (and alert) when it was last seen alive
+async fn report_previous_run(config: &EnvConfig, http_client: &reqwest::Client, previous: &HeartbeatRecord) {
+    if previous.clean_shutdown || previous.last_beat == 0 {
+        return;
+    }
+    let silent_mins = (clock::now_ms() - previous.last_beat) / 60_000;
+    raise_alert(
+        config,
+        http_client,
+        Alert::new(
+            AlertKind::UncleanShutdown,
+            format!(
+                "Previous run (pid {}) stopped without a clean shutdown - last heartbeat {} ({} min ago, up {} min)",
+                previous.pid,
+                clock::local_time_str(previous.last_beat, "%Y-%m-%d %H:%M:%S"),
+                silent_mins,
+                (previous.last_beat - previous.started_at).max(0) / 60_000
+            ),
+        ),
+    )
+    .await;
+}
+
+// Background loop - runs until stop_heartbeat() (disabled when interval is 0)
+pub async fn run_heartbeat(config: EnvConfig, http_client: reqwest::Client) {
+    if config.heartbeat_interval_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let path = config.heartbeat_file.clone();
+    if let Some(previous) = read_record(&path) {
+        report_previous_run(&config, &http_client, &previous).await;
+    }
+    Logger::info(&format!(
+        "Heartbeat every {}s -> {}{}",
+        config.heartbeat_interval_secs,
+        path,
+        if config.heartbeat_url.is_some() { " + ping URL" } else { "" }
+    ));
+
+    let mut record = HeartbeatRecord {
+        pid: std::process::id(),
+        started_at: clock::now_ms(),
+        ..Default::default()
+    };
+    while RUNNING.load(Ordering::SeqCst) {
+        if let Some(url) = &config.heartbeat_url {
+            let result = ping(&config, &http_client, url).await;
+            // Log only on transitions, a down monitor would otherwise flood the log every beat
+            match (&result, record.last_ping_ok) {
+                (Err(e), Some(true) | None) => Logger::warning(&format!("Heartbeat ping failed: {}", e)),
+                (Ok(()), Some(false)) => Logger::info("Heartbeat ping recovered"),
+                _ => {}
+            }
+            record.last_ping_ok = Some(result.is_ok());
+            record.last_ping_error = result.err().map(|e| e.to_string());
+        }
+        record.last_beat = clock::now_ms();
+        record.beats += 1;
+        record.resources = latest_resources();
+        record.ws_schema = Some(rtds_schema_json());
+        if let Err(e) = write_record(&path, &record) {
+            Logger::warning(&format!("Could not write heartbeat file {}: {}", path, e));
+        }
+        sleep(Duration::from_secs(config.heartbeat_interval_secs)).await;
+    }
+}
+
+// Mark the file as a clean stop, so the next start doesn't report it as a crash
+pub fn finish_heartbeat(config: &EnvConfig) {
+    if config.heartbeat_interval_secs == 0 {
+        return;
+    }
+    let mut record = read_record(&config.heartbeat_file).unwrap_or_default();
+    record.last_beat = clock::now_ms();
+    record.clean_shutdown = true;
+    let _ = write_record(&config.heartbeat_file, &record);
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tokio::time::{sleep, Duration};
+
+use super::trailing_stop::open_positions;
+use crate::config::EnvConfig;
+use crate::utils::{fetch_data, raise_alert, Alert, AlertKind, Logger};
+
+// Gamma takes this many condition_ids per request
+const GAMMA_BATCH: usize = 20;
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+// conditionId (lowercased) -> (last checked, in dispute)
+static DISPUTES: OnceLock<Mutex<HashMap<String, (Instant, bool)>>> = OnceLock::new();
+
+pub fn stop_market_disputes() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+fn disputes() -> &'static Mutex<HashMap<String, (Instant, bool)>> {
+    DISPUTES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Last known state, no network (trailing stop / market overrides check this before selling)
+pub fn is_market_disputed(condition_id: &str) -> bool {
+    disputes()
+        .lock()
+        .ok()
+        .and_then(|d| d.get(&condition_id.trim().to_lowercase()).map(|(_, disputed)| *disputed))
+        .unwrap_or(false)
+}
+
+// UMA status from Gamma: umaResolutionStatus, or the latest umaResolutionStatuses entry when that's missing.
+// Disputed / challenged = a proposal was challenged and the outcome is being voted on
+fn in_dispute(market: &serde_json::Value) -> bool {
+    let status = market
+        .get("umaResolutionStatus")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .or_else(|| {
+            let history = market.get("umaResolutionStatuses")?.as_str()?;
+            serde_json::from_str::<Vec<String>>(history).ok()?.pop()
+        })
+        .unwrap_or_default()
+        .to_lowercase();
+    status == "disputed" || status == "challenged"
+}
+
+// Record a state and alert on changes (a market seen for the first time only alerts when it's disputed)
+async fn update(config: &EnvConfig, http_client: &reqwest::Client, condition_id: &str, title: &str, disputed: bool) {
+    let was = {
+        let Ok(mut map) = disputes().lock() else {
+            return;
+        };
+        map.insert(condition_id.to_string(), (Instant::now(), disputed))
+            .map(|(_, d)| d)
+            .unwrap_or(false)
+    };
+    if disputed && !was {
+        raise_alert(
+            config,
+            http_client,
+            Alert::new(
+                AlertKind::MarketDisputed,
+                format!("{} is in a resolution dispute - copying and selling paused until it resolves", title),
+            )
+            .key(condition_id.to_string()),
+        )
+        .await;
+    } else if !disputed && was {
+        Logger::info(&format!("Resolution dispute over for {} - copying and selling resume", title));
+    }
+}
+
+// Fetch and record the state of these markets (unknown to Gamma = left as they were)
+async fn check_markets(config: &EnvConfig, http_client: &reqwest::Client, condition_ids: &[String]) -> Result<()> {
+    for chunk in condition_ids.chunks(GAMMA_BATCH) {
+        let query = chunk
+            .iter()
+            .map(|c| format!("condition_ids={}", c))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("https://gamma-api.polymarket.com/markets?{}", query);
+        let markets = fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+        for market in markets.as_array().into_iter().flatten() {
+            let Some(cid) = market.get("conditionId").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let title = market.get("question").and_then(|v| v.as_str()).unwrap_or(cid);
+            update(config, http_client, &cid.to_lowercase(), title, in_dispute(market)).await;
+        }
+    }
+    Ok(())
+}
+
+// Copy-time check for one market: cached for DISPUTE_CHECK_SECS, fetched otherwise.
+// Lookup failures don't block the copy (false) - the next check tries again
+pub async fn market_disputed(config: &EnvConfig, http_client: &reqwest::Client, condition_id: &str) -> bool {
+    if config.dispute_check_secs == 0 {
+        return false;
+    }
+    let cid = condition_id.trim().to_lowercase();
+    let cached = disputes()
+        .lock()
+        .ok()
+        .and_then(|d| d.get(&cid).copied())
+        .filter(|(at, _)| at.elapsed() < Duration::from_secs(config.dispute_check_secs));
+    if let Some((_, disputed)) = cached {
+        return disputed;
+    }
+    if let Err(e) = check_markets(config, http_client, std::slice::from_ref(&cid)).await {
+        Logger::warning(&format!("Dispute check failed for {}: {}", cid, e));
+    }
+    is_market_disputed(&cid)
+}
+
+// Background loop - re-checks every market we hold every DISPUTE_CHECK_SECS until stop_market_disputes()
+pub async fn run_market_disputes(config: EnvConfig, http_client: reqwest::Client) {
+    if config.dispute_check_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+
+    while RUNNING.load(Ordering::SeqCst) {
+        let held = open_positions(&config, &http_client).await.map(|positions| {
+            let mut cids: Vec<String> =
+                positions.iter().filter_map(|p| p.condition_id.as_deref().map(str::to_lowercase)).collect();
+            cids.sort();
+            cids.dedup();
+            cids
+        });
+        let checked = match held {
+            Ok(cids) => check_markets(&config, &http_client, &cids).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = checked {
+            Logger::warning(&format!("Dispute check failed: {}", e));
+        }
+        sleep(Duration::from_secs(config.dispute_check_secs)).await;
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::Result;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use super::market_disputes::is_market_disputed;
+use super::trailing_stop::open_positions;
+use crate::config::{effective_config, EnvConfig};
+use crate::db::Db;
+use crate::types::UserPosition;
+use crate::utils::{create_clob_client, exit_position, raise_alert, Alert, AlertKind, BotSigner, Logger};
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_market_overrides() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+// Trader whose copied buys built this position (None = manual position, left alone)
+async fn position_trader(config: &EnvConfig, db: &Db, position: &UserPosition, asset: &str) -> Option<String> {
+    for trader in &config.user_addresses {
+        let buys = db
+            .find_all_buy_activities_for_asset(trader, asset, &position.condition_id)
+            .await
+            .ok()?;
+        if !buys.is_empty() {
+            return Some(trader.clone());
+        }
+    }
+    None
+}
+
+struct MarketOverrides {
+    clob: Option<(ClobClient<Authenticated<Normal>>, BotSigner)>, // Created on the first sell
+}
+
+impl MarketOverrides {
+    // Tokens to sell for this position: all of it when the market is closed, the part over MARKET_MAX_USD otherwise
+    fn excess_tokens(config: &EnvConfig, position: &UserPosition, condition_id: &str) -> Option<f64> {
+        let size = position.size.unwrap_or(0.0);
+        if config.closed_markets.contains(&condition_id.to_lowercase()) {
+            return Some(size);
+        }
+        let cap = config.market_cap_usd(condition_id)?;
+        let price = position.cur_price.unwrap_or(0.0);
+        let value = size * price;
+        if price <= 0.0 || value <= cap {
+            return None;
+        }
+        Some(((value - cap) / price).min(size))
+    }
+
+    async fn check(&mut self, config: &EnvConfig, db: &Db, http_client: &reqwest::Client) -> Result<()> {
+        if config.closed_markets.is_empty() && config.market_max_usd.is_empty() {
+            return Ok(());
+        }
+        let positions = open_positions(config, http_client).await?;
+
+        for position in &positions {
+            let (Some(asset), Some(condition_id)) = (position.asset.clone(), position.condition_id.clone()) else {
+                continue;
+            };
+            let Some(tokens) = Self::excess_tokens(config, position, &condition_id) else {
+                continue;
+            };
+            if is_market_disputed(&condition_id) {
+                continue; // Retried once the dispute is over
+            }
+            let title = position.title.clone().unwrap_or_else(|| asset.clone());
+            let Some(trader) = position_trader(config, db, position, &asset).await else {
+                continue;
+            };
+            let closing = config.closed_markets.contains(&condition_id.to_lowercase());
+            Logger::warning(&format!(
+                "{}: {} {} - selling {:.2} of {:.2} tokens",
+                if closing { "close-market" } else { "resize-market" },
+                title,
+                position.outcome.as_deref().unwrap_or(""),
+                tokens,
+                position.size.unwrap_or(0.0)
+            ));
+
+            if self.clob.is_none() {
+                self.clob = Some(create_clob_client(config).await?);
+            }
+            let Some((clob_client, signer)) = self.clob.as_ref() else {
+                continue;
+            };
+            // exit_position sells position.size - hand it a copy sized to the excess
+            let mut slice = position.clone();
+            slice.size = Some(tokens);
+            match exit_position(config, clob_client, signer, http_client, db, &trader, &slice).await {
+                Ok(sold) if sold > 0.0 => {
+                    raise_alert(
+                        config,
+                        http_client,
+                        Alert::new(
+                            AlertKind::General,
+                            format!(
+                                "{} sold {:.2} tokens of {} (trader {})",
+                                if closing { "close-market" } else { "resize-market" },
+                                sold,
+                                title,
+                                Logger::format_address(&trader)
+                            ),
+                        )
+                        .key(asset.clone()),
+                    )
+                    .await;
+                }
+                Ok(_) => Logger::warning(&format!("Market override for {}: nothing sold (no bids?), retrying next check", title)),
+                Err(e) => Logger::error(&format!("Market override sell failed for {}: {}", title, e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+// Background loop - sells closed markets / trims positions over their resize-market cap until stop_market_overrides()
+// FYI: checked every CONFIG_REFRESH_SECS, the same cadence overrides are picked up at
+pub async fn run_market_overrides(config: EnvConfig, db: Db, http_client: reqwest::Client) {
+    RUNNING.store(true, Ordering::SeqCst);
+    let mut overrides = MarketOverrides { clob: None };
+
+    while RUNNING.load(Ordering::SeqCst) {
+        let live = effective_config(&config);
+        if let Err(e) = overrides.check(&live, &db, &http_client).await {
+            Logger::warning(&format!("Market override check failed: {}", e));
+        }
+        sleep(Duration::from_secs(config.config_refresh_secs.max(5))).await;
+    }
+}
@@ -1,5 +1,35 @@
+mod allowance_monitor;
+mod capital_sampler;
+mod config_refresher;
+mod dormant_traders;
+mod drawdown_scaler;
+mod heartbeat;
+mod market_disputes;
+mod market_overrides;
+mod report_scheduler;
+mod resource_monitor;
 mod trade_executor;
 mod trade_monitor;
+mod trailing_stop;
+mod volatility_regime;
+mod wallet_watcher;
 
+pub use allowance_monitor::{run_allowance_monitor, stop_allowance_monitor};
+pub use capital_sampler::{run_capital_sampler, stop_capital_sampler};
+pub use config_refresher::{refresh_overrides, run_config_refresher, stop_config_refresher};
+pub use dormant_traders::{run_dormant_traders, stop_dormant_traders};
+pub use drawdown_scaler::{drawdown_scale, drawdown_state, DrawdownState};
+pub use heartbeat::{finish_heartbeat, run_heartbeat, stop_heartbeat};
+pub use market_disputes::{is_market_disputed, market_disputed, run_market_disputes, stop_market_disputes};
+pub use market_overrides::{run_market_overrides, stop_market_overrides};
+pub use report_scheduler::{run_report_scheduler, stop_report_scheduler};
+pub use resource_monitor::{latest_resources, run_resource_monitor, stop_resource_monitor, ResourceSnapshot};
 pub use trade_executor::{run_trade_executor, stop_trade_executor};
-pub use trade_monitor::{run_trade_monitor, stop_trade_monitor};
+pub use trade_monitor::{run_trade_monitor, stop_trade_monitor, TradeMonitorHandle};
+pub use trailing_stop::{run_trailing_stop, stop_trailing_stop};
+pub use volatility_regime::{
+    run_volatility_regime, stop_volatility_regime, volatility_haircut, volatility_regime, VolatilityRegime,
+};
+pub use wallet_watcher::{
+    cached_usdc_balance, invalidate_balance_cache, run_wallet_watcher, stop_wallet_watcher,
+};
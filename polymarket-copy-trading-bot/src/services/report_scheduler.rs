@@ -0,0 +1,106 @@
+use chrono::{Datelike, Timelike};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::utils::{build_digest, clock, report_channels, send_digest, Logger, ReportPeriod};
+
+// How often the scheduler looks at the clock
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_report_scheduler() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+// Slot the digest for `period` belongs to right now (ET), or None before REPORT_HOUR / on the wrong weekday
+// e.g. daily "2026-10-15", weekly "2026-W42" - one delivery per slot, kept in the DB across restarts
+fn due_slot(config: &EnvConfig, period: ReportPeriod) -> Option<String> {
+    let now = clock::market_time(clock::from_ms(clock::now_ms()));
+    if now.hour() < config.report_hour {
+        return None;
+    }
+    match period {
+        ReportPeriod::Daily => Some(now.format("%Y-%m-%d").to_string()),
+        ReportPeriod::Weekly => {
+            let weekday = now.weekday().to_string().to_lowercase();
+            // "mon", "monday", "Mon" all match
+            if config.report_weekday.get(..3) != Some(weekday.as_str()) {
+                return None;
+            }
+            let week = now.iso_week();
+            Some(format!("{}-W{:02}", week.year(), week.week()))
+        }
+    }
+}
+
+// Background loop - sends REPORT_SCHEDULE digests at REPORT_HOUR ET until stop_report_scheduler()
+pub async fn run_report_scheduler(config: EnvConfig, db: Db, http_client: reqwest::Client) {
+    let periods: Vec<ReportPeriod> = config.report_schedule.iter().filter_map(|p| ReportPeriod::parse(p)).collect();
+    if periods.is_empty() {
+        return;
+    }
+    let channels = report_channels(&config);
+    if channels.is_empty() {
+        Logger::warning("REPORT_SCHEDULE is set but no REPORT_WEBHOOK_URL / REPORT_SMTP_HOST + REPORT_EMAIL_TO - digests are off");
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    Logger::info(&format!(
+        "Report digests: {} at {:02}:00 ET",
+        periods.iter().map(|p| p.as_str()).collect::<Vec<_>>().join(" + "),
+        config.report_hour
+    ));
+
+    while RUNNING.load(Ordering::SeqCst) {
+        for period in &periods {
+            let Some(slot) = due_slot(&config, *period) else {
+                continue;
+            };
+            // Recorded per channel, so a failed one is retried without resending the others
+            // (a slot recorded under the bare period predates per-channel records and counts for all)
+            if db.get_report_sent(period.as_str()).await.ok().flatten().as_deref() == Some(slot.as_str()) {
+                continue;
+            }
+            let mut pending = Vec::new();
+            for channel in &channels {
+                let key = format!("{}:{}", period.as_str(), channel.as_str());
+                if db.get_report_sent(&key).await.ok().flatten().as_deref() != Some(slot.as_str()) {
+                    pending.push((*channel, key));
+                }
+            }
+            if pending.is_empty() {
+                continue;
+            }
+            let digest = match build_digest(&config, &db, &http_client, *period).await {
+                Ok(digest) => digest,
+                // Retried next check
+                Err(e) => {
+                    Logger::warning(&format!("{} digest failed: {}", period.as_str(), e));
+                    continue;
+                }
+            };
+            for (channel, key) in pending {
+                match send_digest(&config, &http_client, &digest, channel).await {
+                    Ok(()) => {
+                        Logger::info(&format!("Sent {} digest by {} ({})", period.as_str(), channel.as_str(), slot));
+                        if let Err(e) = db.set_report_sent(&key, &slot).await {
+                            Logger::warning(&format!("Could not record the {} digest: {}", period.as_str(), e));
+                        }
+                    }
+                    // Retried next check
+                    Err(e) => Logger::warning(&format!(
+                        "{} digest by {} failed: {}",
+                        period.as_str(),
+                        channel.as_str(),
+                        e
+                    )),
+                }
+            }
+        }
+        sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::utils::{clock, Logger};
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+// Last sample, picked up by the heartbeat file
+static LATEST: Mutex<Option<ResourceSnapshot>> = Mutex::new(None);
+
+pub fn stop_resource_monitor() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSnapshot {
+    pub sampled_at: i64,
+    pub rss_mb: Option<f64>, // Linux only
+    pub tasks: Option<usize>, // Tokio tasks alive
+    pub trade_backlog: u64, // Trades seen but not yet handled, all traders
+    pub mongo_ping_ms: Option<u64>, // None = ping failed
+}
+
+pub fn latest_resources() -> Option<ResourceSnapshot> {
+    LATEST.lock().ok().and_then(|s| s.clone())
+}
+
+// Resident memory from /proc (4 KiB pages)
+fn rss_mb() -> Option<f64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: f64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some((pages * 4096.0 / (1024.0 * 1024.0) * 10.0).round() / 10.0)
+}
+
+async fn sample(config: &EnvConfig, db: &Db) -> ResourceSnapshot {
+    let started = clock::now_ms();
+    let mongo_ping_ms = db.ping().await.ok().map(|_| (clock::now_ms() - started).max(0) as u64);
+    let mut trade_backlog = 0;
+    for trader in &config.user_addresses {
+        trade_backlog += db.count_unprocessed_trades(trader).await.unwrap_or(0);
+    }
+    ResourceSnapshot {
+        sampled_at: clock::now_ms(),
+        rss_mb: rss_mb(),
+        tasks: tokio::runtime::Handle::try_current().ok().map(|h| h.metrics().num_alive_tasks()),
+        trade_backlog,
+        mongo_ping_ms,
+    }
+}
+
+// Limits crossed by one sample, by name
+fn breaches(config: &EnvConfig, s: &ResourceSnapshot) -> Vec<(&'static str, String)> {
+    let mut out = Vec::new();
+    if let Some(mb) = s.rss_mb.filter(|mb| *mb > config.resource_max_rss_mb) {
+        out.push(("rss", format!("memory {:.0}MB > {:.0}MB", mb, config.resource_max_rss_mb)));
+    }
+    if let Some(tasks) = s.tasks.filter(|t| *t > config.resource_max_tasks) {
+        out.push(("tasks", format!("{} tasks alive > {}", tasks, config.resource_max_tasks)));
+    }
+    if s.trade_backlog > config.resource_max_backlog {
+        out.push(("backlog", format!("{} trades waiting to be copied > {}", s.trade_backlog, config.resource_max_backlog)));
+    }
+    match s.mongo_ping_ms {
+        Some(ms) if ms > config.resource_max_mongo_ms => {
+            out.push(("mongo", format!("MongoDB round trip {}ms > {}ms", ms, config.resource_max_mongo_ms)))
+        }
+        None => out.push(("mongo", "MongoDB ping failed".to_string())),
+        _ => {}
+    }
+    out
+}
+
+// Background loop - runs until stop_resource_monitor() (disabled when RESOURCE_CHECK_SECS is 0)
+// Warns when a limit is crossed and once when it recovers, not on every sample
+pub async fn run_resource_monitor(config: EnvConfig, db: Db) {
+    if config.resource_check_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let mut breached: Vec<&'static str> = Vec::new();
+
+    while RUNNING.load(Ordering::SeqCst) {
+        let snapshot = sample(&config, &db).await;
+        let current = breaches(&config, &snapshot);
+        for (name, msg) in &current {
+            if !breached.contains(name) {
+                Logger::warning(&format!("Resource limit: {}", msg));
+            }
+        }
+        for name in &breached {
+            if !current.iter().any(|(n, _)| n == name) {
+                Logger::info(&format!("Resource back under its limit: {}", name));
+            }
+        }
+        breached = current.into_iter().map(|(name, _)| name).collect();
+        if let Ok(mut latest) = LATEST.lock() {
+            *latest = Some(snapshot);
+        }
+        sleep(Duration::from_secs(config.resource_check_secs)).await;
+    }
+}
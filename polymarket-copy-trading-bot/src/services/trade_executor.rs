@@ -1,18 +1,22 @@
 use anyhow::Result;
-use alloy::signers::local::PrivateKeySigner;
 use polymarket_client_sdk::clob::Client as ClobClient;
 use polymarket_client_sdk::auth::state::Authenticated;
 use polymarket_client_sdk::auth::Normal;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tokio::time::{sleep, Duration, Instant};
+use tokio::time::{sleep, timeout, Duration, Instant};
 
-use crate::config::EnvConfig;
+use crate::config::{effective_config, EnvConfig, MarketThrottleMode};
 use crate::db::Db;
-use crate::types::{UserActivity, UserPosition};
-use crate::utils::{create_clob_client, fetch_data, get_usdc_balance, post_order, Logger};
+use crate::services::market_disputes::market_disputed;
+use crate::services::wallet_watcher::{cached_usdc_balance, invalidate_balance_cache};
+use crate::types::{SkipReason, UserActivity, UserPosition};
+use crate::utils::{
+    create_clob_client, fetch_paginated, fetch_quote, market_category, paper_wallet, post_order, raise_alert,
+    spawn_trader_note, Alert, AlertKind, BotSigner, Logger,
+};
 
 // Min USD to aggregate trades (small trades get batched)
 const TRADE_AGGREGATION_MIN_TOTAL_USD: f64 = 1.0;
@@ -38,6 +42,7 @@ struct AggregatedTrade {
     event_slug: Option<String>,
     trades: Vec<TradeWithUser>,
     total_usdc_size: f64,
+    total_size: f64,
     average_price: f64,
     first_trade_time: Instant,
     last_trade_time: Instant,
@@ -46,6 +51,15 @@ struct AggregatedTrade {
 // Thread-safe buffer for aggregating trades
 type AggregationBuffer = Arc<Mutex<HashMap<String, AggregatedTrade>>>;
 
+// Copies per trader+market in the last hour (in memory - a restart starts the count over)
+type CopyLog = HashMap<String, VecDeque<Instant>>;
+
+// Over-limit trades held for netting, per trader+market+outcome (both sides)
+struct NetGroup {
+    trades: Vec<TradeWithUser>,
+    first_trade_time: Instant,
+}
+
 // Fetch unprocessed trades from DB for all tracked traders
 async fn read_temp_trades(config: &EnvConfig, db: &Db) -> Result<Vec<TradeWithUser>> {
     let mut all_trades = Vec::new();
@@ -63,23 +77,45 @@ async fn read_temp_trades(config: &EnvConfig, db: &Db) -> Result<Vec<TradeWithUs
     Ok(all_trades)
 }
 
-// Generate key for grouping trades (user:condition:asset:side)
-fn get_aggregation_key(trade: &TradeWithUser) -> String {
+// Generate key for grouping trades (trader:condition:asset:side - side is "NET" when netting both sides)
+// Wallets of one TRADER_GROUPS group share the trader part, so their trades batch together
+fn get_aggregation_key(config: &EnvConfig, trade: &TradeWithUser, net_sides: bool) -> String {
     format!(
         "{}:{}:{}:{}",
-        trade.user_address,
+        config.trader_key(&trade.user_address),
         trade.trade.condition_id.as_deref().unwrap_or(""),
         trade.trade.asset.as_deref().unwrap_or(""),
-        trade.trade.side.as_deref().unwrap_or("BUY")
+        if net_sides { "NET" } else { trade.trade.side.as_deref().unwrap_or("BUY") }
     )
 }
 
+// Buffered trades stay unprocessed in the DB until their group executes - drop them from each poll
+async fn without_buffered(buffer: &AggregationBuffer, trades: Vec<TradeWithUser>) -> Vec<TradeWithUser> {
+    let buffer_guard = buffer.lock().await;
+    trades
+        .into_iter()
+        .filter(|t| {
+            t.trade.id.is_none()
+                || !buffer_guard
+                    .values()
+                    .any(|g| g.trades.iter().any(|b| b.trade.id == t.trade.id))
+        })
+        .collect()
+}
+
+// TRADE_AGGREGATION_MIN_TOTAL_USD is a buy-side floor - an exit is copied however small it is
+fn below_minimum(agg: &AggregatedTrade) -> bool {
+    agg.side != "SELL" && agg.total_usdc_size < TRADE_AGGREGATION_MIN_TOTAL_USD
+}
+
 // Add trade to aggregation buffer (batches small trades)
 async fn add_to_aggregation_buffer(
+    config: &EnvConfig,
     buffer: &AggregationBuffer,
     trade: TradeWithUser,
+    net_sides: bool,
 ) -> Result<()> {
-    let key = get_aggregation_key(&trade);
+    let key = get_aggregation_key(config, &trade, net_sides);
     let mut buffer_guard = buffer.lock().await;
     let now = Instant::now();
 
@@ -87,6 +123,7 @@ async fn add_to_aggregation_buffer(
     if let Some(existing) = buffer_guard.get_mut(&key) {
         existing.trades.push(trade.clone());
         existing.total_usdc_size += trade.trade.usdc_size.unwrap_or(0.0);
+        existing.total_size += trade.trade.size.unwrap_or(0.0);
         // Recalc weighted avg price
         let mut total_value = 0.0;
         for t in &existing.trades {
@@ -113,6 +150,7 @@ async fn add_to_aggregation_buffer(
                 event_slug: trade.trade.event_slug.clone(),
                 trades: vec![trade],
                 total_usdc_size: usdc_size,
+                total_size: trade.trade.size.unwrap_or(0.0),
                 average_price: price,
                 first_trade_time: now,
                 last_trade_time: now,
@@ -139,7 +177,18 @@ async fn get_ready_aggregated_trades(
         let time_elapsed = now.duration_since(agg.first_trade_time);
 
         if time_elapsed >= window_duration {
-            if agg.total_usdc_size >= TRADE_AGGREGATION_MIN_TOTAL_USD {
+            if is_mixed(agg) {
+                let netted = net_trades(agg.trades.clone(), agg.first_trade_time);
+                if !below_minimum(&netted) {
+                    ready.push(netted);
+                } else {
+                    for trade in &agg.trades {
+                        if let Some(ref id) = trade.trade.id {
+                            db.mark_skipped(&trade.user_address, id, SkipReason::NettedOut).await?;
+                        }
+                    }
+                }
+            } else if !below_minimum(agg) {
                 ready.push(agg.clone());
             } else {
                 let asset_display = agg
@@ -159,12 +208,8 @@ async fn get_ready_aggregated_trades(
 
                 for trade in &agg.trades {
                     if let Some(ref id) = trade.trade.id {
-                        db.update_activity(
-                            &trade.user_address,
-                            id,
-                            &mongodb::bson::doc! { "bot": true },
-                        )
-                        .await?;
+                        db.mark_skipped(&trade.user_address, id, SkipReason::AggregationBelowMinimum)
+                            .await?;
                     }
                 }
             }
@@ -179,6 +224,500 @@ async fn get_ready_aggregated_trades(
     Ok(ready)
 }
 
+// Drop trades into MARKET_BLACKLIST markets, and buys outside COPY_MIN_PRICE..COPY_MAX_PRICE (sells are still
+// mirrored so copied positions can exit) - marked skipped with the reason
+async fn skip_filtered_trades(config: &EnvConfig, db: &Db, trades: Vec<TradeWithUser>) -> Vec<TradeWithUser> {
+    let mut out = Vec::with_capacity(trades.len());
+    for trade in trades {
+        let price = trade.trade.price.unwrap_or(0.0);
+        let reason = if let Some(entry) = config.blacklist_match(&trade.trade) {
+            Logger::warning(&format!(
+                "Market matches MARKET_BLACKLIST \"{}\" - skipping trade from {}",
+                entry,
+                Logger::format_address(&trade.user_address)
+            ));
+            SkipReason::Blacklisted
+        } else if trade.trade.side.as_deref() == Some("BUY") && price > 0.0 && config.outside_price_band(price) {
+            Logger::warning(&format!(
+                "Buy at {:.3} is outside COPY_MIN_PRICE..COPY_MAX_PRICE - skipping trade from {}",
+                price,
+                Logger::format_address(&trade.user_address)
+            ));
+            SkipReason::PriceBand
+        } else {
+            out.push(trade);
+            continue;
+        };
+        if let Some(ref id) = trade.trade.id {
+            if let Err(e) = db.mark_skipped(&trade.user_address, id, reason).await {
+                Logger::error(&format!("Failed to mark skipped trade: {}", e));
+            }
+        }
+    }
+    out
+}
+
+// Drop trades while paused (globally, per trader / market, or the market is in a resolution dispute) -
+// marked skipped so they aren't copied late on resume
+async fn skip_paused_trades(
+    config: &EnvConfig,
+    db: &Db,
+    http_client: &reqwest::Client,
+    trades: Vec<TradeWithUser>,
+) -> Vec<TradeWithUser> {
+    let mut out = Vec::with_capacity(trades.len());
+    for trade in trades {
+        let market_blocked = trade
+            .trade
+            .condition_id
+            .as_deref()
+            .map(|cid| config.is_market_blocked(cid))
+            .unwrap_or(false);
+        let paused = config.trading_paused
+            || config.is_trader_paused(&trade.user_address)
+            || market_blocked;
+        let disputed = !paused
+            && match trade.trade.condition_id.as_deref() {
+                Some(cid) => market_disputed(config, http_client, cid).await,
+                None => false,
+            };
+        if disputed {
+            Logger::warning(&format!(
+                "Market in a resolution dispute - skipping trade from {}",
+                Logger::format_address(&trade.user_address)
+            ));
+            if let Some(ref id) = trade.trade.id {
+                if let Err(e) = db.mark_skipped(&trade.user_address, id, SkipReason::Disputed).await {
+                    Logger::error(&format!("Failed to mark disputed trade: {}", e));
+                }
+            }
+            continue;
+        }
+        if !paused {
+            out.push(trade);
+            continue;
+        }
+        Logger::warning(&format!(
+            "Trading paused{} - skipping trade from {}",
+            if config.trading_paused {
+                ""
+            } else if market_blocked {
+                " for this market"
+            } else {
+                " for this trader"
+            },
+            Logger::format_address(&trade.user_address)
+        ));
+        if let Some(ref id) = trade.trade.id {
+            if let Err(e) = db.mark_skipped(&trade.user_address, id, SkipReason::Paused).await {
+                Logger::error(&format!("Failed to mark paused trade: {}", e));
+            }
+        }
+    }
+    out
+}
+
+// Hold new trades for COPY_DELAY_SECONDS, then copy only those whose price hasn't run away
+// (held trades stay unprocessed in the DB, keyed here by id so each poll doesn't re-add them)
+async fn delay_trades(
+    config: &EnvConfig,
+    db: &Db,
+    http_client: &reqwest::Client,
+    delay_buffer: &mut HashMap<String, (TradeWithUser, Instant)>,
+    trades: Vec<TradeWithUser>,
+) -> Vec<TradeWithUser> {
+    let delay = Duration::from_secs(config.copy_delay_seconds);
+    // A held trade that's no longer unprocessed (e.g. skipped while paused) is dropped
+    let pending: std::collections::HashSet<String> =
+        trades.iter().filter_map(|t| t.trade.id.map(|id| id.to_hex())).collect();
+    delay_buffer.retain(|id, _| pending.contains(id));
+
+    let mut out = Vec::with_capacity(trades.len());
+    for trade in trades {
+        let Some(id) = trade.trade.id.map(|id| id.to_hex()) else {
+            out.push(trade);
+            continue;
+        };
+        if delay.is_zero() && !delay_buffer.contains_key(&id) {
+            out.push(trade);
+            continue;
+        }
+        delay_buffer.entry(id).or_insert_with(|| {
+            Logger::info(&format!(
+                "Holding trade from {} for {}s before copying",
+                Logger::format_address(&trade.user_address),
+                config.copy_delay_seconds
+            ));
+            (trade, Instant::now())
+        });
+    }
+
+    let ready: Vec<String> = delay_buffer
+        .iter()
+        .filter(|(_, (_, held_at))| held_at.elapsed() >= delay)
+        .map(|(id, _)| id.clone())
+        .collect();
+    for id in ready {
+        let Some((trade, _)) = delay_buffer.remove(&id) else {
+            continue;
+        };
+        let trader_price = trade.trade.price.unwrap_or(0.0);
+        let asset = trade.trade.asset.as_deref().unwrap_or("");
+        let quote = if trader_price > 0.0 && !asset.is_empty() {
+            fetch_quote(config, http_client, asset).await
+        } else {
+            None
+        };
+        // No quote = let post_order deal with the book as usual
+        let Some((best_bid, best_ask)) = quote else {
+            out.push(trade);
+            continue;
+        };
+        let is_buy = trade.trade.side.as_deref() != Some("SELL");
+        let drift_percent = if is_buy {
+            (best_ask - trader_price) / trader_price * 100.0
+        } else {
+            (trader_price - best_bid) / trader_price * 100.0
+        };
+        if drift_percent <= config.copy_delay_max_drift_percent {
+            out.push(trade);
+            continue;
+        }
+        Logger::warning(&format!(
+            "Price moved {:.1}% against the copy after {}s ({} ${:.4} vs trader ${:.4}) - skipping",
+            drift_percent,
+            config.copy_delay_seconds,
+            if is_buy { "ask" } else { "bid" },
+            if is_buy { best_ask } else { best_bid },
+            trader_price
+        ));
+        if let Some(ref id) = trade.trade.id {
+            if let Err(e) = db.mark_skipped(&trade.user_address, id, SkipReason::PriceMoved).await {
+                Logger::error(&format!("Failed to mark skipped trade: {}", e));
+            }
+        }
+    }
+    out
+}
+
+// Throttle key: trader + market (both outcomes count; a trader group shares one budget)
+fn throttle_key(config: &EnvConfig, trade: &TradeWithUser) -> String {
+    format!(
+        "{}:{}",
+        config.trader_key(&trade.user_address),
+        trade.trade.condition_id.as_deref().unwrap_or("")
+    )
+}
+
+// Count a copy against the trader's hourly budget for this market
+fn record_copy(copy_log: &mut CopyLog, key: String) {
+    copy_log.entry(key).or_default().push_back(Instant::now());
+}
+
+// Enforce MAX_COPIES_PER_MARKET_PER_HOUR - extra trades are skipped or held for netting
+async fn throttle_trades(
+    config: &EnvConfig,
+    db: &Db,
+    copy_log: &mut CopyLog,
+    net_buffer: &mut HashMap<String, NetGroup>,
+    trades: Vec<TradeWithUser>,
+) -> Vec<TradeWithUser> {
+    let max = config.max_copies_per_market_per_hour as usize;
+    if max == 0 {
+        return trades;
+    }
+    let hour_ago = Instant::now().checked_sub(Duration::from_secs(3600));
+    copy_log.retain(|_, times| {
+        while matches!((times.front(), hour_ago), (Some(t), Some(h)) if *t < h) {
+            times.pop_front();
+        }
+        !times.is_empty()
+    });
+
+    let mut out = Vec::with_capacity(trades.len());
+    for trade in trades {
+        // Held trades stay unprocessed in the DB until netted - don't count them twice
+        let held = trade.trade.id.is_some()
+            && net_buffer
+                .values()
+                .any(|g| g.trades.iter().any(|t| t.trade.id == trade.trade.id));
+        if held {
+            continue;
+        }
+        let key = throttle_key(config, &trade);
+        if copy_log.get(&key).map(|t| t.len()).unwrap_or(0) < max {
+            record_copy(copy_log, key);
+            out.push(trade);
+            continue;
+        }
+        let market = trade
+            .trade
+            .slug
+            .clone()
+            .or_else(|| trade.trade.condition_id.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        match config.market_throttle_mode {
+            MarketThrottleMode::Skip => {
+                Logger::warning(&format!(
+                    "{} already copied {}x on {} this hour - skipping",
+                    Logger::format_address(&trade.user_address),
+                    max,
+                    market
+                ));
+                if let Some(ref id) = trade.trade.id {
+                    if let Err(e) = db.mark_skipped(&trade.user_address, id, SkipReason::Throttled).await {
+                        Logger::error(&format!("Failed to mark throttled trade: {}", e));
+                    }
+                }
+            }
+            MarketThrottleMode::Aggregate => {
+                Logger::info(&format!(
+                    "{} over {} copies/hour on {} - holding trade for netting",
+                    Logger::format_address(&trade.user_address),
+                    max,
+                    market
+                ));
+                let net_key = format!("{}:{}", key, trade.trade.asset.as_deref().unwrap_or(""));
+                net_buffer
+                    .entry(net_key)
+                    .or_insert_with(|| NetGroup {
+                        trades: Vec::new(),
+                        first_trade_time: Instant::now(),
+                    })
+                    .trades
+                    .push(trade);
+            }
+        }
+    }
+    out
+}
+
+// Net held trades whose window has passed: buys minus sells (in tokens) becomes one order
+async fn get_ready_netted_trades(
+    net_buffer: &mut HashMap<String, NetGroup>,
+    window_seconds: u64,
+    db: &Db,
+) -> Vec<AggregatedTrade> {
+    let window_duration = Duration::from_secs(window_seconds);
+    let ready_keys: Vec<String> = net_buffer
+        .iter()
+        .filter(|(_, g)| g.first_trade_time.elapsed() >= window_duration)
+        .map(|(k, _)| k.clone())
+        .collect();
+
+    let mut ready = Vec::new();
+    for key in ready_keys {
+        let Some(group) = net_buffer.remove(&key) else {
+            continue;
+        };
+        let netted = net_trades(group.trades, group.first_trade_time);
+
+        if netted.total_usdc_size < TRADE_AGGREGATION_MIN_TOTAL_USD {
+            for t in &netted.trades {
+                if let Some(ref id) = t.trade.id {
+                    if let Err(e) = db.mark_skipped(&t.user_address, id, SkipReason::Throttled).await {
+                        Logger::error(&format!("Failed to mark throttled trade: {}", e));
+                    }
+                }
+            }
+            continue;
+        }
+        ready.push(netted);
+    }
+    ready
+}
+
+// Buys minus sells (in tokens) as one order on the larger side, priced at that side's average
+fn net_trades(trades: Vec<TradeWithUser>, first_trade_time: Instant) -> AggregatedTrade {
+    let (mut buy_size, mut buy_usd, mut sell_size, mut sell_usd) = (0.0, 0.0, 0.0, 0.0);
+    for t in &trades {
+        let size = t.trade.size.unwrap_or(0.0);
+        let usdc = t.trade.usdc_size.unwrap_or(0.0);
+        if t.trade.side.as_deref() == Some("SELL") {
+            sell_size += size;
+            sell_usd += usdc;
+        } else {
+            buy_size += size;
+            buy_usd += usdc;
+        }
+    }
+    let net_size: f64 = buy_size - sell_size;
+    let (side, side_size, side_usd) = if net_size >= 0.0 {
+        ("BUY", buy_size, buy_usd)
+    } else {
+        ("SELL", sell_size, sell_usd)
+    };
+    let average_price = if side_size > 0.0 { side_usd / side_size } else { 0.0 };
+    let net_usd = net_size.abs() * average_price;
+
+    let first = &trades[0];
+    let asset_display = first
+        .trade
+        .slug
+        .as_ref()
+        .or(first.trade.asset.as_ref())
+        .map(|s| s.as_str())
+        .unwrap_or("unknown");
+    Logger::info(&format!(
+        "Netted {} trade(s) from {} on {}: bought {:.2}, sold {:.2} -> {} {:.2} tokens (${:.2})",
+        trades.len(),
+        Logger::format_address(&first.user_address),
+        asset_display,
+        buy_size,
+        sell_size,
+        side,
+        net_size.abs(),
+        net_usd
+    ));
+
+    AggregatedTrade {
+        user_address: first.user_address.clone(),
+        condition_id: first.trade.condition_id.clone(),
+        asset: first.trade.asset.clone(),
+        side: side.to_string(),
+        slug: first.trade.slug.clone(),
+        event_slug: first.trade.event_slug.clone(),
+        total_usdc_size: net_usd,
+        total_size: net_size.abs(),
+        average_price,
+        first_trade_time,
+        last_trade_time: Instant::now(),
+        trades,
+    }
+}
+
+// My position in the traded outcome + my position in the other outcome of the same market
+fn split_my_positions<'a>(
+    my_positions: &'a [UserPosition],
+    condition_id: Option<&str>,
+    asset: Option<&str>,
+) -> (Option<&'a UserPosition>, Option<&'a UserPosition>) {
+    let in_market = |p: &&UserPosition| p.condition_id.as_deref() == condition_id;
+    let same = my_positions
+        .iter()
+        .filter(in_market)
+        .find(|p| p.asset.as_deref() == asset);
+    let opposite = my_positions
+        .iter()
+        .filter(in_market)
+        .find(|p| p.asset.as_deref() != asset && p.size.unwrap_or(0.0) > 0.0);
+    (same, opposite)
+}
+
+// My positions: PROXY_WALLET's from the data API, or the paper wallet's (replayed paper_trades) under DRY_RUN
+async fn fetch_my_positions(config: &EnvConfig, http_client: &reqwest::Client, db: &Db) -> Result<Vec<UserPosition>> {
+    if config.dry_run {
+        return Ok(paper_wallet(db, config.paper_balance_usd).await?.1);
+    }
+    let my_positions_url = format!(
+        "https://data-api.polymarket.com/positions?user={}",
+        config.proxy_wallet
+    );
+    let my_positions_data: serde_json::Value = fetch_paginated(
+        http_client,
+        &my_positions_url,
+        config.request_timeout_ms,
+        config.network_retry_limit,
+    )
+    .await?;
+    Ok(my_positions_data
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+// USDC copies are sized from: the wallet's, or PAPER_BALANCE_USD net of paper fills under DRY_RUN
+async fn my_usdc_balance(config: &EnvConfig, db: &Db) -> f64 {
+    if !config.dry_run {
+        return cached_usdc_balance(config).await.unwrap_or(0.0);
+    }
+    match paper_wallet(db, config.paper_balance_usd).await {
+        Ok((cash, _)) => cash.max(0.0),
+        Err(e) => {
+            Logger::warning(&format!("Failed to read paper trades: {}", e));
+            0.0
+        }
+    }
+}
+
+// Trader's positions across every wallet of their TRADER_GROUPS group (just the one wallet when ungrouped)
+async fn fetch_trader_positions(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    user_address: &str,
+) -> Result<Vec<UserPosition>> {
+    let mut positions = Vec::new();
+    for member in config.group_members(user_address) {
+        let url = format!("https://data-api.polymarket.com/positions?user={}", member);
+        let data: serde_json::Value = fetch_paginated(
+            http_client,
+            &url,
+            config.request_timeout_ms,
+            config.network_retry_limit,
+        )
+        .await?;
+        if let Some(arr) = data.as_array() {
+            positions.extend(arr.iter().filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok()));
+        }
+    }
+    Ok(positions)
+}
+
+// Trader's position in this market: the first outcome found, summed across the group's wallets
+fn trader_position(positions: &[UserPosition], condition_id: Option<&str>) -> Option<UserPosition> {
+    let first = positions.iter().find(|p| p.condition_id.as_deref() == condition_id)?;
+    let mut merged = first.clone();
+    let same_outcome = positions
+        .iter()
+        .filter(|p| p.condition_id.as_deref() == condition_id && p.asset == first.asset);
+    let (size, value, initial) = same_outcome.fold((0.0, 0.0, 0.0), |(s, v, i), p| {
+        (
+            s + p.size.unwrap_or(0.0),
+            v + p.current_value.unwrap_or(0.0),
+            i + p.initial_value.unwrap_or(0.0),
+        )
+    });
+    merged.size = Some(size);
+    merged.current_value = Some(value);
+    merged.initial_value = Some(initial);
+    if size > 0.0 {
+        merged.avg_price = Some(initial / size);
+    }
+    Some(merged)
+}
+
+// "Processing" marker, stamped with the tags active at execution time and the market's category
+// (a tag or category lookup failure never blocks the trade)
+async fn processing_update(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    db: &Db,
+    user_address: &str,
+    trade: &UserActivity,
+) -> mongodb::bson::Document {
+    let mut update = mongodb::bson::doc! { "botExcutedTime": 1_i64 };
+    if config.dry_run {
+        update.insert("dryRun", true);
+    }
+    match db.tags_for_trade(user_address, trade).await {
+        Ok(tags) if !tags.is_empty() => {
+            update.insert("tags", tags);
+        }
+        Ok(_) => {}
+        Err(e) => Logger::warning(&format!("Failed to load tags: {}", e)),
+    }
+    if let Some(cid) = trade.condition_id.as_deref() {
+        if let Some(category) = market_category(config, http_client, cid).await {
+            update.insert("category", category.as_str());
+        }
+    }
+    update
+}
+
 // Execute trades immediately (no aggregation)
 async fn do_trading(
     config: &EnvConfig,
@@ -186,20 +725,21 @@ async fn do_trading(
     clob_client: &ClobClient<Authenticated<Normal>>,
     http_client: &reqwest::Client,
     db: &Db,
-    signer: &mut PrivateKeySigner,
+    signer: &mut BotSigner,
 ) -> Result<()> {
     for trade in trades {
-        // Mark as processing in DB
+        // Mark as processing in DB (+ trader/market tags for reports)
         if let Some(ref id) = trade.trade.id {
             db.update_activity(
                 &trade.user_address,
                 id,
-                &mongodb::bson::doc! { "botExcutedTime": 1_i64 },
+                &processing_update(config, http_client, db, &trade.user_address, &trade.trade).await,
             )
             .await?;
         }
 
-        // Log trade details
+        // Log trade details (the trader's recent comment on the market follows as a trade_note alert with TRADE_NOTES)
+        spawn_trader_note(config, http_client, &trade.user_address, &trade.trade);
         Logger::trade(
             &trade.user_address,
             trade.trade.side.as_deref().unwrap_or("UNKNOWN"),
@@ -215,63 +755,17 @@ async fn do_trading(
             },
         );
 
-        // Fetch positions for both wallets (yours & trader's)
-        let my_positions_url = format!(
-            "https://data-api.polymarket.com/positions?user={}",
-            config.proxy_wallet
-        );
-        let user_positions_url = format!(
-            "https://data-api.polymarket.com/positions?user={}",
-            trade.user_address
-        );
-
-        let my_positions_data: serde_json::Value = fetch_data(
-            http_client,
-            &my_positions_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-        let user_positions_data: serde_json::Value = fetch_data(
-            http_client,
-            &user_positions_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-
-        let my_positions: Vec<UserPosition> = if let Some(arr) = my_positions_data.as_array() {
-            arr.iter()
-                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
-                .collect()
-        } else {
-            Vec::new()
-        };
-
-        let user_positions: Vec<UserPosition> = if let Some(arr) = user_positions_data.as_array() {
-            arr.iter()
-                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
-                .collect()
-        } else {
-            Vec::new()
-        };
+        // Fetch positions for both sides (yours & the trader's - all wallets of a group)
+        let my_positions = fetch_my_positions(config, http_client, db).await?;
+        let user_positions = fetch_trader_positions(config, http_client, &trade.user_address).await?;
 
         let condition_id = trade.trade.condition_id.as_deref();
-        let my_position = my_positions
-            .iter()
-            .find(|p| p.condition_id.as_deref() == condition_id);
-        let user_position = user_positions
-            .iter()
-            .find(|p| p.condition_id.as_deref() == condition_id);
+        let (my_position, my_opposite) =
+            split_my_positions(&my_positions, condition_id, trade.trade.asset.as_deref());
+        let user_position = trader_position(&user_positions, condition_id);
 
         // Get balances & calc trader's portfolio value
-        let my_balance = get_usdc_balance(
-            &config.rpc_url,
-            &config.usdc_contract_address,
-            &config.proxy_wallet,
-        )
-        .await
-        .unwrap_or(0.0);
+        let my_balance = my_usdc_balance(config, db).await;
 
         let user_balance: f64 = user_positions
             .iter()
@@ -292,7 +786,9 @@ async fn do_trading(
             clob_client,
             condition,
             my_position,
-            user_position,
+            my_opposite,
+            &my_positions,
+            user_position.as_ref(),
             &trade.trade,
             my_balance,
             user_balance,
@@ -302,6 +798,7 @@ async fn do_trading(
             signer,
         )
         .await?;
+        invalidate_balance_cache(); // Our own fill moved USDC; re-read before sizing the next copy
 
         Logger::separator();
     }
@@ -315,7 +812,7 @@ async fn do_aggregated_trading(
     clob_client: &ClobClient<Authenticated<Normal>>,
     http_client: &reqwest::Client,
     db: &Db,
-    signer: &mut PrivateKeySigner,
+    signer: &mut BotSigner,
 ) -> Result<()> {
     for agg in aggregated_trades {
         Logger::header(&format!(
@@ -339,67 +836,21 @@ async fn do_aggregated_trading(
                 db.update_activity(
                     &trade.user_address,
                     id,
-                    &mongodb::bson::doc! { "botExcutedTime": 1_i64 },
+                    &processing_update(config, http_client, db, &trade.user_address, &trade.trade).await,
                 )
                 .await?;
             }
         }
 
-        let my_positions_url = format!(
-            "https://data-api.polymarket.com/positions?user={}",
-            config.proxy_wallet
-        );
-        let user_positions_url = format!(
-            "https://data-api.polymarket.com/positions?user={}",
-            agg.user_address
-        );
-
-        let my_positions_data: serde_json::Value = fetch_data(
-            http_client,
-            &my_positions_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-        let user_positions_data: serde_json::Value = fetch_data(
-            http_client,
-            &user_positions_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-
-        let my_positions: Vec<UserPosition> = if let Some(arr) = my_positions_data.as_array() {
-            arr.iter()
-                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
-                .collect()
-        } else {
-            Vec::new()
-        };
-
-        let user_positions: Vec<UserPosition> = if let Some(arr) = user_positions_data.as_array() {
-            arr.iter()
-                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
-                .collect()
-        } else {
-            Vec::new()
-        };
+        let my_positions = fetch_my_positions(config, http_client, db).await?;
+        let user_positions = fetch_trader_positions(config, http_client, &agg.user_address).await?;
 
         let condition_id = agg.condition_id.as_deref();
-        let my_position = my_positions
-            .iter()
-            .find(|p| p.condition_id.as_deref() == condition_id);
-        let user_position = user_positions
-            .iter()
-            .find(|p| p.condition_id.as_deref() == condition_id);
+        let (my_position, my_opposite) =
+            split_my_positions(&my_positions, condition_id, agg.asset.as_deref());
+        let user_position = trader_position(&user_positions, condition_id);
 
-        let my_balance = get_usdc_balance(
-            &config.rpc_url,
-            &config.usdc_contract_address,
-            &config.proxy_wallet,
-        )
-        .await
-        .unwrap_or(0.0);
+        let my_balance = my_usdc_balance(config, db).await;
 
         let user_balance: f64 = user_positions
             .iter()
@@ -410,6 +861,7 @@ async fn do_aggregated_trading(
 
         let mut synthetic_trade = agg.trades[0].trade.clone();
         synthetic_trade.usdc_size = Some(agg.total_usdc_size);
+        synthetic_trade.size = Some(agg.total_size);
         synthetic_trade.price = Some(agg.average_price);
         synthetic_trade.side = Some(agg.side.clone());
 
@@ -420,7 +872,9 @@ async fn do_aggregated_trading(
             clob_client,
             condition,
             my_position,
-            user_position,
+            my_opposite,
+            &my_positions,
+            user_position.as_ref(),
             &synthetic_trade,
             my_balance,
             user_balance,
@@ -430,6 +884,7 @@ async fn do_aggregated_trading(
             signer,
         )
         .await?;
+        invalidate_balance_cache(); // Our own fill moved USDC; re-read before sizing the next copy
 
         Logger::separator();
     }
@@ -437,23 +892,184 @@ async fn do_aggregated_trading(
     Ok(())
 }
 
+// Buys and sells of one outcome in the same group (TRADE_AGGREGATION_NET_SIDES) - only the net goes out
+fn is_mixed(agg: &AggregatedTrade) -> bool {
+    agg.trades.iter().any(|t| t.trade.side.as_deref() == Some("SELL"))
+        && agg.trades.iter().any(|t| t.trade.side.as_deref() != Some("SELL"))
+}
+
+// What the shutdown flush did, for the summary printed before exit
+#[derive(Default)]
+struct FlushSummary {
+    executed: Vec<(usize, f64)>, // (trades, USD) per group copied
+    dropped: Vec<(usize, f64)>, // Marked shutdown_dropped (too small, or no time left)
+    interrupted: Vec<(usize, f64)>, // Grace period ran out mid-order (stamped as processing, fill unknown)
+}
+
+fn group_label(agg: &AggregatedTrade) -> String {
+    format!(
+        "{} {} from {}",
+        agg.side,
+        agg.slug.as_ref().or(agg.asset.as_ref()).map(|s| s.as_str()).unwrap_or("unknown"),
+        Logger::format_address(&agg.user_address)
+    )
+}
+
+// Stop requested: copy what's still buffered (aggregation + netting groups), oldest first, each waiting at most
+// what's left of SHUTDOWN_FLUSH_SECONDS. Groups that don't get to run are dropped: marked skipped as shutdown_dropped,
+// since left unprocessed they'd be swept up as history on the next start and never show in the skips report
+async fn flush_on_shutdown(
+    config: &EnvConfig,
+    aggregation_buffer: &AggregationBuffer,
+    net_buffer: &mut HashMap<String, NetGroup>,
+    clob_client: &ClobClient<Authenticated<Normal>>,
+    http_client: &reqwest::Client,
+    db: &Db,
+    signer: &mut BotSigner,
+) {
+    let mut groups: Vec<AggregatedTrade> = aggregation_buffer
+        .lock()
+        .await
+        .drain()
+        .map(|(_, agg)| if is_mixed(&agg) { net_trades(agg.trades, agg.first_trade_time) } else { agg })
+        .collect();
+    groups.extend(net_buffer.drain().map(|(_, g)| net_trades(g.trades, g.first_trade_time)));
+    if groups.is_empty() {
+        return;
+    }
+    groups.sort_by_key(|g| g.first_trade_time);
+
+    Logger::clear_line();
+    Logger::header(&format!(
+        "⏳ FLUSHING {} BUFFERED TRADE GROUP{} ({}s grace)",
+        groups.len(),
+        if groups.len() > 1 { "S" } else { "" },
+        config.shutdown_flush_seconds
+    ));
+    let deadline = Instant::now() + Duration::from_secs(config.shutdown_flush_seconds);
+    let mut summary = FlushSummary::default();
+
+    for agg in groups {
+        let entry = (agg.trades.len(), agg.total_usdc_size);
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let dropped = if below_minimum(&agg) {
+            Some("below the aggregation minimum")
+        } else if remaining.is_zero() {
+            Some("grace period over")
+        } else {
+            match timeout(
+                remaining,
+                do_aggregated_trading(config, std::slice::from_ref(&agg), clob_client, http_client, db, signer),
+            )
+            .await
+            {
+                Ok(Ok(())) => {
+                    summary.executed.push(entry);
+                    None
+                }
+                Ok(Err(e)) => {
+                    report_executor_error(config, http_client, &e).await;
+                    summary.interrupted.push(entry);
+                    None
+                }
+                Err(_) => {
+                    Logger::warning(&format!(
+                        "Grace period ran out while copying {} - check the position, the order may have gone through",
+                        group_label(&agg)
+                    ));
+                    summary.interrupted.push(entry);
+                    None
+                }
+            }
+        };
+        if let Some(why) = dropped {
+            Logger::info(&format!("Dropped {} (${:.2}): {}", group_label(&agg), agg.total_usdc_size, why));
+            for trade in &agg.trades {
+                if let Some(ref id) = trade.trade.id {
+                    if let Err(e) = db.mark_skipped(&trade.user_address, id, SkipReason::ShutdownDropped).await {
+                        Logger::error(&format!("Failed to mark dropped trade: {}", e));
+                    }
+                }
+            }
+            summary.dropped.push(entry);
+        }
+    }
+
+    let totals = |groups: &[(usize, f64)]| {
+        (groups.len(), groups.iter().map(|g| g.0).sum::<usize>(), groups.iter().map(|g| g.1).sum::<f64>())
+    };
+    let (executed, executed_trades, executed_usd) = totals(&summary.executed);
+    let (dropped, dropped_trades, dropped_usd) = totals(&summary.dropped);
+    Logger::separator();
+    Logger::info(&format!(
+        "Shutdown flush: {} group(s) executed ({} trades, ${:.2}), {} dropped ({} trades, ${:.2}, skipped as shutdown_dropped - not copied on the next start)",
+        executed, executed_trades, executed_usd, dropped, dropped_trades, dropped_usd
+    ));
+    if !summary.interrupted.is_empty() {
+        let (interrupted, interrupted_trades, interrupted_usd) = totals(&summary.interrupted);
+        Logger::warning(&format!(
+            "{} group(s) ({} trades, ${:.2}) failed or were cut off mid-order - not retried on the next start",
+            interrupted, interrupted_trades, interrupted_usd
+        ));
+    }
+}
+
+// 401 / rejected API key / bad signature from the CLOB - every later order would fail the same way
+fn is_auth_error(message: &str) -> bool {
+    let m = message.to_lowercase();
+    ["unauthorized", "401", "invalid api key", "invalid signature", "api key not found"]
+        .iter()
+        .any(|needle| m.contains(needle))
+}
+
+async fn report_executor_error(config: &EnvConfig, http_client: &reqwest::Client, e: &anyhow::Error) {
+    let message = format!("Trade executor error: {}", e);
+    if is_auth_error(&message) {
+        raise_alert(config, http_client, Alert::new(AlertKind::AuthFailure, message).key("clob")).await;
+    } else {
+        Logger::error(&message);
+    }
+}
+
 // Main executor loop - polls DB for trades & executes orders
 pub async fn run_trade_executor(
     config: &EnvConfig,
     db: &Db,
     http_client: &reqwest::Client,
 ) -> Result<()> {
+    // Re-armed per run: an embedding program can stop and start the engine again in one process
+    IS_RUNNING.store(true, Ordering::Relaxed);
+
     // Init CLOB client & signer
-    let (clob_client, mut signer) = create_clob_client(config).await?;
+    let (clob_client, mut signer) = match create_clob_client(config).await {
+        Ok(client) => client,
+        Err(e) => {
+            raise_alert(
+                config,
+                http_client,
+                Alert::new(AlertKind::AuthFailure, format!("Could not create the CLOB client: {} - nothing will be copied", e)),
+            )
+            .await;
+            return Err(e);
+        }
+    };
 
     Logger::success(&format!(
         "Trade executor ready for {} trader(s)",
         config.user_addresses.len()
     ));
+    if config.dry_run {
+        Logger::warning(&format!(
+            "DRY_RUN: full copy flow, but orders are recorded in paper_trades instead of posted (paper balance ${:.2})",
+            my_usdc_balance(config, db).await
+        ));
+    }
     if config.trade_aggregation_enabled {
         Logger::info(&format!(
-            "Trade aggregation enabled: {}s window, ${} minimum",
-            config.trade_aggregation_window_seconds, TRADE_AGGREGATION_MIN_TOTAL_USD
+            "Trade aggregation enabled: {}s window, ${} minimum{}",
+            config.trade_aggregation_window_seconds,
+            TRADE_AGGREGATION_MIN_TOTAL_USD,
+            if config.trade_aggregation_net_sides { ", buys netted against sells" } else { "" }
         ));
     }
 
@@ -464,11 +1080,21 @@ pub async fn run_trade_executor(
         Arc::new(Mutex::new(HashMap::new()))
     };
 
+    // Per trader+market copy counts & trades held back for netting (MAX_COPIES_PER_MARKET_PER_HOUR)
+    let mut copy_log: CopyLog = HashMap::new();
+    let mut net_buffer: HashMap<String, NetGroup> = HashMap::new();
+    // Trades waiting out COPY_DELAY_SECONDS, by activity id
+    let mut delay_buffer: HashMap<String, (TradeWithUser, Instant)> = HashMap::new();
+
     // Poll every 300ms for new trades
     let mut last_check = Instant::now();
     let poll_interval = Duration::from_millis(300);
 
     while IS_RUNNING.load(Ordering::Relaxed) {
+        // Env + DB overrides as of this tick (refreshed in the background)
+        let live = effective_config(config);
+        let config = &live;
+
         // Fetch unprocessed trades from DB
         let trades = match read_temp_trades(config, db).await {
             Ok(t) => t,
@@ -478,6 +1104,49 @@ pub async fn run_trade_executor(
                 continue;
             }
         };
+        // Trades already in the aggregation buffer stay unprocessed in the DB until their group flushes -
+        // drop them before the pause / delay / throttle stages so they aren't held, re-quoted or skipped again
+        let trades = if config.trade_aggregation_enabled {
+            without_buffered(&aggregation_buffer, trades).await
+        } else {
+            trades
+        };
+        let trades = skip_filtered_trades(config, db, trades).await;
+        let trades = skip_paused_trades(config, db, http_client, trades).await;
+        let trades = delay_trades(config, db, http_client, &mut delay_buffer, trades).await;
+        let trades = throttle_trades(config, db, &mut copy_log, &mut net_buffer, trades).await;
+
+        // Held (throttled) trades whose window is up go out as one netted order
+        let netted = get_ready_netted_trades(
+            &mut net_buffer,
+            config.trade_aggregation_window_seconds,
+            db,
+        )
+        .await;
+        if !netted.is_empty() {
+            Logger::clear_line();
+            Logger::header(&format!(
+                "⚡ {} NETTED TRADE{} READY",
+                netted.len(),
+                if netted.len() > 1 { "S" } else { "" }
+            ));
+            for agg in &netted {
+                record_copy(&mut copy_log, throttle_key(config, &agg.trades[0]));
+            }
+            if let Err(e) = do_aggregated_trading(
+                config,
+                &netted,
+                &clob_client,
+                http_client,
+                db,
+                &mut signer,
+            )
+            .await
+            {
+                report_executor_error(config, http_client, &e).await;
+            }
+            last_check = Instant::now();
+        }
 
         // Aggregation mode: batch small trades, execute large ones immediately
         if config.trade_aggregation_enabled {
@@ -493,7 +1162,10 @@ pub async fn run_trade_executor(
                     let usdc_size = trade.trade.usdc_size.unwrap_or(0.0);
                     let side = trade.trade.side.as_deref().unwrap_or("");
                     // Small BUY trades go to buffer, everything else executes immediately
-                    if side == "BUY" && usdc_size < TRADE_AGGREGATION_MIN_TOTAL_USD {
+                    // (netting buffers every trade, either side, so a sell later in the window can still cancel a buy)
+                    let buffered = config.trade_aggregation_net_sides
+                        || (side == "BUY" && usdc_size < TRADE_AGGREGATION_MIN_TOTAL_USD);
+                    if buffered {
                         let asset_display = trade
                             .trade
                             .slug
@@ -505,7 +1177,7 @@ pub async fn run_trade_executor(
                             "Adding ${:.2} {} trade to aggregation buffer for {}",
                             usdc_size, side, asset_display
                         ));
-                        if let Err(e) = add_to_aggregation_buffer(&aggregation_buffer, trade.clone()).await {
+                        if let Err(e) = add_to_aggregation_buffer(config, &aggregation_buffer, trade.clone(), config.trade_aggregation_net_sides).await {
                             Logger::error(&format!("Failed to add trade to aggregation buffer: {}", e));
                         }
                     } else {
@@ -521,7 +1193,7 @@ pub async fn run_trade_executor(
                         )
                         .await
                         {
-                            Logger::error(&format!("Trade executor error: {}", e));
+                            report_executor_error(config, http_client, &e).await;
                         }
                     }
                 }
@@ -558,7 +1230,7 @@ pub async fn run_trade_executor(
                 )
                 .await
                 {
-                    Logger::error(&format!("Trade executor error: {}", e));
+                    report_executor_error(config, http_client, &e).await;
                 }
                 last_check = Instant::now();
             }
@@ -586,7 +1258,7 @@ pub async fn run_trade_executor(
                     if trades.len() > 1 { "S" } else { "" }
                 ));
                 if let Err(e) = do_trading(config, &trades, &clob_client, http_client, db, &mut signer)                .await {
-                    Logger::error(&format!("Trade executor error: {}", e));
+                    report_executor_error(config, http_client, &e).await;
                 }
                 last_check = Instant::now();
             }
@@ -605,6 +1277,9 @@ pub async fn run_trade_executor(
         sleep(poll_interval).await;
     }
 
+    let live = effective_config(config);
+    flush_on_shutdown(&live, &aggregation_buffer, &mut net_buffer, &clob_client, http_client, db, &mut signer).await;
+
     Logger::info("Trade executor stopped");
     Ok(())
 }
@@ -1,22 +1,59 @@
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
+use polymarket_book::schema::{MessageSchema, Novelty, SchemaTelemetry};
 use serde_json::json;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::broadcast;
 use tokio::time::{sleep, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::config::EnvConfig;
 use crate::db::Db;
-use crate::types::{RtdsActivity, UserActivity, UserPosition};
-use crate::utils::{self, Logger};
+use crate::types::{RtdsActivity, SkipReason, UserActivity, UserPosition};
+use crate::utils::{self, raise_alert, Alert, AlertKind, Logger, Severity};
 
-// RTDS WebSocket URL (Polymarket real-time data stream)
-const RTDS_URL: &str = "wss://ws-live-data.polymarket.com";
 const POSITION_UPDATE_INTERVAL_SECS: u64 = 30;
 const MAX_RECONNECT_ATTEMPTS: u32 = 10;
 const RECONNECT_DELAY_SECS: u64 = 5;
+// Catch-up starts this far before the last RTDS message (trades in flight when the socket dropped)
+const CATCHUP_OVERLAP_MS: i64 = 30_000;
+
+// RTDS payloads this client was written against - bump the version whenever the table changes
+const RTDS_SCHEMA_VERSION: u32 = 1;
+const RTDS_SCHEMA: &[MessageSchema] = &[
+    // Every data message, whatever the topic
+    MessageSchema { kind: "envelope", fields: Some(&["topic", "type", "timestamp", "payload", "connection_id"]) },
+    // Payload of activity/trades (RtdsActivity, plus the profile fields we don't read)
+    MessageSchema {
+        kind: "activity/trades",
+        fields: Some(&[
+            "proxyWallet", "timestamp", "conditionId", "type", "size", "price", "asset", "side", "outcomeIndex",
+            "title", "slug", "icon", "eventSlug", "outcome", "name", "transactionHash", "pseudonym", "bio",
+            "profileImage", "profileImageOptimized",
+        ]),
+    },
+];
+
+static RTDS_TELEMETRY: OnceLock<SchemaTelemetry> = OnceLock::new();
+
+fn rtds_telemetry() -> &'static SchemaTelemetry {
+    RTDS_TELEMETRY.get_or_init(|| SchemaTelemetry::new("rtds", RTDS_SCHEMA_VERSION, RTDS_SCHEMA))
+}
+
+// Unknown RTDS types / fields / unparseable trades so far (heartbeat file)
+pub fn rtds_schema_json() -> serde_json::Value {
+    rtds_telemetry().to_json()
+}
+
+// Log each new kind of mismatch once and alert on it - a renamed field would otherwise show up only as trades not being copied
+async fn report_schema(config: &EnvConfig, http_client: &reqwest::Client, novelties: Vec<Novelty>) {
+    for novelty in novelties {
+        let message = format!("RTDS schema v{}: {}", RTDS_SCHEMA_VERSION, novelty);
+        Logger::warning(&message);
+        raise_alert(config, http_client, Alert::new(AlertKind::SchemaDrift, message)).await;
+    }
+}
 
 // Global flag for graceful shutdown
 static RUNNING: AtomicBool = AtomicBool::new(true);
@@ -27,6 +64,34 @@ pub fn stop_trade_monitor() {
     Logger::info("Trade monitor shutdown requested...");
 }
 
+// RTDS service messages (server restarts, maintenance) that need client action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RtdsControl {
+    Resubscribe,
+    Reconnect,
+}
+
+// Recognize control messages by action/type/event/message - anything mentioning
+// resubscribe asks us to resend subscriptions, restart/reconnect means drop & reconnect
+fn parse_control_message(parsed: &serde_json::Value) -> Option<RtdsControl> {
+    if parsed.get("payload").is_some() && parsed.get("topic").and_then(|t| t.as_str()) != Some("system") {
+        return None;
+    }
+    let text = ["action", "type", "event", "message", "status"]
+        .iter()
+        .filter_map(|k| parsed.get(*k).and_then(|v| v.as_str()))
+        .map(|v| v.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.contains("resubscribe") {
+        Some(RtdsControl::Resubscribe)
+    } else if text.contains("reconnect") || text.contains("restart") || text.contains("shutdown") {
+        Some(RtdsControl::Reconnect)
+    } else {
+        None
+    }
+}
+
 pub struct TradeMonitorHandle {
     _tx: broadcast::Sender<()>,
 }
@@ -59,7 +124,7 @@ async fn init(
     .await
     .unwrap_or(0.0);
 
-    match utils::fetch_data(
+    match utils::fetch_paginated(
         http_client,
         &my_positions_url,
         config.request_timeout_ms,
@@ -183,33 +248,33 @@ async fn init(
     Ok(())
 }
 
-// Process trade from RTDS: validate timestamp, check duplicates, save to DB
+// Process trade from RTDS: validate timestamp, check duplicates, save to DB (true = newly stored)
 async fn process_trade_activity(
     db: &Db,
     config: &EnvConfig,
     activity: &RtdsActivity,
     address: &str,
-) -> Result<()> {
-    // Normalize timestamp (handle both ms & sec formats)
-    let ts = activity.timestamp.unwrap_or(0);
-    let ts_ms = if ts > 1_000_000_000_000 {
-        ts
-    } else {
-        ts * 1000
-    };
-    // Skip if trade too old (configurable threshold)
-    let hours_ago = (chrono::Utc::now().timestamp_millis() - ts_ms) as f64 / (1000.0 * 3600.0);
-    if hours_ago > config.too_old_timestamp_hours as f64 {
-        return Ok(());
+) -> Result<bool> {
+    // Our own executions echoed back (RTDS or catch-up) - never copy them
+    if activity.proxy_wallet.as_deref().is_some_and(|w| config.is_own_wallet(w)) || config.is_own_wallet(address) {
+        Logger::warning(&format!(
+            "Ignoring trade {} from your own wallet",
+            activity.transaction_hash.as_deref().unwrap_or("?")
+        ));
+        return Ok(false);
     }
 
+    // Too old trades are stored as skipped (configurable threshold; timestamp may be ms or sec)
+    let hours_ago = utils::clock::age_ms(activity.timestamp.unwrap_or(0)) as f64 / (1000.0 * 3600.0);
+    let is_stale = hours_ago > config.too_old_timestamp_hours as f64;
+
     // Skip if no tx hash or already processed
     let tx_hash = activity.transaction_hash.as_deref().unwrap_or("");
     if tx_hash.is_empty() {
-        return Ok(());
+        return Ok(false);
     }
     if db.find_activity_by_tx(address, tx_hash).await?.is_some() {
-        return Ok(());
+        return Ok(false);
     }
 
     let doc = UserActivity {
@@ -235,17 +300,112 @@ async fn process_trade_activity(
         bio: None,
         profile_image: None,
         profile_image_optimized: None,
-        bot: Some(false),
+        bot: Some(is_stale),
         bot_executed_time: Some(0),
         my_bought_size: None,
+        skip_reason: is_stale.then_some(SkipReason::Stale),
+        skipped_at: is_stale.then(mongodb::bson::DateTime::now),
+        tags: None,
+        category: None,
+        exit_stuck_size: None,
+        dry_run: None,
     };
 
     db.insert_activity(address, &doc).await?;
+    if is_stale {
+        Logger::warning(&format!(
+            "Skipped stale trade for {} ({:.1}h old)",
+            Logger::format_address(address),
+            hours_ago
+        ));
+        return Ok(true);
+    }
     Logger::info(&format!(
         "New trade detected for {}",
         Logger::format_address(address)
     ));
-    Ok(())
+    Ok(true)
+}
+
+// Replay trades RTDS missed while disconnected (duplicates and staleness handled by process_trade_activity)
+async fn catch_up_missed_trades(
+    config: &EnvConfig,
+    db: &Db,
+    http_client: &reqwest::Client,
+    last_seen_ms: i64,
+    reconnected_ms: i64,
+) {
+    if config.rtds_catchup_max_minutes == 0 {
+        return;
+    }
+    let now = utils::clock::now_ms();
+    let earliest = now - config.rtds_catchup_max_minutes as i64 * 60_000;
+    let gap_start = last_seen_ms - CATCHUP_OVERLAP_MS;
+    if gap_start < earliest {
+        Logger::warning(&format!(
+            "RTDS was down {} min - catching up only the last {} min (RTDS_CATCHUP_MAX_MINUTES)",
+            (now - last_seen_ms) / 60_000,
+            config.rtds_catchup_max_minutes
+        ));
+    }
+    let start_secs = utils::clock::to_secs(gap_start.max(earliest));
+    // Anything after the resubscribe arrives on the stream itself
+    let end_secs = utils::clock::to_secs(reconnected_ms);
+
+    let mut recovered = 0;
+    for addr in &config.user_addresses {
+        let url = format!(
+            "https://data-api.polymarket.com/activity?user={}&type=TRADE&start={}",
+            addr, start_secs
+        );
+        let data = match utils::fetch_paginated(
+            http_client,
+            &url,
+            config.request_timeout_ms,
+            config.network_retry_limit,
+        )
+        .await
+        {
+            Ok(data) => data,
+            Err(e) => {
+                Logger::warning(&format!(
+                    "RTDS catch-up failed for {}: {}",
+                    Logger::format_address(addr),
+                    e
+                ));
+                continue;
+            }
+        };
+        let mut trades: Vec<RtdsActivity> = data
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|a| serde_json::from_value::<RtdsActivity>(a.clone()).ok())
+                    .filter(|a| {
+                        let ts = a.timestamp.map(utils::clock::to_secs).unwrap_or(0);
+                        ts >= start_secs && ts <= end_secs
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Oldest first, so the executor sees them in the order the trader made them
+        trades.sort_by_key(|a| a.timestamp.map(utils::clock::to_ms).unwrap_or(0));
+        let address = addr.to_lowercase();
+        for activity in &trades {
+            if let Ok(true) = process_trade_activity(db, config, activity, &address).await {
+                recovered += 1;
+            }
+        }
+    }
+
+    if recovered > 0 {
+        Logger::success(&format!(
+            "RTDS catch-up: recovered {} trade(s) missed during the disconnect",
+            recovered
+        ));
+    } else {
+        Logger::info("RTDS catch-up: no missed trades");
+    }
 }
 
 async fn update_positions(
@@ -254,7 +414,7 @@ async fn update_positions(
     http_client: &reqwest::Client,
 ) -> Result<()> {
     for addr in &config.user_addresses {
-        match utils::fetch_data(
+        match utils::fetch_paginated(
             http_client,
             &format!("https://data-api.polymarket.com/positions?user={}", addr),
             config.request_timeout_ms,
@@ -290,16 +450,27 @@ async fn connect_rtds(
     http_client: Arc<reqwest::Client>,
     reconnect_attempts: Arc<std::sync::atomic::AtomicU32>,
 ) -> Result<()> {
+    // Endpoints in priority order: fail over on connect errors, drift back to the primary later
+    let urls = &config.rtds_urls;
+    let max_attempts = MAX_RECONNECT_ATTEMPTS * urls.len() as u32;
+    let mut endpoint = 0usize;
+    // Last RTDS message time (0 = never connected, nothing to catch up on)
+    let last_seen_ms = Arc::new(AtomicI64::new(0));
+
     loop {
         if !RUNNING.load(Ordering::SeqCst) {
             break;
         }
 
-        Logger::info(&format!("Connecting to RTDS at {}...", RTDS_URL));
+        let url = &urls[endpoint];
+        Logger::info(&format!("Connecting to RTDS at {}...", url));
 
-        match connect_async(RTDS_URL).await {
+        match connect_async(url.as_str()).await {
             Ok((ws_stream, _)) => {
-                Logger::success("RTDS WebSocket connected");
+                Logger::success(&format!(
+                    "RTDS WebSocket connected{}",
+                    if endpoint > 0 { " (backup endpoint)" } else { "" }
+                ));
                 reconnect_attempts.store(0, Ordering::SeqCst);
 
                 let (mut write, mut read) = ws_stream.split();
@@ -327,12 +498,26 @@ async fn connect_rtds(
                     config.user_addresses.len()
                 ));
 
+                // Reconnected after an outage: fill the gap from the REST activity endpoint
+                let reconnected_ms = utils::clock::now_ms();
+                let gap_from = last_seen_ms.swap(reconnected_ms, Ordering::SeqCst);
+                if gap_from > 0 {
+                    let (config_cu, db_cu, http_cu) = (config.clone(), db.clone(), http_client.clone());
+                    tokio::spawn(async move {
+                        catch_up_missed_trades(&config_cu, &db_cu, &http_cu, gap_from, reconnected_ms).await;
+                    });
+                }
+
                 let db_msg = db.clone();
                 let config_msg = config.clone();
+                let last_seen_msg = last_seen_ms.clone();
+                let http_msg = http_client.clone();
                 let mut message_task = tokio::spawn(async move {
+                    // Returns true when the server asked us to reconnect
                     while RUNNING.load(Ordering::SeqCst) {
                         match read.next().await {
                             Some(Ok(Message::Text(t))) => {
+                                last_seen_msg.store(utils::clock::now_ms(), Ordering::SeqCst);
                                 if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&t) {
                                     if parsed.get("action").and_then(|a| a.as_str()) == Some("subscribed")
                                         || parsed.get("status").and_then(|s| s.as_str()) == Some("subscribed")
@@ -341,33 +526,76 @@ async fn connect_rtds(
                                         continue;
                                     }
 
-                                    if parsed.get("topic").and_then(|t| t.as_str()) == Some("activity")
-                                        && parsed.get("type").and_then(|t| t.as_str()) == Some("trades")
-                                    {
-                                        if let Some(payload) = parsed.get("payload") {
-                                            if let Ok(activity) =
-                                                serde_json::from_value::<RtdsActivity>(payload.clone())
+                                    // Server control messages (resubscribe in place, or reconnect)
+                                    match parse_control_message(&parsed) {
+                                        Some(RtdsControl::Resubscribe) => {
+                                            Logger::info("RTDS requested resubscribe - resending subscriptions");
+                                            if let Err(e) = write
+                                                .send(Message::Text(subscribe_message.to_string()))
+                                                .await
                                             {
-                                                let proxy = activity
-                                                    .proxy_wallet
-                                                    .as_deref()
-                                                    .unwrap_or("")
-                                                    .to_lowercase();
-                                                if config_msg
-                                                    .user_addresses
-                                                    .iter()
-                                                    .any(|a| a.to_lowercase() == proxy)
-                                                {
-                                                    let _ = process_trade_activity(
-                                                        &db_msg,
-                                                        &config_msg,
-                                                        &activity,
-                                                        &proxy,
-                                                    )
-                                                    .await;
+                                                Logger::warning(&format!(
+                                                    "Resubscribe failed ({}), reconnecting",
+                                                    e
+                                                ));
+                                                return true;
+                                            }
+                                            continue;
+                                        }
+                                        Some(RtdsControl::Reconnect) => {
+                                            Logger::warning("RTDS server restart notice - reconnecting");
+                                            let _ = write.send(Message::Close(None)).await;
+                                            return true;
+                                        }
+                                        None => {}
+                                    }
+
+                                    let kind = format!(
+                                        "{}/{}",
+                                        parsed.get("topic").and_then(|t| t.as_str()).unwrap_or("(none)"),
+                                        parsed.get("type").and_then(|t| t.as_str()).unwrap_or("(none)")
+                                    );
+                                    let mut novelties = rtds_telemetry().observe("envelope", &parsed);
+                                    let activity = match parsed.get("payload") {
+                                        Some(payload) if kind == "activity/trades" => {
+                                            novelties.extend(rtds_telemetry().observe(&kind, payload));
+                                            match serde_json::from_value::<RtdsActivity>(payload.clone()) {
+                                                Ok(activity) => Some(activity),
+                                                Err(e) => {
+                                                    novelties.extend(rtds_telemetry().unparsed(&kind, &e.to_string(), payload));
+                                                    None
                                                 }
                                             }
                                         }
+                                        _ if kind != "activity/trades" => {
+                                            novelties.extend(rtds_telemetry().observe(&kind, &parsed));
+                                            None
+                                        }
+                                        _ => None,
+                                    };
+                                    if !novelties.is_empty() {
+                                        report_schema(&config_msg, &http_msg, novelties).await;
+                                    }
+
+                                    if let Some(activity) = activity {
+                                        let proxy = activity
+                                            .proxy_wallet
+                                            .as_deref()
+                                            .unwrap_or("")
+                                            .to_lowercase();
+                                        if config_msg
+                                            .user_addresses
+                                            .iter()
+                                            .any(|a| a.to_lowercase() == proxy)
+                                        {
+                                            let _ = process_trade_activity(
+                                                &db_msg,
+                                                &config_msg,
+                                                &activity,
+                                                &proxy,
+                                            )
+                                            .await;
+                                        }
                                     }
                                 }
                             }
@@ -383,29 +611,70 @@ async fn connect_rtds(
                             _ => continue,
                         }
                     }
+                    false
                 });
 
-                message_task.await.ok();
+                // On a backup: periodically drop it and try the primary again
+                let retry_primary = endpoint > 0 && config.ws_primary_retry_secs > 0;
+                let server_reconnect = if retry_primary {
+                    tokio::select! {
+                        r = &mut message_task => r.unwrap_or(false),
+                        _ = sleep(Duration::from_secs(config.ws_primary_retry_secs)) => {
+                            message_task.abort();
+                            Logger::info("Retrying primary RTDS endpoint...");
+                            endpoint = 0;
+                            continue;
+                        }
+                    }
+                } else {
+                    message_task.await.unwrap_or(false)
+                };
+
+                // Server-requested reconnects don't count against the retry limit
+                if server_reconnect {
+                    reconnect_attempts.store(0, Ordering::SeqCst);
+                    sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
             }
             Err(e) => {
-                Logger::error(&format!("Failed to connect to RTDS: {}", e));
+                raise_alert(
+                    &config,
+                    &http_client,
+                    Alert::new(AlertKind::WsDown, format!("Failed to connect to RTDS: {}", e))
+                        .severity(Severity::Warn)
+                        .key("connect"),
+                )
+                .await;
+                if urls.len() > 1 {
+                    endpoint = (endpoint + 1) % urls.len();
+                    Logger::warning(&format!("Failing over to RTDS endpoint {}", urls[endpoint]));
+                }
             }
         }
 
         if RUNNING.load(Ordering::SeqCst) {
             let attempts = reconnect_attempts.fetch_add(1, Ordering::SeqCst) + 1;
-            if attempts < MAX_RECONNECT_ATTEMPTS {
+            if attempts < max_attempts {
                 let delay = RECONNECT_DELAY_SECS * attempts.min(5) as u64;
                 Logger::info(&format!(
                     "Reconnecting to RTDS in {}s (attempt {}/{})...",
-                    delay, attempts, MAX_RECONNECT_ATTEMPTS
+                    delay, attempts, max_attempts
                 ));
                 sleep(Duration::from_secs(delay)).await;
             } else {
-                Logger::error(&format!(
-                    "Max reconnection attempts ({}) reached. Please restart the bot.",
-                    MAX_RECONNECT_ATTEMPTS
-                ));
+                raise_alert(
+                    &config,
+                    &http_client,
+                    Alert::new(
+                        AlertKind::WsDown,
+                        format!(
+                            "Max RTDS reconnection attempts ({}) reached - no new trades are being copied. Please restart the bot.",
+                            max_attempts
+                        ),
+                    ),
+                )
+                .await;
                 break;
             }
         }
@@ -0,0 +1,198 @@
+use anyhow::Result;
+use polymarket_client_sdk::auth::state::Authenticated;
+use polymarket_client_sdk::auth::Normal;
+use polymarket_client_sdk::clob::Client as ClobClient;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::{sleep, Duration};
+
+use super::market_disputes::is_market_disputed;
+use crate::config::{effective_config, EnvConfig};
+use crate::db::Db;
+use crate::types::{normalize_tag, UserPosition};
+use crate::utils::{create_clob_client, exit_position, fetch_paginated, fetch_quote, is_dust, raise_alert, Alert, AlertKind, BotSigner, Logger};
+
+// Per-position overrides, as tags on the trader or the market (tags add ...):
+//   trailing-stop-<percent>   use this % instead of TRAILING_STOP_PERCENT (tightest tag wins)
+//   trailing-stop-off         never trail this position
+const TAG_PREFIX: &str = "trailing-stop-";
+const TAG_OFF: &str = "trailing-stop-off";
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+pub fn stop_trailing_stop() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+fn percent_from_tags(tags: &[String], default: Option<f64>) -> Option<f64> {
+    if tags.iter().any(|t| normalize_tag(t) == TAG_OFF) {
+        return None;
+    }
+    tags.iter()
+        .filter_map(|t| normalize_tag(t).strip_prefix(TAG_PREFIX).and_then(|p| p.parse::<f64>().ok()))
+        .filter(|p| *p > 0.0 && *p < 100.0)
+        .reduce(f64::min)
+        .or(default)
+}
+
+// Trader whose copied buys built this position + the trailing % that applies to it (None = manual position)
+async fn position_rule(config: &EnvConfig, db: &Db, position: &UserPosition, asset: &str) -> Option<(String, Option<f64>)> {
+    for trader in &config.user_addresses {
+        let buys = db
+            .find_all_buy_activities_for_asset(trader, asset, &position.condition_id)
+            .await
+            .ok()?;
+        if let Some(buy) = buys.first() {
+            let tags = db.tags_for_trade(trader, buy).await.unwrap_or_default();
+            return Some((trader.clone(), percent_from_tags(&tags, config.copy_strategy_config.trailing_stop_percent)));
+        }
+    }
+    None
+}
+
+pub(super) async fn open_positions(config: &EnvConfig, http_client: &reqwest::Client) -> Result<Vec<UserPosition>> {
+    let url = format!("https://data-api.polymarket.com/positions?user={}", config.proxy_wallet);
+    let positions = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+    Ok(positions
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
+                // Dust can't be sold anyway - no point watching it
+                .filter(|p| !is_dust(p.size.unwrap_or(0.0), config.min_order_size_tokens) && !p.redeemable.unwrap_or(false))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+struct TrailingStop {
+    highs: HashMap<String, f64>, // Best bid seen per asset since entry (entry price counts as the first high)
+    clob: Option<(ClobClient<Authenticated<Normal>>, BotSigner)>, // Created on the first stop
+}
+
+impl TrailingStop {
+    async fn raise_high(&mut self, db: &Db, asset: &str, price: f64) -> f64 {
+        let high = self.highs.get(asset).copied().unwrap_or(0.0);
+        if price <= high {
+            return high;
+        }
+        self.highs.insert(asset.to_string(), price);
+        if let Err(e) = db.set_trailing_high(asset, price).await {
+            Logger::warning(&format!("Failed to save trailing-stop high: {}", e));
+        }
+        price
+    }
+
+    async fn forget(&mut self, db: &Db, asset: &str) {
+        self.highs.remove(asset);
+        let _ = db.clear_trailing_high(asset).await;
+    }
+
+    async fn check(&mut self, config: &EnvConfig, db: &Db, http_client: &reqwest::Client) -> Result<()> {
+        let positions = open_positions(config, http_client).await?;
+
+        // Positions closed elsewhere (mirrored sell, redemption) drop their watermark
+        let held: Vec<String> = positions.iter().filter_map(|p| p.asset.clone()).collect();
+        let gone: Vec<String> = self.highs.keys().filter(|a| !held.contains(a)).cloned().collect();
+        for asset in gone {
+            self.forget(db, &asset).await;
+        }
+
+        for position in &positions {
+            let Some(asset) = position.asset.clone() else {
+                continue;
+            };
+            // Disputed markets swing on the vote, not on news - no stops (and no new highs) until it resolves
+            if position.condition_id.as_deref().map(is_market_disputed).unwrap_or(false) {
+                continue;
+            }
+            let Some((trader, Some(percent))) = position_rule(config, db, position, &asset).await else {
+                continue;
+            };
+            // Bid = what a stop would actually sell at; no bids -> nothing to trail against
+            let Some((bid, _)) = fetch_quote(config, http_client, &asset).await.filter(|(bid, _)| *bid > 0.0) else {
+                continue;
+            };
+            let entry = position.avg_price.unwrap_or(0.0);
+            let high = self.raise_high(db, &asset, bid.max(entry)).await;
+            let trigger = high * (1.0 - percent / 100.0);
+            if bid > trigger {
+                continue;
+            }
+
+            let title = position.title.clone().unwrap_or_else(|| asset.clone());
+            Logger::warning(&format!(
+                "Trailing stop hit: {} {} bid ${:.4} is {:.1}% below the high ${:.4} (stop {:.0}%) - selling {:.2} tokens",
+                title,
+                position.outcome.as_deref().unwrap_or(""),
+                bid,
+                (1.0 - bid / high) * 100.0,
+                high,
+                percent,
+                position.size.unwrap_or(0.0)
+            ));
+            if self.clob.is_none() {
+                self.clob = Some(create_clob_client(config).await?);
+            }
+            let Some((clob_client, signer)) = self.clob.as_ref() else {
+                continue;
+            };
+            match exit_position(config, clob_client, signer, http_client, db, &trader, position).await {
+                Ok(sold) if sold > 0.0 => {
+                    raise_alert(
+                        config,
+                        http_client,
+                        Alert::new(
+                            AlertKind::TrailingStop,
+                            format!(
+                                "Trailing stop sold {:.2} tokens of {} at ~${:.4} (high ${:.4}, trader {})",
+                                sold,
+                                title,
+                                bid,
+                                high,
+                                Logger::format_address(&trader)
+                            ),
+                        )
+                        .key(asset.clone()),
+                    )
+                    .await;
+                    if sold >= position.size.unwrap_or(0.0) - 1e-6 {
+                        self.forget(db, &asset).await;
+                    }
+                }
+                Ok(_) => Logger::warning(&format!("Trailing stop for {}: nothing sold (no bids?), retrying next check", title)),
+                Err(e) => Logger::error(&format!("Trailing stop sell failed for {}: {}", title, e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+// Background loop - runs until stop_trailing_stop() (disabled when interval is 0)
+// Runs even with TRAILING_STOP_PERCENT unset: trailing-stop-<percent> tags can turn it on per trader / market
+pub async fn run_trailing_stop(config: EnvConfig, db: Db, http_client: reqwest::Client) {
+    if config.trailing_stop_interval_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let mut stop = TrailingStop {
+        highs: db.get_trailing_highs().await.unwrap_or_default(),
+        clob: None,
+    };
+    if let Some(percent) = config.copy_strategy_config.trailing_stop_percent {
+        Logger::info(&format!(
+            "Trailing stop: sell copied positions {}% below their high (checked every {}s)",
+            percent, config.trailing_stop_interval_secs
+        ));
+    }
+
+    while RUNNING.load(Ordering::SeqCst) {
+        // TRAILING_STOP_PERCENT can be changed at runtime (make overrides)
+        let live = effective_config(&config);
+        if let Err(e) = stop.check(&live, &db, &http_client).await {
+            Logger::warning(&format!("Trailing stop check failed: {}", e));
+        }
+        sleep(Duration::from_secs(config.trailing_stop_interval_secs)).await;
+    }
+}
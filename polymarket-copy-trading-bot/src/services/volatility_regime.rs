@@ -0,0 +1,164 @@
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+use crate::config::{EnvConfig, VolatilitySource};
+use crate::types::UserPosition;
+use crate::utils::{clock, fetch_data, fetch_paginated, fetch_price_history, Logger};
+
+// Open positions sampled for the markets source (largest first - they move the book the most)
+const MAX_MARKETS: usize = 20;
+// Fewer 1-minute moves than this in the window -> that market is left out
+const MIN_POINTS: usize = 10;
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+
+// Current regime (None until the first measurement, or with VOL_REGIME_SOURCE=off)
+static REGIME: Mutex<Option<VolatilityRegime>> = Mutex::new(None);
+
+pub fn stop_volatility_regime() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VolatilityRegime {
+    pub turbulent: bool,
+    pub realized: f64, // Last measurement, in the source's unit
+    pub haircut: f64, // Multiplier applied to copy sizes right now (1.0 when calm)
+    pub measured_at: i64,
+}
+
+pub fn volatility_regime() -> Option<VolatilityRegime> {
+    REGIME.lock().ok().and_then(|r| *r)
+}
+
+// Size multiplier for the next copy (1.0 when calm, off or not measured yet)
+pub fn volatility_haircut() -> f64 {
+    volatility_regime().map(|r| r.haircut).unwrap_or(1.0)
+}
+
+// Sample stdev of consecutive differences, scaled from per-minute to per-hour
+fn hourly_stdev(moves: &[f64]) -> Option<f64> {
+    if moves.len() < MIN_POINTS {
+        return None;
+    }
+    let n = moves.len() as f64;
+    let mean = moves.iter().sum::<f64>() / n;
+    let var = moves.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some(var.sqrt() * 60f64.sqrt())
+}
+
+// Median hourly stdev of 1-minute price moves across our largest open positions
+async fn markets_volatility(config: &EnvConfig, http_client: &reqwest::Client) -> Result<f64> {
+    let url = format!("https://data-api.polymarket.com/positions?user={}", config.proxy_wallet);
+    let mut positions: Vec<UserPosition> = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await?
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
+                .filter(|p| p.size.unwrap_or(0.0) > 0.0 && p.asset.is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    positions.sort_by(|a, b| b.current_value.unwrap_or(0.0).total_cmp(&a.current_value.unwrap_or(0.0)));
+
+    let end = clock::now_ms() / 1000;
+    let start = end - config.vol_lookback_minutes as i64 * 60;
+    let mut per_market = Vec::new();
+    for p in positions.iter().take(MAX_MARKETS) {
+        let Some(asset) = p.asset.as_deref() else {
+            continue;
+        };
+        let Some(history) = fetch_price_history(config, http_client, asset, start, end).await else {
+            continue;
+        };
+        let moves: Vec<f64> = history.windows(2).map(|w| w[1].1 - w[0].1).collect();
+        if let Some(v) = hourly_stdev(&moves) {
+            per_market.push(v);
+        }
+    }
+    if per_market.is_empty() {
+        return Err(anyhow!("no open position with enough price history"));
+    }
+    per_market.sort_by(|a, b| a.total_cmp(b));
+    Ok(per_market[per_market.len() / 2])
+}
+
+// Hourly stdev of 1-minute returns of VOL_CRYPTO_SYMBOL, in percent
+async fn crypto_volatility(config: &EnvConfig, http_client: &reqwest::Client) -> Result<f64> {
+    let url = format!(
+        "{}?symbol={}&interval=1m&limit={}",
+        config.vol_crypto_api,
+        config.vol_crypto_symbol,
+        config.vol_lookback_minutes.min(1000) + 1
+    );
+    let data = fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+    // Kline rows: [open time, open, high, low, close, ...] with prices as strings
+    let closes: Vec<f64> = data
+        .as_array()
+        .ok_or_else(|| anyhow!("unexpected klines response"))?
+        .iter()
+        .filter_map(|k| k.get(4)?.as_str()?.parse().ok())
+        .filter(|c: &f64| *c > 0.0)
+        .collect();
+    let returns: Vec<f64> = closes.windows(2).map(|w| (w[1] / w[0]).ln() * 100.0).collect();
+    hourly_stdev(&returns).ok_or_else(|| anyhow!("not enough klines for {}", config.vol_crypto_symbol))
+}
+
+// Background loop - measures realized volatility every VOL_CHECK_SECS and flips the regime with hysteresis:
+// turbulent at VOL_HIGH, calm again only at VOL_CALM. Runs until stop_volatility_regime()
+pub async fn run_volatility_regime(config: EnvConfig, http_client: reqwest::Client) {
+    if config.vol_regime_source == VolatilitySource::Off {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let unit = |v: f64| match config.vol_regime_source {
+        VolatilitySource::Crypto => format!("{:.2}%/h", v),
+        _ => format!("{:.3}/h", v),
+    };
+
+    while RUNNING.load(Ordering::SeqCst) {
+        let measured = match config.vol_regime_source {
+            VolatilitySource::Crypto => crypto_volatility(&config, &http_client).await,
+            _ => markets_volatility(&config, &http_client).await,
+        };
+        match measured {
+            Ok(realized) => {
+                let was_turbulent = volatility_regime().map(|r| r.turbulent).unwrap_or(false);
+                let turbulent = if was_turbulent {
+                    realized > config.vol_calm
+                } else {
+                    realized >= config.vol_high
+                };
+                if turbulent && !was_turbulent {
+                    Logger::warning(&format!(
+                        "Volatility regime: turbulent ({} >= {}) - copy sizes x{}",
+                        unit(realized),
+                        unit(config.vol_high),
+                        config.vol_haircut
+                    ));
+                } else if !turbulent && was_turbulent {
+                    Logger::info(&format!(
+                        "Volatility regime: calm again ({} <= {}) - full copy sizes",
+                        unit(realized),
+                        unit(config.vol_calm)
+                    ));
+                }
+                if let Ok(mut regime) = REGIME.lock() {
+                    *regime = Some(VolatilityRegime {
+                        turbulent,
+                        realized,
+                        haircut: if turbulent { config.vol_haircut } else { 1.0 },
+                        measured_at: clock::now_ms(),
+                    });
+                }
+            }
+            // Keep the last regime; a failed measurement says nothing about the market
+            Err(e) => Logger::warning(&format!("Volatility measurement failed: {}", e)),
+        }
+        sleep(Duration::from_secs(config.vol_check_secs)).await;
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+use crate::config::EnvConfig;
+use crate::utils::{
+    clock, get_usdc_allowance, get_usdc_balance, hex_u64, http_client, raise_alert, rpc_call, topic_address, usdc_amount,
+    Alert, AlertKind, HttpPool, Logger, POLYMARKET_EXCHANGE, TRANSFER_TOPIC,
+};
+
+// keccak256("Approval(address,address,uint256)")
+const APPROVAL_TOPIC: &str = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+
+// USDC moving to/from these is trading (fills, splits, merges, redemptions), not a deposit or withdrawal
+const SETTLEMENT_CONTRACTS: &[&str] = &[
+    POLYMARKET_EXCHANGE,
+    "0xC5d563A36AE78145C45a50134d48A1215220f80a", // Neg-risk CTF exchange
+    "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296", // Neg-risk adapter
+    "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045", // Conditional tokens
+];
+
+// Global flag for graceful shutdown
+static RUNNING: AtomicBool = AtomicBool::new(true);
+static WATCHING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+struct CachedBalance {
+    usdc: f64,
+    updated_at: i64,
+}
+
+static BALANCE: Mutex<Option<CachedBalance>> = Mutex::new(None);
+
+pub fn stop_wallet_watcher() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
+fn store_balance(usdc: f64) {
+    if let Ok(mut cache) = BALANCE.lock() {
+        *cache = Some(CachedBalance { usdc, updated_at: clock::now_ms() });
+    }
+}
+
+// Drop the cached balance (after our own order, so the next sizing reads the chain)
+pub fn invalidate_balance_cache() {
+    if let Ok(mut cache) = BALANCE.lock() {
+        *cache = None;
+    }
+}
+
+// USDC balance of PROXY_WALLET: cached while the watcher runs (it refreshes on every Transfer), RPC otherwise
+pub async fn cached_usdc_balance(config: &EnvConfig) -> Result<f64> {
+    if WATCHING.load(Ordering::SeqCst) {
+        if let Some(cached) = BALANCE.lock().ok().and_then(|c| *c) {
+            return Ok(cached.usdc);
+        }
+    }
+    let usdc = get_usdc_balance(&config.rpc_url, &config.usdc_contract_address, &config.proxy_wallet).await?;
+    if WATCHING.load(Ordering::SeqCst) {
+        store_balance(usdc);
+    }
+    Ok(usdc)
+}
+
+fn is_settlement_contract(address: &str) -> bool {
+    SETTLEMENT_CONTRACTS.iter().any(|c| c.eq_ignore_ascii_case(address))
+}
+
+fn wallet_topic(config: &EnvConfig) -> String {
+    format!("0x{:0>64}", config.proxy_wallet.trim().trim_start_matches("0x").to_lowercase())
+}
+
+async fn get_logs(client: &reqwest::Client, config: &EnvConfig, from: u64, to: u64, topics: Value) -> Result<Vec<Value>> {
+    let logs = rpc_call(
+        client,
+        &config.rpc_url,
+        "eth_getLogs",
+        json!([{
+            "address": config.usdc_contract_address,
+            "fromBlock": format!("0x{:x}", from),
+            "toBlock": format!("0x{:x}", to),
+            "topics": topics,
+        }]),
+    )
+    .await?;
+    Ok(logs.as_array().cloned().unwrap_or_default())
+}
+
+// Report one log; true = the balance may have changed
+async fn handle_log(config: &EnvConfig, http_client: &reqwest::Client, log: &Value) -> bool {
+    let topics: Vec<&str> = log
+        .get("topics")
+        .and_then(|t| t.as_array())
+        .map(|t| t.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    if topics.len() < 3 {
+        return false;
+    }
+    let (from, to) = (topic_address(topics[1]), topic_address(topics[2]));
+    let amount = usdc_amount(log.get("data").and_then(|v| v.as_str()).unwrap_or("0x0"));
+    let tx = log.get("transactionHash").and_then(|v| v.as_str()).unwrap_or("");
+    let wallet = config.proxy_wallet.trim().to_lowercase();
+
+    if topics[0].eq_ignore_ascii_case(APPROVAL_TOPIC) {
+        // Approval(owner = us, spender): the exchange is expected, anything else could drain the wallet
+        if is_settlement_contract(&to) {
+            let shown = if amount > 1e12 { "unlimited".to_string() } else { format!("${:.2}", amount) };
+            Logger::info(&format!("USDC allowance for {} set to {}", Logger::format_address(&to), shown));
+        } else if amount > 0.0 {
+            raise_alert(
+                config,
+                http_client,
+                Alert::new(
+                    AlertKind::UnknownApproval,
+                    format!("USDC approval to unknown spender {} (${:.2}) - tx {}", to, amount.min(1e12), tx),
+                ),
+            )
+            .await;
+        }
+        return false;
+    }
+
+    let counterparty = if to == wallet { &from } else { &to };
+    if is_settlement_contract(counterparty) {
+        return true; // Our own fills / redemptions - just refresh the balance
+    }
+    if to == wallet {
+        Logger::success(&format!(
+            "USDC deposit +${:.2} from {} - balance updated",
+            amount,
+            Logger::format_address(&from)
+        ));
+    } else {
+        raise_alert(
+            config,
+            http_client,
+            Alert::new(AlertKind::Withdrawal, format!("USDC withdrawal -${:.2} to {} - tx {}", amount, to, tx)),
+        )
+        .await;
+    }
+    true
+}
+
+// New USDC Transfer / Approval logs for PROXY_WALLET in (from, to]
+async fn poll_once(config: &EnvConfig, http_client: &reqwest::Client, rpc: &reqwest::Client, from_block: u64) -> Result<u64> {
+    let latest = hex_u64(&rpc_call(rpc, &config.rpc_url, "eth_blockNumber", json!([])).await?)
+        .ok_or_else(|| anyhow!("Bad eth_blockNumber response"))?;
+    if latest <= from_block {
+        return Ok(from_block);
+    }
+    let to_block = latest.min(from_block + config.rewards_scan_block_chunk);
+    let wallet = wallet_topic(config);
+    // Outgoing transfers + approvals share topic1 = us; incoming transfers have us in topic2
+    let mut logs = get_logs(rpc, config, from_block + 1, to_block, json!([[TRANSFER_TOPIC, APPROVAL_TOPIC], wallet])).await?;
+    logs.extend(get_logs(rpc, config, from_block + 1, to_block, json!([TRANSFER_TOPIC, Value::Null, wallet])).await?);
+
+    let mut balance_changed = false;
+    for log in &logs {
+        balance_changed |= handle_log(config, http_client, log).await;
+    }
+    if balance_changed {
+        match get_usdc_balance(&config.rpc_url, &config.usdc_contract_address, &config.proxy_wallet).await {
+            Ok(usdc) => store_balance(usdc),
+            Err(_) => invalidate_balance_cache(),
+        }
+    }
+    Ok(to_block)
+}
+
+// Background loop - runs until stop_wallet_watcher() (disabled when interval is 0)
+pub async fn run_wallet_watcher(config: EnvConfig, http_client: reqwest::Client) {
+    if config.wallet_watch_interval_secs == 0 {
+        return;
+    }
+    RUNNING.store(true, Ordering::SeqCst);
+    let rpc = http_client(HttpPool::Rpc);
+    let mut from_block = match rpc_call(&rpc, &config.rpc_url, "eth_blockNumber", json!([])).await {
+        Ok(v) => hex_u64(&v).unwrap_or(0),
+        Err(e) => {
+            Logger::warning(&format!("Wallet watcher disabled: {}", e));
+            return;
+        }
+    };
+    if let Ok(usdc) = get_usdc_balance(&config.rpc_url, &config.usdc_contract_address, &config.proxy_wallet).await {
+        store_balance(usdc);
+    }
+    if let Ok(allowance) =
+        get_usdc_allowance(&config.rpc_url, &config.usdc_contract_address, &config.proxy_wallet, POLYMARKET_EXCHANGE).await
+    {
+        if allowance.is_finite() {
+            Logger::info(&format!("USDC allowance for the exchange: ${:.2}", allowance));
+        }
+    }
+    WATCHING.store(true, Ordering::SeqCst);
+    Logger::info(&format!(
+        "Watching USDC transfers / approvals for {} every {}s",
+        Logger::format_address(&config.proxy_wallet),
+        config.wallet_watch_interval_secs
+    ));
+
+    while RUNNING.load(Ordering::SeqCst) {
+        sleep(Duration::from_secs(config.wallet_watch_interval_secs)).await;
+        match poll_once(&config, &http_client, &rpc, from_block).await {
+            Ok(block) => from_block = block,
+            Err(e) => {
+                // Stale cache is worse than an RPC read per trade until the next successful poll
+                invalidate_balance_cache();
+                Logger::warning(&format!("Wallet watch poll failed: {}", e));
+            }
+        }
+    }
+    WATCHING.store(false, Ordering::SeqCst);
+}
@@ -31,6 +31,332 @@ pub struct UserActivity {
     #[serde(rename = "botExcutedTime")]
     pub bot_executed_time: Option<i64>,
     pub my_bought_size: Option<f64>,
+    pub skip_reason: Option<SkipReason>,
+    pub skipped_at: Option<mongodb::bson::DateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    // Gamma-derived market category at execution time (reports, CATEGORY_MAX_USD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<MarketCategory>,
+    // Tokens still held after the trader exited and every sell attempt failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_stuck_size: Option<f64>,
+    // Handled under DRY_RUN - its myBoughtSize is paper, kept apart from live sell sizing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+}
+
+// Market category, classified from Gamma tags (utils::market_category)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketCategory {
+    Crypto,
+    Politics,
+    Sports,
+    Entertainment,
+    Other,
+}
+
+impl MarketCategory {
+    pub const ALL: [MarketCategory; 5] = [
+        MarketCategory::Crypto,
+        MarketCategory::Politics,
+        MarketCategory::Sports,
+        MarketCategory::Entertainment,
+        MarketCategory::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketCategory::Crypto => "crypto",
+            MarketCategory::Politics => "politics",
+            MarketCategory::Sports => "sports",
+            MarketCategory::Entertainment => "entertainment",
+            MarketCategory::Other => "other",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        Self::ALL.iter().copied().find(|c| c.as_str() == s)
+    }
+}
+
+// Why a detected trade was not copied (stored as skipReason on the activity)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    Stale,
+    NoAsset,
+    NoPosition,
+    BelowMinimum,
+    PositionTooSmall,
+    AggregationBelowMinimum,
+    InsufficientFunds,
+    NoLiquidity,
+    RetriesExhausted,
+    Paused,
+    OppositePosition,
+    Throttled,
+    NegativeExpectedValue,
+    ExposureLimit,
+    PriceMoved,
+    NettedOut,
+    AdverseBook,
+    AdverseMomentum,
+    Disputed,
+    CategoryLimit,
+    ShutdownDropped,
+    Blacklisted,
+    PriceBand,
+}
+
+impl SkipReason {
+    pub const ALL: [SkipReason; 23] = [
+        SkipReason::Stale,
+        SkipReason::NoAsset,
+        SkipReason::NoPosition,
+        SkipReason::BelowMinimum,
+        SkipReason::PositionTooSmall,
+        SkipReason::AggregationBelowMinimum,
+        SkipReason::InsufficientFunds,
+        SkipReason::NoLiquidity,
+        SkipReason::RetriesExhausted,
+        SkipReason::Paused,
+        SkipReason::OppositePosition,
+        SkipReason::Throttled,
+        SkipReason::NegativeExpectedValue,
+        SkipReason::ExposureLimit,
+        SkipReason::PriceMoved,
+        SkipReason::NettedOut,
+        SkipReason::AdverseBook,
+        SkipReason::AdverseMomentum,
+        SkipReason::Disputed,
+        SkipReason::CategoryLimit,
+        SkipReason::ShutdownDropped,
+        SkipReason::Blacklisted,
+        SkipReason::PriceBand,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::Stale => "stale",
+            SkipReason::NoAsset => "no_asset",
+            SkipReason::NoPosition => "no_position",
+            SkipReason::BelowMinimum => "below_minimum",
+            SkipReason::PositionTooSmall => "position_too_small",
+            SkipReason::AggregationBelowMinimum => "aggregation_below_minimum",
+            SkipReason::InsufficientFunds => "insufficient_funds",
+            SkipReason::NoLiquidity => "no_liquidity",
+            SkipReason::RetriesExhausted => "retries_exhausted",
+            SkipReason::Paused => "paused",
+            SkipReason::OppositePosition => "opposite_position",
+            SkipReason::Throttled => "throttled",
+            SkipReason::NegativeExpectedValue => "negative_ev",
+            SkipReason::ExposureLimit => "exposure_limit",
+            SkipReason::PriceMoved => "price_moved",
+            SkipReason::NettedOut => "netted_out",
+            SkipReason::AdverseBook => "adverse_book",
+            SkipReason::AdverseMomentum => "adverse_momentum",
+            SkipReason::Disputed => "disputed",
+            SkipReason::CategoryLimit => "category_limit",
+            SkipReason::ShutdownDropped => "shutdown_dropped",
+            SkipReason::Blacklisted => "blacklisted",
+            SkipReason::PriceBand => "price_band",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "shutdown_deferred" => Some(SkipReason::ShutdownDropped), // Earlier name, still in older records
+            _ => Self::ALL.iter().copied().find(|r| r.as_str() == s),
+        }
+    }
+
+    // Hint shown in the skips report (what to tune)
+    pub fn hint(&self) -> &'static str {
+        match self {
+            SkipReason::Stale => "raise TOO_OLD_TIMESTAMP or check RTDS latency",
+            SkipReason::NoAsset => "malformed activity from data API",
+            SkipReason::NoPosition => "trader sold something you never copied",
+            SkipReason::BelowMinimum => "raise COPY_SIZE or lower MIN_ORDER_SIZE_USD",
+            SkipReason::PositionTooSmall => "your position is below the 1 token minimum",
+            SkipReason::AggregationBelowMinimum => "widen TRADE_AGGREGATION_WINDOW_SECONDS",
+            SkipReason::InsufficientFunds => "top up USDC or check allowance",
+            SkipReason::NoLiquidity => "empty order book side - market too thin",
+            SkipReason::RetriesExhausted => "raise RETRY_LIMIT or check CLOB errors",
+            SkipReason::Paused => "TRADING_PAUSED / PAUSED_TRADERS was set (env or DB override), or the market was paused / closed with pause-market / close-market",
+            SkipReason::OppositePosition => "you hold the other outcome - OPPOSITE_SIDE_POLICY=skip",
+            SkipReason::Throttled => "trader scalps one market - raise MAX_COPIES_PER_MARKET_PER_HOUR or MARKET_THROTTLE_MODE=aggregate",
+            SkipReason::NegativeExpectedValue => "spread + fee exceed the trader's historical edge - REQUIRE_POSITIVE_EV=true",
+            SkipReason::ExposureLimit => "shared wallet cap reached under the supervisor - raise MAX_TOTAL_EXPOSURE_USD",
+            SkipReason::PriceMoved => "price ran away during COPY_DELAY_SECONDS - raise COPY_DELAY_MAX_DRIFT_PERCENT or shorten the delay",
+            SkipReason::NettedOut => "trader's buys and sells cancelled out inside the window - expected with TRADE_AGGREGATION_NET_SIDES",
+            SkipReason::AdverseBook => "asks outweigh bids near the top - lower ENTRY_MIN_IMBALANCE or ENTRY_IMBALANCE_FILTER=false",
+            SkipReason::AdverseMomentum => "price was falling fast before the copy - raise ENTRY_MAX_ADVERSE_MOVE_PERCENT or ENTRY_MOMENTUM_FILTER=false",
+            SkipReason::Disputed => "market was in a UMA resolution dispute - copying resumes once it resolves (DISPUTE_CHECK_SECS)",
+            SkipReason::CategoryLimit => "open positions in the market's category reached CATEGORY_MAX_USD - raise it or let positions close",
+            SkipReason::ShutdownDropped => "still buffered for aggregation when the bot stopped, so it was dropped (not copied on the next start) - raise SHUTDOWN_FLUSH_SECONDS to copy more of it on the way out",
+            SkipReason::Blacklisted => "market matches a MARKET_BLACKLIST entry - remove the entry to copy it again",
+            SkipReason::PriceBand => "trader bought outside COPY_MIN_PRICE..COPY_MAX_PRICE - widen the band if these are worth copying",
+        }
+    }
+}
+
+// What an annotation is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationKind {
+    Trader,
+    Market,
+}
+
+impl AnnotationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnotationKind::Trader => "trader",
+            AnnotationKind::Market => "market",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "trader" => Some(AnnotationKind::Trader),
+            "market" => Some(AnnotationKind::Market),
+            _ => None,
+        }
+    }
+}
+
+// One post_order run, stored in "bot_orders" - wallet fills in this market inside the window
+// are attributed to the bot, everything else is manual trading (see check_pnl)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotOrder {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<mongodb::bson::oid::ObjectId>,
+    pub trader: String,
+    pub condition_id: String,
+    pub asset: String,
+    pub side: String,
+    pub source_tx: Option<String>, // Trader's transaction that was copied
+    pub started_at: i64,  // ms
+    pub finished_at: i64, // ms
+    // Execution-quality benchmark (missing on orders recorded before it existed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trader_price: Option<f64>, // Price the trader filled at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mid_price: Option<f64>, // Book mid when the copy started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trader_ts: Option<i64>, // ms, trader's fill time
+}
+
+// Per-trader history backfill progress, stored in "backfill_cursors" (see fetch_history).
+// Walks backwards from newest_ts towards target_start_ts; cursor_ts is the oldest trade stored so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillCursor {
+    #[serde(rename = "_id")]
+    pub trader: String,       // Lowercased address
+    pub target_start_ts: i64, // Oldest trade wanted (seconds)
+    pub cursor_ts: i64,       // Everything in (cursor_ts, newest_ts] is stored (seconds)
+    #[serde(default)]
+    pub cursor_offset: u64,   // ... plus the newest cursor_offset trades at cursor_ts itself
+    pub newest_ts: i64,       // Newest trade stored (seconds)
+    pub trades: u64,          // Trades stored in history_<trader>
+    pub chunks: u64,          // Pages fetched over all runs
+    pub done: bool,           // Reached target_start_ts (only top-ups left)
+    pub updated_at: i64,      // ms
+}
+
+// USDC transfer into PROXY_WALLET seen in the RPC logs, stored in "wallet_inflows" (see daily_summary).
+// kind comes from the data-api activity with the same tx hash: trade / redeem / merge / reward,
+// REWARDS_SENDERS marks extra reward payers, anything else is a deposit (not performance)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletInflow {
+    #[serde(rename = "_id")]
+    pub id: String, // txHash:logIndex
+    pub tx_hash: String,
+    pub block: u64,
+    pub timestamp: i64, // seconds
+    pub from: String,
+    pub amount: f64, // USDC
+    pub kind: String,
+}
+
+// Startup database self-test (Db::self_test), shown on the health check "Database" line
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbSelfTest {
+    pub ping_ms: u64,
+    pub round_trip_ms: u64, // Insert + read back + delete in the scratch collection
+    pub replica_set: Option<String>, // None = standalone server
+    pub indexes_created: usize, // 0 on every start after the first
+    pub warnings: Vec<String>,
+}
+
+// One Logger line, stored in the capped "log_events" collection (newest LOG_EVENTS_MAX kept) for replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<mongodb::bson::oid::ObjectId>,
+    pub timestamp: i64, // ms
+    pub level: String, // INFO / SUCCESS / WARNING / ERROR / HEADER / TRADE / ORDER SUCCESS / ORDER FAILED
+    pub message: String,
+}
+
+// Simulated fill recorded instead of posting the order under DRY_RUN ("paper_trades")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperTrade {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<mongodb::bson::oid::ObjectId>,
+    pub trader: String, // Lowercased address whose trade this copies
+    pub condition_id: String,
+    pub asset: String,
+    pub side: String, // BUY / SELL
+    pub size: f64, // Tokens
+    pub price: f64, // Book price the order was built at (FOK = filled whole there)
+    pub usdc_size: f64,
+    pub reason: String, // copy, merge, net_opposite
+    pub source_tx: Option<String>, // Trader's transaction that was copied
+    pub trader_price: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+    pub timestamp: i64, // ms
+}
+
+// PROXY_WALLET cash vs money in positions at one moment ("capital_samples", every CAPITAL_SAMPLE_SECS)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapitalSample {
+    pub timestamp: i64, // ms
+    pub cash_usd: f64, // USDC balance
+    pub deployed_usd: f64, // Current value of open positions
+}
+
+// Tags + free-text note on a trader (address) or market (conditionId or slug)
+// Stored in the "annotations" collection, one doc per (kind, key)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<mongodb::bson::oid::ObjectId>,
+    pub kind: AnnotationKind,
+    pub key: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub updated_at: Option<mongodb::bson::DateTime>,
+}
+
+// Tags are case-insensitive labels like "experiment-a"
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
 }
 
 impl UserActivity {
@@ -0,0 +1,314 @@
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::config::EnvConfig;
+use crate::engine::{emit, EngineEvent};
+use crate::utils::{clock, Logger};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warn,
+    Critical,
+}
+
+impl Severity {
+    pub fn parse(s: &str) -> Option<Severity> {
+        match s.trim().to_lowercase().as_str() {
+            "info" => Some(Severity::Info),
+            "warn" | "warning" => Some(Severity::Warn),
+            "critical" | "crit" | "error" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ️",
+            Severity::Warn => "⚠️",
+            Severity::Critical => "🚨",
+        }
+    }
+}
+
+// What went wrong - each kind has a default severity and is deduplicated on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    AuthFailure,       // CLOB rejected our credentials / signature
+    InsufficientFunds, // Order rejected or skipped for balance / allowance
+    AllowanceLow,      // Approved USDC about to run out
+    LegFailure,        // Part of a copy couldn't be executed (exit stuck, sell left over)
+    WsDown,            // RTDS unreachable - trades are not being seen
+    Withdrawal,        // USDC left the wallet outside trading
+    UnknownApproval,   // USDC approved to a spender that isn't Polymarket
+    UncleanShutdown,   // Previous run died without stopping
+    TrailingStop,      // A trailing stop sold a position
+    DormantTrader,     // A copied trader went quiet while we still hold their positions
+    MarketDisputed,    // A market we hold or copy into entered a resolution dispute
+    SchemaDrift,       // RTDS sent a message type / field we don't know, or a trade we couldn't parse
+    TradeNote,         // A copied trader's public comment on the market they just traded (TRADE_NOTES)
+    General,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::AuthFailure => "auth_failure",
+            AlertKind::InsufficientFunds => "insufficient_funds",
+            AlertKind::AllowanceLow => "allowance_low",
+            AlertKind::LegFailure => "leg_failure",
+            AlertKind::WsDown => "ws_down",
+            AlertKind::Withdrawal => "withdrawal",
+            AlertKind::UnknownApproval => "unknown_approval",
+            AlertKind::UncleanShutdown => "unclean_shutdown",
+            AlertKind::TrailingStop => "trailing_stop",
+            AlertKind::DormantTrader => "dormant_trader",
+            AlertKind::MarketDisputed => "market_disputed",
+            AlertKind::SchemaDrift => "schema_drift",
+            AlertKind::TradeNote => "trade_note",
+            AlertKind::General => "general",
+        }
+    }
+
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            AlertKind::AuthFailure | AlertKind::WsDown | AlertKind::Withdrawal | AlertKind::UnknownApproval => {
+                Severity::Critical
+            }
+            AlertKind::TradeNote => Severity::Info,
+            _ => Severity::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub severity: Severity,
+    pub message: String,
+    // Dedup key within the kind (e.g. the asset); None = the message itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl Alert {
+    pub fn new(kind: AlertKind, message: impl Into<String>) -> Self {
+        Alert { kind, severity: kind.default_severity(), message: message.into(), key: None }
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    fn dedup_key(&self) -> String {
+        format!("{}:{}", self.kind.as_str(), self.key.as_deref().unwrap_or(&self.message))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Channel {
+    Console,
+    File,
+    Telegram,
+    Discord,
+    Webhook,
+}
+
+impl Channel {
+    fn parse(s: &str) -> Option<Channel> {
+        match s.trim().to_lowercase().as_str() {
+            "console" => Some(Channel::Console),
+            "file" => Some(Channel::File),
+            "telegram" => Some(Channel::Telegram),
+            "discord" => Some(Channel::Discord),
+            "webhook" => Some(Channel::Webhook),
+            _ => None,
+        }
+    }
+
+    fn is_remote(&self) -> bool {
+        matches!(self, Channel::Telegram | Channel::Discord | Channel::Webhook)
+    }
+
+    // Remote channels without credentials / URL are skipped silently
+    fn is_configured(&self, config: &EnvConfig) -> bool {
+        match self {
+            Channel::Telegram => config.alert_telegram_token.is_some() && config.alert_chat_id.is_some(),
+            Channel::Discord => config.alert_discord_webhook_url.is_some(),
+            Channel::Webhook => config.alert_webhook_url.is_some(),
+            Channel::Console | Channel::File => true,
+        }
+    }
+}
+
+// ALERT_ROUTES: "channel:min_severity,..." - a channel that isn't listed gets nothing
+fn parse_routes(spec: &str) -> Vec<(Channel, Severity)> {
+    spec.split(',')
+        .filter_map(|part| {
+            let (channel, severity) = part.split_once(':').unwrap_or((part, "info"));
+            Some((Channel::parse(channel)?, Severity::parse(severity)?))
+        })
+        .collect()
+}
+
+// Dedup + rate-limit bookkeeping shared by every caller
+#[derive(Default)]
+struct AlertState {
+    last_sent: HashMap<String, (i64, u32)>, // dedup key -> (sent at ms, repeats suppressed since)
+    remote_sent: VecDeque<i64>,             // Remote deliveries in the last minute
+    rate_limited: u32,                      // Remote deliveries dropped since the last one that went out
+}
+
+static STATE: Mutex<Option<AlertState>> = Mutex::new(None);
+
+// None = same alert inside ALERT_DEDUP_SECS (counted, not sent); Some(n) = send, n repeats were suppressed
+fn dedup(config: &EnvConfig, alert: &Alert, now: i64) -> Option<u32> {
+    let mut guard = STATE.lock().ok()?;
+    let state = guard.get_or_insert_with(AlertState::default);
+    let window_ms = config.alert_dedup_secs as i64 * 1000;
+    let entry = state.last_sent.entry(alert.dedup_key()).or_insert((0, 0));
+    if entry.0 > 0 && now - entry.0 < window_ms {
+        entry.1 += 1;
+        return None;
+    }
+    let suppressed = entry.1;
+    *entry = (now, 0);
+    state.last_sent.retain(|_, (at, _)| now - *at < window_ms.max(60_000));
+    Some(suppressed)
+}
+
+// Remote channels share ALERT_RATE_LIMIT_PER_MIN (critical alerts always go out)
+fn take_remote_slot(config: &EnvConfig, severity: Severity, now: i64) -> Option<u32> {
+    let mut guard = STATE.lock().ok()?;
+    let state = guard.get_or_insert_with(AlertState::default);
+    while state.remote_sent.front().is_some_and(|at| now - at >= 60_000) {
+        state.remote_sent.pop_front();
+    }
+    let limit = config.alert_rate_limit_per_min as usize;
+    if severity < Severity::Critical && limit > 0 && state.remote_sent.len() >= limit {
+        state.rate_limited += 1;
+        return None;
+    }
+    state.remote_sent.push_back(now);
+    Some(std::mem::take(&mut state.rate_limited))
+}
+
+fn append_to_file(path: &str, line: &serde_json::Value) -> std::io::Result<()> {
+    if let Some(dir) = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+async fn post_json(config: &EnvConfig, http_client: &reqwest::Client, name: &str, url: &str, body: &serde_json::Value) {
+    let result = http_client
+        .post(url)
+        .json(body)
+        .timeout(std::time::Duration::from_millis(config.request_timeout_ms))
+        .send()
+        .await;
+    match result {
+        Ok(resp) if !resp.status().is_success() => {
+            Logger::warning(&format!("{} alert failed: HTTP {}", name, resp.status()))
+        }
+        Err(e) => Logger::warning(&format!("{} alert failed: {}", name, e)),
+        _ => {}
+    }
+}
+
+// Route one alert to every channel whose minimum severity it meets
+pub async fn raise_alert(config: &EnvConfig, http_client: &reqwest::Client, alert: Alert) {
+    emit(EngineEvent::Alert(alert.clone()));
+    let now = clock::now_ms();
+    let routes = parse_routes(&config.alert_routes);
+    let suppressed = dedup(config, &alert, now);
+    let text = match suppressed {
+        Some(n) if n > 0 => format!("{} (+{} similar in the last {}s)", alert.message, n, config.alert_dedup_secs),
+        _ => alert.message.clone(),
+    };
+
+    for (channel, min) in routes {
+        if alert.severity < min {
+            continue;
+        }
+        // Repeats still reach the console, everything else waits out the dedup window
+        if (suppressed.is_none() && channel != Channel::Console) || !channel.is_configured(config) {
+            continue;
+        }
+        let dropped = if channel.is_remote() {
+            match take_remote_slot(config, alert.severity, now) {
+                Some(dropped) => dropped,
+                None => continue,
+            }
+        } else {
+            0
+        };
+        let remote_text = if dropped > 0 {
+            format!("{} {} [{}]\n({} alert(s) rate-limited before this one)", alert.severity.icon(), text, alert.kind.as_str(), dropped)
+        } else {
+            format!("{} {} [{}]", alert.severity.icon(), text, alert.kind.as_str())
+        };
+
+        match channel {
+            Channel::Console => {
+                let line = format!("ALERT [{}]: {}", alert.kind.as_str(), text);
+                match alert.severity {
+                    Severity::Info => Logger::info(&line),
+                    Severity::Warn => Logger::warning(&line),
+                    Severity::Critical => Logger::error(&line),
+                }
+            }
+            Channel::File => {
+                let line = serde_json::json!({
+                    "timestamp": now,
+                    "kind": alert.kind,
+                    "severity": alert.severity,
+                    "message": alert.message,
+                    "suppressed": suppressed.unwrap_or(0),
+                });
+                if let Err(e) = append_to_file(&config.alert_file, &line) {
+                    Logger::warning(&format!("Could not write alert file {}: {}", config.alert_file, e));
+                }
+            }
+            Channel::Telegram => {
+                let (Some(token), Some(chat_id)) = (&config.alert_telegram_token, &config.alert_chat_id) else {
+                    continue;
+                };
+                let url = format!("https://api.telegram.org/bot{}/sendMessage", token);
+                post_json(config, http_client, "Telegram", &url, &serde_json::json!({ "chat_id": chat_id, "text": remote_text })).await;
+            }
+            Channel::Discord => {
+                let Some(url) = &config.alert_discord_webhook_url else {
+                    continue;
+                };
+                post_json(config, http_client, "Discord", url, &serde_json::json!({ "content": remote_text })).await;
+            }
+            Channel::Webhook => {
+                let Some(url) = &config.alert_webhook_url else {
+                    continue;
+                };
+                let body = serde_json::json!({
+                    "timestamp": now,
+                    "kind": alert.kind,
+                    "severity": alert.severity,
+                    "message": text,
+                    "text": remote_text, // Slack-compatible
+                });
+                post_json(config, http_client, "Webhook", url, &body).await;
+            }
+        }
+    }
+}
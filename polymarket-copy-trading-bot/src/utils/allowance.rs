@@ -0,0 +1,74 @@
+use alloy::network::TransactionBuilder;
+use alloy::providers::Provider;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::Signer;
+use anyhow::Result;
+use std::str::FromStr;
+
+use crate::config::{EnvConfig, SignerBackend};
+
+// Polymarket CTF exchange (USDC spender for BUY orders)
+pub const POLYMARKET_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+const POLYGON_CHAIN_ID: u64 = 137;
+// Gas limit of the approve tx (also what the gas check prices it at)
+pub const APPROVE_GAS: u64 = 100_000;
+
+// Calldata for a contract call: 4-byte selector + each argument left-padded to a 32-byte word (hex, 0x optional)
+pub fn encode_call(selector: &str, args: &[&str]) -> Result<Vec<u8>> {
+    let mut calldata_hex = selector.trim_start_matches("0x").to_string();
+    for arg in args {
+        calldata_hex.push_str(&format!("{:0>64}", arg.trim().trim_start_matches("0x").to_lowercase()));
+    }
+    hex::decode(&calldata_hex).map_err(|e| anyhow::anyhow!("Bad calldata {}: {}", calldata_hex, e))
+}
+
+// The key approve_usdc_max signs with - fails unless SIGNER_BACKEND=local and PROXY_WALLET is that key's EOA
+// (a Safe / proxy wallet needs its own tx flow). EnvConfig checks it at startup under ALLOWANCE_AUTO_TOPUP
+pub fn approval_signer(config: &EnvConfig) -> Result<PrivateKeySigner> {
+    // The tx is signed & sent by alloy's local wallet - remote signers approve from their own tooling
+    if config.signer_backend != SignerBackend::Local {
+        anyhow::bail!(
+            "Approving USDC needs the local PRIVATE_KEY signer (SIGNER_BACKEND={}); approve from your signer's wallet instead",
+            config.signer_backend.label()
+        );
+    }
+    let signer = PrivateKeySigner::from_str(&format!("0x{}", config.private_key))
+        .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?
+        .with_chain_id(Some(POLYGON_CHAIN_ID));
+    let eoa = format!("0x{:x}", signer.address());
+    if !eoa.eq_ignore_ascii_case(config.proxy_wallet.trim()) {
+        anyhow::bail!(
+            "PROXY_WALLET is not the signer EOA ({}); approve from the proxy wallet instead",
+            eoa
+        );
+    }
+    Ok(signer)
+}
+
+// Send approve(exchange, MAX) for the configured USDC token - returns tx hash
+pub async fn approve_usdc_max(config: &EnvConfig) -> Result<String> {
+    let signer = approval_signer(config)?;
+    let data_bytes = encode_call("095ea7b3", &[POLYMARKET_EXCHANGE, &"f".repeat(64)])?;
+
+    let to_addr = config
+        .usdc_contract_address
+        .parse::<alloy::primitives::Address>()?;
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .with_to(to_addr)
+        .with_gas_limit(APPROVE_GAS)
+        .with_input(alloy::primitives::Bytes::from(data_bytes));
+
+    let url: url::Url = config.rpc_url.parse()?;
+    let provider = alloy::providers::ProviderBuilder::new()
+        .wallet(signer)
+        .with_chain_id(POLYGON_CHAIN_ID)
+        .connect_http(url);
+
+    let pending = provider.send_transaction(tx).await?;
+    let tx_hash = format!("0x{:x}", pending.tx_hash());
+    let receipt = pending.get_receipt().await?;
+    if !receipt.status() {
+        anyhow::bail!("Approval transaction reverted: {}", tx_hash);
+    }
+    Ok(tx_hash)
+}
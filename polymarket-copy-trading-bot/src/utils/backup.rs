@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use mongodb::bson::Bson;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use crate::db::Db;
+
+// Archive = gzip'd JSON lines: one header line, then {"c": collection, "d": document} per line
+// Documents are canonical extended JSON so ObjectIds, dates and int64s survive the round trip
+const BACKUP_FORMAT: &str = "polymarket-copy-backup";
+const BACKUP_VERSION: u64 = 1;
+
+// Documents per collection, in collection order
+pub type BackupSummary = BTreeMap<String, u64>;
+
+// Dump every bot collection to `path` (either backend - an archive from one restores into the other)
+pub async fn backup_database(db: &Db, path: &str) -> Result<BackupSummary> {
+    let file = File::create(path).with_context(|| format!("Cannot create {}", path))?;
+    let mut out = GzEncoder::new(BufWriter::new(file), Compression::default());
+
+    let header = serde_json::json!({
+        "format": BACKUP_FORMAT,
+        "version": BACKUP_VERSION,
+        "createdAt": chrono::Utc::now().to_rfc3339(),
+    });
+    writeln!(out, "{}", header)?;
+
+    let mut summary = BackupSummary::new();
+    for name in db.collection_names().await? {
+        let mut count = 0;
+        for d in db.dump_collection(&name).await? {
+            let line = serde_json::json!({
+                "c": name,
+                "d": Bson::Document(d).into_canonical_extjson(),
+            });
+            writeln!(out, "{}", line)?;
+            count += 1;
+        }
+        summary.insert(name, count);
+    }
+
+    out.finish()?.flush()?;
+    Ok(summary)
+}
+
+// Load an archive back. Documents are upserted by _id (re-running is safe);
+// `drop_existing` empties each collection in the archive first (exact copy of the backup)
+pub async fn restore_database(db: &Db, path: &str, drop_existing: bool) -> Result<BackupSummary> {
+    let file = File::open(path).with_context(|| format!("Cannot open {}", path))?;
+    let mut lines = BufReader::new(GzDecoder::new(file)).lines();
+
+    let header: serde_json::Value = serde_json::from_str(
+        &lines.next().context("Empty backup archive")??,
+    )
+    .context("Invalid backup header")?;
+    if header.get("format").and_then(|v| v.as_str()) != Some(BACKUP_FORMAT) {
+        anyhow::bail!("{} is not a bot backup archive", path);
+    }
+    let version = header.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if version > BACKUP_VERSION {
+        anyhow::bail!("Backup version {} is newer than this tool ({})", version, BACKUP_VERSION);
+    }
+
+    let mut summary = BackupSummary::new();
+    for (i, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: serde_json::Value =
+            serde_json::from_str(&line).with_context(|| format!("Bad entry on line {}", i + 2))?;
+        let name = entry
+            .get("c")
+            .and_then(|v| v.as_str())
+            .with_context(|| format!("Missing collection on line {}", i + 2))?
+            .to_string();
+        let bson = Bson::try_from(entry.get("d").cloned().unwrap_or_default())
+            .with_context(|| format!("Bad document on line {}", i + 2))?;
+        let Bson::Document(d) = bson else {
+            anyhow::bail!("Line {} is not a document", i + 2);
+        };
+
+        if !summary.contains_key(&name) && drop_existing {
+            db.drop_collection(&name).await?;
+        }
+        db.restore_document(&name, &d).await?;
+        *summary.entry(name).or_insert(0) += 1;
+    }
+    Ok(summary)
+}
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::types::CapitalSample;
+
+// A sample stands for at most this many sample intervals (longer gaps = the bot was down, not counted)
+const MAX_GAP_INTERVALS: i64 = 2;
+const MS_PER_YEAR: f64 = 365.0 * 86_400_000.0;
+
+// How hard the money worked over a period, from the "capital_samples" time series
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapitalEfficiency {
+    pub samples: usize,
+    pub covered_hours: f64, // Time the samples account for (less than the period when the bot was down)
+    pub avg_idle_percent: f64, // Time-weighted cash / (cash + deployed)
+    pub min_idle_percent: f64, // Lowest idle share seen - the part of the reserve that was never used
+    pub utilization_percent: f64, // 100 - avg idle
+    pub avg_cash_usd: f64,
+    pub min_cash_usd: f64,
+    pub avg_deployed_usd: f64, // Time-weighted capital deployed
+    pub peak_deployed_usd: f64,
+    pub idle_cash_apr_percent: f64,
+    pub opportunity_cost_usd: f64, // Avg cash x IDLE_CASH_APR_PERCENT over the covered time
+    pub insufficient_funds_skips: u64, // Copies lost because cash ran out in the period
+}
+
+impl CapitalEfficiency {
+    pub fn lines(&self) -> Vec<String> {
+        let mut out = vec![
+            format!(
+                "Idle cash: {:.1}% on average (lowest {:.1}%), utilization {:.1}%",
+                self.avg_idle_percent, self.min_idle_percent, self.utilization_percent
+            ),
+            format!(
+                "Deployed: ${:.2} time-weighted (peak ${:.2}), cash ${:.2} on average (lowest ${:.2})",
+                self.avg_deployed_usd, self.peak_deployed_usd, self.avg_cash_usd, self.min_cash_usd
+            ),
+            format!(
+                "Opportunity cost of idle cash: ${:.2} at {:.1}% APR over {:.0}h sampled ({} samples)",
+                self.opportunity_cost_usd, self.idle_cash_apr_percent, self.covered_hours, self.samples
+            ),
+        ];
+        // The two ends of reserve sizing: cash that never moved vs copies lost for lack of it
+        if self.insufficient_funds_skips > 0 {
+            out.push(format!(
+                "{} copies skipped for insufficient funds - cash was the constraint, not idle",
+                self.insufficient_funds_skips
+            ));
+        } else if self.min_cash_usd > 0.0 {
+            out.push(format!(
+                "Cash never fell below ${:.2} and no copy ran out of funds - that much sat unused all period",
+                self.min_cash_usd
+            ));
+        }
+        out
+    }
+}
+
+// Time-weighted stats over samples (oldest first) ending at to_ms; None without anything to weigh
+fn capital_efficiency(
+    samples: &[CapitalSample],
+    sample_secs: u64,
+    apr_percent: f64,
+    to_ms: i64,
+    insufficient_funds_skips: u64,
+) -> Option<CapitalEfficiency> {
+    let max_gap_ms = (sample_secs as i64 * 1000 * MAX_GAP_INTERVALS).max(1);
+    let (mut weight, mut idle, mut cash, mut deployed) = (0.0, 0.0, 0.0, 0.0);
+    let (mut min_idle, mut min_cash, mut peak_deployed) = (f64::INFINITY, f64::INFINITY, 0.0f64);
+    let mut counted = 0;
+    for (i, s) in samples.iter().enumerate() {
+        let total = s.cash_usd + s.deployed_usd;
+        if total <= 0.0 {
+            continue;
+        }
+        let next = samples.get(i + 1).map(|n| n.timestamp).unwrap_or(to_ms);
+        let w = (next - s.timestamp).clamp(0, max_gap_ms) as f64;
+        if w == 0.0 {
+            continue;
+        }
+        let idle_percent = s.cash_usd / total * 100.0;
+        weight += w;
+        idle += idle_percent * w;
+        cash += s.cash_usd * w;
+        deployed += s.deployed_usd * w;
+        min_idle = min_idle.min(idle_percent);
+        min_cash = min_cash.min(s.cash_usd);
+        peak_deployed = peak_deployed.max(s.deployed_usd);
+        counted += 1;
+    }
+    if weight == 0.0 {
+        return None;
+    }
+    let avg_idle_percent = idle / weight;
+    let avg_cash_usd = cash / weight;
+    Some(CapitalEfficiency {
+        samples: counted,
+        covered_hours: weight / 3_600_000.0,
+        avg_idle_percent,
+        min_idle_percent: min_idle,
+        utilization_percent: 100.0 - avg_idle_percent,
+        avg_cash_usd,
+        min_cash_usd: min_cash,
+        avg_deployed_usd: deployed / weight,
+        peak_deployed_usd: peak_deployed,
+        idle_cash_apr_percent: apr_percent,
+        opportunity_cost_usd: avg_cash_usd * apr_percent / 100.0 * weight / MS_PER_YEAR,
+        insufficient_funds_skips,
+    })
+}
+
+// Capital efficiency for [from_ms, to_ms]; Ok(None) when sampling is off or nothing was sampled yet
+pub async fn build_capital_report(
+    config: &EnvConfig,
+    db: &Db,
+    from_ms: i64,
+    to_ms: i64,
+    insufficient_funds_skips: u64,
+) -> Result<Option<CapitalEfficiency>> {
+    if config.capital_sample_secs == 0 {
+        return Ok(None);
+    }
+    let samples = db.get_capital_samples(from_ms, to_ms).await?;
+    Ok(capital_efficiency(
+        &samples,
+        config.capital_sample_secs,
+        config.idle_cash_apr_percent,
+        to_ms,
+        insufficient_funds_skips,
+    ))
+}
@@ -0,0 +1,137 @@
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use serde_json::json;
+
+use super::http::{http_client, HttpPool};
+use super::rewards::{hex_u64, rpc_call};
+use crate::config::EnvConfig;
+
+// The bot signs orders and approvals for Polygon only
+const POLYGON_CHAIN_ID: u64 = 137;
+
+// USDC contracts Polymarket settles in on Polygon (USDC.e is the one the CLOB uses)
+const POLYGON_USDC: [&str; 2] = [
+    "0x2791bca1f2de4661ed88a30c99a7a9449aa84174", // USDC.e (bridged)
+    "0x3c499c542cef5e3811e1192ce70d8cc03d5c3359", // Native USDC
+];
+
+fn chain_name(chain_id: u64) -> &'static str {
+    match chain_id {
+        1 => "Ethereum mainnet",
+        137 => "Polygon",
+        80001 => "Polygon Mumbai testnet",
+        80002 => "Polygon Amoy testnet",
+        11155111 => "Sepolia testnet",
+        8453 => "Base",
+        42161 => "Arbitrum One",
+        10 => "Optimism",
+        56 => "BNB Chain",
+        _ => "unknown chain",
+    }
+}
+
+// What RPC_URL and USDC_CONTRACT_ADDRESS turned out to be
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainCheck {
+    pub chain_id: u64,
+    pub usdc_symbol: String,
+    pub usdc_decimals: u8,
+    pub usdc_code_hash: String, // keccak256 of the deployed bytecode (pin it with USDC_CODE_HASH)
+    pub warnings: Vec<String>,
+}
+
+impl ChainCheck {
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "chain {} ({}), {} with {} decimals, code hash {}",
+            self.chain_id,
+            chain_name(self.chain_id),
+            self.usdc_symbol,
+            self.usdc_decimals,
+            self.usdc_code_hash
+        );
+        if !self.warnings.is_empty() {
+            line.push_str(&format!(" - {}", self.warnings.join("; ")));
+        }
+        line
+    }
+}
+
+// eth_call returning an ABI-encoded string (symbol())
+fn decode_abi_string(hex: &str) -> Option<String> {
+    let bytes = alloy::hex::decode(hex.trim_start_matches("0x")).ok()?;
+    if bytes.len() < 64 {
+        // Old tokens return bytes32 instead of a string
+        return Some(String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string());
+    }
+    let len = u64::from_be_bytes(bytes[56..64].try_into().ok()?) as usize;
+    let data = bytes.get(64..64 + len)?;
+    Some(String::from_utf8_lossy(data).to_string())
+}
+
+// Fail fast on the wrong network: RPC_URL must report chain id 137 (Polygon) and
+// USDC_CONTRACT_ADDRESS must be a 6-decimal USDC contract there (and match USDC_CODE_HASH when pinned)
+pub async fn verify_chain(config: &EnvConfig) -> Result<ChainCheck> {
+    let client = http_client(HttpPool::Rpc);
+    let chain_id = hex_u64(&rpc_call(&client, &config.rpc_url, "eth_chainId", json!([])).await?)
+        .ok_or_else(|| anyhow!("RPC_URL returned a bad eth_chainId response"))?;
+    if chain_id != POLYGON_CHAIN_ID {
+        bail!(
+            "RPC_URL is on chain {} ({}), expected {} (Polygon) - point RPC_URL at a Polygon mainnet endpoint",
+            chain_id,
+            chain_name(chain_id),
+            POLYGON_CHAIN_ID
+        );
+    }
+
+    let usdc = config.usdc_contract_address.to_lowercase();
+    let code = rpc_call(&client, &config.rpc_url, "eth_getCode", json!([usdc, "latest"])).await?;
+    let code = code.as_str().unwrap_or("0x").trim_start_matches("0x").to_string();
+    if code.is_empty() {
+        bail!(
+            "USDC_CONTRACT_ADDRESS {} has no contract code on {} - wrong address or wrong network",
+            config.usdc_contract_address,
+            chain_name(chain_id)
+        );
+    }
+    let code_bytes = alloy::hex::decode(&code).map_err(|e| anyhow!("Bad eth_getCode response: {}", e))?;
+    let usdc_code_hash = format!("0x{}", alloy::hex::encode(alloy::primitives::keccak256(&code_bytes)));
+    if let Some(ref expected) = config.usdc_code_hash {
+        if !expected.eq_ignore_ascii_case(&usdc_code_hash) {
+            bail!(
+                "USDC_CONTRACT_ADDRESS code hash {} does not match USDC_CODE_HASH {}",
+                usdc_code_hash,
+                expected
+            );
+        }
+    }
+
+    let call = |data: &str| json!([{ "to": usdc, "data": data }, "latest"]);
+    let usdc_decimals = hex_u64(&rpc_call(&client, &config.rpc_url, "eth_call", call("0x313ce567")).await?)
+        .ok_or_else(|| anyhow!("USDC_CONTRACT_ADDRESS has no decimals() - not an ERC-20"))? as u8;
+    let usdc_symbol = rpc_call(&client, &config.rpc_url, "eth_call", call("0x95d89b41"))
+        .await
+        .ok()
+        .and_then(|v| v.as_str().and_then(decode_abi_string))
+        .unwrap_or_default();
+    if usdc_decimals != 6 || !usdc_symbol.to_uppercase().starts_with("USDC") {
+        bail!(
+            "USDC_CONTRACT_ADDRESS is {} with {} decimals, not USDC - check the address",
+            if usdc_symbol.is_empty() { "an unnamed token" } else { &usdc_symbol },
+            usdc_decimals
+        );
+    }
+
+    let mut warnings = Vec::new();
+    if !POLYGON_USDC.contains(&usdc.as_str()) {
+        warnings.push("not a USDC contract Polymarket settles in".to_string());
+    }
+    Ok(ChainCheck {
+        chain_id,
+        usdc_symbol,
+        usdc_decimals,
+        usdc_code_hash,
+        warnings,
+    })
+}
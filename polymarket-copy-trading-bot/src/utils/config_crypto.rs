@@ -0,0 +1,148 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::env;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// Stored form of an encrypted config value: enc:v1:<nonce hex>:<ciphertext+tag hex>
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+// HKDF info - changing it makes every stored secret unreadable
+const KEY_CONTEXT: &[u8] = b"polymarket-copy-trading/configs/v1";
+// HKDF info for the fingerprint key (dedup of stored PRIVATE_KEY values without decrypting them)
+const FINGERPRINT_CONTEXT: &[u8] = b"polymarket-copy-trading/configs/fingerprint/v1";
+// Where older versions created the secret without being asked - only named in the error now
+const LEGACY_SECRET_FILE: &str = "config.secret";
+
+// Config keys whose values are never written to Mongo in plaintext (substring match, case-insensitive)
+const SENSITIVE_KEYS: &[&str] = &["PRIVATE_KEY", "SECRET", "PASSPHRASE", "API_KEY", "BOT_TOKEN", "MONGO_URI"];
+
+static CIPHER: OnceLock<Result<ConfigCipher, String>> = OnceLock::new();
+
+pub fn is_sensitive_config_key(key: &str) -> bool {
+    let key = key.to_uppercase();
+    SENSITIVE_KEYS.iter().any(|k| key.contains(k))
+}
+
+pub fn is_encrypted_config_value(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+// AES-256-GCM over config values, key = HKDF-SHA256(local secret)
+#[derive(Clone)]
+pub struct ConfigCipher {
+    cipher: Aes256Gcm,
+    fingerprint_key: [u8; 32],
+}
+
+impl ConfigCipher {
+    pub fn from_secret(secret: &[u8]) -> Self {
+        let hkdf = Hkdf::<Sha256>::new(None, secret);
+        let (mut key, mut fingerprint_key) = ([0u8; 32], [0u8; 32]);
+        hkdf.expand(KEY_CONTEXT, &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        hkdf.expand(FINGERPRINT_CONTEXT, &mut fingerprint_key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Self {
+            cipher: Aes256Gcm::new(&key.into()),
+            fingerprint_key,
+        }
+    }
+
+    // CONFIG_ENCRYPTION_SECRET, else the CONFIG_SECRET_FILE contents (a missing file is created with a random
+    // secret). One of them must be set - the key is never put in the working directory unasked
+    pub fn load() -> Result<Self> {
+        if let Ok(secret) = env::var("CONFIG_ENCRYPTION_SECRET") {
+            if !secret.trim().is_empty() {
+                return Ok(Self::from_secret(secret.trim().as_bytes()));
+            }
+        }
+        let Some(path) = env::var("CONFIG_SECRET_FILE")
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| PathBuf::from(p.trim()))
+        else {
+            let hint = if PathBuf::from(LEGACY_SECRET_FILE).exists() {
+                format!(" (found ./{} from an older version - set CONFIG_SECRET_FILE to its absolute path)", LEGACY_SECRET_FILE)
+            } else {
+                String::new()
+            };
+            return Err(anyhow!("set CONFIG_ENCRYPTION_SECRET or CONFIG_SECRET_FILE{}", hint));
+        };
+        if path.exists() {
+            let secret = std::fs::read_to_string(&path)
+                .map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+            if secret.trim().is_empty() {
+                return Err(anyhow!("{} is empty", path.display()));
+            }
+            return Ok(Self::from_secret(secret.trim().as_bytes()));
+        }
+
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let secret = hex::encode(bytes);
+        // Created owner-only from the start (never readable under the umask first), and never over an existing file
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&path)
+            .and_then(|mut file| file.write_all(secret.as_bytes()))
+            .map_err(|e| anyhow!("Cannot create {}: {}", path.display(), e))?;
+        Ok(Self::from_secret(secret.as_bytes()))
+    }
+
+    // Deterministic HMAC-SHA256 of a value, stored next to its randomized ciphertext so duplicates can be found
+    // by query
+    pub fn fingerprint(&self, plaintext: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.fingerprint_key)
+            .expect("HMAC-SHA256 accepts any key length");
+        mac.update(plaintext.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| anyhow!("Config value encryption failed"))?;
+        Ok(format!("{}{}:{}", ENCRYPTED_PREFIX, hex::encode(nonce), hex::encode(ciphertext)))
+    }
+
+    // Plaintext (pre-migration) values pass through unchanged
+    pub fn decrypt(&self, stored: &str) -> Result<String> {
+        let Some(body) = stored.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let (nonce_hex, ciphertext_hex) = body
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Malformed encrypted config value"))?;
+        let nonce = hex::decode(nonce_hex)?;
+        if nonce.len() != 12 {
+            return Err(anyhow!("Malformed encrypted config value (nonce)"));
+        }
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(&nonce), hex::decode(ciphertext_hex)?.as_ref())
+            .map_err(|_| anyhow!("Config value decryption failed - wrong CONFIG_ENCRYPTION_SECRET / CONFIG_SECRET_FILE?"))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+}
+
+// Process-wide cipher, loaded on first use so tools that never touch secrets don't need the key
+pub fn config_cipher() -> Result<&'static ConfigCipher> {
+    CIPHER
+        .get_or_init(|| ConfigCipher::load().map_err(|e| e.to_string()))
+        .as_ref()
+        .map_err(|e| anyhow!("Config encryption unavailable: {}", e))
+}
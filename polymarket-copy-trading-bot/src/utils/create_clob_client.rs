@@ -1,37 +1,52 @@
 use anyhow::Result;
-use alloy::signers::local::PrivateKeySigner;
 use alloy::signers::Signer as _;
 use polymarket_client_sdk::clob::Client as ClobClient;
+use polymarket_client_sdk::clob::types::SignatureType;
 use polymarket_client_sdk::auth::state::Authenticated;
 use polymarket_client_sdk::auth::Normal;
-use polymarket_client_sdk::POLYGON;
-use std::str::FromStr;
 use crate::config::EnvConfig;
-use crate::utils::{is_contract_address, Logger};
+use crate::utils::{create_signer, detect_wallet_kind, BotSigner, Logger, WalletKind};
 
-// Init CLOB client & signer - handles both EOA & Gnosis Safe wallets
-pub async fn create_clob_client(config: &EnvConfig) -> Result<(ClobClient<Authenticated<Normal>>, PrivateKeySigner)> {
-    let chain_id = POLYGON;
+// Resolve wallet kind - SIGNATURE_TYPE env wins, otherwise detect on-chain
+// (startup stores the result in config.signature_type, so later clients don't ask the RPC again)
+pub async fn resolve_wallet_kind(config: &EnvConfig, signer: &BotSigner) -> Result<WalletKind> {
+    if let Some(sig_type) = config.signature_type {
+        return WalletKind::from_signature_type(sig_type)
+            .ok_or_else(|| anyhow::anyhow!("Invalid SIGNATURE_TYPE: {} (expected 0, 1 or 2)", sig_type));
+    }
+    let eoa = format!("0x{:x}", signer.address());
+    detect_wallet_kind(&config.rpc_url, &config.proxy_wallet, &eoa).await
+}
+
+// Init CLOB client & signer - handles EOA, Polymarket proxy & Gnosis Safe wallets
+pub async fn create_clob_client(config: &EnvConfig) -> Result<(ClobClient<Authenticated<Normal>>, BotSigner)> {
     let host = &config.clob_http_url;
     
-    // Parse priv key & set chain ID (Polygon mainnet)
-    let signer = PrivateKeySigner::from_str(&format!("0x{}", config.private_key))
-        .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?
-        .with_chain_id(Some(chain_id));
-    
-    // Check if wallet is a contract (Gnosis Safe) vs EOA
-    let is_proxy_safe = is_contract_address(&config.rpc_url, &config.proxy_wallet).await?;
+    // Local key or remote signer (SIGNER_BACKEND) - chain ID set to Polygon mainnet
+    let signer = create_signer(config).await?;
     
-    // Default to EOA, switch to Gnosis if detected
-    let mut wallet_type = "EOA (Externally Owned Account)";
-    if is_proxy_safe {
-        wallet_type = "Gnosis Safe";
-    }
-    
-    Logger::info(&format!("Wallet type detected: {}", wallet_type));
+    // EOA signs for itself; proxy & Safe wallets sign with the EOA but fund from PROXY_WALLET
+    let wallet_kind = resolve_wallet_kind(config, &signer).await?;
+    Logger::info(&format!(
+        "Wallet type: {} (signature type {})",
+        wallet_kind.label(),
+        wallet_kind.signature_type()
+    ));
     
     // Auth with CLOB API using appropriate sig type
-    let clob_client = ClobClient::new(host, Default::default())?;
+    let mut auth = ClobClient::new(host, Default::default())?.authentication_builder(&signer);
+    if wallet_kind != WalletKind::Eoa {
+        let funder = config
+            .proxy_wallet
+            .trim()
+            .parse::<alloy::primitives::Address>()
+            .map_err(|e| anyhow::anyhow!("Invalid PROXY_WALLET: {}", e))?;
+        let signature_type = match wallet_kind {
+            WalletKind::PolyProxy => SignatureType::Proxy,
+            _ => SignatureType::GnosisSafe,
+        };
+        auth = auth.funder(funder).signature_type(signature_type);
+    }
+    let clob_client = auth.authenticate().await?;
     Ok((clob_client, signer))
 }
-
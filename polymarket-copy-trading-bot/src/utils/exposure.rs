@@ -0,0 +1,51 @@
+use std::sync::{Arc, OnceLock};
+
+// Capital cap shared with other engines on the same wallet (set by the supervisor binary)
+pub use polymarket_gates::ExposureGate;
+
+// Name this engine books its exposure and CLOB calls under
+pub const COPY_STRATEGY: &str = "copy";
+
+static EXPOSURE_GATE: OnceLock<Arc<dyn ExposureGate>> = OnceLock::new();
+
+// Install the shared gate (first call wins; standalone runs never call this)
+pub fn set_exposure_gate(gate: Arc<dyn ExposureGate>) {
+    let _ = EXPOSURE_GATE.set(gate);
+}
+
+pub fn release_exposure(usd: f64) {
+    if let Some(gate) = EXPOSURE_GATE.get() {
+        if usd > 0.0 {
+            gate.release(COPY_STRATEGY, usd);
+        }
+    }
+}
+
+// Exposure held for one buy; whatever isn't spent is released on drop (early returns / errors included)
+pub struct ExposureHold {
+    reserved: f64,
+    spent: f64,
+}
+
+impl ExposureHold {
+    // None = the shared cap is reached (always Some when no gate is installed)
+    pub fn reserve(usd: f64) -> Option<Self> {
+        if let Some(gate) = EXPOSURE_GATE.get() {
+            if !gate.try_reserve(COPY_STRATEGY, usd) {
+                return None;
+            }
+        }
+        Some(Self { reserved: usd, spent: 0.0 })
+    }
+
+    // Book a fill - stays as exposure until the position is sold
+    pub fn spend(&mut self, usd: f64) {
+        self.spent += usd;
+    }
+}
+
+impl Drop for ExposureHold {
+    fn drop(&mut self) {
+        release_exposure(self.reserved - self.spent);
+    }
+}
@@ -2,6 +2,8 @@ use anyhow::Result;
 use reqwest::Client;
 use std::time::Duration;
 
+use super::rate_limit;
+
 fn is_network_error(error: &reqwest::Error) -> bool {
     error.is_timeout()
         || error.is_connect()
@@ -18,6 +20,8 @@ pub async fn fetch_data(
     let retry_delay_ms = 1000u64;
     
     for attempt in 1..=retry_limit {
+        // Slow down while the host's quota is low (or it just 429'd us)
+        rate_limit::pace(url).await;
         match client
             .get(url)
             .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
@@ -26,9 +30,12 @@ pub async fn fetch_data(
             .await
         {
             Ok(resp) if resp.status().is_success() => {
+                rate_limit::record_response(url, resp.status(), resp.headers());
                 return Ok(resp.json().await?);
             }
             Ok(resp) => {
+                // A 429 sets a back-off that the next pace() waits out
+                rate_limit::record_response(url, resp.status(), resp.headers());
                 if attempt == retry_limit {
                     return Err(anyhow::anyhow!("HTTP {} after {} attempts", resp.status(), retry_limit));
                 }
@@ -38,7 +45,7 @@ pub async fn fetch_data(
                 
                 if is_network_error(&e) && !is_last_attempt {
                     let delay_ms = retry_delay_ms * 2u64.pow(attempt - 1);
-                    eprintln!(
+                    eoutln!(
                         "⚠️  Network error (attempt {}/{}), retrying in {:.1}s...",
                         attempt,
                         retry_limit,
@@ -49,7 +56,7 @@ pub async fn fetch_data(
                 }
                 
                 if is_last_attempt && is_network_error(&e) {
-                    eprintln!(
+                    eoutln!(
                         "❌ Network timeout after {} attempts - {}",
                         retry_limit,
                         e
@@ -62,3 +69,70 @@ pub async fn fetch_data(
     
     Err(anyhow::anyhow!("Failed after {} attempts", retry_limit))
 }
+
+// data-api page size (max it accepts) & the deepest offset it serves
+const PAGE_LIMIT: usize = 500;
+const MAX_OFFSET: usize = 10_000;
+
+// Fetch every page of a data-api list endpoint (positions, activity) and return one JSON array.
+// Pages by limit/offset; past MAX_OFFSET timestamped lists continue with end=<oldest seen> as a cursor.
+pub async fn fetch_paginated(
+    client: &Client,
+    url: &str,
+    timeout_ms: u64,
+    retry_limit: u32,
+) -> Result<serde_json::Value> {
+    let sep = if url.contains('?') { '&' } else { '?' };
+    let mut items: Vec<serde_json::Value> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut offset = 0;
+    let mut end: Option<i64> = None;
+
+    loop {
+        let mut page_url = format!("{}{}limit={}&offset={}", url, sep, PAGE_LIMIT, offset);
+        if let Some(end) = end {
+            page_url.push_str(&format!("&end={}", end));
+        }
+        let page = fetch_data(client, &page_url, timeout_ms, retry_limit).await?;
+        let Some(page) = page.as_array() else {
+            // Not a list endpoint - hand back whatever came first
+            return Ok(if items.is_empty() { page } else { serde_json::Value::Array(items) });
+        };
+        let page_len = page.len();
+        let mut new_items = 0;
+        for item in page {
+            // Cursor pages overlap on the boundary timestamp
+            if seen.insert(item.to_string()) {
+                items.push(item.clone());
+                new_items += 1;
+            }
+        }
+        if page_len < PAGE_LIMIT || new_items == 0 {
+            break;
+        }
+
+        offset += PAGE_LIMIT;
+        if offset >= MAX_OFFSET {
+            let oldest = items
+                .iter()
+                .filter_map(|i| i.get("timestamp").and_then(|t| t.as_i64()))
+                .min();
+            match oldest {
+                Some(ts) if Some(ts) != end => {
+                    end = Some(ts);
+                    offset = 0;
+                }
+                _ => {
+                    eoutln!(
+                        "⚠️  {} results truncated at {} items (no timestamp cursor)",
+                        url,
+                        items.len()
+                    );
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(serde_json::Value::Array(items))
+}
@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use super::{hex_u64, rpc_call, Logger};
+use crate::config::EnvConfig;
+
+// Gas token price on the spot API (MATIC was renamed POL in 2024, Binance lists POLUSDT)
+const GAS_TOKEN_SYMBOL: &str = "POLUSDT";
+
+// Polygon gas cost of one on-chain action, in USD
+#[derive(Debug, Clone, Copy)]
+pub struct GasQuote {
+    pub gas_units: u64,
+    pub gas_price_gwei: f64,
+    pub pol_usd: f64,
+    pub cost_usd: f64,
+}
+
+// Gas station "standard" max fee (gwei)
+async fn oracle_gwei(http_client: &reqwest::Client, url: &str) -> Result<f64> {
+    let body: Value = http_client
+        .get(url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    body.get("standard")
+        .and_then(|s| s.get("maxFee"))
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| anyhow!("no standard.maxFee in gas oracle response"))
+}
+
+// GAS_ORACLE_URL, or the RPC's eth_gasPrice when unset / down
+async fn gas_price_gwei(config: &EnvConfig, http_client: &reqwest::Client) -> Result<f64> {
+    if let Some(url) = config.gas_oracle_url.as_deref() {
+        match oracle_gwei(http_client, url).await {
+            Ok(gwei) => return Ok(gwei),
+            Err(e) => Logger::warning(&format!("Gas oracle failed, using eth_gasPrice: {}", e)),
+        }
+    }
+    let wei = rpc_call(http_client, &config.rpc_url, "eth_gasPrice", json!([])).await?;
+    hex_u64(&wei)
+        .map(|wei| wei as f64 / 1e9)
+        .ok_or_else(|| anyhow!("bad eth_gasPrice result: {}", wei))
+}
+
+async fn pol_usd(config: &EnvConfig, http_client: &reqwest::Client) -> Result<f64> {
+    let url = format!(
+        "{}/api/v3/ticker/price?symbol={}",
+        config.gas_price_api.trim_end_matches('/'),
+        GAS_TOKEN_SYMBOL
+    );
+    let body: Value = http_client
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    body.get("price")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|p| *p > 0.0)
+        .ok_or_else(|| anyhow!("no {} price", GAS_TOKEN_SYMBOL))
+}
+
+pub async fn gas_quote(config: &EnvConfig, http_client: &reqwest::Client, gas_units: u64) -> Result<GasQuote> {
+    let gas_price_gwei = gas_price_gwei(config, http_client).await?;
+    let pol_usd = pol_usd(config, http_client).await?;
+    Ok(GasQuote {
+        gas_units,
+        gas_price_gwei,
+        pol_usd,
+        cost_usd: gas_units as f64 * gas_price_gwei * 1e-9 * pol_usd,
+    })
+}
+
+// true = send it: GAS_CHECK off, benefit >= GAS_MIN_BENEFIT_RATIO x gas cost and gas under GAS_MAX_GWEI,
+// or gas couldn't be priced (the check never blocks on its own failure)
+pub async fn gas_worth_it(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    action: &str,
+    gas_units: u64,
+    benefit_usd: f64,
+) -> bool {
+    if !config.gas_check {
+        return true;
+    }
+    let quote = match gas_quote(config, http_client, gas_units).await {
+        Ok(q) => q,
+        Err(e) => {
+            Logger::warning(&format!("Gas check for {} skipped, could not price gas: {}", action, e));
+            return true;
+        }
+    };
+    let too_pricey = config.gas_max_gwei.is_some_and(|max| quote.gas_price_gwei > max);
+    let go = !too_pricey && benefit_usd >= quote.cost_usd * config.gas_min_benefit_ratio;
+    let msg = format!(
+        "Gas check {}: {} - benefit ${:.4} vs gas ${:.4} ({} gas @ {:.1} gwei, POL ${:.3}){}",
+        if go { "ok" } else { "SKIP" },
+        action,
+        benefit_usd,
+        quote.cost_usd,
+        quote.gas_units,
+        quote.gas_price_gwei,
+        quote.pol_usd,
+        if too_pricey { " - above GAS_MAX_GWEI" } else { "" }
+    );
+    if go {
+        Logger::info(&msg);
+    } else {
+        Logger::warning(&msg);
+    }
+    go
+}
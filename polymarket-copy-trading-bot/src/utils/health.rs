@@ -1,10 +1,17 @@
 use anyhow::Result;
 use serde::Serialize;
 
+use super::http::{http_client, HttpPool};
+use super::rate_limit::{rate_limit_gauges, RateLimitGauge};
+use crate::types::DbSelfTest;
+
 #[derive(Debug, Serialize)]
 pub struct HealthCheckResult {
     pub healthy: bool,
     pub checks: HealthChecks,
+    pub rate_limits: Vec<RateLimitGauge>, // Remaining quota per API host seen so far
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_self_test: Option<DbSelfTest>,
     pub timestamp: i64,
 }
 
@@ -31,13 +38,35 @@ pub struct BalanceCheckResult {
 }
 
 pub async fn perform_health_check(
-    db_ok: bool,
+    db: Result<DbSelfTest, anyhow::Error>,
     rpc_url: &str,
     balance: Result<f64, anyhow::Error>,
     polymarket_ok: bool,
 ) -> HealthCheckResult {
-    let db_status = if db_ok { "ok" } else { "error" };
-    let db_msg = if db_ok { "Connected" } else { "Not connected" };
+    // Warnings (standalone server, slow round trips) don't make the bot unhealthy
+    let (db_status, db_msg, db_self_test) = match db {
+        Ok(t) => {
+            let mut msg = format!(
+                "Write/read/delete {} ms, ping {} ms, {}",
+                t.round_trip_ms,
+                t.ping_ms,
+                t.replica_set
+                    .as_deref()
+                    .map(|rs| format!("replica set {}", rs))
+                    .unwrap_or_else(|| "standalone".to_string())
+            );
+            if t.indexes_created > 0 {
+                msg.push_str(&format!(", {} index(es) created", t.indexes_created));
+            }
+            if t.warnings.is_empty() {
+                ("ok", msg, Some(t))
+            } else {
+                msg.push_str(&format!(" - {}", t.warnings.join("; ")));
+                ("warning", msg, Some(t))
+            }
+        }
+        Err(e) => ("error", format!("Self-test failed: {}", e), None),
+    };
 
     let (rpc_status, rpc_msg) = match check_rpc(rpc_url).await {
         Ok(()) => ("ok".to_string(), "RPC endpoint responding".to_string()),
@@ -64,14 +93,14 @@ pub async fn perform_health_check(
     };
 
     let healthy =
-        db_status == "ok" && rpc_status == "ok" && balance_status != "error" && pm_status == "ok";
+        db_status != "error" && rpc_status == "ok" && balance_status != "error" && pm_status == "ok";
 
     HealthCheckResult {
         healthy,
         checks: HealthChecks {
             database: CheckResult {
                 status: db_status.to_string(),
-                message: db_msg.to_string(),
+                message: db_msg,
             },
             rpc: CheckResult {
                 status: rpc_status,
@@ -87,7 +116,9 @@ pub async fn perform_health_check(
                 message: pm_msg.to_string(),
             },
         },
-        timestamp: chrono::Utc::now().timestamp_millis(),
+        rate_limits: rate_limit_gauges(),
+        db_self_test,
+        timestamp: super::clock::now_ms(),
     }
 }
 
@@ -98,7 +129,7 @@ async fn check_rpc(rpc_url: &str) -> Result<()> {
         "params": [],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Rpc);
     let resp = client
         .post(rpc_url)
         .json(&body)
@@ -0,0 +1,102 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::config::EnvConfig;
+
+// Shared HTTP clients, one per kind of endpoint, so every call site reuses warm connections
+// (reqwest pools per host inside a client - a Client::new() per call paid DNS + TCP + TLS every time)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpPool {
+    Rpc, // Polygon JSON-RPC (RPC_URL)
+    Api, // Polymarket REST - data-api, gamma-api, CLOB books / prices - and the webhooks services send through it
+}
+
+impl HttpPool {
+    fn index(self) -> usize {
+        match self {
+            HttpPool::Rpc => 0,
+            HttpPool::Api => 1,
+        }
+    }
+
+    // Whole-request timeout; call sites that pass their own .timeout() override it
+    fn timeout(self, settings: &HttpSettings) -> Duration {
+        match self {
+            HttpPool::Rpc => Duration::from_secs(10),
+            HttpPool::Api => Duration::from_millis(settings.request_timeout_ms),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpSettings {
+    pub request_timeout_ms: u64,
+    pub connect_timeout_ms: u64,
+    pub pool_max_idle: usize, // Idle connections kept per host
+    pub pool_idle_secs: u64, // Idle connections closed after this
+    pub tcp_keepalive_secs: u64, // 0 = off
+    pub https_proxy: Option<String>, // HTTPS_PROXY (NO_PROXY hosts bypass it)
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            request_timeout_ms: 10_000,
+            connect_timeout_ms: 5_000,
+            pool_max_idle: 16,
+            pool_idle_secs: 90,
+            tcp_keepalive_secs: 30,
+            https_proxy: std::env::var("HTTPS_PROXY").ok().filter(|v| !v.trim().is_empty()),
+        }
+    }
+}
+
+impl HttpSettings {
+    pub fn from_config(config: &EnvConfig) -> Self {
+        Self {
+            request_timeout_ms: config.request_timeout_ms,
+            connect_timeout_ms: config.http_connect_timeout_ms,
+            pool_max_idle: config.http_pool_max_idle,
+            pool_idle_secs: config.http_pool_idle_secs,
+            tcp_keepalive_secs: config.http_tcp_keepalive_secs,
+            https_proxy: config.https_proxy.clone(),
+        }
+    }
+}
+
+static SETTINGS: OnceLock<HttpSettings> = OnceLock::new();
+static POOLS: [OnceLock<reqwest::Client>; 2] = [OnceLock::new(), OnceLock::new()];
+
+// Called from EnvConfig::from_env; first call wins (clients are built lazily with whatever is set by then)
+pub fn configure_http(settings: HttpSettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn build(pool: HttpPool, settings: &HttpSettings) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .timeout(pool.timeout(settings))
+        .connect_timeout(Duration::from_millis(settings.connect_timeout_ms))
+        .pool_max_idle_per_host(settings.pool_max_idle)
+        .pool_idle_timeout(Duration::from_secs(settings.pool_idle_secs));
+    if settings.tcp_keepalive_secs > 0 {
+        builder = builder.tcp_keepalive(Duration::from_secs(settings.tcp_keepalive_secs));
+    }
+    if let Some(ref url) = settings.https_proxy {
+        match reqwest::Proxy::https(url) {
+            Ok(proxy) => builder = builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_env())),
+            Err(e) => eoutln!("⚠️  Ignoring HTTPS_PROXY {}: {}", url, e),
+        }
+    }
+    // A bad builder setting must not take the bot down - fall back to reqwest's defaults
+    builder.build().unwrap_or_else(|e| {
+        eoutln!("⚠️  HTTP client setup failed ({}), using defaults", e);
+        reqwest::Client::new()
+    })
+}
+
+// Shared client for one kind of endpoint (cheap: clones share the same pool)
+pub fn http_client(pool: HttpPool) -> reqwest::Client {
+    POOLS[pool.index()]
+        .get_or_init(|| build(pool, SETTINGS.get_or_init(HttpSettings::default)))
+        .clone()
+}
@@ -0,0 +1,42 @@
+use anyhow::{anyhow, Result};
+
+use crate::db::Db;
+use crate::types::LogEvent;
+
+// Shared by the `logs` CLI and the Telegram /logs command
+pub const LOGS_USAGE: &str = "\
+[N] - last N lines (default 50)
+[N] <level,...> - only these levels, e.g. 20 error,warning or 30 trade,order";
+
+const DEFAULT_LINES: i64 = 50;
+const MAX_LINES: i64 = 1_000;
+
+pub fn format_log_event(e: &LogEvent) -> String {
+    let ts = chrono::DateTime::from_timestamp_millis(e.timestamp)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| e.timestamp.to_string());
+    format!("[{}] {}: {}", ts, e.level, e.message)
+}
+
+// Parse "[N] [levels]" (args after the command name) and fetch the matching lines, oldest first
+pub async fn recent_log_lines(db: &Db, args: &[String]) -> Result<Vec<LogEvent>> {
+    let mut limit = DEFAULT_LINES;
+    let mut levels: Vec<String> = Vec::new();
+    for arg in args {
+        match arg.parse::<i64>() {
+            Ok(n) if n > 0 => limit = n.min(MAX_LINES),
+            Ok(_) => return Err(anyhow!("N must be positive")),
+            Err(_) => levels.extend(arg.split(',').map(|l| l.trim().to_string()).filter(|l| !l.is_empty())),
+        }
+    }
+    db.recent_log_events(limit, &levels).await
+}
+
+// Run one logs command - returns a human-readable reply
+pub async fn run_logs_command(db: &Db, args: &[String]) -> Result<String> {
+    let events = recent_log_lines(db, args).await?;
+    if events.is_empty() {
+        return Ok("No log events stored (the bot keeps them while LOG_EVENTS_MAX > 0)".to_string());
+    }
+    Ok(events.iter().map(format_log_event).collect::<Vec<_>>().join("\n"))
+}
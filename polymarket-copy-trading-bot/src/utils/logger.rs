@@ -1,11 +1,54 @@
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::sync::Mutex;
+use tokio::sync::mpsc;
 
 use super::theme::{self, colors, icons};
+use crate::db::Db;
+use crate::engine::{emit, EngineEvent};
+use crate::types::LogEvent;
+
+// Lines waiting for the DB writer (past this they only reach the log file)
+const MAX_PENDING_EVENTS: usize = 2_000;
+// Lines per insert at most
+const MAX_BATCH_EVENTS: usize = 200;
+
+// Set by Logger::persist_to in the bot process; CLIs only log to the console + file.
+// Replaced on every engine start and cleared on stop, so a restarted engine logs to its own DB
+static EVENT_SINK: Mutex<Option<mpsc::Sender<LogEvent>>> = Mutex::new(None);
 
 pub struct Logger;
 
 impl Logger {
+    // Also keep every file-logged line in "log_events" (replayed by `logs` / Telegram /logs)
+    pub fn persist_to(db: &Db) {
+        let (tx, rx) = mpsc::channel(MAX_PENDING_EVENTS);
+        // The previous writer (if any) drains what it has queued and exits once its sender is dropped
+        *EVENT_SINK.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+        tokio::spawn(Self::run_event_writer(db.clone(), rx));
+    }
+
+    // Back to console + file only (engine stopped or failed to start)
+    pub fn stop_persisting() {
+        EVENT_SINK.lock().unwrap_or_else(|e| e.into_inner()).take();
+    }
+
+    async fn run_event_writer(db: Db, mut rx: mpsc::Receiver<LogEvent>) {
+        while let Some(event) = rx.recv().await {
+            let mut batch = vec![event];
+            while batch.len() < MAX_BATCH_EVENTS {
+                match rx.try_recv() {
+                    Ok(event) => batch.push(event),
+                    Err(_) => break,
+                }
+            }
+            if let Err(e) = db.insert_log_events(&batch).await {
+                // File only - going through the sink again would loop on a dead DB
+                Self::append_file(&format!("WARNING: Dropped {} log event(s), DB write failed: {}", batch.len(), e));
+            }
+        }
+    }
+
     fn log_dir() -> std::path::PathBuf {
         std::env::current_dir().unwrap_or_default().join("logs")
     }
@@ -22,7 +65,23 @@ impl Logger {
         }
     }
 
+    // "LEVEL: message" -> log file + DB sink + engine subscribers
     fn write_file(msg: &str) {
+        Self::append_file(msg);
+        let (level, message) = msg.split_once(": ").unwrap_or(("INFO", msg));
+        let event = LogEvent {
+            id: None,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            level: level.to_string(),
+            message: message.to_string(),
+        };
+        if let Some(tx) = EVENT_SINK.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            let _ = tx.try_send(event.clone());
+        }
+        emit(EngineEvent::Log(event));
+    }
+
+    fn append_file(msg: &str) {
         Self::ensure_log_dir();
         if let Ok(mut f) = OpenOptions::new()
             .create(true)
@@ -34,7 +93,7 @@ impl Logger {
     }
 
     pub fn info(msg: &str) {
-        println!(
+        outln!(
             "{} {} {}{} {}",
             colors::ACCENT,
             icons::INFO,
@@ -47,7 +106,7 @@ impl Logger {
     }
 
     pub fn success(msg: &str) {
-        println!(
+        outln!(
             "{} {} {}{} {}",
             colors::SUCCESS,
             icons::OK,
@@ -60,7 +119,7 @@ impl Logger {
     }
 
     pub fn warning(msg: &str) {
-        eprintln!(
+        eoutln!(
             "{} {} {}{} {}",
             colors::WARN,
             icons::WARN,
@@ -73,7 +132,7 @@ impl Logger {
     }
 
     pub fn error(msg: &str) {
-        eprintln!(
+        eoutln!(
             "{} {} {}{} {}",
             colors::ERROR,
             icons::ERR,
@@ -86,7 +145,7 @@ impl Logger {
     }
 
     pub fn separator() {
-        println!("{}{} {}", colors::DIM, "─".repeat(72), colors::RESET);
+        outln!("{}{} {}", colors::DIM, "─".repeat(72), colors::RESET);
         let _ = std::io::stdout().flush();
     }
 
@@ -104,11 +163,11 @@ impl Logger {
             colors::BOX,
             colors::RESET
         );
-        println!();
-        println!("{}", theme::panel_top(width));
-        println!("{}", title_line);
-        println!("{}", theme::panel_bottom(width));
-        println!();
+        outln!();
+        outln!("{}", theme::panel_top(width));
+        outln!("{}", title_line);
+        outln!("{}", theme::panel_bottom(width));
+        outln!();
         let _ = std::io::stdout().flush();
         Self::write_file(&format!("HEADER: {}", title));
     }
@@ -123,27 +182,27 @@ impl Logger {
     }
 
     pub fn startup(traders: &[String], my_wallet: &str) {
-        println!();
+        outln!();
         for (i, line) in theme::BANNER.iter().enumerate() {
             let color = if i < 3 {
                 colors::ACCENT
             } else {
                 colors::HIGHLIGHT
             };
-            println!("{}{}{}", color, line, colors::RESET);
+            outln!("{}{}{}", color, line, colors::RESET);
         }
-        println!(
+        outln!(
             "{}  {} Copy the best. Automate success.{}",
             colors::MUTED,
             icons::ARROW,
             colors::RESET
         );
-        println!();
+        outln!();
         let _ = std::io::stdout().flush();
 
         let width = 68;
-        println!("{}", theme::panel_top(width));
-        println!(
+        outln!("{}", theme::panel_top(width));
+        outln!(
             "{}│{}  {} Alpha sources ({}) {}│{}",
             colors::BOX,
             colors::RESET,
@@ -153,7 +212,7 @@ impl Logger {
             colors::BOX
         );
         for (i, addr) in traders.iter().enumerate() {
-            println!(
+            outln!(
                 "{}│{}    {}. {} {}│{}{}",
                 colors::BOX,
                 colors::RESET,
@@ -173,7 +232,7 @@ impl Logger {
         } else {
             my_wallet.to_string()
         };
-        println!(
+        outln!(
             "{}│{}  {} Your vault {} {}│{}{}",
             colors::BOX,
             colors::RESET,
@@ -183,8 +242,8 @@ impl Logger {
             colors::BOX,
             colors::RESET
         );
-        println!("{}", theme::panel_bottom(width));
-        println!();
+        outln!("{}", theme::panel_bottom(width));
+        outln!();
         let _ = std::io::stdout().flush();
     }
 
@@ -204,12 +263,12 @@ impl Logger {
                 trader_count
             ),
         };
-        print!("\r{}[{}] {}{}  ", colors::MUTED, ts, msg, colors::RESET);
+        out!("\r{}[{}] {}{}  ", colors::MUTED, ts, msg, colors::RESET);
         let _ = std::io::stdout().flush();
     }
 
     pub fn clear_line() {
-        print!("\r{}\r", " ".repeat(100));
+        out!("\r{}\r", " ".repeat(100));
         let _ = std::io::stdout().flush();
     }
 
@@ -218,7 +277,7 @@ impl Logger {
     }
 
     pub fn field(label: &str, value: &str) {
-        println!("  {} {} {} {}", colors::MUTED, label, colors::ACCENT, value);
+        outln!("  {} {} {} {}", colors::MUTED, label, colors::ACCENT, value);
         let _ = std::io::stdout().flush();
     }
 
@@ -228,7 +287,7 @@ impl Logger {
             "warning" => (icons::WARN, colors::WARN),
             _ => (icons::ERR, colors::ERROR),
         };
-        println!(
+        outln!(
             "  {} {} {} {} {} {} {}",
             color,
             icon,
@@ -242,34 +301,34 @@ impl Logger {
     }
 
     pub fn trade(trader_address: &str, action: &str, details: TradeDetails) {
-        println!();
-        println!("{}{}", colors::HIGHLIGHT, "─".repeat(70));
-        println!("{}📊 NEW TRADE DETECTED{}", format!("{}{}", colors::HIGHLIGHT, colors::BOLD), colors::RESET);
-        println!("{}Trader: {}{}", colors::MUTED, Self::format_address(trader_address), colors::RESET);
-        println!("{}Action: {}{}{}", colors::MUTED, colors::RESET, action, colors::RESET);
+        outln!();
+        outln!("{}{}", colors::HIGHLIGHT, "─".repeat(70));
+        outln!("{}📊 NEW TRADE DETECTED{}", format!("{}{}", colors::HIGHLIGHT, colors::BOLD), colors::RESET);
+        outln!("{}Trader: {}{}", colors::MUTED, Self::format_address(trader_address), colors::RESET);
+        outln!("{}Action: {}{}{}", colors::MUTED, colors::RESET, action, colors::RESET);
         if let Some(asset) = &details.asset {
-            println!("{}Asset:  {}{}", colors::MUTED, Self::format_address(asset), colors::RESET);
+            outln!("{}Asset:  {}{}", colors::MUTED, Self::format_address(asset), colors::RESET);
         }
         if let Some(side) = &details.side {
             let side_color = if side == "BUY" { colors::SUCCESS } else { colors::ERROR };
-            println!("{}Side:   {}{}{}{}", colors::MUTED, side_color, colors::BOLD, side, colors::RESET);
+            outln!("{}Side:   {}{}{}{}", colors::MUTED, side_color, colors::BOLD, side, colors::RESET);
         }
         if let Some(amount) = details.amount {
-            println!("{}Amount: {}$ {:.2}{}", colors::MUTED, colors::WARN, amount, colors::RESET);
+            outln!("{}Amount: {}$ {:.2}{}", colors::MUTED, colors::WARN, amount, colors::RESET);
         }
         if let Some(price) = details.price {
-            println!("{}Price:  {}{}{}", colors::MUTED, colors::ACCENT, price, colors::RESET);
+            outln!("{}Price:  {}{}{}", colors::MUTED, colors::ACCENT, price, colors::RESET);
         }
         if let Some(slug) = details.event_slug.or(details.slug) {
             let market_url = format!("https://polymarket.com/event/{}", slug);
-            println!("{}Market: {}{}{}", colors::MUTED, colors::ACCENT, market_url, colors::RESET);
+            outln!("{}Market: {}{}{}", colors::MUTED, colors::ACCENT, market_url, colors::RESET);
         }
         if let Some(tx_hash) = &details.transaction_hash {
             let tx_url = format!("https://polygonscan.com/tx/{}", tx_hash);
-            println!("{}TX:     {}{}{}", colors::MUTED, colors::ACCENT, tx_url, colors::RESET);
+            outln!("{}TX:     {}{}{}", colors::MUTED, colors::ACCENT, tx_url, colors::RESET);
         }
-        println!("{}{}{}", colors::HIGHLIGHT, "─".repeat(70), colors::RESET);
-        println!();
+        outln!("{}{}{}", colors::HIGHLIGHT, "─".repeat(70), colors::RESET);
+        outln!();
         let _ = std::io::stdout().flush();
 
         let mut trade_log = format!("TRADE: {} - {}", Self::format_address(trader_address), action);
@@ -292,15 +351,15 @@ impl Logger {
     }
 
     pub fn balance(my_balance: f64, trader_balance: f64, trader_address: &str) {
-        println!("{}Capital (USDC + Positions):{}", colors::MUTED, colors::RESET);
-        println!(
+        outln!("{}Capital (USDC + Positions):{}", colors::MUTED, colors::RESET);
+        outln!(
             "{}  Your total capital:   {}$ {:.2}{}",
             colors::MUTED,
             format!("{}{}", colors::SUCCESS, colors::BOLD),
             my_balance,
             colors::RESET
         );
-        println!(
+        outln!(
             "{}  Trader total capital: {}$ {:.2} ({}){}",
             colors::MUTED,
             format!("{}{}", colors::ACCENT, colors::BOLD),
@@ -313,7 +372,7 @@ impl Logger {
 
     pub fn order_result(success: bool, message: &str) {
         if success {
-            println!(
+            outln!(
                 "{} {} {}Order executed:{} {}",
                 colors::SUCCESS,
                 icons::OK,
@@ -324,7 +383,7 @@ impl Logger {
             let _ = std::io::stdout().flush();
             Self::write_file(&format!("ORDER SUCCESS: {}", message));
         } else {
-            println!(
+            outln!(
                 "{} {} {}Order failed:{} {}",
                 colors::ERROR,
                 icons::ERR,
@@ -338,11 +397,11 @@ impl Logger {
     }
 
     pub fn db_connection(traders: &[String], counts: &[u64]) {
-        println!();
-        println!("{}📦 Database Status:{}", colors::ACCENT, colors::RESET);
+        outln!();
+        outln!("{}📦 Database Status:{}", colors::ACCENT, colors::RESET);
         for (i, addr) in traders.iter().enumerate() {
             let count = counts.get(i).copied().unwrap_or(0);
-            println!(
+            outln!(
                 "{}   {}: {} {} trades{}",
                 colors::MUTED,
                 Self::format_address(addr),
@@ -351,7 +410,7 @@ impl Logger {
                 colors::RESET
             );
         }
-        println!();
+        outln!();
         let _ = std::io::stdout().flush();
     }
 
@@ -364,24 +423,24 @@ impl Logger {
         initial_value: f64,
         current_balance: f64,
     ) {
-        println!();
-        println!(
+        outln!();
+        outln!(
             "{}💼 YOUR POSITIONS{}",
             format!("{}{}", colors::HIGHLIGHT, colors::BOLD),
             colors::RESET
         );
-        println!("{}   Wallet: {}{}", colors::MUTED, Self::format_address(wallet), colors::RESET);
-        println!();
+        outln!("{}   Wallet: {}{}", colors::MUTED, Self::format_address(wallet), colors::RESET);
+        outln!();
 
         let total_portfolio = current_balance + total_value;
-        println!(
+        outln!(
             "{}   💰 Available Cash:    {}$ {:.2}{}",
             colors::MUTED,
             format!("{}{}", colors::WARN, colors::BOLD),
             current_balance,
             colors::RESET
         );
-        println!(
+        outln!(
             "{}   📊 Total Portfolio:   {}$ {:.2}{}",
             colors::MUTED,
             format!("{}{}", colors::ACCENT, colors::BOLD),
@@ -390,10 +449,10 @@ impl Logger {
         );
 
         if count == 0 {
-            println!("{}   No open positions{}", colors::MUTED, colors::RESET);
+            outln!("{}   No open positions{}", colors::MUTED, colors::RESET);
         } else {
-            println!();
-            println!(
+            outln!();
+            outln!(
                 "{}   📈 Open Positions:    {}{} position{}{}",
                 colors::MUTED,
                 colors::SUCCESS,
@@ -401,14 +460,14 @@ impl Logger {
                 if count > 1 { "s" } else { "" },
                 colors::RESET
             );
-            println!(
+            outln!(
                 "{}      Invested:          {}$ {:.2}{}",
                 colors::MUTED,
                 colors::MUTED,
                 initial_value,
                 colors::RESET
             );
-            println!(
+            outln!(
                 "{}      Current Value:     {}$ {:.2}{}",
                 colors::MUTED,
                 colors::ACCENT,
@@ -421,7 +480,7 @@ impl Logger {
             } else {
                 colors::ERROR
             };
-            println!(
+            outln!(
                 "{}      Profit/Loss:       {}{}{:.1}%{}",
                 colors::MUTED,
                 pnl_color,
@@ -431,7 +490,7 @@ impl Logger {
             );
 
             if !top_positions.is_empty() {
-                println!("{}   🔝 Top Positions:{}", colors::MUTED, colors::RESET);
+                outln!("{}   🔝 Top Positions:{}", colors::MUTED, colors::RESET);
                 for pos in top_positions.iter().take(5) {
                     let percent_pnl = pos
                         .get("percentPnl")
@@ -468,14 +527,14 @@ impl Logger {
                     } else {
                         colors::ERROR
                     };
-                    println!(
+                    outln!(
                         "{}      • {} - {}{}",
                         colors::MUTED,
                         outcome,
                         title_display,
                         colors::RESET
                     );
-                    println!(
+                    outln!(
                         "{}        Value: {}$ {:.2}{} | PnL: {}{}{:.1}%{}",
                         colors::MUTED,
                         colors::ACCENT,
@@ -486,7 +545,7 @@ impl Logger {
                         percent_pnl,
                         colors::RESET
                     );
-                    println!(
+                    outln!(
                         "{}        Bought @ {}{:.1}¢{} | Current @ {}{:.1}¢{}",
                         colors::MUTED,
                         colors::WARN,
@@ -499,7 +558,7 @@ impl Logger {
                 }
             }
         }
-        println!();
+        outln!();
         let _ = std::io::stdout().flush();
     }
 
@@ -509,7 +568,7 @@ impl Logger {
         position_details: &[Vec<serde_json::Value>],
         profitabilities: &[f64],
     ) {
-        println!("{}📈 TRADERS YOU'RE COPYING{}", colors::ACCENT, colors::RESET);
+        outln!("{}📈 TRADERS YOU'RE COPYING{}", colors::ACCENT, colors::RESET);
         for (i, addr) in traders.iter().enumerate() {
             let pos_count = position_counts.get(i).copied().unwrap_or(0);
             let pnl = profitabilities.get(i).copied().unwrap_or(0.0);
@@ -519,7 +578,7 @@ impl Logger {
             } else {
                 colors::ERROR
             };
-            println!(
+            outln!(
                 "{}   {}: {} positions · {}PnL {}{:.1}%{}",
                 colors::MUTED,
                 Self::format_address(addr),
@@ -567,14 +626,14 @@ impl Logger {
                     } else {
                         colors::ERROR
                     };
-                    println!(
+                    outln!(
                         "{}     • {} - {}{}",
                         colors::MUTED,
                         outcome,
                         title_display,
                         colors::RESET
                     );
-                    println!(
+                    outln!(
                         "{}       Value: {}$ {:.2}{} | PnL: {}{}{:.1}%{}",
                         colors::MUTED,
                         colors::ACCENT,
@@ -585,7 +644,7 @@ impl Logger {
                         percent_pnl,
                         colors::RESET
                     );
-                    println!(
+                    outln!(
                         "{}       Bought @ {}{:.1}¢{} | Current @ {}{:.1}¢{}",
                         colors::MUTED,
                         colors::WARN,
@@ -598,7 +657,7 @@ impl Logger {
                 }
             }
         }
-        println!();
+        outln!();
         let _ = std::io::stdout().flush();
     }
 }
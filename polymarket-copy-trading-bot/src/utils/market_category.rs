@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::{fetch_data, Logger};
+use crate::config::EnvConfig;
+use crate::types::{MarketCategory, UserPosition};
+
+// Gamma takes this many condition_ids per request
+const GAMMA_BATCH: usize = 20;
+
+// Gamma tag slugs / labels per category, checked in this order (a "bitcoin" market tagged "trump" too stays crypto)
+const CATEGORY_TAGS: &[(MarketCategory, &[&str])] = &[
+    (
+        MarketCategory::Crypto,
+        &["crypto", "crypto-prices", "bitcoin", "btc", "ethereum", "eth", "solana", "xrp", "defi", "stablecoins", "memecoins", "airdrops"],
+    ),
+    (
+        MarketCategory::Sports,
+        &[
+            "sports", "nba", "nfl", "mlb", "nhl", "wnba", "ncaa", "cfb", "cbb", "soccer", "football", "epl", "premier-league",
+            "champions-league", "la-liga", "serie-a", "bundesliga", "mls", "world-cup", "tennis", "golf", "ufc", "mma", "boxing",
+            "f1", "formula-1", "nascar", "cricket", "rugby", "esports", "olympics", "basketball", "baseball", "hockey",
+        ],
+    ),
+    (
+        MarketCategory::Politics,
+        &[
+            "politics", "us-politics", "elections", "us-elections", "global-elections", "world-elections", "geopolitics",
+            "world-politics", "trump", "trump-presidency", "congress", "senate", "house", "primaries", "supreme-court",
+            "policy", "uk-politics", "middle-east", "ukraine", "government",
+        ],
+    ),
+    (
+        MarketCategory::Entertainment,
+        &[
+            "pop-culture", "culture", "entertainment", "movies", "music", "tv", "awards", "oscars", "grammys", "emmys",
+            "box-office", "celebrities", "gaming", "youtube", "tweet-markets", "mrbeast", "taylor-swift",
+        ],
+    ),
+];
+
+// conditionId (lowercased) -> category; a market's tags don't change, so entries never expire
+static CATEGORIES: OnceLock<Mutex<HashMap<String, MarketCategory>>> = OnceLock::new();
+
+fn categories() -> &'static Mutex<HashMap<String, MarketCategory>> {
+    CATEGORIES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn normalize(s: &str) -> String {
+    s.trim().to_lowercase().replace([' ', '_'], "-")
+}
+
+// Tags first (slug and label), then Gamma's older free-text `category` field; nothing matching = Other
+pub fn classify_market(market: &serde_json::Value) -> MarketCategory {
+    let mut labels: Vec<String> = Vec::new();
+    let tag_lists = market
+        .get("tags")
+        .into_iter()
+        .chain(market.get("events").and_then(|e| e.as_array()).into_iter().flatten().filter_map(|e| e.get("tags")));
+    for tag in tag_lists.filter_map(|t| t.as_array()).flatten() {
+        for key in ["slug", "label"] {
+            if let Some(v) = tag.get(key).and_then(|v| v.as_str()) {
+                labels.push(normalize(v));
+            }
+        }
+    }
+    if let Some(category) = market.get("category").and_then(|v| v.as_str()) {
+        labels.push(normalize(category));
+    }
+    CATEGORY_TAGS
+        .iter()
+        .find(|(_, known)| labels.iter().any(|l| known.contains(&l.as_str())))
+        .map(|(category, _)| *category)
+        .unwrap_or(MarketCategory::Other)
+}
+
+// Categories for these markets: cached ones as is, the rest from Gamma in batches.
+// A market Gamma can't be asked about right now is left out (callers treat it as unknown) and retried next time
+pub async fn market_categories(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    condition_ids: &[String],
+) -> HashMap<String, MarketCategory> {
+    let wanted: Vec<String> = condition_ids.iter().map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty()).collect();
+    let missing: Vec<String> = {
+        let Ok(cache) = categories().lock() else {
+            return HashMap::new();
+        };
+        let mut missing: Vec<String> = wanted.iter().filter(|c| !cache.contains_key(*c)).cloned().collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    };
+    for chunk in missing.chunks(GAMMA_BATCH) {
+        let query = chunk.iter().map(|c| format!("condition_ids={}", c)).collect::<Vec<_>>().join("&");
+        let url = format!("https://gamma-api.polymarket.com/markets?include_tag=true&{}", query);
+        match fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await {
+            Ok(markets) => {
+                let Ok(mut cache) = categories().lock() else {
+                    break;
+                };
+                for market in markets.as_array().into_iter().flatten() {
+                    if let Some(cid) = market.get("conditionId").and_then(|v| v.as_str()) {
+                        cache.insert(cid.to_lowercase(), classify_market(market));
+                    }
+                }
+            }
+            Err(e) => Logger::warning(&format!("Market category lookup failed: {}", e)),
+        }
+    }
+    let Ok(cache) = categories().lock() else {
+        return HashMap::new();
+    };
+    wanted.into_iter().filter_map(|c| cache.get(&c).map(|cat| (c, *cat))).collect()
+}
+
+// One market (None = Gamma couldn't be reached and it isn't cached)
+pub async fn market_category(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    condition_id: &str,
+) -> Option<MarketCategory> {
+    market_categories(config, http_client, &[condition_id.to_string()])
+        .await
+        .into_values()
+        .next()
+}
+
+// What CATEGORY_MAX_USD allows for a buy into one market
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CategoryExposure {
+    Uncapped, // No cap for the market's category (or no caps at all)
+    Capped { category: MarketCategory, cap: f64, used: f64 }, // used = open positions in that category at current value
+    Unknown, // The market couldn't be classified and some category is capped at 0 - the buy must not go ahead
+}
+
+// CATEGORY_MAX_USD for a buy into this market, against my open positions (the executor's snapshot, no extra fetch).
+// Categories come from the process cache (the executor classifies each trade's market before it gets here), so Gamma
+// is only asked about markets not seen yet. A market Gamma can't classify right now fails closed when any category
+// is capped at 0 (it could be one we never copy) and open otherwise; held positions it can't classify count as
+// outside the category
+pub async fn category_exposure(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    condition_id: &str,
+    my_positions: &[UserPosition],
+) -> CategoryExposure {
+    if config.category_max_usd.is_empty() {
+        return CategoryExposure::Uncapped;
+    }
+    let Some(category) = market_category(config, http_client, condition_id).await else {
+        if config.category_max_usd.values().any(|cap| *cap <= 0.0) {
+            return CategoryExposure::Unknown;
+        }
+        Logger::warning("Market category unknown (Gamma unreachable) - CATEGORY_MAX_USD not checked for this buy");
+        return CategoryExposure::Uncapped;
+    };
+    let Some(cap) = config.category_max_usd.get(&category).copied() else {
+        return CategoryExposure::Uncapped;
+    };
+    if cap <= 0.0 {
+        return CategoryExposure::Capped { category, cap, used: 0.0 };
+    }
+
+    let open: Vec<(String, f64)> = my_positions
+        .iter()
+        .filter(|p| p.size.unwrap_or(0.0) > 0.0 && !p.redeemable.unwrap_or(false))
+        .filter_map(|p| Some((p.condition_id.as_deref()?.to_lowercase(), p.current_value.unwrap_or(0.0))))
+        .collect();
+    let held: Vec<String> = open.iter().map(|(cid, _)| cid.clone()).collect();
+    let known = market_categories(config, http_client, &held).await;
+    let used = open
+        .iter()
+        .filter(|(cid, _)| known.get(cid) == Some(&category))
+        .map(|(_, value)| value)
+        .sum();
+    CategoryExposure::Capped { category, cap, used }
+}
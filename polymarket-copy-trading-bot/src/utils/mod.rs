@@ -1,17 +1,79 @@
+mod alerts;
+mod allowance;
+mod backup;
+mod capital;
+mod chain_check;
+mod config_crypto;
 mod create_clob_client;
+mod exposure;
 mod fetch;
+mod gas_check;
 mod health;
+mod http;
+mod log_replay;
 mod logger;
+mod market_category;
+mod override_command;
+mod paper;
 mod post_order;
+mod progress;
+mod rate_limit;
+mod report;
+mod rewards;
+mod signer;
 mod spinner;
+mod tagging;
 pub mod theme;
+mod token_amount;
+mod trade_notes;
+mod trader_resolver;
+mod tuning;
+mod wallet;
+mod wallet_pnl;
 
-pub use create_clob_client::create_clob_client;
-pub use fetch::fetch_data;
+pub use alerts::{raise_alert, Alert, AlertKind, Severity};
+pub use allowance::{approval_signer, approve_usdc_max, encode_call, APPROVE_GAS, POLYMARKET_EXCHANGE};
+pub use backup::{backup_database, restore_database, BackupSummary};
+pub use capital::{build_capital_report, CapitalEfficiency};
+pub use chain_check::{verify_chain, ChainCheck};
+pub use polymarket_clock as clock; // Shared with the arbitrage bot
+pub use config_crypto::{
+    config_cipher, is_encrypted_config_value, is_sensitive_config_key, ConfigCipher,
+};
+pub use create_clob_client::{create_clob_client, resolve_wallet_kind};
+pub use exposure::{release_exposure, set_exposure_gate, ExposureGate, ExposureHold, COPY_STRATEGY};
+pub use fetch::{fetch_data, fetch_paginated};
+pub use gas_check::{gas_quote, gas_worth_it, GasQuote};
 pub use health::perform_health_check;
+pub use http::{configure_http, http_client, HttpPool, HttpSettings};
+pub use log_replay::{format_log_event, recent_log_lines, run_logs_command, LOGS_USAGE};
 pub use logger::{Logger, TradeDetails};
-pub use post_order::post_order;
+pub use market_category::{category_exposure, classify_market, market_categories, market_category, CategoryExposure};
+pub use override_command::{run_override_command, OVERRIDE_USAGE};
+pub use paper::{paper_books, paper_cash, PaperBook, PaperHolding};
+pub(crate) use paper::{paper_wallet, record_paper_fill};
+pub use post_order::{fetch_quote, post_order};
+pub(crate) use post_order::{exit_position, fetch_price_history};
+pub use progress::{ItemResult, ItemStatus, Progress, ProgressSummary};
+pub use rate_limit::{clob_call, rate_limit_gauges, set_clob_gate, ClobGate, RateLimitGauge};
+pub use report::{
+    build_digest, deliver_digest, report_channels, report_delivery_configured, send_digest, CategoryPnl, Digest,
+    DigestPosition, ReportChannel, ReportPeriod, SkipCount,
+};
+pub use rewards::{scan_wallet_inflows, InflowScan};
+pub use signer::{create_signer, BotSigner, RemoteSigner};
+pub(crate) use rewards::{hex_u64, rpc_call, topic_address, usdc_amount, TRANSFER_TOPIC};
 pub use spinner::Spinner;
+pub use tagging::{format_annotation, run_tag_command, TAG_USAGE};
+pub use token_amount::{buy_tokens, from_units, is_dust, sell_size, size_tokens, sweep_dust, to_units};
+pub use trade_notes::spawn_trader_note;
+pub use trader_resolver::{resolve_trader_address, resolve_trader_addresses, TraderResolution};
+pub use tuning::{build_tuning_report, TuningReport, TuningSuggestion};
+pub use wallet::{detect_wallet_kind, WalletKind};
+pub use wallet_pnl::{
+    attribute_fills, fetch_wallet_positions, in_order_window, is_open_position, ActivityKind, CashFlows, PositionTotals,
+    ATTRIBUTION_SLACK_BEFORE_MS,
+};
 
 pub async fn is_contract_address(rpc_url: &str, address: &str) -> anyhow::Result<bool> {
     let addr_trimmed = address.trim().trim_start_matches("0x");
@@ -21,7 +83,7 @@ pub async fn is_contract_address(rpc_url: &str, address: &str) -> anyhow::Result
         "params": [format!("0x{}", addr_trimmed), "latest"],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Rpc);
     let resp = client
         .post(rpc_url)
         .json(&body)
@@ -45,7 +107,7 @@ async fn get_erc20_decimals(rpc_url: &str, contract: &str) -> anyhow::Result<u8>
         "params": [{"to": contract, "data": data}, "latest"],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Rpc);
     let resp = client
         .post(rpc_url)
         .json(&body)
@@ -79,7 +141,7 @@ pub async fn get_erc20_balance(
         "params": [{"to": contract, "data": data}, "latest"],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Rpc);
     let resp = client
         .post(rpc_url)
         .json(&body)
@@ -142,7 +204,7 @@ pub async fn get_erc20_allowance(
         "params": [{"to": contract, "data": data}, "latest"],
         "id": 1
     });
-    let client = reqwest::Client::new();
+    let client = http_client(HttpPool::Rpc);
     let resp = client
         .post(rpc_url)
         .json(&body)
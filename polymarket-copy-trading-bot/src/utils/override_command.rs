@@ -0,0 +1,128 @@
+use anyhow::Result;
+
+use crate::config::{
+    format_market_caps, parse_market_caps, parse_markets, validate_override, OVERRIDABLE_KEYS,
+};
+use crate::db::Db;
+
+// Shared by the `overrides` CLI and the Telegram /override command
+pub const OVERRIDE_USAGE: &str = "\
+list
+set <KEY> <value>
+clear <KEY>
+pause-market <conditionId>
+resume-market <conditionId>
+close-market <conditionId>
+resize-market <conditionId> <usd|off>";
+
+// Current DB value of one override key ("" when unset)
+async fn stored_override(db: &Db, key: &str) -> Result<String> {
+    Ok(db
+        .get_config_overrides(&[key])
+        .await?
+        .into_iter()
+        .next()
+        .map(|(_, v)| v)
+        .unwrap_or_default())
+}
+
+// Add / remove one conditionId in a PAUSED_MARKETS-style list and store it
+async fn update_market_list(db: &Db, key: &str, condition_id: &str, add: bool) -> Result<bool> {
+    let mut markets = parse_markets(&stored_override(db, key).await?);
+    let present = markets.contains(&condition_id.to_string());
+    if add && !present {
+        markets.push(condition_id.to_string());
+    } else if !add && present {
+        markets.retain(|m| m != condition_id);
+    } else {
+        return Ok(false);
+    }
+    let value = markets.join(",");
+    validate_override(key, &value)?;
+    db.set_config(key, &value).await?;
+    Ok(true)
+}
+
+// Run one override command (args after the command name) - returns a human-readable reply
+// FYI: the running bot picks changes up within CONFIG_REFRESH_SECS
+pub async fn run_override_command(db: &Db, args: &[String]) -> Result<String> {
+    let action = args.first().map(|s| s.as_str()).unwrap_or("list");
+    let key = args.get(1).map(|k| k.trim().to_uppercase());
+
+    match (action, key) {
+        ("list", _) => {
+            let overrides = db.get_config_overrides(&OVERRIDABLE_KEYS).await?;
+            if overrides.is_empty() {
+                return Ok(format!(
+                    "No overrides - env values apply\nOverridable: {}",
+                    OVERRIDABLE_KEYS.join(", ")
+                ));
+            }
+            Ok(overrides
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        ("set", Some(key)) if args.len() > 2 => {
+            let value = args[2..].join(" ");
+            validate_override(&key, &value)?;
+            db.set_config(&key, value.trim()).await?;
+            Ok(format!("{}={} (applies on next refresh)", key, value.trim()))
+        }
+        ("clear", Some(key)) => {
+            validate_override(&key, "")?;
+            db.set_config(&key, "").await?;
+            Ok(format!("{} cleared - env value applies on next refresh", key))
+        }
+        ("pause-market", Some(_)) => {
+            let cid = args[1].trim().to_lowercase();
+            update_market_list(db, "PAUSED_MARKETS", &cid, true).await?;
+            Ok(format!("{} paused - new copies in this market are skipped (applies on next refresh)", cid))
+        }
+        ("resume-market", Some(_)) => {
+            let cid = args[1].trim().to_lowercase();
+            let unpaused = update_market_list(db, "PAUSED_MARKETS", &cid, false).await?;
+            let unclosed = update_market_list(db, "CLOSED_MARKETS", &cid, false).await?;
+            if !unpaused && !unclosed {
+                return Ok(format!("{} was not paused or closed", cid));
+            }
+            Ok(format!("{} resumed - copies apply again on next refresh", cid))
+        }
+        ("close-market", Some(_)) => {
+            let cid = args[1].trim().to_lowercase();
+            update_market_list(db, "CLOSED_MARKETS", &cid, true).await?;
+            Ok(format!(
+                "{} closing - new copies skipped and the open position is sold on next refresh",
+                cid
+            ))
+        }
+        ("resize-market", Some(_)) if args.len() > 2 => {
+            let cid = args[1].trim().to_lowercase();
+            let mut caps = parse_market_caps(&stored_override(db, "MARKET_MAX_USD").await?);
+            caps.retain(|(c, _)| *c != cid);
+            let size = args[2].trim();
+            let reply = if size.eq_ignore_ascii_case("off") {
+                format!("{} cap removed - MAX_POSITION_SIZE_USD applies on next refresh", cid)
+            } else {
+                let usd: f64 = size
+                    .trim_start_matches('$')
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Size must be a USD amount or off"))?;
+                if usd < 0.0 {
+                    anyhow::bail!("Size must not be negative");
+                }
+                caps.push((cid.clone(), usd));
+                format!(
+                    "{} capped at ${:.2} - buys are limited and any excess is sold on next refresh",
+                    cid, usd
+                )
+            };
+            let value = format_market_caps(&caps);
+            validate_override("MARKET_MAX_USD", &value)?;
+            db.set_config("MARKET_MAX_USD", &value).await?;
+            Ok(reply)
+        }
+        _ => anyhow::bail!("Usage:\n{}", OVERRIDE_USAGE),
+    }
+}
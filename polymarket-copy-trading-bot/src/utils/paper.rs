@@ -0,0 +1,226 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use tokio::sync::Mutex;
+
+use crate::db::Db;
+use crate::types::{PaperTrade, UserActivity, UserPosition};
+use crate::utils::{clock, Logger};
+
+// The DRY_RUN wallet: paper_trades replayed once, then kept current by every fill this process records
+#[derive(Default)]
+struct PaperWallet {
+    net_usd: f64, // Sells minus buys
+    holdings: BTreeMap<String, PaperHolding>,
+}
+
+impl PaperWallet {
+    fn apply(&mut self, trade: &PaperTrade) {
+        self.net_usd += if trade.side == "SELL" { trade.usdc_size } else { -trade.usdc_size };
+        self.holdings
+            .entry(trade.asset.clone())
+            .or_insert_with(|| PaperHolding {
+                condition_id: trade.condition_id.clone(),
+                asset: trade.asset.clone(),
+                ..Default::default()
+            })
+            .apply(trade);
+    }
+}
+
+static PAPER_WALLET: OnceLock<Mutex<Option<PaperWallet>>> = OnceLock::new();
+
+// DRY_RUN stand-in for clob_client.post_order: the signed order is recorded as filled whole at its own price.
+// Every paper order is FOK at the touch, so this is what a live fill would have been minus queue / latency
+pub(crate) async fn record_paper_fill(db: &Db, fill: PaperTrade) {
+    Logger::info(&format!(
+        "📝 DRY_RUN: {} {:.2} tokens @ ${:.4} (${:.2}) recorded in paper_trades, not posted",
+        fill.side, fill.size, fill.price, fill.usdc_size
+    ));
+    // Held across the insert so a wallet being loaded can't miss this fill
+    let mut wallet = PAPER_WALLET.get_or_init(|| Mutex::new(None)).lock().await;
+    if let Err(e) = db.insert_paper_trade(&fill).await {
+        Logger::warning(&format!("Failed to record paper trade: {}", e));
+        return;
+    }
+    if let Some(wallet) = wallet.as_mut() {
+        wallet.apply(&fill);
+    }
+}
+
+// Paper cash (PAPER_BALANCE_USD net of fills) and positions, shaped like the data API's - paper_trades is read
+// on the first call only
+pub(crate) async fn paper_wallet(db: &Db, start_usd: f64) -> Result<(f64, Vec<UserPosition>)> {
+    let mut guard = PAPER_WALLET.get_or_init(|| Mutex::new(None)).lock().await;
+    if guard.is_none() {
+        let mut wallet = PaperWallet::default();
+        for trade in db.get_paper_trades(None).await? {
+            wallet.apply(&trade);
+        }
+        *guard = Some(wallet);
+    }
+    let wallet = guard.get_or_insert_with(PaperWallet::default);
+    let positions = wallet.holdings.values().filter(|h| h.size > 1e-9).cloned().map(holding_position).collect();
+    Ok((start_usd + wallet.net_usd, positions))
+}
+
+// Tokens of one asset held on paper and what they cost (average cost basis)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperHolding {
+    pub condition_id: String,
+    pub asset: String,
+    pub title: Option<String>,
+    pub outcome: Option<String>,
+    pub size: f64,
+    pub cost_usd: f64,
+}
+
+impl PaperHolding {
+    pub fn avg_price(&self) -> f64 {
+        if self.size > 0.0 {
+            self.cost_usd / self.size
+        } else {
+            0.0
+        }
+    }
+
+    fn apply(&mut self, trade: &PaperTrade) -> f64 {
+        if self.title.is_none() {
+            self.title = trade.title.clone();
+            self.outcome = trade.outcome.clone();
+        }
+        if trade.side == "SELL" {
+            // Sold tokens leave at the average cost; the difference is realized
+            let sold = trade.size.min(self.size);
+            let basis = self.avg_price() * sold;
+            self.size -= sold;
+            self.cost_usd = (self.cost_usd - basis).max(0.0);
+            trade.usdc_size - basis
+        } else {
+            self.size += trade.size;
+            self.cost_usd += trade.usdc_size;
+            0.0
+        }
+    }
+}
+
+// One trader's copies replayed from paper_trades
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaperBook {
+    pub trader: String,
+    pub buys: usize,
+    pub sells: usize,
+    pub bought_usd: f64,
+    pub sold_usd: f64,
+    pub realized_pnl: f64,
+    pub holdings: BTreeMap<String, PaperHolding>, // By asset, open ones only after paper_books
+}
+
+// Paper trades (oldest first) -> one book per trader, sorted by address
+pub fn paper_books(trades: &[PaperTrade]) -> BTreeMap<String, PaperBook> {
+    let mut books: BTreeMap<String, PaperBook> = BTreeMap::new();
+    for trade in trades {
+        let book = books.entry(trade.trader.clone()).or_insert_with(|| PaperBook {
+            trader: trade.trader.clone(),
+            ..Default::default()
+        });
+        if trade.side == "SELL" {
+            book.sells += 1;
+            book.sold_usd += trade.usdc_size;
+        } else {
+            book.buys += 1;
+            book.bought_usd += trade.usdc_size;
+        }
+        let holding = book.holdings.entry(trade.asset.clone()).or_insert_with(|| PaperHolding {
+            condition_id: trade.condition_id.clone(),
+            asset: trade.asset.clone(),
+            ..Default::default()
+        });
+        book.realized_pnl += holding.apply(trade);
+    }
+    for book in books.values_mut() {
+        book.holdings.retain(|_, h| h.size > 1e-9);
+    }
+    books
+}
+
+// One paper holding as a data-API position (every trader's paper fills of the asset together)
+fn holding_position(h: PaperHolding) -> UserPosition {
+    let avg_price = h.avg_price();
+    UserPosition {
+        id: None,
+        proxy_wallet: None,
+        asset: Some(h.asset),
+        condition_id: Some(h.condition_id),
+        size: Some(h.size),
+        avg_price: Some(avg_price),
+        initial_value: Some(h.cost_usd),
+        current_value: Some(h.cost_usd), // At cost - the executor only sizes off it
+        cash_pnl: None,
+        percent_pnl: None,
+        total_bought: None,
+        realized_pnl: None,
+        percent_realized_pnl: None,
+        cur_price: Some(avg_price),
+        redeemable: None,
+        mergeable: None,
+        title: h.title,
+        slug: None,
+        icon: None,
+        event_slug: None,
+        outcome: h.outcome,
+        outcome_index: None,
+        opposite_outcome: None,
+        opposite_asset: None,
+        end_date: None,
+        negative_risk: None,
+    }
+}
+
+// PAPER_BALANCE_USD minus paper buys plus paper sells
+pub fn paper_cash(start_usd: f64, trades: &[PaperTrade]) -> f64 {
+    trades
+        .iter()
+        .fold(start_usd, |cash, t| if t.side == "SELL" { cash + t.usdc_size } else { cash - t.usdc_size })
+}
+
+impl PaperTrade {
+    // `size` tokens at `price` for `trader` (reason: copy, merge, net_opposite)
+    pub fn fill(trader: &str, asset: &str, side: &str, size: f64, price: f64, reason: &str) -> Self {
+        PaperTrade {
+            id: None,
+            trader: trader.to_lowercase(),
+            condition_id: String::new(),
+            asset: asset.to_string(),
+            side: side.to_string(),
+            size,
+            price,
+            usdc_size: size * price,
+            reason: reason.to_string(),
+            source_tx: None,
+            trader_price: None,
+            title: None,
+            outcome: None,
+            timestamp: clock::now_ms(),
+        }
+    }
+
+    // Market + source trade details from the activity being copied
+    pub fn copying(mut self, source: Option<&UserActivity>) -> Self {
+        let Some(trade) = source else {
+            return self;
+        };
+        self.condition_id = trade.condition_id.clone().unwrap_or_default();
+        self.source_tx = trade.transaction_hash.clone();
+        self.title = trade.title.clone();
+        // Netting sells the other outcome - the trader's price and outcome are for theirs
+        if trade.asset.as_deref() == Some(self.asset.as_str()) {
+            self.trader_price = trade.price.filter(|p| *p > 0.0);
+            self.outcome = trade.outcome.clone();
+        }
+        self
+    }
+}
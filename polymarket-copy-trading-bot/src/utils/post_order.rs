@@ -1,20 +1,32 @@
 use anyhow::Result;
-use alloy::signers::local::PrivateKeySigner;
 use polymarket_client_sdk::clob::Client as ClobClient;
 use polymarket_client_sdk::auth::Normal;
 use polymarket_client_sdk::clob::types::{OrderType as SdkOrderType, Amount, Side};
 use polymarket_client_sdk::types::Decimal;
+use polymarket_book::{depth_within, momentum, parse_f64, Book, Level};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::config::{get_trade_multiplier, EnvConfig};
+use crate::config::{get_trade_multiplier, EnvConfig, ExecutionPolicy, MarketLiquidity, OppositeSidePolicy};
 use crate::db::Db;
-use crate::types::{UserActivity, UserPosition};
-use crate::utils::{fetch_data, Logger};
+use crate::types::{BotOrder, PaperTrade, SkipReason, UserActivity, UserPosition};
+use crate::utils::{
+    buy_tokens, category_exposure, clob_call, CategoryExposure, clock, fetch_data, fetch_paginated, is_dust, raise_alert, record_paper_fill, release_exposure, sell_size,
+    size_tokens, sweep_dust, Alert, AlertKind, BotSigner, ExposureHold, Logger,
+};
 
-// Min order sizes (PM API requirements)
-const MIN_ORDER_SIZE_USD: f64 = 1.0;
-const MIN_ORDER_SIZE_TOKENS: f64 = 1.0;
+// Per-token min_order_size from the CLOB's /book - raises MIN_ORDER_SIZE_TOKENS where the market needs more
+static MARKET_MIN_TOKENS: OnceLock<Mutex<HashMap<String, f64>>> = OnceLock::new();
+
+// Trader alpha is recomputed at most this often (one positions crawl per trader)
+const TRADER_ALPHA_TTL: Duration = Duration::from_secs(3600);
+static TRADER_ALPHA: OnceLock<Mutex<HashMap<String, (Instant, Option<f64>)>>> = OnceLock::new();
+
+// Tokens this process sold per asset, kept long enough to cover the data API's position lag
+const SELL_LEDGER_TTL: Duration = Duration::from_secs(300);
+static SELL_LEDGER: OnceLock<Mutex<HashMap<String, Vec<(Instant, f64)>>>> = OnceLock::new();
 
 // Extract error msg from API response (handles nested error objs)
 fn extract_order_error(response: &serde_json::Value) -> Option<String> {
@@ -54,6 +66,12 @@ fn extract_order_error(response: &serde_json::Value) -> Option<String> {
     None
 }
 
+// Add skip reason fields to a status update (same shape as Db::mark_skipped)
+fn tag_skip(update_doc: &mut mongodb::bson::Document, reason: SkipReason) {
+    update_doc.insert("skipReason", reason.as_str());
+    update_doc.insert("skippedAt", mongodb::bson::DateTime::now());
+}
+
 // Check if error is balance/allowance related (retry won't help)
 fn is_insufficient_balance_or_allowance_error(message: Option<&str>) -> bool {
     let Some(msg) = message else {
@@ -63,12 +81,896 @@ fn is_insufficient_balance_or_allowance_error(message: Option<&str>) -> bool {
     lower.contains("not enough balance") || lower.contains("allowance")
 }
 
+fn book_f64(book: &serde_json::Value, key: &str) -> Option<f64> {
+    book.get(key).and_then(parse_f64)
+}
+
+// Ask-side depth (CLOB book) + 24h volume (Gamma) for liquidity scaling - None when scaling is off
+async fn fetch_market_liquidity(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    asset: &str,
+    condition_id: Option<&str>,
+) -> Option<MarketLiquidity> {
+    let strategy = &config.copy_strategy_config;
+    if strategy.max_depth_percent.is_none() && strategy.min_volume_24h_usd.is_none() {
+        return None;
+    }
+
+    let book = Book::from_json(&fetch_book(config, http_client, asset).await.ok()?);
+    let depth_usd = depth_within(&book.asks, strategy.depth_price_range);
+
+    let volume_24h_usd = match (strategy.min_volume_24h_usd, condition_id) {
+        (Some(_), Some(cid)) => {
+            let url = format!("https://gamma-api.polymarket.com/markets?condition_ids={}", cid);
+            fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+                .await
+                .ok()
+                .and_then(|v| v.as_array().and_then(|a| a.first().cloned()))
+                .and_then(|m| book_f64(&m, "volume24hr"))
+        }
+        _ => None,
+    };
+
+    Some(MarketLiquidity {
+        depth_usd,
+        volume_24h_usd,
+    })
+}
+
+// Snapshot time of a /book response (CLOB sends ms as a string)
+fn book_timestamp(book: &serde_json::Value) -> Option<i64> {
+    let ts = book.get("timestamp")?;
+    ts.as_i64().or_else(|| ts.as_str().and_then(|s| s.parse().ok()))
+}
+
+// GET /book for one token, rejecting snapshots older than BOOK_MAX_AGE_MS.
+// A stale book is refetched once with a cache-buster; if that still comes back old
+// (or with the same hash) the caller gets an error instead of stale prices.
+// Remember the market's own minimum order size, warning once when it is above MIN_ORDER_SIZE_TOKENS
+fn note_market_min(config: &EnvConfig, asset: &str, book: &serde_json::Value) {
+    let Some(min) = book_f64(book, "min_order_size").filter(|m| *m > 0.0) else {
+        return;
+    };
+    let cache = MARKET_MIN_TOKENS.get_or_init(|| Mutex::new(HashMap::new()));
+    let Ok(mut guard) = cache.lock() else {
+        return;
+    };
+    let previous = guard.insert(asset.to_string(), min);
+    if previous != Some(min) && min > config.min_order_size_tokens {
+        Logger::warning(&format!(
+            "Market minimum for {}... is {:.2} tokens (MIN_ORDER_SIZE_TOKENS={}) - using the market's",
+            &asset[..asset.len().min(20)],
+            min,
+            config.min_order_size_tokens
+        ));
+    }
+}
+
+// Min tokens per order on this asset: MIN_ORDER_SIZE_TOKENS, or the market's minimum once its book was seen (if higher)
+fn min_order_tokens(config: &EnvConfig, asset: &str) -> f64 {
+    let market = MARKET_MIN_TOKENS
+        .get()
+        .and_then(|c| c.lock().ok().and_then(|g| g.get(asset).copied()))
+        .unwrap_or(0.0);
+    config.min_order_size_tokens.max(market)
+}
+
+async fn fetch_book(config: &EnvConfig, http_client: &reqwest::Client, asset: &str) -> Result<serde_json::Value> {
+    let book = fetch_fresh_book(config, http_client, asset).await?;
+    note_market_min(config, asset, &book);
+    Ok(book)
+}
+
+async fn fetch_fresh_book(config: &EnvConfig, http_client: &reqwest::Client, asset: &str) -> Result<serde_json::Value> {
+    let book_url = format!(
+        "{}/book?token_id={}",
+        config.clob_http_url.trim_end_matches('/'),
+        asset
+    );
+    let book = fetch_data(http_client, &book_url, config.request_timeout_ms, config.network_retry_limit).await?;
+    if config.book_max_age_ms == 0 {
+        return Ok(book);
+    }
+
+    let age_ms = |book: &serde_json::Value| {
+        book_timestamp(book).map(clock::age_ms)
+    };
+    let max_age = config.book_max_age_ms as i64;
+    let Some(age) = age_ms(&book).filter(|a| *a > max_age) else {
+        return Ok(book); // Fresh, or no timestamp to check
+    };
+
+    Logger::warning(&format!("Order book is {}ms old (BOOK_MAX_AGE_MS={}) - refetching", age, max_age));
+    let fresh_url = format!("{}&_={}", book_url, clock::now_ms());
+    let fresh = fetch_data(http_client, &fresh_url, config.request_timeout_ms, config.network_retry_limit).await?;
+    let same_snapshot = book.get("hash").is_some() && fresh.get("hash") == book.get("hash");
+    match age_ms(&fresh) {
+        Some(age) if age > max_age || same_snapshot => Err(anyhow::anyhow!(
+            "Order book for {} is still {}ms old after refetch - not pricing off stale data",
+            asset,
+            age
+        )),
+        _ => Ok(fresh),
+    }
+}
+
+// Best bid/ask for the cost model (and the copy-delay re-check)
+pub async fn fetch_quote(config: &EnvConfig, http_client: &reqwest::Client, asset: &str) -> Option<(f64, f64)> {
+    let book = Book::from_json(&fetch_book(config, http_client, asset).await.ok()?);
+    let best_bid = book.best_bid().map(|l| l.price).unwrap_or(0.0);
+    Some((best_bid, book.best_ask()?.price))
+}
+
+// Trader's historical return on capital: (cash + realized PnL) / cost basis over their positions
+// Redeemed positions drop out of the data API, so this is a rough estimate - clamped to +-100%
+// A TRADER_GROUPS group is scored as one trader over all its wallets
+async fn trader_alpha(config: &EnvConfig, http_client: &reqwest::Client, user_address: &str) -> Option<f64> {
+    let cache = TRADER_ALPHA.get_or_init(|| Mutex::new(HashMap::new()));
+    let key = config.trader_key(user_address);
+    let cached = cache.lock().ok()?.get(&key).copied();
+    if let Some((at, alpha)) = cached {
+        if at.elapsed() < TRADER_ALPHA_TTL {
+            return alpha;
+        }
+    }
+
+    let (mut pnl, mut basis) = (0.0, 0.0);
+    for member in config.group_members(user_address) {
+        let url = format!("https://data-api.polymarket.com/positions?user={}", member);
+        let positions = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+            .await
+            .ok()?;
+        for p in positions.as_array().into_iter().flatten() {
+            pnl += book_f64(p, "cashPnl").unwrap_or(0.0) + book_f64(p, "realizedPnl").unwrap_or(0.0);
+            basis += book_f64(p, "initialValue").unwrap_or(0.0);
+        }
+    }
+    let alpha = (basis >= 1.0).then(|| (pnl / basis).clamp(-1.0, 1.0));
+    if let Ok(mut guard) = cache.lock() {
+        guard.insert(key, (Instant::now(), alpha));
+    }
+    alpha
+}
+
+// CLOB /prices-history for one token as (unix secs, price), 1-minute points
+pub(crate) async fn fetch_price_history(config: &EnvConfig, http_client: &reqwest::Client, asset: &str, start: i64, end: i64) -> Option<Vec<(i64, f64)>> {
+    let url = format!(
+        "{}/prices-history?market={}&startTs={}&endTs={}&fidelity=1",
+        config.clob_http_url.trim_end_matches('/'),
+        asset,
+        start,
+        end
+    );
+    let data = fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await.ok()?;
+    let mut points: Vec<(i64, f64)> = data
+        .get("history")?
+        .as_array()?
+        .iter()
+        .filter_map(|p| Some((p.get("t")?.as_i64()?, parse_f64(p.get("p")?)?)))
+        .collect();
+    points.sort_by_key(|(t, _)| *t);
+    Some(points)
+}
+
+// Optional entry filters (ENTRY_IMBALANCE_FILTER / ENTRY_MOMENTUM_FILTER), each on its own
+// Some = skip the buy; missing data never blocks a copy
+async fn adverse_entry(config: &EnvConfig, http_client: &reqwest::Client, asset: &str) -> Option<(SkipReason, String)> {
+    if !config.entry_imbalance_filter && !config.entry_momentum_filter {
+        return None;
+    }
+    let book = Book::from_json(&fetch_book(config, http_client, asset).await.ok()?);
+
+    // Ask size stacked over bid size near the top = sellers leaning on the market
+    if config.entry_imbalance_filter {
+        let imbalance = book.imbalance(config.entry_imbalance_levels);
+        if imbalance < config.entry_min_imbalance {
+            return Some((
+                SkipReason::AdverseBook,
+                format!(
+                    "Book imbalance {:+.2} over the top {} levels is below ENTRY_MIN_IMBALANCE {:+.2}",
+                    imbalance, config.entry_imbalance_levels, config.entry_min_imbalance
+                ),
+            ));
+        }
+    }
+
+    // Mid now vs the price-history point one lookback ago (fast drop = catching a falling knife)
+    if config.entry_momentum_filter {
+        let mid = book.mid()?;
+        let now = clock::to_secs(clock::now_ms());
+        let lookback = config.entry_momentum_lookback_secs as i64;
+        let history = fetch_price_history(config, http_client, asset, now - lookback - 120, now).await?;
+        let change = momentum(&history, now, lookback, mid)? * 100.0;
+        if change < -config.entry_max_adverse_move_percent {
+            return Some((
+                SkipReason::AdverseMomentum,
+                format!(
+                    "Price fell {:.1}% in the last {}s (ENTRY_MAX_ADVERSE_MOVE_PERCENT {:.1})",
+                    -change, lookback, config.entry_max_adverse_move_percent
+                ),
+            ));
+        }
+    }
+    None
+}
+
+fn decimal_to_f64(d: &Decimal) -> f64 {
+    d.to_string().parse().unwrap_or(0.0)
+}
+
+// Maker price improvement: rest one tick inside the spread, wait, cancel the rest
+// Returns (tokens filled, USDC value) - caller crosses the spread for whatever is left
+// If the cancel fails the order may still be resting, so the whole size counts as committed (no taker fallback)
+async fn try_maker_order(
+    config: &EnvConfig,
+    clob_client: &ClobClient,
+    signer: &BotSigner,
+    http_client: &reqwest::Client,
+    asset: &str,
+    user_address: &str,
+    side: Side,
+    remaining: f64,
+) -> Result<(f64, f64)> {
+    // DRY_RUN always crosses: a resting order's fills can't be paper-traded honestly
+    if config.dry_run {
+        return Ok((0.0, 0.0));
+    }
+    let policy_config = &config.execution_policy_config;
+    let raw_book: serde_json::Value = fetch_book(config, http_client, asset).await?;
+    let book = Book::from_json(&raw_book);
+    let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) else {
+        return Ok((0.0, 0.0));
+    };
+    let (best_bid, best_ask) = (bid.price, ask.price);
+
+    // Liquidity class from depth on the side we would otherwise take
+    let is_buy = matches!(side, Side::Buy);
+    let take_side = if is_buy { &book.asks } else { &book.bids };
+    let depth_usd: f64 = take_side.iter().map(Level::notional).sum();
+    let liquidity = policy_config.classify_liquidity(depth_usd);
+    let policy = policy_config.resolve(user_address, liquidity);
+    if policy != ExecutionPolicy::Maker {
+        return Ok((0.0, 0.0));
+    }
+
+    let spread = best_ask - best_bid;
+    if spread < policy_config.maker_min_spread {
+        Logger::info(&format!(
+            "Spread {:.4} below MAKER_MIN_SPREAD {:.4} - crossing as taker",
+            spread, policy_config.maker_min_spread
+        ));
+        return Ok((0.0, 0.0));
+    }
+
+    let tick = book_f64(&raw_book, "tick_size").unwrap_or(0.01);
+    let price = if is_buy { best_bid + tick } else { best_ask - tick };
+    // Must stay strictly inside the spread (otherwise it would just cross)
+    if price <= best_bid || price >= best_ask || price <= 0.0 || price >= 1.0 {
+        return Ok((0.0, 0.0));
+    }
+    let size = if is_buy { remaining / price } else { remaining };
+    let min_size = min_order_tokens(config, asset);
+    if size < min_size {
+        return Ok((0.0, 0.0));
+    }
+
+    Logger::info(&format!(
+        "🪄 Maker mode ({} liquidity, ${:.0} depth): resting {:.2} tokens @ ${:.4} (bid {:.4} / ask {:.4}), taker fallback in {}s",
+        liquidity.as_str(),
+        depth_usd,
+        size,
+        price,
+        best_bid,
+        best_ask,
+        policy_config.maker_fallback_secs
+    ));
+
+    let token_id = alloy::primitives::U256::from_str_radix(asset.trim_start_matches("0x"), 16)
+        .or_else(|_| alloy::primitives::U256::from_str(asset))?;
+    // Sells in whole lots of what we hold (rounding up would ask for tokens we don't have)
+    let decimal_size = if is_buy {
+        Decimal::from_str(&format!("{:.2}", size)).map_err(|e| anyhow::anyhow!("{}", e))?
+    } else {
+        match sell_size(size, size) {
+            Some(d) => d,
+            None => return Ok((0.0, 0.0)),
+        }
+    };
+    let size = if is_buy { size } else { size_tokens(&decimal_size) };
+    let decimal_price = Decimal::from_str(&format!("{:.4}", price))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let order = clob_client
+        .limit_order()
+        .token_id(token_id)
+        .size(decimal_size)
+        .price(decimal_price)
+        .side(side)
+        .order_type(SdkOrderType::GTC)
+        .build()
+        .await?;
+    let signed = clob_client.sign(signer, order).await?;
+    let resp = clob_call(config, clob_client.post_order(signed)).await?;
+    if !resp.error_msg.as_ref().map(|s| s.is_empty()).unwrap_or(true) {
+        Logger::warning(&format!(
+            "Maker order rejected{} - crossing as taker",
+            resp.error_msg.map(|m| format!(": {}", m)).unwrap_or_default()
+        ));
+        return Ok((0.0, 0.0));
+    }
+    let order_id = resp.order_id.clone();
+
+    // Poll until filled or fallback timeout
+    let deadline = std::time::Instant::now()
+        + std::time::Duration::from_secs(policy_config.maker_fallback_secs);
+    let mut filled = 0.0;
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        if let Ok(open) = clob_call(config, clob_client.order(&order_id)).await {
+            filled = decimal_to_f64(&open.size_matched);
+            if filled + 1e-9 >= size {
+                break;
+            }
+        }
+    }
+
+    if filled + 1e-9 < size {
+        if let Err(e) = clob_call(config, clob_client.cancel_order(&order_id)).await {
+            // Crossing now could fill the same trade twice - leave the rest to the resting order
+            Logger::warning(&format!(
+                "Failed to cancel maker order {}: {} - {:.2}/{:.2} tokens filled, the rest stays resting @ ${:.4} (not crossing)",
+                order_id, e, filled.min(size), size, price
+            ));
+            return Ok((size, size * price));
+        }
+        // Re-read after cancel (fills can land in between)
+        if let Ok(open) = clob_call(config, clob_client.order(&order_id)).await {
+            filled = decimal_to_f64(&open.size_matched);
+        }
+    }
+
+    let filled = filled.min(size);
+    if filled > 0.0 {
+        Logger::order_result(
+            true,
+            &format!(
+                "Maker filled {:.2}/{:.2} tokens @ ${:.4} (saved ${:.4}/token vs crossing)",
+                filled,
+                size,
+                price,
+                if is_buy { best_ask - price } else { price - best_bid }
+            ),
+        );
+    } else {
+        Logger::info("Maker order unfilled - falling back to taker");
+    }
+    Ok((filled, filled * price))
+}
+
+// Resting GTC sell at a limit price - returns order id
+async fn place_limit_sell(
+    config: &EnvConfig,
+    clob_client: &ClobClient,
+    signer: &BotSigner,
+    asset: &str,
+    size: f64,
+    limit_price: f64,
+) -> Result<String> {
+    let token_id = alloy::primitives::U256::from_str_radix(asset.trim_start_matches("0x"), 16)
+        .or_else(|_| alloy::primitives::U256::from_str(asset))?;
+    let decimal_size = sell_size(size, size)
+        .ok_or_else(|| anyhow::anyhow!("{:.6} tokens is less than one 0.01 lot", size))?;
+    // Round up to a cent so we never rest below the limit
+    let decimal_price = Decimal::from_str(&format!("{:.2}", (limit_price * 100.0).ceil() / 100.0))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let order = clob_client
+        .limit_order()
+        .token_id(token_id)
+        .size(decimal_size)
+        .price(decimal_price)
+        .side(Side::Sell)
+        .order_type(SdkOrderType::GTC)
+        .build()
+        .await?;
+    let signed = clob_client.sign(signer, order).await?;
+    let resp = clob_call(config, clob_client.post_order(signed)).await?;
+    if let Some(msg) = resp.error_msg.filter(|m| !m.is_empty()) {
+        anyhow::bail!(msg);
+    }
+    Ok(resp.order_id)
+}
+
+fn record_sell(asset: &str, tokens: f64) {
+    if tokens <= 0.0 {
+        return;
+    }
+    let ledger = SELL_LEDGER.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(mut guard) = ledger.lock() {
+        let sells = guard.entry(asset.to_string()).or_default();
+        sells.retain(|(at, _)| at.elapsed() < SELL_LEDGER_TTL);
+        sells.push((Instant::now(), tokens));
+    }
+}
+
+// Tokens of `asset` sold by any copy run since `since` (this one included)
+fn sold_since(asset: &str, since: Instant) -> f64 {
+    SELL_LEDGER
+        .get()
+        .and_then(|l| l.lock().ok())
+        .and_then(|g| {
+            g.get(asset)
+                .map(|sells| sells.iter().filter(|(at, _)| *at >= since).map(|(_, t)| t).sum())
+        })
+        .unwrap_or(0.0)
+}
+
+// My current size in one outcome from the data API (None = lookup failed, Some(0) = no position)
+async fn fetch_my_position_size(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    condition_id: &str,
+    asset: &str,
+) -> Option<f64> {
+    let url = format!(
+        "https://data-api.polymarket.com/positions?user={}&market={}",
+        config.proxy_wallet, condition_id
+    );
+    let positions = fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await
+        .ok()?;
+    Some(
+        positions
+            .as_array()?
+            .iter()
+            .filter(|p| p.get("asset").and_then(|a| a.as_str()) == Some(asset))
+            .filter_map(|p| p.get("size").and_then(parse_f64))
+            .sum(),
+    )
+}
+
+// What can still be sold right now: the position snapshot minus every sell since it was taken,
+// capped by a fresh data-API read (catches sells made elsewhere; our own recent sells may not show there yet)
+async fn available_to_sell(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    trade: &UserActivity,
+    asset: &str,
+    snapshot_size: f64,
+    snapshot_at: Instant,
+) -> f64 {
+    let local = (snapshot_size - sold_since(asset, snapshot_at)).max(0.0);
+    let fresh = match trade.condition_id.as_deref() {
+        Some(cid) if !config.dry_run => fetch_my_position_size(config, http_client, cid, asset).await,
+        _ => None,
+    };
+    fresh.map(|f| f.min(local)).unwrap_or(local)
+}
+
+// Assets with an exit (escalation or floor watch) running in the background - one per asset, a second would sell the same tokens
+static BACKGROUND_EXITS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn claim_background_exit(asset: &str) -> bool {
+    BACKGROUND_EXITS
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .map(|mut assets| assets.insert(asset.to_string()))
+        .unwrap_or(false)
+}
+
+fn background_exit_running(asset: &str) -> bool {
+    BACKGROUND_EXITS
+        .get()
+        .and_then(|exits| exits.lock().ok().map(|assets| assets.contains(asset)))
+        .unwrap_or(false)
+}
+
+fn release_background_exit(asset: &str) {
+    if let Some(Ok(mut assets)) = BACKGROUND_EXITS.get().map(|exits| exits.lock()) {
+        assets.remove(asset);
+    }
+}
+
+// Escalating exit: GTC sells at widening limits below the reference price,
+// one step per interval (never below the sell floor) - returns tokens sold.
+// Stops at the first failed cancel: that order may still be resting, and the next step would sell the same tokens again
+async fn escalate_exit(
+    config: &EnvConfig,
+    clob_client: &ClobClient,
+    signer: &BotSigner,
+    asset: &str,
+    size: f64,
+    reference_price: f64,
+    floor_price: Option<f64>,
+) -> f64 {
+    let mut sold = 0.0;
+    for step in 1..=config.exit_escalation_steps {
+        let remaining = size - sold;
+        if remaining < min_order_tokens(config, asset) {
+            break;
+        }
+        let discount = step as f64 * config.exit_escalation_step_percent / 100.0;
+        let limit = (reference_price * (1.0 - discount))
+            .max(floor_price.unwrap_or(0.0))
+            .max(0.01);
+        Logger::info(&format!(
+            "Exit escalation {}/{}: {:.2} tokens @ ${:.4} ({:.0}% below ${:.4})",
+            step,
+            config.exit_escalation_steps,
+            remaining,
+            limit,
+            discount * 100.0,
+            reference_price
+        ));
+
+        let order_id = match place_limit_sell(config, clob_client, signer, asset, remaining, limit).await {
+            Ok(id) => id,
+            Err(e) => {
+                Logger::warning(&format!("Exit escalation order failed: {}", e));
+                tokio::time::sleep(std::time::Duration::from_secs(config.exit_escalation_interval_secs)).await;
+                continue;
+            }
+        };
+        tokio::time::sleep(std::time::Duration::from_secs(config.exit_escalation_interval_secs)).await;
+        let cancel_failed = match clob_call(config, clob_client.cancel_order(&order_id)).await {
+            Ok(_) => false,
+            Err(e) => {
+                Logger::warning(&format!(
+                    "Failed to cancel exit order {}: {} - stopping the escalation, the order may still be resting",
+                    order_id, e
+                ));
+                true
+            }
+        };
+        let filled = match clob_call(config, clob_client.order(&order_id)).await {
+            Ok(open) => decimal_to_f64(&open.size_matched).min(remaining),
+            Err(_) => 0.0,
+        };
+        if filled > 0.0 {
+            Logger::order_result(
+                true,
+                &format!("Exit escalation filled {:.2} tokens at ${:.4}", filled, limit),
+            );
+            record_sell(asset, filled);
+            sold += filled;
+        }
+        if cancel_failed {
+            break;
+        }
+    }
+    sold
+}
+
+// Everything an exit escalation needs once the copy run that started it has returned
+struct StuckExit {
+    config: EnvConfig,
+    clob_client: ClobClient,
+    signer: BotSigner,
+    http_client: reqwest::Client,
+    db: Db,
+    user_address: String,
+    trade: UserActivity,
+    asset: String,
+    size: f64,
+    reference_price: f64,
+    floor_price: Option<f64>,
+    avg_price: f64, // Cost basis per token, released from the exposure cap as tokens sell
+}
+
+// Run the escalation as its own task (steps x interval would otherwise hold up the copy queue), then
+// settle what it sold and alert if tokens are still held. False = one is already running for the asset
+fn spawn_exit_escalation(exit: StuckExit) -> bool {
+    if !claim_background_exit(&exit.asset) {
+        return false;
+    }
+    tokio::spawn(async move {
+        let StuckExit { config, clob_client, signer, http_client, db, user_address, trade, asset, size, reference_price, floor_price, avg_price } = exit;
+        let sold = escalate_exit(&config, &clob_client, &signer, &asset, size, reference_price, floor_price).await;
+        release_background_exit(&asset);
+        settle_background_sells(&config, &db, &user_address, &trade, &asset, sold, avg_price).await;
+
+        let left = size - sold;
+        if is_dust(left, min_order_tokens(&config, &asset)) {
+            return;
+        }
+        raise_alert(&config, &http_client, exit_stuck_alert(&config, &user_address, &trade, &asset, left)).await;
+        if let Some(ref id) = trade.id {
+            if let Err(e) = db.update_activity(&user_address, id, &mongodb::bson::doc! { "exitStuckSize": left }).await {
+                Logger::error(&format!("Failed to record stuck exit: {}", e));
+            }
+        }
+    });
+    true
+}
+
+// Tokens a background exit sold: free their cost basis and shrink the tracked purchases
+async fn settle_background_sells(
+    config: &EnvConfig,
+    db: &Db,
+    user_address: &str,
+    trade: &UserActivity,
+    asset: &str,
+    sold: f64,
+    avg_price: f64,
+) {
+    if sold <= 0.0 {
+        return;
+    }
+    release_exposure(sold * avg_price);
+    if let Err(e) = reduce_tracked_buys(config, db, user_address, asset, &trade.condition_id, sold).await {
+        Logger::error(&format!("Failed to update purchase tracking after a background sell: {}", e));
+    }
+}
+
+// A floor limit resting while the book is under the sell floor, re-evaluated every SELL_FLOOR_REEVAL_SECS
+struct FloorWatch {
+    config: EnvConfig,
+    clob_client: ClobClient,
+    signer: BotSigner,
+    http_client: reqwest::Client,
+    db: Db,
+    user_address: String,
+    trade: UserActivity,
+    asset: String,
+    size: f64,
+    floor: f64,
+    order_id: String, // The floor limit placed by the copy run
+    avg_price: f64,
+}
+
+// Re-evaluate the floor limit in its own task: wait, collect fills, cancel the rest, then sell at the bid if it's
+// back above the floor or rest a new limit. A failed cancel ends the watch (that order may still be resting, and
+// a second one would sell the same tokens); after SELL_FLOOR_MAX_REEVALS the last limit is left resting
+fn spawn_floor_watch(watch: FloorWatch) {
+    tokio::spawn(async move {
+        let FloorWatch { config, clob_client, signer, http_client, db, user_address, trade, asset, size, floor, order_id, avg_price } = watch;
+        let min_tokens = min_order_tokens(&config, &asset);
+        let (mut remaining, mut sold) = (size, 0.0);
+        let mut resting = Some(order_id);
+        for reeval in 1..=config.sell_floor_max_reevals {
+            let Some(order_id) = resting.take() else {
+                break;
+            };
+            tokio::time::sleep(std::time::Duration::from_secs(config.sell_floor_reeval_secs)).await;
+            Logger::info(&format!(
+                "Re-evaluating floor sell of {} ({}/{})",
+                trade.title.as_deref().unwrap_or(&asset),
+                reeval,
+                config.sell_floor_max_reevals
+            ));
+            let cancelled = match clob_call(&config, clob_client.cancel_order(&order_id)).await {
+                Ok(_) => true,
+                Err(e) => {
+                    Logger::warning(&format!(
+                        "Failed to cancel floor order {}: {} - leaving it resting, no more re-evaluations",
+                        order_id, e
+                    ));
+                    false
+                }
+            };
+            let filled = match clob_call(&config, clob_client.order(&order_id)).await {
+                Ok(open) => decimal_to_f64(&open.size_matched).min(remaining),
+                Err(_) => 0.0,
+            };
+            if filled > 0.0 {
+                Logger::order_result(true, &format!("Floor limit filled {:.2} tokens at ${:.4}", filled, floor));
+                record_sell(&asset, filled);
+                sold += filled;
+                remaining -= filled;
+            }
+            if !cancelled || remaining < min_tokens {
+                break;
+            }
+
+            // Bid back above the floor: sell at it, then rest whatever is left at the floor again
+            let bid = match fetch_book(&config, &http_client, &asset).await {
+                Ok(book) => Book::from_json(&book).best_bid().map(|l| l.price),
+                Err(_) => None,
+            };
+            if bid.is_some_and(|b| b >= floor) {
+                match sell_at_best_bid(&config, &clob_client, &signer, &http_client, &db, &user_address, Some(&trade), &asset, remaining, Some(floor)).await {
+                    Ok(n) => {
+                        sold += n;
+                        remaining -= n;
+                    }
+                    Err(e) => Logger::warning(&format!("Sell at the bid failed: {}", e)),
+                }
+                if remaining < min_tokens {
+                    break;
+                }
+            }
+            match place_limit_sell(&config, &clob_client, &signer, &asset, remaining, floor).await {
+                Ok(id) => {
+                    Logger::info(&format!("Placed floor limit: {:.2} tokens @ ${:.4}", remaining, floor));
+                    resting = Some(id);
+                }
+                Err(e) => Logger::warning(&format!("Floor limit order failed: {}", e)),
+            }
+        }
+        release_background_exit(&asset);
+        settle_background_sells(&config, &db, &user_address, &trade, &asset, sold, avg_price).await;
+    });
+}
+
+fn exit_stuck_alert(config: &EnvConfig, user_address: &str, trade: &UserActivity, asset: &str, held: f64) -> Alert {
+    Alert::new(
+        AlertKind::LegFailure,
+        format!(
+            "Exit stuck: trader {} sold {} ({}) but {:.2} tokens are still held after {} escalation step(s) - holding",
+            Logger::format_address(user_address),
+            trade.title.as_deref().unwrap_or(asset),
+            trade.outcome.as_deref().unwrap_or("?"),
+            held,
+            config.exit_escalation_steps
+        ),
+    )
+    .key(asset)
+}
+
+// FOK sells at the best bid until `size` is gone, retries run out or the bid drops under `floor` - returns tokens sold
+// (`source` = the trade being copied, for the paper record under DRY_RUN)
+async fn sell_at_best_bid(
+    config: &EnvConfig,
+    clob_client: &ClobClient,
+    signer: &BotSigner,
+    http_client: &reqwest::Client,
+    db: &Db,
+    user_address: &str,
+    source: Option<&UserActivity>,
+    asset: &str,
+    size: f64,
+    floor: Option<f64>,
+) -> Result<f64> {
+    let mut remaining = size;
+    let mut sold = 0.0;
+    let mut retry = 0u32;
+    while remaining >= min_order_tokens(config, asset) && retry < config.retry_limit {
+        let book = Book::from_json(&fetch_book(config, http_client, asset).await?);
+        let Some(Level { price, size: depth }) = book.best_bid() else {
+            Logger::warning("No bids for the opposite side");
+            break;
+        };
+        if floor.is_some_and(|f| price < f) {
+            break;
+        }
+        let Some(decimal_size) = sell_size(remaining.min(depth), remaining) else {
+            break;
+        };
+        let amount = size_tokens(&decimal_size);
+        if amount < min_order_tokens(config, asset) {
+            break;
+        }
+
+        let token_id = alloy::primitives::U256::from_str_radix(asset.trim_start_matches("0x"), 16)
+            .or_else(|_| alloy::primitives::U256::from_str(asset))?;
+        let decimal_price = Decimal::from_str(&format!("{:.2}", price))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let order = clob_client
+            .limit_order()
+            .token_id(token_id)
+            .size(decimal_size)
+            .price(decimal_price)
+            .side(Side::Sell)
+            .order_type(SdkOrderType::FOK)
+            .build()
+            .await?;
+        let signed = clob_client.sign(signer, order).await?;
+        let posted_error = if config.dry_run {
+            let fill = PaperTrade::fill(user_address, asset, "SELL", amount, price, "net_opposite").copying(source);
+            record_paper_fill(db, fill).await;
+            None
+        } else {
+            clob_call(config, clob_client.post_order(signed)).await?.error_msg
+        };
+        match posted_error.filter(|m| !m.is_empty()) {
+            None => {
+                retry = 0;
+                record_sell(asset, amount);
+                sold += amount;
+                remaining -= amount;
+            }
+            Some(msg) => {
+                retry += 1;
+                Logger::warning(&format!(
+                    "Opposite-side sell failed (attempt {}/{}) - {}",
+                    retry, config.retry_limit, msg
+                ));
+            }
+        }
+    }
+    Ok(sold)
+}
+
+// Sell a copied position outside a mirrored trade (trailing stop, close-market): FOK at the best bid, then shrink
+// the trader's tracked buys so a later mirrored sell doesn't try to sell the same tokens again
+pub(crate) async fn exit_position(
+    config: &EnvConfig,
+    clob_client: &ClobClient,
+    signer: &BotSigner,
+    http_client: &reqwest::Client,
+    db: &Db,
+    user_address: &str,
+    position: &UserPosition,
+) -> Result<f64> {
+    // The trailing stop / close-market see the real wallet's positions, which DRY_RUN never trades
+    if config.dry_run {
+        Logger::info("DRY_RUN: position exits (trailing stop, close-market) are not simulated");
+        return Ok(0.0);
+    }
+    let asset = position.asset.clone().unwrap_or_default();
+    let sold = sell_at_best_bid(
+        config,
+        clob_client,
+        signer,
+        http_client,
+        db,
+        user_address,
+        None,
+        &asset,
+        position.size.unwrap_or(0.0),
+        None,
+    )
+    .await?;
+    if sold > 0.0 {
+        reduce_tracked_buys(config, db, user_address, &asset, &position.condition_id, sold).await?;
+    }
+    Ok(sold)
+}
+
+// Copied buys of an asset still held, across every wallet of the trader's group, as (wallet, buy)
+// FYI: a group member may sell what we bought copying another member
+async fn tracked_buys(
+    config: &EnvConfig,
+    db: &Db,
+    user_address: &str,
+    asset: &str,
+    condition_id: &Option<String>,
+) -> Result<Vec<(String, UserActivity)>> {
+    let mut out = Vec::new();
+    for member in config.group_members(user_address) {
+        let buys = db.find_all_buy_activities_for_asset(&member, asset, condition_id).await?;
+        // Paper buys size paper sells only (and live ones live sells)
+        out.extend(
+            buys.into_iter()
+                .filter(|b| b.dry_run.unwrap_or(false) == config.dry_run)
+                .map(|b| (member.clone(), b)),
+        );
+    }
+    Ok(out)
+}
+
+// Shrink tracked purchases of an asset after selling `sold` tokens of it outside a mirrored sell
+async fn reduce_tracked_buys(
+    config: &EnvConfig,
+    db: &Db,
+    user_address: &str,
+    asset: &str,
+    condition_id: &Option<String>,
+    sold: f64,
+) -> Result<()> {
+    let buys = tracked_buys(config, db, user_address, asset, condition_id).await?;
+    let tracked: f64 = buys.iter().filter_map(|(_, b)| b.my_bought_size).sum();
+    if tracked <= 0.0 {
+        return Ok(());
+    }
+    let keep = (1.0 - sold / tracked).max(0.0);
+    for (wallet, buy) in &buys {
+        if let Some(ref id) = buy.id {
+            let new_size = buy.my_bought_size.unwrap_or(0.0) * keep;
+            db.update_activity(wallet, id, &mongodb::bson::doc! { "myBoughtSize": new_size })
+                .await?;
+        }
+    }
+    Ok(())
+}
+
 // Main order router - dispatches to buy/sell/merge strategies
 pub async fn post_order(
     config: &EnvConfig,
     clob_client: &ClobClient,
     condition: &str,
     my_position: Option<&UserPosition>,
+    my_opposite: Option<&UserPosition>,
+    my_positions: &[UserPosition], // All of mine, for CATEGORY_MAX_USD
     user_position: Option<&UserPosition>,
     trade: &UserActivity,
     my_balance: f64,
@@ -76,23 +978,52 @@ pub async fn post_order(
     user_address: &str,
     http_client: &reqwest::Client,
     db: &Db,
-    signer: &mut PrivateKeySigner,
+    signer: &mut BotSigner,
 ) -> Result<()> {
-    match condition {
+    if !matches!(condition, "merge" | "buy" | "sell") {
+        Logger::error(&format!("Unknown condition: {}", condition));
+        return Ok(());
+    }
+    let started_at = clock::now_ms();
+    // Mid at detection for the execution-quality report - read before anything is sent, so our own fills can't move it
+    let asset = trade.asset.clone().unwrap_or_default();
+    let mid_price = fetch_quote(config, http_client, &asset)
+        .await
+        .filter(|(bid, _)| *bid > 0.0)
+        .map(|(bid, ask)| (bid + ask) / 2.0);
+    let result = match condition {
         "merge" => {
-            execute_merge_strategy(config, trade, my_position, user_address, clob_client, http_client, db, signer).await?;
+            execute_merge_strategy(config, trade, my_position, user_address, clob_client, http_client, db, signer).await
         }
         "buy" => {
-            execute_buy_strategy(config, trade, my_position, my_balance, user_address, clob_client, http_client, db, signer).await?;
-        }
-        "sell" => {
-            execute_sell_strategy(config, trade, my_position, user_position, user_address, clob_client, http_client, db, signer).await?;
+            execute_buy_strategy(config, trade, my_position, my_opposite, my_positions, my_balance, user_address, clob_client, http_client, db, signer).await
         }
         _ => {
-            Logger::error(&format!("Unknown condition: {}", condition));
+            execute_sell_strategy(config, trade, my_position, user_position, user_address, clob_client, http_client, db, signer).await
+        }
+    };
+
+    // Order ledger for PnL attribution (recorded even on error - some orders may have filled)
+    let order = BotOrder {
+        id: None,
+        trader: user_address.to_lowercase(),
+        condition_id: trade.condition_id.clone().unwrap_or_default(),
+        asset,
+        side: condition.to_uppercase(),
+        source_tx: trade.transaction_hash.clone(),
+        started_at,
+        finished_at: clock::now_ms(),
+        trader_price: trade.price.filter(|p| *p > 0.0),
+        mid_price,
+        trader_ts: trade.timestamp.map(clock::to_ms),
+    };
+    // DRY_RUN orders never reach the wallet - paper_trades is their ledger
+    if !config.dry_run {
+        if let Err(e) = db.record_bot_order(&order).await {
+            Logger::warning(&format!("Failed to record bot order: {}", e));
         }
     }
-    Ok(())
+    result
 }
 
 // Merge strategy: sell entire position at best bid (FOK orders)
@@ -104,7 +1035,7 @@ async fn execute_merge_strategy(
     clob_client: &ClobClient,
     http_client: &reqwest::Client,
     db: &Db,
-    signer: &mut PrivateKeySigner,
+    signer: &mut BotSigner,
 ) -> Result<()> {
     Logger::info("Executing MERGE strategy...");
     
@@ -114,8 +1045,7 @@ async fn execute_merge_strategy(
         None => {
             Logger::warning("No position to merge");
             if let Some(ref id) = trade.id {
-                db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                    .await?;
+                db.mark_skipped(user_address, id, SkipReason::NoPosition).await?;
             }
             return Ok(());
         }
@@ -125,23 +1055,21 @@ async fn execute_merge_strategy(
     if asset.is_empty() {
         Logger::warning("No asset specified");
         if let Some(ref id) = trade.id {
-            db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                .await?;
+            db.mark_skipped(user_address, id, SkipReason::NoAsset).await?;
         }
         return Ok(());
     }
 
     let mut remaining = my_position.size.unwrap_or(0.0);
 
-    // Skip if position too small (below PM min)
-    if remaining < MIN_ORDER_SIZE_TOKENS {
+    // Skip if position too small (below PM min) - dust, left for redemption
+    if is_dust(remaining, min_order_tokens(config, asset)) {
         Logger::warning(&format!(
             "Position size ({:.2} tokens) too small to merge - skipping",
             remaining
         ));
         if let Some(ref id) = trade.id {
-            db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                .await?;
+            db.mark_skipped(user_address, id, SkipReason::PositionTooSmall).await?;
         }
         return Ok(());
     }
@@ -150,63 +1078,23 @@ async fn execute_merge_strategy(
     let mut abort_due_to_funds = false;
 
         while remaining > 0.0 && retry < config.retry_limit {
-        let book_url = format!(
-            "{}/book?token_id={}",
-            config.clob_http_url.trim_end_matches('/'),
-            asset
-        );
-        let book: serde_json::Value = fetch_data(
-            http_client,
-            &book_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-        
-        let bids = book
-            .get("bids")
-            .and_then(|b| b.as_array())
-            .ok_or_else(|| anyhow::anyhow!("No bids"))?;
+        let book = Book::from_json(&fetch_book(config, http_client, asset).await?);
 
-        if bids.is_empty() {
+        let Some(Level { price, size }) = book.best_bid() else {
             Logger::warning("No bids available in order book");
             if let Some(ref id) = trade.id {
-                db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                    .await?;
+                db.mark_skipped(user_address, id, SkipReason::NoLiquidity).await?;
             }
             break;
-        }
-
-        let best_bid = bids
-            .iter()
-            .filter_map(|b| {
-                let price: f64 = b
-                    .get("price")
-                    .and_then(|p| p.as_str())
-                    .and_then(|s| s.parse().ok())?;
-                let size: f64 = b
-                    .get("size")
-                    .and_then(|s| s.as_str())
-                    .and_then(|s| s.parse().ok())?;
-                Some((price, size))
-            })
-            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-
-        let (price, size) = match best_bid {
-            Some((p, s)) => (p, s),
-            None => {
-                Logger::warning("No bids in order book");
-                break;
-            }
         };
 
         Logger::info(&format!("Best bid: {} @ ${:.4}", size, price));
 
-        let sell_amount = if remaining <= size {
-            remaining
-        } else {
-            size
+        // Whole lots of the exact position size (sub-lot residue is dust, redeemed at resolution)
+        let Some(decimal_size) = sell_size(remaining.min(size), remaining) else {
+            break;
         };
+        let sell_amount = size_tokens(&decimal_size);
 
         let exp_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 90;
         let exp = chrono::DateTime::from_timestamp(exp_secs as i64, 0)
@@ -216,8 +1104,6 @@ async fn execute_merge_strategy(
             16,
         )
         .or_else(|_| alloy::primitives::U256::from_str(&asset))?;
-        let decimal_size = Decimal::from_str(&format!("{:.4}", sell_amount))
-            .map_err(|e| anyhow::anyhow!("{}", e))?;
         let decimal_price =
             Decimal::from_str(&format!("{:.2}", price))
                 .map_err(|e| anyhow::anyhow!("{}", e))?;
@@ -232,11 +1118,16 @@ async fn execute_merge_strategy(
             .build()
             .await?;
         let signed = clob_client.sign(&signer, order).await?;
-        let resp = clob_client.post_order(signed).await?;
-
-        let error_msg = resp.error_msg.as_deref();
+        let posted_error = if config.dry_run {
+            let fill = PaperTrade::fill(user_address, asset, "SELL", sell_amount, price, "merge").copying(Some(trade));
+            record_paper_fill(db, fill).await;
+            None
+        } else {
+            clob_call(config, clob_client.post_order(signed)).await?.error_msg
+        };
+        let error_msg = posted_error.as_deref().filter(|m| !m.is_empty());
 
-        if resp.error_msg.as_ref().map(|s| s.is_empty()).unwrap_or(true) {
+        if error_msg.is_none() {
             retry = 0;
             Logger::order_result(
                 true,
@@ -246,13 +1137,19 @@ async fn execute_merge_strategy(
         } else {
             if is_insufficient_balance_or_allowance_error(error_msg) {
                 abort_due_to_funds = true;
-                Logger::warning(&format!(
-                    "Order rejected: {}",
-                    error_msg.unwrap_or("Insufficient balance or allowance")
-                ));
-                Logger::warning(
-                    "Skipping remaining attempts. Top up funds or check allowance.",
-                );
+                raise_alert(
+                    config,
+                    http_client,
+                    Alert::new(
+                        AlertKind::InsufficientFunds,
+                        format!(
+                            "Order rejected: {} - skipping remaining attempts. Top up funds or check allowance.",
+                            error_msg.unwrap_or("Insufficient balance or allowance")
+                        ),
+                    )
+                    .key("order-rejected"),
+                )
+                .await;
                 break;
             }
             retry += 1;
@@ -269,8 +1166,10 @@ async fn execute_merge_strategy(
         let mut update_doc = mongodb::bson::doc! { "bot": true };
         if abort_due_to_funds {
             update_doc.insert("botExcutedTime", config.retry_limit as i64);
+            tag_skip(&mut update_doc, SkipReason::InsufficientFunds);
         } else if retry >= config.retry_limit {
             update_doc.insert("botExcutedTime", retry as i64);
+            tag_skip(&mut update_doc, SkipReason::RetriesExhausted);
         }
         db.update_activity(user_address, id, &update_doc).await?;
     }
@@ -283,12 +1182,14 @@ async fn execute_buy_strategy(
     config: &EnvConfig,
     trade: &UserActivity,
     my_position: Option<&UserPosition>,
+    my_opposite: Option<&UserPosition>,
+    my_positions: &[UserPosition],
     my_balance: f64,
     user_address: &str,
     clob_client: &ClobClient,
     http_client: &reqwest::Client,
     db: &Db,
-    signer: &mut PrivateKeySigner,
+    signer: &mut BotSigner,
 ) -> Result<()> {
     Logger::info("Executing BUY strategy...");
     Logger::info(&format!("Your balance: ${:.2}", my_balance));
@@ -298,8 +1199,7 @@ async fn execute_buy_strategy(
     if asset.is_empty() {
         Logger::warning("No asset specified");
         if let Some(ref id) = trade.id {
-            db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                .await?;
+            db.mark_skipped(user_address, id, SkipReason::NoAsset).await?;
         }
         return Ok(());
     }
@@ -309,14 +1209,41 @@ async fn execute_buy_strategy(
         .map(|p| p.size.unwrap_or(0.0) * p.avg_price.unwrap_or(0.0))
         .unwrap_or(0.0);
 
-    // Calc order size based on strategy (percentage/fixed/adaptive)
-    let order_calc = crate::config::calculate_order_size(
-        &config.copy_strategy_config,
+    // Liquidity snapshot (only fetched when MAX_DEPTH_PERCENT / MIN_MARKET_VOLUME_24H_USD are set)
+    let liquidity =
+        fetch_market_liquidity(config, http_client, asset, trade.condition_id.as_deref()).await;
+
+    // resize-market cap (MARKET_MAX_USD) tightens the position limit for this market only
+    let mut strategy = config.copy_strategy_config.clone();
+    if let Some(cap) = trade.condition_id.as_deref().and_then(|cid| config.market_cap_usd(cid)) {
+        strategy.max_position_size_usd = Some(strategy.max_position_size_usd.map_or(cap, |max| max.min(cap)));
+    }
+    // Turbulent regime (VOL_REGIME_SOURCE) shrinks the copy until the market calms down
+    strategy.volatility_haircut = crate::services::volatility_haircut();
+    // Drawdown steps (DRAWDOWN_LEVELS, fed by the capital sampler) shrink it until my equity recovers
+    strategy.drawdown_scale = crate::services::drawdown_scale();
+
+    // Calc order size based on strategy (percentage/fixed/adaptive) + liquidity
+    let mut order_calc = crate::config::calculate_order_size(
+        &strategy,
         trade.usdc_size.unwrap_or(0.0),
         my_balance,
         current_position_value,
+        liquidity.as_ref(),
     );
 
+    // Expected execution cost vs trader edge (left out when the book can't be read)
+    if let Some((best_bid, best_ask)) = fetch_quote(config, http_client, asset).await {
+        let alpha = trader_alpha(config, http_client, user_address).await;
+        crate::config::estimate_execution_cost(
+            &mut order_calc,
+            best_bid,
+            best_ask,
+            config.taker_fee_bps / 10_000.0,
+            alpha,
+        );
+    }
+
     Logger::info(&format!("📊 {}", order_calc.reasoning));
 
     // Skip if below min order size
@@ -326,73 +1253,206 @@ async fn execute_buy_strategy(
             Logger::warning("💡 Increase COPY_SIZE or wait for larger trades");
         }
         if let Some(ref id) = trade.id {
-            db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                .await?;
+            db.mark_skipped(user_address, id, SkipReason::BelowMinimum).await?;
+        }
+        return Ok(());
+    }
+
+    // Costs eat the edge: don't pay the spread for a trade expected to lose
+    if let Some(cost) = order_calc.cost.filter(|c| config.require_positive_ev && c.expected_value_usd < 0.0) {
+        Logger::warning(&format!(
+            "❌ Negative expected value after costs (EV ${:.3}) - REQUIRE_POSITIVE_EV=true",
+            cost.expected_value_usd
+        ));
+        if let Some(ref id) = trade.id {
+            db.mark_skipped(user_address, id, SkipReason::NegativeExpectedValue).await?;
+        }
+        return Ok(());
+    }
+
+    // Entry filters: skip buys into a sell-heavy book or a collapsing price
+    if let Some((reason, message)) = adverse_entry(config, http_client, asset).await {
+        Logger::warning(&format!("❌ {} - not copying this buy", message));
+        if let Some(ref id) = trade.id {
+            db.mark_skipped(user_address, id, reason).await?;
         }
         return Ok(());
     }
 
     let mut remaining = order_calc.final_amount;
+
+    // Already holding the other outcome of this market: allow, skip, or net it first
+    if let Some(opposite) = my_opposite
+        .filter(|p| p.size.unwrap_or(0.0) >= min_order_tokens(config, p.asset.as_deref().unwrap_or("")))
+    {
+        let opposite_size = opposite.size.unwrap_or(0.0);
+        let opposite_asset = opposite.asset.as_deref().unwrap_or("");
+        Logger::warning(&format!(
+            "You hold {:.2} tokens of the other outcome ({}) in this market",
+            opposite_size,
+            opposite.outcome.as_deref().unwrap_or("?")
+        ));
+        match config.opposite_side_policy {
+            OppositeSidePolicy::Allow => {
+                Logger::info("OPPOSITE_SIDE_POLICY=allow - buying anyway (both sides held)");
+            }
+            OppositeSidePolicy::Skip => {
+                Logger::warning("OPPOSITE_SIDE_POLICY=skip - not copying this buy");
+                if let Some(ref id) = trade.id {
+                    db.mark_skipped(user_address, id, SkipReason::OppositePosition).await?;
+                }
+                return Ok(());
+            }
+            OppositeSidePolicy::Net if !opposite_asset.is_empty() => {
+                // Selling N of the other side ~ buying N of this side (without locking up both), sized at the
+                // trader's price or else this side's live ask - with neither, there's nothing to size the sale by
+                let price = match trade.price.filter(|p| *p > 0.0 && *p < 1.0) {
+                    Some(p) => Some(p),
+                    None => fetch_quote(config, http_client, asset)
+                        .await
+                        .map(|(_, ask)| ask)
+                        .filter(|p| *p > 0.0 && *p < 1.0),
+                };
+                match price {
+                    None => Logger::warning("OPPOSITE_SIDE_POLICY=net - no valid price to size the netting sale, buying without netting"),
+                    Some(price) => {
+                        let to_net = opposite_size.min(remaining / price);
+                        Logger::info(&format!(
+                            "OPPOSITE_SIDE_POLICY=net - selling {:.2} opposite tokens first",
+                            to_net
+                        ));
+                        let sold = sell_at_best_bid(
+                            config,
+                            clob_client,
+                            signer,
+                            http_client,
+                            db,
+                            user_address,
+                            Some(trade),
+                            opposite_asset,
+                            to_net,
+                            None,
+                        )
+                        .await?;
+                        if sold > 0.0 {
+                            Logger::order_result(true, &format!("Netted {:.2} opposite tokens", sold));
+                            reduce_tracked_buys(config, db, user_address, opposite_asset, &trade.condition_id, sold).await?;
+                            remaining -= sold * price;
+                        }
+                        if remaining < config.copy_strategy_config.min_order_size_usd {
+                            Logger::info("Buy fully netted against the opposite position");
+                            if let Some(ref id) = trade.id {
+                                db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
+                                    .await?;
+                            }
+                            return Ok(());
+                        }
+                        Logger::info(&format!("Buying the rest: ${:.2}", remaining));
+                    }
+                }
+            }
+            OppositeSidePolicy::Net => {}
+        }
+    }
+
+    // CATEGORY_MAX_USD: open positions in this market's category stay under its cap (shrink to the room left)
+    let category_cap = match trade.condition_id.as_deref() {
+        Some(cid) => category_exposure(config, http_client, cid, my_positions).await,
+        None => CategoryExposure::Uncapped,
+    };
+    if category_cap == CategoryExposure::Unknown {
+        Logger::warning("❌ Market category unknown (Gamma unreachable) and a CATEGORY_MAX_USD cap is 0 - not copying this buy");
+        if let Some(ref id) = trade.id {
+            db.mark_skipped(user_address, id, SkipReason::CategoryLimit).await?;
+        }
+        return Ok(());
+    }
+    if let CategoryExposure::Capped { category, cap, used } = category_cap {
+        let room = (cap - used).max(0.0);
+        if room < config.copy_strategy_config.min_order_size_usd {
+            Logger::warning(&format!(
+                "❌ {} positions hold ${:.2} of CATEGORY_MAX_USD ${:.2} - not copying this buy",
+                category.as_str(),
+                used,
+                cap
+            ));
+            if let Some(ref id) = trade.id {
+                db.mark_skipped(user_address, id, SkipReason::CategoryLimit).await?;
+            }
+            return Ok(());
+        }
+        if room < remaining {
+            Logger::info(&format!(
+                "Category cap: buying ${:.2} instead of ${:.2} ({} ${:.2} / ${:.2})",
+                room,
+                remaining,
+                category.as_str(),
+                used,
+                cap
+            ));
+            remaining = room;
+        }
+    }
+
+    // Shared capital cap when the supervisor runs other engines on this wallet
+    let Some(mut exposure) = ExposureHold::reserve(remaining) else {
+        Logger::warning(&format!(
+            "❌ Wallet exposure cap reached - not buying ${:.2}",
+            remaining
+        ));
+        if let Some(ref id) = trade.id {
+            db.mark_skipped(user_address, id, SkipReason::ExposureLimit).await?;
+        }
+        return Ok(());
+    };
+
     let mut available_balance = my_balance;
 
     let mut retry = 0u32;
     let mut abort_due_to_funds = false;
     let mut total_bought_tokens = 0.0;
 
+    // Optional maker attempt (execution policy) before crossing the spread
+    match try_maker_order(
+        config,
+        clob_client,
+        signer,
+        http_client,
+        asset,
+        user_address,
+        Side::Buy,
+        remaining.min(available_balance),
+    )
+    .await
+    {
+        Ok((tokens, usdc)) => {
+            total_bought_tokens += tokens;
+            remaining -= usdc;
+            available_balance -= usdc;
+            exposure.spend(usdc);
+        }
+        Err(e) => Logger::warning(&format!("Maker attempt failed: {} - crossing as taker", e)),
+    }
+
     while remaining > 0.0 && retry < config.retry_limit {
-        let book_url = format!(
-            "{}/book?token_id={}",
-            config.clob_http_url.trim_end_matches('/'),
-            asset
-        );
-        let book: serde_json::Value = fetch_data(
-            http_client,
-            &book_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-        
-        let asks = book
-            .get("asks")
-            .and_then(|a| a.as_array())
-            .ok_or_else(|| anyhow::anyhow!("No asks"))?;
+        let book = Book::from_json(&fetch_book(config, http_client, asset).await?);
 
-        if asks.is_empty() {
+        let Some(Level { price: best_price, size: best_size }) = book.best_ask() else {
             Logger::warning("No asks available in order book");
             if let Some(ref id) = trade.id {
-                db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                    .await?;
+                if total_bought_tokens > 0.0 {
+                    db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
+                        .await?;
+                } else {
+                    db.mark_skipped(user_address, id, SkipReason::NoLiquidity).await?;
+                }
             }
             break;
-        }
-
-        let best_ask = asks
-            .iter()
-            .filter_map(|a| {
-                let price: f64 = a
-                    .get("price")
-                    .and_then(|p| p.as_str())
-                    .and_then(|s| s.parse().ok())?;
-                let size: f64 = a
-                    .get("size")
-                    .and_then(|s| s.as_str())
-                    .and_then(|s| s.parse().ok())?;
-                Some((price, size))
-            })
-            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-
-        let (best_price, best_size) = match best_ask {
-            Some(x) => x,
-            None => {
-                Logger::warning("No asks in order book");
-                break;
-            }
         };
 
         Logger::info(&format!("Best ask: {} @ ${:.4}", best_size, best_price));
 
-        if remaining < MIN_ORDER_SIZE_USD {
+        if remaining < config.copy_strategy_config.min_order_size_usd {
             Logger::info(&format!(
                 "Remaining amount (${:.2}) below minimum - completing trade",
                 remaining
@@ -410,10 +1470,10 @@ async fn execute_buy_strategy(
         let max_order_size = best_size * best_price;
         let order_size = remaining.min(max_order_size);
 
-        if order_size < MIN_ORDER_SIZE_USD {
+        if order_size < config.copy_strategy_config.min_order_size_usd {
             Logger::info(&format!(
                 "Order size (${:.2}) below minimum (${}) - completing trade",
-                order_size, MIN_ORDER_SIZE_USD
+                order_size, config.copy_strategy_config.min_order_size_usd
             ));
             if let Some(ref id) = trade.id {
                 let mut update_doc = mongodb::bson::doc! { "bot": true };
@@ -426,10 +1486,16 @@ async fn execute_buy_strategy(
         }
 
         if available_balance < order_size {
-            Logger::warning(&format!(
-                "Insufficient balance: Need ${:.2} but only have ${:.2}",
-                order_size, available_balance
-            ));
+            raise_alert(
+                config,
+                http_client,
+                Alert::new(
+                    AlertKind::InsufficientFunds,
+                    format!("Insufficient balance: Need ${:.2} but only have ${:.2}", order_size, available_balance),
+                )
+                .key("balance"),
+            )
+            .await;
             abort_due_to_funds = true;
             break;
         }
@@ -460,13 +1526,20 @@ async fn execute_buy_strategy(
             .build()
             .await?;
         let signed = clob_client.sign(&signer, order).await?;
-        let resp = clob_client.post_order(signed).await?;
-
-        let error_msg = resp.error_msg.as_deref();
+        // Whole lots of tokens for the cent-rounded amount - what the order asks for, not amount / price
+        let order_tokens = buy_tokens(size_tokens(&decimal_amount), best_price);
+        let posted_error = if config.dry_run {
+            let fill = PaperTrade::fill(user_address, asset, "BUY", order_tokens, best_price, "copy").copying(Some(trade));
+            record_paper_fill(db, fill).await;
+            None
+        } else {
+            clob_call(config, clob_client.post_order(signed)).await?.error_msg
+        };
+        let error_msg = posted_error.as_deref().filter(|m| !m.is_empty());
 
-        if resp.error_msg.as_ref().map(|s| s.is_empty()).unwrap_or(true) {
+        if error_msg.is_none() {
             retry = 0;
-            let tokens_bought = order_size / best_price;
+            let tokens_bought = order_tokens;
             total_bought_tokens += tokens_bought;
             Logger::order_result(
                 true,
@@ -477,16 +1550,23 @@ async fn execute_buy_strategy(
             );
             remaining -= order_size;
             available_balance -= order_size;
+            exposure.spend(order_size);
         } else {
             if is_insufficient_balance_or_allowance_error(error_msg) {
                 abort_due_to_funds = true;
-                Logger::warning(&format!(
-                    "Order rejected: {}",
-                    error_msg.unwrap_or("Insufficient balance or allowance")
-                ));
-                Logger::warning(
-                    "Skipping remaining attempts. Top up funds or check allowance.",
-                );
+                raise_alert(
+                    config,
+                    http_client,
+                    Alert::new(
+                        AlertKind::InsufficientFunds,
+                        format!(
+                            "Order rejected: {} - skipping remaining attempts. Top up funds or check allowance.",
+                            error_msg.unwrap_or("Insufficient balance or allowance")
+                        ),
+                    )
+                    .key("order-rejected"),
+                )
+                .await;
                 break;
             }
             retry += 1;
@@ -508,6 +1588,10 @@ async fn execute_buy_strategy(
         }
         if total_bought_tokens > 0.0 {
             update_doc.insert("myBoughtSize", total_bought_tokens);
+        } else if abort_due_to_funds {
+            tag_skip(&mut update_doc, SkipReason::InsufficientFunds);
+        } else if retry >= config.retry_limit {
+            tag_skip(&mut update_doc, SkipReason::RetriesExhausted);
         }
         db.update_activity(user_address, id, &update_doc).await?;
     }
@@ -531,17 +1615,18 @@ async fn execute_sell_strategy(
     clob_client: &ClobClient,
     http_client: &reqwest::Client,
     db: &Db,
-    signer: &mut PrivateKeySigner,
+    signer: &mut BotSigner,
 ) -> Result<()> {
     Logger::info("Executing SELL strategy...");
+    // my_position was fetched before this run - later sells (ours or concurrent) are subtracted from it
+    let snapshot_at = Instant::now();
 
     let my_position = match my_position {
         Some(p) => p,
         None => {
             Logger::warning("No position to sell");
             if let Some(ref id) = trade.id {
-                db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                    .await?;
+                db.mark_skipped(user_address, id, SkipReason::NoPosition).await?;
             }
             return Ok(());
         }
@@ -551,18 +1636,15 @@ async fn execute_sell_strategy(
     if asset.is_empty() {
         Logger::warning("No asset specified");
         if let Some(ref id) = trade.id {
-            db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                .await?;
+            db.mark_skipped(user_address, id, SkipReason::NoAsset).await?;
         }
         return Ok(());
     }
 
-    let previous_buys = db
-        .find_all_buy_activities_for_asset(user_address, asset, &trade.condition_id)
-        .await?;
+    let previous_buys = tracked_buys(config, db, user_address, asset, &trade.condition_id).await?;
     let total_bought_tokens: f64 = previous_buys
         .iter()
-        .filter_map(|t| t.my_bought_size)
+        .filter_map(|(_, t)| t.my_bought_size)
         .sum();
 
     if total_bought_tokens > 0.0 {
@@ -633,90 +1715,160 @@ async fn execute_sell_strategy(
         calculated
     };
 
-    if remaining < MIN_ORDER_SIZE_TOKENS {
+    if remaining < min_order_tokens(config, asset) {
         Logger::warning(&format!(
             "❌ Cannot execute: Sell amount {:.2} tokens below minimum ({:.2} token)",
-            remaining, MIN_ORDER_SIZE_TOKENS
+            remaining, min_order_tokens(config, asset)
         ));
         Logger::warning("💡 This happens when position sizes are too small or mismatched");
         if let Some(ref id) = trade.id {
-            db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                .await?;
+            db.mark_skipped(user_address, id, SkipReason::BelowMinimum).await?;
         }
         return Ok(());
     }
 
-    if remaining > my_position.size.unwrap_or(0.0) {
+    let snapshot_size = my_position.size.unwrap_or(0.0);
+    let available = available_to_sell(config, http_client, trade, asset, snapshot_size, snapshot_at).await;
+    if remaining > available {
         Logger::warning(&format!(
             "⚠️  Calculated sell {:.2} tokens > Your position {:.2} tokens",
-            remaining,
-            my_position.size.unwrap_or(0.0)
+            remaining, available
         ));
-        Logger::warning(&format!(
-            "Capping to maximum available: {:.2} tokens",
-            my_position.size.unwrap_or(0.0)
+        Logger::warning(&format!("Capping to maximum available: {:.2} tokens", available));
+        remaining = available;
+    }
+    let swept = sweep_dust(remaining, available, min_order_tokens(config, asset));
+    if swept > remaining {
+        Logger::info(&format!(
+            "🧹 Selling the whole {:.4} tokens - the {:.4} left over would be below the market minimum ({:.2})",
+            swept,
+            swept - remaining,
+            min_order_tokens(config, asset)
         ));
-        remaining = my_position.size.unwrap_or(0.0);
+        remaining = swept;
     }
 
     let mut retry = 0u32;
     let mut abort_due_to_funds = false;
     let mut total_sold_tokens = 0.0;
+    let mut no_bids = false;
+    let mut floor_order_resting = false;
+    let mut last_bid: Option<f64> = None;
+
+    // Optional maker attempt (execution policy) before crossing the spread
+    match try_maker_order(
+        config,
+        clob_client,
+        signer,
+        http_client,
+        asset,
+        user_address,
+        Side::Sell,
+        remaining,
+    )
+    .await
+    {
+        Ok((tokens, _)) => {
+            record_sell(asset, tokens);
+            total_sold_tokens += tokens;
+            remaining -= tokens;
+        }
+        Err(e) => Logger::warning(&format!("Maker attempt failed: {} - crossing as taker", e)),
+    }
 
     while remaining > 0.0 && retry < config.retry_limit {
-        let book_url = format!(
-            "{}/book?token_id={}",
-            config.clob_http_url.trim_end_matches('/'),
-            asset
-        );
-        let book: serde_json::Value = fetch_data(
-            http_client,
-            &book_url,
-            config.request_timeout_ms,
-            config.network_retry_limit,
-        )
-        .await?;
-        
-        let bids = book
-            .get("bids")
-            .and_then(|b| b.as_array())
-            .ok_or_else(|| anyhow::anyhow!("No bids"))?;
+        // Re-check what's left before every order (another copy run may have sold the same tokens)
+        if total_sold_tokens > 0.0 || retry > 0 {
+            let available = available_to_sell(config, http_client, trade, asset, snapshot_size, snapshot_at).await;
+            if remaining > available + 1e-9 {
+                Logger::warning(&format!(
+                    "Position shrank while selling: capping {:.2} -> {:.2} tokens",
+                    remaining, available
+                ));
+                remaining = available;
+                if remaining <= 0.0 {
+                    break;
+                }
+            }
+        }
+
+        let book = Book::from_json(&fetch_book(config, http_client, asset).await?);
 
-        if bids.is_empty() {
+        let Some(Level { price, size }) = book.best_bid() else {
             Logger::warning("No bids available in order book");
-            if let Some(ref id) = trade.id {
-                db.update_activity(user_address, id, &mongodb::bson::doc! { "bot": true })
-                    .await?;
-            }
+            no_bids = true;
             break;
-        }
+        };
+        last_bid = Some(price);
 
-        let best_bid = bids
-            .iter()
-            .filter_map(|b| {
-                let price: f64 = b
-                    .get("price")
-                    .and_then(|p| p.as_str())
-                    .and_then(|s| s.parse().ok())?;
-                let size: f64 = b
-                    .get("size")
-                    .and_then(|s| s.as_str())
-                    .and_then(|s| s.parse().ok())?;
-                Some((price, size))
-            })
-            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-
-        let (price, size) = match best_bid {
-            Some((p, s)) => (p, s),
-            None => {
-                Logger::warning("No bids in order book");
+        Logger::info(&format!("Best bid: {} @ ${:.4}", size, price));
+
+        // Price floor: don't dump into a collapsed book far below the trader's price
+        let floor_price = trade
+            .price
+            .filter(|p| *p > 0.0 && config.min_sell_price_ratio > 0.0)
+            .map(|p| p * config.min_sell_price_ratio);
+        if let Some(floor) = floor_price.filter(|f| price < *f) {
+            Logger::warning(&format!(
+                "Best bid ${:.4} is below floor ${:.4} ({:.0}% of trader's ${:.4}) - refusing to dump",
+                price,
+                floor,
+                config.min_sell_price_ratio * 100.0,
+                trade.price.unwrap_or(0.0)
+            ));
+            if remaining < min_order_tokens(config, asset) {
                 break;
             }
-        };
+            if config.dry_run {
+                Logger::info("DRY_RUN: floor limit not simulated - holding the rest");
+                floor_order_resting = true;
+                break;
+            }
+            // A floor watch / escalation already holds sells for these tokens - don't rest a second order
+            if background_exit_running(asset) {
+                Logger::info("A background exit is already selling this asset - holding the rest");
+                floor_order_resting = true;
+                break;
+            }
+            let order_id =
+                match place_limit_sell(config, clob_client, signer, asset, remaining, floor).await {
+                    Ok(id) => id,
+                    Err(e) => {
+                        Logger::warning(&format!("Floor limit order failed: {}", e));
+                        break;
+                    }
+                };
+            Logger::info(&format!(
+                "Placed floor limit: {:.2} tokens @ ${:.4}",
+                remaining, floor
+            ));
 
-        Logger::info(&format!("Best bid: {} @ ${:.4}", size, price));
+            // The limit rests either way; with a schedule it's re-evaluated in the background
+            floor_order_resting = true;
+            if config.sell_floor_reeval_secs > 0 && config.sell_floor_max_reevals > 0 && claim_background_exit(asset) {
+                Logger::info(&format!(
+                    "Re-evaluating every {}s (up to {} times)",
+                    config.sell_floor_reeval_secs, config.sell_floor_max_reevals
+                ));
+                spawn_floor_watch(FloorWatch {
+                    config: config.clone(),
+                    clob_client: clob_client.clone(),
+                    signer: signer.clone(),
+                    http_client: http_client.clone(),
+                    db: db.clone(),
+                    user_address: user_address.to_string(),
+                    trade: trade.clone(),
+                    asset: asset.to_string(),
+                    size: remaining,
+                    floor,
+                    order_id,
+                    avg_price: my_position.avg_price.unwrap_or(0.0),
+                });
+            }
+            break;
+        }
 
-        if remaining < MIN_ORDER_SIZE_TOKENS {
+        if remaining < min_order_tokens(config, asset) {
             Logger::info(&format!(
                 "Remaining amount ({:.2} tokens) below minimum - completing trade",
                 remaining
@@ -728,9 +1880,11 @@ async fn execute_sell_strategy(
             break;
         }
 
-        let sell_amount = remaining.min(size);
+        // Whole lots, never more than what's left of the position
+        let decimal_size = sell_size(remaining.min(size), remaining).unwrap_or_default();
+        let sell_amount = size_tokens(&decimal_size);
 
-        if sell_amount < MIN_ORDER_SIZE_TOKENS {
+        if sell_amount < min_order_tokens(config, asset) {
             Logger::info(&format!(
                 "Order amount ({:.2} tokens) below minimum - completing trade",
                 sell_amount
@@ -750,8 +1904,6 @@ async fn execute_sell_strategy(
             16,
         )
         .or_else(|_| alloy::primitives::U256::from_str(&asset))?;
-        let decimal_size = Decimal::from_str(&format!("{:.4}", sell_amount))
-            .map_err(|e| anyhow::anyhow!("{}", e))?;
         let decimal_price =
             Decimal::from_str(&format!("{:.2}", price))
                 .map_err(|e| anyhow::anyhow!("{}", e))?;
@@ -766,12 +1918,18 @@ async fn execute_sell_strategy(
             .build()
             .await?;
         let signed = clob_client.sign(&signer, order).await?;
-        let resp = clob_client.post_order(signed).await?;
-
-        let error_msg = resp.error_msg.as_deref();
+        let posted_error = if config.dry_run {
+            let fill = PaperTrade::fill(user_address, asset, "SELL", sell_amount, price, "copy").copying(Some(trade));
+            record_paper_fill(db, fill).await;
+            None
+        } else {
+            clob_call(config, clob_client.post_order(signed)).await?.error_msg
+        };
+        let error_msg = posted_error.as_deref().filter(|m| !m.is_empty());
 
-        if resp.error_msg.as_ref().map(|s| s.is_empty()).unwrap_or(true) {
+        if error_msg.is_none() {
             retry = 0;
+            record_sell(asset, sell_amount);
             total_sold_tokens += sell_amount;
             Logger::order_result(
                 true,
@@ -781,13 +1939,19 @@ async fn execute_sell_strategy(
         } else {
             if is_insufficient_balance_or_allowance_error(error_msg) {
                 abort_due_to_funds = true;
-                Logger::warning(&format!(
-                    "Order rejected: {}",
-                    error_msg.unwrap_or("Insufficient balance or allowance")
-                ));
-                Logger::warning(
-                    "Skipping remaining attempts. Top up funds or check allowance.",
-                );
+                raise_alert(
+                    config,
+                    http_client,
+                    Alert::new(
+                        AlertKind::InsufficientFunds,
+                        format!(
+                            "Order rejected: {} - skipping remaining attempts. Top up funds or check allowance.",
+                            error_msg.unwrap_or("Insufficient balance or allowance")
+                        ),
+                    )
+                    .key("order-rejected"),
+                )
+                .await;
                 break;
             }
             retry += 1;
@@ -800,27 +1964,82 @@ async fn execute_sell_strategy(
         }
     }
 
+    // Trader is out but we're still holding: escalate (in the background), then hold + alert instead of giving up silently
+    let mut exit_stuck = false;
+    let mut escalating = false;
+    if remaining >= min_order_tokens(config, asset)
+        && !abort_due_to_funds
+        && !floor_order_resting
+        && (no_bids || retry >= config.retry_limit)
+    {
+        let floor_price = trade
+            .price
+            .filter(|p| *p > 0.0 && config.min_sell_price_ratio > 0.0)
+            .map(|p| p * config.min_sell_price_ratio);
+        let reference_price = last_bid.or(trade.price).unwrap_or(0.0);
+        remaining = remaining.min(available_to_sell(config, http_client, trade, asset, snapshot_size, snapshot_at).await);
+        // Resting limits aren't simulated under DRY_RUN
+        if !config.dry_run
+            && config.exit_escalation_steps > 0
+            && reference_price > 0.0
+            && remaining >= min_order_tokens(config, asset)
+        {
+            escalating = spawn_exit_escalation(StuckExit {
+                config: config.clone(),
+                clob_client: clob_client.clone(),
+                signer: signer.clone(),
+                http_client: http_client.clone(),
+                db: db.clone(),
+                user_address: user_address.to_string(),
+                trade: trade.clone(),
+                asset: asset.to_string(),
+                size: remaining,
+                reference_price,
+                floor_price,
+                avg_price: my_position.avg_price.unwrap_or(0.0),
+            });
+            if escalating {
+                Logger::info(&format!(
+                    "Exit escalation started for {:.2} tokens ({} step(s), every {}s)",
+                    remaining, config.exit_escalation_steps, config.exit_escalation_interval_secs
+                ));
+            } else {
+                Logger::info("An exit escalation is already running for this asset - leaving it to that one");
+            }
+        }
+
+        if !escalating && !is_dust(remaining, min_order_tokens(config, asset)) {
+            exit_stuck = true;
+            raise_alert(config, http_client, exit_stuck_alert(config, user_address, trade, asset, remaining)).await;
+        }
+    }
+
+    // Sold tokens free their cost basis under the shared cap
+    release_exposure(total_sold_tokens * my_position.avg_price.unwrap_or(0.0));
+
     if total_sold_tokens > 0.0 && total_bought_tokens > 0.0 {
         let sell_percentage = total_sold_tokens / total_bought_tokens;
 
         if sell_percentage >= 0.99 {
-            db.update_many_activities(
-                user_address,
-                asset,
-                &trade.condition_id,
-                &mongodb::bson::doc! { "myBoughtSize": 0.0 },
-            )
-            .await?;
+            for member in config.group_members(user_address) {
+                db.update_many_activities(
+                    &member,
+                    asset,
+                    &trade.condition_id,
+                    &mongodb::bson::doc! { "myBoughtSize": 0.0 },
+                )
+                .await?;
+            }
             Logger::info(&format!(
                 "🧹 Cleared purchase tracking (sold {:.1}% of position)",
                 sell_percentage * 100.0
             ));
         } else {
-            for buy in &previous_buys {
+            for (wallet, buy) in &previous_buys {
                 if let Some(ref id) = buy.id {
                     let new_size = (buy.my_bought_size.unwrap_or(0.0)) * (1.0 - sell_percentage);
                     db.update_activity(
-                        user_address,
+                        wallet,
                         id,
                         &mongodb::bson::doc! { "myBoughtSize": new_size },
                     )
@@ -841,6 +2060,19 @@ async fn execute_sell_strategy(
         } else if retry >= config.retry_limit {
             update_doc.insert("botExcutedTime", retry as i64);
         }
+        if exit_stuck {
+            update_doc.insert("exitStuckSize", remaining);
+        }
+        // Only a skip if nothing was sold at all (and no escalation is still working the exit)
+        if total_sold_tokens == 0.0 && !escalating {
+            if abort_due_to_funds {
+                tag_skip(&mut update_doc, SkipReason::InsufficientFunds);
+            } else if no_bids {
+                tag_skip(&mut update_doc, SkipReason::NoLiquidity);
+            } else if retry >= config.retry_limit {
+                tag_skip(&mut update_doc, SkipReason::RetriesExhausted);
+            }
+        }
         db.update_activity(user_address, id, &update_doc).await?;
     }
 
@@ -0,0 +1,190 @@
+use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::time::Instant;
+
+use super::spinner::Spinner;
+use super::theme::{colors, icons};
+
+const BAR_WIDTH: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemStatus {
+    Ok,
+    Skipped,
+    Failed,
+}
+
+// Outcome of one unit of work (a position sold, a market redeemed, a trader backfilled ...)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemResult {
+    pub name: String,
+    pub status: ItemStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressSummary {
+    pub operation: String,
+    pub total: usize,
+    pub ok: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub usd: f64,
+    pub elapsed_ms: u64,
+    pub items: Vec<ItemResult>,
+}
+
+// Progress + audit trail for maintenance binaries: a live bar on stderr (TTY only), one line per item,
+// and a final summary table - or, with --json, nothing but the summary as JSON on stdout for scripts
+pub struct Progress {
+    operation: String,
+    total: Option<usize>,
+    json: bool,
+    live: bool,
+    started: Instant,
+    items: Vec<ItemResult>,
+}
+
+impl Progress {
+    // total = None when the amount of work isn't known up front (spinner instead of a bar)
+    pub fn new(operation: &str, total: Option<usize>) -> Self {
+        let json = Self::json_requested();
+        Progress {
+            operation: operation.to_string(),
+            total,
+            json,
+            live: std::io::stderr().is_terminal(),
+            started: Instant::now(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn json_requested() -> bool {
+        std::env::args().any(|a| a == "--json")
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    pub fn set_total(&mut self, total: usize) {
+        self.total = Some(total);
+    }
+
+    // Redraw the live line with what is being worked on now
+    pub fn tick(&self, current: &str) {
+        if !self.live {
+            return;
+        }
+        let done = self.items.len();
+        let gauge = match self.total {
+            Some(total) if total > 0 => {
+                let filled = (done.min(total) * BAR_WIDTH) / total;
+                format!(
+                    "{}{}{}{} {}/{}",
+                    colors::ACCENT,
+                    "▰".repeat(filled),
+                    "▱".repeat(BAR_WIDTH - filled),
+                    colors::RESET,
+                    done,
+                    total
+                )
+            }
+            _ => format!("{}{}{} {}", colors::ACCENT, Spinner::frame(), colors::RESET, done),
+        };
+        let line = format!("\r\x1b[2K  {} {}{}{}", gauge, colors::MUTED, current, colors::RESET);
+        eprint!("{}", polymarket_term::render(&line));
+        let _ = std::io::stderr().flush();
+    }
+
+    fn clear_line(&self) {
+        if self.live {
+            eprint!("\r\x1b[2K");
+        }
+    }
+
+    pub fn record(&mut self, item: ItemResult) {
+        self.clear_line();
+        if !self.json {
+            let (color, icon) = match item.status {
+                ItemStatus::Ok => (colors::SUCCESS, icons::OK),
+                ItemStatus::Skipped => (colors::MUTED, icons::DOT),
+                ItemStatus::Failed => (colors::ERROR, icons::ERR),
+            };
+            let usd = item.usd.map(|u| format!("  ${:.2}", u)).unwrap_or_default();
+            outln!(
+                "  {}{}{} {:<28} {}{}{}{}",
+                color,
+                icon,
+                colors::RESET,
+                item.name,
+                colors::MUTED,
+                item.detail,
+                colors::RESET,
+                usd
+            );
+        }
+        self.items.push(item);
+        let last = self.items.last().map(|i| i.name.clone()).unwrap_or_default();
+        self.tick(&last);
+    }
+
+    pub fn ok(&mut self, name: &str, detail: &str, usd: Option<f64>) {
+        self.record(ItemResult { name: name.to_string(), status: ItemStatus::Ok, detail: detail.to_string(), usd });
+    }
+
+    pub fn skipped(&mut self, name: &str, reason: &str) {
+        self.record(ItemResult { name: name.to_string(), status: ItemStatus::Skipped, detail: reason.to_string(), usd: None });
+    }
+
+    pub fn failed(&mut self, name: &str, error: &str) {
+        self.record(ItemResult { name: name.to_string(), status: ItemStatus::Failed, detail: error.to_string(), usd: None });
+    }
+
+    // Print the summary (table or JSON) and hand it back; callers exit non-zero when summary.failed > 0
+    pub fn finish(self) -> ProgressSummary {
+        self.clear_line();
+        let count = |s: ItemStatus| self.items.iter().filter(|i| i.status == s).count();
+        let summary = ProgressSummary {
+            operation: self.operation.clone(),
+            total: self.total.unwrap_or(self.items.len()),
+            ok: count(ItemStatus::Ok),
+            skipped: count(ItemStatus::Skipped),
+            failed: count(ItemStatus::Failed),
+            usd: self.items.iter().filter(|i| i.status == ItemStatus::Ok).filter_map(|i| i.usd).sum(),
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            items: self.items,
+        };
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&summary).unwrap_or_default());
+        } else {
+            print_summary(&summary);
+        }
+        summary
+    }
+}
+
+fn print_summary(s: &ProgressSummary) {
+    outln!();
+    outln!("  {}{}{}", colors::BOLD, s.operation, colors::RESET);
+    outln!("  {:<10} {:>6}", "OK", s.ok);
+    outln!("  {:<10} {:>6}", "SKIPPED", s.skipped);
+    outln!("  {:<10} {:>6}", "FAILED", s.failed);
+    if s.usd != 0.0 {
+        outln!("  {:<10} {:>+10.2}", "USD", s.usd);
+    }
+    outln!("  {:<10} {:>8.1}s", "TIME", s.elapsed_ms as f64 / 1000.0);
+    let failures: Vec<&ItemResult> = s.items.iter().filter(|i| i.status == ItemStatus::Failed).collect();
+    if !failures.is_empty() {
+        outln!("\n  {}Failed:{}", colors::ERROR, colors::RESET);
+        for item in failures {
+            outln!("    {:<28} {}", item.name, item.detail);
+        }
+    }
+    outln!();
+}
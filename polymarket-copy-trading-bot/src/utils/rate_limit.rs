@@ -0,0 +1,260 @@
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::config::EnvConfig;
+use crate::utils::{Logger, COPY_STRATEGY};
+
+// CLOB order budget shared with other engines on the same wallet (set by the supervisor binary)
+pub use polymarket_gates::ClobGate;
+
+// Start spacing requests out once less than this share of the window's quota is left
+const LOW_QUOTA_RATIO: f64 = 0.1;
+// Never pace a single request by more than this (a wrong reset header shouldn't stall the bot)
+const MAX_PACE: Duration = Duration::from_secs(10);
+// 429 without Retry-After
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(2);
+// Longest back-off / reset window taken from a header (anything bigger is a bad value)
+const MAX_HEADER_SECS: f64 = 600.0;
+
+// Last quota seen per API host
+#[derive(Debug, Clone)]
+struct HostQuota {
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    reset_at: Option<Instant>,
+    blocked_until: Option<Instant>, // Set by a 429
+    rate_limited: u64,              // 429s seen
+    low_warned: bool,
+}
+
+// Remaining-quota gauge for one host (what metrics / status output reads)
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitGauge {
+    pub host: String,
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    pub reset_in_secs: Option<f64>,
+    pub rate_limited_total: u64,
+}
+
+impl RateLimitGauge {
+    // "ok" / "warning" for Logger::health_line
+    pub fn status(&self) -> &'static str {
+        match (self.remaining, self.limit) {
+            (Some(r), Some(l)) if l > 0 && (r as f64) < l as f64 * LOW_QUOTA_RATIO => "warning",
+            _ if self.rate_limited_total > 0 => "warning",
+            _ => "ok",
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let quota = match (self.remaining, self.limit) {
+            (Some(r), Some(l)) => format!("{}/{} requests left", r, l),
+            (Some(r), None) => format!("{} requests left", r),
+            _ => "no quota headers".to_string(),
+        };
+        let reset = self
+            .reset_in_secs
+            .map(|s| format!(", resets in {:.0}s", s))
+            .unwrap_or_default();
+        format!("{}{} ({} x 429)", quota, reset, self.rate_limited_total)
+    }
+}
+
+static QUOTAS: OnceLock<Mutex<HashMap<String, HostQuota>>> = OnceLock::new();
+
+fn quotas() -> &'static Mutex<HashMap<String, HostQuota>> {
+    QUOTAS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn header_f64(headers: &HeaderMap, names: &[&str]) -> Option<f64> {
+    names.iter().find_map(|n| {
+        headers
+            .get(*n)
+            .and_then(|v| v.to_str().ok())
+            // "100" or "100;w=10" (IETF draft style)
+            .and_then(|v| v.split(';').next())
+            .and_then(|v| v.trim().parse().ok())
+    })
+}
+
+// Header seconds -> Duration, None for NaN / infinite (Duration::from_secs_f64 panics on those)
+fn header_duration(secs: f64) -> Option<Duration> {
+    Some(secs)
+        .filter(|s| s.is_finite())
+        .map(|s| Duration::from_secs_f64(s.clamp(0.0, MAX_HEADER_SECS)))
+}
+
+// Reset is either seconds-until or a unix timestamp depending on the API
+fn reset_duration(value: f64) -> Option<Duration> {
+    let now = chrono::Utc::now().timestamp() as f64;
+    header_duration(if value > 1_000_000_000.0 { value - now } else { value })
+}
+
+fn host_quota<'a>(map: &'a mut HashMap<String, HostQuota>, host: &str) -> &'a mut HostQuota {
+    map.entry(host.to_string()).or_insert(HostQuota {
+        limit: None,
+        remaining: None,
+        reset_at: None,
+        blocked_until: None,
+        rate_limited: 0,
+        low_warned: false,
+    })
+}
+
+fn back_off(host: &str, q: &mut HostQuota, retry_after: Option<f64>) -> Duration {
+    q.rate_limited += 1;
+    let wait = retry_after.and_then(header_duration).unwrap_or(DEFAULT_RETRY_AFTER);
+    q.blocked_until = Some(Instant::now() + wait);
+    Logger::warning(&format!(
+        "Rate limited by {} (429) - backing off {:.1}s",
+        host,
+        wait.as_secs_f64()
+    ));
+    wait
+}
+
+// Record rate-limit headers (x-ratelimit-*, ratelimit-*, retry-after) from a response
+pub fn record_response(url: &str, status: reqwest::StatusCode, headers: &HeaderMap) {
+    let limit = header_f64(headers, &["x-ratelimit-limit", "ratelimit-limit"]);
+    let remaining = header_f64(headers, &["x-ratelimit-remaining", "ratelimit-remaining"]);
+    let reset = header_f64(headers, &["x-ratelimit-reset", "ratelimit-reset"]);
+    let retry_after = header_f64(headers, &["retry-after"]);
+    let is_429 = status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    if limit.is_none() && remaining.is_none() && !is_429 {
+        return;
+    }
+
+    let host = host_of(url);
+    let Ok(mut map) = quotas().lock() else {
+        return;
+    };
+    let q = host_quota(&mut map, &host);
+    let now = Instant::now();
+    if let Some(l) = limit {
+        q.limit = Some(l as u64);
+    }
+    if let Some(r) = remaining {
+        q.remaining = Some(r as u64);
+    }
+    if let Some(d) = reset.and_then(reset_duration) {
+        q.reset_at = Some(now + d);
+    }
+    if is_429 {
+        back_off(&host, q, retry_after);
+    }
+
+    let low = matches!((q.remaining, q.limit), (Some(r), Some(l)) if l > 0 && (r as f64) < l as f64 * LOW_QUOTA_RATIO);
+    if low && !q.low_warned {
+        Logger::warning(&format!(
+            "{} rate-limit quota low: {}/{} left - slowing requests",
+            host,
+            q.remaining.unwrap_or(0),
+            q.limit.unwrap_or(0)
+        ));
+    }
+    q.low_warned = low;
+}
+
+// How long to wait before the next request to this host (zero when quota is healthy)
+fn pace_delay(url: &str) -> Duration {
+    let Ok(map) = quotas().lock() else {
+        return Duration::ZERO;
+    };
+    let Some(q) = map.get(&host_of(url)) else {
+        return Duration::ZERO;
+    };
+    let now = Instant::now();
+    if let Some(until) = q.blocked_until.filter(|u| *u > now) {
+        return (until - now).min(MAX_PACE);
+    }
+    match (q.remaining, q.limit, q.reset_at) {
+        (Some(remaining), Some(limit), Some(reset_at))
+            if limit > 0 && (remaining as f64) < limit as f64 * LOW_QUOTA_RATIO && reset_at > now =>
+        {
+            // Spread what's left evenly over the rest of the window
+            ((reset_at - now) / (remaining.max(1) as u32)).min(MAX_PACE)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+// Sleep as needed before hitting this host again
+pub async fn pace(url: &str) {
+    let delay = pace_delay(url);
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+}
+
+static CLOB_GATE: OnceLock<Arc<dyn ClobGate>> = OnceLock::new();
+
+// Install the shared CLOB budget (first call wins; standalone runs never call this)
+pub fn set_clob_gate(gate: Arc<dyn ClobGate>) {
+    let _ = CLOB_GATE.set(gate);
+}
+
+// One CLOB SDK call through the tracker: paced like fetch_data (and by the shared budget under the supervisor),
+// and an error that reads as a 429 backs CLOB_HTTP_URL off for every engine on the wallet
+// (the SDK doesn't hand out response headers, so there is no quota to record)
+pub async fn clob_call<T, E: std::fmt::Display>(
+    config: &EnvConfig,
+    call: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let url = &config.clob_http_url;
+    pace(url).await;
+    if let Some(gate) = CLOB_GATE.get() {
+        let wait = gate.reserve_call(COPY_STRATEGY).min(MAX_PACE);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+    let result = call.await;
+    if let Err(e) = &result {
+        let msg = e.to_string().to_lowercase();
+        if msg.contains("429") || msg.contains("too many requests") {
+            let host = host_of(url);
+            let wait = match quotas().lock() {
+                Ok(mut map) => back_off(&host, host_quota(&mut map, &host), None),
+                Err(_) => DEFAULT_RETRY_AFTER,
+            };
+            if let Some(gate) = CLOB_GATE.get() {
+                gate.throttled(COPY_STRATEGY, wait);
+            }
+        }
+    }
+    result
+}
+
+// Snapshot of every host's quota
+pub fn rate_limit_gauges() -> Vec<RateLimitGauge> {
+    let Ok(map) = quotas().lock() else {
+        return Vec::new();
+    };
+    let now = Instant::now();
+    let mut gauges: Vec<RateLimitGauge> = map
+        .iter()
+        .map(|(host, q)| RateLimitGauge {
+            host: host.clone(),
+            limit: q.limit,
+            remaining: q.remaining,
+            reset_in_secs: q
+                .reset_at
+                .map(|r| r.saturating_duration_since(now).as_secs_f64()),
+            rate_limited_total: q.rate_limited,
+        })
+        .collect();
+    gauges.sort_by(|a, b| a.host.cmp(&b.host));
+    gauges
+}
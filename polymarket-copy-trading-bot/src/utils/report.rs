@@ -0,0 +1,491 @@
+use anyhow::Result;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::{
+    build_capital_report, build_tuning_report, clock, fetch_paginated, fetch_wallet_positions, get_usdc_balance,
+    is_open_position, market_categories, ActivityKind, CapitalEfficiency, CashFlows, Logger, PositionTotals,
+    TuningSuggestion,
+};
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::types::{MarketCategory, SkipReason, UserPosition};
+
+// Rows per top-N list in a digest
+const DIGEST_TOP_N: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    Daily,
+    Weekly,
+}
+
+impl ReportPeriod {
+    pub fn parse(s: &str) -> Option<ReportPeriod> {
+        match s.trim().to_lowercase().as_str() {
+            "daily" | "day" => Some(ReportPeriod::Daily),
+            "weekly" | "week" => Some(ReportPeriod::Weekly),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportPeriod::Daily => "daily",
+            ReportPeriod::Weekly => "weekly",
+        }
+    }
+
+    pub fn days(&self) -> i64 {
+        match self {
+            ReportPeriod::Daily => 1,
+            ReportPeriod::Weekly => 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestPosition {
+    pub title: String,
+    pub outcome: Option<String>,
+    pub current_value: f64,
+    pub cash_pnl: f64,
+    pub percent_pnl: f64,
+}
+
+impl DigestPosition {
+    fn from_position(p: &UserPosition) -> Self {
+        DigestPosition {
+            title: p.title.clone().unwrap_or_else(|| "Unknown market".to_string()),
+            outcome: p.outcome.clone(),
+            current_value: p.current_value.unwrap_or(0.0),
+            cash_pnl: p.cash_pnl.unwrap_or(0.0),
+            percent_pnl: p.percent_pnl.unwrap_or(0.0),
+        }
+    }
+
+    fn line(&self) -> String {
+        format!(
+            "{}{}  ${:+.2} ({:+.1}%), value ${:.2}",
+            self.title,
+            self.outcome.as_deref().map(|o| format!(" [{}]", o)).unwrap_or_default(),
+            self.cash_pnl,
+            self.percent_pnl,
+            self.current_value
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkipCount {
+    pub reason: String,
+    pub count: u64,
+}
+
+// One market category's slice of the digest (markets Gamma couldn't classify count as "other")
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryPnl {
+    pub category: MarketCategory,
+    pub bought_usd: f64, // In the period
+    pub sold_usd: f64,
+    pub open_positions: usize,
+    pub open_value: f64,
+    pub unrealized_pnl: f64,
+    pub realized_pnl: f64, // Open and closed positions, all time
+}
+
+impl CategoryPnl {
+    fn new(category: MarketCategory) -> Self {
+        CategoryPnl {
+            category,
+            bought_usd: 0.0,
+            sold_usd: 0.0,
+            open_positions: 0,
+            open_value: 0.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+        }
+    }
+
+    fn line(&self) -> String {
+        format!(
+            "{:<14} {} open worth ${:.2}, unrealized ${:+.2}, realized ${:+.2} | bought ${:.2}, sold ${:.2}",
+            self.category.as_str(),
+            self.open_positions,
+            self.open_value,
+            self.unrealized_pnl,
+            self.realized_pnl,
+            self.bought_usd,
+            self.sold_usd
+        )
+    }
+}
+
+// One daily / weekly digest - cash basis like daily_summary, positions totalled like check_pnl (see utils::wallet_pnl)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Digest {
+    pub period: ReportPeriod,
+    pub wallet: String,
+    pub from_ms: i64,
+    pub to_ms: i64,
+    pub buys: usize,
+    pub sells: usize,
+    pub bought_usd: f64,
+    pub sold_usd: f64,
+    pub redeemed_usd: f64,
+    pub merged_usd: f64,
+    pub split_usd: f64,
+    pub converted_usd: f64,
+    pub net_cash_flow: f64, // Sold + redeemed + merged + converted - bought - split in the period
+    pub realized_pnl: f64,  // Open and closed positions, all time (data-api)
+    pub unrealized_pnl: f64,
+    pub open_positions: usize,
+    pub open_value: f64,
+    pub usdc_balance: Option<f64>,
+    pub skipped: u64,
+    pub skips: Vec<SkipCount>,
+    pub top_winners: Vec<DigestPosition>,
+    pub top_losers: Vec<DigestPosition>,
+    pub largest_exposure: Vec<DigestPosition>,
+    pub categories: Vec<CategoryPnl>, // Sorted by open value
+    pub suggestions: Vec<TuningSuggestion>, // Weekly only (see utils::tuning)
+    pub capital: Option<CapitalEfficiency>, // Weekly only, needs CAPITAL_SAMPLE_SECS samples (see utils::capital)
+}
+
+impl Digest {
+    pub fn subject(&self) -> String {
+        format!(
+            "Polymarket copy bot {} digest {} - net ${:+.2}",
+            self.period.as_str(),
+            clock::market_time_str(self.to_ms, "%Y-%m-%d"),
+            self.net_cash_flow
+        )
+    }
+
+    // Plain-text body (email, Slack-style "text" field, report CLI)
+    pub fn to_text(&self) -> String {
+        let mut out = vec![
+            self.subject(),
+            format!(
+                "Wallet {} - {} to {}",
+                self.wallet,
+                clock::market_time_str(self.from_ms, "%Y-%m-%d %H:%M"),
+                clock::market_time_str(self.to_ms, "%Y-%m-%d %H:%M")
+            ),
+            String::new(),
+            format!("Trades: {} buys (${:.2}), {} sells (${:.2})", self.buys, self.bought_usd, self.sells, self.sold_usd),
+            format!("Redeemed: ${:.2}", self.redeemed_usd),
+        ];
+        if self.merged_usd > 0.0 || self.split_usd > 0.0 || self.converted_usd > 0.0 {
+            out.push(format!(
+                "Merged: ${:.2}, split: ${:.2}, converted: ${:.2}",
+                self.merged_usd, self.split_usd, self.converted_usd
+            ));
+        }
+        out.extend([
+            format!("Net cash flow: ${:+.2}", self.net_cash_flow),
+            format!(
+                "Open positions: {} worth ${:.2} (unrealized ${:+.2})",
+                self.open_positions, self.open_value, self.unrealized_pnl
+            ),
+            format!("Realized PnL (open + closed positions): ${:+.2}", self.realized_pnl),
+        ]);
+        if let Some(balance) = self.usdc_balance {
+            out.push(format!("USDC balance: ${:.2}", balance));
+        }
+        out.push(format!("Skipped trades: {}", self.skipped));
+        out.extend(self.skips.iter().map(|s| format!("  {:<24} {}", s.reason, s.count)));
+
+        let sections = [
+            ("Top winners", &self.top_winners),
+            ("Top losers", &self.top_losers),
+            ("Largest exposure", &self.largest_exposure),
+        ];
+        for (name, rows) in sections {
+            if rows.is_empty() {
+                continue;
+            }
+            out.push(String::new());
+            out.push(format!("{}:", name));
+            out.extend(rows.iter().enumerate().map(|(i, p)| format!("  {}. {}", i + 1, p.line())));
+        }
+        if !self.categories.is_empty() {
+            out.push(String::new());
+            out.push("By category:".to_string());
+            out.extend(self.categories.iter().map(|c| format!("  {}", c.line())));
+        }
+        if let Some(ref capital) = self.capital {
+            out.push(String::new());
+            out.push("Capital efficiency:".to_string());
+            out.extend(capital.lines().into_iter().map(|l| format!("  {}", l)));
+        }
+        if !self.suggestions.is_empty() {
+            out.push(String::new());
+            out.push("Tuning suggestions:".to_string());
+            out.extend(self.suggestions.iter().map(|s| format!("  - {}", s.line())));
+        }
+        out.join("\n")
+    }
+}
+
+// Build the digest for the period ending now (positions are a snapshot, trades/skips are the window)
+pub async fn build_digest(
+    config: &EnvConfig,
+    db: &Db,
+    http_client: &reqwest::Client,
+    period: ReportPeriod,
+) -> Result<Digest> {
+    let to_ms = clock::now_ms();
+    let since = to_ms / 1000 - period.days() * 86_400;
+
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&start={}",
+        config.proxy_wallet, since
+    );
+    let activity = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await?
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let flows = CashFlows::from_activity(&activity);
+    let mut traded: Vec<(String, bool, f64)> = Vec::new(); // (conditionId, buy, USDC) for the category split
+    for a in &activity {
+        let buy = match ActivityKind::of(a) {
+            ActivityKind::Buy => true,
+            ActivityKind::Sell => false,
+            _ => continue,
+        };
+        traded.push((
+            a.get("conditionId").and_then(|v| v.as_str()).unwrap_or("").to_lowercase(),
+            buy,
+            a.get("usdcSize").and_then(|v| v.as_f64()).unwrap_or(0.0),
+        ));
+    }
+
+    let all_positions = fetch_wallet_positions(config, http_client, &config.proxy_wallet).await?;
+    let totals = PositionTotals::from_positions(&all_positions);
+    let positions: Vec<UserPosition> = all_positions.iter().filter(|p| is_open_position(p)).cloned().collect();
+
+    // PnL / flows per market category (one Gamma lookup per market, cached for the process)
+    let mut cids: Vec<String> = traded.iter().map(|(cid, _, _)| cid.clone()).collect();
+    cids.extend(all_positions.iter().filter_map(|p| p.condition_id.as_deref().map(str::to_lowercase)));
+    let known = market_categories(config, http_client, &cids).await;
+    let category_of = |cid: &str| known.get(cid).copied().unwrap_or(MarketCategory::Other);
+    let mut by_category: HashMap<MarketCategory, CategoryPnl> = HashMap::new();
+    for (cid, buy, usdc) in &traded {
+        let row = by_category.entry(category_of(cid)).or_insert_with_key(|c| CategoryPnl::new(*c));
+        if *buy {
+            row.bought_usd += usdc;
+        } else {
+            row.sold_usd += usdc;
+        }
+    }
+    for p in &all_positions {
+        let cid = p.condition_id.as_deref().unwrap_or("").to_lowercase();
+        let row = by_category.entry(category_of(&cid)).or_insert_with_key(|c| CategoryPnl::new(*c));
+        row.realized_pnl += p.realized_pnl.unwrap_or(0.0);
+        if is_open_position(p) {
+            row.open_positions += 1;
+            row.open_value += p.current_value.unwrap_or(0.0);
+            row.unrealized_pnl += p.cash_pnl.unwrap_or(0.0);
+        }
+    }
+    let mut categories: Vec<CategoryPnl> = by_category.into_values().collect();
+    categories.sort_by(|a, b| b.open_value.total_cmp(&a.open_value).then(a.category.cmp(&b.category)));
+
+    let mut skip_totals: HashMap<String, u64> = HashMap::new();
+    for address in &config.user_addresses {
+        for (reason, count) in db.count_skips(address, Some(since)).await? {
+            *skip_totals.entry(reason).or_insert(0) += count;
+        }
+    }
+    let mut skips: Vec<SkipCount> = skip_totals
+        .into_iter()
+        .map(|(reason, count)| SkipCount { reason, count })
+        .collect();
+    skips.sort_by(|a, b| b.count.cmp(&a.count));
+
+    let top = |key: &dyn Fn(&UserPosition) -> f64, keep: &dyn Fn(f64) -> bool| -> Vec<DigestPosition> {
+        let mut rows: Vec<&UserPosition> = positions.iter().filter(|p| keep(key(p))).collect();
+        rows.sort_by(|a, b| key(b).total_cmp(&key(a)));
+        rows.into_iter().take(DIGEST_TOP_N).map(DigestPosition::from_position).collect()
+    };
+    let pnl = |p: &UserPosition| p.cash_pnl.unwrap_or(0.0);
+
+    // A suggestion needs a week of data to mean anything; a failed analysis never blocks the digest
+    let suggestions = match period {
+        ReportPeriod::Weekly => match build_tuning_report(config, db, http_client, period.days()).await {
+            Ok(report) => report.suggestions,
+            Err(e) => {
+                Logger::warning(&format!("Tuning suggestions failed: {}", e));
+                Vec::new()
+            }
+        },
+        ReportPeriod::Daily => Vec::new(),
+    };
+    let capital = match period {
+        ReportPeriod::Weekly => {
+            let out_of_funds = skips
+                .iter()
+                .filter(|s| s.reason == SkipReason::InsufficientFunds.as_str())
+                .map(|s| s.count)
+                .sum();
+            match build_capital_report(config, db, since * 1000, to_ms, out_of_funds).await {
+                Ok(capital) => capital,
+                Err(e) => {
+                    Logger::warning(&format!("Capital efficiency report failed: {}", e));
+                    None
+                }
+            }
+        }
+        ReportPeriod::Daily => None,
+    };
+
+    Ok(Digest {
+        period,
+        wallet: config.proxy_wallet.clone(),
+        from_ms: since * 1000,
+        to_ms,
+        buys: flows.buys,
+        sells: flows.sells,
+        bought_usd: flows.bought_usd,
+        sold_usd: flows.sold_usd,
+        redeemed_usd: flows.redeemed_usd,
+        merged_usd: flows.merged_usd,
+        split_usd: flows.split_usd,
+        converted_usd: flows.converted_usd,
+        net_cash_flow: flows.net(),
+        realized_pnl: totals.realized_pnl(),
+        unrealized_pnl: totals.unrealized_pnl,
+        open_positions: totals.open,
+        open_value: totals.open_value,
+        usdc_balance: get_usdc_balance(&config.rpc_url, &config.usdc_contract_address, &config.proxy_wallet)
+            .await
+            .ok(),
+        skipped: skips.iter().map(|s| s.count).sum(),
+        skips,
+        top_winners: top(&pnl, &|v| v > 0.0),
+        top_losers: top(&|p| -pnl(p), &|v| v > 0.0),
+        largest_exposure: top(&|p| p.current_value.unwrap_or(0.0), &|v| v > 0.0),
+        categories,
+        suggestions,
+        capital,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportChannel {
+    Webhook,
+    Email,
+}
+
+impl ReportChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportChannel::Webhook => "webhook",
+            ReportChannel::Email => "email",
+        }
+    }
+}
+
+// Delivery channels with their settings filled in
+pub fn report_channels(config: &EnvConfig) -> Vec<ReportChannel> {
+    let mut channels = Vec::new();
+    if config.report_webhook_url.is_some() {
+        channels.push(ReportChannel::Webhook);
+    }
+    if config.report_smtp_host.is_some() && !config.report_email_to.is_empty() {
+        channels.push(ReportChannel::Email);
+    }
+    channels
+}
+
+// True when at least one delivery channel is configured
+pub fn report_delivery_configured(config: &EnvConfig) -> bool {
+    !report_channels(config).is_empty()
+}
+
+async fn send_email(config: &EnvConfig, digest: &Digest) -> Result<()> {
+    let Some(host) = config.report_smtp_host.as_deref() else {
+        return Ok(());
+    };
+    let from = config
+        .report_email_from
+        .clone()
+        .or_else(|| config.report_smtp_user.clone())
+        .ok_or_else(|| anyhow::anyhow!("REPORT_EMAIL_FROM is not set"))?;
+    let mut builder = Message::builder().from(from.parse()?).subject(digest.subject());
+    for to in &config.report_email_to {
+        builder = builder.to(to.parse()?);
+    }
+    let email = builder.header(ContentType::TEXT_PLAIN).body(digest.to_text())?;
+
+    // 465 = implicit TLS, anything else upgrades with STARTTLS
+    let mut transport = if config.report_smtp_port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(host)?
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?
+    }
+    .port(config.report_smtp_port);
+    if let (Some(user), Some(password)) = (&config.report_smtp_user, &config.report_smtp_password) {
+        transport = transport.credentials(Credentials::new(user.clone(), password.clone()));
+    }
+    transport.build().send(email).await?;
+    Ok(())
+}
+
+async fn send_webhook(config: &EnvConfig, http_client: &reqwest::Client, digest: &Digest) -> Result<()> {
+    let Some(url) = config.report_webhook_url.as_deref() else {
+        return Ok(());
+    };
+    let mut body = serde_json::to_value(digest)?;
+    body["text"] = serde_json::Value::String(digest.to_text()); // Slack-compatible, same as alert webhooks
+    http_client
+        .post(url)
+        .json(&body)
+        .timeout(std::time::Duration::from_millis(config.request_timeout_ms))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+// Send the digest to one channel
+pub async fn send_digest(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    digest: &Digest,
+    channel: ReportChannel,
+) -> Result<()> {
+    match channel {
+        ReportChannel::Webhook => send_webhook(config, http_client, digest).await,
+        ReportChannel::Email => send_email(config, digest).await,
+    }
+}
+
+// Send the digest to every configured channel; Err when any of them failed
+pub async fn deliver_digest(config: &EnvConfig, http_client: &reqwest::Client, digest: &Digest) -> Result<()> {
+    let channels = report_channels(config);
+    if channels.is_empty() {
+        anyhow::bail!("No report channel configured (REPORT_WEBHOOK_URL or REPORT_SMTP_HOST + REPORT_EMAIL_TO)");
+    }
+    let mut failed = Vec::new();
+    for channel in channels {
+        if let Err(e) = send_digest(config, http_client, digest, channel).await {
+            Logger::warning(&format!("Report {} failed: {}", channel.as_str(), e));
+            failed.push(channel.as_str());
+        }
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("Report delivery failed: {}", failed.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::types::WalletInflow;
+use crate::utils::{fetch_paginated, http_client, HttpPool};
+
+// keccak256("Transfer(address,address,uint256)")
+pub(crate) const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+const SCANNER: &str = "usdc_inflows";
+const POLYGON_BLOCKS_PER_DAY: u64 = 43_200; // ~2s blocks
+const USDC_DECIMALS: i32 = 6;
+
+#[derive(Debug, Default, Clone)]
+pub struct InflowScan {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub inflows: usize,
+    pub rewards: usize,
+    pub reward_usd: f64,
+}
+
+pub(crate) async fn rpc_call(client: &reqwest::Client, rpc_url: &str, method: &str, params: Value) -> Result<Value> {
+    let body = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+    let resp = client
+        .post(rpc_url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(20))
+        .send()
+        .await?;
+    let json: Value = resp.json().await?;
+    if let Some(err) = json.get("error") {
+        return Err(anyhow!("{} failed: {}", method, err));
+    }
+    json.get("result")
+        .cloned()
+        .ok_or_else(|| anyhow!("No result in {} response", method))
+}
+
+pub(crate) fn hex_u64(value: &Value) -> Option<u64> {
+    u64::from_str_radix(value.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+// Last 40 hex chars of a 32-byte topic
+pub(crate) fn topic_address(topic: &str) -> String {
+    let hex = topic.trim_start_matches("0x");
+    format!("0x{}", &hex[hex.len().saturating_sub(40)..]).to_lowercase()
+}
+
+pub(crate) fn usdc_amount(data: &str) -> f64 {
+    let hex = data.trim_start_matches("0x");
+    let low = &hex[hex.len().saturating_sub(32)..];
+    u128::from_str_radix(low, 16).unwrap_or(0) as f64 / 10_f64.powi(USDC_DECIMALS)
+}
+
+// Wallet activity type per tx hash (TRADE / REDEEM / MERGE / REWARD ...) from start_ts on
+async fn activity_types(config: &EnvConfig, client: &reqwest::Client, start_ts: i64) -> Result<HashMap<String, String>> {
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&start={}",
+        config.proxy_wallet, start_ts
+    );
+    let data = fetch_paginated(client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+    Ok(data
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|a| {
+                    let tx = a.get("transactionHash")?.as_str()?.to_lowercase();
+                    let kind = a.get("type")?.as_str()?.to_string();
+                    Some((tx, kind))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn classify(activity_type: Option<&str>, from: &str, config: &EnvConfig) -> &'static str {
+    match activity_type {
+        Some("TRADE") => "trade",
+        Some("REDEEM") => "redeem",
+        Some("MERGE") => "merge",
+        Some("CONVERSION") => "conversion",
+        Some("REWARD") => "reward",
+        _ if config.rewards_senders.iter().any(|s| s == from) => "reward",
+        _ => "deposit",
+    }
+}
+
+// Pull USDC transfers into PROXY_WALLET since the last scan (first run: lookback_days back) and store them
+// classified in "wallet_inflows"; the cursor advances per chunk, so an interrupted scan resumes
+pub async fn scan_wallet_inflows(config: &EnvConfig, db: &Db, lookback_days: u64) -> Result<InflowScan> {
+    let client = http_client(HttpPool::Rpc);
+    let latest = hex_u64(&rpc_call(&client, &config.rpc_url, "eth_blockNumber", json!([])).await?)
+        .ok_or_else(|| anyhow!("Bad eth_blockNumber response"))?;
+    let from_block = match db.get_scan_block(SCANNER).await? {
+        Some(block) => block + 1,
+        None => latest.saturating_sub(lookback_days * POLYGON_BLOCKS_PER_DAY),
+    };
+    let mut scan = InflowScan {
+        from_block,
+        to_block: latest,
+        ..Default::default()
+    };
+    if from_block > latest {
+        return Ok(scan);
+    }
+
+    let start_block = rpc_call(&client, &config.rpc_url, "eth_getBlockByNumber", json!([format!("0x{:x}", from_block), false])).await?;
+    let start_ts = start_block.get("timestamp").and_then(hex_u64).unwrap_or(0) as i64;
+    let types = activity_types(config, &client, start_ts).await?;
+
+    let wallet_topic = format!("0x{:0>64}", config.proxy_wallet.trim().trim_start_matches("0x").to_lowercase());
+    let mut block_times: HashMap<u64, i64> = HashMap::new();
+    let mut chunk_start = from_block;
+    while chunk_start <= latest {
+        let chunk_end = (chunk_start + config.rewards_scan_block_chunk - 1).min(latest);
+        let logs = rpc_call(
+            &client,
+            &config.rpc_url,
+            "eth_getLogs",
+            json!([{
+                "address": config.usdc_contract_address,
+                "fromBlock": format!("0x{:x}", chunk_start),
+                "toBlock": format!("0x{:x}", chunk_end),
+                "topics": [TRANSFER_TOPIC, Value::Null, wallet_topic],
+            }]),
+        )
+        .await?;
+
+        let mut inflows = Vec::new();
+        for log in logs.as_array().cloned().unwrap_or_default() {
+            let (Some(tx_hash), Some(block), Some(from_topic)) = (
+                log.get("transactionHash").and_then(|v| v.as_str()).map(|s| s.to_lowercase()),
+                log.get("blockNumber").and_then(hex_u64),
+                log.get("topics").and_then(|t| t.get(1)).and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            let timestamp = match block_times.get(&block) {
+                Some(ts) => *ts,
+                None => {
+                    let b = rpc_call(&client, &config.rpc_url, "eth_getBlockByNumber", json!([format!("0x{:x}", block), false])).await?;
+                    let ts = b.get("timestamp").and_then(hex_u64).unwrap_or(0) as i64;
+                    block_times.insert(block, ts);
+                    ts
+                }
+            };
+            let from = topic_address(from_topic);
+            let log_index = log.get("logIndex").and_then(hex_u64).unwrap_or(0);
+            let kind = classify(types.get(&tx_hash).map(|s| s.as_str()), &from, config);
+            inflows.push(WalletInflow {
+                id: format!("{}:{}", tx_hash, log_index),
+                tx_hash,
+                block,
+                timestamp,
+                from,
+                amount: usdc_amount(log.get("data").and_then(|v| v.as_str()).unwrap_or("0x0")),
+                kind: kind.to_string(),
+            });
+        }
+
+        scan.inflows += inflows.len();
+        for inflow in inflows.iter().filter(|i| i.kind == "reward") {
+            scan.rewards += 1;
+            scan.reward_usd += inflow.amount;
+        }
+        db.save_wallet_inflows(&inflows).await?;
+        db.set_scan_block(SCANNER, chunk_end).await?;
+        chunk_start = chunk_end + 1;
+    }
+    Ok(scan)
+}
@@ -0,0 +1,203 @@
+use alloy::dyn_abi::TypedData;
+use alloy::primitives::{Address, ChainId, Signature, B256};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::Signer;
+use alloy::sol_types::{Eip712Domain, SolStruct};
+use anyhow::Result;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::{EnvConfig, SignerBackend};
+use crate::utils::{http_client, HttpPool, Logger};
+
+// Orders and CLOB auth are signed for Polygon mainnet
+const POLYGON_CHAIN_ID: ChainId = 137;
+// A remote signer that takes longer than this is treated as down (the order would be stale anyway)
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Signing service reached over JSON-RPC 2.0, so the key never sits on the trading host.
+// Methods: eth_accounts, signer_signHash [address, hash] (the CLOB client signs EIP-712 hashes)
+// and eth_signTypedData_v4 / personal_sign for callers that hand over the full payload
+#[derive(Clone)]
+pub struct RemoteSigner {
+    url: String,
+    token: Option<String>,
+    address: Address,
+    chain_id: Option<ChainId>,
+    http_client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    // Ask the service which accounts it holds; REMOTE_SIGNER_ADDRESS must be one of them (unset = the first)
+    pub async fn connect(url: &str, token: Option<&str>, wanted: Option<&str>) -> Result<Self> {
+        let mut signer = RemoteSigner {
+            url: url.to_string(),
+            token: token.map(str::to_string),
+            address: Address::ZERO,
+            chain_id: Some(POLYGON_CHAIN_ID),
+            http_client: http_client(HttpPool::Api),
+        };
+        let accounts: Vec<String> = serde_json::from_value(signer.call("eth_accounts", serde_json::json!([])).await?)
+            .map_err(|e| anyhow::anyhow!("Remote signer returned unexpected accounts: {}", e))?;
+        let chosen = match wanted {
+            Some(w) => accounts
+                .iter()
+                .find(|a| a.eq_ignore_ascii_case(w))
+                .ok_or_else(|| anyhow::anyhow!("Remote signer has no account {} (it holds {})", w, accounts.join(", ")))?,
+            None => accounts.first().ok_or_else(|| anyhow::anyhow!("Remote signer holds no accounts"))?,
+        };
+        signer.address = Address::from_str(chosen).map_err(|e| anyhow::anyhow!("Remote signer account {}: {}", chosen, e))?;
+        Ok(signer)
+    }
+
+    async fn call(&self, method: &str, params: serde_json::Value) -> alloy::signers::Result<serde_json::Value> {
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut request = self.http_client.post(&self.url).json(&body).timeout(REMOTE_TIMEOUT);
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(alloy::signers::Error::other)?;
+        if !response.status().is_success() {
+            return Err(alloy::signers::Error::other(format!("remote signer {}: HTTP {}", method, response.status())));
+        }
+        let json: serde_json::Value = response.json().await.map_err(alloy::signers::Error::other)?;
+        if let Some(err) = json.get("error") {
+            let message = err.get("message").and_then(|m| m.as_str()).unwrap_or("rejected");
+            return Err(alloy::signers::Error::other(format!("remote signer {}: {}", method, message)));
+        }
+        json.get("result")
+            .cloned()
+            .ok_or_else(|| alloy::signers::Error::other(format!("remote signer {}: no result", method)))
+    }
+
+    async fn call_signature(&self, method: &str, params: serde_json::Value) -> alloy::signers::Result<Signature> {
+        let result = self.call(method, params).await?;
+        let hex_sig = result
+            .as_str()
+            .ok_or_else(|| alloy::signers::Error::other(format!("remote signer {}: signature is not a string", method)))?;
+        let bytes = hex::decode(hex_sig.trim_start_matches("0x")).map_err(alloy::signers::Error::other)?;
+        Signature::try_from(bytes.as_slice()).map_err(alloy::signers::Error::other)
+    }
+
+    fn account(&self) -> String {
+        format!("0x{:x}", self.address)
+    }
+}
+
+// Whatever SIGNER_BACKEND picked, behind alloy's Signer trait - the CLOB client and every order path
+// take this, so they don't care where the key is
+#[derive(Clone)]
+pub enum BotSigner {
+    Local(PrivateKeySigner),
+    Remote(RemoteSigner),
+}
+
+impl BotSigner {
+    // The raw key, for the on-chain helpers that send their own transactions (None = not a local backend)
+    pub fn local_key(&self) -> Option<&PrivateKeySigner> {
+        match self {
+            BotSigner::Local(signer) => Some(signer),
+            _ => None,
+        }
+    }
+}
+
+// Build the configured signer (Polygon chain id set); a remote backend is contacted here,
+// so a signer that's down fails at startup rather than on the first copy
+pub async fn create_signer(config: &EnvConfig) -> Result<BotSigner> {
+    let signer = match config.signer_backend {
+        SignerBackend::Local => BotSigner::Local(
+            PrivateKeySigner::from_str(&format!("0x{}", config.private_key))
+                .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?
+                .with_chain_id(Some(POLYGON_CHAIN_ID)),
+        ),
+        SignerBackend::Remote => {
+            let url = config
+                .remote_signer_url
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("SIGNER_BACKEND=remote needs REMOTE_SIGNER_URL"))?;
+            BotSigner::Remote(
+                RemoteSigner::connect(url, config.remote_signer_token.as_deref(), config.remote_signer_address.as_deref())
+                    .await?,
+            )
+        }
+    };
+    if config.signer_backend != SignerBackend::Local {
+        Logger::info(&format!(
+            "Signing with {} {}",
+            config.signer_backend.label(),
+            Logger::format_address(&format!("0x{:x}", signer.address()))
+        ));
+    }
+    Ok(signer)
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Signer for BotSigner {
+    async fn sign_hash(&self, hash: &B256) -> alloy::signers::Result<Signature> {
+        match self {
+            BotSigner::Local(signer) => signer.sign_hash(hash).await,
+            BotSigner::Remote(remote) => {
+                remote
+                    .call_signature("signer_signHash", serde_json::json!([remote.account(), format!("0x{:x}", hash)]))
+                    .await
+            }
+        }
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> alloy::signers::Result<Signature> {
+        match self {
+            BotSigner::Local(signer) => signer.sign_message(message).await,
+            BotSigner::Remote(remote) => {
+                remote
+                    .call_signature("personal_sign", serde_json::json!([format!("0x{}", hex::encode(message)), remote.account()]))
+                    .await
+            }
+        }
+    }
+
+    async fn sign_typed_data<T: SolStruct + Send + Sync>(
+        &self,
+        payload: &T,
+        domain: &Eip712Domain,
+    ) -> alloy::signers::Result<Signature>
+    where
+        Self: Sized,
+    {
+        match self {
+            BotSigner::Local(signer) => signer.sign_typed_data(payload, domain).await,
+            BotSigner::Remote(_) => self.sign_dynamic_typed_data(&TypedData::from_struct(payload, Some(domain.clone()))).await,
+        }
+    }
+
+    async fn sign_dynamic_typed_data(&self, payload: &TypedData) -> alloy::signers::Result<Signature> {
+        match self {
+            BotSigner::Local(signer) => signer.sign_dynamic_typed_data(payload).await,
+            BotSigner::Remote(remote) => {
+                let json = serde_json::to_value(payload).map_err(alloy::signers::Error::other)?;
+                remote.call_signature("eth_signTypedData_v4", serde_json::json!([remote.account(), json])).await
+            }
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            BotSigner::Local(signer) => signer.address(),
+            BotSigner::Remote(remote) => remote.address,
+        }
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        match self {
+            BotSigner::Local(signer) => signer.chain_id(),
+            BotSigner::Remote(remote) => remote.chain_id,
+        }
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        match self {
+            BotSigner::Local(signer) => signer.set_chain_id(chain_id),
+            BotSigner::Remote(remote) => remote.chain_id = chain_id,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+
+use crate::db::Db;
+use crate::types::{Annotation, AnnotationKind};
+
+// Shared by the `tags` CLI and the Telegram /tag command
+pub const TAG_USAGE: &str = "\
+add <trader|market> <key> <tag1,tag2>
+rm <trader|market> <key> <tag1,tag2>
+note <trader|market> <key> <text> (empty text clears)
+show <trader|market> <key>
+list [tag]";
+
+fn parse_tags(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+pub fn format_annotation(a: &Annotation) -> String {
+    let mut line = format!("{} {}", a.kind.as_str(), a.key);
+    if !a.tags.is_empty() {
+        line.push_str(&format!(" [{}]", a.tags.join(", ")));
+    }
+    if let Some(ref note) = a.note {
+        line.push_str(&format!(" - {}", note));
+    }
+    line
+}
+
+// Run one tag command (args after the command name) - returns a human-readable reply
+pub async fn run_tag_command(db: &Db, args: &[String]) -> Result<String> {
+    let action = args.first().map(|s| s.as_str()).unwrap_or("");
+
+    if action == "list" {
+        let tag = args.get(1).map(|s| s.as_str());
+        let annotations = db.list_annotations(tag).await?;
+        if annotations.is_empty() {
+            return Ok("No annotations".to_string());
+        }
+        return Ok(annotations
+            .iter()
+            .map(format_annotation)
+            .collect::<Vec<_>>()
+            .join("\n"));
+    }
+
+    let (Some(kind), Some(key)) = (
+        args.get(1).and_then(|k| AnnotationKind::parse(k)),
+        args.get(2),
+    ) else {
+        anyhow::bail!("Usage:\n{}", TAG_USAGE);
+    };
+    let rest = args[3..].join(" ");
+
+    match action {
+        "add" | "rm" if rest.trim().is_empty() => anyhow::bail!("No tags given"),
+        "add" => db.add_tags(kind, key, &parse_tags(&rest)).await?,
+        "rm" => db.remove_tags(kind, key, &parse_tags(&rest)).await?,
+        "note" => db.set_note(kind, key, &rest).await?,
+        "show" => {}
+        _ => anyhow::bail!("Usage:\n{}", TAG_USAGE),
+    }
+
+    Ok(match db.get_annotation(kind, key).await? {
+        Some(a) => format_annotation(&a),
+        None => format!("{} {} has no tags or note", kind.as_str(), key),
+    })
+}
@@ -0,0 +1,57 @@
+use polymarket_client_sdk::types::Decimal;
+
+// Outcome tokens are 6-decimal ERC-1155s (like USDC); the data API reports those amounts as floats
+pub const TOKEN_DECIMALS: u32 = 6;
+// CLOB order sizes go in 0.01-token lots
+pub const SIZE_DECIMALS: u32 = 2;
+const UNITS_PER_TOKEN: f64 = 1_000_000.0;
+const UNITS_PER_LOT: u64 = 10u64.pow(TOKEN_DECIMALS - SIZE_DECIMALS);
+
+// API float -> base units. Rounded, not floored: 12.345678 is an exact on-chain amount the f64 can't spell
+pub fn to_units(tokens: f64) -> u64 {
+    if tokens.is_finite() && tokens > 0.0 {
+        (tokens * UNITS_PER_TOKEN).round() as u64
+    } else {
+        0
+    }
+}
+
+pub fn from_units(units: u64) -> f64 {
+    units as f64 / UNITS_PER_TOKEN
+}
+
+// Sell order size for `wanted` tokens out of `held`: whole lots, rounded down, never above either
+// (None = not even one lot). Formatting with {:.4} used to round 10.12346 up past what the wallet holds
+pub fn sell_size(wanted: f64, held: f64) -> Option<Decimal> {
+    let lots = to_units(wanted).min(to_units(held)) / UNITS_PER_LOT;
+    (lots > 0).then(|| Decimal::new(lots as i64, SIZE_DECIMALS))
+}
+
+// Tokens a market buy of `usdc` gets at `price`: whole lots, rounded down (what the built order asks for)
+pub fn buy_tokens(usdc: f64, price: f64) -> f64 {
+    if price <= 0.0 {
+        return 0.0;
+    }
+    from_units(to_units(usdc / price) / UNITS_PER_LOT * UNITS_PER_LOT)
+}
+
+// Tokens an order size stands for (exact for lot sizes)
+pub fn size_tokens(size: &Decimal) -> f64 {
+    size.to_string().parse().unwrap_or(0.0)
+}
+
+// Dust sweeper: when selling `wanted` would leave less than the market minimum, sell all of `held` instead -
+// that leftover could never be sold on its own later. Returns what to sell
+pub fn sweep_dust(wanted: f64, held: f64, min_tokens: f64) -> f64 {
+    let leftover = from_units(to_units(held).saturating_sub(to_units(wanted)));
+    if leftover > 0.0 && leftover < min_tokens && held >= min_tokens {
+        held
+    } else {
+        wanted
+    }
+}
+
+// Below the market minimum: can't be sold on the CLOB, only redeemed at resolution - not worth an order or an alert
+pub fn is_dust(tokens: f64, min_tokens: f64) -> bool {
+    tokens < min_tokens.max(from_units(UNITS_PER_LOT))
+}
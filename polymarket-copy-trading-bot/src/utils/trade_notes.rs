@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::config::EnvConfig;
+use crate::types::UserActivity;
+use crate::utils::{clock, fetch_data, raise_alert, Alert, AlertKind, Logger};
+
+const GAMMA_URL: &str = "https://gamma-api.polymarket.com";
+// Most recent comments of the trader looked through for one on this market
+const COMMENTS_LIMIT: usize = 20;
+// Longer comments are cut here (the alert is meant to be glanced at)
+const NOTE_MAX_CHARS: usize = 280;
+// A trader usually fills several times in one market - their comments there are looked up again after this
+const NOTE_REFRESH_MS: i64 = 10 * 60_000;
+
+// Event slug -> Gamma event id (comments hang off the event, trades only carry the slug)
+static EVENT_IDS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+// (trader, event slug) -> (looked up at ms, note found then)
+static NOTES: OnceLock<Mutex<HashMap<(String, String), (i64, Option<String>)>>> = OnceLock::new();
+
+async fn event_id(config: &EnvConfig, http_client: &reqwest::Client, event_slug: &str) -> Option<String> {
+    let cache = EVENT_IDS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(id) = cache.lock().ok().and_then(|c| c.get(event_slug).cloned()) {
+        return Some(id);
+    }
+    let url = format!("{}/events?slug={}", GAMMA_URL, event_slug);
+    let events = fetch_data(http_client, &url, config.request_timeout_ms, 1).await.ok()?;
+    let event = events.as_array()?.first()?;
+    let id = match event.get("id")? {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if let Ok(mut c) = cache.lock() {
+        c.insert(event_slug.to_string(), id.clone());
+    }
+    Some(id)
+}
+
+// With TRADE_NOTES, look up the trader's newest public comment on the trade's event in the background and raise
+// it as a trade_note alert, so it goes wherever ALERT_ROUTES sends info alerts. The copy never waits for it;
+// each comment is raised once per trader and market. Informational only: a failed lookup is just "no note"
+pub fn spawn_trader_note(config: &EnvConfig, http_client: &reqwest::Client, trader: &str, trade: &UserActivity) {
+    let Some(event_slug) = trade.event_slug.clone().filter(|_| config.trade_notes) else {
+        return;
+    };
+    let key = (trader.to_lowercase(), event_slug);
+    let now = clock::now_ms();
+    let cache = NOTES.get_or_init(|| Mutex::new(HashMap::new()));
+    let previous = {
+        let Ok(mut notes) = cache.lock() else {
+            return;
+        };
+        let previous = notes.get(&key).cloned();
+        if previous.as_ref().is_some_and(|(at, _)| now - at < NOTE_REFRESH_MS) {
+            return;
+        }
+        // Claimed before the lookup, so fills arriving meanwhile don't start another one
+        let note = previous.as_ref().and_then(|(_, note)| note.clone());
+        notes.insert(key.clone(), (now, note.clone()));
+        // Anything older couldn't be shown again anyway
+        let max_age_ms = config.trade_notes_max_age_hours as i64 * 3_600_000;
+        notes.retain(|_, (at, _)| now - *at < max_age_ms.max(NOTE_REFRESH_MS));
+        note
+    };
+
+    let config = config.clone();
+    let http_client = http_client.clone();
+    let market = trade.title.clone().or_else(|| trade.slug.clone()).unwrap_or_else(|| key.1.clone());
+    tokio::spawn(async move {
+        let note = find_note(&config, &http_client, &key.0, &key.1).await;
+        if let Ok(mut notes) = cache.lock() {
+            notes.insert(key.clone(), (now, note.clone().or_else(|| previous.clone())));
+        }
+        let Some(note) = note.filter(|n| previous.as_ref() != Some(n)) else {
+            return;
+        };
+        let message = format!("{} on {}: 💬 \"{}\"", Logger::format_address(&key.0), market, note);
+        raise_alert(&config, &http_client, Alert::new(AlertKind::TradeNote, message)).await;
+    });
+}
+
+async fn find_note(config: &EnvConfig, http_client: &reqwest::Client, trader: &str, event_slug: &str) -> Option<String> {
+    let event_id = event_id(config, http_client, event_slug).await?;
+    let url = format!(
+        "{}/comments/user_address/{}?limit={}&order=createdAt&ascending=false",
+        GAMMA_URL, trader, COMMENTS_LIMIT
+    );
+    let comments = fetch_data(http_client, &url, config.request_timeout_ms, 1).await.ok()?;
+    let max_age_ms = config.trade_notes_max_age_hours as i64 * 3_600_000;
+
+    comments.as_array()?.iter().find_map(|c| {
+        let on_event = match c.get("parentEntityID")? {
+            serde_json::Value::String(s) => *s == event_id,
+            other => other.to_string() == event_id,
+        };
+        if !on_event {
+            return None;
+        }
+        let created_ms = chrono::DateTime::parse_from_rfc3339(c.get("createdAt")?.as_str()?)
+            .ok()?
+            .timestamp_millis();
+        if clock::now_ms() - created_ms > max_age_ms {
+            return None;
+        }
+        let body = c.get("body")?.as_str()?.split_whitespace().collect::<Vec<_>>().join(" ");
+        if body.is_empty() {
+            return None;
+        }
+        Some(if body.chars().count() > NOTE_MAX_CHARS {
+            format!("{}…", body.chars().take(NOTE_MAX_CHARS).collect::<String>())
+        } else {
+            body
+        })
+    })
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+
+use crate::config::{is_valid_ethereum_address, EnvConfig};
+use crate::db::Db;
+use crate::utils::{fetch_data, is_contract_address, Logger};
+
+const GAMMA_PROFILE_URL: &str = "https://gamma-api.polymarket.com/public-profile";
+
+// What we found out about one configured trader address
+#[derive(Debug, Clone)]
+pub struct TraderResolution {
+    pub configured: String,
+    pub resolved: String,
+    pub is_contract: bool,
+    pub profile_proxy: Option<String>,
+    pub has_activity: bool,
+}
+
+impl TraderResolution {
+    pub fn changed(&self) -> bool {
+        self.configured != self.resolved
+    }
+}
+
+// Polymarket profile lookup - returns the proxyWallet that actually trades (if any)
+async fn fetch_profile_proxy(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    address: &str,
+) -> Option<String> {
+    let url = format!("{}?address={}", GAMMA_PROFILE_URL, address);
+    let data = fetch_data(http_client, &url, config.request_timeout_ms, 1)
+        .await
+        .ok()?;
+    data.get("proxyWallet")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| is_valid_ethereum_address(s))
+}
+
+// Any trade activity at all under this address?
+async fn has_trade_activity(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    address: &str,
+) -> bool {
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&type=TRADE&limit=1",
+        address
+    );
+    fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await
+        .ok()
+        .and_then(|d| d.as_array().map(|a| !a.is_empty()))
+        .unwrap_or(false)
+}
+
+// Map a configured address to the proxyWallet that shows up in RTDS trades
+// - contract with activity => already the proxy, keep it
+// - profile lists a different proxyWallet => use that (EOA / signer address was configured)
+// - otherwise keep it, and the caller warns if there's no activity either
+pub async fn resolve_trader_address(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    address: &str,
+) -> Result<TraderResolution> {
+    let configured = address.trim().to_lowercase();
+    let is_contract = is_contract_address(&config.rpc_url, &configured).await?;
+    let has_activity = has_trade_activity(config, http_client, &configured).await;
+
+    let profile_proxy = if is_contract && has_activity {
+        None
+    } else {
+        fetch_profile_proxy(config, http_client, &configured).await
+    };
+
+    let resolved = match profile_proxy {
+        Some(ref proxy) if *proxy != configured => proxy.clone(),
+        _ => configured.clone(),
+    };
+
+    Ok(TraderResolution {
+        configured,
+        resolved,
+        is_contract,
+        profile_proxy,
+        has_activity,
+    })
+}
+
+// Resolve every USER_ADDRESSES entry, logging mismatches - returns the addresses to watch
+// TRADER_GROUPS members that resolve to a proxy are regrouped under the proxy, and the DB history stored under
+// the configured address moves with it (if that fails the configured address is kept, so nothing is orphaned)
+pub async fn resolve_trader_addresses(
+    config: &mut EnvConfig,
+    db: &Db,
+    http_client: &reqwest::Client,
+) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for address in &config.user_addresses.clone() {
+        let resolution = match resolve_trader_address(config, http_client, address).await {
+            Ok(r) => r,
+            Err(e) => {
+                Logger::warning(&format!(
+                    "Could not resolve trader {}: {} - using as configured",
+                    Logger::format_address(address),
+                    e
+                ));
+                if !out.contains(address) {
+                    out.push(address.clone());
+                }
+                continue;
+            }
+        };
+
+        if resolution.changed() {
+            match db.migrate_trader(&resolution.configured, &resolution.resolved).await {
+                Ok(0) => {}
+                Ok(n) => Logger::info(&format!(
+                    "Moved {} stored record(s) of {} to its proxy wallet {}",
+                    n,
+                    Logger::format_address(&resolution.configured),
+                    Logger::format_address(&resolution.resolved)
+                )),
+                Err(e) => {
+                    Logger::error(&format!(
+                        "Could not move the stored history of {} to its proxy wallet {}: {} - watching it as configured",
+                        Logger::format_address(&resolution.configured),
+                        Logger::format_address(&resolution.resolved),
+                        e
+                    ));
+                    if !out.contains(&resolution.configured) {
+                        out.push(resolution.configured);
+                    }
+                    continue;
+                }
+            }
+            for member in config.trader_groups.iter_mut().flat_map(|g| g.addresses.iter_mut()) {
+                if *member == resolution.configured {
+                    *member = resolution.resolved.clone();
+                }
+            }
+            Logger::warning(&format!(
+                "Trader {} trades through proxy wallet {} - watching the proxy instead",
+                Logger::format_address(&resolution.configured),
+                Logger::format_address(&resolution.resolved)
+            ));
+            Logger::warning(&format!(
+                "💡 Put {} in USER_ADDRESSES to silence this",
+                resolution.resolved
+            ));
+        } else if !resolution.has_activity {
+            Logger::warning(&format!(
+                "Trader {} has no trade history ({}) - RTDS will likely detect nothing for it",
+                Logger::format_address(&resolution.configured),
+                if resolution.is_contract {
+                    "contract wallet"
+                } else {
+                    "plain EOA, no Polymarket profile found"
+                }
+            ));
+        }
+
+        if !out.contains(&resolution.resolved) {
+            out.push(resolution.resolved);
+        }
+    }
+    out
+}
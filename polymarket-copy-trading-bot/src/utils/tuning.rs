@@ -0,0 +1,299 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{attribute_fills, clock, fetch_paginated, Logger, ATTRIBUTION_SLACK_BEFORE_MS};
+use crate::config::EnvConfig;
+use crate::db::Db;
+use crate::types::{BotOrder, SkipReason, UserPosition};
+
+// Fewer copies / signals than this -> no suggestion (too noisy to tune on)
+const MIN_SAMPLE: usize = 10;
+// A copy under this multiple of MIN_ORDER_SIZE_USD counts as "small"
+const SMALL_COPY_FACTOR: f64 = 2.0;
+// Share of small copies worth acting on
+const SMALL_COPY_SHARE: f64 = 0.3;
+// A copy at this fraction of MAX_ORDER_SIZE_USD or above was capped
+const CAP_HIT_RATIO: f64 = 0.98;
+// Share of a trader's copies hitting the cap worth acting on
+const CAP_HIT_SHARE: f64 = 0.5;
+// Share of a trader's signals lost to one skip reason worth acting on
+const SKIP_SHARE: f64 = 0.2;
+
+#[derive(Debug, Deserialize, Clone)]
+struct Activity {
+    timestamp: i64,
+    #[serde(rename = "usdcSize")]
+    usdc_size: f64,
+    asset: String,
+    side: String,
+}
+
+// One suggested config change with the numbers behind it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TuningSuggestion {
+    pub setting: String,           // Env key (or "" when it is not one knob)
+    pub trader: Option<String>,    // Trader / group it applies to, None = all
+    pub current: Option<String>,
+    pub suggested: Option<String>, // None = review it, no obvious value
+    pub reason: String,
+}
+
+impl TuningSuggestion {
+    pub fn line(&self) -> String {
+        let change = match (&self.current, &self.suggested) {
+            (Some(c), Some(s)) => format!("{} {} -> {}: ", self.setting, c, s),
+            (_, Some(s)) => format!("{}={}: ", self.setting, s),
+            _ if !self.setting.is_empty() => format!("{}: ", self.setting),
+            _ => String::new(),
+        };
+        format!("{}{}", change, self.reason)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TuningReport {
+    pub days: i64,
+    pub copies: usize,  // Bot buys with wallet fills
+    pub skipped: u64,
+    pub suggestions: Vec<TuningSuggestion>,
+}
+
+impl TuningReport {
+    pub fn to_text(&self) -> String {
+        let mut out = vec![format!(
+            "Strategy tuning - last {} day(s): {} copies, {} skipped signals",
+            self.days, self.copies, self.skipped
+        )];
+        if self.suggestions.is_empty() {
+            out.push("  Nothing to change - no setting stands out in this window".to_string());
+        }
+        out.extend(self.suggestions.iter().map(|s| format!("  - {}", s.line())));
+        out.join("\n")
+    }
+}
+
+// One copied buy: USD the wallet actually filled inside the order's window
+struct CopyBuy {
+    trader: String, // trader_key
+    asset: String,
+    usd: f64,
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    part as f64 / total.max(1) as f64 * 100.0
+}
+
+fn trader_label(key: &str) -> String {
+    match key.strip_prefix("group:") {
+        Some(name) => format!("group {}", name),
+        None => Logger::format_address(key),
+    }
+}
+
+// Wallet buys attributed to bot orders (each fill counted once)
+fn attribute_buys(config: &EnvConfig, orders: &[BotOrder], trades: &[Activity]) -> Vec<CopyBuy> {
+    let claimed = attribute_fills(orders, trades, |t| t.timestamp, |o, t| {
+        o.side == "BUY" && t.side == "BUY" && t.asset == o.asset
+    });
+    let mut out = Vec::new();
+    for (order, fills) in orders.iter().zip(claimed) {
+        let usd: f64 = fills.iter().map(|&i| trades[i].usdc_size).sum();
+        if usd > 0.0 {
+            out.push(CopyBuy {
+                trader: config.trader_key(&order.trader),
+                asset: order.asset.clone(),
+                usd,
+            });
+        }
+    }
+    out
+}
+
+// Return on cost of the wallet's positions in these assets (realized + unrealized), None without data
+fn positions_return(positions: &[UserPosition], assets: &[&str]) -> Option<f64> {
+    let (mut pnl, mut cost) = (0.0, 0.0);
+    for p in positions {
+        if !p.asset.as_deref().is_some_and(|a| assets.contains(&a)) {
+            continue;
+        }
+        pnl += p.cash_pnl.unwrap_or(0.0) + p.realized_pnl.unwrap_or(0.0);
+        cost += p.total_bought.or(p.initial_value).unwrap_or(0.0);
+    }
+    (cost > 0.0).then(|| pnl / cost * 100.0)
+}
+
+// Many copies barely above the minimum: the spread and rounding to ticks / whole cents eat a bigger share of them
+fn small_copy_suggestion(config: &EnvConfig, copies: &[CopyBuy], positions: &[UserPosition]) -> Option<TuningSuggestion> {
+    let min_usd = config.copy_strategy_config.min_order_size_usd;
+    let threshold = min_usd * SMALL_COPY_FACTOR;
+    let (small, large): (Vec<&CopyBuy>, Vec<&CopyBuy>) = copies.iter().partition(|c| c.usd < threshold);
+    if copies.len() < MIN_SAMPLE || (small.len() as f64) < copies.len() as f64 * SMALL_COPY_SHARE {
+        return None;
+    }
+    let assets = |rows: &[&CopyBuy]| rows.iter().map(|c| c.asset.as_str()).collect::<Vec<_>>();
+    let small_assets = assets(&small);
+    // Assets also bought in larger size belong to the larger group
+    let large_assets = assets(&large);
+    let small_only: Vec<&str> = small_assets.iter().copied().filter(|a| !large_assets.contains(a)).collect();
+    let small_return = positions_return(positions, &small_only);
+    let large_return = positions_return(positions, &large_assets);
+    // Small copies doing fine -> leave the minimum alone
+    if let (Some(s), Some(l)) = (small_return, large_return) {
+        if s >= 0.0 && s >= l {
+            return None;
+        }
+    }
+    let mut reason = format!(
+        "{:.0}% of your copies ({} of {}) were below ${:.2}",
+        percent(small.len(), copies.len()),
+        small.len(),
+        copies.len(),
+        threshold
+    );
+    match (small_return, large_return) {
+        (Some(s), Some(l)) => reason.push_str(&format!(" and returned {:+.1}% vs {:+.1}% on larger copies", s, l)),
+        (Some(s), None) => reason.push_str(&format!(" and returned {:+.1}%", s)),
+        _ => {}
+    }
+    reason.push_str(" - the spread and tick / token rounding cost small orders the most");
+    Some(TuningSuggestion {
+        setting: "MIN_ORDER_SIZE_USD".to_string(),
+        trader: None,
+        current: Some(format!("{:.2}", min_usd)),
+        suggested: Some(format!("{:.2}", threshold)),
+        reason,
+    })
+}
+
+// A trader whose copies mostly hit MAX_ORDER_SIZE_USD is copied at a flat size, not proportionally
+fn cap_suggestions(config: &EnvConfig, copies: &[CopyBuy]) -> Vec<TuningSuggestion> {
+    let max_usd = config.copy_strategy_config.max_order_size_usd;
+    let mut by_trader: HashMap<&str, (usize, usize)> = HashMap::new();
+    for c in copies {
+        let entry = by_trader.entry(c.trader.as_str()).or_insert((0, 0));
+        entry.0 += 1;
+        if c.usd >= max_usd * CAP_HIT_RATIO {
+            entry.1 += 1;
+        }
+    }
+    let mut rows: Vec<(&str, usize, usize)> = by_trader
+        .into_iter()
+        .filter(|(_, (n, capped))| *n >= MIN_SAMPLE && (*capped as f64) >= *n as f64 * CAP_HIT_SHARE)
+        .map(|(t, (n, capped))| (t, n, capped))
+        .collect();
+    rows.sort_by(|a, b| percent(b.2, b.1).total_cmp(&percent(a.2, a.1)));
+    rows.into_iter()
+        .map(|(trader, n, capped)| TuningSuggestion {
+            setting: "MAX_ORDER_SIZE_USD".to_string(),
+            trader: Some(trader_label(trader)),
+            current: Some(format!("{:.2}", max_usd)),
+            suggested: None,
+            reason: format!(
+                "the cap binds on {:.0}% of {}'s copies ({} of {}) - raise it if you want their size differences copied, or lower COPY_SIZE if the cap is your real budget",
+                percent(capped, n),
+                trader_label(trader),
+                capped,
+                n
+            ),
+        })
+        .collect()
+}
+
+// Skip reasons eating a big share of a trader's signals (signals = copies + skips)
+async fn skip_suggestions(
+    config: &EnvConfig,
+    db: &Db,
+    copies: &[CopyBuy],
+    since: i64,
+) -> Result<(u64, Vec<TuningSuggestion>)> {
+    let mut skips: HashMap<String, HashMap<String, u64>> = HashMap::new();
+    let mut total = 0;
+    for address in &config.user_addresses {
+        let key = config.trader_key(address);
+        for (reason, count) in db.count_skips(address, Some(since)).await? {
+            total += count;
+            *skips.entry(key.clone()).or_default().entry(reason).or_insert(0) += count;
+        }
+    }
+
+    let mut out = Vec::new();
+    for (trader, reasons) in &skips {
+        let copied = copies.iter().filter(|c| &c.trader == trader).count();
+        let signals = copied + reasons.values().sum::<u64>() as usize;
+        if signals < MIN_SAMPLE {
+            continue;
+        }
+        let mut rows: Vec<(&String, &u64)> = reasons.iter().collect();
+        rows.sort_by(|a, b| b.1.cmp(a.1));
+        for (reason, count) in rows {
+            let count = *count as usize;
+            if (count as f64) < signals as f64 * SKIP_SHARE {
+                break;
+            }
+            let Some(skip) = SkipReason::parse(reason) else {
+                continue;
+            };
+            out.push(TuningSuggestion {
+                setting: String::new(),
+                trader: Some(trader_label(trader)),
+                current: None,
+                suggested: None,
+                reason: format!(
+                    "{:.0}% of {}'s signals ({} of {}) were skipped as {} - {}",
+                    percent(count, signals),
+                    trader_label(trader),
+                    count,
+                    signals,
+                    skip.as_str(),
+                    skip.hint()
+                ),
+            });
+        }
+    }
+    Ok((total, out))
+}
+
+// Suggestions for the last `days` from stored bot orders, wallet fills, positions and skip reasons
+pub async fn build_tuning_report(
+    config: &EnvConfig,
+    db: &Db,
+    http_client: &reqwest::Client,
+    days: i64,
+) -> Result<TuningReport> {
+    let since_ms = clock::now_ms() - days * 86_400_000;
+    let orders = db.get_bot_orders(Some(since_ms)).await?;
+
+    let url = format!(
+        "https://data-api.polymarket.com/activity?user={}&type=TRADE&start={}",
+        config.proxy_wallet,
+        since_ms / 1000 - ATTRIBUTION_SLACK_BEFORE_MS / 1000
+    );
+    let trades: Vec<Activity> = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await?
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|v| serde_json::from_value(v.clone()).ok()).collect())
+        .unwrap_or_default();
+
+    let url = format!("https://data-api.polymarket.com/positions?user={}", config.proxy_wallet);
+    let positions: Vec<UserPosition> = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await?
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|p| serde_json::from_value(p.clone()).ok()).collect())
+        .unwrap_or_default();
+
+    let copies = attribute_buys(config, &orders, &trades);
+    let mut suggestions: Vec<TuningSuggestion> = small_copy_suggestion(config, &copies, &positions).into_iter().collect();
+    suggestions.extend(cap_suggestions(config, &copies));
+    let (skipped, skip_rows) = skip_suggestions(config, db, &copies, since_ms / 1000).await?;
+    suggestions.extend(skip_rows);
+
+    Ok(TuningReport {
+        days,
+        copies: copies.len(),
+        skipped,
+        suggestions,
+    })
+}
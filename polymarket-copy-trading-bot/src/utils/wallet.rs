@@ -0,0 +1,91 @@
+use anyhow::Result;
+
+use super::http::{http_client, HttpPool};
+
+// EIP-1167 minimal proxy prefix - Polymarket's proxy/deposit wallets are deployed this way
+const MINIMAL_PROXY_PREFIX: &str = "363d3d373d3d3d363d73";
+
+// How PROXY_WALLET relates to the signing key (maps 1:1 to CLOB signature_type)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletKind {
+    Eoa,
+    PolyProxy,
+    GnosisSafe,
+}
+
+impl WalletKind {
+    pub fn signature_type(&self) -> u8 {
+        match self {
+            WalletKind::Eoa => 0,
+            WalletKind::PolyProxy => 1,
+            WalletKind::GnosisSafe => 2,
+        }
+    }
+
+    pub fn from_signature_type(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(WalletKind::Eoa),
+            1 => Some(WalletKind::PolyProxy),
+            2 => Some(WalletKind::GnosisSafe),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WalletKind::Eoa => "EOA (Externally Owned Account)",
+            WalletKind::PolyProxy => "Polymarket proxy (deposit address)",
+            WalletKind::GnosisSafe => "Gnosis Safe",
+        }
+    }
+}
+
+async fn get_code(rpc_url: &str, address: &str) -> Result<String> {
+    let addr_trimmed = address.trim().trim_start_matches("0x");
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "eth_getCode",
+        "params": [format!("0x{}", addr_trimmed), "latest"],
+        "id": 1
+    });
+    let client = http_client(HttpPool::Rpc);
+    let resp = client
+        .post(rpc_url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+    let json: serde_json::Value = resp.json().await?;
+    let result = json
+        .get("result")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("No result in RPC response"))?;
+    Ok(result.trim_start_matches("0x").to_lowercase())
+}
+
+// Work out the wallet kind for PROXY_WALLET given the signer's EOA
+// - same address as signer => EOA
+// - minimal proxy bytecode => Polymarket proxy (web UI / deposit address users)
+// - any other contract => Gnosis Safe
+// - no code but not the signer => error: could be an undeployed proxy, or just the wrong address -
+//   SIGNATURE_TYPE says which
+pub async fn detect_wallet_kind(rpc_url: &str, proxy_wallet: &str, eoa: &str) -> Result<WalletKind> {
+    let proxy = proxy_wallet.trim().to_lowercase();
+    if proxy == eoa.trim().to_lowercase() {
+        return Ok(WalletKind::Eoa);
+    }
+    let code = get_code(rpc_url, &proxy).await?;
+    let has_code = !code.is_empty() && code.chars().any(|c| c != '0');
+    if !has_code {
+        anyhow::bail!(
+            "PROXY_WALLET {} has no contract code and isn't the signer's address ({}). If it's a Polymarket proxy that hasn't traded yet, set SIGNATURE_TYPE=1 (2 for a Gnosis Safe); otherwise check the address",
+            proxy_wallet.trim(),
+            eoa
+        );
+    }
+    if code.starts_with(MINIMAL_PROXY_PREFIX) {
+        Ok(WalletKind::PolyProxy)
+    } else {
+        Ok(WalletKind::GnosisSafe)
+    }
+}
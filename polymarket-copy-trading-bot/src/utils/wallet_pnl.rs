@@ -0,0 +1,227 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{clock, fetch_data, fetch_paginated, Logger};
+use crate::config::EnvConfig;
+use crate::types::{BotOrder, UserPosition};
+
+// Wallet fills this close to a bot order run are the bot's (fills land on-chain a few seconds late).
+// check_pnl, execution_quality and the tuning report all attribute with this window
+pub const ATTRIBUTION_SLACK_BEFORE_MS: i64 = 5_000;
+pub const ATTRIBUTION_SLACK_AFTER_MS: i64 = 60_000;
+
+// What one data-api /activity row does to the wallet's cash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Buy,
+    Sell,
+    Redeem,
+    Merge,      // YES + NO back into USDC
+    Split,      // USDC into YES + NO
+    Conversion, // Neg-risk NO set converted into YES + USDC
+    Other,      // Rewards, transfers, unknown sides - not a trade cash flow
+}
+
+impl ActivityKind {
+    // A TRADE counts as a sell only when the side says so
+    pub fn parse(activity_type: &str, side: &str) -> ActivityKind {
+        match (activity_type, side) {
+            ("TRADE", "BUY") => ActivityKind::Buy,
+            ("TRADE", "SELL") => ActivityKind::Sell,
+            ("REDEEM", _) => ActivityKind::Redeem,
+            ("MERGE", _) => ActivityKind::Merge,
+            ("SPLIT", _) => ActivityKind::Split,
+            ("CONVERSION", _) => ActivityKind::Conversion,
+            _ => ActivityKind::Other,
+        }
+    }
+
+    pub fn of(activity: &serde_json::Value) -> ActivityKind {
+        ActivityKind::parse(
+            activity.get("type").and_then(|v| v.as_str()).unwrap_or(""),
+            activity.get("side").and_then(|v| v.as_str()).unwrap_or(""),
+        )
+    }
+}
+
+// Cash in / out of the wallet over a set of activity rows
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashFlows {
+    pub buys: usize,
+    pub sells: usize,
+    pub bought_usd: f64,
+    pub sold_usd: f64,
+    pub redeemed_usd: f64,
+    pub merged_usd: f64,
+    pub split_usd: f64,
+    pub converted_usd: f64,
+}
+
+impl CashFlows {
+    pub fn add(&mut self, kind: ActivityKind, usdc: f64) {
+        match kind {
+            ActivityKind::Buy => {
+                self.buys += 1;
+                self.bought_usd += usdc;
+            }
+            ActivityKind::Sell => {
+                self.sells += 1;
+                self.sold_usd += usdc;
+            }
+            ActivityKind::Redeem => self.redeemed_usd += usdc,
+            ActivityKind::Merge => self.merged_usd += usdc,
+            ActivityKind::Split => self.split_usd += usdc,
+            ActivityKind::Conversion => self.converted_usd += usdc,
+            ActivityKind::Other => {}
+        }
+    }
+
+    pub fn from_activity(activity: &[serde_json::Value]) -> CashFlows {
+        let mut flows = CashFlows::default();
+        for a in activity {
+            flows.add(ActivityKind::of(a), a.get("usdcSize").and_then(|v| v.as_f64()).unwrap_or(0.0));
+        }
+        flows
+    }
+
+    // USDC that came back (sells, redemptions, merges, conversions) minus USDC spent (buys, splits)
+    pub fn net(&self) -> f64 {
+        self.sold_usd + self.redeemed_usd + self.merged_usd + self.converted_usd - self.bought_usd - self.split_usd
+    }
+}
+
+pub fn is_open_position(p: &UserPosition) -> bool {
+    p.size.unwrap_or(0.0) > 0.0
+}
+
+// data-api /closed-positions serves at most this many rows per page
+const CLOSED_PAGE_LIMIT: usize = 50;
+// and no offsets past this
+const CLOSED_MAX_OFFSET: usize = 10_000;
+
+// Fully exited / resolved positions (/positions drops them); size is set to 0 so they count as closed
+async fn fetch_closed_positions(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    wallet: &str,
+) -> Result<Vec<UserPosition>> {
+    let mut closed = Vec::new();
+    let mut offset = 0;
+    loop {
+        let url = format!(
+            "https://data-api.polymarket.com/closed-positions?user={}&limit={}&offset={}",
+            wallet, CLOSED_PAGE_LIMIT, offset
+        );
+        let page = fetch_data(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await?;
+        let rows = page.as_array().map(|a| a.as_slice()).unwrap_or_default();
+        closed.extend(rows.iter().filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok()).map(|mut p| {
+            p.size = Some(0.0);
+            p
+        }));
+        if rows.len() < CLOSED_PAGE_LIMIT {
+            break;
+        }
+        offset += CLOSED_PAGE_LIMIT;
+        if offset >= CLOSED_MAX_OFFSET {
+            Logger::warning(&format!("Closed positions of {} truncated at {}", wallet, closed.len()));
+            break;
+        }
+    }
+    Ok(closed)
+}
+
+// Every position of the wallet: open ones from /positions, closed ones from /closed-positions
+// (an asset in both - re-entered after a full exit - is taken from /positions only, so it isn't counted twice)
+pub async fn fetch_wallet_positions(
+    config: &EnvConfig,
+    http_client: &reqwest::Client,
+    wallet: &str,
+) -> Result<Vec<UserPosition>> {
+    let url = format!("https://data-api.polymarket.com/positions?user={}", wallet);
+    let mut positions: Vec<UserPosition> = fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit)
+        .await?
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Expected array of positions"))?
+        .iter()
+        .filter_map(|p| serde_json::from_value::<UserPosition>(p.clone()).ok())
+        .collect();
+    let current: std::collections::HashSet<String> = positions.iter().filter_map(|p| p.asset.clone()).collect();
+    positions.extend(
+        fetch_closed_positions(config, http_client, wallet)
+            .await?
+            .into_iter()
+            .filter(|p| p.asset.as_ref().is_none_or(|a| !current.contains(a))),
+    );
+    Ok(positions)
+}
+
+// Position totals, split open / closed - realized PnL is the sum of both
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionTotals {
+    pub open: usize,
+    pub closed: usize,
+    pub open_value: f64,
+    pub open_initial: f64,
+    pub closed_initial: f64,
+    pub unrealized_pnl: f64,
+    pub open_realized_pnl: f64,
+    pub closed_realized_pnl: f64,
+}
+
+impl PositionTotals {
+    pub fn from_positions(positions: &[UserPosition]) -> PositionTotals {
+        let mut t = PositionTotals::default();
+        for p in positions {
+            let realized = p.realized_pnl.unwrap_or(0.0);
+            if is_open_position(p) {
+                t.open += 1;
+                t.open_value += p.current_value.unwrap_or(0.0);
+                t.open_initial += p.initial_value.unwrap_or(0.0);
+                t.unrealized_pnl += p.cash_pnl.unwrap_or(0.0);
+                t.open_realized_pnl += realized;
+            } else {
+                t.closed += 1;
+                t.closed_initial += p.initial_value.unwrap_or(0.0);
+                t.closed_realized_pnl += realized;
+            }
+        }
+        t
+    }
+
+    pub fn realized_pnl(&self) -> f64 {
+        self.open_realized_pnl + self.closed_realized_pnl
+    }
+}
+
+// A wallet fill at `ts` (seconds or ms) lands inside this order run's attribution window
+pub fn in_order_window(order: &BotOrder, ts: i64) -> bool {
+    let ts = clock::to_ms(ts);
+    ts >= order.started_at - ATTRIBUTION_SLACK_BEFORE_MS && ts <= order.finished_at + ATTRIBUTION_SLACK_AFTER_MS
+}
+
+// Wallet fills claimed by bot orders, in order: for each order, the indexes of the fills inside its window that
+// `same_trade` accepts (asset / side). A fill goes to the first order that claims it, never to two
+pub fn attribute_fills<T>(
+    orders: &[BotOrder],
+    fills: &[T],
+    fill_ts: impl Fn(&T) -> i64,
+    same_trade: impl Fn(&BotOrder, &T) -> bool,
+) -> Vec<Vec<usize>> {
+    let mut used = vec![false; fills.len()];
+    orders
+        .iter()
+        .map(|order| {
+            let mut claimed = Vec::new();
+            for (i, fill) in fills.iter().enumerate() {
+                if used[i] || !in_order_window(order, fill_ts(fill)) || !same_trade(order, fill) {
+                    continue;
+                }
+                used[i] = true;
+                claimed.push(i);
+            }
+            claimed
+        })
+        .collect()
+}
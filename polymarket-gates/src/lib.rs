@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+// Hooks a host process (the supervisor) installs so engines trading one wallet share its limits.
+// Each engine keeps its own slot for them; standalone runs never install one and every check passes
+
+// Capital cap shared by every engine on the wallet
+pub trait ExposureGate: Send + Sync {
+    // Claim `usd` of new exposure; false = over the cap, don't trade
+    fn try_reserve(&self, strategy: &str, usd: f64) -> bool;
+    // Hand back exposure (unfilled part of a reservation, a position sold, a market that resolved)
+    fn release(&self, strategy: &str, usd: f64);
+}
+
+// One CLOB order budget for every engine on the wallet (same API key, same rate limit)
+pub trait ClobGate: Send + Sync {
+    // How long to wait before sending one CLOB call (zero = now); the call is booked either way
+    fn reserve_call(&self, strategy: &str) -> Duration;
+    // The CLOB answered 429: hold every engine's calls for `wait`
+    fn throttled(&self, strategy: &str, wait: Duration);
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use polymarket_gates::{ClobGate, ExposureGate};
+
+// One exposure book for every engine trading the wallet (USDC tied up in open positions + orders in flight)
+pub struct SharedExposure {
+    max_total_usd: Option<f64>,
+    used: Mutex<BTreeMap<String, f64>>,
+    rejected: AtomicU64,
+}
+
+impl SharedExposure {
+    pub fn new(max_total_usd: Option<f64>) -> Self {
+        Self {
+            max_total_usd,
+            used: Mutex::new(BTreeMap::new()),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    pub fn max_total_usd(&self) -> Option<f64> {
+        self.max_total_usd
+    }
+
+    // Positions that were already open when the process started
+    pub fn seed(&self, strategy: &str, usd: f64) {
+        if let Ok(mut used) = self.used.lock() {
+            *used.entry(strategy.to_string()).or_default() += usd.max(0.0);
+        }
+    }
+
+    // Per-strategy exposure (for the status line)
+    pub fn snapshot(&self) -> BTreeMap<String, f64> {
+        self.used.lock().map(|u| u.clone()).unwrap_or_default()
+    }
+
+    pub fn total(&self) -> f64 {
+        self.snapshot().values().sum()
+    }
+
+    // Reservations refused because of the cap (status loop alerts when this grows)
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    fn reserve(&self, strategy: &str, usd: f64) -> bool {
+        let Ok(mut used) = self.used.lock() else {
+            return false;
+        };
+        let total: f64 = used.values().sum();
+        if let Some(max) = self.max_total_usd {
+            if total + usd > max {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                return false;
+            }
+        }
+        *used.entry(strategy.to_string()).or_default() += usd;
+        true
+    }
+
+    fn release(&self, strategy: &str, usd: f64) {
+        if let Ok(mut used) = self.used.lock() {
+            if let Some(v) = used.get_mut(strategy) {
+                *v = (*v - usd).max(0.0);
+            }
+        }
+    }
+}
+
+// Both engines take the gate trait from polymarket-gates, so one impl serves them
+impl ExposureGate for SharedExposure {
+    fn try_reserve(&self, strategy: &str, usd: f64) -> bool {
+        self.reserve(strategy, usd)
+    }
+
+    fn release(&self, strategy: &str, usd: f64) {
+        SharedExposure::release(self, strategy, usd)
+    }
+}
+
+// One CLOB budget for both engines: calls spaced at least `min_interval` apart, and a 429 seen by either
+// engine holds both until it has passed
+pub struct SharedClob {
+    min_interval: Duration,
+    state: Mutex<ClobSchedule>,
+    throttled: AtomicU64,
+}
+
+struct ClobSchedule {
+    next_slot: Instant,
+    blocked_until: Instant,
+    calls: BTreeMap<String, u64>,
+}
+
+impl SharedClob {
+    pub fn new(min_interval: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            min_interval,
+            state: Mutex::new(ClobSchedule { next_slot: now, blocked_until: now, calls: BTreeMap::new() }),
+            throttled: AtomicU64::new(0),
+        }
+    }
+
+    // CLOB calls per strategy since startup (for the status line)
+    pub fn calls(&self) -> BTreeMap<String, u64> {
+        self.state.lock().map(|s| s.calls.clone()).unwrap_or_default()
+    }
+
+    // 429s either engine got from the CLOB
+    pub fn throttled_total(&self) -> u64 {
+        self.throttled.load(Ordering::Relaxed)
+    }
+}
+
+impl ClobGate for SharedClob {
+    fn reserve_call(&self, strategy: &str) -> Duration {
+        let Ok(mut s) = self.state.lock() else {
+            return Duration::ZERO;
+        };
+        let now = Instant::now();
+        let start = now.max(s.next_slot).max(s.blocked_until);
+        s.next_slot = start + self.min_interval;
+        *s.calls.entry(strategy.to_string()).or_default() += 1;
+        start - now
+    }
+
+    fn throttled(&self, _strategy: &str, wait: Duration) {
+        self.throttled.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut s) = self.state.lock() {
+            s.blocked_until = s.blocked_until.max(Instant::now() + wait);
+        }
+    }
+}
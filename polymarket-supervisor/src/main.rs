@@ -0,0 +1,228 @@
+mod exposure;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::signal;
+
+use arb_rust::config::{ConfigReport, Env as ArbEnv};
+use arb_rust::services::engine::{run_arbitrage_engine, stop_arbitrage_engine};
+use arb_rust::utils::coin_selector::get_available_coins;
+use exposure::{SharedClob, SharedExposure};
+use polymarket_copy_rust::utils::{rate_limit_gauges, send_alert, set_clob_gate, set_exposure_gate, COPY_STRATEGY};
+use polymarket_copy_rust::{fetch_paginated, CopyEngine, EnvConfig, Logger};
+
+// How often the combined status line (exposure + API quota) is logged
+const STATUS_INTERVAL: Duration = Duration::from_secs(60);
+// CLOB_MIN_INTERVAL_MS unset: at most 20 CLOB calls a second across both engines
+const DEFAULT_CLOB_MIN_INTERVAL_MS: u64 = 50;
+
+fn normalize_key(key: &str) -> String {
+    key.trim().trim_start_matches("0x").to_lowercase()
+}
+
+// Both engines read the same .env; refuse to start if they'd trade different wallets
+fn check_same_wallet(config: &EnvConfig, arb_env: &ArbEnv) -> Result<()> {
+    let arb_key = arb_env.private_key.as_deref().unwrap_or("");
+    if normalize_key(arb_key) != normalize_key(&config.private_key) {
+        anyhow::bail!("Arbitrage PRIVATE_KEY differs from the copy bot's - the supervisor runs one wallet");
+    }
+    if let Some(proxy) = arb_env.proxy_wallet.as_deref() {
+        if !proxy.eq_ignore_ascii_case(&config.proxy_wallet) {
+            anyhow::bail!(
+                "Arbitrage PROXY_WALLET {} differs from the copy bot's {}",
+                proxy,
+                config.proxy_wallet
+            );
+        }
+    }
+    Ok(())
+}
+
+// Cost basis of positions already open on the wallet (counted before either engine trades)
+async fn open_position_cost(config: &EnvConfig, http_client: &reqwest::Client) -> f64 {
+    let url = format!(
+        "https://data-api.polymarket.com/positions?user={}",
+        config.proxy_wallet
+    );
+    match fetch_paginated(http_client, &url, config.request_timeout_ms, config.network_retry_limit).await {
+        Ok(positions) => positions
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|p| p.get("initialValue").and_then(|v| v.as_f64()))
+                    .sum()
+            })
+            .unwrap_or(0.0),
+        Err(e) => {
+            Logger::warning(&format!("Could not load open positions for exposure: {}", e));
+            0.0
+        }
+    }
+}
+
+// Periodic status for both engines + one alert per interval while the cap is refusing trades
+async fn run_status_loop(
+    config: EnvConfig,
+    http_client: reqwest::Client,
+    exposure: Arc<SharedExposure>,
+    clob: Arc<SharedClob>,
+) {
+    let mut last_rejected = 0;
+    loop {
+        tokio::time::sleep(STATUS_INTERVAL).await;
+        let per_strategy = exposure
+            .snapshot()
+            .iter()
+            .map(|(k, v)| format!("{} ${:.2}", k, v))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let cap = exposure
+            .max_total_usd()
+            .map(|m| format!(" / ${:.2}", m))
+            .unwrap_or_default();
+        Logger::info(&format!(
+            "Exposure ${:.2}{} ({})",
+            exposure.total(),
+            cap,
+            if per_strategy.is_empty() { "none" } else { &per_strategy }
+        ));
+        let calls = clob
+            .calls()
+            .iter()
+            .map(|(k, v)| format!("{} {}", k, v))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Logger::info(&format!(
+            "CLOB calls: {} ({} rate-limited)",
+            if calls.is_empty() { "none" } else { &calls },
+            clob.throttled_total()
+        ));
+        for gauge in rate_limit_gauges().iter().filter(|g| g.status() != "ok") {
+            Logger::warning(&format!("Quota {}: {}", gauge.host, gauge.summary()));
+        }
+
+        let rejected = exposure.rejected();
+        if rejected > last_rejected {
+            send_alert(
+                &config,
+                &http_client,
+                &format!(
+                    "Wallet exposure cap reached: {} trade(s) refused in the last {}s (${:.2} deployed)",
+                    rejected - last_rejected,
+                    STATUS_INTERVAL.as_secs(),
+                    exposure.total()
+                ),
+            )
+            .await;
+        }
+        last_rejected = rejected;
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    polymarket_term::init(); // --plain applies to both engines' output
+
+    // Coin for the 15-minute arbitrage engine: first arg, else ARB_COIN (no interactive menu here)
+    let coin = std::env::args()
+        .skip(1)
+        .find(|a| !a.starts_with("--"))
+        .or_else(|| std::env::var("ARB_COIN").ok())
+        .unwrap_or_else(|| "BTC".to_string())
+        .to_uppercase();
+    if !get_available_coins().contains(&coin.as_str()) {
+        anyhow::bail!(
+            "Unknown ARB_COIN '{}' (available: {})",
+            coin,
+            get_available_coins().join(", ")
+        );
+    }
+
+    let config = EnvConfig::from_env().await?;
+    let arb_env = ArbEnv::load();
+    // ArbEnv::load falls back to defaults on typos - refuse to trade on a setting it couldn't read
+    let arb_report = ConfigReport::collect(&arb_env);
+    if arb_report.has_errors() {
+        arb_report.print(false);
+        anyhow::bail!("Fix the arbitrage settings above before starting");
+    }
+    check_same_wallet(&config, &arb_env)?;
+    arb_rust::utils::logger::init_logging(arb_env.log_level);
+
+    Logger::header("SUPERVISOR");
+    Logger::info(&format!("Engines: copy trading + {} 15-minute arbitrage", coin));
+
+    let http_client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(config.request_timeout_ms))
+        .build()?;
+
+    // One exposure book for both engines (MAX_TOTAL_EXPOSURE_USD unset/0 = track only)
+    let max_total = std::env::var("MAX_TOTAL_EXPOSURE_USD")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| *v > 0.0);
+    let exposure = Arc::new(SharedExposure::new(max_total));
+    exposure.seed(COPY_STRATEGY, open_position_cost(&config, &http_client).await);
+    set_exposure_gate(exposure.clone());
+    arb_rust::services::exposure::set_exposure_gate(exposure.clone());
+    Logger::info(&format!(
+        "Shared exposure: ${:.2} already open{}",
+        exposure.total(),
+        max_total.map(|m| format!(", cap ${:.2}", m)).unwrap_or_default()
+    ));
+
+    // One CLOB budget for both engines (same wallet, same API key)
+    let clob_interval = std::env::var("CLOB_MIN_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CLOB_MIN_INTERVAL_MS);
+    let clob = Arc::new(SharedClob::new(Duration::from_millis(clob_interval)));
+    set_clob_gate(clob.clone());
+    arb_rust::services::create_clob_client::set_clob_gate(clob.clone());
+    Logger::info(&format!("Shared CLOB budget: one call per {}ms across both engines", clob_interval));
+    Logger::separator();
+
+    // Copy engine: the copy bot's own startup (checks, DB from STORAGE_BACKEND, every service)
+    let engine = CopyEngine::start(config.clone()).await?;
+
+    // Arbitrage engine (creates its own order client for the same wallet)
+    Logger::info(&format!("Starting {} arbitrage engine...", coin));
+    let arb_coin = coin.clone();
+    let mut arb_handle = tokio::spawn(async move { run_arbitrage_engine(&arb_coin, &arb_env).await });
+
+    let status_handle = tokio::spawn(run_status_loop(
+        config.clone(),
+        http_client.clone(),
+        exposure.clone(),
+        clob.clone(),
+    ));
+
+    // Ctrl+C or either engine dying takes the whole process down (alerting on the latter)
+    tokio::select! {
+        _ = signal::ctrl_c() => {
+            Logger::separator();
+            Logger::info("Shutdown requested. Stopping both engines…");
+        }
+        reason = engine.executor_exited() => {
+            send_alert(&config, &http_client, &format!("Copy engine {} - shutting down supervisor", reason)).await;
+        }
+        result = &mut arb_handle => {
+            let reason = match result {
+                Ok(Ok(())) => "stopped".to_string(),
+                Ok(Err(e)) => format!("failed: {}", e),
+                Err(e) => format!("panicked: {}", e),
+            };
+            send_alert(&config, &http_client, &format!("Arbitrage engine {} - shutting down supervisor", reason)).await;
+        }
+    }
+
+    stop_arbitrage_engine();
+    // Waits for the copy executor's shutdown flush, then closes the database
+    engine.stop().await;
+    arb_handle.abort();
+    status_handle.abort();
+    Logger::success("Goodbye.");
+    Ok(())
+}
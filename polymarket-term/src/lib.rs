@@ -0,0 +1,212 @@
+use std::borrow::Cow;
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// What the terminal can show (unicode = box drawing / emoji, color = ANSI escapes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TermCaps {
+    pub unicode: bool,
+    pub color: bool,
+}
+
+impl TermCaps {
+    pub const FULL: TermCaps = TermCaps { unicode: true, color: true };
+    pub const PLAIN: TermCaps = TermCaps { unicode: false, color: false };
+
+    fn bits(self) -> u8 {
+        1 | ((self.unicode as u8) << 1) | ((self.color as u8) << 2)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        TermCaps {
+            unicode: bits & 2 != 0,
+            color: bits & 4 != 0,
+        }
+    }
+}
+
+static CAPS: AtomicU8 = AtomicU8::new(0); // 0 = not initialized yet (treated as FULL)
+
+fn env_flag(key: &str) -> bool {
+    env::var(key)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+// Best guess from the environment (no terminal queries, so it also works when piped)
+pub fn detect() -> TermCaps {
+    let term = env::var("TERM").unwrap_or_default();
+    if term == "dumb" {
+        return TermCaps::PLAIN;
+    }
+
+    // Legacy Windows console (cmd.exe / old PowerShell host) mangles UTF-8 and ignores ANSI;
+    // Windows Terminal, VS Code and mintty/Git Bash set one of these
+    let modern_windows_host = env::var_os("WT_SESSION").is_some()
+        || env::var_os("TERM_PROGRAM").is_some()
+        || !term.is_empty();
+    if cfg!(windows) && !modern_windows_host {
+        return TermCaps::PLAIN;
+    }
+
+    // A non-UTF-8 locale (LANG=C, POSIX, latin1 ...) garbles the glyphs, colors are still fine
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|k| env::var(k).ok().filter(|v| !v.is_empty()));
+    let unicode = match locale {
+        Some(l) => {
+            let l = l.to_lowercase();
+            l.contains("utf-8") || l.contains("utf8")
+        }
+        None => !cfg!(unix) || env::var_os("TERM_PROGRAM").is_some(),
+    };
+    let color = env::var_os("NO_COLOR").is_none();
+    TermCaps { unicode, color }
+}
+
+// --plain / PLAIN_OUTPUT=true force ASCII without color, otherwise detect (call once at startup)
+pub fn init() -> TermCaps {
+    let forced = env::args().any(|a| a == "--plain") || env_flag("PLAIN_OUTPUT");
+    let caps = if forced { TermCaps::PLAIN } else { detect() };
+    set_caps(caps);
+    caps
+}
+
+// Command-line arguments after the program name, minus --plain (init() already took it), for tools that
+// read positional arguments
+pub fn args() -> impl Iterator<Item = String> {
+    env::args().skip(1).filter(|a| a != "--plain")
+}
+
+pub fn set_caps(caps: TermCaps) {
+    CAPS.store(caps.bits(), Ordering::Relaxed);
+}
+
+pub fn caps() -> TermCaps {
+    match CAPS.load(Ordering::Relaxed) {
+        0 => TermCaps::FULL,
+        bits => TermCaps::from_bits(bits),
+    }
+}
+
+pub fn is_plain() -> bool {
+    !caps().unicode
+}
+
+pub fn color_enabled() -> bool {
+    caps().color
+}
+
+// ASCII stand-ins for the glyphs the bots actually print (anything else non-ASCII is dropped)
+fn ascii_symbol(c: char) -> Option<&'static str> {
+    Some(match c {
+        '✓' | '✔' | '✅' => "[OK]",
+        '✗' | '✘' | '❌' => "[X]",
+        '⚠' => "[!]",
+        '●' | '•' | '·' | '◆' | '◇' => "*",
+        '▸' | '►' => ">",
+        '→' => "->",
+        '←' => "<-",
+        '↑' => "^",
+        '↓' => "v",
+        '…' => "...",
+        '—' | '–' => "-",
+        '≥' => ">=",
+        '≤' => "<=",
+        '×' => "x",
+        '▰' => "#",
+        '▱' => ".",
+        '🚀' | '⚡' => "[>]",
+        '🔔' | '🚨' => "[!]",
+        '💰' | '💵' => "[$]",
+        '📊' | '📈' | '📉' => "[#]",
+        '🔍' => "[?]",
+        '⏳' | '⏱' | '🕐' => "[..]",
+        '🛑' | '⛔' => "[STOP]",
+        _ => return None,
+    })
+}
+
+fn box_char(c: char) -> Option<char> {
+    match c as u32 {
+        // Box drawing: straight lines, then everything else (corners / tees / crosses)
+        0x2500 | 0x2501 | 0x2504 | 0x2505 | 0x2508 | 0x2509 | 0x254C | 0x254D | 0x2574 | 0x2576 => Some('-'),
+        0x2550 => Some('='),
+        0x2502 | 0x2503 | 0x2506 | 0x2507 | 0x250A | 0x250B | 0x254E | 0x254F | 0x2551 | 0x2575 | 0x2577 => Some('|'),
+        0x2500..=0x257F => Some('+'),
+        // Block elements (banners, bars)
+        0x2580..=0x259F => Some('#'),
+        _ => None,
+    }
+}
+
+// Drop ANSI color/style sequences (ESC [ ... m) - cursor moves and screen clears are kept
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            let mut seq = String::from(c);
+            for n in chars.by_ref() {
+                seq.push(n);
+                if n.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            if !seq.ends_with('m') {
+                out.push_str(&seq);
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn to_ascii(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() || c.is_alphabetic() {
+            out.push(c);
+        } else if let Some(b) = box_char(c) {
+            out.push(b);
+        } else if let Some(sym) = ascii_symbol(c) {
+            out.push_str(sym);
+        }
+        // Emoji, variation selectors, braille spinners ... are dropped
+    }
+    out
+}
+
+// Text as this terminal should see it (borrowed when nothing needs changing)
+pub fn render(s: &str) -> Cow<'_, str> {
+    let caps = caps();
+    let s: Cow<'_, str> = if !caps.color && s.contains('\x1b') {
+        Cow::Owned(strip_ansi(s))
+    } else {
+        Cow::Borrowed(s)
+    };
+    if caps.unicode || s.is_ascii() {
+        s
+    } else {
+        Cow::Owned(to_ascii(&s))
+    }
+}
+
+// println! / print! / eprintln! through render()
+#[macro_export]
+macro_rules! outln {
+    () => { println!() };
+    ($($arg:tt)*) => { println!("{}", $crate::render(&format!($($arg)*))) };
+}
+
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => { print!("{}", $crate::render(&format!($($arg)*))) };
+}
+
+#[macro_export]
+macro_rules! eoutln {
+    () => { eprintln!() };
+    ($($arg:tt)*) => { eprintln!("{}", $crate::render(&format!($($arg)*))) };
+}